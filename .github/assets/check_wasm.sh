@@ -59,6 +59,7 @@ exclude_crates=(
   reth-engine-local
   # The following are not supposed to be working
   reth # all of the crates below
+  reth-fork-dry-run # reth-provider
   reth-invalid-block-hooks # reth-provider
   reth-libmdbx # mdbx
   reth-mdbx-sys # mdbx
@@ -9,6 +9,9 @@
 //!   and leak detection functionality. See [jemalloc's opt.prof](https://jemalloc.net/jemalloc.3.html#opt.prof)
 //!   documentation for usage details. This is **not recommended on Windows**. See [here](https://rust-lang.github.io/rfcs/1974-global-allocators.html#jemalloc)
 //!   for more info.
+//! - `cpu-profiling`: Enables on-demand CPU profiling via `reth stage run --cpu-profile` and the
+//!   `admin_startCpuProfile`/`admin_stopCpuProfile`/`admin_profileNextCall` RPC methods. Profiles
+//!   requested over RPC are written to `<DATADIR>/cpu-profiles`. Only supported on unix platforms.
 //! - `asm-keccak`: replaces the default, pure-Rust implementation of Keccak256 with one implemented
 //!   in assembly; see [the `keccak-asm` crate](https://github.com/DaniPopes/keccak-asm) for more
 //!   details and supported targets
@@ -98,6 +101,11 @@ pub mod providers {
     pub use reth_provider::*;
 }
 
+/// Re-exported from `reth_profiling`.
+pub mod profiling {
+    pub use reth_profiling::*;
+}
+
 /// Re-exported from `reth_primitives`.
 pub mod primitives {
     pub use reth_primitives::*;
@@ -6,15 +6,28 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 use clap::{Args, Parser};
 use reth::cli::Cli;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_node_api::FullNodeComponents;
 use reth_node_builder::{
     engine_tree_config::{
-        TreeConfig, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD,
+        TreeConfig, DEFAULT_EXECUTION_CACHE_SIZE, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+        DEFAULT_PERSISTENCE_THRESHOLD,
     },
     EngineNodeLauncher,
 };
-use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
-use reth_provider::providers::BlockchainProvider2;
+use reth_node_ethereum::{
+    node::{EthereumAddOns, EthereumPayloadBuilder},
+    EthereumNode,
+};
+use reth_provider::{providers::BlockchainProvider2, CanonStateSubscriptions};
+use reth_rpc::{EthPrivateTransactions, MevBundleApi, UserOperationApi};
+use reth_rpc_api::servers::{
+    EthPrivateTransactionApiServer, EthSendBundleApiServer, EthUserOperationApiServer,
+    MevFullApiServer,
+};
+use reth_rpc_server_types::RethRpcModule;
+use reth_tasks::pool::BlockingTaskGuard;
 use reth_tracing::tracing::warn;
+use reth_transaction_pool::alt::{maintain_user_operation_pool, MevBundlePool, UserOperationPool};
 use tracing::info;
 
 /// Parameters for configuring the engine
@@ -39,6 +52,11 @@ pub struct EngineArgs {
     /// Configure the target number of blocks to keep in memory.
     #[arg(long = "engine.memory-block-buffer-target", conflicts_with = "legacy", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
+
+    /// Configure the maximum number of accounts, storage slots and contracts kept in the cache of
+    /// the state of the last executed block, 0 disables the cache.
+    #[arg(long = "engine.execution-cache-size", conflicts_with = "legacy", default_value_t = DEFAULT_EXECUTION_CACHE_SIZE)]
+    pub execution_cache_size: usize,
 }
 
 impl Default for EngineArgs {
@@ -48,6 +66,7 @@ impl Default for EngineArgs {
             legacy: false,
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            execution_cache_size: DEFAULT_EXECUTION_CACHE_SIZE,
         }
     }
 }
@@ -71,11 +90,52 @@ fn main() {
                 false => {
                     let engine_tree_config = TreeConfig::default()
                         .with_persistence_threshold(engine_args.persistence_threshold)
-                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target);
+                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target)
+                        .with_execution_cache_size(engine_args.execution_cache_size);
+                    // bundles submitted to the `mev` namespace are included by the payload
+                    // builder
+                    let bundle_pool = MevBundlePool::default();
                     let handle = builder
                         .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
-                        .with_components(EthereumNode::components())
+                        .with_components(EthereumNode::components().payload(
+                            EthereumPayloadBuilder::<()>::default()
+                                .with_alt_mempool(bundle_pool.clone()),
+                        ))
                         .with_add_ons(EthereumAddOns::default())
+                        .extend_rpc_modules(move |ctx| {
+                            let mev_api = MevBundleApi::new(
+                                ctx.registry.eth_api().clone(),
+                                bundle_pool,
+                                BlockingTaskGuard::new(ctx.config().rpc.rpc_max_tracing_requests),
+                            );
+                            let mut module = MevFullApiServer::into_rpc(mev_api.clone());
+                            module.merge(EthSendBundleApiServer::into_rpc(mev_api))?;
+                            module.merge(
+                                EthPrivateTransactions::new(ctx.pool().clone()).into_rpc(),
+                            )?;
+                            ctx.modules.replace_if_module_configured(RethRpcModule::Mev, module)?;
+
+                            if ctx.modules.module_config().contains_any(&RethRpcModule::Bundler) {
+                                // user operations are removed from the pool once the entry point
+                                // executed them
+                                let user_operation_pool = UserOperationPool::default();
+                                ctx.node().task_executor().spawn(Box::pin(
+                                    maintain_user_operation_pool(
+                                        user_operation_pool.clone(),
+                                        ctx.provider().canonical_state_stream(),
+                                    ),
+                                ));
+                                let bundler_api = UserOperationApi::new(
+                                    ctx.registry.eth_api().clone(),
+                                    user_operation_pool,
+                                );
+                                ctx.modules.replace_if_module_configured(
+                                    RethRpcModule::Bundler,
+                                    bundler_api.into_rpc(),
+                                )?;
+                            }
+                            Ok(())
+                        })
                         .launch_with_fn(|builder| {
                             let launcher = EngineNodeLauncher::new(
                                 builder.task_executor().clone(),
@@ -7,7 +7,7 @@ use alloy_eips::{
     eip1559::INITIAL_BASE_FEE, eip6110::MAINNET_DEPOSIT_CONTRACT_ADDRESS,
     eip7685::EMPTY_REQUESTS_HASH,
 };
-use alloy_genesis::Genesis;
+use alloy_genesis::{Genesis, GenesisAccount};
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
 use derive_more::From;
 
@@ -716,6 +716,7 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    deposit_contract: Option<DepositContract>,
 }
 
 impl ChainSpecBuilder {
@@ -725,6 +726,7 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            deposit_contract: MAINNET.deposit_contract,
         }
     }
 }
@@ -742,6 +744,27 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the given accounts to the genesis allocation.
+    ///
+    /// Existing accounts with the same address are replaced. If no genesis is set yet, the
+    /// accounts are added to a default genesis.
+    pub fn genesis_alloc(
+        mut self,
+        accounts: impl IntoIterator<Item = (Address, GenesisAccount)>,
+    ) -> Self {
+        self.genesis.get_or_insert_with(Default::default).alloc.extend(accounts);
+        self
+    }
+
+    /// Set the deposit contract deployed for `PoS`.
+    ///
+    /// If not set, the deposit contract is derived from the `depositContractAddress` of the genesis
+    /// config, like it is for chain specs loaded from a genesis file.
+    pub const fn deposit_contract(mut self, deposit_contract: DepositContract) -> Self {
+        self.deposit_contract = Some(deposit_contract);
+        self
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork<H: Hardfork>(mut self, fork: H, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -893,13 +916,21 @@ impl ChainSpecBuilder {
                 }
             })
         };
+        let genesis = self.genesis.expect("The genesis is required");
+        let deposit_contract = self.deposit_contract.or_else(|| {
+            genesis.config.deposit_contract_address.map(|address| DepositContract {
+                address,
+                block: 0,
+                topic: MAINNET_DEPOSIT_CONTRACT.topic,
+            })
+        });
         ChainSpec {
             chain: self.chain.expect("The chain is required"),
-            genesis: self.genesis.expect("The genesis is required"),
+            genesis,
             genesis_hash: OnceLock::new(),
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
-            deposit_contract: None,
+            deposit_contract,
             ..Default::default()
         }
     }
@@ -911,6 +942,7 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            deposit_contract: value.deposit_contract,
         }
     }
 }
@@ -2111,6 +2143,32 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.cancun_time, Some(4661));
     }
 
+    #[test]
+    fn builder_genesis_alloc_and_deposit_contract() {
+        let address = Address::with_last_byte(0xaa);
+        let account = GenesisAccount { balance: U256::from(1), ..Default::default() };
+        let deposit_contract =
+            DepositContract::new(Address::with_last_byte(0xdd), 5, MAINNET_DEPOSIT_CONTRACT.topic);
+
+        let chainspec = ChainSpecBuilder::default()
+            .chain(Chain::from_id(1337))
+            .genesis_alloc([(address, account.clone())])
+            .deposit_contract(deposit_contract)
+            .cancun_activated()
+            .build();
+        assert_eq!(chainspec.genesis.alloc.get(&address), Some(&account));
+        assert_eq!(chainspec.deposit_contract, Some(deposit_contract));
+
+        // without an explicit deposit contract, the genesis config is used like for genesis files
+        let mut genesis = Genesis::default();
+        genesis.config.deposit_contract_address = Some(deposit_contract.address);
+        let chainspec = ChainSpecBuilder::default()
+            .chain(Chain::from_id(1337))
+            .genesis(genesis.clone())
+            .build();
+        assert_eq!(chainspec.deposit_contract, ChainSpec::from(genesis).deposit_contract);
+    }
+
     #[test]
     fn test_paris_block_and_total_difficulty() {
         let genesis = Genesis { gas_limit: 0x2fefd8u64, ..Default::default() };
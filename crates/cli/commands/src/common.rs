@@ -7,10 +7,10 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_config::{config::EtlConfig, Config};
 use reth_db::{init_db, open_db_read_only, DatabaseEnv};
-use reth_db_common::init::init_genesis;
+use reth_db_common::init::{init_genesis, validate_chain_spec};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::noop::NoopBlockExecutorProvider;
-use reth_node_builder::{NodeTypesWithDBAdapter, NodeTypesWithEngine};
+use reth_node_builder::{NodePrimitives, NodeTypesWithDBAdapter, NodeTypesWithEngine};
 use reth_node_core::{
     args::{DatabaseArgs, DatadirArgs},
     dirs::{ChainPath, DataDirPath},
@@ -80,7 +80,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Environmen
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
-        let (db, sfp) = match access {
+        let (db, mut sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
                 StaticFileProvider::read_write(sf_path)?,
@@ -90,11 +90,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Environmen
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
+        if let Some(url) = &self.datadir.static_files_remote {
+            sfp = with_remote_static_files(sfp, url)?;
+        }
 
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
             debug!(target: "reth::cli", chain=%self.chain.chain(), genesis=?self.chain.genesis_hash(), "Initializing genesis");
             init_genesis(&provider_factory)?;
+            validate_chain_spec(&provider_factory)?;
         }
 
         Ok(Environment { config, provider_factory, data_dir })
@@ -193,3 +197,24 @@ impl AccessRights {
 /// [`NodeTypes`](reth_node_builder::NodeTypes) in CLI.
 pub trait CliNodeTypes: NodeTypesWithEngine<ChainSpec: EthereumHardforks> {}
 impl<N> CliNodeTypes for N where N: NodeTypesWithEngine<ChainSpec: EthereumHardforks> {}
+
+/// Indexes the static files of the remote location at `url` that are missing on disk, their data
+/// is only downloaded once it's accessed.
+#[cfg(feature = "remote-static-files")]
+fn with_remote_static_files<N: NodePrimitives>(
+    static_file_provider: StaticFileProvider<N>,
+    url: &str,
+) -> eyre::Result<StaticFileProvider<N>> {
+    use reth_provider::providers::RemoteStaticFiles;
+
+    Ok(static_file_provider.with_remote(RemoteStaticFiles::new(url)?)?)
+}
+
+/// Remote static files are not supported without the `remote-static-files` feature.
+#[cfg(not(feature = "remote-static-files"))]
+fn with_remote_static_files<N: NodePrimitives>(
+    _static_file_provider: StaticFileProvider<N>,
+    url: &str,
+) -> eyre::Result<StaticFileProvider<N>> {
+    eyre::bail!("fetching static files from {url} requires the `remote-static-files` feature")
+}
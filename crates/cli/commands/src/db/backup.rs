@@ -0,0 +1,152 @@
+//! `reth db backup` command.
+
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{version::db_version_file_path, DatabaseEnv};
+use reth_db_common::DbTool;
+use reth_node_builder::NodeTypesWithDB;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// The file name of the MDBX data file inside the database directory.
+pub(crate) const MDBX_DATA_FILE: &str = "mdbx.dat";
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db backup` command
+pub struct Command {
+    /// The directory the backup is written to. It must not exist yet, or be empty.
+    ///
+    /// The backup uses the datadir layout, with the database in `db` and the static files in
+    /// `static_files`, and can be restored with `reth db restore`.
+    #[arg(long, value_name = "PATH")]
+    output: PathBuf,
+
+    /// Compact the database while copying it, omitting free pages.
+    #[arg(long, default_value_t = false)]
+    compact: bool,
+
+    /// Hard link static files instead of copying them, if the backup is on the same filesystem.
+    ///
+    /// Only static files that are no longer appended to are linked. Note that an unwind reaching
+    /// into the block range of a linked file still modifies the backup.
+    #[arg(long, default_value_t = false)]
+    hard_link: bool,
+}
+
+impl Command {
+    /// Execute `db backup` command
+    ///
+    /// The database is copied within a single read transaction first, so the backup is a
+    /// consistent snapshot even if the node is running. Static files are copied afterwards and can
+    /// only be ahead of the database snapshot, which `reth db restore` heals by truncating them.
+    pub fn execute<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        self,
+        data_dir: ChainPath<DataDirPath>,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<()> {
+        if self.output.exists() && reth_fs_util::read_dir(&self.output)?.next().is_some() {
+            eyre::bail!("Backup directory {:?} is not empty", self.output)
+        }
+
+        let db_path = self.output.join("db");
+        let static_files_path = self.output.join("static_files");
+        reth_fs_util::create_dir_all(&db_path)?;
+        reth_fs_util::create_dir_all(&static_files_path)?;
+
+        info!(target: "reth::cli", output = ?self.output, compact = self.compact, "Copying database");
+        tool.provider_factory.db_ref().copy(&db_path.join(MDBX_DATA_FILE), self.compact)?;
+        reth_fs_util::copy(db_version_file_path(data_dir.db()), db_version_file_path(&db_path))?;
+        let db_size = reth_fs_util::metadata(db_path.join(MDBX_DATA_FILE))?.len();
+        info!(target: "reth::cli", size = human_bytes(db_size as f64), "Copied database");
+
+        let (copied, linked) =
+            backup_static_files(&data_dir.static_files(), &static_files_path, self.hard_link)?;
+        info!(target: "reth::cli", copied, linked, "Copied static files");
+
+        println!("Backup written to {}", self.output.display());
+        Ok(())
+    }
+}
+
+/// Copies all static files from `from` into `to`, returning the number of copied and hard linked
+/// files.
+///
+/// The last file of each segment may still be appended to, so it's always copied. Its
+/// configuration is copied before its offsets and data, which can only ever be ahead of the
+/// configuration and are truncated by the consistency check on restore.
+fn backup_static_files(from: &Path, to: &Path, hard_link: bool) -> eyre::Result<(usize, usize)> {
+    let mut files = Vec::new();
+    let mut latest = HashMap::<StaticFileSegment, u64>::new();
+    for entry in reth_fs_util::read_dir(from)? {
+        let path = entry?.path();
+        let Some(stem) = path.file_stem() else { continue };
+        let Some((segment, range)) = StaticFileSegment::parse_filename(&stem.to_string_lossy())
+        else {
+            continue
+        };
+
+        let start = latest.entry(segment).or_insert(range.start());
+        *start = (*start).max(range.start());
+        files.push((path, segment, range.start()));
+    }
+
+    // Configuration files first, then offsets, then data.
+    files.sort_by_key(|(path, _, _)| match path.extension().and_then(|ext| ext.to_str()) {
+        Some("conf") => 0,
+        Some("off") => 1,
+        _ => 2,
+    });
+
+    let (mut copied, mut linked) = (0, 0);
+    for (path, segment, start) in files {
+        let dest = to.join(path.file_name().expect("file name exists"));
+        if hard_link && latest.get(&segment) != Some(&start) {
+            match reth_fs_util::hard_link(&path, &dest) {
+                Ok(()) => {
+                    linked += 1;
+                    continue
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, "Failed to hard link static file, copying it instead")
+                }
+            }
+        }
+
+        reth_fs_util::copy(&path, &dest)?;
+        copied += 1;
+    }
+
+    Ok((copied, linked))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_static_files_links_immutable_files() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+
+        let names = ["static_file_headers_0_499999", "static_file_headers_500000_999999"];
+        for name in names {
+            for ext in ["", ".conf", ".off"] {
+                std::fs::write(from.path().join(format!("{name}{ext}")), name).unwrap();
+            }
+        }
+        std::fs::write(from.path().join("lock"), "").unwrap();
+
+        let (copied, linked) = backup_static_files(from.path(), to.path(), true).unwrap();
+        assert_eq!((copied, linked), (3, 3));
+        assert!(!to.path().join("lock").exists());
+        for name in names {
+            assert_eq!(std::fs::read_to_string(to.path().join(name)).unwrap(), name);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     common::CliNodeTypes,
     db::get::{maybe_json_value_parser, table_key},
 };
-use ahash::RandomState;
+use alloy_primitives::B256;
 use clap::Parser;
 use reth_chainspec::EthereumHardforks;
 use reth_db::{DatabaseEnv, RawKey, RawTable, RawValue, TableViewer, Tables};
@@ -10,8 +10,8 @@ use reth_db_api::{cursor::DbCursorRO, table::Table, transaction::DbTx};
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_provider::{providers::ProviderNodeTypes, DBProvider};
+use sha2::{Digest, Sha256};
 use std::{
-    hash::{BuildHasher, Hasher},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -19,6 +19,9 @@ use tracing::{info, warn};
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db checksum` command
+///
+/// The checksum is a SHA-256 digest of the raw keys and values, so it can be compared between
+/// nodes regardless of their platform or reth version, as long as the database version matches.
 pub struct Command {
     /// The table name
     table: Tables,
@@ -67,10 +70,10 @@ impl<N: NodeTypesWithDB> ChecksumViewer<'_, N> {
     }
 }
 
-impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N> {
+impl<N: ProviderNodeTypes> TableViewer<(B256, Duration)> for ChecksumViewer<'_, N> {
     type Error = eyre::Report;
 
-    fn view<T: Table>(&self) -> Result<(u64, Duration), Self::Error> {
+    fn view<T: Table>(&self) -> Result<(B256, Duration), Self::Error> {
         let provider =
             self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
         let tx = provider.tx_ref();
@@ -99,7 +102,7 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
         };
 
         let start_time = Instant::now();
-        let mut hasher = RandomState::with_seeds(1, 2, 3, 4).build_hasher();
+        let mut hasher = Sha256::new();
         let mut total = 0;
 
         let limit = self.limit.unwrap_or(usize::MAX);
@@ -112,8 +115,11 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
                 info!("Hashed {index} entries.");
             }
 
-            hasher.write(k.raw_key());
-            hasher.write(v.raw_value());
+            // length prefixes keep the boundary between keys and values unambiguous
+            for bytes in [k.raw_key().as_slice(), v.raw_value()] {
+                hasher.update((bytes.len() as u64).to_be_bytes());
+                hasher.update(bytes);
+            }
 
             if enumerate_start_key.is_none() {
                 enumerate_start_key = Some(k.clone());
@@ -132,11 +138,43 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
             info!("end-key: {}", serde_json::to_string(&e.key()?).unwrap_or_default());
         }
 
-        let checksum = hasher.finish();
+        let checksum = B256::from_slice(&hasher.finalize());
         let elapsed = start_time.elapsed();
 
-        info!("Checksum for table `{}`: {:#x} (elapsed: {:?})", T::NAME, checksum, elapsed);
+        info!(
+            "Checksum for table `{}`: {checksum} over {total} entries (elapsed: {elapsed:?})",
+            T::NAME
+        );
 
         Ok((checksum, elapsed))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::tables;
+    use reth_db_api::{database::Database, transaction::DbTxMut};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn checksum_is_sha256_of_entries() {
+        let factory = create_test_provider_factory();
+        let tx = factory.db_ref().tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(1, B256::with_last_byte(1)).unwrap();
+        tx.put::<tables::CanonicalHeaders>(2, B256::with_last_byte(2)).unwrap();
+        tx.commit().unwrap();
+
+        let mut expected = Sha256::new();
+        for number in 1u64..=2 {
+            expected.update(8u64.to_be_bytes());
+            expected.update(number.to_be_bytes());
+            expected.update(32u64.to_be_bytes());
+            expected.update(B256::with_last_byte(number as u8));
+        }
+
+        let tool = DbTool::new(factory).unwrap();
+        let (checksum, _) = ChecksumViewer::new(&tool).view_rt(Tables::CanonicalHeaders).unwrap();
+        assert_eq!(checksum, B256::from_slice(&expected.finalize()));
+    }
+}
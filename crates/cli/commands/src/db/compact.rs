@@ -0,0 +1,217 @@
+//! `reth db compact` command.
+
+use crate::db::MDBX_DATA_FILE;
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{
+    mdbx::{DatabaseArguments, DatabaseEnvKind},
+    open_db, open_db_read_only, DatabaseEnv, RawDupSort, RawTable, TableViewer, Tables,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    database::Database,
+    models::DbStatsSnapshot,
+    table::{DupSort, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use std::path::Path;
+use tracing::info;
+
+/// The file name of the MDBX lock file inside the database directory.
+const MDBX_LOCK_FILE: &str = "mdbx.lck";
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db compact` command
+pub struct Command {
+    /// Rebuild the given tables by re-inserting their entries in key order, which also packs
+    /// partially filled pages. This needs a second copy of the database.
+    #[arg(long, value_delimiter = ',')]
+    tables: Vec<Tables>,
+
+    /// Keep the uncompacted data file as `mdbx.dat.old` in the database directory.
+    #[arg(long, default_value_t = false)]
+    keep_old: bool,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// The database is copied with compaction next to the database directory, which omits its
+    /// free pages. After the number of entries of every table is validated, the compacted data
+    /// file atomically replaces the current one. The database is opened with write access for the
+    /// whole command, so the node must be stopped. `reth db backup --compact` writes a compacted
+    /// copy of a running node's database instead.
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        let compact_path = db_path.with_extension("compact");
+        let rebuild_path = db_path.with_extension("rebuild");
+        // leftovers of an interrupted run
+        for path in [&compact_path, &rebuild_path] {
+            if path.exists() {
+                reth_fs_util::remove_dir_all(path)?;
+            }
+        }
+        reth_fs_util::create_dir_all(&compact_path)?;
+
+        let db = open_db(db_path, args.clone())?;
+        let before = db.stats_snapshot()?;
+
+        info!(target: "reth::cli", ?compact_path, "Copying database with compaction");
+        if self.tables.is_empty() {
+            db.copy(&compact_path.join(MDBX_DATA_FILE), true)?;
+        } else {
+            reth_fs_util::create_dir_all(&rebuild_path)?;
+            db.copy(&rebuild_path.join(MDBX_DATA_FILE), true)?;
+
+            // Opened directly, so no client version or stats snapshot is recorded into the copy.
+            let rebuilt = DatabaseEnv::open(&rebuild_path, DatabaseEnvKind::RW, args.clone())?;
+            for table in &self.tables {
+                info!(target: "reth::cli", %table, "Rebuilding table");
+                table.view(&RebuildViewer { from: &db, to: &rebuilt })?;
+            }
+
+            info!(target: "reth::cli", ?compact_path, "Copying rebuilt database with compaction");
+            rebuilt.copy(&compact_path.join(MDBX_DATA_FILE), true)?;
+            drop(rebuilt);
+            reth_fs_util::remove_dir_all(&rebuild_path)?;
+        }
+
+        let after = open_db_read_only(&compact_path, args)?.stats_snapshot()?;
+        if let Err(err) = validate_entries(&before, &after) {
+            reth_fs_util::remove_dir_all(&compact_path)?;
+            return Err(err)
+        }
+
+        // Replace the data file while the database is still open, so the storage lock is held
+        // until the end.
+        let data_file = db_path.join(MDBX_DATA_FILE);
+        if self.keep_old {
+            reth_fs_util::hard_link(&data_file, db_path.join(format!("{MDBX_DATA_FILE}.old")))?;
+        }
+        reth_fs_util::rename(compact_path.join(MDBX_DATA_FILE), &data_file)?;
+        drop(db);
+        // The lock file describes the replaced data file, MDBX recreates it on the next open.
+        let lock_file = db_path.join(MDBX_LOCK_FILE);
+        if lock_file.exists() {
+            reth_fs_util::remove_file(lock_file)?;
+        }
+        reth_fs_util::remove_dir_all(&compact_path)?;
+
+        println!(
+            "Compacted database from {} to {}",
+            human_bytes(before.allocated_size() as f64),
+            human_bytes(after.allocated_size() as f64)
+        );
+        Ok(())
+    }
+}
+
+/// Checks that every table of the compacted database has the same number of entries.
+fn validate_entries(before: &DbStatsSnapshot, after: &DbStatsSnapshot) -> eyre::Result<()> {
+    for table in &before.tables {
+        let entries = after.tables.iter().find(|t| t.name == table.name).map(|t| t.entries);
+        eyre::ensure!(
+            entries == Some(table.entries),
+            "Compacted table {} has {} entries instead of {}",
+            table.name,
+            entries.unwrap_or_default(),
+            table.entries
+        );
+    }
+    Ok(())
+}
+
+/// Replaces the entries of a table in `to` with the entries of the same table in `from`.
+struct RebuildViewer<'a> {
+    from: &'a DatabaseEnv,
+    to: &'a DatabaseEnv,
+}
+
+impl TableViewer<()> for RebuildViewer<'_> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let mut from = self.from.tx()?;
+        from.disable_long_read_transaction_safety();
+        let to = self.to.tx_mut()?;
+        to.clear::<T>()?;
+
+        let mut cursor = to.cursor_write::<RawTable<T>>()?;
+        for entry in from.cursor_read::<RawTable<T>>()?.walk(None)? {
+            let (key, value) = entry?;
+            cursor.append(key, value)?;
+        }
+        drop(cursor);
+        to.commit()?;
+        Ok(())
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        let mut from = self.from.tx()?;
+        from.disable_long_read_transaction_safety();
+        let to = self.to.tx_mut()?;
+        to.clear::<T>()?;
+
+        let mut cursor = to.cursor_dup_write::<RawDupSort<T>>()?;
+        for entry in from.cursor_read::<RawDupSort<T>>()?.walk(None)? {
+            let (key, value) = entry?;
+            cursor.append_dup(key, value)?;
+        }
+        drop(cursor);
+        to.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::{init_db, tables};
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_primitives::StorageEntry;
+
+    #[test]
+    fn compact_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        let args = DatabaseArguments::default();
+
+        let db = init_db(&db_path, args.clone()).unwrap();
+        let tx = db.tx_mut().unwrap();
+        for i in 0..1000u64 {
+            tx.put::<tables::BlockBodyIndices>(i, StoredBlockBodyIndices::default()).unwrap();
+            tx.put::<tables::PlainStorageState>(
+                Address::with_last_byte((i % 10) as u8),
+                StorageEntry { key: B256::with_last_byte((i / 10) as u8), value: U256::from(i) },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+        let tx = db.tx_mut().unwrap();
+        for i in (0..1000u64).step_by(2) {
+            tx.delete::<tables::BlockBodyIndices>(i, None).unwrap();
+        }
+        tx.commit().unwrap();
+        drop(db);
+
+        Command {
+            tables: vec![Tables::BlockBodyIndices, Tables::PlainStorageState],
+            keep_old: true,
+        }
+        .execute(&db_path, args.clone())
+        .unwrap();
+
+        assert!(db_path.join(format!("{MDBX_DATA_FILE}.old")).exists());
+        assert!(!db_path.with_extension("compact").exists());
+        assert!(!db_path.with_extension("rebuild").exists());
+
+        let db = open_db_read_only(&db_path, args).unwrap();
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.entries::<tables::BlockBodyIndices>().unwrap(), 500);
+        assert_eq!(tx.entries::<tables::PlainStorageState>().unwrap(), 1000);
+        assert_eq!(
+            tx.cursor_read::<tables::BlockBodyIndices>().unwrap().first().unwrap().map(|e| e.0),
+            Some(1)
+        );
+    }
+}
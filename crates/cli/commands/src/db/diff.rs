@@ -1,4 +1,6 @@
+use crate::db::get::{maybe_json_value_parser, table_key};
 use clap::Parser;
+use itertools::{EitherOrBoth, Itertools};
 use reth_db::{open_db_read_only, tables_to_generic, DatabaseEnv, Tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx};
 use reth_db_common::DbTool;
@@ -13,6 +15,7 @@ use std::{
     fs::{self, File},
     hash::Hash,
     io::Write,
+    ops::Bound,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -33,6 +36,19 @@ pub struct Command {
     #[arg(long, verbatim_doc_comment)]
     table: Option<Tables>,
 
+    /// The first key of the range to diff, as JSON. Requires `--table`.
+    #[arg(long, requires = "table", value_parser = maybe_json_value_parser)]
+    start_key: Option<String>,
+
+    /// The last key of the range to diff, as JSON. Requires `--table`.
+    #[arg(long, requires = "table", value_parser = maybe_json_value_parser)]
+    end_key: Option<String>,
+
+    /// The maximum number of mismatched entries per table that are printed to stdout. All of
+    /// them are written to the diff report.
+    #[arg(long, default_value_t = 10)]
+    print_limit: usize,
+
     /// The output directory for the diff report.
     #[arg(long, verbatim_doc_comment)]
     output: PlatformPath<PathBuf>,
@@ -45,13 +61,15 @@ impl Command {
     /// opened read-only.
     ///
     /// The tool will then iterate through all key-value pairs for the primary and secondary
-    /// databases. The value for each key will be compared with its corresponding value in the
+    /// databases, optionally restricted to the key range given by `--start-key` and `--end-key`.
+    /// The value for each key will be compared with its corresponding value in the
     /// other database. If the values are different, a discrepancy will be recorded in-memory. If
     /// one key is present in one database but not the other, this will be recorded as an "extra
     /// element" for that database.
     ///
     /// The discrepancies and extra elements, along with a brief summary of the diff results are
-    /// then written to a file in the output directory.
+    /// then written to a file in the output directory. The first `--print-limit` mismatches of
+    /// every table are also printed to stdout.
     pub fn execute<T: NodeTypesWithEngine>(
         self,
         tool: &DbTool<NodeTypesWithDBAdapter<T, Arc<DatabaseEnv>>>,
@@ -76,9 +94,15 @@ impl Command {
             secondary_tx.disable_long_read_transaction_safety();
 
             let output_dir = self.output.clone();
+            let range = DiffRange {
+                start_key: self.start_key.as_deref(),
+                end_key: self.end_key.as_deref(),
+            };
             tables_to_generic!(table, |Table| find_diffs::<Table>(
                 primary_tx,
                 secondary_tx,
+                range,
+                self.print_limit,
                 output_dir
             ))?;
         }
@@ -87,10 +111,37 @@ impl Command {
     }
 }
 
+/// Key bounds of a table range.
+type KeyBounds<T> = (Bound<<T as Table>::Key>, Bound<<T as Table>::Key>);
+
+/// The JSON encoded key range to diff, unbounded on either side if the key is not set.
+#[derive(Debug, Clone, Copy)]
+struct DiffRange<'a> {
+    start_key: Option<&'a str>,
+    end_key: Option<&'a str>,
+}
+
+impl DiffRange<'_> {
+    /// Parses the range into the key bounds of table `T`.
+    fn bounds<T: Table>(&self) -> eyre::Result<KeyBounds<T>> {
+        let start = match self.start_key {
+            Some(key) => Bound::Included(table_key::<T>(key)?),
+            None => Bound::Unbounded,
+        };
+        let end = match self.end_key {
+            Some(key) => Bound::Included(table_key::<T>(key)?),
+            None => Bound::Unbounded,
+        };
+        Ok((start, end))
+    }
+}
+
 /// Find diffs for a table, then analyzing the result
 fn find_diffs<T: Table>(
     primary_tx: impl DbTx,
     secondary_tx: impl DbTx,
+    range: DiffRange<'_>,
+    print_limit: usize,
     output_dir: impl AsRef<Path>,
 ) -> eyre::Result<()>
 where
@@ -100,7 +151,7 @@ where
     let table = T::NAME;
 
     info!("Analyzing table {table}...");
-    let result = find_diffs_advanced::<T>(&primary_tx, &secondary_tx)?;
+    let result = find_diffs_advanced::<T>(&primary_tx, &secondary_tx, range.bounds::<T>()?)?;
     info!("Done analyzing table {table}!");
 
     // Pretty info summary header: newline then header
@@ -146,6 +197,18 @@ where
         info!("No extra elements found in table {table}");
     }
 
+    // print the first mismatches, so small divergences can be investigated without the report
+    let mismatches =
+        result.discrepancies.values().map(|discrepancy| format!("{discrepancy:?}")).chain(
+            result.extra_elements.values().map(|extra_element| format!("{extra_element:?}")),
+        );
+    for mismatch in mismatches.take(print_limit) {
+        println!("{table}: {mismatch}");
+    }
+    if discrepancies + extra_elements > print_limit {
+        println!("{table}: {} more mismatches", discrepancies + extra_elements - print_limit);
+    }
+
     info!("Writing diff results for {table} to {file_name}...");
 
     if discrepancies > 0 {
@@ -174,6 +237,7 @@ where
 fn find_diffs_advanced<T: Table>(
     primary_tx: &impl DbTx,
     secondary_tx: &impl DbTx,
+    range: KeyBounds<T>,
 ) -> eyre::Result<TableDiffResult<T>>
 where
     T::Value: PartialEq,
@@ -182,12 +246,12 @@ where
     // initialize the zipped walker
     let mut primary_zip_cursor =
         primary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let primary_walker = primary_zip_cursor.walk(None)?;
+    let primary_walker = primary_zip_cursor.walk_range(range.clone())?;
 
     let mut secondary_zip_cursor =
         secondary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let secondary_walker = secondary_zip_cursor.walk(None)?;
-    let zipped_cursor = primary_walker.zip(secondary_walker);
+    let secondary_walker = secondary_zip_cursor.walk_range(range)?;
+    let zipped_cursor = primary_walker.zip_longest(secondary_walker);
 
     // initialize the cursors for seeking when we are cross checking elements
     let mut primary_cursor =
@@ -201,8 +265,26 @@ where
     // this loop will walk both tables, cross-checking for the element in the other table.
     // it basically just loops through both tables at the same time. if the keys are different, it
     // will check each key in the other table. if the keys are the same, it will compare the
-    // values
-    for (primary_entry, secondary_entry) in zipped_cursor {
+    // values. once one of the tables is exhausted, the remaining elements of the other table are
+    // only cross-checked
+    for entries in zipped_cursor {
+        let (primary_entry, secondary_entry) = match entries {
+            EitherOrBoth::Both(primary_entry, secondary_entry) => (primary_entry, secondary_entry),
+            EitherOrBoth::Left(primary_entry) => {
+                let (primary_key, primary_value) = primary_entry?;
+                let crossed_secondary =
+                    secondary_cursor.seek_exact(primary_key.clone())?.map(|(_, value)| value);
+                result.try_push_discrepancy(primary_key, Some(primary_value), crossed_secondary);
+                continue
+            }
+            EitherOrBoth::Right(secondary_entry) => {
+                let (secondary_key, secondary_value) = secondary_entry?;
+                let crossed_primary =
+                    primary_cursor.seek_exact(secondary_key.clone())?.map(|(_, value)| value);
+                result.try_push_discrepancy(secondary_key, crossed_primary, Some(secondary_value));
+                continue
+            }
+        };
         let (primary_key, primary_value) = primary_entry?;
         let (secondary_key, secondary_value) = secondary_entry?;
 
@@ -347,3 +429,56 @@ impl<T: Table> ExtraTableElement<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_db::{tables, test_utils::create_test_rw_db};
+    use reth_db_api::transaction::DbTxMut;
+
+    #[test]
+    fn diff_key_range() {
+        let primary = create_test_rw_db();
+        let secondary = create_test_rw_db();
+
+        let tx = primary.tx_mut().unwrap();
+        for number in 0..5 {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        tx.commit().unwrap();
+
+        // block 2 differs, blocks 3 and 4 only exist in the primary database
+        let tx = secondary.tx_mut().unwrap();
+        for number in 0..3 {
+            let hash = if number == 2 { B256::ZERO } else { B256::with_last_byte(number as u8) };
+            tx.put::<tables::CanonicalHeaders>(number, hash).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let primary_tx = primary.tx().unwrap();
+        let secondary_tx = secondary.tx().unwrap();
+
+        let range = DiffRange { start_key: None, end_key: None };
+        let result = find_diffs_advanced::<tables::CanonicalHeaders>(
+            &primary_tx,
+            &secondary_tx,
+            range.bounds::<tables::CanonicalHeaders>().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result.discrepancies.keys().collect::<Vec<_>>(), vec![&2]);
+        let mut extra = result.extra_elements.keys().copied().collect::<Vec<_>>();
+        extra.sort_unstable();
+        assert_eq!(extra, vec![3, 4]);
+
+        let range = DiffRange { start_key: Some("0"), end_key: Some("1") };
+        let result = find_diffs_advanced::<tables::CanonicalHeaders>(
+            &primary_tx,
+            &secondary_tx,
+            range.bounds::<tables::CanonicalHeaders>().unwrap(),
+        )
+        .unwrap();
+        assert!(result.discrepancies.is_empty());
+        assert!(result.extra_elements.is_empty());
+    }
+}
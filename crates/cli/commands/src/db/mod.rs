@@ -6,15 +6,21 @@ use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
 use reth_db_common::DbTool;
 use std::io::{self, Write};
 
+mod backup;
 mod checksum;
 mod clear;
+mod compact;
 mod diff;
 mod get;
 mod list;
+mod repl;
+mod restore;
 mod stats;
 /// DB List TUI
 mod tui;
 
+pub(crate) use backup::MDBX_DATA_FILE;
+
 /// `reth db` command
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
@@ -38,6 +44,8 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Starts an interactive shell to query the tables
+    Repl(repl::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -50,6 +58,13 @@ pub enum Subcommands {
     Version,
     /// Returns the full database path
     Path,
+    /// Creates a consistent backup of the database and static files, also while the node is
+    /// running
+    Backup(backup::Command),
+    /// Restores a backup created by `reth db backup` into the datadir
+    Restore(restore::Command),
+    /// Compacts the database file by copying it without its free pages
+    Compact(compact::Command),
 }
 
 /// `db_ro_exec` opens a database in read-only mode, and then execute with the provided command
@@ -65,6 +80,12 @@ macro_rules! db_ro_exec {
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
     /// Execute `db` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        // restoring creates the database, so it must not exist yet
+        let command = match self.command {
+            Subcommands::Restore(command) => return command.execute::<C, N>(self.env),
+            command => command,
+        };
+
         let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
@@ -79,7 +100,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         // ensure the provided database exist
         eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
 
-        match self.command {
+        match command {
             // TODO: We'll need to add this on the DB trait.
             Subcommands::Stats(command) => {
                 db_ro_exec!(self.env, tool, N, {
@@ -106,6 +127,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Repl(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -148,6 +174,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            Subcommands::Backup(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(data_dir, &tool)?;
+                });
+            }
+            Subcommands::Compact(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
+            Subcommands::Restore(_) => unreachable!("handled above"),
         }
 
         Ok(())
@@ -172,4 +207,23 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[test]
+    fn parse_backup_and_restore() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "backup",
+            "--output",
+            "backup",
+            "--hard-link",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Backup(_)));
+
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", "restore", "--input", "backup", "--force",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Restore(_)));
+    }
 }
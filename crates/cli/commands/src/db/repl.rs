@@ -0,0 +1,358 @@
+use crate::db::get::{maybe_json_value_parser, table_key, table_subkey};
+use alloy_primitives::hex;
+use clap::Parser;
+use reth_db::{TableViewer, Tables};
+use reth_db_api::table::{DupSort, Table};
+use reth_db_common::{DbTool, ListFilter};
+use reth_provider::providers::ProviderNodeTypes;
+use serde_json::Value;
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+/// The arguments for the `reth db repl` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The number of entries returned by `seek` and `scan` if `--len` is not given.
+    #[arg(long, default_value_t = 10)]
+    len: usize,
+}
+
+impl Command {
+    /// Execute `db repl` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        println!("Type `help` for a list of commands, `exit` to quit.");
+
+        let mut lines = io::stdin().lock().lines();
+        loop {
+            print!("reth> ");
+            io::stdout().flush()?;
+
+            let Some(line) = lines.next().transpose()? else { break };
+            let (input, filter) = match line.split_once('|') {
+                Some((input, filter)) => (input, Some(filter)),
+                None => (line.as_str(), None),
+            };
+            let filter = match filter.map(Filter::from_str).transpose() {
+                Ok(filter) => filter,
+                Err(err) => {
+                    println!("Invalid filter: {err}");
+                    continue
+                }
+            };
+
+            let words = input.split_whitespace().collect::<Vec<_>>();
+            if words.is_empty() {
+                continue
+            }
+            let input = match ReplInput::try_parse_from(words) {
+                Ok(input) => input,
+                Err(err) => {
+                    err.print()?;
+                    continue
+                }
+            };
+
+            let values = match input.command {
+                ReplCommand::Exit => break,
+                ReplCommand::Tables => {
+                    Ok(Tables::ALL.iter().map(|table| Value::from(table.name())).collect())
+                }
+                ReplCommand::Get { table, key, subkey } => {
+                    table.view(&ReplViewer { tool, query: Query::Get { key, subkey } })
+                }
+                ReplCommand::Seek { table, key, len } => table.view(&ReplViewer {
+                    tool,
+                    query: Query::Seek { key, len: len.unwrap_or(self.len) },
+                }),
+                ReplCommand::Scan { table, skip, len, search, reverse } => {
+                    let filter = ListFilter {
+                        skip,
+                        len: len.unwrap_or(self.len),
+                        search: search.unwrap_or_default(),
+                        min_row_size: 0,
+                        min_key_size: 0,
+                        min_value_size: 0,
+                        reverse,
+                        only_count: false,
+                    };
+                    table.view(&ReplViewer { tool, query: Query::Scan(filter) })
+                }
+            };
+
+            let values = match values {
+                Ok(values) => values,
+                Err(err) => {
+                    println!("Error: {err}");
+                    continue
+                }
+            };
+            for value in values {
+                let values = match &filter {
+                    Some(filter) => filter.apply(value),
+                    None => vec![value],
+                };
+                for value in values {
+                    println!("{}", serde_json::to_string_pretty(&value)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Queries the tables of the database.
+///
+/// Keys are JSON values, so they must not contain whitespace. Append `| <FILTER>` to a command to
+/// apply a jq-style path to each of its results, e.g. `get PlainAccountState 0x.. | .nonce` or
+/// `scan Headers | .value.gas_used`.
+#[derive(Parser, Debug)]
+#[command(
+    name = "",
+    override_usage = "<COMMAND> [| <FILTER>]",
+    no_binary_name = true,
+    disable_help_flag = true,
+    disable_version_flag = true
+)]
+struct ReplInput {
+    #[command(subcommand)]
+    command: ReplCommand,
+}
+
+/// Commands of the repl.
+#[derive(clap::Subcommand, Debug)]
+enum ReplCommand {
+    /// Gets the value of the given key
+    Get {
+        table: Tables,
+
+        /// The key to get the value for
+        #[arg(value_parser = maybe_json_value_parser)]
+        key: String,
+
+        /// The subkey to get the value for, only for dupsort tables
+        #[arg(value_parser = maybe_json_value_parser)]
+        subkey: Option<String>,
+    },
+    /// Lists the entries starting at the first key greater than or equal to the given key
+    Seek {
+        table: Tables,
+
+        /// The key to start at
+        #[arg(value_parser = maybe_json_value_parser)]
+        key: String,
+
+        /// How many entries to list
+        #[arg(long, short)]
+        len: Option<usize>,
+    },
+    /// Lists the entries of the table
+    Scan {
+        table: Tables,
+
+        /// Skip first N entries
+        #[arg(long, short, default_value_t = 0)]
+        skip: usize,
+
+        /// How many entries to list
+        #[arg(long, short)]
+        len: Option<usize>,
+
+        /// Only list entries whose raw key or value contains the given bytes. Prefix it with `0x`
+        /// to search for binary data, and text otherwise.
+        #[arg(long, value_parser = parse_search)]
+        search: Option<Vec<u8>>,
+
+        /// Start at the last entry of the table
+        #[arg(long, short)]
+        reverse: bool,
+    },
+    /// Lists all tables
+    Tables,
+    /// Exits the repl
+    #[command(alias = "quit")]
+    Exit,
+}
+
+/// Parses the search bytes of `scan`, same as `reth db list --search`.
+fn parse_search(search: &str) -> eyre::Result<Vec<u8>> {
+    match search.strip_prefix("0x") {
+        Some(search) => Ok(hex::decode(search)?),
+        None => Ok(search.as_bytes().to_vec()),
+    }
+}
+
+/// A query of the repl on a single table.
+enum Query {
+    Get { key: String, subkey: Option<String> },
+    Seek { key: String, len: usize },
+    Scan(ListFilter),
+}
+
+/// Runs a [`Query`] and returns the decoded results.
+///
+/// Entries are returned as `{"key": .., "value": ..}` objects, single values are returned as is,
+/// or as `null` if the key doesn't exist.
+struct ReplViewer<'a, N: ProviderNodeTypes> {
+    tool: &'a DbTool<N>,
+    query: Query,
+}
+
+impl<N: ProviderNodeTypes> ReplViewer<'_, N> {
+    fn entries<T: Table>(rows: Vec<(T::Key, T::Value)>) -> eyre::Result<Vec<Value>> {
+        rows.into_iter()
+            .map(|(key, value)| {
+                Ok(serde_json::json!({
+                    "key": serde_json::to_value(key)?,
+                    "value": serde_json::to_value(value)?,
+                }))
+            })
+            .collect()
+    }
+}
+
+impl<N: ProviderNodeTypes> TableViewer<Vec<Value>> for ReplViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<Vec<Value>, Self::Error> {
+        match &self.query {
+            Query::Get { key, subkey: None } => {
+                let value = self.tool.get::<T>(table_key::<T>(key)?)?;
+                Ok(vec![serde_json::to_value(value)?])
+            }
+            Query::Get { subkey: Some(_), .. } => {
+                eyre::bail!("{} is not a dupsort table, subkeys are not supported", T::NAME)
+            }
+            Query::Seek { key, len } => {
+                Self::entries::<T>(self.tool.seek::<T>(table_key::<T>(key)?, *len)?)
+            }
+            Query::Scan(filter) => Self::entries::<T>(self.tool.list::<T>(filter)?.0),
+        }
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<Vec<Value>, Self::Error> {
+        match &self.query {
+            Query::Get { key, subkey: Some(subkey) } => {
+                let value = self
+                    .tool
+                    .get_dup::<T>(table_key::<T>(key)?, table_subkey::<T>(Some(subkey))?)?;
+                Ok(vec![serde_json::to_value(value)?])
+            }
+            _ => self.view::<T>(),
+        }
+    }
+}
+
+/// A jq-style path into a JSON value, e.g. `.value.withdrawals[0]` or `.[].key`.
+///
+/// Supports the identity `.`, object fields `.field`, array indices `.[N]` and iteration over all
+/// elements of arrays and objects `.[]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Filter(Vec<FilterSegment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterSegment {
+    Field(String),
+    Index(usize),
+    Iterate,
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut rest = s.strip_prefix('.').ok_or_else(|| format!("`{s}` must start with `.`"))?;
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(index) = rest.strip_prefix('[') {
+                let (index, tail) =
+                    index.split_once(']').ok_or_else(|| format!("unclosed `[` in `{s}`"))?;
+                segments.push(if index.is_empty() {
+                    FilterSegment::Iterate
+                } else {
+                    FilterSegment::Index(
+                        index.parse().map_err(|_| format!("invalid index `{index}` in `{s}`"))?,
+                    )
+                });
+                rest = tail.strip_prefix('.').unwrap_or(tail);
+            } else {
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                let (field, tail) = rest.split_at(end);
+                if field.is_empty() {
+                    return Err(format!("empty field in `{s}`"))
+                }
+                segments.push(FilterSegment::Field(field.to_string()));
+                rest = tail.strip_prefix('.').unwrap_or(tail);
+            }
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl Filter {
+    /// Applies the filter, returning all matching values. Missing fields and indices evaluate to
+    /// `null`, like in jq.
+    fn apply(&self, value: Value) -> Vec<Value> {
+        self.0.iter().fold(vec![value], |values, segment| {
+            values
+                .into_iter()
+                .flat_map(|value| match segment {
+                    FilterSegment::Field(field) => {
+                        vec![value.get(field).cloned().unwrap_or_default()]
+                    }
+                    FilterSegment::Index(index) => {
+                        vec![value.get(index).cloned().unwrap_or_default()]
+                    }
+                    FilterSegment::Iterate => match value {
+                        Value::Array(values) => values,
+                        Value::Object(values) => values.into_iter().map(|(_, v)| v).collect(),
+                        _ => Vec::new(),
+                    },
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_repl_input() {
+        let input =
+            ReplInput::try_parse_from(["seek", "CanonicalHeaders", "10", "--len", "2"]).unwrap();
+        assert!(matches!(
+            input.command,
+            ReplCommand::Seek { table: Tables::CanonicalHeaders, len: Some(2), .. }
+        ));
+        assert!(matches!(ReplInput::try_parse_from(["quit"]).unwrap().command, ReplCommand::Exit));
+    }
+
+    #[test]
+    fn jq_filter() {
+        let value = json!([
+            {"key": 1, "value": {"nonce": 2, "hashes": ["0xaa", "0xbb"]}},
+            {"key": 2, "value": {"nonce": 3, "hashes": []}},
+        ]);
+
+        assert_eq!(".".parse::<Filter>().unwrap().apply(value.clone()), vec![value.clone()]);
+        assert_eq!(
+            ".[].value.nonce".parse::<Filter>().unwrap().apply(value.clone()),
+            vec![json!(2), json!(3)]
+        );
+        assert_eq!(
+            ".[0].value.hashes[1]".parse::<Filter>().unwrap().apply(value.clone()),
+            vec![json!("0xbb")]
+        );
+        assert_eq!(".[5].key".parse::<Filter>().unwrap().apply(value), vec![Value::Null]);
+
+        assert!("value".parse::<Filter>().is_err());
+        assert!(".[x]".parse::<Filter>().is_err());
+        assert!(".a..b".parse::<Filter>().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! `reth db restore` command.
+
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    db::backup::MDBX_DATA_FILE,
+};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::{
+    open_db_read_only,
+    version::{check_db_version_file, db_version_file_path},
+};
+use reth_provider::{
+    BlockHashReader, BlockReader, HeaderProvider, ProviderError, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db restore` command
+pub struct Command {
+    /// The backup directory written by `reth db backup`.
+    #[arg(long, value_name = "PATH")]
+    input: PathBuf,
+
+    /// Replace the existing database and static files of the datadir.
+    #[arg(long, default_value_t = false)]
+    force: bool,
+}
+
+impl Command {
+    /// Execute `db restore` command
+    ///
+    /// Copies the backup into the datadir and opens it, which heals static files that are ahead
+    /// of the database snapshot. The restored chain tip is then checked to be fully available.
+    pub fn execute<
+        C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>,
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    >(
+        self,
+        env: EnvironmentArgs<C>,
+    ) -> eyre::Result<()> {
+        let backup_db_path = self.input.join("db");
+        let backup_static_files_path = self.input.join("static_files");
+        eyre::ensure!(
+            backup_db_path.join(MDBX_DATA_FILE).is_file(),
+            "Backup database does not exist: {:?}",
+            backup_db_path
+        );
+        eyre::ensure!(
+            backup_static_files_path.is_dir(),
+            "Backup static files do not exist: {:?}",
+            backup_static_files_path
+        );
+        check_db_version_file(&backup_db_path)?;
+
+        let data_dir = env.datadir.clone().resolve_datadir(env.chain.chain());
+        let db_path = data_dir.db();
+        let static_files_path = data_dir.static_files();
+        if db_path.join(MDBX_DATA_FILE).exists() || has_static_files(&static_files_path)? {
+            if !self.force {
+                eyre::bail!(
+                    "Datadir {data_dir} already contains a database, use --force to replace it"
+                )
+            }
+            info!(target: "reth::cli", ?db_path, ?static_files_path, "Removing existing database and static files");
+            if db_path.exists() {
+                reth_fs_util::remove_dir_all(&db_path)?;
+            }
+            if static_files_path.exists() {
+                reth_fs_util::remove_dir_all(&static_files_path)?;
+            }
+        }
+
+        info!(target: "reth::cli", input = ?self.input, %data_dir, "Restoring backup");
+        reth_fs_util::create_dir_all(&db_path)?;
+        reth_fs_util::create_dir_all(&static_files_path)?;
+        // Copying through MDBX keeps the data file sparse, unlike a plain file copy.
+        open_db_read_only(&backup_db_path, env.db.database_args())?
+            .copy(&db_path.join(MDBX_DATA_FILE), false)?;
+        reth_fs_util::copy(db_version_file_path(&backup_db_path), db_version_file_path(&db_path))?;
+        for entry in reth_fs_util::read_dir(&backup_static_files_path)? {
+            let path = entry?.path();
+            if path.is_file() {
+                reth_fs_util::copy(&path, static_files_path.join(path.file_name().unwrap()))?;
+            }
+        }
+
+        // Opening the datadir runs the static file consistency check.
+        let Environment { provider_factory, .. } = env.init::<N>(AccessRights::RW)?;
+        let provider = provider_factory.provider()?;
+
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        let highest_header = provider_factory
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+        eyre::ensure!(
+            highest_header >= tip,
+            "Restored static files end at block {highest_header}, behind the chain tip {tip}"
+        );
+        let header = provider
+            .sealed_header(tip)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(tip.into()))?;
+        let canonical_hash =
+            provider.block_hash(tip)?.ok_or_else(|| ProviderError::HeaderNotFound(tip.into()))?;
+        eyre::ensure!(
+            header.hash() == canonical_hash,
+            "Restored header {tip} does not match its canonical hash {canonical_hash}"
+        );
+        provider.block_body_indices(tip)?.ok_or(ProviderError::BlockBodyIndicesNotFound(tip))?;
+
+        println!("Restored backup with chain tip {tip} ({})", header.hash());
+        Ok(())
+    }
+}
+
+/// Returns `true` if the static files directory contains any static files.
+fn has_static_files(path: &Path) -> eyre::Result<bool> {
+    if !path.exists() {
+        return Ok(false)
+    }
+    for entry in reth_fs_util::read_dir(path)? {
+        if StaticFileSegment::parse_filename(&entry?.file_name().to_string_lossy()).is_some() {
+            return Ok(true)
+        }
+    }
+    Ok(false)
+}
@@ -5,15 +5,19 @@ use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv, TableViewer, Tables};
-use reth_db_api::database::Database;
+use reth_db::{mdbx, static_file::iter_static_files, tables, DatabaseEnv, TableViewer, Tables};
+use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
 use reth_node_builder::{NodePrimitives, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_provider::providers::{ProviderNodeTypes, StaticFileProvider};
 use reth_static_file_types::SegmentRangeInclusive;
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db stats` command
@@ -34,6 +38,13 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Show the growth of the database and its tables over time instead of the current stats.
+    ///
+    /// Snapshots are recorded at most hourly whenever the database is opened with write access,
+    /// e.g. by a starting node.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["checksum", "detailed_sizes", "detailed_segments"])]
+    history: bool,
 }
 
 impl Command {
@@ -43,6 +54,14 @@ impl Command {
         data_dir: ChainPath<DataDirPath>,
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
+        if self.history {
+            let (snapshots_table, tables_table) = self.history_tables(tool)?;
+            println!("{snapshots_table}");
+            println!("\n");
+            println!("{tables_table}");
+            return Ok(())
+        }
+
         if self.checksum {
             let checksum_report = self.checksum_report(tool)?;
             println!("{checksum_report}");
@@ -80,7 +99,9 @@ impl Command {
             db_tables.sort();
             let mut total_size = 0;
             for db_table in db_tables {
-                let table_db = tx.inner.open_db(Some(db_table)).wrap_err("Could not open db.")?;
+                // tables added in newer versions only exist once the database was opened with
+                // write access
+                let Ok(table_db) = tx.inner.open_db(Some(db_table)) else { continue };
 
                 let stats = tx
                     .inner
@@ -137,12 +158,121 @@ impl Command {
                 .add_cell(Cell::new(human_bytes(freelist_size as f64)));
             table.add_row(row);
 
+            let allocated_pages = tx.inner.env().info()?.last_pgno() + 1;
+            let mut row = Row::new();
+            row.add_cell(Cell::new("Allocated"))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(human_bytes((allocated_pages * pagesize) as f64)));
+            table.add_row(row);
+
+            let utilization =
+                allocated_pages.saturating_sub(freelist) as f64 / allocated_pages as f64;
+            let mut row = Row::new();
+            row.add_cell(Cell::new("Page Utilization"))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(format!("{:.2}%", utilization * 100.0)));
+            table.add_row(row);
+
             Ok::<(), eyre::Report>(())
         })??;
 
         Ok(table)
     }
 
+    fn history_tables<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        &self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<(ComfyTable, ComfyTable)> {
+        let snapshots = tool
+            .provider_factory
+            .db_ref()
+            .view(|tx| {
+                tx.cursor_read::<tables::DbStatsHistory>()?
+                    .walk(None)?
+                    .collect::<Result<Vec<_>, _>>()
+            })?
+            .wrap_err("Could not read the database stats history.")?;
+
+        let mut snapshots_table = ComfyTable::new();
+        snapshots_table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        snapshots_table.set_header([
+            "Timestamp",
+            "Allocated",
+            "Used",
+            "Freelist",
+            "Page Utilization",
+            "Change",
+        ]);
+
+        let mut previous_used = None;
+        for (timestamp, snapshot) in &snapshots {
+            let used = snapshot.used_pages() * snapshot.page_size as u64;
+            let change = previous_used.map(|previous| signed_human_bytes(used, previous));
+            previous_used = Some(used);
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new(humantime::format_rfc3339_seconds(
+                UNIX_EPOCH + Duration::from_secs(*timestamp),
+            )))
+            .add_cell(Cell::new(human_bytes(snapshot.allocated_size() as f64)))
+            .add_cell(Cell::new(human_bytes(used as f64)))
+            .add_cell(Cell::new(human_bytes(snapshot.freelist_size() as f64)))
+            .add_cell(Cell::new(format!("{:.2}%", snapshot.utilization() * 100.0)))
+            .add_cell(Cell::new(change.unwrap_or_default()));
+            snapshots_table.add_row(row);
+        }
+
+        let mut tables_table = ComfyTable::new();
+        tables_table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        tables_table.set_header([
+            "Table Name",
+            "First # Entries",
+            "Last # Entries",
+            "First Size",
+            "Last Size",
+            "Growth",
+        ]);
+
+        // compare every table between the oldest and the latest snapshot
+        if let (Some((_, first)), Some((_, last))) = (snapshots.first(), snapshots.last()) {
+            let first_tables = first
+                .tables
+                .iter()
+                .map(|table| (table.name.as_str(), table))
+                .collect::<HashMap<_, _>>();
+            let growth = last
+                .tables
+                .iter()
+                .map(|table| {
+                    let first_table = first_tables.get(table.name.as_str());
+                    let first_size = first_table.map_or(0, |t| t.pages * first.page_size as u64);
+                    let last_size = table.pages * last.page_size as u64;
+                    (table, first_table.map_or(0, |t| t.entries), first_size, last_size)
+                })
+                .sorted_by_key(|(_, _, first_size, last_size)| {
+                    std::cmp::Reverse(*last_size as i128 - *first_size as i128)
+                });
+            for (table, first_entries, first_size, last_size) in growth {
+                let mut row = Row::new();
+                row.add_cell(Cell::new(&table.name))
+                    .add_cell(Cell::new(first_entries))
+                    .add_cell(Cell::new(table.entries))
+                    .add_cell(Cell::new(human_bytes(first_size as f64)))
+                    .add_cell(Cell::new(human_bytes(last_size as f64)))
+                    .add_cell(Cell::new(signed_human_bytes(last_size, first_size)));
+                tables_table.add_row(row);
+            }
+        }
+
+        Ok((snapshots_table, tables_table))
+    }
+
     fn static_files_stats_table<N: NodePrimitives>(
         &self,
         data_dir: ChainPath<DataDirPath>,
@@ -343,7 +473,7 @@ impl Command {
             // add rows containing checksums to the table
             let mut row = Row::new();
             row.add_cell(Cell::new(db_table));
-            row.add_cell(Cell::new(format!("{:x}", checksum)));
+            row.add_cell(Cell::new(checksum));
             row.add_cell(Cell::new(format!("{:?}", elapsed)));
             table.add_row(row);
         }
@@ -366,3 +496,12 @@ impl Command {
         Ok(table)
     }
 }
+
+/// Formats the difference between two sizes with a sign.
+fn signed_human_bytes(current: u64, previous: u64) -> String {
+    if current >= previous {
+        format!("+{}", human_bytes((current - previous) as f64))
+    } else {
+        format!("-{}", human_bytes((previous - current) as f64))
+    }
+}
@@ -0,0 +1,617 @@
+//! `reth doctor` command.
+//!
+//! Opens the datadir read-only and runs consistency checks on it. The problems found are printed
+//! from the most to the least severe, together with the commands that usually recover from them.
+
+use crate::{
+    common::{CliNodeTypes, EnvironmentArgs},
+    db::MDBX_DATA_FILE,
+};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_config::Config;
+use reth_db::{open_db_read_only, tables, DatabaseEnv};
+use reth_db_api::{cursor::DbCursorRO, table::Table, transaction::DbTx};
+use reth_exex::Wal;
+use reth_nippy_jar::{NippyJar, NippyJarChecker, NippyJarError};
+use reth_node_builder::{NodePrimitives, NodeTypesWithDBAdapter};
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, BlockReader, DBProvider, HeaderProvider,
+    ProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use std::{cmp::Ordering, collections::BTreeMap, fmt, path::Path, sync::Arc};
+use tracing::info;
+
+/// Number of blocks below the chain tip from which on the `ExEx` WAL is considered to not be
+/// finalized anymore.
+const WAL_MAX_BLOCKS_BEHIND_TIP: u64 = 1024;
+
+/// Minimum size of the database freelist that's reported.
+const FREELIST_MIN_SIZE: u64 = 1024 * 1024 * 1024;
+
+const RESTORE_BACKUP: &str = "reth db restore --input <BACKUP> --force";
+
+/// Diagnoses the datadir and prints its problems with suggested recovery commands
+#[derive(Debug, Parser)]
+pub struct DoctorCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DoctorCommand<C> {
+    /// Execute the `doctor` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+        info!(target: "reth::cli", %data_dir, "Diagnosing datadir");
+
+        let mut report = Report::default();
+        self.diagnose::<N>(&data_dir, &mut report)?;
+
+        let mut problems = report.problems;
+        if problems.is_empty() {
+            println!("No problems found");
+            return Ok(())
+        }
+
+        problems.sort_by_key(|problem| problem.severity);
+        println!("Found {} problem(s), most severe first:\n", problems.len());
+        for (i, problem) in problems.iter().enumerate() {
+            println!("{}. {problem}", i + 1);
+        }
+        println!(
+            "\nRun the suggested commands with the same `--chain` and `--datadir` arguments, one \
+             problem at a time, and run `reth doctor` again after each of them."
+        );
+
+        let errors = problems.iter().filter(|problem| problem.severity <= Severity::Error).count();
+        if errors > 0 {
+            eyre::bail!("Found {errors} critical problem(s) or error(s)")
+        }
+        Ok(())
+    }
+
+    fn diagnose<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+        &self,
+        data_dir: &ChainPath<DataDirPath>,
+        report: &mut Report,
+    ) -> eyre::Result<()> {
+        let db_path = data_dir.db();
+        if !db_path.join(MDBX_DATA_FILE).is_file() {
+            report.push(
+                Severity::Critical,
+                "database",
+                format!("No database found in {}", db_path.display()),
+                Some("reth init".to_string()),
+            );
+            return Ok(())
+        }
+        let db = match open_db_read_only(&db_path, self.env.db.database_args()) {
+            Ok(db) => Arc::new(db),
+            Err(err) => {
+                report.push(
+                    Severity::Critical,
+                    "database",
+                    format!("The database can't be opened: {err}"),
+                    Some(RESTORE_BACKUP.to_string()),
+                );
+                return Ok(())
+            }
+        };
+        check_freelist(&db, report)?;
+
+        let static_files_path = data_dir.static_files();
+        check_static_file_headers(&static_files_path, report)?;
+        let static_file_provider = match StaticFileProvider::read_only(&static_files_path, false) {
+            Ok(provider) => provider,
+            Err(err) => {
+                report.push(
+                    Severity::Critical,
+                    "static files",
+                    format!("The static files can't be opened: {err}"),
+                    Some(RESTORE_BACKUP.to_string()),
+                );
+                return Ok(())
+            }
+        };
+
+        let config =
+            Config::from_path(self.env.config.clone().unwrap_or_else(|| data_dir.config()))
+                .unwrap_or_default();
+        let has_receipt_pruning = config.prune.is_some_and(|prune| prune.has_receipts_pruning());
+
+        let factory = ProviderFactory::<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>::new(
+            db,
+            self.env.chain.clone(),
+            static_file_provider,
+        );
+        let provider = factory.provider()?;
+        check_stage_checkpoints(&provider, report)?;
+        check_static_file_boundaries(
+            &provider,
+            &factory.static_file_provider(),
+            has_receipt_pruning,
+            report,
+        )?;
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        // The chain tip is read from the static files, so its problems follow from theirs.
+        if !report.problems.iter().any(|problem| problem.check == "static files") {
+            check_chain_tip(&provider, tip, report)?;
+        }
+
+        check_wal(&data_dir.exex_wal(), tip, report);
+
+        Ok(())
+    }
+}
+
+/// Severity of a [`Problem`], ordered from the most to the least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    /// Data is lost or corrupted, the node can't start or sync from it.
+    Critical,
+    /// The node can start, but some of its data is inconsistent.
+    Error,
+    /// The node heals the problem on startup, or it only affects performance.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Critical => f.write_str("critical"),
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A problem found in the datadir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Problem {
+    severity: Severity,
+    /// The check that found the problem.
+    check: &'static str,
+    message: String,
+    /// The command that usually recovers from the problem.
+    recovery: Option<String>,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.check, self.message)?;
+        if let Some(recovery) = &self.recovery {
+            write!(f, "\n   Recovery: {recovery}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Problems found by the checks.
+#[derive(Debug, Default)]
+struct Report {
+    problems: Vec<Problem>,
+}
+
+impl Report {
+    fn push(
+        &mut self,
+        severity: Severity,
+        check: &'static str,
+        message: String,
+        recovery: Option<String>,
+    ) {
+        self.problems.push(Problem { severity, check, message, recovery });
+    }
+}
+
+/// Reports a large share of free pages in the database file.
+fn check_freelist(db: &DatabaseEnv, report: &mut Report) -> eyre::Result<()> {
+    let stats = db.stats_snapshot()?;
+    if stats.freelist_size() >= FREELIST_MIN_SIZE && stats.utilization() < 0.5 {
+        report.push(
+            Severity::Warning,
+            "database",
+            format!(
+                "{} of the {} database file are free pages ({:.2}% utilization)",
+                human_bytes(stats.freelist_size() as f64),
+                human_bytes(stats.allocated_size() as f64),
+                stats.utilization() * 100.0
+            ),
+            Some("reth db compact".to_string()),
+        );
+    }
+    Ok(())
+}
+
+/// Checks the header of every static file against its file name and its neighbours, and its data
+/// and offsets files against the header.
+fn check_static_file_headers(path: &Path, report: &mut Report) -> eyre::Result<()> {
+    if !path.exists() {
+        return Ok(())
+    }
+
+    let mut files = BTreeMap::<StaticFileSegment, Vec<_>>::new();
+    for entry in reth_fs_util::read_dir(path)? {
+        let path = entry?.path();
+        let Some((segment, range)) = path
+            .file_name()
+            .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
+        else {
+            continue
+        };
+        files.entry(segment).or_default().push((range, path));
+    }
+
+    for (segment, mut files) in files {
+        files.sort_by_key(|(range, _)| range.start());
+        let latest = files.len() - 1;
+
+        let mut previous: Option<SegmentHeader> = None;
+        for (i, (range, path)) in files.into_iter().enumerate() {
+            let name = path.file_name().expect("file name exists").to_string_lossy().into_owned();
+            let jar = match NippyJar::<SegmentHeader>::load(&path) {
+                Ok(jar) => jar,
+                Err(err) => {
+                    report.push(
+                        Severity::Critical,
+                        "static files",
+                        format!("{name} can't be loaded: {err}"),
+                        Some(RESTORE_BACKUP.to_string()),
+                    );
+                    previous = None;
+                    continue
+                }
+            };
+            let header = jar.user_header().clone();
+            let expected_range = SegmentRangeInclusive::new(
+                header.expected_block_start(),
+                header.expected_block_end(),
+            );
+
+            if header.segment() != segment || expected_range != range {
+                report.push(
+                    Severity::Critical,
+                    "static files",
+                    format!("{name} has a header for {} blocks {expected_range}", header.segment()),
+                    Some(RESTORE_BACKUP.to_string()),
+                );
+            } else if let Some(block_range) = header.block_range() {
+                if block_range.start() < range.start() || block_range.end() > range.end() {
+                    report.push(
+                        Severity::Critical,
+                        "static files",
+                        format!("{name} holds blocks {block_range}, outside of its file range"),
+                        Some(RESTORE_BACKUP.to_string()),
+                    );
+                }
+            } else if i != latest {
+                report.push(
+                    Severity::Critical,
+                    "static files",
+                    format!("{name} holds no blocks, but isn't the latest {segment} static file"),
+                    Some(RESTORE_BACKUP.to_string()),
+                );
+            }
+
+            if let Some(previous) = &previous {
+                if let Some((previous_end, start)) = previous.block_end().zip(header.block_start())
+                {
+                    if start != previous_end + 1 {
+                        report.push(
+                            Severity::Critical,
+                            "static files",
+                            format!(
+                                "{name} starts at block {start}, but the previous {segment} \
+                                 static file ends at block {previous_end}"
+                            ),
+                            Some(RESTORE_BACKUP.to_string()),
+                        );
+                    }
+                }
+                if let Some((previous_end, start)) = previous.tx_end().zip(header.tx_start()) {
+                    if start != previous_end + 1 {
+                        report.push(
+                            Severity::Critical,
+                            "static files",
+                            format!(
+                                "{name} starts at transaction {start}, but the previous {segment} \
+                                 static file ends at transaction {previous_end}"
+                            ),
+                            Some(RESTORE_BACKUP.to_string()),
+                        );
+                    }
+                }
+            }
+
+            match NippyJarChecker::new(jar).check_consistency() {
+                Ok(()) | Err(NippyJarError::FrozenJar) => {}
+                // Only the latest file is appended to, so only its writes can be interrupted.
+                Err(err) if i == latest => report.push(
+                    Severity::Warning,
+                    "static files",
+                    format!("{name} has an interrupted write: {err}"),
+                    Some("Start the node, which heals it on startup".to_string()),
+                ),
+                Err(err) => report.push(
+                    Severity::Critical,
+                    "static files",
+                    format!("{name} is inconsistent: {err}"),
+                    Some(RESTORE_BACKUP.to_string()),
+                ),
+            }
+
+            previous = Some(header);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that no stage checkpoint is ahead of the checkpoint of the stage before it.
+fn check_stage_checkpoints<Provider: StageCheckpointReader>(
+    provider: &Provider,
+    report: &mut Report,
+) -> eyre::Result<()> {
+    let mut previous: Option<(StageId, BlockNumber)> = None;
+    for stage in StageId::ALL {
+        // Stages that never ran have no checkpoint.
+        let Some(checkpoint) = provider.get_stage_checkpoint(stage)? else { continue };
+
+        if let Some((previous_stage, previous_block)) = previous {
+            if checkpoint.block_number > previous_block {
+                report.push(
+                    Severity::Error,
+                    "checkpoints",
+                    format!(
+                        "The {stage} checkpoint at block {} is ahead of the {previous_stage} \
+                         checkpoint at block {previous_block}",
+                        checkpoint.block_number
+                    ),
+                    Some(format!("reth stage unwind to-block {previous_block}")),
+                );
+                continue
+            }
+        }
+        previous = Some((stage, checkpoint.block_number));
+    }
+
+    Ok(())
+}
+
+/// Checks the boundaries between the static files and the database tables of each segment, like
+/// [`StaticFileProvider::check_consistency`] does on startup, without healing them.
+fn check_static_file_boundaries<Provider, N>(
+    provider: &Provider,
+    static_file_provider: &StaticFileProvider<N>,
+    has_receipt_pruning: bool,
+    report: &mut Report,
+) -> eyre::Result<()>
+where
+    Provider: DBProvider + StageCheckpointReader,
+    N: NodePrimitives,
+{
+    for segment in
+        [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+    {
+        if has_receipt_pruning && segment.is_receipts() {
+            // Pruned nodes (including full node) do not store receipts as static files.
+            continue
+        }
+
+        let highest_block = static_file_provider.get_highest_static_file_block(segment);
+        let (highest_entry, db_range, stage, entry) = match segment {
+            StaticFileSegment::Headers => (
+                highest_block,
+                table_range::<tables::Headers, _>(provider)?,
+                StageId::Headers,
+                "block",
+            ),
+            StaticFileSegment::Transactions => (
+                static_file_provider.get_highest_static_file_tx(segment),
+                table_range::<tables::Transactions, _>(provider)?,
+                StageId::Bodies,
+                "transaction",
+            ),
+            StaticFileSegment::Receipts => (
+                static_file_provider.get_highest_static_file_tx(segment),
+                table_range::<tables::Receipts, _>(provider)?,
+                StageId::Execution,
+                "transaction",
+            ),
+        };
+        let highest_block = highest_block.unwrap_or_default();
+        let highest_entry = highest_entry.unwrap_or_default();
+
+        if let Some((db_first_entry, db_last_entry)) = db_range {
+            if db_first_entry > highest_entry + 1 {
+                report.push(
+                    Severity::Critical,
+                    "static files",
+                    format!(
+                        "{segment} static files end at {entry} {highest_entry}, but the database \
+                         only continues at {entry} {db_first_entry}"
+                    ),
+                    Some(format!("reth stage unwind to-block {highest_block}")),
+                );
+                continue
+            }
+            if db_last_entry > highest_entry {
+                continue
+            }
+        }
+
+        let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+        match checkpoint.cmp(&highest_block) {
+            Ordering::Greater => report.push(
+                Severity::Critical,
+                "static files",
+                format!(
+                    "{segment} static files end at block {highest_block}, behind the {stage} \
+                     checkpoint at block {checkpoint}"
+                ),
+                Some(format!("reth stage unwind to-block {highest_block}")),
+            ),
+            Ordering::Less => report.push(
+                Severity::Warning,
+                "static files",
+                format!(
+                    "{segment} static files end at block {highest_block}, ahead of the {stage} \
+                     checkpoint at block {checkpoint}"
+                ),
+                Some("Start the node, which truncates the static files on startup".to_string()),
+            ),
+            Ordering::Equal => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the first and last key of a table.
+fn table_range<T: Table<Key = u64>, Provider: DBProvider>(
+    provider: &Provider,
+) -> eyre::Result<Option<(u64, u64)>> {
+    let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+    Ok(cursor.first()?.map(|(first, _)| first).zip(cursor.last()?.map(|(last, _)| last)))
+}
+
+/// Checks that the header, hash and body of the chain tip are available.
+fn check_chain_tip<Provider>(
+    provider: &Provider,
+    tip: BlockNumber,
+    report: &mut Report,
+) -> eyre::Result<()>
+where
+    Provider: HeaderProvider + BlockHashReader + BlockReader,
+{
+    let header = provider.sealed_header(tip)?;
+    let canonical_hash = provider.block_hash(tip)?;
+    let message = match (header, canonical_hash) {
+        (None, _) => Some(format!("The header of the chain tip {tip} is missing")),
+        (_, None) => Some(format!("The canonical hash of the chain tip {tip} is missing")),
+        (Some(header), Some(hash)) if header.hash() != hash => Some(format!(
+            "The header of the chain tip {tip} doesn't match its canonical hash {hash}"
+        )),
+        _ if provider.block_body_indices(tip)?.is_none() => {
+            Some(format!("The body of the chain tip {tip} is missing"))
+        }
+        _ => None,
+    };
+    if let Some(message) = message {
+        report.push(Severity::Critical, "chain tip", message, Some(RESTORE_BACKUP.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checks that the `ExEx` WAL can be read and is finalized.
+fn check_wal(path: &Path, tip: BlockNumber, report: &mut Report) {
+    if !path.exists() {
+        return
+    }
+    let remove_wal = format!("rm -r {}", path.display());
+
+    let lowest_block = Wal::new(path).and_then(|wal| {
+        let mut lowest_block = None::<BlockNumber>;
+        for notification in wal.iter_notifications()? {
+            if let Some(chain) = notification?.committed_chain() {
+                let first = chain.first().number;
+                lowest_block = Some(lowest_block.map_or(first, |lowest| lowest.min(first)));
+            }
+        }
+        Ok(lowest_block)
+    });
+
+    match lowest_block {
+        Ok(Some(lowest_block)) if tip.saturating_sub(lowest_block) > WAL_MAX_BLOCKS_BEHIND_TIP => {
+            report.push(
+                Severity::Warning,
+                "exex wal",
+                format!(
+                    "The ExEx WAL holds blocks from block {lowest_block}, {} blocks behind the \
+                     chain tip, so it's not finalized by the installed ExExes",
+                    tip - lowest_block
+                ),
+                Some(format!("If no ExExes are installed anymore: {remove_wal}")),
+            )
+        }
+        Ok(_) => {}
+        Err(err) => report.push(
+            Severity::Error,
+            "exex wal",
+            format!("The ExEx WAL can't be read: {err}"),
+            Some(remove_wal),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::U256;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileWriter,
+    };
+    use reth_stages::StageCheckpoint;
+
+    #[test]
+    fn reports_stage_ahead_of_previous_stage() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(10)).unwrap();
+        provider.save_stage_checkpoint(StageId::Bodies, StageCheckpoint::new(10)).unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(5)).unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(5)).unwrap();
+
+        let mut report = Report::default();
+        check_stage_checkpoints(&*provider, &mut report).unwrap();
+        assert!(report.problems.is_empty());
+
+        provider.save_stage_checkpoint(StageId::MerkleExecute, StageCheckpoint::new(8)).unwrap();
+        check_stage_checkpoints(&*provider, &mut report).unwrap();
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].severity, Severity::Error);
+        assert_eq!(report.problems[0].recovery.as_deref(), Some("reth stage unwind to-block 5"));
+    }
+
+    #[test]
+    fn reports_static_file_problems() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..3 {
+            let header = Header { number, ..Default::default() };
+            writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider = factory.provider().unwrap();
+        let mut report = Report::default();
+        check_static_file_headers(static_file_provider.directory(), &mut report).unwrap();
+        check_static_file_boundaries(&provider, &static_file_provider, false, &mut report).unwrap();
+        // the static files are ahead of the missing headers checkpoint
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].severity, Severity::Warning);
+
+        // interrupt a write by truncating the data file
+        let data_file = static_file_provider
+            .directory()
+            .join(StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(0, 499_999)));
+        let file = std::fs::OpenOptions::new().write(true).open(&data_file).unwrap();
+        file.set_len(file.metadata().unwrap().len() - 1).unwrap();
+
+        let mut report = Report::default();
+        check_static_file_headers(static_file_provider.directory(), &mut report).unwrap();
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].severity, Severity::Warning);
+        assert!(report.problems[0].message.contains("interrupted write"));
+    }
+}
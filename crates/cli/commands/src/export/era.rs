@@ -0,0 +1,281 @@
+//! `reth export era` command.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reqwest::{header::ACCEPT, StatusCode};
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era::{
+    era1_file_name, era_block_slots, era_file_name, BlockTuple, Era1Writer, EraWriter,
+    SignedBeaconBlockView, MAX_BLOCKS_PER_ERA1, SLOTS_PER_HISTORICAL_ROOT,
+};
+use reth_provider::{
+    BlockHashReader, BlockNumReader, BlockReader, DBProvider, HeaderProvider, ProviderError,
+    ReceiptProvider,
+};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// Number of seconds per beacon chain slot.
+const SECONDS_PER_SLOT: u64 = 12;
+
+/// `reth export era` command
+///
+/// Writes pre-merge blocks, receipts and total difficulties into standardized era1 files, one
+/// file per epoch of 8192 blocks.
+///
+/// Post-merge era files store signed beacon blocks and beacon states, which are not available to
+/// the execution layer. If a beacon node API is configured, the export continues after the merge
+/// with era files of 8192 slots sourced from the beacon node, whose execution payloads are checked
+/// against the local chain.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export. Must be the first block of an epoch.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the last block available in the database.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// The directory the era1 and era files are written to.
+    ///
+    /// Defaults to `<DATADIR>/era`.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    output: Option<PathBuf>,
+
+    /// The URL of a beacon node API to source the post-merge era files from.
+    ///
+    /// Only complete eras that are finalized and covered by the exported block range are
+    /// written. Older eras require a beacon node that serves historical states.
+    #[arg(long, value_name = "URL")]
+    beacon_api: Option<String>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `export era` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        if self.from % MAX_BLOCKS_PER_ERA1 as u64 != 0 {
+            eyre::bail!(
+                "era1 files start at epoch boundaries, --from must be a multiple of {MAX_BLOCKS_PER_ERA1}"
+            )
+        }
+
+        let Environment { provider_factory, data_dir, .. } =
+            self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+
+        let output = self.output.unwrap_or_else(|| data_dir.data_dir().join("era"));
+        reth_fs_util::create_dir_all(&output)?;
+
+        let last_block = provider.last_block_number()?;
+        let to = self.to.map_or(last_block, |to| to.min(last_block));
+        let network = self.env.chain.chain().to_string();
+        let paris = self.env.chain.fork(EthereumHardfork::Paris);
+
+        info!(target: "reth::cli", from = self.from, to, ?output, "Exporting era1 files");
+
+        let mut start = self.from;
+        let mut merge_block = None;
+        'files: while start <= to {
+            let epoch = start / MAX_BLOCKS_PER_ERA1 as u64;
+            let tmp_path = output.join(format!(".{network}-{epoch:05}.era1.tmp"));
+            let mut writer = Era1Writer::new(BufWriter::new(File::create(&tmp_path)?), start)?;
+            let mut reached_merge = false;
+
+            while !writer.is_full() && writer.next_block_number() <= to {
+                let number = writer.next_block_number();
+                let header = provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                let total_difficulty = provider
+                    .header_td_by_number(number)?
+                    .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+                if self.env.chain.is_paris_active_at_block(number) == Some(true) ||
+                    paris.active_at_ttd(total_difficulty, header.difficulty)
+                {
+                    reached_merge = true;
+                    break
+                }
+
+                let block = provider
+                    .block_by_number(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                let receipts = provider
+                    .receipts_by_block(number.into())?
+                    .ok_or_else(|| eyre::eyre!("receipts of block {number} are not available"))?;
+
+                writer.write_block(&BlockTuple {
+                    header: alloy_rlp::encode(&header),
+                    body: alloy_rlp::encode(&block.body),
+                    receipts: alloy_rlp::encode(
+                        receipts
+                            .into_iter()
+                            .map(|receipt| receipt.with_bloom())
+                            .collect::<Vec<_>>(),
+                    ),
+                    total_difficulty,
+                })?;
+            }
+
+            let end = writer.next_block_number();
+            if end == start {
+                // nothing to write, the merge coincides with the epoch boundary
+                reth_fs_util::remove_file(&tmp_path)?;
+                merge_block = Some(end);
+                break 'files
+            }
+
+            let (accumulator, _) = writer.finish()?;
+            let path = output.join(era1_file_name(&network, epoch, accumulator));
+            reth_fs_util::rename(&tmp_path, &path)?;
+            info!(target: "reth::cli", ?path, from = start, to = end - 1, "Wrote era1 file");
+
+            if reached_merge {
+                info!(target: "reth::cli", block = end, "Reached the merge, stopping era1 export");
+                merge_block = Some(end);
+                break
+            }
+            start = end;
+        }
+
+        if let (Some(url), Some(merge_block)) = (&self.beacon_api, merge_block) {
+            let beacon_api = BeaconApi::new(url);
+            let genesis_time = beacon_api.genesis_time().await?;
+            let slot_of = |number: BlockNumber| -> eyre::Result<u64> {
+                let header = provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                Ok(header.timestamp.saturating_sub(genesis_time) / SECONDS_PER_SLOT)
+            };
+
+            // era `N` holds the blocks of the slots before `N * 8192`, only eras whose last slot
+            // is finalized and not after the last exported block are complete
+            let first_era = slot_of(merge_block)? / SLOTS_PER_HISTORICAL_ROOT + 1;
+            let last_slot = beacon_api.finalized_slot().await?.min(slot_of(to)? + 1);
+            let last_era = last_slot / SLOTS_PER_HISTORICAL_ROOT;
+
+            info!(target: "reth::cli", first_era, last_era, "Exporting era files");
+            for era in first_era..=last_era {
+                let tmp_path = output.join(format!(".{network}-{era:05}.era.tmp"));
+                let mut writer = EraWriter::new(BufWriter::new(File::create(&tmp_path)?), era)?;
+
+                for slot in era_block_slots(era) {
+                    let Some(block) = beacon_api.block(slot).await? else { continue };
+                    if let Some((number, hash)) =
+                        SignedBeaconBlockView::new(&block)?.execution_block()?
+                    {
+                        let local = provider
+                            .block_hash(number)?
+                            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                        if local != hash {
+                            eyre::bail!(
+                                "execution payload {hash} of slot {slot} does not match local block {number} ({local})"
+                            )
+                        }
+                    }
+                    writer.write_block(slot, &block)?;
+                }
+
+                let state = beacon_api.state(era * SLOTS_PER_HISTORICAL_ROOT).await?;
+                let (root, _) = writer.finish(&state)?;
+                let path = output.join(era_file_name(&network, era, root));
+                reth_fs_util::rename(&tmp_path, &path)?;
+                info!(target: "reth::cli", ?path, era, "Wrote era file");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Client of the beacon node API the post-merge era files are sourced from.
+#[derive(Debug)]
+struct BeaconApi {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl BeaconApi {
+    fn new(url: &str) -> Self {
+        Self { client: reqwest::Client::new(), url: url.trim_end_matches('/').to_string() }
+    }
+
+    /// Returns the genesis time of the beacon chain.
+    async fn genesis_time(&self) -> eyre::Result<u64> {
+        self.json_u64("/eth/v1/beacon/genesis", "/data/genesis_time").await
+    }
+
+    /// Returns the slot of the finalized block.
+    async fn finalized_slot(&self) -> eyre::Result<u64> {
+        self.json_u64("/eth/v1/beacon/headers/finalized", "/data/header/message/slot").await
+    }
+
+    /// Returns the SSZ encoded signed block of the slot, or `None` if the slot is empty.
+    async fn block(&self, slot: u64) -> eyre::Result<Option<Vec<u8>>> {
+        self.ssz(&format!("/eth/v2/beacon/blocks/{slot}")).await
+    }
+
+    /// Returns the SSZ encoded state at the slot.
+    async fn state(&self, slot: u64) -> eyre::Result<Vec<u8>> {
+        self.ssz(&format!("/eth/v2/debug/beacon/states/{slot}"))
+            .await?
+            .ok_or_else(|| eyre::eyre!("beacon state at slot {slot} is not available"))
+    }
+
+    async fn json_u64(&self, path: &str, pointer: &str) -> eyre::Result<u64> {
+        let response: serde_json::Value = self
+            .client
+            .get(format!("{}{path}", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        response
+            .pointer(pointer)
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| eyre::eyre!("missing {pointer} in response of {path}"))
+    }
+
+    async fn ssz(&self, path: &str) -> eyre::Result<Option<Vec<u8>>> {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.url))
+            .header(ACCEPT, "application/octet-stream")
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None)
+        }
+        Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_era_args() {
+        let args: Command<EthereumChainSpecParser> =
+            Command::parse_from(["reth", "--from", "8192", "--to", "20000", "--output", "era"]);
+        assert_eq!(args.from, 8192);
+        assert_eq!(args.to, Some(20000));
+        assert_eq!(args.output, Some(PathBuf::from("era")));
+        assert_eq!(args.beacon_api, None);
+
+        let args: Command<EthereumChainSpecParser> =
+            Command::parse_from(["reth", "--beacon-api", "http://localhost:5052"]);
+        assert_eq!(args.beacon_api.as_deref(), Some("http://localhost:5052"));
+    }
+}
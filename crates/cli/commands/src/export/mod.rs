@@ -0,0 +1,35 @@
+//! `reth export` command.
+
+use crate::common::CliNodeTypes;
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+
+mod era;
+mod parquet;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Export history into era1 and era archive files.
+    Era(era::Command<C>),
+    /// Export transactions, receipts and logs into partitioned Parquet datasets.
+    Parquet(parquet::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `export` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Era(command) => command.execute::<N>().await,
+            Subcommands::Parquet(command) => command.execute::<N>().await,
+        }
+    }
+}
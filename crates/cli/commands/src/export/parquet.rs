@@ -0,0 +1,599 @@
+//! `reth export parquet` command.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::Transaction as _;
+use alloy_primitives::BlockNumber;
+use clap::{Parser, ValueEnum};
+use parquet::{
+    basic::{Compression, Repetition, Type as PhysicalType, ZstdLevel},
+    data_type::{
+        BoolType, ByteArray, ByteArrayType, FixedLenByteArray, FixedLenByteArrayType, Int32Type,
+        Int64Type,
+    },
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::{parser::parse_message_type, types::Type},
+};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::{
+    BlockNumReader, BlockReader, DBProvider, HeaderProvider, ProviderError, ReceiptProvider,
+    TransactionsProvider,
+};
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Default number of blocks per exported parquet file.
+const DEFAULT_PARTITION_SIZE: u64 = 100_000;
+
+/// Maximum number of rows buffered in memory before a row group is flushed to the file.
+const ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// Schema of the `transactions` table.
+///
+/// `value` is a big-endian 256-bit unsigned integer, fees are in wei.
+const TRANSACTIONS_SCHEMA: &str = "
+message transactions {
+    required int64 block_number (UINT_64);
+    required int64 block_timestamp (UINT_64);
+    required int64 transaction_index (UINT_64);
+    required fixed_len_byte_array(32) transaction_hash;
+    required int32 transaction_type (UINT_8);
+    required fixed_len_byte_array(20) from;
+    optional fixed_len_byte_array(20) to;
+    required int64 nonce (UINT_64);
+    required fixed_len_byte_array(32) value;
+    required int64 gas_limit (UINT_64);
+    required fixed_len_byte_array(16) max_fee_per_gas (DECIMAL(38,0));
+    optional fixed_len_byte_array(16) max_priority_fee_per_gas (DECIMAL(38,0));
+    required binary input;
+}";
+
+/// Schema of the `receipts` table.
+const RECEIPTS_SCHEMA: &str = "
+message receipts {
+    required int64 block_number (UINT_64);
+    required int64 block_timestamp (UINT_64);
+    required int64 transaction_index (UINT_64);
+    required fixed_len_byte_array(32) transaction_hash;
+    required int32 transaction_type (UINT_8);
+    required boolean success;
+    required int64 cumulative_gas_used (UINT_64);
+    required int64 gas_used (UINT_64);
+    required int64 log_count (UINT_64);
+}";
+
+/// Schema of the `logs` table.
+///
+/// `log_index` is the index of the log within its block.
+const LOGS_SCHEMA: &str = "
+message logs {
+    required int64 block_number (UINT_64);
+    required int64 block_timestamp (UINT_64);
+    required int64 transaction_index (UINT_64);
+    required fixed_len_byte_array(32) transaction_hash;
+    required int64 log_index (UINT_64);
+    required fixed_len_byte_array(20) address;
+    optional fixed_len_byte_array(32) topic0;
+    optional fixed_len_byte_array(32) topic1;
+    optional fixed_len_byte_array(32) topic2;
+    optional fixed_len_byte_array(32) topic3;
+    required binary data;
+}";
+
+/// `reth export parquet` command
+///
+/// Streams transactions, receipts and logs out of the static files into Parquet datasets, one
+/// directory per table, partitioned into `<TABLE>/<TABLE>-<FROM>-<TO>.parquet` files of
+/// `--partition-size` blocks each. Files are ZSTD compressed, the schema of every table is listed
+/// in the `--tables` help.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The tables to export.
+    #[arg(long, value_delimiter = ',', default_value = "transactions,receipts,logs")]
+    tables: Vec<ParquetTable>,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the last block available in the database.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// The number of blocks per exported file.
+    #[arg(long, value_name = "BLOCKS", default_value_t = DEFAULT_PARTITION_SIZE, value_parser = clap::value_parser!(u64).range(1..))]
+    partition_size: u64,
+
+    /// The directory the datasets are written to.
+    ///
+    /// Defaults to `<DATADIR>/parquet`.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    output: Option<PathBuf>,
+}
+
+/// A table that can be exported to Parquet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ParquetTable {
+    /// One row per transaction: `block_number`, `block_timestamp`, `transaction_index`,
+    /// `transaction_hash`, `transaction_type`, `from`, `to`, `nonce`, `value` (big-endian
+    /// uint256), `gas_limit`, `max_fee_per_gas`, `max_priority_fee_per_gas`, `input`.
+    Transactions,
+    /// One row per receipt: `block_number`, `block_timestamp`, `transaction_index`,
+    /// `transaction_hash`, `transaction_type`, `success`, `cumulative_gas_used`, `gas_used`,
+    /// `log_count`.
+    Receipts,
+    /// One row per log: `block_number`, `block_timestamp`, `transaction_index`,
+    /// `transaction_hash`, `log_index` (within the block), `address`, `topic0`-`topic3`, `data`.
+    Logs,
+}
+
+impl ParquetTable {
+    /// Returns the name of the table, used for its directory and file names.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Transactions => "transactions",
+            Self::Receipts => "receipts",
+            Self::Logs => "logs",
+        }
+    }
+
+    /// Returns the Parquet message type of the table.
+    const fn schema(&self) -> &'static str {
+        match self {
+            Self::Transactions => TRANSACTIONS_SCHEMA,
+            Self::Receipts => RECEIPTS_SCHEMA,
+            Self::Logs => LOGS_SCHEMA,
+        }
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `export parquet` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let mut tables = self.tables.clone();
+        tables.sort_by_key(|table| *table as u8);
+        tables.dedup();
+
+        let Environment { provider_factory, data_dir, .. } =
+            self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+
+        let output = self.output.unwrap_or_else(|| data_dir.data_dir().join("parquet"));
+        for table in &tables {
+            reth_fs_util::create_dir_all(output.join(table.name()))?;
+        }
+
+        let last_block = provider.last_block_number()?;
+        let to = self.to.map_or(last_block, |to| to.min(last_block));
+        if self.from > to {
+            eyre::bail!("--from {} is past the last exported block {to}", self.from)
+        }
+
+        info!(target: "reth::cli", from = self.from, to, ?tables, ?output, "Exporting parquet datasets");
+
+        let mut start = self.from;
+        while start <= to {
+            let end = start.saturating_add(self.partition_size - 1).min(to);
+            let mut files = tables
+                .iter()
+                .map(|table| ParquetFile::create(*table, &output, start, end))
+                .collect::<eyre::Result<Vec<_>>>()?;
+
+            for number in start..=end {
+                let header = provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                let indices = provider
+                    .block_body_indices(number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+                let tx_range = indices.tx_num_range();
+
+                let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+                let receipts = if tables.iter().any(|table| *table != ParquetTable::Transactions) {
+                    let receipts = provider.receipts_by_tx_range(tx_range.clone())?;
+                    if receipts.len() != transactions.len() {
+                        eyre::bail!("receipts of block {number} are not available")
+                    }
+                    receipts
+                } else {
+                    Vec::new()
+                };
+                let senders = if tables.contains(&ParquetTable::Transactions) {
+                    let senders = provider.senders_by_tx_range(tx_range)?;
+                    if senders.len() == transactions.len() {
+                        senders
+                    } else {
+                        transactions
+                            .iter()
+                            .map(|tx| tx.recover_signer().ok_or(ProviderError::SenderRecoveryError))
+                            .collect::<Result<_, _>>()?
+                    }
+                } else {
+                    Vec::new()
+                };
+
+                let mut log_index = 0u64;
+                let mut previous_cumulative_gas_used = 0;
+                for (index, tx) in transactions.iter().enumerate() {
+                    let hash = tx.hash();
+                    let common = |row: &mut RowWriter<'_>| {
+                        row.u64(number)
+                            .u64(header.timestamp)
+                            .u64(index as u64)
+                            .fixed(hash.as_slice());
+                    };
+
+                    for file in &mut files {
+                        match file.table {
+                            ParquetTable::Transactions => {
+                                let tx = &tx.transaction;
+                                let mut row = file.row();
+                                common(&mut row);
+                                row.u8(tx.tx_type() as u8)
+                                    .fixed(senders[index].as_slice())
+                                    .optional_fixed(tx.to().as_ref().map(|to| to.as_slice()))
+                                    .u64(tx.nonce())
+                                    .fixed(&tx.value().to_be_bytes::<32>())
+                                    .u64(tx.gas_limit())
+                                    .fixed(&tx.max_fee_per_gas().to_be_bytes())
+                                    .optional_fixed(
+                                        tx.max_priority_fee_per_gas()
+                                            .map(|fee| fee.to_be_bytes())
+                                            .as_ref()
+                                            .map(|fee| fee.as_slice()),
+                                    )
+                                    .bytes(tx.input());
+                                file.end_row()?;
+                            }
+                            ParquetTable::Receipts => {
+                                let receipt = &receipts[index];
+                                let mut row = file.row();
+                                common(&mut row);
+                                row.u8(receipt.tx_type as u8)
+                                    .bool(receipt.success)
+                                    .u64(receipt.cumulative_gas_used)
+                                    .u64(receipt.cumulative_gas_used - previous_cumulative_gas_used)
+                                    .u64(receipt.logs.len() as u64);
+                                file.end_row()?;
+                            }
+                            ParquetTable::Logs => {
+                                for (offset, log) in receipts[index].logs.iter().enumerate() {
+                                    let topics = log.topics();
+                                    let mut row = file.row();
+                                    common(&mut row);
+                                    row.u64(log_index + offset as u64)
+                                        .fixed(log.address.as_slice());
+                                    for topic in 0..4 {
+                                        row.optional_fixed(
+                                            topics.get(topic).map(|topic| topic.as_slice()),
+                                        );
+                                    }
+                                    row.bytes(&log.data.data);
+                                    file.end_row()?;
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(receipt) = receipts.get(index) {
+                        log_index += receipt.logs.len() as u64;
+                        previous_cumulative_gas_used = receipt.cumulative_gas_used;
+                    }
+                }
+            }
+
+            for file in files {
+                let (path, rows) = file.finish()?;
+                info!(target: "reth::cli", ?path, from = start, to = end, rows, "Wrote parquet file");
+            }
+
+            start = end + 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Buffered values of a single column of the current row group.
+#[derive(Debug)]
+enum Column {
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Bool(Vec<bool>),
+    Binary(Vec<ByteArray>),
+    Fixed { values: Vec<FixedLenByteArray>, def_levels: Option<Vec<i16>> },
+}
+
+impl Column {
+    /// Creates an empty column for the given leaf of a schema.
+    fn new(field: &Type) -> eyre::Result<Self> {
+        let optional = field.get_basic_info().repetition() == Repetition::OPTIONAL;
+        Ok(match field.get_physical_type() {
+            PhysicalType::INT32 => Self::Int32(Vec::new()),
+            PhysicalType::INT64 => Self::Int64(Vec::new()),
+            PhysicalType::BOOLEAN => Self::Bool(Vec::new()),
+            PhysicalType::BYTE_ARRAY => Self::Binary(Vec::new()),
+            PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+                Self::Fixed { values: Vec::new(), def_levels: optional.then(Vec::new) }
+            }
+            other => eyre::bail!("unsupported parquet column type {other}"),
+        })
+    }
+}
+
+/// Appends the values of a single row to the columns of a [`ParquetFile`], in schema order.
+#[derive(Debug)]
+struct RowWriter<'a> {
+    columns: &'a mut [Column],
+    next: usize,
+}
+
+impl RowWriter<'_> {
+    fn column(&mut self) -> &mut Column {
+        self.next += 1;
+        &mut self.columns[self.next - 1]
+    }
+
+    fn u8(&mut self, value: u8) -> &mut Self {
+        match self.column() {
+            Column::Int32(values) => values.push(value as i32),
+            column => unreachable!("expected int32 column, got {column:?}"),
+        }
+        self
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        match self.column() {
+            Column::Int64(values) => values.push(value as i64),
+            column => unreachable!("expected int64 column, got {column:?}"),
+        }
+        self
+    }
+
+    fn bool(&mut self, value: bool) -> &mut Self {
+        match self.column() {
+            Column::Bool(values) => values.push(value),
+            column => unreachable!("expected boolean column, got {column:?}"),
+        }
+        self
+    }
+
+    fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        match self.column() {
+            Column::Binary(values) => values.push(value.to_vec().into()),
+            column => unreachable!("expected binary column, got {column:?}"),
+        }
+        self
+    }
+
+    fn fixed(&mut self, value: &[u8]) -> &mut Self {
+        self.optional_fixed(Some(value))
+    }
+
+    fn optional_fixed(&mut self, value: Option<&[u8]>) -> &mut Self {
+        match self.column() {
+            Column::Fixed { values, def_levels } => {
+                if let Some(def_levels) = def_levels {
+                    def_levels.push(value.is_some() as i16);
+                }
+                if let Some(value) = value {
+                    values.push(value.to_vec().into());
+                }
+            }
+            column => unreachable!("expected fixed length column, got {column:?}"),
+        }
+        self
+    }
+}
+
+/// A Parquet file of a single table that is being written.
+///
+/// Rows are buffered per column and flushed as a row group every [`ROW_GROUP_SIZE`] rows. The
+/// file is written to a temporary path and only moved into place once it's complete.
+struct ParquetFile {
+    table: ParquetTable,
+    writer: SerializedFileWriter<BufWriter<File>>,
+    columns: Vec<Column>,
+    buffered_rows: usize,
+    rows: usize,
+    tmp_path: PathBuf,
+    path: PathBuf,
+}
+
+impl std::fmt::Debug for ParquetFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetFile")
+            .field("table", &self.table)
+            .field("rows", &self.rows)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ParquetFile {
+    /// Creates the file of `table` covering blocks `start..=end` in the `output` directory.
+    fn create(
+        table: ParquetTable,
+        output: &Path,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> eyre::Result<Self> {
+        let schema = Arc::new(parse_message_type(table.schema())?);
+        let columns =
+            schema.get_fields().iter().map(|field| Column::new(field)).collect::<Result<_, _>>()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+
+        let dir = output.join(table.name());
+        let path = dir.join(format!("{}-{start:010}-{end:010}.parquet", table.name()));
+        let tmp_path = dir.join(format!(".{}-{start:010}-{end:010}.parquet.tmp", table.name()));
+        let writer = SerializedFileWriter::new(
+            BufWriter::new(File::create(&tmp_path)?),
+            schema,
+            Arc::new(properties),
+        )?;
+
+        Ok(Self { table, writer, columns, buffered_rows: 0, rows: 0, tmp_path, path })
+    }
+
+    /// Returns a writer for the values of the next row.
+    fn row(&mut self) -> RowWriter<'_> {
+        RowWriter { columns: &mut self.columns, next: 0 }
+    }
+
+    /// Completes the current row, flushing the row group if it's full.
+    fn end_row(&mut self) -> eyre::Result<()> {
+        self.buffered_rows += 1;
+        self.rows += 1;
+        if self.buffered_rows >= ROW_GROUP_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes all buffered rows as a new row group.
+    fn flush(&mut self) -> eyre::Result<()> {
+        if self.buffered_rows == 0 {
+            return Ok(())
+        }
+
+        let mut row_group = self.writer.next_row_group()?;
+        for column in &mut self.columns {
+            let mut writer =
+                row_group.next_column()?.ok_or_else(|| eyre::eyre!("missing parquet column"))?;
+            match column {
+                Column::Int32(values) => {
+                    writer.typed::<Int32Type>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                Column::Int64(values) => {
+                    writer.typed::<Int64Type>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                Column::Bool(values) => {
+                    writer.typed::<BoolType>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                Column::Binary(values) => {
+                    writer.typed::<ByteArrayType>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                Column::Fixed { values, def_levels } => {
+                    writer.typed::<FixedLenByteArrayType>().write_batch(
+                        values,
+                        def_levels.as_deref(),
+                        None,
+                    )?;
+                    values.clear();
+                    if let Some(def_levels) = def_levels {
+                        def_levels.clear();
+                    }
+                }
+            }
+            writer.close()?;
+        }
+        row_group.close()?;
+        self.buffered_rows = 0;
+
+        Ok(())
+    }
+
+    /// Flushes the remaining rows and moves the file into place, returning its path and the
+    /// number of rows written.
+    fn finish(mut self) -> eyre::Result<(PathBuf, usize)> {
+        self.flush()?;
+        self.writer.close()?;
+        reth_fs_util::rename(&self.tmp_path, &self.path)?;
+        Ok((self.path, self.rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_parquet_args() {
+        let args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "--tables",
+            "receipts,logs",
+            "--from",
+            "100",
+            "--to",
+            "200",
+            "--partition-size",
+            "50",
+        ]);
+        assert_eq!(args.tables, vec![ParquetTable::Receipts, ParquetTable::Logs]);
+        assert_eq!(args.from, 100);
+        assert_eq!(args.to, Some(200));
+        assert_eq!(args.partition_size, 50);
+
+        let args: Command<EthereumChainSpecParser> = Command::parse_from(["reth"]);
+        assert_eq!(
+            args.tables,
+            vec![ParquetTable::Transactions, ParquetTable::Receipts, ParquetTable::Logs]
+        );
+        assert_eq!(args.partition_size, DEFAULT_PARTITION_SIZE);
+    }
+
+    #[test]
+    fn table_schemas_parse() {
+        for table in ParquetTable::value_variants() {
+            let schema = parse_message_type(table.schema()).unwrap();
+            assert_eq!(schema.name(), table.name());
+            for field in schema.get_fields() {
+                Column::new(field).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        use parquet::{
+            file::reader::{FileReader, SerializedFileReader},
+            record::Field,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("logs")).unwrap();
+
+        let mut file = ParquetFile::create(ParquetTable::Logs, dir.path(), 10, 19).unwrap();
+        for log_index in 0..2u64 {
+            let mut row = file.row();
+            row.u64(10).u64(1_000).u64(0).fixed(&[1; 32]).u64(log_index).fixed(&[2; 20]);
+            row.optional_fixed(Some(&[3; 32]));
+            for _ in 0..3 {
+                row.optional_fixed(None);
+            }
+            row.bytes(&[4, 5]);
+            file.end_row().unwrap();
+        }
+        let (path, rows) = file.finish().unwrap();
+        assert_eq!(rows, 2);
+        assert_eq!(path, dir.path().join("logs/logs-0000000010-0000000019.parquet"));
+
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+        let rows = reader.get_row_iter(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        let fields = rows[1].get_column_iter().collect::<Vec<_>>();
+        assert_eq!(fields[0], (&"block_number".to_string(), &Field::ULong(10)));
+        assert_eq!(fields[4], (&"log_index".to_string(), &Field::ULong(1)));
+        assert!(matches!(fields[6].1, Field::Bytes(topic) if topic.data() == [3; 32]));
+        assert_eq!(fields[7].1, &Field::Null);
+        assert!(matches!(fields[10].1, Field::Bytes(data) if data.data() == [4, 5]));
+    }
+}
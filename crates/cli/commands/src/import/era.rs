@@ -0,0 +1,588 @@
+//! `reth import era` command.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::Header;
+use alloy_eips::{eip4844::kzg_to_versioned_hash, eip7685::Requests};
+use alloy_primitives::{Bytes, U256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1,
+    ExecutionPayloadV2, ExecutionPayloadV3,
+};
+use clap::Parser;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::Consensus;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_era::{era_file_name, Era1Reader, EraReader, SignedBeaconBlockView};
+use reth_payload_validator::ExecutionPayloadValidator;
+use reth_primitives::{BlockBody, SealedHeader, StaticFileSegment};
+use reth_provider::{
+    providers::StaticFileWriter, writer::UnifiedStorageWriter, BlockWriter, DBProvider,
+    DatabaseProviderFactory, HeaderProvider, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileProviderFactory,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use ssz::Decode;
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// `reth import era` command
+///
+/// Validates era1 and era files and writes their headers and bodies directly into static files and
+/// the database, advancing the checkpoints of the headers and bodies stages. Receipts are not
+/// imported, they are recomputed by the execution stage on the next `reth node` run.
+///
+/// Era1 files are verified against their accumulator. Era files carry no accumulator over the
+/// execution blocks, their blocks are verified by recomputing the block hashes of the execution
+/// payloads, which have to chain up to the database head. Nothing of a file is written before the
+/// whole file was verified.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The directory containing the era1 and era files.
+    #[arg(long, value_name = "PATH")]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `import era` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let files = history_files(&self.path)?;
+        if files.is_empty() {
+            eyre::bail!("no era1 or era files found in {}", self.path.display())
+        }
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let consensus = EthBeaconConsensus::new(self.env.chain.clone());
+        let validator = ExecutionPayloadValidator::new(self.env.chain.clone());
+
+        let provider = provider_factory.provider()?;
+        let headers_checkpoint =
+            provider.get_stage_checkpoint(StageId::Headers)?.unwrap_or_default().block_number;
+        let bodies_checkpoint =
+            provider.get_stage_checkpoint(StageId::Bodies)?.unwrap_or_default().block_number;
+        if headers_checkpoint != bodies_checkpoint {
+            eyre::bail!(
+                "headers ({headers_checkpoint}) and bodies ({bodies_checkpoint}) stages are at different heights, sync them before importing"
+            )
+        }
+        let mut parent = provider
+            .sealed_header(headers_checkpoint)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(headers_checkpoint.into()))?;
+        let mut td = provider
+            .header_td_by_number(headers_checkpoint)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(headers_checkpoint))?;
+        drop(provider);
+
+        info!(target: "reth::cli", files = files.len(), head = parent.number, "Importing history files");
+
+        for path in files {
+            let provider_rw = provider_factory.database_provider_rw()?;
+            let imported =
+                import_file(&provider_rw, &consensus, &validator, &path, &mut parent, &mut td)?;
+            if imported == 0 {
+                info!(target: "reth::cli", ?path, "Skipping history file, blocks already imported");
+                continue
+            }
+
+            let checkpoint = StageCheckpoint::new(parent.number);
+            provider_rw.save_stage_checkpoint(StageId::Headers, checkpoint)?;
+            provider_rw.save_stage_checkpoint(StageId::Bodies, checkpoint)?;
+            UnifiedStorageWriter::commit(provider_rw)?;
+
+            info!(target: "reth::cli", ?path, blocks = imported, head = parent.number, "Imported history file");
+        }
+
+        info!(target: "reth::cli", head = parent.number, "History import finished, run `reth node` to execute the imported blocks");
+
+        Ok(())
+    }
+}
+
+/// Returns the era1 files ordered by name and therefore by epoch, followed by the era files
+/// ordered by era.
+fn history_files(dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut era1_files = Vec::new();
+    let mut era_files = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("era1") => era1_files.push(path),
+            Some("era") => era_files.push(path),
+            _ => {}
+        }
+    }
+    era1_files.sort();
+    era_files.sort();
+    era1_files.extend(era_files);
+    Ok(era1_files)
+}
+
+/// Validates the blocks of an era1 or era file on top of `parent` and writes their headers and
+/// bodies.
+///
+/// The file is read twice: the first pass verifies all blocks and the file itself, the second
+/// pass writes the blocks. Blocks at or below `parent` are skipped. Returns the number of written
+/// blocks, `parent` and `td` are updated to the last written block.
+fn import_file<P, ChainSpec>(
+    provider: &P,
+    consensus: &impl Consensus,
+    validator: &ExecutionPayloadValidator<ChainSpec>,
+    path: &Path,
+    parent: &mut SealedHeader,
+    td: &mut U256,
+) -> eyre::Result<u64>
+where
+    P: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory + BlockWriter<Body = BlockBody>,
+    ChainSpec: EthereumHardforks,
+{
+    let mut verified = Vec::new();
+    let mut head = parent.clone();
+    let mut head_td = *td;
+    read_blocks(path, validator, |header, body, total_difficulty| {
+        if header.number <= head.number {
+            return Ok(())
+        }
+        if header.number != head.number + 1 {
+            eyre::bail!(
+                "{} continues at block {}, but the head is {}",
+                path.display(),
+                header.number,
+                head.number
+            )
+        }
+
+        consensus.validate_header(&header)?;
+        consensus.validate_header_against_parent(&header, &head)?;
+
+        head_td += header.difficulty;
+        if let Some(total_difficulty) = total_difficulty {
+            if head_td != total_difficulty {
+                eyre::bail!(
+                    "total difficulty mismatch at block {}: stored {total_difficulty}, computed {head_td}",
+                    header.number
+                )
+            }
+        }
+        consensus.validate_header_with_total_difficulty(&header, head_td)?;
+        consensus.validate_body_against_header(&body, &header)?;
+
+        verified.push(header.hash());
+        head = header;
+        Ok(())
+    })?;
+    if verified.is_empty() {
+        return Ok(0)
+    }
+
+    let static_file_provider = provider.static_file_provider();
+
+    // Transactions are written to static files, which must be at the same height as the database.
+    let mut next_tx_num = provider
+        .tx_ref()
+        .cursor_read::<tables::TransactionBlocks>()?
+        .last()?
+        .map(|(id, _)| id + 1)
+        .unwrap_or_default();
+    let next_static_file_tx_num = static_file_provider
+        .get_highest_static_file_tx(StaticFileSegment::Transactions)
+        .map(|id| id + 1)
+        .unwrap_or_default();
+    if next_static_file_tx_num != next_tx_num {
+        eyre::bail!(
+            "transactions in static files ({next_static_file_tx_num}) and database ({next_tx_num}) are inconsistent"
+        )
+    }
+
+    let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+    let mut transactions_writer =
+        static_file_provider.get_writer(parent.number + 1, StaticFileSegment::Transactions)?;
+    let imported = verified.len() as u64;
+    let mut verified = verified.into_iter();
+
+    read_blocks(path, validator, |header, body, _| {
+        if header.number <= parent.number {
+            return Ok(())
+        }
+        // the blocks were verified by hash, guard against the file changing in between
+        if verified.next() != Some(header.hash()) {
+            eyre::bail!("{} changed while importing", path.display())
+        }
+
+        *td += header.difficulty;
+        headers_writer.append_header(&header, *td, &header.hash())?;
+        provider.tx_ref().put::<tables::HeaderNumbers>(header.hash(), header.number)?;
+
+        transactions_writer.increment_block(header.number)?;
+        for transaction in &body.transactions {
+            transactions_writer.append_transaction(next_tx_num, transaction)?;
+            next_tx_num += 1;
+        }
+        provider.append_block_bodies(std::iter::once((header.number, Some(body))))?;
+
+        *parent = header;
+        Ok(())
+    })?;
+    if verified.next().is_some() {
+        eyre::bail!("{} changed while importing", path.display())
+    }
+
+    Ok(imported)
+}
+
+/// Passes the blocks of an era1 or era file to `f`, together with their total difficulty if the
+/// file records it, and verifies the file once all blocks were read.
+fn read_blocks<ChainSpec: EthereumHardforks>(
+    path: &Path,
+    validator: &ExecutionPayloadValidator<ChainSpec>,
+    mut f: impl FnMut(SealedHeader, BlockBody, Option<U256>) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let file = BufReader::new(File::open(path)?);
+    if path.extension().is_some_and(|ext| ext == "era1") {
+        let mut reader = Era1Reader::new(file)?;
+        while let Some(block) = reader.next_block()? {
+            let header = SealedHeader::seal(Header::decode(&mut block.header.as_slice())?);
+            let body = BlockBody::decode(&mut block.body.as_slice())?;
+            f(header, body, Some(block.total_difficulty))?;
+        }
+        reader.finish()?;
+        return Ok(())
+    }
+
+    let mut reader = EraReader::new(file)?;
+    while let Some(block) = reader.next_block()? {
+        let view = SignedBeaconBlockView::new(&block)?;
+        let Some((payload, sidecar)) = execution_payload(&view)? else { continue };
+        let (header, body) =
+            validator.ensure_well_formed_payload(payload, sidecar)?.split_header_body();
+        f(header, body, None)?;
+    }
+    let footer = reader.finish()?;
+
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    let expected = era_file_name("", footer.era, footer.historical_root);
+    if !name.ends_with(&expected) {
+        eyre::bail!(
+            "{} holds era {} with historical root {}, expected a file name ending in {expected}",
+            path.display(),
+            footer.era,
+            footer.historical_root
+        )
+    }
+    Ok(())
+}
+
+/// Returns the execution payload of a signed beacon block together with the fields of the
+/// beacon block that are required to reconstruct the execution block, or `None` for blocks before
+/// the merge.
+fn execution_payload(
+    view: &SignedBeaconBlockView<'_>,
+) -> eyre::Result<Option<(ExecutionPayload, ExecutionPayloadSidecar)>> {
+    let (Some(payload), Some(version)) =
+        (view.execution_payload()?, view.execution_payload_version()?)
+    else {
+        return Ok(None)
+    };
+    let payload = match version {
+        1 => ExecutionPayloadV1::from_ssz_bytes(payload).map(ExecutionPayload::V1),
+        2 => ExecutionPayloadV2::from_ssz_bytes(payload).map(ExecutionPayload::V2),
+        _ => ExecutionPayloadV3::from_ssz_bytes(payload).map(ExecutionPayload::V3),
+    }
+    .map_err(|err| eyre::eyre!("invalid execution payload: {err:?}"))?;
+
+    // blocks between bellatrix and the merge carry an empty payload
+    if payload.block_hash().is_zero() {
+        return Ok(None)
+    }
+
+    let Some(commitments) = view.blob_kzg_commitments()? else {
+        return Ok(Some((payload, ExecutionPayloadSidecar::none())))
+    };
+    let cancun = CancunPayloadFields {
+        parent_beacon_block_root: view.parent_root()?,
+        versioned_hashes: commitments.map(kzg_to_versioned_hash).collect(),
+    };
+    let sidecar = match view.execution_requests()? {
+        Some(requests) => ExecutionPayloadSidecar::v4(
+            cancun,
+            Requests::new(requests.map(Bytes::copy_from_slice).to_vec()),
+        ),
+        None => ExecutionPayloadSidecar::v3(cancun),
+    };
+    Ok(Some((payload, sidecar)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_primitives::B256;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+    use reth_db_common::init::init_genesis;
+    use reth_era::{
+        era_block_slots, era_historical_root, BlockTuple, Era1Writer, EraWriter,
+        SLOTS_PER_HISTORICAL_ROOT,
+    };
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, create_test_provider_factory_with_chain_spec},
+        BlockNumReader, ProviderFactory,
+    };
+    use ssz::Encode;
+    use std::sync::Arc;
+
+    #[test]
+    fn parse_import_era_args() {
+        let args: Command<EthereumChainSpecParser> = Command::parse_from(["reth", "--path", "era"]);
+        assert_eq!(args.path, PathBuf::from("era"));
+    }
+
+    /// Returns the head of the database and its total difficulty.
+    fn head<N: reth_provider::providers::ProviderNodeTypes>(
+        factory: &ProviderFactory<N>,
+    ) -> (SealedHeader, U256) {
+        let provider = factory.provider().unwrap();
+        let number = provider.last_block_number().unwrap();
+        (
+            provider.sealed_header(number).unwrap().unwrap(),
+            provider.header_td_by_number(number).unwrap().unwrap(),
+        )
+    }
+
+    /// Returns an era1 file of empty pre-merge blocks on top of the mainnet genesis, and the last
+    /// block.
+    fn era1_file(genesis: &SealedHeader, td: U256) -> (Vec<u8>, SealedHeader, U256) {
+        let mut writer = Era1Writer::new(Vec::new(), 0).unwrap();
+        let mut parent = genesis.clone();
+        let mut td = td;
+        for number in 0..=3 {
+            let header = if number == 0 {
+                genesis.clone()
+            } else {
+                SealedHeader::seal(Header {
+                    parent_hash: parent.hash(),
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    transactions_root: EMPTY_ROOT_HASH,
+                    receipts_root: EMPTY_ROOT_HASH,
+                    difficulty: parent.difficulty,
+                    number,
+                    gas_limit: parent.gas_limit,
+                    timestamp: parent.timestamp + 15,
+                    ..Default::default()
+                })
+            };
+            if number > 0 {
+                td += header.difficulty;
+            }
+            writer
+                .write_block(&BlockTuple {
+                    header: alloy_rlp::encode(header.header()),
+                    body: alloy_rlp::encode(BlockBody::default()),
+                    receipts: alloy_rlp::encode(Vec::<u8>::new()),
+                    total_difficulty: td,
+                })
+                .unwrap();
+            parent = header;
+        }
+        let (_, buf) = writer.finish().unwrap();
+        (buf, parent, td)
+    }
+
+    #[test]
+    fn import_era1_file() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+        let (genesis, genesis_td) = head(&factory);
+        let (buf, last, td) = era1_file(&genesis, genesis_td);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mainnet-00000-00000000.era1");
+        std::fs::write(&path, buf).unwrap();
+
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let validator = ExecutionPayloadValidator::new(MAINNET.clone());
+        let (mut head, mut head_td) = (genesis, genesis_td);
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let imported =
+            import_file(&provider_rw, &consensus, &validator, &path, &mut head, &mut head_td)
+                .unwrap();
+        UnifiedStorageWriter::commit(provider_rw).unwrap();
+
+        assert_eq!(imported, 3);
+        assert_eq!(head.hash(), last.hash());
+        assert_eq!(head_td, td);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 3);
+        assert_eq!(provider.sealed_header(3).unwrap(), Some(last.clone()));
+        assert_eq!(provider.block_number(last.hash()).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn tampered_era1_file_is_not_imported() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+        let (genesis, genesis_td) = head(&factory);
+        let (mut buf, _, _) = era1_file(&genesis, genesis_td);
+
+        // the accumulator root precedes the block index of four blocks
+        let block_index_len = 8 + 16 + 4 * 8;
+        let root_end = buf.len() - block_index_len;
+        buf[root_end - 1] ^= 1;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mainnet-00000-00000000.era1");
+        std::fs::write(&path, buf).unwrap();
+
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let validator = ExecutionPayloadValidator::new(MAINNET.clone());
+        let (mut head, mut head_td) = (genesis.clone(), genesis_td);
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let err = import_file(&provider_rw, &consensus, &validator, &path, &mut head, &mut head_td)
+            .unwrap_err();
+        assert!(err.to_string().contains("accumulator mismatch"), "{err}");
+        assert_eq!(head, genesis);
+
+        // nothing was appended to the static files
+        let static_file_provider = provider_rw.static_file_provider();
+        assert_eq!(
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(0)
+        );
+    }
+
+    /// SSZ encoding of a bellatrix `SignedBeaconBlock` holding the given execution payload.
+    fn signed_beacon_block(slot: u64, payload: &ExecutionPayloadV1) -> Vec<u8> {
+        const BODY_FIXED_SIZE: u32 = 384;
+
+        let mut body = vec![0u8; BODY_FIXED_SIZE as usize];
+        // empty operation lists followed by the execution payload
+        for offset in (200..220).step_by(4).chain([380]) {
+            body[offset..offset + 4].copy_from_slice(&BODY_FIXED_SIZE.to_le_bytes());
+        }
+        body.extend_from_slice(&payload.as_ssz_bytes());
+
+        let mut message = vec![0u8; 84];
+        message[..8].copy_from_slice(&slot.to_le_bytes());
+        message[80..84].copy_from_slice(&84u32.to_le_bytes());
+        message.extend_from_slice(&body);
+
+        let mut block = vec![0u8; 100];
+        block[..4].copy_from_slice(&100u32.to_le_bytes());
+        block.extend_from_slice(&message);
+        block
+    }
+
+    fn execution_payload_of(header: &SealedHeader) -> ExecutionPayloadV1 {
+        ExecutionPayloadV1 {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.beneficiary,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            prev_randao: header.mix_hash,
+            block_number: header.number,
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data.clone(),
+            base_fee_per_gas: U256::from(header.base_fee_per_gas.unwrap_or_default()),
+            block_hash: header.hash(),
+            transactions: Vec::new(),
+        }
+    }
+
+    /// SSZ encoding of a `BeaconState` at the given slot, up to the `state_roots`.
+    fn beacon_state(slot: u64) -> Vec<u8> {
+        let mut state = vec![0u8; 176 + 2 * SLOTS_PER_HISTORICAL_ROOT as usize * 32];
+        state[40..48].copy_from_slice(&slot.to_le_bytes());
+        state
+    }
+
+    #[test]
+    fn import_era_file() {
+        let chain_spec: Arc<ChainSpec> =
+            Arc::new(ChainSpecBuilder::mainnet().paris_activated().build());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        let (genesis, genesis_td) = head(&factory);
+
+        // an empty bellatrix payload before the merge, followed by two post-merge blocks
+        let era = 1;
+        let slots = era_block_slots(era);
+        let mut writer = EraWriter::new(Vec::new(), era).unwrap();
+        writer
+            .write_block(
+                slots.start,
+                &signed_beacon_block(
+                    slots.start,
+                    &execution_payload_of(&SealedHeader::new(Header::default(), B256::ZERO)),
+                ),
+            )
+            .unwrap();
+        let mut parent = genesis.clone();
+        for (number, slot) in [(1, slots.start + 1), (2, slots.start + 3)] {
+            let header = SealedHeader::seal(Header {
+                parent_hash: parent.hash(),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                transactions_root: EMPTY_ROOT_HASH,
+                receipts_root: EMPTY_ROOT_HASH,
+                number,
+                gas_limit: parent.gas_limit,
+                timestamp: parent.timestamp + 12,
+                base_fee_per_gas: parent.next_block_base_fee(
+                    chain_spec.base_fee_params_at_timestamp(parent.timestamp + 12),
+                ),
+                mix_hash: B256::with_last_byte(number as u8),
+                ..Default::default()
+            });
+            let payload = execution_payload_of(&header);
+            writer.write_block(slot, &signed_beacon_block(slot, &payload)).unwrap();
+            parent = header;
+        }
+        let (root, buf) = writer.finish(&beacon_state(slots.end)).unwrap();
+        assert_eq!(era_historical_root(era, &beacon_state(slots.end)).unwrap(), root);
+
+        let dir = tempfile::tempdir().unwrap();
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let validator = ExecutionPayloadValidator::new(chain_spec.clone());
+
+        // the file name has to match the contents
+        let path = dir.path().join("mainnet-00001-00000000.era");
+        std::fs::write(&path, &buf).unwrap();
+        let (mut head, mut head_td) = (genesis.clone(), genesis_td);
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let err = import_file(&provider_rw, &consensus, &validator, &path, &mut head, &mut head_td)
+            .unwrap_err();
+        assert!(err.to_string().contains("expected a file name ending in"), "{err}");
+        drop(provider_rw);
+
+        let path = dir.path().join(era_file_name("mainnet", era, root));
+        std::fs::write(&path, &buf).unwrap();
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let imported =
+            import_file(&provider_rw, &consensus, &validator, &path, &mut head, &mut head_td)
+                .unwrap();
+        UnifiedStorageWriter::commit(provider_rw).unwrap();
+
+        assert_eq!(imported, 2);
+        assert_eq!(head, parent);
+        assert_eq!(head_td, genesis_td);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 2);
+        assert_eq!(provider.sealed_header(2).unwrap(), Some(parent.clone()));
+    }
+}
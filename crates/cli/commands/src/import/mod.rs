@@ -1,7 +1,7 @@
 //! Command that initializes the node by importing a chain from a file.
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use alloy_primitives::B256;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
@@ -33,9 +33,15 @@ use std::{path::PathBuf, sync::Arc};
 use tokio::sync::watch;
 use tracing::{debug, error, info};
 
+mod era;
+
 /// Syncs RLP encoded blocks from a file.
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct ImportCommand<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Option<Subcommands<C>>,
+
     #[command(flatten)]
     env: EnvironmentArgs<C>,
 
@@ -51,8 +57,15 @@ pub struct ImportCommand<C: ChainSpecParser> {
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
-    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
-    path: PathBuf,
+    #[arg(value_name = "IMPORT_PATH", required = true, verbatim_doc_comment)]
+    path: Option<PathBuf>,
+}
+
+/// `reth import` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Import history from era1 and era archive files, bypassing the headers and bodies stages.
+    Era(era::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportCommand<C> {
@@ -63,6 +76,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
         E: BlockExecutorProvider,
         F: FnOnce(Arc<N::ChainSpec>) -> E,
     {
+        if let Some(Subcommands::Era(command)) = self.command {
+            return command.execute::<N>().await
+        }
+        let path = self.path.ok_or_else(|| eyre::eyre!("missing import path"))?;
+
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
 
         if self.no_state {
@@ -81,7 +99,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
         info!(target: "reth::cli", "Consensus engine initialized");
 
         // open file
-        let mut reader = ChunkedFileReader::new(&self.path, self.chunk_len).await?;
+        let mut reader = ChunkedFileReader::new(&path, self.chunk_len).await?;
 
         let mut total_decoded_blocks = 0;
         let mut total_decoded_txns = 0;
@@ -207,6 +225,7 @@ where
         // we want to sync all blocks the file client provides or 0 if empty
         .with_max_block(max_block)
         .with_fail_on_unwind(true)
+        .with_state_root_check_interval(config.stages.merkle.state_root_check_interval)
         .add_stages(
             DefaultStages::new(
                 provider_factory.clone(),
@@ -245,4 +264,12 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parse_import_era_subcommand() {
+        let args: ImportCommand<EthereumChainSpecParser> =
+            ImportCommand::parse_from(["reth", "era", "--path", "era"]);
+        assert!(matches!(args.command, Some(Subcommands::Era(_))));
+        assert_eq!(args.path, None);
+    }
 }
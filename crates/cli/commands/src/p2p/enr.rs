@@ -0,0 +1,134 @@
+//! ENR subcommand of P2P Debugging tool.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use clap::Parser;
+use humantime::parse_duration;
+use reth_discv4::proto::{EnrRequest, EnrResponse, Message, NodeEndpoint, Ping, Pong};
+use reth_network::config::rng_secret_key;
+use reth_network_peers::AnyNode;
+use tokio::net::UdpSocket;
+
+/// How long the discovery packets are valid.
+const PACKET_EXPIRATION: Duration = Duration::from_secs(20);
+
+/// Requests the ENR of a node via discv4 and prints its entries.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The node to request the ENR from.
+    node: AnyNode,
+
+    /// How long to wait for the node's response, e.g. `10s`.
+    #[arg(long, value_parser = parse_duration, default_value = "10s", value_name = "DURATION")]
+    timeout: Duration,
+}
+
+impl Command {
+    /// Execute `p2p get-enr` command.
+    pub async fn execute(self) -> eyre::Result<()> {
+        let node_record = self
+            .node
+            .node_record()
+            .ok_or_else(|| eyre::eyre!("failed to parse node {}", self.node))?;
+        let remote_addr = node_record.udp_addr();
+
+        let bind_ip = match remote_addr.ip() {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        let socket = UdpSocket::bind(SocketAddr::new(bind_ip, 0)).await?;
+        let local_addr = socket.local_addr()?;
+        let key = rng_secret_key();
+
+        // nodes only answer ENR requests of bonded nodes, so the ping of the node is answered
+        // before the ENR is requested
+        let ping = Ping {
+            from: NodeEndpoint {
+                address: local_addr.ip(),
+                udp_port: local_addr.port(),
+                tcp_port: 0,
+            },
+            to: node_record.into(),
+            expire: expiration(),
+            enr_sq: None,
+        };
+        let (packet, _) = Message::Ping(ping).encode(&key);
+        socket.send_to(&packet, remote_addr).await?;
+
+        let response = tokio::time::timeout(self.timeout, async {
+            let mut buf = [0u8; 1280];
+            let mut enr_request_hash = None;
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await?;
+                if from != remote_addr {
+                    continue
+                }
+                let packet = match Message::decode(&buf[..len]) {
+                    Ok(packet) if packet.node_id == node_record.id => packet,
+                    _ => continue,
+                };
+                match packet.msg {
+                    Message::Ping(ping) => {
+                        let pong = Pong {
+                            to: ping.from,
+                            echo: packet.hash,
+                            expire: expiration(),
+                            enr_sq: None,
+                        };
+                        let (packet, _) = Message::Pong(pong).encode(&key);
+                        socket.send_to(&packet, remote_addr).await?;
+
+                        if enr_request_hash.is_none() {
+                            let request = EnrRequest { expire: expiration() };
+                            let (packet, hash) = Message::EnrRequest(request).encode(&key);
+                            socket.send_to(&packet, remote_addr).await?;
+                            enr_request_hash = Some(hash);
+                        }
+                    }
+                    Message::EnrResponse(response)
+                        if Some(response.request_hash) == enr_request_hash =>
+                    {
+                        return Ok::<_, eyre::Error>(response)
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .map_err(|_| eyre::eyre!("timed out waiting for the ENR of {}", self.node))??;
+
+        print_enr(&response);
+        Ok(())
+    }
+}
+
+/// Prints the ENR of the response and its decoded entries.
+fn print_enr(response: &EnrResponse) {
+    let enr = &response.enr;
+    println!("{}", enr.to_base64());
+    println!("Sequence: {}", enr.seq());
+    if let Some(ip) = enr.ip4() {
+        println!("IPv4: {ip}");
+    }
+    if let Some(ip) = enr.ip6() {
+        println!("IPv6: {ip}");
+    }
+    if let Some(port) = enr.tcp4().or_else(|| enr.tcp6()) {
+        println!("TCP port: {port}");
+    }
+    if let Some(port) = enr.udp4().or_else(|| enr.udp6()) {
+        println!("UDP port: {port}");
+    }
+    match response.eth_fork_id() {
+        Some(fork_id) => println!("Fork ID: {fork_id:?}"),
+        None => println!("Fork ID: none"),
+    }
+}
+
+/// Returns the expiration timestamp of an outgoing packet.
+fn expiration() -> u64 {
+    (SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + PACKET_EXPIRATION).as_secs()
+}
@@ -16,7 +16,10 @@ use reth_node_core::{
     utils::get_single_header,
 };
 
+mod enr;
+mod peers;
 mod rlpx;
+mod sentry;
 
 /// `reth p2p` command
 #[derive(Debug, Parser)]
@@ -71,11 +74,24 @@ pub enum Subcommands {
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
+    /// Request the ENR of a node via discv4
+    GetEnr(enr::Command),
+    /// Show diagnostics of the connected peers
+    Peers(peers::Command),
+    /// Run the network as a standalone sentry, served over gRPC
+    Sentry(sentry::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
     /// Execute `p2p` command
     pub async fn execute(self) -> eyre::Result<()> {
+        // these only connect to the given node and don't need a running network
+        let command = match self.command {
+            Subcommands::Rlpx(command) => return command.execute(self.chain).await,
+            Subcommands::GetEnr(command) => return command.execute().await,
+            command => command,
+        };
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
@@ -115,7 +131,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let retries = self.retries.max(1);
         let backoff = ConstantBuilder::default().with_max_times(retries);
 
-        match self.command {
+        match command {
             Subcommands::Header { id } => {
                 let header = (move || get_single_header(fetch_client.clone(), id))
                     .retry(backoff)
@@ -155,8 +171,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
-            Subcommands::Rlpx(command) => {
-                command.execute().await?;
+            Subcommands::Rlpx(_) | Subcommands::GetEnr(_) => unreachable!("handled above"),
+            Subcommands::Peers(command) => {
+                command.execute(network).await?;
+            }
+            Subcommands::Sentry(command) => {
+                command.execute(network, self.network.sentry_grpc_socket_addr()).await?;
             }
         }
 
@@ -0,0 +1,111 @@
+//! Peers subcommand of P2P Debugging tool.
+
+use std::time::Duration;
+
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use humantime::parse_duration;
+use reth_network::{PeerDiagnostics, Peers};
+use reth_network_peers::PeerId;
+
+/// Shows the diagnostics of the peers the node connected to.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// How long to run the network before printing the peers, e.g. `30s`.
+    #[arg(long, value_parser = parse_duration, default_value = "30s", value_name = "DURATION")]
+    duration: Duration,
+
+    /// Only show the given peer.
+    #[arg(long, value_name = "PEER_ID")]
+    peer: Option<PeerId>,
+
+    /// Print the diagnostics as JSON, the same as returned by `admin_peerDiagnostics`.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `p2p peers` command.
+    pub async fn execute(self, network: impl Peers) -> eyre::Result<()> {
+        println!("Collecting peers for {}...", humantime::format_duration(self.duration));
+        tokio::time::sleep(self.duration).await;
+
+        let peers = network.peer_diagnostics(self.peer).await?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&peers)?);
+        } else {
+            println!("{}", sessions_table(&peers));
+            println!("{}", disconnects_table(&peers));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a table with the session of every connected peer.
+fn sessions_table(peers: &[PeerDiagnostics]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Peer",
+        "Client",
+        "Address",
+        "Direction",
+        "Capabilities",
+        "Latency",
+        "Best Block",
+        "Connected",
+        "In",
+        "Out",
+    ]);
+
+    for (peer_id, session) in
+        peers.iter().filter_map(|peer| peer.session.as_ref().map(|session| (peer.peer_id, session)))
+    {
+        let latency =
+            session.latency_ms.map(|latency| format!("{latency}ms")).unwrap_or_else(|| "-".into());
+        let best_block = session
+            .best_number
+            .map(|number| number.to_string())
+            .unwrap_or_else(|| session.best_hash.to_string());
+        let direction = if session.inbound { "inbound" } else { "outbound" };
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(peer_id))
+            .add_cell(Cell::new(&session.client_version))
+            .add_cell(Cell::new(session.remote_addr))
+            .add_cell(Cell::new(direction))
+            .add_cell(Cell::new(session.capabilities.join(", ")))
+            .add_cell(Cell::new(latency))
+            .add_cell(Cell::new(best_block))
+            .add_cell(Cell::new(humantime::format_duration(Duration::from_secs(
+                session.connected_secs,
+            ))))
+            .add_cell(Cell::new(human_bytes(session.bytes_in as f64)))
+            .add_cell(Cell::new(human_bytes(session.bytes_out as f64)));
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Returns a table with the disconnect history of every peer.
+fn disconnects_table(peers: &[PeerDiagnostics]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Peer", "Disconnected At", "Reason"]);
+
+    for peer in peers {
+        for disconnect in &peer.disconnects {
+            let at = std::time::UNIX_EPOCH + Duration::from_secs(disconnect.timestamp);
+            let mut row = Row::new();
+            row.add_cell(Cell::new(peer.peer_id))
+                .add_cell(Cell::new(humantime::format_rfc3339_seconds(at)))
+                .add_cell(Cell::new(&disconnect.reason));
+            table.add_row(row);
+        }
+    }
+
+    table
+}
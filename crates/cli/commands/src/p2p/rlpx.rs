@@ -1,13 +1,27 @@
 //! RLPx subcommand of P2P Debugging tool.
 
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::bytes::Bytes;
 use clap::{Parser, Subcommand};
+use futures::{SinkExt, StreamExt};
+use reth_chainspec::{EthChainSpec, Hardforks, Head};
 use reth_ecies::stream::ECIESStream;
-use reth_eth_wire::{HelloMessage, UnauthedP2PStream};
+use reth_eth_wire::{
+    EthNetworkPrimitives, HelloMessage, P2PMessage, P2PMessageID, P2PStream, Status,
+    UnauthedEthStream, UnauthedP2PStream,
+};
 use reth_network::config::rng_secret_key;
 use reth_network_peers::{pk2id, AnyNode};
 use secp256k1::SECP256K1;
 use tokio::net::TcpStream;
 
+/// How long to wait for the pong of a ping.
+const PONG_TIMEOUT: Duration = Duration::from_secs(15);
+
 /// RLPx commands
 #[derive(Parser, Debug)]
 pub struct Command {
@@ -17,24 +31,63 @@ pub struct Command {
 
 impl Command {
     // Execute `p2p rlpx` command.
-    pub async fn execute(self) -> eyre::Result<()> {
+    pub async fn execute<Spec>(self, chain_spec: Arc<Spec>) -> eyre::Result<()>
+    where
+        Spec: EthChainSpec + Hardforks,
+    {
         match self.subcommand {
-            Subcommands::Ping { node } => {
-                let key = rng_secret_key();
-                let node_record = node
-                    .node_record()
-                    .ok_or_else(|| eyre::eyre!("failed to parse node {}", node))?;
-                let outgoing =
-                    TcpStream::connect((node_record.address, node_record.tcp_port)).await?;
-                let ecies_stream = ECIESStream::connect(outgoing, key, node_record.id).await?;
-
-                let peer_id = pk2id(&key.public_key(SECP256K1));
-                let hello = HelloMessage::builder(peer_id).build();
-
-                let (_, their_hello) =
-                    UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
-
-                println!("{:#?}", their_hello);
+            Subcommands::Ping { node, count } => {
+                let (mut p2p_stream, their_hello) = connect(&node).await?;
+                println!("{their_hello:#?}");
+
+                for seq in 1..=count {
+                    let rtt = ping(&mut p2p_stream).await?;
+                    println!("Pong from {node}: seq={seq} time={rtt:?}");
+                }
+            }
+            Subcommands::Handshake { node } => {
+                let (p2p_stream, their_hello) = connect(&node).await?;
+                println!("Client version: {}", their_hello.client_version);
+                println!("Protocol version: {}", their_hello.protocol_version);
+                println!(
+                    "Capabilities: {}",
+                    their_hello
+                        .capabilities
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                println!(
+                    "Shared capabilities: {}",
+                    p2p_stream
+                        .shared_capabilities()
+                        .iter_caps()
+                        .map(|cap| format!("{}/{}", cap.name(), cap.version()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                let eth_version = p2p_stream.shared_capabilities().eth_version()?;
+                println!("Negotiated eth version: {eth_version:?}");
+
+                // identify as a node at genesis, this is only used to exchange the status
+                let genesis = chain_spec.genesis();
+                let head = Head {
+                    hash: chain_spec.genesis_hash(),
+                    number: 0,
+                    timestamp: genesis.timestamp,
+                    difficulty: genesis.difficulty,
+                    total_difficulty: genesis.difficulty,
+                };
+                let mut status = Status::spec_builder(&chain_spec, &head).build();
+                status.set_eth_version(eth_version);
+                println!("Our status: {status:#?}");
+
+                let (_, their_status) = UnauthedEthStream::new(p2p_stream)
+                    .handshake::<EthNetworkPrimitives>(status, chain_spec.fork_filter(head))
+                    .await?;
+                println!("Their status: {their_status:#?}");
             }
         }
         Ok(())
@@ -47,5 +100,61 @@ enum Subcommands {
     Ping {
         /// The node to ping.
         node: AnyNode,
+
+        /// The number of pings to send after the handshake.
+        #[arg(long, short, default_value_t = 1)]
+        count: usize,
+    },
+    /// Perform the `RLPx` and `eth` handshake with a node and print the negotiated capabilities
+    /// and status messages
+    Handshake {
+        /// The node to connect to.
+        node: AnyNode,
     },
 }
+
+/// Connects to the node and performs the `p2p` handshake, returning the node's hello message.
+async fn connect(
+    node: &AnyNode,
+) -> eyre::Result<(P2PStream<ECIESStream<TcpStream>>, HelloMessage)> {
+    let key = rng_secret_key();
+    let node_record =
+        node.node_record().ok_or_else(|| eyre::eyre!("failed to parse node {}", node))?;
+    let outgoing = TcpStream::connect((node_record.address, node_record.tcp_port)).await?;
+    let ecies_stream = ECIESStream::connect(outgoing, key, node_record.id).await?;
+
+    let peer_id = pk2id(&key.public_key(SECP256K1));
+    let hello = HelloMessage::builder(peer_id).build();
+
+    Ok(UnauthedP2PStream::new(ecies_stream).handshake(hello).await?)
+}
+
+/// Sends a `p2p` ping and returns the round trip time until the pong arrived.
+///
+/// The [`P2PStream`] answers pings internally and swallows pongs, so the messages are exchanged on
+/// the underlying ECIES stream.
+async fn ping(p2p_stream: &mut P2PStream<ECIESStream<TcpStream>>) -> eyre::Result<Duration> {
+    let stream = p2p_stream.inner_mut();
+    let sent_at = Instant::now();
+    stream.send(Bytes::from(alloy_rlp::encode(P2PMessage::Ping))).await?;
+
+    tokio::time::timeout(PONG_TIMEOUT, async {
+        while let Some(msg) = stream.next().await {
+            let msg = msg?;
+            match msg.first().copied() {
+                Some(id) if id == P2PMessageID::Pong as u8 => return Ok(sent_at.elapsed()),
+                Some(id) if id == P2PMessageID::Ping as u8 => {
+                    stream.send(Bytes::from(alloy_rlp::encode(P2PMessage::Pong))).await?;
+                }
+                Some(id) if id == P2PMessageID::Disconnect as u8 => {
+                    eyre::bail!("node disconnected while waiting for pong")
+                }
+                // skip all subprotocol messages, e.g. the node's status
+                _ => {}
+            }
+        }
+        eyre::bail!("connection closed while waiting for pong")
+    })
+    .await
+    .map_err(|_| eyre::eyre!("timed out waiting for pong"))?
+}
@@ -0,0 +1,24 @@
+//! Sentry subcommand of P2P Debugging tool.
+
+use std::net::SocketAddr;
+
+use clap::Parser;
+use reth_network::NetworkHandle;
+use reth_network_sentry::SentryService;
+
+/// Runs the network as a standalone sentry.
+///
+/// The network is served over gRPC on `--sentry.grpc.addr` and `--sentry.grpc.port`, execution
+/// nodes download blocks through it with `--sentry.remote`.
+#[derive(Parser, Debug)]
+pub struct Command {}
+
+impl Command {
+    /// Execute `p2p sentry` command.
+    pub async fn execute(self, network: NetworkHandle, addr: SocketAddr) -> eyre::Result<()> {
+        let (addr, server) = SentryService::new(network).await?.bind(addr).await?;
+        println!("Serving the network over gRPC on {addr}");
+        server.await?;
+        Ok(())
+    }
+}
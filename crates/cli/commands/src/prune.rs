@@ -1,17 +1,46 @@
 //! Command that runs pruning without any limits.
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
 use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_prune::PrunerBuilder;
+use reth_db::{
+    mdbx::{tx::Tx, RW},
+    Tables,
+};
+use reth_exex::FinishedExExHeight;
+use reth_provider::{DatabaseProviderFactory, StaticFileProviderFactory};
+use reth_prune::{segments::SegmentSet, Pruner};
 use reth_static_file::StaticFileProducer;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tracing::info;
 
+/// Maximum number of entries deleted per committed prune run, so progress is reported and kept
+/// while pruning large segments.
+const DELETE_LIMIT_PER_RUN: usize = 100_000;
+
 /// Prunes according to the configuration without any limits
 #[derive(Debug, Parser)]
 pub struct PruneCommand<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// The block to prune up to. The configured prune modes are applied relative to it.
+    ///
+    /// Defaults to the highest block that's available in all static file segments.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<BlockNumber>,
+
+    /// Report how many rows and bytes each segment would delete, without modifying the
+    /// database or static files.
+    ///
+    /// Data is not copied to static files first, so only data that's already in static files is
+    /// counted for the static file segments.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
@@ -20,25 +49,167 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
         let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
         let prune_config = config.prune.unwrap_or_default();
 
-        // Copy data from database to static files
-        info!(target: "reth::cli", "Copying data from database to static files...");
-        let static_file_producer =
-            StaticFileProducer::new(provider_factory.clone(), prune_config.segments.clone());
-        let lowest_static_file_height = static_file_producer.lock().copy_to_static_files()?.min();
-        info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+        let lowest_static_file_height = if self.dry_run {
+            provider_factory.static_file_provider().get_highest_static_files().min()
+        } else {
+            // Copy data from database to static files
+            info!(target: "reth::cli", "Copying data from database to static files...");
+            let static_file_producer =
+                StaticFileProducer::new(provider_factory.clone(), prune_config.segments.clone());
+            let lowest_static_file_height =
+                static_file_producer.lock().copy_to_static_files()?.min();
+            info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+            lowest_static_file_height
+        };
 
         // Delete data which has been copied to static files.
-        if let Some(prune_tip) = lowest_static_file_height {
-            info!(target: "reth::cli", ?prune_tip, ?prune_config, "Pruning data from database...");
-            // Run the pruner according to the configuration, and don't enforce any limits on it
-            let mut pruner = PrunerBuilder::new(prune_config)
-                .delete_limit(usize::MAX)
-                .build_with_provider_factory(provider_factory);
-
-            pruner.run(prune_tip)?;
+        let Some(mut prune_tip) = lowest_static_file_height else {
+            info!(target: "reth::cli", "No data in static files, nothing to prune");
+            return Ok(())
+        };
+        if let Some(to) = self.to {
+            eyre::ensure!(
+                to <= prune_tip,
+                "Block {to} is beyond the highest block available in static files {prune_tip}"
+            );
+            prune_tip = to;
+        }
+
+        info!(target: "reth::cli", ?prune_tip, ?prune_config, dry_run = self.dry_run, "Pruning data from database...");
+        let segments = SegmentSet::from_components(
+            provider_factory.static_file_provider(),
+            prune_config.segments,
+        )
+        .into_vec();
+
+        // A dry run prunes all segments within a single transaction that's never committed.
+        let dry_run_provider =
+            if self.dry_run { Some(provider_factory.database_provider_rw()?) } else { None };
+        let delete_limit = if self.dry_run { usize::MAX } else { DELETE_LIMIT_PER_RUN };
+
+        let mut report = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let name = segment.segment();
+            let label = if segment.purpose().is_static_file() {
+                format!("{name} (static files)")
+            } else {
+                name.to_string()
+            };
+            // Run the pruner for each segment separately, so deleted rows and bytes can be
+            // attributed to it.
+            let mut pruner = Pruner::new(
+                vec![segment],
+                0,
+                delete_limit,
+                None,
+                watch::channel(FinishedExExHeight::NoExExs).1,
+            );
+
+            let start = Instant::now();
+            let mut pruned = SegmentReport::default();
+            loop {
+                let mut provider_rw = None;
+                let provider = match &dry_run_provider {
+                    Some(provider) => provider,
+                    None => provider_rw.insert(provider_factory.database_provider_rw()?),
+                };
+                let pages_before = used_pages(provider.tx_ref())?;
+                let output = pruner.run_with_provider(provider, prune_tip)?;
+                let freed_pages = pages_before.saturating_sub(used_pages(provider.tx_ref())?);
+
+                let Some((_, output)) = output.segments.first() else { break };
+                pruned.rows += output.pruned;
+                pruned.pages += freed_pages;
+                if let Some(provider) = provider_rw {
+                    provider.commit()?;
+                }
+
+                let highest_pruned_block = output.checkpoint.and_then(|c| c.block_number);
+                if output.progress.is_finished() || output.pruned == 0 {
+                    break
+                }
+                info!(
+                    target: "reth::cli",
+                    segment = %label,
+                    rows = pruned.rows,
+                    ?highest_pruned_block,
+                    "Pruning segment..."
+                );
+            }
+
+            info!(target: "reth::cli", segment = %label, rows = pruned.rows, elapsed = ?start.elapsed(), "Pruned segment");
+            pruned.elapsed = start.elapsed();
+            report.push((label, pruned));
+        }
+
+        let page_size = provider_factory.db_ref().stat()?.page_size() as usize;
+        println!("{}", report_table(&report, page_size, self.dry_run));
+        if self.dry_run {
+            println!("Dry run, no data was deleted");
+        } else {
             info!(target: "reth::cli", "Pruned data from database");
         }
 
         Ok(())
     }
 }
+
+/// Rows and pages deleted by a prune segment.
+#[derive(Debug, Default)]
+struct SegmentReport {
+    rows: usize,
+    pages: usize,
+    elapsed: Duration,
+}
+
+fn report_table(report: &[(String, SegmentReport)], page_size: usize, dry_run: bool) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    if dry_run {
+        table.set_header(["Segment", "Rows To Delete", "Size To Free"]);
+    } else {
+        table.set_header(["Segment", "Deleted Rows", "Freed Size", "Elapsed"]);
+    }
+
+    for (label, segment) in report {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(label))
+            .add_cell(Cell::new(segment.rows))
+            .add_cell(Cell::new(human_bytes((segment.pages * page_size) as f64)));
+        if !dry_run {
+            row.add_cell(Cell::new(format!("{:?}", segment.elapsed)));
+        }
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Returns the number of pages used by all tables, as seen by the given transaction.
+fn used_pages(tx: &Tx<RW>) -> eyre::Result<usize> {
+    let mut pages = 0;
+    for table in Tables::ALL.iter().map(Tables::name) {
+        let Ok(table_db) = tx.inner.open_db(Some(table)) else { continue };
+        let stats = tx.inner.db_stat(&table_db)?;
+        pages += stats.branch_pages() + stats.leaf_pages() + stats.overflow_pages();
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_prune_args() {
+        let args: PruneCommand<EthereumChainSpecParser> =
+            PruneCommand::parse_from(["reth", "--to", "100", "--dry-run"]);
+        assert_eq!(args.to, Some(100));
+        assert!(args.dry_run);
+
+        let args: PruneCommand<EthereumChainSpecParser> = PruneCommand::parse_from(["reth"]);
+        assert_eq!(args.to, None);
+        assert!(!args.dry_run);
+    }
+}
@@ -18,7 +18,8 @@ pub struct Command<C: ChainSpecParser> {
 /// `reth recover` subcommands
 #[derive(Subcommand, Debug)]
 pub enum Subcommands<C: ChainSpecParser> {
-    /// Recover the node by deleting dangling storage tries.
+    /// Recover the trie tables by deleting dangling storage tries, or by rebuilding them from the
+    /// hashed state if the state root is still incorrect.
     StorageTries(storage_tries::Command<C>),
 }
 
@@ -6,10 +6,14 @@ use reth_cli_runner::CliContext;
 use reth_db::tables;
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRW},
-    transaction::DbTx,
+    transaction::{DbTx, DbTxMut},
 };
-use reth_provider::{BlockNumReader, HeaderProvider, ProviderError};
-use reth_trie::StateRoot;
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
+    TrieWriter,
+};
+use reth_stages::{EntitiesCheckpoint, StageCheckpoint, StageId};
+use reth_trie::{StateRoot, StateRootProgress};
 use reth_trie_db::DatabaseStateRoot;
 use tracing::*;
 
@@ -18,6 +22,11 @@ use tracing::*;
 pub struct Command<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// Rebuild the `AccountsTrie` and `StoragesTrie` tables from the hashed state, even if the
+    /// state root computed from the existing tables is correct.
+    #[arg(long, default_value_t = false)]
+    rebuild: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -28,40 +37,205 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     ) -> eyre::Result<()> {
         let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
 
-        let mut provider = provider_factory.provider_rw()?;
-        let best_block = provider.best_block_number()?;
-        let best_header = provider
-            .sealed_header(best_block)?
-            .ok_or_else(|| ProviderError::HeaderNotFound(best_block.into()))?;
+        let provider = provider_factory.provider_rw()?;
+        recover_tries(&*provider, self.rebuild)?;
+        provider.commit()?;
+        info!(target: "reth::cli", "Finished recovery");
 
-        let mut deleted_tries = 0;
-        let tx_mut = provider.tx_mut();
-        let mut hashed_account_cursor = tx_mut.cursor_read::<tables::HashedAccounts>()?;
-        let mut storage_trie_cursor = tx_mut.cursor_dup_read::<tables::StoragesTrie>()?;
-        let mut entry = storage_trie_cursor.first()?;
+        Ok(())
+    }
+}
+
+/// Recovers the trie tables for the block of the hashed state and sets the Merkle stage
+/// checkpoints to it.
+///
+/// Dangling storage tries are deleted first. If the state root is still incorrect, or `rebuild`
+/// is set, both trie tables are rebuilt from the hashed state, like the Merkle stage does for
+/// large block ranges.
+fn recover_tries<Provider>(provider: &Provider, rebuild: bool) -> eyre::Result<()>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + TrieWriter,
+{
+    // The hashing stages run before the Merkle stage, so after a crash the hashed state can be
+    // ahead of the Merkle stage checkpoint.
+    let block =
+        provider.get_stage_checkpoint(StageId::AccountHashing)?.unwrap_or_default().block_number;
+    let storage_block =
+        provider.get_stage_checkpoint(StageId::StorageHashing)?.unwrap_or_default().block_number;
+    eyre::ensure!(
+        block == storage_block,
+        "Hashed accounts are at block {block}, but hashed storages are at block {storage_block}. \
+         The hashing stages have to reach the same block first."
+    );
+    let header = provider
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
 
+    let tx = provider.tx_ref();
+    if !rebuild {
         info!(target: "reth::cli", "Starting pruning of storage tries");
-        while let Some((hashed_address, _)) = entry {
-            if hashed_account_cursor.seek_exact(hashed_address)?.is_none() {
-                deleted_tries += 1;
-                storage_trie_cursor.delete_current_duplicates()?;
+        let deleted_tries = delete_dangling_storage_tries(tx)?;
+        info!(target: "reth::cli", deleted = deleted_tries, "Finished pruning of storage tries");
+
+        match StateRoot::from_tx(tx).root() {
+            Ok(state_root) if state_root == header.state_root => {
+                save_merkle_checkpoints(provider, block)?;
+                return Ok(())
+            }
+            Ok(state_root) => {
+                info!(target: "reth::cli", expected = ?header.state_root, got = ?state_root, "Incorrect state root, rebuilding tries")
             }
+            Err(err) => {
+                info!(target: "reth::cli", %err, "Failed to compute state root, rebuilding tries")
+            }
+        }
+    }
+
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+
+    let total = tx.entries::<tables::HashedAccounts>()? + tx.entries::<tables::HashedStorages>()?;
+    let mut processed = 0;
+    let mut intermediate_state = None;
+    info!(target: "reth::cli", block, total, "Rebuilding tries from hashed state");
+    let state_root = loop {
+        let progress = StateRoot::from_tx(tx)
+            .with_intermediate_state(intermediate_state.take())
+            .root_with_progress()?;
+        match progress {
+            StateRootProgress::Progress(state, hashed_entries_walked, updates) => {
+                provider.write_trie_updates(&updates)?;
+                processed += hashed_entries_walked;
+                intermediate_state = Some(*state);
+                info!(target: "reth::cli", processed, total, "Rebuilding tries");
+            }
+            StateRootProgress::Complete(state_root, _, updates) => {
+                provider.write_trie_updates(&updates)?;
+                break state_root
+            }
+        }
+    };
 
-            entry = storage_trie_cursor.next()?;
+    eyre::ensure!(
+        state_root == header.state_root,
+        "Recovery failed. Incorrect state root. Expected: {:?}. Received: {:?}",
+        header.state_root,
+        state_root
+    );
+    save_merkle_checkpoints(provider, block)?;
+
+    Ok(())
+}
+
+/// Deletes the storage tries of accounts that don't exist in the hashed state.
+fn delete_dangling_storage_tries<TX: DbTxMut + DbTx>(tx: &TX) -> eyre::Result<usize> {
+    let mut deleted_tries = 0;
+    let mut hashed_account_cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+    let mut storage_trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+    let mut entry = storage_trie_cursor.first()?;
+
+    while let Some((hashed_address, _)) = entry {
+        if hashed_account_cursor.seek_exact(hashed_address)?.is_none() {
+            deleted_tries += 1;
+            storage_trie_cursor.delete_current_duplicates()?;
         }
 
-        let state_root = StateRoot::from_tx(tx_mut).root()?;
-        if state_root != best_header.state_root {
-            eyre::bail!(
-                "Recovery failed. Incorrect state root. Expected: {:?}. Received: {:?}",
-                best_header.state_root,
-                state_root
-            );
+        entry = storage_trie_cursor.next()?;
+    }
+
+    Ok(deleted_tries)
+}
+
+/// Sets the Merkle stage checkpoints to the given block and discards any intermediate progress.
+fn save_merkle_checkpoints<Provider>(provider: &Provider, block: u64) -> eyre::Result<()>
+where
+    Provider: DBProvider + StageCheckpointWriter,
+{
+    let tx = provider.tx_ref();
+    let total =
+        (tx.entries::<tables::HashedAccounts>()? + tx.entries::<tables::HashedStorages>()?) as u64;
+    let checkpoint = StageCheckpoint::new(block)
+        .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: total, total });
+
+    provider.save_stage_checkpoint(StageId::MerkleExecute, checkpoint)?;
+    provider.save_stage_checkpoint_progress(StageId::MerkleExecute, Vec::new())?;
+    provider.save_stage_checkpoint(StageId::MerkleUnwind, StageCheckpoint::new(block))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use reth_db_api::cursor::DbCursorRW;
+    use reth_primitives::{Account, StorageEntry};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StaticFileProviderFactory, StaticFileWriter,
+    };
+    use reth_static_file_types::StaticFileSegment;
+    use reth_trie::{BranchNodeCompact, Nibbles, StorageTrieEntry, StoredNibblesSubKey};
+
+    #[test]
+    fn rebuilds_corrupted_tries() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        for i in 0..100u8 {
+            let hashed_address = keccak256(Address::with_last_byte(i));
+            tx.put::<tables::HashedAccounts>(
+                hashed_address,
+                Account { nonce: i as u64, balance: U256::from(i), bytecode_hash: None },
+            )
+            .unwrap();
+            for slot in 0..i % 5 {
+                tx.put::<tables::HashedStorages>(
+                    hashed_address,
+                    StorageEntry { key: keccak256([slot]), value: U256::from(slot + 1) },
+                )
+                .unwrap();
+            }
         }
+        let (state_root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
 
-        provider.commit()?;
-        info!(target: "reth::cli", deleted = deleted_tries, "Finished recovery");
+        let header = Header { state_root, ..Default::default() };
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
 
-        Ok(())
+        // a storage trie of an account that doesn't exist and a corrupted account trie node
+        tx.put::<tables::StoragesTrie>(
+            B256::repeat_byte(0xff),
+            StorageTrieEntry {
+                nibbles: StoredNibblesSubKey(Nibbles::from_nibbles([0x1])),
+                node: BranchNodeCompact::new(0b11, 0, 0, vec![], None),
+            },
+        )
+        .unwrap();
+        let mut cursor = tx.cursor_write::<tables::AccountsTrie>().unwrap();
+        let (nibbles, mut node) = cursor.first().unwrap().unwrap();
+        node.hashes = node.hashes.iter().map(|_| B256::repeat_byte(1)).collect();
+        cursor.upsert(nibbles, node).unwrap();
+        drop(cursor);
+        assert_ne!(StateRoot::from_tx(tx).root().unwrap(), state_root);
+
+        recover_tries(&*provider, false).unwrap();
+        let tx = provider.tx_ref();
+        assert_eq!(StateRoot::from_tx(tx).root().unwrap(), state_root);
+        assert_eq!(tx.get::<tables::StoragesTrie>(B256::repeat_byte(0xff)).unwrap(), None);
+        let checkpoint = provider.get_stage_checkpoint(StageId::MerkleExecute).unwrap().unwrap();
+        assert_eq!(checkpoint.block_number, 0);
+        assert_eq!(
+            provider.get_stage_checkpoint_progress(StageId::MerkleExecute).unwrap(),
+            Some(Vec::new())
+        );
     }
 }
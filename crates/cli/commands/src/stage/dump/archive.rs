@@ -0,0 +1,105 @@
+//! Compressed archives of dumped datadirs.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+use tracing::info;
+
+/// Extension of compressed datadir archives.
+pub(crate) const ARCHIVE_EXTENSION: &str = "tar.zst";
+
+/// Compression level used for datadir archives.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Streams the contents of `datadir` into a zstd compressed tar archive at `archive`.
+///
+/// Every file is removed from `datadir` as soon as it is appended to the archive, so the dumped
+/// datadir and its archive never occupy the disk in full at the same time. The archive is written
+/// to a `.partial` file first and only moved to `archive` once it is complete.
+pub(crate) fn pack_datadir(datadir: &Path, archive: &Path) -> eyre::Result<()> {
+    info!(target: "reth::cli", ?datadir, ?archive, "Packing datadir");
+
+    let mut partial = archive.as_os_str().to_owned();
+    partial.push(".partial");
+    let encoder = zstd::Encoder::new(BufWriter::new(File::create(&partial)?), COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+    append_and_remove(&mut builder, datadir, Path::new("."))?;
+    builder.into_inner()?.finish()?.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+    reth_fs_util::rename(&partial, archive)?;
+
+    info!(target: "reth::cli", ?archive, size = reth_fs_util::metadata(archive)?.len(), "Packed datadir");
+
+    Ok(())
+}
+
+/// Appends the entries of `dir` to the archive under `name`, removing every file once it is
+/// appended.
+fn append_and_remove<W: Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &Path,
+    name: &Path,
+) -> eyre::Result<()> {
+    builder.append_dir(name, dir)?;
+
+    let mut entries = reth_fs_util::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let entry_name = name.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            append_and_remove(builder, &path, &entry_name)?;
+        } else {
+            builder.append_path_with_name(&path, &entry_name)?;
+            reth_fs_util::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Unpacks a datadir archive created by [`pack_datadir`] into `datadir`.
+///
+/// Entries that would be written outside of `datadir` are rejected.
+pub(crate) fn unpack_datadir(archive: &Path, datadir: &Path) -> eyre::Result<()> {
+    info!(target: "reth::cli", ?archive, ?datadir, "Unpacking datadir");
+
+    reth_fs_util::create_dir_all(datadir)?;
+    let decoder = zstd::Decoder::new(BufReader::new(File::open(archive)?))?;
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.unpack_in(datadir)? {
+            eyre::bail!("archive entry {} is outside of the datadir", entry.path()?.display())
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_unpack_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let datadir = dir.path().join("dump");
+        std::fs::create_dir_all(datadir.join("db")).unwrap();
+        std::fs::write(datadir.join("db").join("mdbx.dat"), [1u8; 1024]).unwrap();
+        std::fs::write(datadir.join("blocks.rlp"), b"blocks").unwrap();
+
+        let archive = dir.path().join(format!("dump.{ARCHIVE_EXTENSION}"));
+        pack_datadir(&datadir, &archive).unwrap();
+        // files are removed while packing, only the directories are left
+        assert!(!datadir.join("db").join("mdbx.dat").exists());
+        assert!(!datadir.join("blocks.rlp").exists());
+
+        let unpacked = dir.path().join("unpacked");
+        unpack_datadir(&archive, &unpacked).unwrap();
+        assert_eq!(std::fs::read(unpacked.join("db").join("mdbx.dat")).unwrap(), [1u8; 1024]);
+        assert_eq!(std::fs::read(unpacked.join("blocks.rlp")).unwrap(), b"blocks");
+    }
+}
@@ -14,6 +14,7 @@ use reth_provider::{
     DatabaseProviderFactory, ProviderFactory,
 };
 use reth_stages::{stages::ExecutionStage, Stage, StageCheckpoint, UnwindInput};
+use reth_static_file_types::StaticFileSegment;
 use tracing::info;
 
 pub(crate) async fn dump_execution_stage<N, E>(
@@ -28,7 +29,17 @@ where
     N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>,
     E: BlockExecutorProvider,
 {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) = setup(
+        from,
+        to,
+        &output_datadir,
+        db_tool,
+        &[
+            (StaticFileSegment::Headers, to),
+            (StaticFileSegment::Transactions, to),
+            (StaticFileSegment::Receipts, from),
+        ],
+    )?;
 
     import_tables_with_range(&output_db, db_tool, from, to)?;
 
@@ -21,7 +21,7 @@ pub(crate) async fn dump_hashing_account_stage<N: ProviderNodeTypes<DB = Arc<Dat
     output_datadir: ChainPath<DataDirPath>,
     should_run: bool,
 ) -> Result<()> {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) = setup(from, to, &output_datadir, db_tool, &[])?;
 
     // Import relevant AccountChangeSets
     output_db.update(|tx| {
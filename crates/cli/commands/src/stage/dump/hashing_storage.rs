@@ -20,7 +20,7 @@ pub(crate) async fn dump_hashing_storage_stage<N: ProviderNodeTypes<DB = Arc<Dat
     output_datadir: ChainPath<DataDirPath>,
     should_run: bool,
 ) -> Result<()> {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) = setup(from, to, &output_datadir, db_tool, &[])?;
 
     unwind_and_copy(db_tool, from, tip_block_number, &output_db)?;
 
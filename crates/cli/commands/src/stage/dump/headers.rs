@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use super::{setup, write_chain_file, CHAIN_FILE_NAME};
+use alloy_primitives::BlockNumber;
+use eyre::Result;
+use reth_config::config::{EtlConfig, HeadersConfig};
+use reth_consensus::Consensus;
+use reth_db::{tables, DatabaseEnv};
+use reth_db_api::{database::Database, table::TableImporter};
+use reth_db_common::DbTool;
+use reth_downloaders::{
+    file_client::FileClient, headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+};
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    DatabaseProviderFactory, ProviderFactory,
+};
+use reth_stages::{stages::HeaderStage, ExecInput, Stage, StageCheckpoint, StageExt};
+use reth_static_file_types::StaticFileSegment;
+use tokio::sync::watch;
+use tracing::info;
+
+pub(crate) async fn dump_headers_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+    output_datadir: ChainPath<DataDirPath>,
+    should_run: bool,
+    consensus: Arc<dyn Consensus>,
+) -> Result<()> {
+    let (output_db, _) =
+        setup(from, to, &output_datadir, db_tool, &[(StaticFileSegment::Headers, from)])?;
+
+    // Only the local head is imported, the headers above it are downloaded by the stage.
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::CanonicalHeaders, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from),
+            from,
+        )
+    })??;
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::HeaderTerminalDifficulties, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from),
+            from,
+        )
+    })??;
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::Headers, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from),
+            from,
+        )
+    })??;
+
+    let chain_file = output_datadir.data_dir().join(CHAIN_FILE_NAME);
+    write_chain_file(db_tool, from + 1, to, &chain_file)?;
+
+    if should_run {
+        dry_run(
+            ProviderFactory::<N>::new(
+                Arc::new(output_db),
+                db_tool.chain(),
+                StaticFileProvider::read_write(output_datadir.static_files())?,
+            ),
+            to,
+            from,
+            FileClient::new(chain_file).await?,
+            consensus,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Try to re-execute the stage straight away, downloading the headers from the dumped chain file
+async fn dry_run<N: ProviderNodeTypes>(
+    output_provider_factory: ProviderFactory<N>,
+    to: u64,
+    from: u64,
+    file_client: FileClient,
+    consensus: Arc<dyn Consensus>,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", "Executing stage.");
+
+    let tip = file_client.tip().ok_or_else(|| eyre::eyre!("dumped chain file is empty"))?;
+    let (_tip_tx, tip_rx) = watch::channel(tip);
+
+    let mut stage = HeaderStage::new(
+        output_provider_factory.clone(),
+        ReverseHeadersDownloaderBuilder::new(HeadersConfig::default())
+            .build(Arc::new(file_client), consensus.clone()),
+        tip_rx,
+        consensus,
+        EtlConfig::default(),
+    );
+
+    let provider = output_provider_factory.database_provider_rw()?;
+    let mut input = ExecInput { target: Some(to), checkpoint: Some(StageCheckpoint::new(from)) };
+    loop {
+        StageExt::<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW>::execute_ready(
+            &mut stage, input,
+        )
+        .await?;
+        let output = stage.execute(&provider, input)?;
+        if output.done {
+            break
+        }
+        input.checkpoint = Some(output.checkpoint);
+    }
+
+    info!(target: "reth::cli", "Success.");
+
+    Ok(())
+}
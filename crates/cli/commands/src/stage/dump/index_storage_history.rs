@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use super::setup;
+use alloy_primitives::BlockNumber;
+use eyre::Result;
+use reth_db::{tables, DatabaseEnv};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::BlockNumberAddress,
+    table::TableImporter,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_db_common::DbTool;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    DatabaseProviderFactory, ProviderFactory,
+};
+use reth_stages::{stages::IndexStorageHistoryStage, Stage, StageCheckpoint, UnwindInput};
+use tracing::info;
+
+pub(crate) async fn dump_index_storage_history_stage<
+    N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>,
+>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+    output_datadir: ChainPath<DataDirPath>,
+    should_run: bool,
+) -> Result<()> {
+    let (output_db, tip_block_number) = setup(from, to, &output_datadir, db_tool, &[])?;
+
+    // Import relevant StorageChangeSets
+    output_db.update(|tx| {
+        let source_tx = db_tool.provider_factory.db_ref().tx()?;
+        let mut source_cursor = source_tx.cursor_read::<tables::StorageChangeSets>()?;
+        let range = BlockNumberAddress::range(from..=to);
+        for row in source_cursor.walk_range(range)? {
+            let (key, value) = row?;
+            tx.put::<tables::StorageChangeSets>(key, value)?;
+        }
+        Ok::<(), eyre::Report>(())
+    })??;
+
+    unwind_and_copy(db_tool, from, tip_block_number, &output_db)?;
+
+    if should_run {
+        dry_run(
+            ProviderFactory::<N>::new(
+                Arc::new(output_db),
+                db_tool.chain(),
+                StaticFileProvider::read_write(output_datadir.static_files())?,
+            ),
+            to,
+            from,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Dry-run an unwind to FROM block and copy the necessary table data to the new database.
+fn unwind_and_copy<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    from: u64,
+    tip_block_number: u64,
+    output_db: &DatabaseEnv,
+) -> eyre::Result<()> {
+    let provider = db_tool.provider_factory.database_provider_rw()?;
+    let mut stage = IndexStorageHistoryStage::default();
+
+    stage.unwind(
+        &provider,
+        UnwindInput {
+            unwind_to: from,
+            checkpoint: StageCheckpoint::new(tip_block_number),
+            bad_block: None,
+        },
+    )?;
+    let unwind_inner_tx = provider.into_tx();
+
+    output_db.update(|tx| tx.import_table::<tables::StoragesHistory, _>(&unwind_inner_tx))??;
+
+    Ok(())
+}
+
+/// Try to re-execute the stage straight away
+fn dry_run<N: ProviderNodeTypes>(
+    output_provider_factory: ProviderFactory<N>,
+    to: u64,
+    from: u64,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", "Executing stage.");
+
+    let provider = output_provider_factory.database_provider_rw()?;
+    let mut stage = IndexStorageHistoryStage::default();
+
+    let mut input =
+        reth_stages::ExecInput { target: Some(to), checkpoint: Some(StageCheckpoint::new(from)) };
+    loop {
+        let output = stage.execute(&provider, input)?;
+        if output.done {
+            break
+        }
+        input.checkpoint = Some(output.checkpoint);
+    }
+
+    info!(target: "reth::cli", "Success.");
+
+    Ok(())
+}
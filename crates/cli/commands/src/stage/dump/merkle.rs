@@ -22,6 +22,7 @@ use reth_stages::{
     },
     ExecutionStageThresholds, Stage, StageCheckpoint, UnwindInput,
 };
+use reth_static_file_types::StaticFileSegment;
 use tracing::info;
 
 pub(crate) async fn dump_merkle_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
@@ -31,7 +32,8 @@ pub(crate) async fn dump_merkle_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>
     output_datadir: ChainPath<DataDirPath>,
     should_run: bool,
 ) -> Result<()> {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) =
+        setup(from, to, &output_datadir, db_tool, &[(StaticFileSegment::Headers, to)])?;
 
     output_db.update(|tx| {
         tx.import_table_with_range::<tables::Headers, _>(
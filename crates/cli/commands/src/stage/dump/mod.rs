@@ -1,9 +1,13 @@
 //! Database debugging tool
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
-use clap::Parser;
+use alloy_primitives::BlockNumber;
+use clap::{Parser, ValueEnum};
+use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_db::{init_db, mdbx::DatabaseArguments, tables, DatabaseEnv};
+use reth_db::{
+    init_db, mdbx::DatabaseArguments, static_file::iter_static_files, tables, DatabaseEnv,
+};
 use reth_db_api::{
     cursor::DbCursorRO, database::Database, models::ClientVersion, table::TableImporter,
     transaction::DbTx,
@@ -13,10 +17,20 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_node_builder::NodeTypesWithDB;
 use reth_node_core::{
     args::DatadirArgs,
-    dirs::{DataDirPath, PlatformPath},
+    dirs::{ChainPath, DataDirPath, PlatformPath},
+};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider, StaticFileWriter},
+    BlockReader, ProviderError, StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use std::{path::PathBuf, sync::Arc};
-use tracing::info;
+use tracing::{info, warn};
 
 mod hashing_storage;
 use hashing_storage::dump_hashing_storage_stage;
@@ -30,6 +44,31 @@ use execution::dump_execution_stage;
 mod merkle;
 use merkle::dump_merkle_stage;
 
+mod headers;
+use headers::dump_headers_stage;
+
+mod bodies;
+use bodies::dump_bodies_stage;
+
+mod sender_recovery;
+use sender_recovery::dump_sender_recovery_stage;
+
+mod tx_lookup;
+use tx_lookup::dump_tx_lookup_stage;
+
+mod index_account_history;
+use index_account_history::dump_index_account_history_stage;
+
+mod index_storage_history;
+use index_storage_history::dump_index_storage_history_stage;
+
+pub(crate) mod archive;
+use archive::{pack_datadir, ARCHIVE_EXTENSION};
+
+/// Name of the file in the dumped datadir that contains the RLP encoded blocks that the headers and
+/// bodies stages download.
+const CHAIN_FILE_NAME: &str = "blocks.rlp";
+
 /// `reth dump-stage` command
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
@@ -43,6 +82,12 @@ pub struct Command<C: ChainSpecParser> {
 /// Supported stages to be dumped
 #[derive(Debug, Clone, Parser)]
 pub enum Stages {
+    /// Headers stage.
+    Headers(StageCommand),
+    /// Bodies stage.
+    Bodies(StageCommand),
+    /// `SenderRecovery` stage.
+    SenderRecovery(StageCommand),
     /// Execution stage.
     Execution(StageCommand),
     /// `StorageHashing` stage.
@@ -51,6 +96,12 @@ pub enum Stages {
     AccountHashing(StageCommand),
     /// Merkle stage.
     Merkle(StageCommand),
+    /// `TransactionLookup` stage.
+    TransactionLookup(StageCommand),
+    /// `IndexAccountHistory` stage.
+    IndexAccountHistory(StageCommand),
+    /// `IndexStorageHistory` stage.
+    IndexStorageHistory(StageCommand),
 }
 
 /// Stage command that takes a range
@@ -70,21 +121,43 @@ pub struct StageCommand {
     /// dumping.
     #[arg(long, short, default_value = "false")]
     dry_run: bool,
+    /// The format of the dumped datadir.
+    ///
+    /// With `tar.zst`, the datadir is packed into `<OUTPUT_PATH>.tar.zst` after dumping and the
+    /// dry-run, and removed afterwards. `<OUTPUT_PATH>` must not exist or be empty, and the
+    /// archive must not exist. The archive can be unpacked with `reth stage load`.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Dir, verbatim_doc_comment)]
+    output_format: OutputFormat,
+}
+
+/// Output format of `reth dump-stage`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Plain datadir.
+    #[default]
+    Dir,
+    /// Zstd compressed tar archive of the datadir.
+    #[value(name = "tar.zst")]
+    TarZst,
 }
 
 macro_rules! handle_stage {
     ($stage_fn:ident, $tool:expr, $command:expr) => {{
-        let StageCommand { output_datadir, from, to, dry_run, .. } = $command;
-        let output_datadir =
+        let StageCommand { output_datadir, from, to, dry_run, output_format } = $command;
+        prepare_output(output_datadir.as_ref(), *output_format)?;
+        let chain_datadir =
             output_datadir.with_chain($tool.chain().chain(), DatadirArgs::default());
-        $stage_fn($tool, *from, *to, output_datadir, *dry_run).await?
+        $stage_fn($tool, *from, *to, chain_datadir.clone(), *dry_run).await?;
+        finish_output(output_datadir.as_ref(), chain_datadir.as_ref(), *output_format)?
     }};
 
-    ($stage_fn:ident, $tool:expr, $command:expr, $executor:expr) => {{
-        let StageCommand { output_datadir, from, to, dry_run, .. } = $command;
-        let output_datadir =
+    ($stage_fn:ident, $tool:expr, $command:expr, $arg:expr) => {{
+        let StageCommand { output_datadir, from, to, dry_run, output_format } = $command;
+        prepare_output(output_datadir.as_ref(), *output_format)?;
+        let chain_datadir =
             output_datadir.with_chain($tool.chain().chain(), DatadirArgs::default());
-        $stage_fn($tool, *from, *to, output_datadir, *dry_run, $executor).await?
+        $stage_fn($tool, *from, *to, chain_datadir.clone(), *dry_run, $arg).await?;
+        finish_output(output_datadir.as_ref(), chain_datadir.as_ref(), *output_format)?
     }};
 }
 
@@ -100,6 +173,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let tool = DbTool::new(provider_factory)?;
 
         match &self.command {
+            Stages::Headers(cmd) => {
+                let consensus = Arc::new(EthBeaconConsensus::new(tool.chain()));
+                handle_stage!(dump_headers_stage, &tool, cmd, consensus)
+            }
+            Stages::Bodies(cmd) => {
+                let consensus = Arc::new(EthBeaconConsensus::new(tool.chain()));
+                handle_stage!(dump_bodies_stage, &tool, cmd, consensus)
+            }
+            Stages::SenderRecovery(cmd) => handle_stage!(dump_sender_recovery_stage, &tool, cmd),
             Stages::Execution(cmd) => {
                 let executor = executor(tool.chain());
                 handle_stage!(dump_execution_stage, &tool, cmd, executor)
@@ -107,27 +189,88 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Stages::StorageHashing(cmd) => handle_stage!(dump_hashing_storage_stage, &tool, cmd),
             Stages::AccountHashing(cmd) => handle_stage!(dump_hashing_account_stage, &tool, cmd),
             Stages::Merkle(cmd) => handle_stage!(dump_merkle_stage, &tool, cmd),
+            Stages::TransactionLookup(cmd) => handle_stage!(dump_tx_lookup_stage, &tool, cmd),
+            Stages::IndexAccountHistory(cmd) => {
+                handle_stage!(dump_index_account_history_stage, &tool, cmd)
+            }
+            Stages::IndexStorageHistory(cmd) => {
+                handle_stage!(dump_index_storage_history_stage, &tool, cmd)
+            }
         }
 
         Ok(())
     }
 }
 
-/// Sets up the database and initial state on [`tables::BlockBodyIndices`]. Also returns the tip
-/// block number.
-pub(crate) fn setup<N: NodeTypesWithDB>(
+/// Returns the path of the archive the datadir is packed into with [`OutputFormat::TarZst`].
+fn archive_path(output_datadir: &Path) -> PathBuf {
+    let mut archive = output_datadir.as_os_str().to_owned();
+    archive.push(format!(".{ARCHIVE_EXTENSION}"));
+    archive.into()
+}
+
+/// Checks that the output of the requested [`OutputFormat`] can be written before dumping.
+///
+/// With [`OutputFormat::TarZst`] the datadir is removed after packing, so it must be created by
+/// the command: the output directory must not exist or be empty.
+fn prepare_output(output_datadir: &Path, output_format: OutputFormat) -> eyre::Result<()> {
+    match output_format {
+        OutputFormat::Dir => {}
+        OutputFormat::TarZst => {
+            let archive = archive_path(output_datadir);
+            if archive.exists() {
+                eyre::bail!("archive {} already exists", archive.display())
+            }
+            if output_datadir.exists() && reth_fs_util::read_dir(output_datadir)?.next().is_some() {
+                eyre::bail!(
+                    "output directory {} is not empty, use a fresh directory with --output-format {ARCHIVE_EXTENSION}",
+                    output_datadir.display()
+                )
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the dumped datadir into the requested [`OutputFormat`].
+fn finish_output(
+    output_datadir: &Path,
+    chain_datadir: &Path,
+    output_format: OutputFormat,
+) -> eyre::Result<()> {
+    match output_format {
+        OutputFormat::Dir => {}
+        OutputFormat::TarZst => {
+            pack_datadir(chain_datadir, &archive_path(output_datadir))?;
+
+            // Both directories were created by the command, see `prepare_output`.
+            reth_fs_util::remove_dir_all(chain_datadir)?;
+            reth_fs_util::remove_dir_all(output_datadir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets up the database and initial state on [`tables::BlockBodyIndices`], and copies the
+/// `static_files` segments up to their respective highest block into the output datadir. Also
+/// returns the tip block number.
+pub(crate) fn setup<N: ProviderNodeTypes>(
     from: u64,
     to: u64,
-    output_db: &PathBuf,
+    output_datadir: &ChainPath<DataDirPath>,
     db_tool: &DbTool<N>,
+    static_files: &[(StaticFileSegment, BlockNumber)],
 ) -> eyre::Result<(DatabaseEnv, u64)> {
     assert!(from < to, "FROM block should be bigger than TO block.");
 
+    let output_db = output_datadir.db();
     info!(target: "reth::cli", ?output_db, "Creating separate db");
 
-    let output_datadir = init_db(output_db, DatabaseArguments::new(ClientVersion::default()))?;
+    let output_datadir_db = init_db(output_db, DatabaseArguments::new(ClientVersion::default()))?;
 
-    output_datadir.update(|tx| {
+    output_datadir_db.update(|tx| {
         tx.import_table_with_range::<tables::BlockBodyIndices, _>(
             &db_tool.provider_factory.db_ref().tx()?,
             Some(from - 1),
@@ -141,5 +284,157 @@ pub(crate) fn setup<N: NodeTypesWithDB>(
         .view(|tx| tx.cursor_read::<tables::BlockBodyIndices>()?.last())??
         .expect("some");
 
-    Ok((output_datadir, tip_block_number))
+    for (segment, highest_block) in static_files {
+        copy_static_files(db_tool, &output_datadir.static_files(), *segment, from, *highest_block)?;
+    }
+
+    Ok((output_datadir_db, tip_block_number))
+}
+
+/// Copies the static files of `segment` that contain blocks of `from..=highest_block` into
+/// `output_dir`, and truncates the copies so that `highest_block` is their last block.
+fn copy_static_files<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    output_dir: &Path,
+    segment: StaticFileSegment,
+    from: BlockNumber,
+    highest_block: BlockNumber,
+) -> eyre::Result<()> {
+    let static_file_provider = db_tool.provider_factory.static_file_provider();
+    let source_dir = static_file_provider.directory();
+
+    let Some(source_highest_block) = static_file_provider.get_highest_static_file_block(segment)
+    else {
+        warn!(target: "reth::cli", %segment, "No static files to copy");
+        return Ok(())
+    };
+    if source_highest_block < highest_block {
+        warn!(target: "reth::cli", %segment, source_highest_block, highest_block, "Static files end before the requested block");
+    }
+
+    info!(target: "reth::cli", %segment, from, highest_block, ?output_dir, "Copying static files");
+
+    reth_fs_util::create_dir_all(output_dir)?;
+    let static_files = iter_static_files(source_dir)?;
+    for (block_range, _) in static_files.get(&segment).into_iter().flatten() {
+        if block_range.end() < from || block_range.start() > highest_block {
+            continue
+        }
+
+        // Copies the data file along with its offsets, configuration and index files.
+        let jar_name =
+            segment.filename(&static_file_provider.find_fixed_range(block_range.start()));
+        for entry in reth_fs_util::read_dir(source_dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+            if file_name == jar_name || file_name.starts_with(&format!("{jar_name}.")) {
+                std::fs::copy(&path, output_dir.join(file_name))?;
+            }
+        }
+    }
+
+    let output_static_file_provider = StaticFileProvider::<N::Primitives>::read_write(output_dir)?;
+    let Some(copied_highest_block) =
+        output_static_file_provider.get_highest_static_file_block(segment)
+    else {
+        return Ok(())
+    };
+    if copied_highest_block <= highest_block {
+        return Ok(())
+    }
+
+    let mut writer = output_static_file_provider.latest_writer(segment)?;
+    match segment {
+        StaticFileSegment::Headers => writer.prune_headers(copied_highest_block - highest_block)?,
+        StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            let next_tx_num = db_tool
+                .provider_factory
+                .block_body_indices(highest_block)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(highest_block))?
+                .next_tx_num();
+            let to_delete = output_static_file_provider
+                .get_highest_static_file_tx(segment)
+                .map(|highest_tx| (highest_tx + 1).saturating_sub(next_tx_num))
+                .unwrap_or_default();
+            if segment.is_receipts() {
+                writer.prune_receipts(to_delete, highest_block)?;
+            } else {
+                writer.prune_transactions(to_delete, highest_block)?;
+            }
+        }
+    }
+    writer.commit()?;
+
+    Ok(())
+}
+
+/// Imports the transactions of the block range into the output database.
+pub(crate) fn import_transactions<N: NodeTypesWithDB>(
+    output_db: &DatabaseEnv,
+    db_tool: &DbTool<N>,
+    from: u64,
+    to: u64,
+) -> eyre::Result<()> {
+    let (from_tx, to_tx) = db_tool.provider_factory.db_ref().view(|read_tx| {
+        let mut read_cursor = read_tx.cursor_read::<tables::BlockBodyIndices>()?;
+        let (_, from_block) =
+            read_cursor.seek(from)?.ok_or(eyre::eyre!("BlockBody {from} does not exist."))?;
+        let (_, to_block) =
+            read_cursor.seek(to)?.ok_or(eyre::eyre!("BlockBody {to} does not exist."))?;
+
+        Ok::<(u64, u64), eyre::ErrReport>((from_block.first_tx_num, to_block.next_tx_num()))
+    })??;
+
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::Transactions, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from_tx),
+            to_tx,
+        )
+    })??;
+
+    Ok(())
+}
+
+/// Writes the blocks of the range as a chain file, see
+/// [`FileClient`](reth_downloaders::file_client::FileClient).
+pub(crate) fn write_chain_file<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    from: u64,
+    to: u64,
+    path: &Path,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", ?path, "Writing chain file");
+
+    let provider = db_tool.provider_factory.provider()?;
+    let mut file = BufWriter::new(File::create(path)?);
+    for block in provider.block_range(from..=to)? {
+        file.write_all(&alloy_rlp::encode(&block))?;
+    }
+    file.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_output_requires_fresh_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("dump");
+        prepare_output(&output, OutputFormat::TarZst).unwrap();
+
+        std::fs::create_dir(&output).unwrap();
+        prepare_output(&output, OutputFormat::TarZst).unwrap();
+
+        std::fs::write(output.join("keep"), b"user data").unwrap();
+        assert!(prepare_output(&output, OutputFormat::TarZst).is_err());
+        prepare_output(&output, OutputFormat::Dir).unwrap();
+
+        std::fs::remove_file(output.join("keep")).unwrap();
+        std::fs::write(archive_path(&output), b"archive").unwrap();
+        assert!(prepare_output(&output, OutputFormat::TarZst).is_err());
+    }
 }
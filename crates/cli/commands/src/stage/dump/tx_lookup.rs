@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use super::{import_transactions, setup};
+use alloy_primitives::BlockNumber;
+use eyre::Result;
+use reth_db::DatabaseEnv;
+use reth_db_common::DbTool;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    DatabaseProviderFactory, ProviderFactory,
+};
+use reth_stages::{stages::TransactionLookupStage, Stage, StageCheckpoint};
+use reth_static_file_types::StaticFileSegment;
+use tracing::info;
+
+pub(crate) async fn dump_tx_lookup_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+    output_datadir: ChainPath<DataDirPath>,
+    should_run: bool,
+) -> Result<()> {
+    let (output_db, _) =
+        setup(from, to, &output_datadir, db_tool, &[(StaticFileSegment::Transactions, to)])?;
+
+    import_transactions(&output_db, db_tool, from, to)?;
+
+    if should_run {
+        dry_run(
+            ProviderFactory::<N>::new(
+                Arc::new(output_db),
+                db_tool.chain(),
+                StaticFileProvider::read_write(output_datadir.static_files())?,
+            ),
+            to,
+            from,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Try to re-execute the stage straight away
+fn dry_run<N: ProviderNodeTypes>(
+    output_provider_factory: ProviderFactory<N>,
+    to: u64,
+    from: u64,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", "Executing stage.");
+
+    let provider = output_provider_factory.database_provider_rw()?;
+    let mut stage = TransactionLookupStage::default();
+
+    let mut input =
+        reth_stages::ExecInput { target: Some(to), checkpoint: Some(StageCheckpoint::new(from)) };
+    loop {
+        let output = stage.execute(&provider, input)?;
+        if output.done {
+            break
+        }
+        input.checkpoint = Some(output.checkpoint);
+    }
+
+    info!(target: "reth::cli", "Success.");
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! `reth stage load` command
+
+use super::dump::archive::unpack_datadir;
+use clap::Parser;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_node_core::args::DatadirArgs;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// `reth stage load` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// Parameters for datadir configuration
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    /// The archive created by `reth stage dump --output-format tar.zst`.
+    #[arg(value_name = "ARCHIVE")]
+    archive: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `stage load` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.resolve_datadir(self.chain.chain());
+        if data_dir.db().exists() {
+            eyre::bail!("datadir {data_dir} already contains a database, use a fresh datadir")
+        }
+
+        unpack_datadir(&self.archive, data_dir.as_ref())?;
+
+        info!(target: "reth::cli", %data_dir, "Loaded dumped datadir");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_load_args() {
+        let args: Command<EthereumChainSpecParser> =
+            Command::parse_from(["reth", "--datadir", "fresh", "dump.tar.zst"]);
+        assert_eq!(args.archive, PathBuf::from("dump.tar.zst"));
+    }
+}
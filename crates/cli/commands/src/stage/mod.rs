@@ -11,6 +11,7 @@ use reth_evm::execute::BlockExecutorProvider;
 
 pub mod drop;
 pub mod dump;
+pub mod load;
 pub mod run;
 pub mod unwind;
 
@@ -35,6 +36,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     Drop(drop::Command<C>),
     /// Dumps a stage from a range into a new database.
     Dump(dump::Command<C>),
+    /// Unpacks a dumped stage archive into a fresh datadir.
+    Load(load::Command<C>),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command<C>),
 }
@@ -51,6 +54,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Run(command) => command.execute::<N, _, _>(ctx, executor).await,
             Subcommands::Drop(command) => command.execute::<N>().await,
             Subcommands::Dump(command) => command.execute::<N, _, _>(executor).await,
+            Subcommands::Load(command) => command.execute().await,
             Subcommands::Unwind(command) => command.execute::<N>().await,
         }
     }
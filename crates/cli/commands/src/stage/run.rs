@@ -32,8 +32,9 @@ use reth_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
+use reth_profiling::{cpu_profiler, DEFAULT_PROFILING_FREQUENCY};
 use reth_provider::{
-    writer::UnifiedStorageWriter, ChainSpecProvider, DatabaseProviderFactory,
+    writer::UnifiedStorageWriter, ChainSpecProvider, DatabaseProviderFactory, HeaderProvider,
     StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_stages::{
@@ -45,7 +46,17 @@ use reth_stages::{
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageError, StageExt, UnwindInput,
     UnwindOutput,
 };
-use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
+use serde::Serialize;
+use std::{
+    any::Any,
+    fs::File,
+    io::BufWriter,
+    net::SocketAddr,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::watch;
 use tracing::*;
 
@@ -97,6 +108,29 @@ pub struct Command<C: ChainSpecParser> {
     #[arg(long)]
     checkpoints: bool,
 
+    /// Profile the CPU usage of the stage execution and write it to the given file.
+    ///
+    /// Files with an `svg` extension are written as a flamegraph, all others as folded stacks.
+    /// Requires reth to be built with the `cpu-profiling` feature.
+    #[arg(long, value_name = "PATH")]
+    cpu_profile: Option<PathBuf>,
+
+    /// Benchmark the stage and write the results of every chunk to the given file.
+    ///
+    /// Files with a `json` extension are written as JSON, all others as CSV.
+    #[arg(long, value_name = "PATH")]
+    bench: Option<PathBuf>,
+
+    /// The number of blocks the stage executes at once when benchmarking. Defaults to the whole
+    /// range.
+    #[arg(long = "bench.chunk-size", value_name = "BLOCKS", requires = "bench")]
+    bench_chunk_size: Option<u64>,
+
+    /// How many times the range is executed when benchmarking. The range is unwound before every
+    /// run.
+    #[arg(long = "bench.runs", default_value_t = 1, requires = "bench")]
+    bench_runs: usize,
+
     #[command(flatten)]
     network: NetworkArgs,
 }
@@ -310,57 +344,215 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         let checkpoint = provider_rw.get_stage_checkpoint(exec_stage.id())?.unwrap_or_default();
 
-        let unwind_stage = unwind_stage.as_mut().unwrap_or(&mut exec_stage);
+        let chunk_size = self.bench_chunk_size.unwrap_or(self.to.saturating_sub(self.from)).max(1);
+        let runs = if self.bench.is_some() { self.bench_runs } else { 1 };
+        let mut results = Vec::new();
+
+        if self.cpu_profile.is_some() {
+            cpu_profiler().start(DEFAULT_PROFILING_FREQUENCY)?;
+        }
 
-        let mut unwind = UnwindInput {
-            checkpoint: checkpoint.with_block_number(self.to),
-            unwind_to: self.from,
-            bad_block: None,
-        };
+        for run in 0..runs {
+            // later runs must always unwind the blocks of the previous run
+            if !self.skip_unwind || run > 0 {
+                let unwind_stage = unwind_stage.as_mut().unwrap_or(&mut exec_stage);
+                let mut unwind = UnwindInput {
+                    checkpoint: checkpoint.with_block_number(self.to),
+                    unwind_to: self.from,
+                    bad_block: None,
+                };
+
+                while unwind.checkpoint.block_number > self.from {
+                    let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, unwind)?;
+                    unwind.checkpoint = checkpoint;
+
+                    if self.checkpoints {
+                        provider_rw.save_stage_checkpoint(unwind_stage.id(), checkpoint)?;
+                    }
+
+                    if self.commit {
+                        UnifiedStorageWriter::commit_unwind(provider_rw)?;
+                        provider_rw = provider_factory.database_provider_rw()?;
+                    }
+                }
+            }
 
-        if !self.skip_unwind {
-            while unwind.checkpoint.block_number > self.from {
-                let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, unwind)?;
-                unwind.checkpoint = checkpoint;
+            let start = Instant::now();
+            info!(target: "reth::cli", stage = %self.stage, run, "Executing stage");
+            let mut chunk_start = self.from;
+            loop {
+                let chunk_end = chunk_start.saturating_add(chunk_size).min(self.to);
+                let mut input = ExecInput {
+                    target: Some(chunk_end),
+                    checkpoint: Some(checkpoint.with_block_number(chunk_start)),
+                };
+
+                let chunk_started_at = Instant::now();
+                let mut commit_time = Duration::ZERO;
+                loop {
+                    exec_stage.execute_ready(input).await?;
+                    let ExecOutput { checkpoint, done } =
+                        exec_stage.execute(&provider_rw, input)?;
+
+                    input.checkpoint = Some(checkpoint);
+
+                    if self.checkpoints {
+                        provider_rw.save_stage_checkpoint(exec_stage.id(), checkpoint)?;
+                    }
+                    if self.commit {
+                        let commit_started_at = Instant::now();
+                        UnifiedStorageWriter::commit(provider_rw)?;
+                        commit_time += commit_started_at.elapsed();
+                        provider_rw = provider_factory.database_provider_rw()?;
+                    }
+
+                    if done {
+                        break
+                    }
+                }
 
-                if self.checkpoints {
-                    provider_rw.save_stage_checkpoint(unwind_stage.id(), checkpoint)?;
+                if self.bench.is_some() {
+                    let gas_used = provider_rw
+                        .headers_range(chunk_start + 1..=chunk_end)?
+                        .iter()
+                        .map(|header| header.gas_used)
+                        .sum();
+                    let result = BenchResult::new(
+                        run,
+                        chunk_start + 1..=chunk_end,
+                        gas_used,
+                        chunk_started_at.elapsed(),
+                        commit_time,
+                    );
+                    info!(
+                        target: "reth::cli",
+                        stage = %self.stage,
+                        run,
+                        from = result.from,
+                        to = result.to,
+                        time = ?chunk_started_at.elapsed(),
+                        gas_per_second = result.gas_per_second,
+                        blocks_per_second = result.blocks_per_second,
+                        "Executed chunk"
+                    );
+                    results.push(result);
                 }
 
-                if self.commit {
-                    UnifiedStorageWriter::commit_unwind(provider_rw)?;
-                    provider_rw = provider_factory.database_provider_rw()?;
+                if chunk_end >= self.to {
+                    break
                 }
+                chunk_start = chunk_end;
             }
+            info!(target: "reth::cli", stage = %self.stage, run, time = ?start.elapsed(), "Finished stage");
+        }
+
+        if let Some(path) = &self.cpu_profile {
+            cpu_profiler().stop()?.write_to_file(path)?;
         }
 
-        let mut input = ExecInput {
-            target: Some(self.to),
-            checkpoint: Some(checkpoint.with_block_number(self.from)),
-        };
+        if let Some(path) = &self.bench {
+            write_bench_results(path, &results)?;
+            info!(target: "reth::cli", path = %path.display(), chunks = results.len(), "Wrote benchmark results");
+        }
 
-        let start = Instant::now();
-        info!(target: "reth::cli", stage = %self.stage, "Executing stage");
-        loop {
-            exec_stage.execute_ready(input).await?;
-            let ExecOutput { checkpoint, done } = exec_stage.execute(&provider_rw, input)?;
+        Ok(())
+    }
+}
 
-            input.checkpoint = Some(checkpoint);
+/// The benchmark results of a single chunk of blocks.
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    /// The run the chunk was executed in, starting at 0.
+    run: usize,
+    /// The first block of the chunk.
+    from: u64,
+    /// The last block of the chunk.
+    to: u64,
+    /// The gas used by the blocks of the chunk.
+    gas_used: u64,
+    /// The wall time of the chunk in microseconds, including the commits.
+    wall_time_us: u128,
+    /// The time spent committing to the database in microseconds.
+    commit_time_us: u128,
+    /// The gas processed per second of wall time.
+    gas_per_second: f64,
+    /// The blocks processed per second of wall time.
+    blocks_per_second: f64,
+}
 
-            if self.checkpoints {
-                provider_rw.save_stage_checkpoint(exec_stage.id(), checkpoint)?;
-            }
-            if self.commit {
-                UnifiedStorageWriter::commit(provider_rw)?;
-                provider_rw = provider_factory.database_provider_rw()?;
-            }
+impl BenchResult {
+    fn new(
+        run: usize,
+        blocks: RangeInclusive<u64>,
+        gas_used: u64,
+        wall_time: Duration,
+        commit_time: Duration,
+    ) -> Self {
+        let seconds = wall_time.as_secs_f64();
+        let block_count = blocks.clone().count() as f64;
+        Self {
+            run,
+            from: *blocks.start(),
+            to: *blocks.end(),
+            gas_used,
+            wall_time_us: wall_time.as_micros(),
+            commit_time_us: commit_time.as_micros(),
+            gas_per_second: if seconds > 0.0 { gas_used as f64 / seconds } else { 0.0 },
+            blocks_per_second: if seconds > 0.0 { block_count / seconds } else { 0.0 },
+        }
+    }
+}
 
-            if done {
-                break
-            }
+/// Writes the benchmark results as JSON if the path has a `json` extension, and as CSV otherwise.
+fn write_bench_results(path: &Path, results: &[BenchResult]) -> eyre::Result<()> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), results)?;
+    } else {
+        let mut writer = csv::Writer::from_path(path)?;
+        for result in results {
+            writer.serialize(result)?;
         }
-        info!(target: "reth::cli", stage = %self.stage, time = ?start.elapsed(), "Finished stage");
+        writer.flush()?;
+    }
+    Ok(())
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    use super::*;
+
+    #[test]
+    fn parse_bench() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "senders",
+            "--from",
+            "0",
+            "--to",
+            "100",
+            "--bench",
+            "bench.csv",
+            "--bench.chunk-size",
+            "10",
+            "--bench.runs",
+            "3",
+        ]);
+        assert_eq!(cmd.bench, Some(PathBuf::from("bench.csv")));
+        assert_eq!(cmd.bench_chunk_size, Some(10));
+        assert_eq!(cmd.bench_runs, 3);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "senders",
+            "--from",
+            "0",
+            "--to",
+            "100",
+            "--bench.runs",
+            "3",
+        ])
+        .is_err());
     }
 }
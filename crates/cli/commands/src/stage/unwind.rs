@@ -13,16 +13,22 @@ use reth_db::DatabaseEnv;
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
-use reth_node_core::args::NetworkArgs;
+use reth_node_core::args::{NetworkArgs, StageEnum};
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockExecutionWriter, BlockNumReader, ChainSpecProvider,
-    ChainStateBlockReader, ChainStateBlockWriter, ProviderFactory, StaticFileProviderFactory,
+    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockExecutionWriter,
+    BlockNumReader, ChainSpecProvider, ChainStateBlockReader, ChainStateBlockWriter,
+    DatabaseProviderFactory, ProviderFactory, ProviderResult, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune::PruneModes;
 use reth_stages::{
     sets::{DefaultStages, OfflineStages},
-    stages::ExecutionStage,
-    ExecutionStageThresholds, Pipeline, StageSet,
+    stages::{
+        AccountHashingStage, ExecutionStage, IndexAccountHistoryStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+    },
+    ExecInput, ExecOutput, ExecutionStageThresholds, Pipeline, Stage, StageExt, StageSet,
+    UnwindInput, UnwindOutput,
 };
 use reth_static_file::StaticFileProducer;
 use std::{ops::RangeInclusive, sync::Arc};
@@ -45,6 +51,18 @@ pub struct Command<C: ChainSpecParser> {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Only unwind the given stage, leaving the checkpoints and data of all other stages as they
+    /// are.
+    ///
+    /// Only stages that are derived from other data in the database are supported, so headers,
+    /// bodies and execution can only be unwound together with the whole pipeline.
+    #[arg(long, value_enum, conflicts_with = "offline")]
+    stage: Option<StageEnum>,
+
+    /// Run the stage again up to its previous checkpoint after unwinding it.
+    #[arg(long, requires = "stage")]
+    rerun: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -57,6 +75,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             eyre::bail!("Cannot unwind genesis block")
         }
 
+        if let Some(stage) = self.stage {
+            return self.unwind_stage(stage, &config, provider_factory, *range.start() - 1).await
+        }
+
         let highest_static_file_block = provider_factory
             .static_file_provider()
             .get_highest_static_files()
@@ -113,6 +135,138 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         Ok(())
     }
 
+    /// Unwinds a single stage to the given block, and runs it again up to its previous checkpoint
+    /// if `--rerun` is set.
+    ///
+    /// The stage removes its own data above the target block while unwinding. The checkpoint is
+    /// saved and committed after every unwind and execution batch, so an interrupted run can be
+    /// resumed.
+    ///
+    /// The merkle stage computes the trie from the hashed state, so the hashing stages are unwound
+    /// and run again together with it.
+    async fn unwind_stage<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
+        &self,
+        stage: StageEnum,
+        config: &Config,
+        provider_factory: ProviderFactory<N>,
+        unwind_to: BlockNumber,
+    ) -> eyre::Result<()> {
+        let etl_config = config.stages.etl.clone();
+        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+        // The stages are unwound and run in this order.
+        let mut stages: Vec<UnwindableStage<_>> = match stage {
+            StageEnum::Senders => {
+                vec![UnwindableStage::new(SenderRecoveryStage::new(config.stages.sender_recovery))]
+            }
+            StageEnum::TxLookup => vec![UnwindableStage::new(TransactionLookupStage::new(
+                config.stages.transaction_lookup,
+                etl_config,
+                prune_modes.transaction_lookup,
+            ))],
+            StageEnum::AccountHashing => vec![UnwindableStage::new(AccountHashingStage::new(
+                config.stages.account_hashing,
+                etl_config,
+            ))],
+            StageEnum::StorageHashing => vec![UnwindableStage::new(StorageHashingStage::new(
+                config.stages.storage_hashing,
+                etl_config,
+            ))],
+            StageEnum::Merkle => vec![
+                UnwindableStage::new(AccountHashingStage::new(
+                    config.stages.account_hashing,
+                    etl_config.clone(),
+                )),
+                UnwindableStage::new(StorageHashingStage::new(
+                    config.stages.storage_hashing,
+                    etl_config,
+                )),
+                // The execution variant skips unwinding, the unwind variant only unwinds. Both
+                // use the `MerkleExecute` checkpoint here, since that's the one the pipeline
+                // runs the stage from.
+                UnwindableStage::new(MerkleStage::new_execution(
+                    config.stages.merkle.clean_threshold,
+                ))
+                .with_unwind_stage(MerkleStage::default_unwind()),
+            ],
+            StageEnum::AccountHistory => vec![UnwindableStage::new(IndexAccountHistoryStage::new(
+                config.stages.index_account_history,
+                etl_config,
+                prune_modes.account_history,
+            ))],
+            StageEnum::StorageHistory => vec![UnwindableStage::new(IndexStorageHistoryStage::new(
+                config.stages.index_storage_history,
+                etl_config,
+                prune_modes.storage_history,
+            ))],
+            _ => eyre::bail!(
+                "Unwinding the {stage} stage on its own is not supported, unwind the whole pipeline instead"
+            ),
+        };
+
+        let mut provider_rw = provider_factory.database_provider_rw()?;
+
+        // The checkpoints of the stages before unwinding, the stages are run up to them again.
+        let checkpoints = stages
+            .iter()
+            .map(|stage| Ok(provider_rw.get_stage_checkpoint(stage.exec.id())?.unwrap_or_default()))
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        for (stage, checkpoint) in stages.iter_mut().zip(&checkpoints) {
+            let id = stage.exec.id();
+            if checkpoint.block_number <= unwind_to {
+                info!(target: "reth::cli", stage = %id, checkpoint = checkpoint.block_number, unwind_to, "Stage is already at or below the target block");
+                continue
+            }
+
+            info!(target: "reth::cli", stage = %id, from = checkpoint.block_number, unwind_to, "Unwinding stage");
+            let unwind_stage = stage.unwind.as_mut().unwrap_or(&mut stage.exec);
+            let mut input = UnwindInput { checkpoint: *checkpoint, unwind_to, bad_block: None };
+            while input.checkpoint.block_number > unwind_to {
+                let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, input)?;
+                input.checkpoint = checkpoint;
+
+                provider_rw.save_stage_checkpoint(id, checkpoint)?;
+                UnifiedStorageWriter::commit_unwind(provider_rw)?;
+                provider_rw = provider_factory.database_provider_rw()?;
+            }
+            info!(target: "reth::cli", stage = %id, checkpoint = input.checkpoint.block_number, "Unwound stage");
+        }
+
+        if !self.rerun {
+            return Ok(())
+        }
+
+        for (stage, checkpoint) in stages.iter_mut().zip(checkpoints) {
+            let id = stage.exec.id();
+            if checkpoint.block_number <= unwind_to {
+                continue
+            }
+
+            info!(target: "reth::cli", stage = %id, from = unwind_to, to = checkpoint.block_number, "Running stage");
+            let mut input = ExecInput {
+                target: Some(checkpoint.block_number),
+                checkpoint: provider_rw.get_stage_checkpoint(id)?,
+            };
+            loop {
+                stage.exec.execute_ready(input).await?;
+                let ExecOutput { checkpoint, done } = stage.exec.execute(&provider_rw, input)?;
+                input.checkpoint = Some(checkpoint);
+
+                provider_rw.save_stage_checkpoint(id, checkpoint)?;
+                UnifiedStorageWriter::commit(provider_rw)?;
+                provider_rw = provider_factory.database_provider_rw()?;
+
+                if done {
+                    break
+                }
+            }
+            info!(target: "reth::cli", stage = %id, checkpoint = checkpoint.block_number, "Finished stage");
+        }
+
+        Ok(())
+    }
+
     fn build_pipeline<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
         self,
         config: Config,
@@ -169,6 +323,25 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     }
 }
 
+/// A stage that is unwound on its own by `reth stage unwind --stage`.
+struct UnwindableStage<Provider> {
+    /// The stage that is run again after unwinding. Its id is the checkpoint that is unwound.
+    exec: Box<dyn Stage<Provider>>,
+    /// The stage that unwinds the data, if it's not the same as [`Self::exec`].
+    unwind: Option<Box<dyn Stage<Provider>>>,
+}
+
+impl<Provider> UnwindableStage<Provider> {
+    fn new(stage: impl Stage<Provider> + 'static) -> Self {
+        Self { exec: Box::new(stage), unwind: None }
+    }
+
+    fn with_unwind_stage(mut self, stage: impl Stage<Provider> + 'static) -> Self {
+        self.unwind = Some(Box::new(stage));
+        self
+    }
+}
+
 /// `reth stage unwind` subcommand
 #[derive(Subcommand, Debug, Eq, PartialEq)]
 enum Subcommands {
@@ -213,6 +386,20 @@ mod tests {
     use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 
     use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, Address, U256};
+    use reth_db::tables;
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{
+        Account, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry,
+    };
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_stages::{StageCheckpoint, StageId};
+    use reth_trie::{test_utils::state_root, StateRoot};
+    use reth_trie_db::DatabaseStateRoot;
 
     #[test]
     fn parse_unwind() {
@@ -233,5 +420,129 @@ mod tests {
             "100",
         ]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--stage",
+            "storage-history",
+            "--rerun",
+            "to-block",
+            "100",
+        ]);
+        assert_eq!(cmd.stage, Some(StageEnum::StorageHistory));
+        assert!(cmd.rerun);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--rerun",
+            "to-block",
+            "100",
+        ])
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn unwind_merkle_stage() {
+        const A: Address = address!("00000000000000000000000000000000000000aa");
+        const B: Address = address!("00000000000000000000000000000000000000bb");
+        const C: Address = address!("00000000000000000000000000000000000000cc");
+        let slot = |n: u8| B256::with_last_byte(n);
+
+        let a1 = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let a2 = Account { nonce: 2, balance: U256::from(9), bytecode_hash: None };
+        let b = Account { nonce: 0, balance: U256::from(5), bytecode_hash: None };
+        let c = Account { nonce: 0, balance: U256::from(3), bytecode_hash: None };
+        let state_root_1 =
+            state_root([(A, (a1, vec![])), (B, (b, vec![(slot(1), U256::from(7))]))]);
+        let state_root_2 = state_root([
+            (A, (a2, vec![])),
+            (B, (b, vec![(slot(1), U256::from(8)), (slot(2), U256::from(1))])),
+            (C, (c, vec![])),
+        ]);
+
+        // blocks 0..=2 with the state after block 2 and the changes of block 2
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut parent_hash = B256::ZERO;
+        for (number, state_root) in [(0, B256::ZERO), (1, state_root_1), (2, state_root_2)] {
+            let header = SealedHeader::seal(Header {
+                number,
+                parent_hash,
+                state_root,
+                ..Default::default()
+            });
+            parent_hash = header.hash();
+            let block = SealedBlock { header, body: Default::default() };
+            provider_rw
+                .insert_historical_block(SealedBlockWithSenders::new(block, vec![]).unwrap())
+                .unwrap();
+        }
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::PlainAccountState>(A, a2).unwrap();
+        tx.put::<tables::PlainAccountState>(B, b).unwrap();
+        tx.put::<tables::PlainAccountState>(C, c).unwrap();
+        tx.put::<tables::PlainStorageState>(B, StorageEntry::new(slot(1), U256::from(8))).unwrap();
+        tx.put::<tables::PlainStorageState>(B, StorageEntry::new(slot(2), U256::from(1))).unwrap();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address: A, info: Some(a1) })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address: B, info: Some(b) })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address: C, info: None }).unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            BlockNumberAddress((2, B)),
+            StorageEntry::new(slot(1), U256::from(7)),
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            BlockNumberAddress((2, B)),
+            StorageEntry::new(slot(2), U256::ZERO),
+        )
+        .unwrap();
+        provider_rw.commit().unwrap();
+
+        // run the stages up to block 2
+        let stages = [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute];
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(2), checkpoint: None };
+        assert!(AccountHashingStage::default().execute(&provider_rw, input).unwrap().done);
+        assert!(StorageHashingStage::default().execute(&provider_rw, input).unwrap().done);
+        assert!(MerkleStage::default_execution().execute(&provider_rw, input).unwrap().done);
+        for id in stages {
+            provider_rw.save_stage_checkpoint(id, StageCheckpoint::new(2)).unwrap();
+        }
+        UnifiedStorageWriter::commit(provider_rw).unwrap();
+
+        let unwind_merkle = |args: &[&str]| {
+            let cmd = Command::<EthereumChainSpecParser>::parse_from(
+                ["reth", "--datadir", "dir", "--stage", "merkle"]
+                    .into_iter()
+                    .chain(args.iter().copied())
+                    .chain(["to-block", "1"]),
+            );
+            let factory = factory.clone();
+            async move { cmd.unwind_stage(StageEnum::Merkle, &Config::default(), factory, 1).await }
+        };
+        let checkpoints = || {
+            let provider = factory.provider().unwrap();
+            stages.map(|id| provider.get_stage_checkpoint(id).unwrap().unwrap_or_default())
+        };
+        let trie_root = || StateRoot::from_tx(factory.provider().unwrap().tx_ref()).root().unwrap();
+
+        // the trie is unwound to block 1 and computed again up to block 2
+        unwind_merkle(&["--rerun"]).await.unwrap();
+        assert_eq!(checkpoints().map(|checkpoint| checkpoint.block_number), [2; 3]);
+        assert_eq!(trie_root(), state_root_2);
+
+        unwind_merkle(&[]).await.unwrap();
+        assert_eq!(checkpoints().map(|checkpoint| checkpoint.block_number), [1; 3]);
+        assert_eq!(trie_root(), state_root_1);
+
+        // the checkpoint the pipeline only uses while unwinding is left as it is
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.get_stage_checkpoint(StageId::MerkleUnwind).unwrap(), None);
     }
 }
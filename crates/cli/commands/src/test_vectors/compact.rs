@@ -17,7 +17,10 @@ use reth_codecs::alloy::{
     withdrawal::Withdrawal,
 };
 use reth_db::{
-    models::{AccountBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    models::{
+        AccountBeforeTx, DbStatsSnapshot, StoredBlockBodyIndices, StoredBlockOmmers,
+        StoredBlockWithdrawals,
+    },
     ClientVersion,
 };
 use reth_fs_util as fs;
@@ -28,11 +31,14 @@ use reth_primitives::{
 use reth_prune_types::{PruneCheckpoint, PruneMode};
 use reth_stages_types::{
     AccountHashingCheckpoint, CheckpointBlockRange, EntitiesCheckpoint, ExecutionCheckpoint,
-    HeadersCheckpoint, IndexHistoryCheckpoint, StageCheckpoint, StageUnitCheckpoint,
-    StorageHashingCheckpoint,
+    HeadersCheckpoint, IndexHistoryCheckpoint, SnapSyncCheckpoint, StageCheckpoint,
+    StageUnitCheckpoint, StorageHashingCheckpoint,
 };
 use reth_trie::{hash_builder::HashBuilderValue, TrieMask};
-use reth_trie_common::{hash_builder::HashBuilderState, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    hash_builder::HashBuilderState, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey,
+    StoredSubNode,
+};
 use std::{fs::File, io::BufReader};
 
 pub const VECTORS_FOLDER: &str = "testdata/micro/compact";
@@ -104,6 +110,7 @@ compact_types!(
         ExecutionCheckpoint,
         HeadersCheckpoint,
         IndexHistoryCheckpoint,
+        SnapSyncCheckpoint,
         EntitiesCheckpoint,
         CheckpointBlockRange,
         StageCheckpoint,
@@ -119,10 +126,11 @@ compact_types!(
         // MerkleCheckpoint, // todo storedsubnode -> branchnodecompact arbitrary
         AccountBeforeTx,
         ClientVersion,
+        DbStatsSnapshot,
         StoredNibbles,
         StoredNibblesSubKey,
-        // StorageTrieEntry, // todo branchnodecompact arbitrary
-        // StoredSubNode, // todo branchnodecompact arbitrary
+        StorageTrieEntry,
+        StoredSubNode,
         HashBuilderState
     ],
     // These types require an extra identifier which is usually stored elsewhere (eg. parent type).
@@ -0,0 +1,459 @@
+//! Execution test vectors, generated from ranges of blocks of a synced chain.
+//!
+//! A vector holds the blocks of the range together with all state they read before execution,
+//! and the receipts and state of the touched accounts after execution, as stored in the
+//! database. Vectors are self-contained, so the blocks can be re-executed without a database.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use clap::Parser;
+use eyre::{Context, Result};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_evm::execute::{BatchExecutor, BlockExecutorProvider};
+use reth_fs_util as fs;
+use reth_primitives::{Block, BlockWithSenders, Receipt};
+use reth_provider::{
+    AccountReader, BlockNumReader, BlockReader, ChainSpecProvider, HeaderProvider, ProviderError,
+    ReceiptProvider, StateProvider,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    primitives::{AccountInfo, Bytecode},
+    Database,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+use tracing::{error, info};
+
+const VECTORS_FOLDER: &str = "testdata/micro/execution";
+
+/// `reth test-vectors execution` command
+///
+/// The blocks are executed to record the state they read, so ranges that this build doesn't
+/// execute correctly are rejected.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block of the range.
+    #[arg(long, value_name = "BLOCK", required_unless_present = "read")]
+    from: Option<BlockNumber>,
+
+    /// The last block of the range, inclusive.
+    #[arg(long, value_name = "BLOCK", required_unless_present = "read")]
+    to: Option<BlockNumber>,
+
+    /// Read test vectors from files and execute them.
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    read: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute the command
+    pub async fn execute<N, E, F>(self, executor: F) -> Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        E: BlockExecutorProvider,
+        F: FnOnce(Arc<C::ChainSpec>) -> E,
+    {
+        if self.read {
+            let chain_id = self.env.chain.chain().id();
+            return read_vectors(chain_id, &executor(self.env.chain.clone()))
+        }
+
+        let (Some(from), Some(to)) = (self.from, self.to) else {
+            eyre::bail!("--from and --to are required")
+        };
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+        let executor = executor(chain_spec.clone());
+
+        eyre::ensure!(from > 0 && from <= to, "Invalid block range {from}..={to}");
+        let best_block = provider_factory.best_block_number()?;
+        eyre::ensure!(to <= best_block, "Block {to} is beyond the tip {best_block}");
+
+        let provider = provider_factory.provider()?;
+        let blocks = provider.block_with_senders_range(from..=to)?;
+        eyre::ensure!(blocks.len() as u64 == to - from + 1, "Blocks {from}..={to} are missing");
+
+        info!(target: "reth::cli", from, to, "Executing blocks");
+        let mut db = RecordingDatabase::new(StateProviderDatabase::new(
+            provider_factory.history_by_block_number(from - 1)?,
+        ));
+        let mut batch_executor = executor.batch_executor(&mut db);
+        let mut vector_blocks = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let number = block.number;
+            let total_difficulty = provider
+                .header_td_by_number(number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+            batch_executor
+                .execute_and_verify_one((&block, total_difficulty).into())
+                .wrap_err_with(|| format!("Failed to execute block {number}"))?;
+
+            let receipts = provider
+                .receipts_by_block(number.into())?
+                .ok_or_else(|| eyre::eyre!("Receipts of block {number} are not available"))?;
+            vector_blocks.push(VectorBlock {
+                rlp: alloy_rlp::encode(&block.block).into(),
+                senders: block.senders,
+                total_difficulty,
+                receipts,
+            });
+        }
+        let outcome = batch_executor.finalize();
+        let RecordingDatabase { inner: mut pre_state_db, accounts, storage, block_hashes } = db;
+
+        let mut pre_state = BTreeMap::new();
+        for (address, info) in &accounts {
+            let Some(info) = info else { continue };
+            let storage = storage
+                .get(address)
+                .into_iter()
+                .flatten()
+                .map(|(slot, value)| (B256::from(*slot), *value))
+                .collect();
+            let code = account_code(&mut pre_state_db, info.code_hash)?;
+            pre_state.insert(
+                *address,
+                VectorAccount { nonce: info.nonce, balance: info.balance, code, storage },
+            );
+        }
+
+        let post_state_provider = provider_factory.history_by_block_number(to)?;
+        let mut post_state = BTreeMap::new();
+        for address in accounts.keys() {
+            let Some(account) = post_state_provider.basic_account(*address)? else {
+                post_state.insert(*address, None);
+                continue
+            };
+            let slots = storage
+                .get(address)
+                .into_iter()
+                .flat_map(|slots| slots.keys())
+                // slots of created accounts are written without being read
+                .chain(outcome.bundle.account(address).into_iter().flat_map(|a| a.storage.keys()))
+                .map(|slot| B256::from(*slot))
+                .collect::<BTreeSet<_>>();
+            let mut storage = BTreeMap::new();
+            for slot in slots {
+                storage
+                    .insert(slot, post_state_provider.storage(*address, slot)?.unwrap_or_default());
+            }
+            let code = match account.bytecode_hash {
+                Some(hash) => post_state_provider
+                    .bytecode_by_hash(hash)?
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default(),
+                None => Bytes::new(),
+            };
+            post_state.insert(
+                *address,
+                Some(VectorAccount {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    code,
+                    storage,
+                }),
+            );
+        }
+
+        let vector = ExecutionVector {
+            chain_id: chain_spec.chain().id(),
+            pre_state,
+            block_hashes,
+            blocks: vector_blocks,
+            post_state,
+        };
+        // Validate the vector against this build before writing it.
+        execute_vector(&executor, &vector)?;
+
+        fs::create_dir_all(VECTORS_FOLDER)?;
+        let file_path = format!("{VECTORS_FOLDER}/{}_{from}_{to}.json", vector.chain_id);
+        serde_json::to_writer_pretty(BufWriter::new(File::create(&file_path)?), &vector)?;
+        println!("Written execution test vector to {file_path}");
+
+        Ok(())
+    }
+}
+
+/// Reads all vectors of the given chain from the vectors folder and executes them.
+fn read_vectors<E: BlockExecutorProvider>(chain_id: u64, executor: &E) -> Result<()> {
+    fs::create_dir_all(VECTORS_FOLDER)?;
+    let mut errors = None;
+
+    for entry in fs::read_dir(VECTORS_FOLDER)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue
+        }
+
+        let result = read_vector(&path).and_then(|vector| {
+            if vector.chain_id != chain_id {
+                println!("Skipping {} of chain {}.", path.display(), vector.chain_id);
+                return Ok(())
+            }
+            execute_vector(executor, &vector)?;
+            println!("Executed {}.", path.display());
+            Ok(())
+        });
+        if let Err(err) = result {
+            errors.get_or_insert_with(Vec::new).push(err.to_string());
+        }
+    }
+
+    if let Some(err_list) = errors {
+        for error in err_list {
+            error!(target: "reth::cli", "{:?}", error);
+        }
+        eyre::bail!("Failed to execute execution test vectors.");
+    }
+
+    Ok(())
+}
+
+fn read_vector(path: &Path) -> Result<ExecutionVector> {
+    let file =
+        File::open(path).wrap_err_with(|| format!("Failed to open vector {}.", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .wrap_err_with(|| format!("Failed to parse vector {}.", path.display()))
+}
+
+/// Executes the blocks of the vector on top of its pre-state, and checks the receipts and the
+/// state of the touched accounts.
+pub fn execute_vector<E: BlockExecutorProvider>(
+    executor: &E,
+    vector: &ExecutionVector,
+) -> Result<()> {
+    let mut db = VectorDatabase::new(vector);
+    let mut batch_executor = executor.batch_executor(&mut db);
+    for vector_block in &vector.blocks {
+        let block = Block::decode(&mut vector_block.rlp.as_ref())?;
+        let number = block.number;
+        let block = BlockWithSenders::new(block, vector_block.senders.clone())
+            .ok_or_else(|| eyre::eyre!("Senders of block {number} don't match its transactions"))?;
+        batch_executor
+            .execute_and_verify_one((&block, vector_block.total_difficulty).into())
+            .wrap_err_with(|| format!("Failed to execute block {number}"))?;
+    }
+    let outcome = batch_executor.finalize();
+
+    for (number, (vector_block, receipts)) in
+        (outcome.first_block..).zip(vector.blocks.iter().zip(outcome.receipts.iter()))
+    {
+        let receipts = receipts.iter().flatten().cloned().collect::<Vec<_>>();
+        eyre::ensure!(receipts == vector_block.receipts, "Receipts of block {number} don't match");
+    }
+
+    for (address, expected) in &vector.post_state {
+        let pre = vector.pre_state.get(address);
+        let bundle_account = outcome.bundle.account(address);
+        let info = match bundle_account {
+            Some(account) => account.info.clone(),
+            None => pre.map(VectorAccount::info),
+        };
+
+        match (info, expected) {
+            (None, None) => {}
+            (Some(info), Some(expected)) => {
+                eyre::ensure!(
+                    info.nonce == expected.nonce &&
+                        info.balance == expected.balance &&
+                        info.code_hash == expected.code_hash(),
+                    "Account {address} doesn't match"
+                );
+                for (slot, value) in &expected.storage {
+                    let actual = bundle_account
+                        .and_then(|account| account.storage_slot((*slot).into()))
+                        .or_else(|| pre.and_then(|account| account.storage.get(slot).copied()))
+                        .unwrap_or_default();
+                    eyre::ensure!(
+                        actual == *value,
+                        "Storage slot {slot} of account {address} doesn't match"
+                    );
+                }
+            }
+            (info, _) => eyre::bail!(
+                "Account {address} {} exist",
+                if info.is_some() { "shouldn't" } else { "should" }
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the code of an account, or empty bytes if it has none.
+fn account_code<DB: Database<Error = ProviderError>>(
+    db: &mut DB,
+    code_hash: B256,
+) -> Result<Bytes> {
+    if code_hash == KECCAK_EMPTY {
+        return Ok(Bytes::new())
+    }
+    Ok(db.code_by_hash(code_hash)?.original_bytes())
+}
+
+/// An execution test vector.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionVector {
+    /// Id of the chain of the blocks.
+    pub chain_id: u64,
+    /// Accounts read by the blocks, as of the parent of the first block. Only holds the storage
+    /// slots that are read.
+    pub pre_state: BTreeMap<Address, VectorAccount>,
+    /// Block hashes read by the blocks.
+    pub block_hashes: BTreeMap<BlockNumber, B256>,
+    /// The blocks, in execution order.
+    pub blocks: Vec<VectorBlock>,
+    /// Accounts touched by the blocks, as of the last block, or `None` if they don't exist. Only
+    /// holds the storage slots that are read or written.
+    pub post_state: BTreeMap<Address, Option<VectorAccount>>,
+}
+
+/// A block of an [`ExecutionVector`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorBlock {
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+    /// The senders of the block's transactions.
+    pub senders: Vec<Address>,
+    /// The total difficulty at the block.
+    pub total_difficulty: U256,
+    /// The receipts of the block's transactions.
+    pub receipts: Vec<Receipt>,
+}
+
+/// An account of an [`ExecutionVector`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorAccount {
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance.
+    pub balance: U256,
+    /// The account code, empty if the account has none.
+    pub code: Bytes,
+    /// Storage slots of the account.
+    pub storage: BTreeMap<B256, U256>,
+}
+
+impl VectorAccount {
+    fn code_hash(&self) -> B256 {
+        if self.code.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            keccak256(&self.code)
+        }
+    }
+
+    fn info(&self) -> AccountInfo {
+        AccountInfo {
+            balance: self.balance,
+            nonce: self.nonce,
+            code_hash: self.code_hash(),
+            code: Some(Bytecode::new_raw(self.code.clone())),
+        }
+    }
+}
+
+/// Records the state read through the inner database.
+#[derive(Debug)]
+struct RecordingDatabase<DB> {
+    inner: DB,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<Address, BTreeMap<U256, U256>>,
+    block_hashes: BTreeMap<BlockNumber, B256>,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    fn new(inner: DB) -> Self {
+        Self {
+            inner,
+            accounts: HashMap::new(),
+            storage: HashMap::new(),
+            block_hashes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic(address)?;
+        self.accounts.entry(address).or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.inner.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.inner.storage(address, index)?;
+        self.storage.entry(address).or_default().entry(index).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.inner.block_hash(number)?;
+        self.block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Serves the pre-state of an [`ExecutionVector`].
+#[derive(Debug)]
+struct VectorDatabase<'a> {
+    vector: &'a ExecutionVector,
+    codes: HashMap<B256, Bytecode>,
+}
+
+impl<'a> VectorDatabase<'a> {
+    fn new(vector: &'a ExecutionVector) -> Self {
+        let codes = vector
+            .pre_state
+            .values()
+            .map(|account| (account.code_hash(), Bytecode::new_raw(account.code.clone())))
+            .collect();
+        Self { vector, codes }
+    }
+}
+
+impl Database for VectorDatabase<'_> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Ok(self.vector.pre_state.get(&address).map(VectorAccount::info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Ok(self.codes.get(&code_hash).cloned().unwrap_or_default())
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        Ok(self
+            .vector
+            .pre_state
+            .get(&address)
+            .and_then(|account| account.storage.get(&B256::from(index)).copied())
+            .unwrap_or_default())
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        Ok(self.vector.block_hashes.get(&number).copied().unwrap_or_default())
+    }
+}
@@ -1,20 +1,26 @@
 //! Command for generating test vectors.
 
+use crate::common::CliNodeTypes;
 use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_evm::execute::BlockExecutorProvider;
+use std::sync::Arc;
 
 pub mod compact;
+pub mod execution;
 pub mod tables;
 
 /// Generate test-vectors for different data types.
 #[derive(Debug, Parser)]
-pub struct Command {
+pub struct Command<C: ChainSpecParser> {
     #[command(subcommand)]
-    command: Subcommands,
+    command: Subcommands<C>,
 }
 
 #[derive(Subcommand, Debug)]
 /// `reth test-vectors` subcommands
-pub enum Subcommands {
+pub enum Subcommands<C: ChainSpecParser> {
     /// Generates test vectors for specified tables. If no table is specified, generate for all.
     Tables {
         /// List of table names. Case-sensitive.
@@ -36,11 +42,23 @@ pub enum Subcommands {
         #[arg(long)]
         read: bool,
     },
+    /// Generates an execution test vector for a range of blocks of the chain in the datadir.
+    ///
+    /// The vector holds the blocks, the state they read and the receipts and state they produce,
+    /// so the blocks can be re-executed without the database.
+    ///
+    /// Use the `--read` flag to execute the previously generated vectors.
+    Execution(execution::Command<C>),
 }
 
-impl Command {
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
     /// Execute the command
-    pub async fn execute(self) -> eyre::Result<()> {
+    pub async fn execute<N, E, F>(self, executor: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        E: BlockExecutorProvider,
+        F: FnOnce(Arc<C::ChainSpec>) -> E,
+    {
         match self.command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
@@ -52,6 +70,7 @@ impl Command {
                     compact::read_vectors()?;
                 }
             }
+            Subcommands::Execution(command) => command.execute::<N, _, _>(executor).await?,
         }
         Ok(())
     }
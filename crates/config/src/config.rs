@@ -3,6 +3,7 @@
 use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
+use reth_rpc_server_types::{RpcClientLimitsConfig, TenantConfig};
 use reth_stages_types::ExecutionStageThresholds;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
@@ -31,6 +32,15 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the RPC servers.
+    #[serde(skip_serializing_if = "RpcConfig::is_empty")]
+    pub rpc: RpcConfig,
+    /// Configuration for the transaction pool.
+    #[serde(skip_serializing_if = "TxPoolConfig::is_empty")]
+    pub txpool: TxPoolConfig,
+    /// Configuration for the propagation of transactions to peers.
+    #[serde(skip_serializing_if = "TxPropagationConfig::is_empty")]
+    pub tx_propagation: TxPropagationConfig,
 }
 
 impl Config {
@@ -303,11 +313,17 @@ pub struct MerkleConfig {
     /// The threshold (in number of blocks) for switching from incremental trie building of changes
     /// to whole rebuild.
     pub clean_threshold: u64,
+    /// Verify the state root against the headers every N blocks, instead of only at the end of
+    /// each pipeline run.
+    ///
+    /// The pipeline runs the stages from execution to merkle in ranges aligned to multiples of N,
+    /// so a state root divergence is detected at most N blocks after it occurred.
+    pub state_root_check_interval: Option<u64>,
 }
 
 impl Default for MerkleConfig {
     fn default() -> Self {
-        Self { clean_threshold: 5_000 }
+        Self { clean_threshold: 5_000, state_root_check_interval: None }
     }
 }
 
@@ -373,6 +389,100 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// RPC configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// API key tenants of the HTTP and WS servers, in addition to the tenants of the
+    /// `--rpc.tenants` file.
+    #[serde(rename = "tenant", skip_serializing_if = "Vec::is_empty")]
+    pub tenants: Vec<TenantConfig>,
+    /// Per-client rate limits of the HTTP and WS servers, disabled if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_limits: Option<RpcClientLimitsConfig>,
+}
+
+impl RpcConfig {
+    /// Returns `true` if nothing is configured.
+    pub fn is_empty(&self) -> bool {
+        self.tenants.is_empty() && self.client_limits.is_none()
+    }
+}
+
+/// Transaction pool configuration.
+///
+/// These settings take precedence over the `--txpool.*` arguments and are reloaded while the node
+/// is running. Unset settings keep their current value.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPoolConfig {
+    /// Price bump (in %) required to replace a non-blob transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_bump: Option<u64>,
+    /// Price bump (in %) required to replace a blob transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_price_bump: Option<u64>,
+    /// Max number of executable transaction slots guaranteed per account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_account_slots: Option<usize>,
+    /// Size limits of the pending sub-pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<SubPoolLimitConfig>,
+    /// Size limits of the basefee sub-pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basefee: Option<SubPoolLimitConfig>,
+    /// Size limits of the queued sub-pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued: Option<SubPoolLimitConfig>,
+    /// Size limits of the blob sub-pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<SubPoolLimitConfig>,
+}
+
+impl TxPoolConfig {
+    /// Returns `true` if nothing is configured.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Transaction propagation configuration.
+///
+/// Trades the bandwidth used for gossiping new pending transactions against how fast they spread.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPropagationConfig {
+    /// Percentage of the square root of the number of peers that new transactions are sent to in
+    /// full, e.g. `200` to send them to twice as many peers. All other peers only receive the
+    /// hashes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqrt_fanout_percent: Option<u64>,
+    /// Max size (in bytes) of a transaction that is sent in full, larger transactions are only
+    /// announced by hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_full_broadcast_size: Option<usize>,
+    /// Only announce the hashes of new transactions, never send them in full.
+    pub announce_only: bool,
+    /// Neither announce nor send blob transactions to peers.
+    pub disable_blob_propagation: bool,
+}
+
+impl TxPropagationConfig {
+    /// Returns `true` if nothing is configured.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Size limits of a transaction sub-pool.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SubPoolLimitConfig {
+    /// Max number of transactions in the sub-pool.
+    pub max_count: usize,
+    /// Max combined size (in megabytes) of the transactions in the sub-pool.
+    pub max_size_mb: usize,
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -451,7 +561,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{
+        Config, RpcClientLimitsConfig, SubPoolLimitConfig, TxPoolConfig, TxPropagationConfig,
+        EXTENSION,
+    };
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
@@ -604,6 +717,98 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_load_rpc_tenants() {
+        let s = r#"
+[[rpc.tenant]]
+name = "alice"
+api_key = "d3adb33f"
+namespaces = ["eth"]
+methods = ["eth_get*"]
+requests_per_second = 50
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        assert_eq!(config.rpc.tenants.len(), 1);
+        assert_eq!(config.rpc.tenants[0].name, "alice");
+        assert!(config.rpc.tenants[0].is_method_allowed("eth_getBalance"));
+
+        let loaded: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, loaded);
+    }
+
+    #[test]
+    fn test_load_txpool_config() {
+        let s = r#"
+[txpool]
+price_bump = 20
+blob_price_bump = 150
+max_account_slots = 32
+
+[txpool.pending]
+max_count = 5000
+max_size_mb = 10
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            config.txpool,
+            TxPoolConfig {
+                price_bump: Some(20),
+                blob_price_bump: Some(150),
+                max_account_slots: Some(32),
+                pending: Some(SubPoolLimitConfig { max_count: 5000, max_size_mb: 10 }),
+                ..Default::default()
+            }
+        );
+
+        // the section is omitted if nothing is configured
+        let s = toml::to_string(&Config::default()).unwrap();
+        assert!(!s.contains("txpool"));
+    }
+
+    #[test]
+    fn test_load_tx_propagation_config() {
+        let s = r#"
+[tx_propagation]
+sqrt_fanout_percent = 50
+max_full_broadcast_size = 4096
+disable_blob_propagation = true
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            config.tx_propagation,
+            TxPropagationConfig {
+                sqrt_fanout_percent: Some(50),
+                max_full_broadcast_size: Some(4096),
+                announce_only: false,
+                disable_blob_propagation: true,
+            }
+        );
+
+        // the section is omitted if nothing is configured
+        let s = toml::to_string(&Config::default()).unwrap();
+        assert!(!s.contains("tx_propagation"));
+    }
+
+    #[test]
+    fn test_load_rpc_client_limits() {
+        let s = r#"
+[rpc.client_limits]
+credits_per_second = 10
+burst = 50
+method_costs = { "trace_*" = 25 }
+client_ip_header = "x-forwarded-for"
+"#;
+        let config: Config = toml::from_str(s).unwrap();
+        let limits = config.rpc.client_limits.as_ref().unwrap();
+        assert_eq!(limits.burst.get(), 50);
+        assert_eq!(limits.cost("trace_block"), 25);
+        assert_eq!(limits.cost("debug_traceTransaction"), 1);
+        assert_eq!(limits.ban_threshold, RpcClientLimitsConfig::default().ban_threshold);
+
+        let loaded: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(config, loaded);
+    }
+
     // ensures config deserialization is backwards compatible
     #[test]
     fn test_backwards_compatibility() {
@@ -996,6 +1201,39 @@ connect_trusted_nodes_only = true
         assert!(conf.peers.trusted_nodes_only);
     }
 
+    #[test]
+    fn test_conf_reconnect_backoff() {
+        let reconnect_backoff = r"#
+[peers.reconnect_backoff]
+initial = '1s'
+max = '1m'
+#";
+        let conf: Config = toml::from_str(reconnect_backoff).unwrap();
+        assert_eq!(conf.peers.reconnect_backoff.initial, Duration::from_secs(1));
+        assert_eq!(conf.peers.reconnect_backoff.max, Duration::from_secs(60));
+
+        // configs without the section use the default
+        let conf: Config = toml::from_str("[peers]").unwrap();
+        assert_eq!(conf.peers.reconnect_backoff, Default::default());
+    }
+
+    #[test]
+    fn test_conf_bandwidth_limits() {
+        let bandwidth = r"#
+[sessions.bandwidth]
+max_upload = 10485760
+max_peer_upload = 1048576
+#";
+        let conf: Config = toml::from_str(bandwidth).unwrap();
+        assert_eq!(conf.sessions.bandwidth.max_upload, Some(10 * 1024 * 1024));
+        assert_eq!(conf.sessions.bandwidth.max_download, None);
+        assert_eq!(conf.sessions.bandwidth.max_peer_upload, Some(1024 * 1024));
+
+        // configs without the section are unlimited
+        let conf: Config = toml::from_str("[sessions]").unwrap();
+        assert!(conf.sessions.bandwidth.is_unlimited());
+    }
+
     #[test]
     fn test_can_support_dns_in_trusted_nodes() {
         let reth_toml = r#"
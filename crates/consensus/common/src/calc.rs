@@ -1,6 +1,6 @@
 use alloy_consensus::constants::ETH_TO_WEI;
 use alloy_primitives::{BlockNumber, U256};
-use reth_chainspec::{EthereumHardfork, Hardforks};
+use reth_chainspec::{EthereumHardfork, EthereumHardforks};
 
 /// Calculates the base block reward.
 ///
@@ -22,12 +22,15 @@ use reth_chainspec::{EthereumHardfork, Hardforks};
 ///
 /// [yp]: https://ethereum.github.io/yellowpaper/paper.pdf
 pub fn base_block_reward(
-    chain_spec: impl Hardforks,
+    chain_spec: impl EthereumHardforks,
     block_number: BlockNumber,
     block_difficulty: U256,
     total_difficulty: U256,
 ) -> Option<u128> {
-    if chain_spec.fork(EthereumHardfork::Paris).active_at_ttd(total_difficulty, block_difficulty) {
+    if chain_spec
+        .ethereum_fork_activation(EthereumHardfork::Paris)
+        .active_at_ttd(total_difficulty, block_difficulty)
+    {
         None
     } else {
         Some(base_block_reward_pre_merge(chain_spec, block_number))
@@ -37,10 +40,13 @@ pub fn base_block_reward(
 /// Calculates the base block reward __before__ the merge (Paris hardfork).
 ///
 /// Caution: The caller must ensure that the block number is before the merge.
-pub fn base_block_reward_pre_merge(chain_spec: impl Hardforks, block_number: BlockNumber) -> u128 {
-    if chain_spec.fork(EthereumHardfork::Constantinople).active_at_block(block_number) {
+pub fn base_block_reward_pre_merge(
+    chain_spec: impl EthereumHardforks,
+    block_number: BlockNumber,
+) -> u128 {
+    if chain_spec.is_ethereum_fork_active_at_block(EthereumHardfork::Constantinople, block_number) {
         ETH_TO_WEI * 2
-    } else if chain_spec.fork(EthereumHardfork::Byzantium).active_at_block(block_number) {
+    } else if chain_spec.is_byzantium_active_at_block(block_number) {
         ETH_TO_WEI * 3
     } else {
         ETH_TO_WEI * 5
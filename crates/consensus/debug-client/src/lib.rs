@@ -3,6 +3,9 @@
 //! This is a worker that sends FCUs and new payloads by fetching recent blocks from an external
 //! provider like Etherscan or an RPC endpoint. This allows to quickly test the execution client
 //! without running a consensus node.
+//!
+//! Additionally, [`BeaconLightClient`] follows the beacon chain with a sync committee based light
+//! client, which allows to run a non-staking node without a consensus node.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -13,7 +16,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod client;
+mod light_client;
 mod providers;
 
 pub use client::{block_to_execution_payload_v3, BlockProvider, DebugConsensusClient};
-pub use providers::{EtherscanBlockProvider, RpcBlockProvider};
+pub use light_client::BeaconLightClient;
+pub use providers::{EtherscanBlockProvider, RpcBlockProvider, RpcPollingBlockProvider};
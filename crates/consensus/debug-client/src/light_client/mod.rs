@@ -0,0 +1,272 @@
+//! Beacon light client that drives the engine API.
+
+mod ssz;
+mod store;
+mod types;
+
+use crate::client::ExecutionNewPayload;
+use alloy_eips::{eip4844::kzg_to_versioned_hash, eip7685::Requests};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::ForkchoiceState;
+use eyre::ensure;
+use reqwest::Client;
+use reth_node_api::EngineTypes;
+use reth_rpc_builder::auth::AuthServerHandle;
+use reth_tracing::tracing::{info, warn};
+use serde::de::DeserializeOwned;
+use ssz::HashTreeRoot;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{sync_committee_period, ChainConfig, LightClientStore};
+use tokio::time::{interval, sleep};
+use types::{
+    BeaconResponse, Fork, Genesis, HeaderData, LightClientBootstrap, LightClientHeader,
+    LightClientUpdate, SignedBeaconBlock,
+};
+
+/// Number of seconds per slot.
+const SECONDS_PER_SLOT: u64 = 12;
+/// Maximum number of light client updates that can be requested at once.
+const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// Consensus client that follows the beacon chain with a sync committee based light client and
+/// sends FCUs and new payloads for the verified headers to the execution client.
+///
+/// The light client is bootstrapped from a trusted beacon block root, every header it follows
+/// afterwards is verified against the signatures of the sync committees. The beacon node API is
+/// only used to fetch the light client data and the execution payloads of the verified headers, so
+/// the beacon node doesn't need to be trusted.
+///
+/// This doesn't participate in consensus and is therefore only suitable for non-staking nodes.
+#[derive(Debug)]
+pub struct BeaconLightClient {
+    /// Handle to execution client.
+    auth_server: AuthServerHandle,
+    /// Client of the beacon node API to fetch the light client data from.
+    beacon_api: BeaconApiClient,
+    /// The trusted beacon block root to bootstrap from. If not set, the finalized block of the
+    /// beacon node is used.
+    checkpoint: Option<B256>,
+    /// The interval at which the light client polls for updates.
+    interval: Duration,
+}
+
+impl BeaconLightClient {
+    /// Creates a new light client with the given handle to the execution client and the URL of
+    /// the beacon node API.
+    pub fn new(auth_server: AuthServerHandle, beacon_api_url: String) -> Self {
+        Self {
+            auth_server,
+            beacon_api: BeaconApiClient { http_client: Client::new(), base_url: beacon_api_url },
+            checkpoint: None,
+            interval: Duration::from_secs(4),
+        }
+    }
+
+    /// Sets the trusted beacon block root to bootstrap from.
+    pub const fn with_checkpoint(mut self, checkpoint: B256) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Sets the interval at which the light client polls for updates.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Runs the light client, bootstrapping it first, and sends FCUs and new payloads to the
+    /// execution client whenever the verified head changes.
+    pub async fn run<T: EngineTypes>(self) {
+        let (mut store, genesis_time) = loop {
+            match self.bootstrap().await {
+                Ok(store) => break store,
+                Err(err) => {
+                    warn!(target: "consensus::light-client", %err, "failed to bootstrap light client");
+                    sleep(self.interval).await;
+                }
+            }
+        };
+        info!(target: "consensus::light-client", slot = store.finalized_header().beacon.slot, "Bootstrapped beacon light client");
+
+        let execution_client = self.auth_server.http_client();
+        let mut head_block_hash = None;
+        let mut interval = interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.sync(&mut store, genesis_time).await {
+                warn!(target: "consensus::light-client", %err, "failed to process light client updates");
+            }
+
+            let head = store.optimistic_header();
+            let block_hash = head.execution.block_hash;
+            if head_block_hash == Some(block_hash) {
+                continue
+            }
+
+            let (payload, execution_requests) = match self.fetch_payload(head).await {
+                Ok(payload) => payload,
+                Err(err) => {
+                    warn!(target: "consensus::light-client", %err, %block_hash, "failed to fetch execution payload");
+                    continue
+                }
+            };
+            let block_number = payload.block_number();
+            let status = match execution_requests {
+                Some(execution_requests) => {
+                    reth_rpc_api::EngineApiClient::<T>::new_payload_v4(
+                        &execution_client,
+                        payload.execution_payload_v3,
+                        payload.versioned_hashes,
+                        payload.parent_beacon_block_root,
+                        execution_requests,
+                    )
+                    .await
+                }
+                None => {
+                    reth_rpc_api::EngineApiClient::<T>::new_payload_v3(
+                        &execution_client,
+                        payload.execution_payload_v3,
+                        payload.versioned_hashes,
+                        payload.parent_beacon_block_root,
+                    )
+                    .await
+                }
+            };
+            match status {
+                Ok(status) if status.is_invalid() => {
+                    warn!(target: "consensus::light-client", ?status, %block_hash, block_number, "execution client rejected new payload");
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(target: "consensus::light-client", %err, %block_hash, block_number, "failed to submit new payload to execution client");
+                }
+            }
+
+            // A light client doesn't follow the justified checkpoints, so the finalized block is
+            // also used as the safe block.
+            let finalized_block_hash = store.finalized_header().execution.block_hash;
+            let state = ForkchoiceState {
+                head_block_hash: block_hash,
+                safe_block_hash: finalized_block_hash,
+                finalized_block_hash,
+            };
+            match reth_rpc_api::EngineApiClient::<T>::fork_choice_updated_v3(
+                &execution_client,
+                state,
+                None,
+            )
+            .await
+            {
+                Ok(_) => head_block_hash = Some(block_hash),
+                Err(err) => {
+                    warn!(target: "consensus::light-client", %err, ?state, "failed to submit fork choice update to execution client");
+                }
+            }
+        }
+    }
+
+    /// Bootstraps the light client store from the trusted block root and returns it together
+    /// with the genesis time of the beacon chain.
+    async fn bootstrap(&self) -> eyre::Result<(LightClientStore, u64)> {
+        let genesis: Genesis = self.beacon_api.get("/eth/v1/beacon/genesis").await?;
+        let forks: Vec<Fork> = self.beacon_api.get("/eth/v1/config/fork_schedule").await?;
+
+        let checkpoint = match self.checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let header: HeaderData =
+                    self.beacon_api.get("/eth/v1/beacon/headers/finalized").await?;
+                warn!(target: "consensus::light-client", root = %header.root, "No beacon checkpoint configured, trusting the finalized block of the beacon node");
+                header.root
+            }
+        };
+        let bootstrap: LightClientBootstrap = self
+            .beacon_api
+            .get(&format!("/eth/v1/beacon/light_client/bootstrap/{checkpoint}"))
+            .await?;
+
+        let config =
+            ChainConfig { genesis_validators_root: genesis.genesis_validators_root, forks };
+        Ok((LightClientStore::bootstrap(config, checkpoint, bootstrap)?, genesis.genesis_time))
+    }
+
+    /// Advances the store with the latest light client updates of the beacon node.
+    async fn sync(&self, store: &mut LightClientStore, genesis_time: u64) -> eyre::Result<()> {
+        // Catch up with the sync committee periods after the period of the store, and learn the
+        // next sync committee, which signs the updates after the end of the period.
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let current_period =
+            sync_committee_period(now.saturating_sub(genesis_time) / SECONDS_PER_SLOT);
+        if store.period() < current_period || !store.has_next_sync_committee() {
+            let count = (current_period.saturating_sub(store.period()) + 1)
+                .min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+            let updates: Vec<BeaconResponse<LightClientUpdate>> = self
+                .beacon_api
+                .get_unwrapped(&format!(
+                    "/eth/v1/beacon/light_client/updates?start_period={}&count={count}",
+                    store.period()
+                ))
+                .await?;
+            for update in updates {
+                store.process_update(update.data)?;
+            }
+        }
+
+        let update = self.beacon_api.get("/eth/v1/beacon/light_client/finality_update").await?;
+        store.process_update(update)?;
+        let update = self.beacon_api.get("/eth/v1/beacon/light_client/optimistic_update").await?;
+        store.process_update(update)?;
+
+        Ok(())
+    }
+
+    /// Fetches the execution payload of the verified header, together with the execution requests
+    /// since Electra.
+    async fn fetch_payload(
+        &self,
+        header: &LightClientHeader,
+    ) -> eyre::Result<(ExecutionNewPayload, Option<Requests>)> {
+        let block_root = header.beacon.hash_tree_root();
+        let block: SignedBeaconBlock =
+            self.beacon_api.get(&format!("/eth/v2/beacon/blocks/{block_root}")).await?;
+        let body = block.message.body;
+
+        // The block hash commits to the whole execution block, which is validated by the
+        // execution client.
+        let payload = ExecutionNewPayload {
+            execution_payload_v3: body.execution_payload,
+            versioned_hashes: body
+                .blob_kzg_commitments
+                .iter()
+                .map(|commitment| kzg_to_versioned_hash(commitment.as_slice()))
+                .collect(),
+            parent_beacon_block_root: header.beacon.parent_root,
+        };
+        ensure!(
+            payload.block_hash() == header.execution.block_hash,
+            "execution payload does not match the verified header"
+        );
+
+        Ok((payload, body.execution_requests.map(|requests| requests.to_requests())))
+    }
+}
+
+/// Client of the beacon node API.
+#[derive(Debug, Clone)]
+struct BeaconApiClient {
+    http_client: Client,
+    base_url: String,
+}
+
+impl BeaconApiClient {
+    /// Fetches the object at the given path, which is wrapped in a `data` field.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> eyre::Result<T> {
+        self.get_unwrapped::<BeaconResponse<T>>(path).await.map(|response| response.data)
+    }
+
+    /// Fetches the response at the given path.
+    async fn get_unwrapped<T: DeserializeOwned>(&self, path: &str) -> eyre::Result<T> {
+        let url = format!("{}{path}", self.base_url.trim_end_matches('/'));
+        Ok(self.http_client.get(url).send().await?.error_for_status()?.json().await?)
+    }
+}
@@ -0,0 +1,158 @@
+//! SSZ merkleization of the beacon chain containers used by the light client.
+//!
+//! See also <https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md#merkleization>
+
+use super::types::{ExecutionPayloadHeader, SyncCommittee};
+use alloy_primitives::{FixedBytes, B256};
+use alloy_rpc_types_beacon::header::BeaconBlockHeader;
+use sha2::{Digest, Sha256};
+
+/// Types that can be merkleized into an SSZ hash tree root.
+pub(crate) trait HashTreeRoot {
+    /// Returns the SSZ hash tree root.
+    fn hash_tree_root(&self) -> B256;
+}
+
+impl HashTreeRoot for BeaconBlockHeader {
+    fn hash_tree_root(&self) -> B256 {
+        merkleize(
+            vec![
+                uint64_chunk(self.slot),
+                uint64_chunk(self.proposer_index),
+                self.parent_root,
+                self.state_root,
+                self.body_root,
+            ],
+            0,
+        )
+    }
+}
+
+impl HashTreeRoot for SyncCommittee {
+    fn hash_tree_root(&self) -> B256 {
+        let pubkeys = self.pubkeys.iter().map(bytes_root).collect();
+        hash(merkleize(pubkeys, 0), bytes_root(&self.aggregate_pubkey))
+    }
+}
+
+impl HashTreeRoot for ExecutionPayloadHeader {
+    fn hash_tree_root(&self) -> B256 {
+        let mut fee_recipient = B256::ZERO;
+        fee_recipient[..20].copy_from_slice(self.fee_recipient.as_slice());
+
+        // `extra_data` is a `ByteList[MAX_EXTRA_DATA_BYTES]`, which fits into a single chunk.
+        let extra_data = mix_in_length(
+            merkleize(self.extra_data.chunks(32).map(B256::right_padding_from).collect(), 1),
+            self.extra_data.len(),
+        );
+
+        merkleize(
+            vec![
+                self.parent_hash,
+                fee_recipient,
+                self.state_root,
+                self.receipts_root,
+                bytes_root(&self.logs_bloom.0),
+                self.prev_randao,
+                uint64_chunk(self.block_number),
+                uint64_chunk(self.gas_limit),
+                uint64_chunk(self.gas_used),
+                uint64_chunk(self.timestamp),
+                extra_data,
+                self.base_fee_per_gas.to_le_bytes().into(),
+                self.block_hash,
+                self.transactions_root,
+                self.withdrawals_root,
+                uint64_chunk(self.blob_gas_used),
+                uint64_chunk(self.excess_blob_gas),
+            ],
+            0,
+        )
+    }
+}
+
+/// Returns the SHA-256 hash of the concatenation of two chunks.
+pub(crate) fn hash(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Merkleizes the chunks into a binary tree, padded with zero chunks to the next power of two of
+/// the number of chunks or the given limit, whichever is larger.
+pub(crate) fn merkleize(mut chunks: Vec<B256>, limit: usize) -> B256 {
+    let width = chunks.len().max(limit).max(1).next_power_of_two();
+    chunks.resize(width, B256::ZERO);
+    while chunks.len() > 1 {
+        chunks = chunks.chunks(2).map(|pair| hash(pair[0], pair[1])).collect();
+    }
+    chunks[0]
+}
+
+/// Mixes the length of a list into its merkleized root.
+fn mix_in_length(root: B256, len: usize) -> B256 {
+    hash(root, uint64_chunk(len as u64))
+}
+
+/// Returns the chunk of a little-endian encoded `uint64`.
+fn uint64_chunk(value: u64) -> B256 {
+    B256::right_padding_from(&value.to_le_bytes())
+}
+
+/// Returns the hash tree root of a fixed size byte vector.
+fn bytes_root<const N: usize>(bytes: &FixedBytes<N>) -> B256 {
+    merkleize(bytes.chunks(32).map(B256::right_padding_from).collect(), 0)
+}
+
+/// Returns whether the branch proves that the leaf is at the given index of a tree with the given
+/// depth and root.
+pub(crate) fn is_valid_merkle_branch(
+    leaf: B256,
+    branch: &[B256],
+    depth: usize,
+    index: u64,
+    root: B256,
+) -> bool {
+    if branch.len() != depth {
+        return false
+    }
+    let computed = branch.iter().enumerate().fold(leaf, |node, (height, sibling)| {
+        if (index >> height) & 1 == 1 {
+            hash(*sibling, node)
+        } else {
+            hash(node, *sibling)
+        }
+    });
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_merkle_branch() {
+        let leaves = (0..8u8).map(B256::with_last_byte).collect::<Vec<_>>();
+        let root = merkleize(leaves.clone(), 0);
+
+        // branch of the leaf at index 5
+        let branch = vec![
+            leaves[4],
+            hash(leaves[6], leaves[7]),
+            hash(hash(leaves[0], leaves[1]), hash(leaves[2], leaves[3])),
+        ];
+        assert!(is_valid_merkle_branch(leaves[5], &branch, 3, 5, root));
+        assert!(!is_valid_merkle_branch(leaves[5], &branch, 3, 4, root));
+        assert!(!is_valid_merkle_branch(leaves[4], &branch, 3, 5, root));
+        assert!(!is_valid_merkle_branch(leaves[5], &branch[..2], 2, 5, root));
+    }
+
+    #[test]
+    fn pads_to_limit() {
+        let chunk = B256::with_last_byte(1);
+        assert_eq!(merkleize(vec![chunk], 0), chunk);
+        assert_eq!(merkleize(vec![chunk], 2), hash(chunk, B256::ZERO));
+        assert_eq!(merkleize(vec![], 0), B256::ZERO);
+    }
+}
@@ -0,0 +1,346 @@
+//! Verification of light client data signed by the sync committees of the beacon chain.
+//!
+//! See also <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md>
+
+use super::{
+    ssz::{hash, is_valid_merkle_branch, HashTreeRoot},
+    types::{
+        Fork, LightClientBootstrap, LightClientHeader, LightClientUpdate, SyncAggregate,
+        SyncCommittee,
+    },
+};
+use alloy_primitives::{FixedBytes, B256};
+use alloy_rpc_types_beacon::header::BeaconBlockHeader;
+use blst::{
+    min_pk::{AggregatePublicKey, PublicKey, Signature},
+    BLST_ERROR,
+};
+use eyre::{bail, ensure, OptionExt};
+
+/// Number of slots per epoch.
+const SLOTS_PER_EPOCH: u64 = 32;
+/// Number of epochs per sync committee period.
+const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+/// Number of members of a sync committee.
+const SYNC_COMMITTEE_SIZE: usize = 512;
+/// Domain type of sync committee signatures.
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+/// Domain separation tag of the BLS signatures of the beacon chain.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Index of Electra in the fork schedule, which changes the depth of the beacon state tree.
+const ELECTRA_FORK_INDEX: usize = 5;
+
+/// Index of the execution payload in the beacon block body tree.
+const EXECUTION_PAYLOAD_INDEX: u64 = 9;
+/// Depth of the beacon block body tree.
+const EXECUTION_PAYLOAD_DEPTH: usize = 4;
+/// Index of the current sync committee in the beacon state tree.
+const CURRENT_SYNC_COMMITTEE_INDEX: u64 = 22;
+/// Index of the next sync committee in the beacon state tree.
+const NEXT_SYNC_COMMITTEE_INDEX: u64 = 23;
+/// Index of the finalized checkpoint root in the beacon state tree, one level below the fields of
+/// the state.
+const FINALIZED_ROOT_INDEX: u64 = 41;
+
+/// Returns the sync committee period of the slot.
+pub(crate) const fn sync_committee_period(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// Chain configuration required to verify sync committee signatures.
+#[derive(Debug, Clone)]
+pub(crate) struct ChainConfig {
+    /// The root of the validators at genesis.
+    pub(crate) genesis_validators_root: B256,
+    /// The forks of the chain, ordered by activation epoch.
+    pub(crate) forks: Vec<Fork>,
+}
+
+impl ChainConfig {
+    /// Returns the fork version that is active at the epoch.
+    fn fork_version(&self, epoch: u64) -> eyre::Result<FixedBytes<4>> {
+        self.forks
+            .iter()
+            .rev()
+            .find(|fork| fork.epoch <= epoch)
+            .map(|fork| fork.current_version)
+            .ok_or_eyre("no fork active at epoch")
+    }
+
+    /// Returns the depth of the beacon state tree at the slot.
+    fn state_depth(&self, slot: u64) -> usize {
+        let electra = self
+            .forks
+            .get(ELECTRA_FORK_INDEX)
+            .is_some_and(|fork| fork.epoch <= slot / SLOTS_PER_EPOCH);
+        if electra {
+            6
+        } else {
+            5
+        }
+    }
+}
+
+/// Sync committee with its decompressed public keys.
+#[derive(Debug)]
+struct SyncCommitteeKeys(Vec<PublicKey>);
+
+impl SyncCommitteeKeys {
+    fn new(committee: &SyncCommittee) -> eyre::Result<Self> {
+        ensure!(committee.pubkeys.len() == SYNC_COMMITTEE_SIZE, "invalid sync committee size");
+        committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| {
+                PublicKey::key_validate(pubkey.as_slice())
+                    .map_err(|err| eyre::eyre!("invalid sync committee public key: {err:?}"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// State of the light client, which only advances with updates that are signed by a supermajority
+/// of the known sync committees.
+#[derive(Debug)]
+pub(crate) struct LightClientStore {
+    config: ChainConfig,
+    /// The latest verified finalized header.
+    finalized_header: LightClientHeader,
+    /// The latest verified header, which is not necessarily finalized.
+    optimistic_header: LightClientHeader,
+    /// Sync committee of the period of the finalized header.
+    current_sync_committee: SyncCommitteeKeys,
+    /// Sync committee of the period after the period of the finalized header, if known.
+    next_sync_committee: Option<SyncCommitteeKeys>,
+}
+
+impl LightClientStore {
+    /// Initializes the store from the bootstrap of the trusted block root.
+    pub(crate) fn bootstrap(
+        config: ChainConfig,
+        trusted_block_root: B256,
+        bootstrap: LightClientBootstrap,
+    ) -> eyre::Result<Self> {
+        let LightClientBootstrap { header, current_sync_committee, current_sync_committee_branch } =
+            bootstrap;
+        verify_header(&header)?;
+        ensure!(
+            header.beacon.hash_tree_root() == trusted_block_root,
+            "bootstrap header does not match the trusted block root"
+        );
+        ensure!(
+            is_valid_merkle_branch(
+                current_sync_committee.hash_tree_root(),
+                &current_sync_committee_branch,
+                config.state_depth(header.beacon.slot),
+                CURRENT_SYNC_COMMITTEE_INDEX,
+                header.beacon.state_root,
+            ),
+            "invalid current sync committee branch"
+        );
+
+        Ok(Self {
+            config,
+            optimistic_header: header.clone(),
+            finalized_header: header,
+            current_sync_committee: SyncCommitteeKeys::new(&current_sync_committee)?,
+            next_sync_committee: None,
+        })
+    }
+
+    /// Returns the latest verified finalized header.
+    pub(crate) const fn finalized_header(&self) -> &LightClientHeader {
+        &self.finalized_header
+    }
+
+    /// Returns the latest verified header.
+    pub(crate) const fn optimistic_header(&self) -> &LightClientHeader {
+        &self.optimistic_header
+    }
+
+    /// Returns the sync committee period of the store.
+    pub(crate) const fn period(&self) -> u64 {
+        sync_committee_period(self.finalized_header.beacon.slot)
+    }
+
+    /// Returns whether the sync committee of the next period is known.
+    pub(crate) const fn has_next_sync_committee(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// Verifies the update and applies it to the store.
+    ///
+    /// Headers of the update that are older than the headers of the store are ignored.
+    pub(crate) fn process_update(&mut self, update: LightClientUpdate) -> eyre::Result<()> {
+        let LightClientUpdate {
+            attested_header,
+            next_sync_committee,
+            next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_aggregate,
+            signature_slot,
+        } = update;
+
+        let store_period = self.period();
+        let attested_slot = attested_header.beacon.slot;
+        ensure!(signature_slot > attested_slot, "update is signed before the attested slot");
+        verify_header(&attested_header)?;
+        let state_depth = self.config.state_depth(attested_slot);
+
+        // the updates of the finality endpoint don't include the next sync committee, and the
+        // branches of updates without the respective data are zero
+        let finalized_header = match (finalized_header, finality_branch) {
+            (Some(header), Some(branch)) if !is_zero_branch(&branch) => {
+                verify_header(&header)?;
+                ensure!(header.beacon.slot <= attested_slot, "finalized header after attested");
+                ensure!(
+                    is_valid_merkle_branch(
+                        header.beacon.hash_tree_root(),
+                        &branch,
+                        state_depth + 1,
+                        FINALIZED_ROOT_INDEX,
+                        attested_header.beacon.state_root,
+                    ),
+                    "invalid finality branch"
+                );
+                Some(header)
+            }
+            _ => None,
+        };
+
+        // the store advances to the next period if the finalized header is in the next period
+        let finalized_header = finalized_header
+            .filter(|header| header.beacon.slot > self.finalized_header.beacon.slot);
+        let rotate = finalized_header
+            .as_ref()
+            .is_some_and(|header| sync_committee_period(header.beacon.slot) == store_period + 1);
+        ensure!(!rotate || self.next_sync_committee.is_some(), "next sync committee unknown");
+        let new_period = store_period + rotate as u64;
+
+        let next_sync_committee = match (next_sync_committee, next_sync_committee_branch) {
+            (Some(committee), Some(branch))
+                if !is_zero_branch(&branch) &&
+                    sync_committee_period(attested_slot) == new_period &&
+                    (rotate || self.next_sync_committee.is_none()) =>
+            {
+                ensure!(
+                    is_valid_merkle_branch(
+                        committee.hash_tree_root(),
+                        &branch,
+                        state_depth,
+                        NEXT_SYNC_COMMITTEE_INDEX,
+                        attested_header.beacon.state_root,
+                    ),
+                    "invalid next sync committee branch"
+                );
+                Some(SyncCommitteeKeys::new(&committee)?)
+            }
+            _ => None,
+        };
+
+        let signature_period = sync_committee_period(signature_slot);
+        let sync_committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else if signature_period == store_period + 1 {
+            self.next_sync_committee.as_ref().ok_or_eyre("next sync committee unknown")?
+        } else {
+            bail!("update is not signed by the current or next sync committee")
+        };
+        self.verify_sync_aggregate(
+            sync_committee,
+            &attested_header.beacon,
+            &sync_aggregate,
+            signature_slot,
+        )?;
+
+        // apply the verified update
+        if rotate {
+            self.current_sync_committee =
+                self.next_sync_committee.take().expect("checked above; qed");
+        }
+        if let Some(finalized_header) = finalized_header {
+            self.finalized_header = finalized_header;
+            if self.finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+                self.optimistic_header = self.finalized_header.clone();
+            }
+        }
+        if next_sync_committee.is_some() {
+            self.next_sync_committee = next_sync_committee;
+        }
+        if attested_slot > self.optimistic_header.beacon.slot {
+            self.optimistic_header = attested_header;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the header was signed by a supermajority of the sync committee.
+    fn verify_sync_aggregate(
+        &self,
+        sync_committee: &SyncCommitteeKeys,
+        header: &BeaconBlockHeader,
+        sync_aggregate: &SyncAggregate,
+        signature_slot: u64,
+    ) -> eyre::Result<()> {
+        let bits = &sync_aggregate.sync_committee_bits;
+        ensure!(bits.len() * 8 == SYNC_COMMITTEE_SIZE, "invalid sync committee bits");
+        let participants = sync_committee
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| bits[index / 8] & (1 << (index % 8)) != 0)
+            .map(|(_, pubkey)| pubkey)
+            .collect::<Vec<_>>();
+        ensure!(
+            participants.len() * 3 >= SYNC_COMMITTEE_SIZE * 2,
+            "insufficient sync committee participation: {}",
+            participants.len()
+        );
+
+        let fork_version_epoch = signature_slot.max(1).saturating_sub(1) / SLOTS_PER_EPOCH;
+        let fork_version = self.config.fork_version(fork_version_epoch)?;
+        let fork_data_root = hash(
+            B256::right_padding_from(fork_version.as_slice()),
+            self.config.genesis_validators_root,
+        );
+        let mut domain = B256::ZERO;
+        domain[..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+        domain[4..].copy_from_slice(&fork_data_root[..28]);
+        let signing_root = hash(header.hash_tree_root(), domain);
+
+        let pubkey = AggregatePublicKey::aggregate(&participants, false)
+            .map_err(|err| eyre::eyre!("failed to aggregate public keys: {err:?}"))?
+            .to_public_key();
+        let signature = Signature::from_bytes(sync_aggregate.sync_committee_signature.as_slice())
+            .map_err(|err| eyre::eyre!("invalid sync committee signature: {err:?}"))?;
+        ensure!(
+            signature.verify(true, signing_root.as_slice(), BLS_DST, &[], &pubkey, false) ==
+                BLST_ERROR::BLST_SUCCESS,
+            "invalid sync committee signature"
+        );
+
+        Ok(())
+    }
+}
+
+/// Verifies that the execution payload header is part of the beacon block.
+fn verify_header(header: &LightClientHeader) -> eyre::Result<()> {
+    ensure!(
+        is_valid_merkle_branch(
+            header.execution.hash_tree_root(),
+            &header.execution_branch,
+            EXECUTION_PAYLOAD_DEPTH,
+            EXECUTION_PAYLOAD_INDEX,
+            header.beacon.body_root,
+        ),
+        "invalid execution payload branch"
+    );
+    Ok(())
+}
+
+/// Returns whether the branch is zero, which denotes the absence of the proven data.
+fn is_zero_branch(branch: &[B256]) -> bool {
+    branch.iter().all(|node| node.is_zero())
+}
@@ -0,0 +1,249 @@
+//! Beacon node API types used by the light client.
+//!
+//! The beacon node API encodes integers as quoted decimals.
+
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::{Address, Bloom, Bytes, FixedBytes, B256, U256};
+use alloy_rpc_types_beacon::{
+    header::BeaconBlockHeader, payload::beacon_payload_v3, BlsPublicKey, BlsSignature,
+};
+use alloy_rpc_types_engine::ExecutionPayloadV3;
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+/// Response of the beacon node API that wraps the returned object.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BeaconResponse<T> {
+    /// The returned object.
+    pub(crate) data: T,
+}
+
+/// Response of `GET /eth/v1/beacon/genesis`.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub(crate) struct Genesis {
+    /// The genesis time of the beacon chain.
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) genesis_time: u64,
+    /// The root of the validators at genesis, which is part of every signing domain.
+    pub(crate) genesis_validators_root: B256,
+}
+
+/// Fork of the beacon chain, as returned by `GET /eth/v1/config/fork_schedule`.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Fork {
+    /// The fork version after the fork.
+    pub(crate) current_version: FixedBytes<4>,
+    /// The epoch at which the fork activates.
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) epoch: u64,
+}
+
+/// Response of `GET /eth/v1/beacon/headers/{block_id}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct HeaderData {
+    /// The root of the beacon block.
+    pub(crate) root: B256,
+}
+
+/// Header of a beacon block together with the header of its execution payload, as used by the
+/// light client protocol since Capella.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct LightClientHeader {
+    /// The beacon block header.
+    pub(crate) beacon: BeaconBlockHeader,
+    /// The header of the execution payload of the beacon block.
+    pub(crate) execution: ExecutionPayloadHeader,
+    /// Merkle branch of the execution payload header in the beacon block body.
+    pub(crate) execution_branch: Vec<B256>,
+}
+
+/// Header of an execution payload since Deneb.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ExecutionPayloadHeader {
+    pub(crate) parent_hash: B256,
+    pub(crate) fee_recipient: Address,
+    pub(crate) state_root: B256,
+    pub(crate) receipts_root: B256,
+    pub(crate) logs_bloom: Bloom,
+    pub(crate) prev_randao: B256,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) block_number: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) gas_limit: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) gas_used: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) timestamp: u64,
+    pub(crate) extra_data: Bytes,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) base_fee_per_gas: U256,
+    pub(crate) block_hash: B256,
+    pub(crate) transactions_root: B256,
+    pub(crate) withdrawals_root: B256,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) blob_gas_used: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) excess_blob_gas: u64,
+}
+
+/// Public keys of the members of a sync committee.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SyncCommittee {
+    /// Public keys of the members.
+    pub(crate) pubkeys: Vec<BlsPublicKey>,
+    /// Aggregate of the public keys of all members.
+    pub(crate) aggregate_pubkey: BlsPublicKey,
+}
+
+/// Aggregate signature of the participating members of a sync committee.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SyncAggregate {
+    /// Bitvector of the participating members.
+    pub(crate) sync_committee_bits: Bytes,
+    /// Aggregate signature of the participating members.
+    pub(crate) sync_committee_signature: BlsSignature,
+}
+
+/// Response of `GET /eth/v1/beacon/light_client/bootstrap/{block_root}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LightClientBootstrap {
+    /// Header of the requested block.
+    pub(crate) header: LightClientHeader,
+    /// The sync committee of the period of the requested block.
+    pub(crate) current_sync_committee: SyncCommittee,
+    /// Merkle branch of the sync committee in the state of the requested block.
+    pub(crate) current_sync_committee_branch: Vec<B256>,
+}
+
+/// Light client update signed by a sync committee.
+///
+/// This is the response of `GET /eth/v1/beacon/light_client/updates`, and, with a subset of the
+/// fields, of the finality and optimistic update endpoints.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub(crate) struct LightClientUpdate {
+    /// The header that was signed by the sync committee.
+    pub(crate) attested_header: LightClientHeader,
+    /// The next sync committee in the state of the attested header.
+    #[serde(default)]
+    pub(crate) next_sync_committee: Option<SyncCommittee>,
+    /// Merkle branch of the next sync committee in the state of the attested header.
+    #[serde(default)]
+    pub(crate) next_sync_committee_branch: Option<Vec<B256>>,
+    /// The finalized header in the state of the attested header.
+    #[serde(default)]
+    pub(crate) finalized_header: Option<LightClientHeader>,
+    /// Merkle branch of the finalized block root in the state of the attested header.
+    #[serde(default)]
+    pub(crate) finality_branch: Option<Vec<B256>>,
+    /// Aggregate signature of the sync committee over the attested header.
+    pub(crate) sync_aggregate: SyncAggregate,
+    /// The slot at which the signature was included.
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) signature_slot: u64,
+}
+
+/// Response of `GET /eth/v2/beacon/blocks/{block_id}`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SignedBeaconBlock {
+    /// The beacon block.
+    pub(crate) message: BeaconBlock,
+}
+
+/// A beacon block, with the fields required to build the execution payload.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BeaconBlock {
+    /// The body of the beacon block.
+    pub(crate) body: BeaconBlockBody,
+}
+
+/// The body of a beacon block, with the fields required to build the execution payload.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BeaconBlockBody {
+    /// The execution payload.
+    #[serde(with = "beacon_payload_v3")]
+    pub(crate) execution_payload: ExecutionPayloadV3,
+    /// The KZG commitments of the blobs of the execution payload.
+    pub(crate) blob_kzg_commitments: Vec<FixedBytes<48>>,
+    /// The execution layer requests, since Electra.
+    #[serde(default)]
+    pub(crate) execution_requests: Option<ExecutionRequests>,
+}
+
+/// Execution layer requests of a beacon block.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExecutionRequests {
+    /// EIP-6110 deposit requests.
+    pub(crate) deposits: Vec<DepositRequest>,
+    /// EIP-7002 withdrawal requests.
+    pub(crate) withdrawals: Vec<WithdrawalRequest>,
+    /// EIP-7251 consolidation requests.
+    pub(crate) consolidations: Vec<ConsolidationRequest>,
+}
+
+impl ExecutionRequests {
+    /// Returns the EIP-7685 request data of the requests, ordered by request type.
+    ///
+    /// All request types are fixed size containers, so the SSZ encoding of each list is the
+    /// concatenation of its elements.
+    pub(crate) fn to_requests(&self) -> Requests {
+        let mut deposits = Vec::new();
+        for request in &self.deposits {
+            deposits.extend_from_slice(request.pubkey.as_slice());
+            deposits.extend_from_slice(request.withdrawal_credentials.as_slice());
+            deposits.extend_from_slice(&request.amount.to_le_bytes());
+            deposits.extend_from_slice(request.signature.as_slice());
+            deposits.extend_from_slice(&request.index.to_le_bytes());
+        }
+
+        let mut withdrawals = Vec::new();
+        for request in &self.withdrawals {
+            withdrawals.extend_from_slice(request.source_address.as_slice());
+            withdrawals.extend_from_slice(request.validator_pubkey.as_slice());
+            withdrawals.extend_from_slice(&request.amount.to_le_bytes());
+        }
+
+        let mut consolidations = Vec::new();
+        for request in &self.consolidations {
+            consolidations.extend_from_slice(request.source_address.as_slice());
+            consolidations.extend_from_slice(request.source_pubkey.as_slice());
+            consolidations.extend_from_slice(request.target_pubkey.as_slice());
+        }
+
+        Requests::new(vec![deposits.into(), withdrawals.into(), consolidations.into()])
+    }
+}
+
+/// EIP-6110 deposit request.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub(crate) struct DepositRequest {
+    pub(crate) pubkey: BlsPublicKey,
+    pub(crate) withdrawal_credentials: B256,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) amount: u64,
+    pub(crate) signature: BlsSignature,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) index: u64,
+}
+
+/// EIP-7002 withdrawal request.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub(crate) struct WithdrawalRequest {
+    pub(crate) source_address: Address,
+    pub(crate) validator_pubkey: BlsPublicKey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub(crate) amount: u64,
+}
+
+/// EIP-7251 consolidation request.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ConsolidationRequest {
+    pub(crate) source_address: Address,
+    pub(crate) source_pubkey: BlsPublicKey,
+    pub(crate) target_pubkey: BlsPublicKey,
+}
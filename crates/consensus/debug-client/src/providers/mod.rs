@@ -2,4 +2,4 @@ mod etherscan;
 mod rpc;
 
 pub use etherscan::EtherscanBlockProvider;
-pub use rpc::RpcBlockProvider;
+pub use rpc::{RpcBlockProvider, RpcPollingBlockProvider};
@@ -3,7 +3,9 @@ use alloy_eips::BlockNumberOrTag;
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types_eth::{Block, BlockTransactionsKind};
 use futures::StreamExt;
-use tokio::sync::mpsc::Sender;
+use reth_tracing::tracing::warn;
+use std::time::Duration;
+use tokio::{sync::mpsc::Sender, time::interval};
 
 /// Block provider that fetches new blocks from an RPC endpoint using a websocket connection.
 #[derive(Debug, Clone)]
@@ -55,3 +57,87 @@ impl BlockProvider for RpcBlockProvider {
         Ok(block)
     }
 }
+
+/// Block provider that follows the chain of an RPC endpoint by polling it over HTTP.
+///
+/// Every block is fetched with `eth_getBlockByNumber`, so no blocks are skipped if the endpoint
+/// advances by more than one block between polls.
+#[derive(Debug, Clone)]
+pub struct RpcPollingBlockProvider {
+    rpc_url: String,
+    interval: Duration,
+    start_block: Option<u64>,
+}
+
+impl RpcPollingBlockProvider {
+    /// Create a new polling RPC block provider with the given HTTP RPC URL.
+    ///
+    /// Following starts at the latest block of the endpoint.
+    pub const fn new(rpc_url: String) -> Self {
+        Self { rpc_url, interval: Duration::from_secs(3), start_block: None }
+    }
+
+    /// Sets the first block to fetch, instead of the latest block of the endpoint.
+    pub const fn with_start_block(mut self, start_block: u64) -> Self {
+        self.start_block = Some(start_block);
+        self
+    }
+
+    /// Sets the interval at which the provider polls for new blocks.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl BlockProvider for RpcPollingBlockProvider {
+    async fn subscribe_blocks(&self, tx: Sender<Block>) {
+        let provider = ProviderBuilder::new()
+            .on_builtin(&self.rpc_url)
+            .await
+            .expect("failed to create RPC provider");
+
+        let mut next_block_number = self.start_block;
+        let mut interval = interval(self.interval);
+        loop {
+            interval.tick().await;
+            let latest_block_number = match provider.get_block_number().await {
+                Ok(number) => number,
+                Err(err) => {
+                    warn!(target: "consensus::debug-client", %err, "failed to fetch the latest block number from RPC");
+                    continue
+                }
+            };
+
+            let mut block_number = *next_block_number.get_or_insert(latest_block_number);
+            while block_number <= latest_block_number {
+                let block = match provider
+                    .get_block_by_number(BlockNumberOrTag::Number(block_number), true.into())
+                    .await
+                {
+                    Ok(Some(block)) => block,
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!(target: "consensus::debug-client", %err, block_number, "failed to fetch a block from RPC");
+                        break
+                    }
+                };
+                if tx.send(block).await.is_err() {
+                    // channel closed
+                    return
+                }
+
+                block_number += 1;
+                next_block_number = Some(block_number);
+            }
+        }
+    }
+
+    async fn get_block(&self, block_number: u64) -> eyre::Result<Block> {
+        let provider = ProviderBuilder::new().on_builtin(&self.rpc_url).await?;
+        provider
+            .get_block_by_number(BlockNumberOrTag::Number(block_number), true.into())
+            .await?
+            .ok_or_else(|| eyre::eyre!("block not found by number {}", block_number))
+    }
+}
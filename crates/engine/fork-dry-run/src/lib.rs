@@ -0,0 +1,318 @@
+//! Shadow execution of canonical blocks under the rules of an upcoming hardfork.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_consensus::Header;
+use alloy_primitives::{TxHash, U256};
+use futures::{Stream, StreamExt};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkCondition, Hardforks};
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm};
+use reth_primitives::{Receipt, SealedBlockWithSenders};
+use reth_provider::{CanonStateNotification, ChainSpecProvider, StateProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, SpecId},
+    DatabaseCommit, StateBuilder,
+};
+use reth_tracing::tracing::{info, warn};
+
+/// Executes every new canonical block a second time under the rules of an upcoming hardfork and
+/// reports transactions that behave differently.
+///
+/// Only the transactions are executed under the rules of the upcoming hardfork, system calls and
+/// block rewards follow the chain spec. The shadow execution doesn't affect the node.
+#[derive(Debug)]
+pub struct ForkDryRun<P, EvmConfig> {
+    /// The provider to read the historical state and the chain spec.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: EvmConfig,
+    /// The hardfork to dry run. If not set, the next scheduled hardfork of the chain spec is used.
+    hardfork: Option<String>,
+}
+
+impl<P, EvmConfig> ForkDryRun<P, EvmConfig> {
+    /// Creates a new fork dry run.
+    pub const fn new(provider: P, evm_config: EvmConfig, hardfork: Option<String>) -> Self {
+        Self { provider, evm_config, hardfork }
+    }
+}
+
+impl<P, EvmConfig> ForkDryRun<P, EvmConfig>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    /// Dry runs the blocks of all canonical state notifications until the stream ends.
+    pub async fn run<St>(self, mut notifications: St)
+    where
+        St: Stream<Item = CanonStateNotification> + Unpin,
+    {
+        while let Some(notification) = notifications.next().await {
+            let chain = notification.committed();
+            for block in chain.blocks_iter() {
+                let Some(receipts) = chain.receipts_by_block_hash(block.hash()) else { continue };
+                if let Err(err) = self.dry_run_block(block, &receipts) {
+                    warn!(target: "engine::fork_dry_run", number = block.number, hash = %block.hash(), %err, "Failed to dry run block");
+                }
+            }
+        }
+    }
+
+    /// Returns the name and spec id of the hardfork to dry run at the given header, or `None` if
+    /// there is no upcoming hardfork the EVM knows about.
+    fn hardfork_at(&self, header: &Header) -> Option<(String, SpecId)> {
+        let name = match &self.hardfork {
+            Some(name) => name.clone(),
+            None => {
+                let chain_spec = self.provider.chain_spec();
+                let (fork, _) = chain_spec.forks_iter().find(|(_, condition)| match condition {
+                    ForkCondition::Block(block) => *block > header.number,
+                    ForkCondition::Timestamp(timestamp) => *timestamp > header.timestamp,
+                    ForkCondition::TTD { .. } | ForkCondition::Never => false,
+                })?;
+                fork.name().to_string()
+            }
+        };
+
+        // unknown names are mapped to the latest spec, so require an exact match
+        let spec_id = SpecId::from(name.as_str());
+        (<&'static str>::from(spec_id) == name).then_some((name, spec_id))
+    }
+
+    /// Executes the transactions of the block under the rules of the upcoming hardfork and
+    /// compares them with the receipts of the canonical execution.
+    ///
+    /// Returns the divergences, which are also logged.
+    pub fn dry_run_block(
+        &self,
+        block: &SealedBlockWithSenders,
+        receipts: &[&Receipt],
+    ) -> eyre::Result<Vec<ForkDryRunDivergence>> {
+        let Some((hardfork, spec_id)) = self.hardfork_at(block.header()) else {
+            return Ok(Vec::new())
+        };
+
+        let mut db = StateBuilder::new()
+            .with_database(StateProviderDatabase::new(
+                self.provider.state_by_block_hash(block.parent_hash)?,
+            ))
+            .build();
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        self.evm_config.fill_cfg_and_block_env(&mut cfg, &mut block_env, block.header(), U256::MAX);
+        let canonical_spec_id = cfg.handler_cfg.spec_id;
+
+        let mut evm = self.evm_config.evm_with_env(
+            &mut db,
+            EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default()),
+        );
+
+        let mut system_caller =
+            SystemCaller::new(self.evm_config.clone(), self.provider.chain_spec());
+        system_caller.apply_pre_execution_changes(&block.clone().unseal(), &mut evm)?;
+
+        evm.modify_spec_id(spec_id);
+
+        let mut divergences = Vec::new();
+        let mut cumulative_gas_used = 0;
+        let mut dry_run_gas_used = 0;
+        for ((sender, tx), receipt) in block.transactions_with_sender().zip(receipts) {
+            let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used;
+
+            self.evm_config.fill_tx_env(evm.tx_mut(), tx, *sender);
+            let outcome = match evm.transact() {
+                Ok(result) => {
+                    let outcome = match &result.result {
+                        ExecutionResult::Success { gas_used, .. } => {
+                            DryRunOutcome::Success { gas_used: *gas_used }
+                        }
+                        ExecutionResult::Revert { gas_used, .. } => {
+                            DryRunOutcome::Revert { gas_used: *gas_used }
+                        }
+                        ExecutionResult::Halt { reason, gas_used } => DryRunOutcome::Halt {
+                            reason: format!("{reason:?}"),
+                            gas_used: *gas_used,
+                        },
+                    };
+                    evm.db_mut().commit(result.state);
+                    outcome
+                }
+                Err(err) => DryRunOutcome::Invalid(err.to_string()),
+            };
+            dry_run_gas_used += outcome.gas_used().unwrap_or_default();
+
+            if outcome.gas_used() != Some(gas_used) || outcome.is_success() != receipt.success {
+                let divergence = ForkDryRunDivergence {
+                    tx_hash: tx.hash(),
+                    gas_used,
+                    success: receipt.success,
+                    outcome,
+                };
+                warn!(
+                    target: "engine::fork_dry_run",
+                    number = block.number,
+                    %hardfork,
+                    tx_hash = %divergence.tx_hash,
+                    gas_used = divergence.gas_used,
+                    success = divergence.success,
+                    dry_run = ?divergence.outcome,
+                    "Transaction diverges under upcoming hardfork"
+                );
+                divergences.push(divergence);
+            }
+        }
+
+        info!(
+            target: "engine::fork_dry_run",
+            number = block.number,
+            hash = %block.hash(),
+            %hardfork,
+            ?canonical_spec_id,
+            gas_used = block.gas_used,
+            dry_run_gas_used,
+            divergences = divergences.len(),
+            "Dry ran block under upcoming hardfork"
+        );
+
+        Ok(divergences)
+    }
+}
+
+/// A transaction that behaves differently under the rules of the upcoming hardfork.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkDryRunDivergence {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The gas used by the canonical execution.
+    pub gas_used: u64,
+    /// Whether the canonical execution succeeded.
+    pub success: bool,
+    /// The outcome of the execution under the upcoming hardfork.
+    pub outcome: DryRunOutcome,
+}
+
+/// The outcome of a transaction executed under the rules of the upcoming hardfork.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryRunOutcome {
+    /// The transaction succeeded.
+    Success {
+        /// The gas used by the transaction.
+        gas_used: u64,
+    },
+    /// The transaction reverted.
+    Revert {
+        /// The gas used by the transaction.
+        gas_used: u64,
+    },
+    /// The transaction halted.
+    Halt {
+        /// Why the transaction halted.
+        reason: String,
+        /// The gas used by the transaction.
+        gas_used: u64,
+    },
+    /// The transaction is invalid and can't be included in the block.
+    Invalid(String),
+}
+
+impl DryRunOutcome {
+    /// Returns the gas used by the transaction, `None` if it's invalid.
+    pub const fn gas_used(&self) -> Option<u64> {
+        match self {
+            Self::Success { gas_used } |
+            Self::Revert { gas_used } |
+            Self::Halt { gas_used, .. } => Some(*gas_used),
+            Self::Invalid(_) => None,
+        }
+    }
+
+    /// Returns `true` if the transaction succeeded.
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{bytes, Address, PrimitiveSignature as Signature, TxKind};
+    use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, MAINNET};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Block, BlockBody, BlockWithSenders, Transaction, TransactionSigned};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use std::sync::Arc;
+
+    #[test]
+    fn dry_run_block_reports_divergences() {
+        // `BASEFEE` is an invalid opcode before London, so calling the contract halts
+        let (sender, contract) = (Address::with_last_byte(0x10), Address::with_last_byte(0x20));
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .berlin_activated()
+                .with_fork(EthereumHardfork::London, ForkCondition::Block(10))
+                .build(),
+        );
+        let provider = MockEthProvider { chain_spec: chain_spec.clone(), ..Default::default() };
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000_000)));
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(bytes!("485000")),
+        );
+
+        let tx = |nonce, to, gas_limit| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy {
+                    nonce,
+                    gas_price: 1,
+                    gas_limit,
+                    to: TxKind::Call(to),
+                    ..Default::default()
+                }),
+                Signature::test_signature(),
+            )
+        };
+        let (call, transfer) =
+            (tx(0, contract, 100_000), tx(1, Address::with_last_byte(0x30), 21_000));
+        let block = Block {
+            header: Header { number: 1, gas_limit: 1_000_000, ..Default::default() },
+            body: BlockBody { transactions: vec![call.clone(), transfer], ..Default::default() },
+        };
+        let block = BlockWithSenders::new(block, vec![sender, sender]).unwrap().seal_slow();
+
+        // the call halts and consumes all of its gas, the transfer succeeds
+        let receipts = [
+            Receipt { success: false, cumulative_gas_used: 100_000, ..Default::default() },
+            Receipt { success: true, cumulative_gas_used: 121_000, ..Default::default() },
+        ];
+        let receipts = receipts.iter().collect::<Vec<_>>();
+
+        // London is the next scheduled hardfork
+        let fork_dry_run = ForkDryRun::new(provider, EthEvmConfig::new(chain_spec), None);
+        let divergences = fork_dry_run.dry_run_block(&block, &receipts).unwrap();
+        assert_eq!(
+            divergences,
+            vec![ForkDryRunDivergence {
+                tx_hash: call.hash(),
+                gas_used: 100_000,
+                success: false,
+                outcome: DryRunOutcome::Success { gas_used: 21_004 },
+            }]
+        );
+    }
+}
@@ -1,5 +1,8 @@
 //! Invalid block hook implementations.
 
+mod output;
+mod prestate;
 mod witness;
 
+pub use prestate::InvalidBlockPreStateHook;
 pub use witness::InvalidBlockWitnessHook;
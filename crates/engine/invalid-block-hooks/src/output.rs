@@ -0,0 +1,47 @@
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use pretty_assertions::Comparison;
+use serde::Serialize;
+
+/// Saves the diff of two values into a file with the given name in the output directory.
+pub(crate) fn save_diff<T: PartialEq + Debug>(
+    output_directory: &Path,
+    filename: String,
+    original: &T,
+    new: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    let diff = Comparison::new(original, new);
+    File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+    Ok(path)
+}
+
+/// Saves the value as JSON into a file with the given name in the output directory.
+pub(crate) fn save_file<T: Serialize>(
+    output_directory: &Path,
+    filename: String,
+    value: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Saves the raw bytes into a file with the given name in the output directory.
+pub(crate) fn save_bytes(
+    output_directory: &Path,
+    filename: String,
+    bytes: &[u8],
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    File::create(&path)?.write_all(bytes)?;
+
+    Ok(path)
+}
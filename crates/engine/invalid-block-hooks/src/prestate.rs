@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_trace::geth::{
+    GethDebugTracingOptions, GethTrace, PreStateConfig, TraceResult,
+};
+use eyre::OptionExt;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm};
+use reth_primitives::{Receipt, SealedBlockWithSenders, SealedHeader};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
+    DatabaseCommit,
+};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+
+use crate::output::{save_diff, save_file};
+
+/// Re-executes the given block with the prestate tracer in diff mode and saves the state changes
+/// of every transaction to a file.
+///
+/// If a healthy node is configured, the traces are compared against the ones of the healthy node.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, EvmConfig> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: EvmConfig,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case the traces don't match the ones of the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, EvmConfig> InvalidBlockPreStateHook<P, EvmConfig> {
+    /// Creates a new prestate hook.
+    pub const fn new(
+        provider: P,
+        evm_config: EvmConfig,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockPreStateHook<P, EvmConfig>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader,
+        block: &SealedBlockWithSenders,
+    ) -> eyre::Result<()> {
+        let prestate_config = PreStateConfig { diff_mode: Some(true), ..Default::default() };
+
+        // Setup database.
+        let mut db = CacheDB::new(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(parent_header.hash())?,
+        ));
+
+        // Setup environment for the execution.
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        self.evm_config.fill_cfg_and_block_env(&mut cfg, &mut block_env, block.header(), U256::MAX);
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default());
+
+        // Apply pre-block system contract calls.
+        let mut evm = self.evm_config.evm_with_env(&mut db, env.clone());
+        let mut system_caller =
+            SystemCaller::new(self.evm_config.clone(), self.provider.chain_spec());
+        system_caller.apply_pre_execution_changes(&block.clone().unseal(), &mut evm)?;
+        drop(evm);
+
+        // Re-execute all of the transactions in the block and trace their state changes.
+        let mut traces = Vec::with_capacity(block.body.transactions.len());
+        for tx in block.transactions() {
+            let mut env = env.clone();
+            self.evm_config.fill_tx_env(
+                &mut env.tx,
+                tx,
+                tx.recover_signer().ok_or_eyre("failed to recover sender")?,
+            );
+            let gas_limit = env.tx.gas_limit;
+
+            let mut inspector = TracingInspector::new(
+                TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
+            );
+            let mut evm = self.evm_config.evm_with_env_and_inspector(&mut db, env, &mut inspector);
+            let result = evm.transact()?;
+            drop(evm);
+
+            // The prestate is read from the database before the changes of the transaction are
+            // committed.
+            let frame = inspector
+                .with_transaction_gas_limit(gas_limit)
+                .into_geth_builder()
+                .geth_prestate_traces(&result, &prestate_config, &db)?;
+            db.commit(result.state);
+
+            traces.push(TraceResult::Success {
+                result: GethTrace::from(frame),
+                tx_hash: Some(tx.hash()),
+            });
+        }
+
+        let re_executed_path = save_file(
+            &self.output_directory,
+            format!("{}_{}.prestate.re_executed.json", block.number, block.hash()),
+            &traces,
+        )?;
+
+        if let Some(healthy_node_client) = &self.healthy_node_client {
+            // Compare the traces against the healthy node.
+            let healthy_node_traces = futures::executor::block_on(async move {
+                DebugApiClient::debug_trace_block_by_number(
+                    healthy_node_client,
+                    block.number.into(),
+                    Some(GethDebugTracingOptions::prestate_tracer(prestate_config)),
+                )
+                .await
+            })?;
+
+            let healthy_path = save_file(
+                &self.output_directory,
+                format!("{}_{}.prestate.healthy.json", block.number, block.hash()),
+                &healthy_node_traces,
+            )?;
+
+            // If the traces are different, write the diff to the output directory.
+            if traces != healthy_node_traces {
+                let filename = format!("{}_{}.prestate.diff", block.number, block.hash());
+                let diff_path =
+                    save_diff(&self.output_directory, filename, &traces, &healthy_node_traces)?;
+                warn!(
+                    target: "engine::invalid_block_hooks::prestate",
+                    diff_path = %diff_path.display(),
+                    re_executed_path = %re_executed_path.display(),
+                    healthy_path = %healthy_path.display(),
+                    "Prestate traces mismatch against healthy node"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockHook for InvalidBlockPreStateHook<P, EvmConfig>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader,
+        block: &SealedBlockWithSenders,
+        _output: &BlockExecutionOutput<Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::prestate", %err, "Failed to invoke hook");
+        }
+    }
+}
@@ -1,10 +1,9 @@
-use std::{collections::HashMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf};
 
 use alloy_consensus::Header;
 use alloy_primitives::{keccak256, B256, U256};
 use alloy_rpc_types_debug::ExecutionWitness;
 use eyre::OptionExt;
-use pretty_assertions::Comparison;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_engine_primitives::InvalidBlockHook;
 use reth_evm::{
@@ -21,9 +20,11 @@ use reth_revm::{
 use reth_rpc_api::DebugApiClient;
 use reth_tracing::tracing::warn;
 use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
-use serde::Serialize;
 
-/// Generates a witness for the given block and saves it to a file.
+use crate::output::{save_bytes, save_diff, save_file};
+
+/// Generates a witness for the given block and saves it to a file, together with the RLP encoded
+/// block.
 #[derive(Debug)]
 pub struct InvalidBlockWitnessHook<P, EvmConfig> {
     /// The provider to read the historical state and do the EVM execution.
@@ -67,6 +68,13 @@ where
     ) -> eyre::Result<()> {
         // TODO(alexey): unify with `DebugApi::debug_execution_witness`
 
+        // Save the block itself, so that it can be re-imported for debugging.
+        save_bytes(
+            &self.output_directory,
+            format!("{}_{}.block.rlp", block.number, block.hash()),
+            &alloy_rlp::encode(&block.block),
+        )?;
+
         // Setup database.
         let mut db = StateBuilder::new()
             .with_database(StateProviderDatabase::new(
@@ -166,7 +174,8 @@ where
             codes: Default::default(),
             keys: state_preimages,
         };
-        let re_executed_witness_path = self.save_file(
+        let re_executed_witness_path = save_file(
+            &self.output_directory,
             format!("{}_{}.witness.re_executed.json", block.number, block.hash()),
             &response,
         )?;
@@ -177,7 +186,8 @@ where
                     .await
             })?;
 
-            let healthy_path = self.save_file(
+            let healthy_path = save_file(
+                &self.output_directory,
                 format!("{}_{}.witness.healthy.json", block.number, block.hash()),
                 &healthy_node_witness,
             )?;
@@ -185,7 +195,8 @@ where
             // If the witnesses are different, write the diff to the output directory.
             if response != healthy_node_witness {
                 let filename = format!("{}_{}.witness.diff", block.number, block.hash());
-                let diff_path = self.save_diff(filename, &response, &healthy_node_witness)?;
+                let diff_path =
+                    save_diff(&self.output_directory, filename, &response, &healthy_node_witness)?;
                 warn!(
                     target: "engine::invalid_block_hooks::witness",
                     diff_path = %diff_path.display(),
@@ -213,17 +224,20 @@ where
         }
 
         if bundle_state != output.state {
-            let original_path = self.save_file(
+            let original_path = save_file(
+                &self.output_directory,
                 format!("{}_{}.bundle_state.original.json", block.number, block.hash()),
                 &output.state,
             )?;
-            let re_executed_path = self.save_file(
+            let re_executed_path = save_file(
+                &self.output_directory,
                 format!("{}_{}.bundle_state.re_executed.json", block.number, block.hash()),
                 &bundle_state,
             )?;
 
             let filename = format!("{}_{}.bundle_state.diff", block.number, block.hash());
-            let diff_path = self.save_diff(filename, &bundle_state, &output.state)?;
+            let diff_path =
+                save_diff(&self.output_directory, filename, &bundle_state, &output.state)?;
 
             warn!(
                 target: "engine::invalid_block_hooks::witness",
@@ -241,24 +255,32 @@ where
         if let Some((original_updates, original_root)) = trie_updates {
             if re_executed_root != original_root {
                 let filename = format!("{}_{}.state_root.diff", block.number, block.hash());
-                let diff_path = self.save_diff(filename, &re_executed_root, &original_root)?;
+                let diff_path =
+                    save_diff(&self.output_directory, filename, &re_executed_root, &original_root)?;
                 warn!(target: "engine::invalid_block_hooks::witness", ?original_root, ?re_executed_root, diff_path = %diff_path.display(), "State root mismatch after re-execution");
             }
 
             // If the re-executed state root does not match the _header_ state root, also log that.
             if re_executed_root != block.state_root {
                 let filename = format!("{}_{}.header_state_root.diff", block.number, block.hash());
-                let diff_path = self.save_diff(filename, &re_executed_root, &block.state_root)?;
+                let diff_path = save_diff(
+                    &self.output_directory,
+                    filename,
+                    &re_executed_root,
+                    &block.state_root,
+                )?;
                 warn!(target: "engine::invalid_block_hooks::witness", header_state_root=?block.state_root, ?re_executed_root, diff_path = %diff_path.display(), "Re-executed state root does not match block state root");
             }
 
             if &trie_output != original_updates {
                 // Trie updates are too big to diff, so we just save the original and re-executed
-                let original_path = self.save_file(
+                let original_path = save_file(
+                    &self.output_directory,
                     format!("{}_{}.trie_updates.original.json", block.number, block.hash()),
                     original_updates,
                 )?;
-                let re_executed_path = self.save_file(
+                let re_executed_path = save_file(
+                    &self.output_directory,
                     format!("{}_{}.trie_updates.re_executed.json", block.number, block.hash()),
                     &trie_output,
                 )?;
@@ -273,27 +295,6 @@ where
 
         Ok(())
     }
-
-    /// Saves the diff of two values into a file with the given name in the output directory.
-    fn save_diff<T: PartialEq + Debug>(
-        &self,
-        filename: String,
-        original: &T,
-        new: &T,
-    ) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        let diff = Comparison::new(original, new);
-        File::create(&path)?.write_all(diff.to_string().as_bytes())?;
-
-        Ok(path)
-    }
-
-    fn save_file<T: Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
-        let path = self.output_directory.join(filename);
-        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
-
-        Ok(path)
-    }
 }
 
 impl<P, EvmConfig> InvalidBlockHook for InvalidBlockWitnessHook<P, EvmConfig>
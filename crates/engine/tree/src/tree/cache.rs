@@ -0,0 +1,303 @@
+//! Execution cache that is carried over from one executed block to the next.
+
+use crate::tree::metrics::ExecutionCacheMetrics;
+use alloy_primitives::{
+    map::{Entry, HashMap},
+    Address, B256, U256,
+};
+use reth_revm::db::BundleState;
+use revm_primitives::{
+    db::{Database, DatabaseRef},
+    AccountInfo, Bytecode,
+};
+
+/// A cache of the state of a block, seeded by the reads and writes of executed blocks.
+///
+/// The cache reflects the post state of a single block. If the next block is executed on top of
+/// that block, the state reads of its execution are served from the cache, and once the block is
+/// valid the cache is advanced to its post state by applying the writes of the block. Hot accounts
+/// and storage slots are therefore only read from disk once, as long as blocks are executed on top
+/// of each other.
+#[derive(Debug)]
+pub(crate) struct ExecutionCache {
+    /// Hash of the block whose post state is cached.
+    block_hash: Option<B256>,
+    /// Maximum number of cached entries, the cache is disabled if zero.
+    max_entries: usize,
+    /// Cached accounts, `None` if the account does not exist.
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Cached storage slots of accounts.
+    storage: HashMap<Address, HashMap<U256, U256>>,
+    /// Cached bytecode by code hash.
+    contracts: HashMap<B256, Bytecode>,
+    /// Number of cached storage slots.
+    storage_len: usize,
+    metrics: ExecutionCacheMetrics,
+}
+
+impl ExecutionCache {
+    /// Creates an empty cache that holds at most the given number of entries.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        Self {
+            block_hash: None,
+            max_entries,
+            accounts: HashMap::default(),
+            storage: HashMap::default(),
+            contracts: HashMap::default(),
+            storage_len: 0,
+            metrics: ExecutionCacheMetrics::default(),
+        }
+    }
+
+    /// Returns the number of cached entries.
+    pub(crate) fn len(&self) -> usize {
+        self.accounts.len() + self.storage_len + self.contracts.len()
+    }
+
+    /// Returns a [`Database`] that serves reads from the cache if it holds the post state of the
+    /// given parent block and reads from the given database otherwise.
+    ///
+    /// If the cache holds the state of another block it is cleared and starts caching the state of
+    /// the parent.
+    pub(crate) fn as_db<DB>(&mut self, parent_hash: B256, db: DB) -> CachedStateDb<'_, DB> {
+        if self.block_hash != Some(parent_hash) {
+            self.clear();
+            self.block_hash = Some(parent_hash);
+        }
+        CachedStateDb { cache: self, db, hits: 0, misses: 0 }
+    }
+
+    /// Advances the cache from the parent state to the post state of the given valid block by
+    /// applying the writes of the block.
+    ///
+    /// If the cache doesn't hold the parent state, or exceeds the maximum number of entries
+    /// afterwards, it is cleared.
+    pub(crate) fn on_block_executed(
+        &mut self,
+        parent_hash: B256,
+        block_hash: B256,
+        state: &BundleState,
+    ) {
+        if self.max_entries == 0 || self.block_hash != Some(parent_hash) {
+            self.clear();
+            return
+        }
+
+        for (address, account) in &state.state {
+            if account.was_destroyed() {
+                if let Some(storage) = self.storage.remove(address) {
+                    self.storage_len -= storage.len();
+                }
+            }
+            self.accounts.insert(*address, account.info.clone());
+            if account.storage.is_empty() {
+                continue
+            }
+            let storage = self.storage.entry(*address).or_default();
+            for (slot, value) in &account.storage {
+                if storage.insert(*slot, value.present_value).is_none() {
+                    self.storage_len += 1;
+                }
+            }
+        }
+        for (code_hash, code) in &state.contracts {
+            self.contracts.insert(*code_hash, code.clone());
+        }
+
+        if self.len() > self.max_entries {
+            self.clear();
+        } else {
+            self.block_hash = Some(block_hash);
+        }
+        self.metrics.size.set(self.len() as f64);
+    }
+
+    /// Removes all entries from the cache.
+    pub(crate) fn clear(&mut self) {
+        self.block_hash = None;
+        self.accounts.clear();
+        self.storage.clear();
+        self.contracts.clear();
+        self.storage_len = 0;
+        self.metrics.size.set(0.0);
+    }
+}
+
+/// A [`Database`] that serves reads from an [`ExecutionCache`] and caches reads of the underlying
+/// database.
+#[derive(Debug)]
+pub(crate) struct CachedStateDb<'a, DB> {
+    cache: &'a mut ExecutionCache,
+    db: DB,
+    hits: u64,
+    misses: u64,
+}
+
+impl<DB> CachedStateDb<'_, DB> {
+    /// Records a read that was served from the cache if `hit` is true and from the database
+    /// otherwise.
+    fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Returns whether entries can be added to the cache.
+    fn has_capacity(&self) -> bool {
+        self.cache.len() < self.cache.max_entries
+    }
+}
+
+impl<DB> Drop for CachedStateDb<'_, DB> {
+    fn drop(&mut self) {
+        self.cache.metrics.hits.increment(self.hits);
+        self.cache.metrics.misses.increment(self.misses);
+    }
+}
+
+impl<DB: DatabaseRef> Database for CachedStateDb<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.cache.accounts.get(&address) {
+            let info = info.clone();
+            self.record(true);
+            return Ok(info)
+        }
+        self.record(false);
+        let info = self.db.basic_ref(address)?;
+        if self.has_capacity() {
+            self.cache.accounts.insert(address, info.clone());
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.cache.contracts.get(&code_hash) {
+            let code = code.clone();
+            self.record(true);
+            return Ok(code)
+        }
+        self.record(false);
+        let code = self.db.code_by_hash_ref(code_hash)?;
+        if self.has_capacity() {
+            self.cache.contracts.insert(code_hash, code.clone());
+        }
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.cache.storage.get(&address).and_then(|slots| slots.get(&index)) {
+            let value = *value;
+            self.record(true);
+            return Ok(value)
+        }
+        self.record(false);
+        let value = self.db.storage_ref(address, index)?;
+        if self.has_capacity() {
+            if let Entry::Vacant(entry) =
+                self.cache.storage.entry(address).or_default().entry(index)
+            {
+                entry.insert(value);
+                self.cache.storage_len += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::db::{states::StorageSlot, AccountStatus, BundleAccount, EmptyDB};
+
+    fn account(nonce: u64) -> AccountInfo {
+        AccountInfo { nonce, ..Default::default() }
+    }
+
+    #[test]
+    fn caches_reads_of_parent_state() {
+        let mut cache = ExecutionCache::new(100);
+        let parent = B256::with_last_byte(1);
+        let address = Address::with_last_byte(1);
+
+        let mut db = cache.as_db(parent, EmptyDB::default());
+        assert_eq!(db.basic(address).unwrap(), None);
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+        assert_eq!((db.hits, db.misses), (0, 2));
+        assert_eq!(db.basic(address).unwrap(), None);
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+        assert_eq!((db.hits, db.misses), (2, 2));
+        drop(db);
+        assert_eq!(cache.len(), 2);
+
+        // reads on top of another block don't use the cache
+        let db = cache.as_db(B256::with_last_byte(2), EmptyDB::default());
+        drop(db);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn applies_block_writes() {
+        let mut cache = ExecutionCache::new(100);
+        let parent = B256::with_last_byte(1);
+        let block = B256::with_last_byte(2);
+        let address = Address::with_last_byte(1);
+
+        let mut db = cache.as_db(parent, EmptyDB::default());
+        db.basic(address).unwrap();
+        db.storage(address, U256::from(1)).unwrap();
+        drop(db);
+
+        let mut state = BundleState::default();
+        state.state.insert(
+            address,
+            BundleAccount {
+                info: Some(account(1)),
+                original_info: None,
+                storage: HashMap::from_iter([(
+                    U256::from(1),
+                    StorageSlot::new_changed(U256::ZERO, U256::from(7)),
+                )]),
+                status: AccountStatus::InMemoryChange,
+            },
+        );
+        cache.on_block_executed(parent, block, &state);
+
+        let mut db = cache.as_db(block, EmptyDB::default());
+        assert_eq!(db.basic(address).unwrap(), Some(account(1)));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(7));
+        assert_eq!(db.misses, 0);
+    }
+
+    #[test]
+    fn clears_when_full() {
+        let mut cache = ExecutionCache::new(1);
+        let parent = B256::with_last_byte(1);
+
+        let mut db = cache.as_db(parent, EmptyDB::default());
+        db.basic(Address::with_last_byte(1)).unwrap();
+        db.basic(Address::with_last_byte(2)).unwrap();
+        drop(db);
+        assert_eq!(cache.len(), 1);
+
+        let mut state = BundleState::default();
+        state.state.insert(
+            Address::with_last_byte(3),
+            BundleAccount {
+                info: Some(account(1)),
+                original_info: None,
+                storage: HashMap::default(),
+                status: AccountStatus::InMemoryChange,
+            },
+        );
+        cache.on_block_executed(parent, B256::with_last_byte(2), &state);
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -6,6 +6,9 @@ pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 /// How close to the canonical head we persist blocks.
 pub const DEFAULT_MEMORY_BLOCK_BUFFER_TARGET: u64 = 2;
 
+/// Maximum number of accounts, storage slots and contracts in the execution cache.
+pub const DEFAULT_EXECUTION_CACHE_SIZE: usize = 1_000_000;
+
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = 256;
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 
@@ -32,6 +35,9 @@ pub struct TreeConfig {
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
     /// a batch of downloaded blocks.
     max_execute_block_batch_size: usize,
+    /// Maximum number of accounts, storage slots and contracts in the cache of the state of the
+    /// last executed block, zero disables the cache.
+    execution_cache_size: usize,
 }
 
 impl Default for TreeConfig {
@@ -42,6 +48,7 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            execution_cache_size: DEFAULT_EXECUTION_CACHE_SIZE,
         }
     }
 }
@@ -54,6 +61,7 @@ impl TreeConfig {
         block_buffer_limit: u32,
         max_invalid_header_cache_length: u32,
         max_execute_block_batch_size: usize,
+        execution_cache_size: usize,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -61,6 +69,7 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            execution_cache_size,
         }
     }
 
@@ -89,6 +98,11 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Return the maximum number of entries in the execution cache.
+    pub const fn execution_cache_size(&self) -> usize {
+        self.execution_cache_size
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -127,4 +141,10 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for the maximum number of entries in the execution cache.
+    pub const fn with_execution_cache_size(mut self, execution_cache_size: usize) -> Self {
+        self.execution_cache_size = execution_cache_size;
+        self
+    }
 }
@@ -46,6 +46,18 @@ pub(crate) struct EngineMetrics {
     // TODO add latency metrics
 }
 
+/// Metrics for the cache of the state of executed blocks.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.execution_cache")]
+pub(crate) struct ExecutionCacheMetrics {
+    /// Number of state reads that were served from the cache.
+    pub(crate) hits: Counter,
+    /// Number of state reads that were served from the database.
+    pub(crate) misses: Counter,
+    /// Number of cached accounts, storage slots and contracts.
+    pub(crate) size: Gauge,
+}
+
 /// Metrics for non-execution related block validation.
 #[derive(Metrics)]
 #[metrics(scope = "sync.block_validation")]
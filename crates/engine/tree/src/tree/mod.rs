@@ -64,13 +64,14 @@ use tokio::sync::{
 };
 use tracing::*;
 
+mod cache;
 pub mod config;
 mod invalid_block_hook;
 mod metrics;
 mod persistence_state;
 use crate::{
     engine::{EngineApiKind, EngineApiRequest},
-    tree::metrics::EngineApiMetrics,
+    tree::{cache::ExecutionCache, metrics::EngineApiMetrics},
 };
 pub use config::TreeConfig;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
@@ -502,6 +503,8 @@ pub struct EngineApiTreeHandler<P, E, T: EngineTypes, Spec> {
     payload_builder: PayloadBuilderHandle<T>,
     /// Configuration settings.
     config: TreeConfig,
+    /// Cache of the state of the last executed block, used to execute the next block on top of it.
+    execution_cache: ExecutionCache,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
     /// An invalid block hook.
@@ -527,6 +530,7 @@ impl<P: Debug, E: Debug, T: EngineTypes + Debug, Spec: Debug> std::fmt::Debug
             .field("canonical_in_memory_state", &self.canonical_in_memory_state)
             .field("payload_builder", &self.payload_builder)
             .field("config", &self.config)
+            .field("execution_cache", &self.execution_cache)
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
             .field("engine_kind", &self.engine_kind)
@@ -573,6 +577,7 @@ where
             state,
             canonical_in_memory_state,
             payload_builder,
+            execution_cache: ExecutionCache::new(config.execution_cache_size()),
             config,
             metrics: Default::default(),
             incoming_tx,
@@ -2181,7 +2186,10 @@ where
         }
 
         trace!(target: "engine::tree", block=?block.num_hash(), "Executing block");
-        let executor = self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
+        let executor = self.executor_provider.executor(
+            self.execution_cache
+                .as_db(block.parent_hash, StateProviderDatabase::new(&state_provider)),
+        );
 
         let block_number = block.number;
         let block_hash = block.hash();
@@ -2267,6 +2275,9 @@ where
         self.metrics.block_validation.record_state_root(&trie_output, root_elapsed.as_secs_f64());
         debug!(target: "engine::tree", ?root_elapsed, block=?sealed_block.num_hash(), "Calculated state root");
 
+        // the block is valid, so the next block executed on top of it can reuse the cached state
+        self.execution_cache.on_block_executed(block.parent_hash, block_hash, &output.state);
+
         let executed = ExecutedBlock {
             block: sealed_block.clone(),
             senders: Arc::new(block.senders),
@@ -0,0 +1,92 @@
+//! SSZ header accumulator of era1 files.
+//!
+//! The accumulator is the `hash_tree_root` of `List[HeaderRecord, 8192]` where every record is
+//! the block hash together with the total difficulty at that block.
+
+use crate::MAX_BLOCKS_PER_ERA1;
+use alloy_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Depth of the merkle tree over the maximum number of header records.
+const DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
+/// Computes the SSZ root of a single `HeaderRecord { block_hash, total_difficulty }`.
+pub fn header_record_root(block_hash: B256, total_difficulty: U256) -> B256 {
+    hash_pair(&block_hash.0, &total_difficulty.to_le_bytes::<32>())
+}
+
+/// Incrementally computes the era1 header accumulator.
+#[derive(Debug, Default, Clone)]
+pub struct Accumulator {
+    records: Vec<B256>,
+}
+
+impl Accumulator {
+    /// Adds the next header record.
+    pub fn push(&mut self, block_hash: B256, total_difficulty: U256) {
+        self.records.push(header_record_root(block_hash, total_difficulty));
+    }
+
+    /// Returns the number of records in the accumulator.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no records were added.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the `hash_tree_root` of the record list.
+    pub fn root(&self) -> B256 {
+        let mut layer = self.records.clone();
+        let mut zero = B256::ZERO;
+        for _ in 0..DEPTH {
+            if layer.len() % 2 == 1 {
+                layer.push(zero);
+            }
+            layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0].0, &pair[1].0)).collect();
+            zero = hash_pair(&zero.0, &zero.0);
+        }
+        let root = layer.first().copied().unwrap_or(zero);
+
+        // mix in the list length
+        hash_pair(&root.0, &U256::from(self.records.len()).to_le_bytes::<32>())
+    }
+}
+
+pub(crate) fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_accumulator() {
+        // root of an empty list is the zero subtree root mixed with a zero length
+        let mut zero = B256::ZERO;
+        for _ in 0..DEPTH {
+            zero = hash_pair(&zero.0, &zero.0);
+        }
+        assert_eq!(Accumulator::default().root(), hash_pair(&zero.0, &[0u8; 32]));
+    }
+
+    #[test]
+    fn root_depends_on_order() {
+        let mut a = Accumulator::default();
+        a.push(B256::with_last_byte(1), U256::from(1));
+        a.push(B256::with_last_byte(2), U256::from(2));
+
+        let mut b = Accumulator::default();
+        b.push(B256::with_last_byte(2), U256::from(2));
+        b.push(B256::with_last_byte(1), U256::from(1));
+
+        assert_eq!(a.len(), 2);
+        assert_ne!(a.root(), b.root());
+    }
+}
@@ -0,0 +1,149 @@
+//! The `e2store` container format.
+//!
+//! Every entry is prefixed with an 8 byte header: a little-endian `u16` type, a little-endian
+//! `u32` data length and two reserved zero bytes.
+
+use std::io::{self, Read, Write};
+
+/// Size of the header preceding every entry.
+pub const HEADER_SIZE: usize = 8;
+
+/// Entry type of the version record that starts every e2store file.
+pub const VERSION: u16 = 0x3265;
+
+/// Errors that can occur when reading or writing e2store files.
+#[derive(Debug, thiserror::Error)]
+pub enum E2sError {
+    /// I/O error.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The reserved bytes of an entry header are not zero.
+    #[error("reserved header bytes must be zero, got {0:#x}")]
+    ReservedNotZero(u16),
+    /// An entry payload is too large to be described by the header.
+    #[error("entry of {0} bytes exceeds maximum entry size")]
+    EntryTooLarge(usize),
+    /// The file does not start with a version record.
+    #[error("missing version record")]
+    MissingVersion,
+    /// An entry of a different type was expected.
+    #[error("unexpected entry type {got:#x}, expected {expected:#x}")]
+    UnexpectedEntry {
+        /// The type that was expected at this position.
+        expected: u16,
+        /// The type that was found.
+        got: u16,
+    },
+    /// The contents of an entry are malformed.
+    #[error("malformed entry: {0}")]
+    Malformed(String),
+}
+
+/// A single typed e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The entry type.
+    pub entry_type: u16,
+    /// The raw entry payload.
+    pub data: Vec<u8>,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    pub const fn new(entry_type: u16, data: Vec<u8>) -> Self {
+        Self { entry_type, data }
+    }
+
+    /// Returns the number of bytes this entry occupies on disk, including its header.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_SIZE + self.data.len()
+    }
+
+    /// Writes the entry, prefixed with its header.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), E2sError> {
+        let len =
+            u32::try_from(self.data.len()).map_err(|_| E2sError::EntryTooLarge(self.data.len()))?;
+        let mut header = [0u8; HEADER_SIZE];
+        header[..2].copy_from_slice(&self.entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    /// Reads the next entry.
+    ///
+    /// Returns `None` if the reader is exhausted at an entry boundary.
+    pub fn read<R: Read>(reader: &mut R) -> Result<Option<Self>, E2sError> {
+        let mut header = [0u8; HEADER_SIZE];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        let reserved = u16::from_le_bytes([header[6], header[7]]);
+        if reserved != 0 {
+            return Err(E2sError::ReservedNotZero(reserved))
+        }
+
+        let mut data = vec![0u8; len];
+        reader.read_exact(&mut data)?;
+        Ok(Some(Self { entry_type, data }))
+    }
+
+    /// Returns an error if the entry is not of the expected type.
+    pub const fn expect_type(&self, expected: u16) -> Result<(), E2sError> {
+        if self.entry_type != expected {
+            return Err(E2sError::UnexpectedEntry { expected, got: self.entry_type })
+        }
+        Ok(())
+    }
+}
+
+/// Compresses the data with the snappy framing format.
+pub(crate) fn compress(data: &[u8]) -> Result<Vec<u8>, E2sError> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    encoder.into_inner().map_err(|err| E2sError::Io(err.into_error()))
+}
+
+/// Decompresses snappy framed data.
+pub(crate) fn decompress(data: &[u8]) -> Result<Vec<u8>, E2sError> {
+    let mut out = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrip() {
+        let entry = Entry::new(0x03, vec![1, 2, 3]);
+        let mut buf = Vec::new();
+        entry.write(&mut buf).unwrap();
+        assert_eq!(buf, [0x03, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 1, 2, 3]);
+        assert_eq!(buf.len(), entry.encoded_len());
+
+        let mut reader = buf.as_slice();
+        assert_eq!(Entry::read(&mut reader).unwrap(), Some(entry));
+        assert_eq!(Entry::read(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn version_entry_bytes() {
+        let mut buf = Vec::new();
+        Entry::new(VERSION, Vec::new()).write(&mut buf).unwrap();
+        assert_eq!(&buf[..2], b"e2");
+    }
+
+    #[test]
+    fn rejects_reserved_bytes() {
+        let buf = [0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00];
+        assert!(matches!(Entry::read(&mut buf.as_slice()), Err(E2sError::ReservedNotZero(1))));
+    }
+}
@@ -0,0 +1,647 @@
+//! Era files: archives of the beacon chain history of one era of [`SLOTS_PER_HISTORICAL_ROOT`]
+//! slots.
+//!
+//! ```text
+//! era := group+
+//! group := Version | block* | era-state | other-entries* | slot-index(block)? | slot-index(state)
+//! ```
+//!
+//! Signed beacon blocks and the beacon state are stored as snappy framed SSZ. Era `N` holds the
+//! blocks of the slots `(N - 1) * 8192..N * 8192` and the state at slot `N * 8192`, the genesis
+//! era only holds the genesis state.
+
+use crate::{
+    accumulator::hash_pair,
+    e2s::{compress, decompress, E2sError, Entry, VERSION},
+};
+use alloy_primitives::{BlockNumber, B256};
+use std::{
+    io::{Read, Write},
+    ops::Range,
+};
+
+/// Number of slots in an era.
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+
+/// Entry type of a snappy compressed SSZ `SignedBeaconBlock`.
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: u16 = 0x01;
+/// Entry type of a snappy compressed SSZ `BeaconState`.
+pub const COMPRESSED_BEACON_STATE: u16 = 0x02;
+/// Entry type of a slot index.
+pub const SLOT_INDEX: u16 = 0x3269;
+
+/// Offset of `genesis_validators_root` in the SSZ encoding of a `BeaconState`.
+const GENESIS_VALIDATORS_ROOT_OFFSET: usize = 8;
+/// Offset of `slot` in the SSZ encoding of a `BeaconState`.
+const STATE_SLOT_OFFSET: usize = 40;
+/// Offset of `block_roots` in the SSZ encoding of a `BeaconState`, followed by `state_roots`.
+///
+/// All fields up to and including `state_roots` are fixed size and identical across forks.
+const BLOCK_ROOTS_OFFSET: usize = 176;
+/// Size of the `block_roots` and `state_roots` vectors.
+const ROOTS_SIZE: usize = SLOTS_PER_HISTORICAL_ROOT as usize * 32;
+
+/// Size of the fixed part of a `BeaconBlockBody` since Bellatrix, the first fork with an
+/// execution payload.
+const BELLATRIX_BODY_FIXED_SIZE: usize = 384;
+/// Size of the fixed part of a `BeaconBlockBody` since Capella, which added withdrawals to the
+/// execution payload.
+const CAPELLA_BODY_FIXED_SIZE: usize = 388;
+/// Size of the fixed part of a `BeaconBlockBody` since Deneb, which added blob gas to the
+/// execution payload.
+const DENEB_BODY_FIXED_SIZE: usize = 392;
+/// Size of the fixed part of a `BeaconBlockBody` since Electra, which added execution requests.
+const ELECTRA_BODY_FIXED_SIZE: usize = 396;
+/// Offset of the `execution_payload` offset in the SSZ encoding of a `BeaconBlockBody`.
+const EXECUTION_PAYLOAD_OFFSET: usize = 380;
+/// Offset of the `blob_kzg_commitments` offset in the SSZ encoding of a `BeaconBlockBody`.
+const BLOB_KZG_COMMITMENTS_OFFSET: usize = 388;
+/// Offset of the `execution_requests` offset in the SSZ encoding of a `BeaconBlockBody`.
+const EXECUTION_REQUESTS_OFFSET: usize = 392;
+/// Size of a KZG commitment.
+const KZG_COMMITMENT_SIZE: usize = 48;
+
+/// Returns the canonical file name of an era file, e.g. `mainnet-00001-40cf2f3c.era`.
+pub fn era_file_name(network: &str, era: u64, historical_root: B256) -> String {
+    format!("{network}-{era:05}-{}.era", alloy_primitives::hex::encode(&historical_root[..4]))
+}
+
+/// Returns the range of slots whose blocks are stored in the given era.
+pub const fn era_block_slots(era: u64) -> Range<u64> {
+    if era == 0 {
+        return 0..0
+    }
+    (era - 1) * SLOTS_PER_HISTORICAL_ROOT..era * SLOTS_PER_HISTORICAL_ROOT
+}
+
+/// Returns the historical root that names the era file with the given state.
+///
+/// This is the `genesis_validators_root` for the genesis era, and the root of the historical
+/// batch of `block_roots` and `state_roots` that was added to the state at the end of the era
+/// otherwise. The state must be at the last slot of the era.
+pub fn era_historical_root(era: u64, state: &[u8]) -> Result<B256, E2sError> {
+    if state.len() < BLOCK_ROOTS_OFFSET + 2 * ROOTS_SIZE {
+        return Err(E2sError::Malformed(format!("beacon state of {} bytes", state.len())))
+    }
+    let slot = read_u64(state, STATE_SLOT_OFFSET)?;
+    if slot != era * SLOTS_PER_HISTORICAL_ROOT {
+        return Err(E2sError::Malformed(format!(
+            "beacon state at slot {slot} does not end era {era}"
+        )))
+    }
+
+    if era == 0 {
+        return Ok(B256::from_slice(
+            &state[GENESIS_VALIDATORS_ROOT_OFFSET..GENESIS_VALIDATORS_ROOT_OFFSET + 32],
+        ))
+    }
+    let block_roots = &state[BLOCK_ROOTS_OFFSET..BLOCK_ROOTS_OFFSET + ROOTS_SIZE];
+    let state_roots = &state[BLOCK_ROOTS_OFFSET + ROOTS_SIZE..BLOCK_ROOTS_OFFSET + 2 * ROOTS_SIZE];
+    Ok(hash_pair(&vector_root(block_roots).0, &vector_root(state_roots).0))
+}
+
+/// Writes the blocks and the state of an era into an era file.
+///
+/// [`EraWriter::finish`] must be called to write the state and the slot indices.
+#[derive(Debug)]
+pub struct EraWriter<W> {
+    writer: W,
+    era: u64,
+    written: u64,
+    /// Offsets of the blocks of the slots written so far, `None` for empty slots.
+    offsets: Vec<Option<u64>>,
+}
+
+impl<W: Write> EraWriter<W> {
+    /// Creates a new writer for the given era and writes the version record.
+    pub fn new(mut writer: W, era: u64) -> Result<Self, E2sError> {
+        let version = Entry::new(VERSION, Vec::new());
+        version.write(&mut writer)?;
+        Ok(Self { writer, era, written: version.encoded_len() as u64, offsets: Vec::new() })
+    }
+
+    /// Appends the SSZ encoded signed block of the given slot.
+    ///
+    /// Slots must be written in ascending order, slots that are skipped are recorded as empty.
+    pub fn write_block(&mut self, slot: u64, block: &[u8]) -> Result<(), E2sError> {
+        let slots = era_block_slots(self.era);
+        let next = slots.start + self.offsets.len() as u64;
+        if !slots.contains(&slot) || slot < next {
+            return Err(E2sError::Malformed(format!(
+                "cannot write block of slot {slot} into era {}, next slot is {next}",
+                self.era
+            )))
+        }
+
+        self.offsets.resize((slot - slots.start) as usize, None);
+        self.offsets.push(Some(self.written));
+        self.write_entry(Entry::new(COMPRESSED_SIGNED_BEACON_BLOCK, compress(block)?))
+    }
+
+    /// Writes the SSZ encoded state at the end of the era and the slot indices, and returns the
+    /// historical root of the era together with the underlying writer.
+    pub fn finish(mut self, state: &[u8]) -> Result<(B256, W), E2sError> {
+        let root = era_historical_root(self.era, state)?;
+
+        let state_offset = self.written;
+        self.write_entry(Entry::new(COMPRESSED_BEACON_STATE, compress(state)?))?;
+
+        let slots = era_block_slots(self.era);
+        if !slots.is_empty() {
+            let mut offsets = std::mem::take(&mut self.offsets);
+            offsets.resize(SLOTS_PER_HISTORICAL_ROOT as usize, None);
+            self.write_slot_index(slots.start, &offsets)?;
+        }
+        self.write_slot_index(slots.end, &[Some(state_offset)])?;
+
+        self.writer.flush()?;
+        Ok((root, self.writer))
+    }
+
+    /// Writes a slot index. Offsets are relative to the start of the index entry, empty slots
+    /// have an offset of zero.
+    fn write_slot_index(
+        &mut self,
+        starting_slot: u64,
+        offsets: &[Option<u64>],
+    ) -> Result<(), E2sError> {
+        let index_offset = self.written as i64;
+        let mut index = Vec::with_capacity(16 + offsets.len() * 8);
+        index.extend_from_slice(&starting_slot.to_le_bytes());
+        for offset in offsets {
+            let relative = offset.map_or(0, |offset| offset as i64 - index_offset);
+            index.extend_from_slice(&relative.to_le_bytes());
+        }
+        index.extend_from_slice(&(offsets.len() as u64).to_le_bytes());
+        self.write_entry(Entry::new(SLOT_INDEX, index))
+    }
+
+    fn write_entry(&mut self, entry: Entry) -> Result<(), E2sError> {
+        entry.write(&mut self.writer)?;
+        self.written += entry.encoded_len() as u64;
+        Ok(())
+    }
+}
+
+/// Trailing metadata of an era file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EraFooter {
+    /// The era of the file.
+    pub era: u64,
+    /// The historical root of the era, see [`era_historical_root`].
+    pub historical_root: B256,
+}
+
+/// Streams the signed blocks out of an era file.
+#[derive(Debug)]
+pub struct EraReader<R> {
+    reader: R,
+    /// Slots of the blocks read so far.
+    slots: Vec<u64>,
+    /// The SSZ encoded state, set once all blocks were read.
+    state: Option<Vec<u8>>,
+}
+
+impl<R: Read> EraReader<R> {
+    /// Creates a new reader and consumes the version record.
+    pub fn new(mut reader: R) -> Result<Self, E2sError> {
+        let version = Entry::read(&mut reader)?.ok_or(E2sError::MissingVersion)?;
+        if version.entry_type != VERSION {
+            return Err(E2sError::MissingVersion)
+        }
+        Ok(Self { reader, slots: Vec::new(), state: None })
+    }
+
+    /// Returns the next SSZ encoded signed block, or `None` once all blocks were read.
+    pub fn next_block(&mut self) -> Result<Option<Vec<u8>>, E2sError> {
+        if self.state.is_some() {
+            return Ok(None)
+        }
+
+        let entry = self.next_entry()?;
+        if entry.entry_type == COMPRESSED_BEACON_STATE {
+            self.state = Some(decompress(&entry.data)?);
+            return Ok(None)
+        }
+        entry.expect_type(COMPRESSED_SIGNED_BEACON_BLOCK)?;
+        let block = decompress(&entry.data)?;
+
+        let slot = SignedBeaconBlockView::new(&block)?.slot()?;
+        if self.slots.last().is_some_and(|last| *last >= slot) {
+            return Err(E2sError::Malformed(format!("block of slot {slot} is out of order")))
+        }
+        self.slots.push(slot);
+        Ok(Some(block))
+    }
+
+    /// Reads the slot indices and verifies that the blocks that were read and the state belong
+    /// to the same era.
+    ///
+    /// Must be called after [`EraReader::next_block`] returned `None`.
+    pub fn finish(mut self) -> Result<EraFooter, E2sError> {
+        let state = self
+            .state
+            .take()
+            .ok_or_else(|| E2sError::Malformed("not all blocks were read".to_string()))?;
+        let state_slot = read_u64(&state, STATE_SLOT_OFFSET)?;
+        if state_slot % SLOTS_PER_HISTORICAL_ROOT != 0 {
+            return Err(E2sError::Malformed(format!("beacon state at slot {state_slot}")))
+        }
+        let era = state_slot / SLOTS_PER_HISTORICAL_ROOT;
+        let historical_root = era_historical_root(era, &state)?;
+
+        let slots = era_block_slots(era);
+        if let Some(slot) = self.slots.iter().find(|slot| !slots.contains(slot)) {
+            return Err(E2sError::Malformed(format!(
+                "block of slot {slot} is not part of era {era}"
+            )))
+        }
+
+        // other entries may precede the slot indices
+        let mut entry = self.next_entry()?;
+        while entry.entry_type != SLOT_INDEX {
+            entry = self.next_entry()?;
+        }
+        if !slots.is_empty() {
+            let blocks = read_slot_index(&entry.data, slots.start, SLOTS_PER_HISTORICAL_ROOT)?;
+            if blocks != self.slots.len() as u64 {
+                return Err(E2sError::Malformed(format!(
+                    "block index holds {blocks} blocks, file holds {}",
+                    self.slots.len()
+                )))
+            }
+            entry = self.next_entry()?;
+            entry.expect_type(SLOT_INDEX)?;
+        }
+        read_slot_index(&entry.data, slots.end, 1)?;
+
+        Ok(EraFooter { era, historical_root })
+    }
+
+    fn next_entry(&mut self) -> Result<Entry, E2sError> {
+        Entry::read(&mut self.reader)?
+            .ok_or_else(|| E2sError::Malformed("unexpected end of file".to_string()))
+    }
+}
+
+/// Checks the starting slot and the number of slots of a slot index and returns the number of
+/// non-empty slots.
+fn read_slot_index(data: &[u8], starting_slot: u64, count: u64) -> Result<u64, E2sError> {
+    if data.len() != 16 + count as usize * 8 ||
+        read_u64(data, 0)? != starting_slot ||
+        read_u64(data, data.len() - 8)? != count
+    {
+        return Err(E2sError::Malformed(format!(
+            "expected slot index of {count} slots starting at {starting_slot}"
+        )))
+    }
+    Ok(data[8..data.len() - 8].chunks_exact(8).filter(|offset| offset != &[0; 8]).count() as u64)
+}
+
+/// View of the SSZ encoding of a `SignedBeaconBlock` that exposes the fields relevant to the
+/// execution layer.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedBeaconBlockView<'a> {
+    message: &'a [u8],
+    body: &'a [u8],
+}
+
+impl<'a> SignedBeaconBlockView<'a> {
+    /// Creates a view of the SSZ encoded signed block.
+    pub fn new(data: &'a [u8]) -> Result<Self, E2sError> {
+        let message = &data[read_offset(data, 0)?..];
+        let body = &message[read_offset(message, 80)?..];
+        Ok(Self { message, body })
+    }
+
+    /// Returns the slot of the block.
+    pub fn slot(&self) -> Result<u64, E2sError> {
+        read_u64(self.message, 0)
+    }
+
+    /// Returns the root of the parent beacon block.
+    pub fn parent_root(&self) -> Result<B256, E2sError> {
+        self.message
+            .get(16..48)
+            .map(B256::from_slice)
+            .ok_or_else(|| E2sError::Malformed("truncated beacon block".to_string()))
+    }
+
+    /// Returns the SSZ encoded execution payload, or `None` for blocks before Bellatrix.
+    pub fn execution_payload(&self) -> Result<Option<&'a [u8]>, E2sError> {
+        self.variable_field(EXECUTION_PAYLOAD_OFFSET, BELLATRIX_BODY_FIXED_SIZE)
+    }
+
+    /// Returns the version of the execution payload encoding: `1` for Bellatrix, `2` for Capella
+    /// and `3` since Deneb, or `None` for blocks before Bellatrix.
+    pub fn execution_payload_version(&self) -> Result<Option<u8>, E2sError> {
+        let version = match self.body_fixed_size()? {
+            size if size >= DENEB_BODY_FIXED_SIZE => 3,
+            size if size >= CAPELLA_BODY_FIXED_SIZE => 2,
+            size if size >= BELLATRIX_BODY_FIXED_SIZE => 1,
+            _ => return Ok(None),
+        };
+        Ok(Some(version))
+    }
+
+    /// Returns the KZG commitments of the blobs of the block, or `None` for blocks before Deneb.
+    pub fn blob_kzg_commitments(&self) -> Result<Option<impl Iterator<Item = &'a [u8]>>, E2sError> {
+        let Some(commitments) =
+            self.variable_field(BLOB_KZG_COMMITMENTS_OFFSET, DENEB_BODY_FIXED_SIZE)?
+        else {
+            return Ok(None)
+        };
+        if commitments.len() % KZG_COMMITMENT_SIZE != 0 {
+            return Err(E2sError::Malformed(format!(
+                "blob kzg commitments of {} bytes",
+                commitments.len()
+            )))
+        }
+        Ok(Some(commitments.chunks_exact(KZG_COMMITMENT_SIZE)))
+    }
+
+    /// Returns the SSZ encoded deposit, withdrawal and consolidation requests of the block, or
+    /// `None` for blocks before Electra.
+    ///
+    /// The requests are lists of fixed size containers, so their encoding is the concatenation of
+    /// the encoded requests, as expected by EIP-7685.
+    pub fn execution_requests(&self) -> Result<Option<[&'a [u8]; 3]>, E2sError> {
+        let Some(requests) =
+            self.variable_field(EXECUTION_REQUESTS_OFFSET, ELECTRA_BODY_FIXED_SIZE)?
+        else {
+            return Ok(None)
+        };
+        let deposits = read_offset(requests, 0)?;
+        let withdrawals = read_offset(requests, 4)?;
+        let consolidations = read_offset(requests, 8)?;
+        if deposits != 12 || withdrawals < deposits || consolidations < withdrawals {
+            return Err(E2sError::Malformed("invalid execution requests offsets".to_string()))
+        }
+        Ok(Some([
+            &requests[deposits..withdrawals],
+            &requests[withdrawals..consolidations],
+            &requests[consolidations..],
+        ]))
+    }
+
+    /// Returns the number and hash of the execution block, or `None` for blocks before
+    /// Bellatrix.
+    pub fn execution_block(&self) -> Result<Option<(BlockNumber, B256)>, E2sError> {
+        let Some(payload) = self.execution_payload()? else { return Ok(None) };
+        let number = read_u64(payload, 404)?;
+        let hash = payload
+            .get(472..504)
+            .map(B256::from_slice)
+            .ok_or_else(|| E2sError::Malformed("truncated execution payload".to_string()))?;
+        Ok(Some((number, hash)))
+    }
+
+    /// Size of the fixed part of the body, which identifies the fork of the block.
+    fn body_fixed_size(&self) -> Result<usize, E2sError> {
+        // the fixed part of the body ends where the first variable size field starts
+        read_offset(self.body, 200)
+    }
+
+    /// Returns the variable size field of the body whose offset is stored at `offset`, or `None`
+    /// if the body predates the fork with the given fixed size that introduced the field.
+    fn variable_field(
+        &self,
+        offset: usize,
+        introduced_fixed_size: usize,
+    ) -> Result<Option<&'a [u8]>, E2sError> {
+        let fixed_size = self.body_fixed_size()?;
+        if fixed_size < introduced_fixed_size {
+            return Ok(None)
+        }
+        let start = read_offset(self.body, offset)?;
+        let end = if fixed_size > offset + 4 {
+            read_offset(self.body, offset + 4)?
+        } else {
+            self.body.len()
+        };
+        self.body
+            .get(start..end)
+            .map(Some)
+            .ok_or_else(|| E2sError::Malformed(format!("invalid offset of body field {offset}")))
+    }
+}
+
+/// Returns the root of an SSZ vector of 32 byte chunks whose length is a power of two.
+fn vector_root(chunks: &[u8]) -> B256 {
+    let mut layer = chunks.chunks_exact(32).map(B256::from_slice).collect::<Vec<_>>();
+    while layer.len() > 1 {
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0].0, &pair[1].0)).collect();
+    }
+    layer.first().copied().unwrap_or_default()
+}
+
+fn read_u64(data: &[u8], at: usize) -> Result<u64, E2sError> {
+    data.get(at..at + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+        .ok_or_else(|| E2sError::Malformed(format!("truncated SSZ data at offset {at}")))
+}
+
+fn read_offset(data: &[u8], at: usize) -> Result<usize, E2sError> {
+    let offset = data
+        .get(at..at + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")) as usize)
+        .ok_or_else(|| E2sError::Malformed(format!("truncated SSZ data at offset {at}")))?;
+    if offset > data.len() {
+        return Err(E2sError::Malformed(format!("SSZ offset {offset} out of bounds")))
+    }
+    Ok(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::e2s::decompress;
+
+    fn state(slot: u64) -> Vec<u8> {
+        let mut state = vec![0u8; BLOCK_ROOTS_OFFSET + 2 * ROOTS_SIZE + 64];
+        state[GENESIS_VALIDATORS_ROOT_OFFSET] = 0xaa;
+        state[STATE_SLOT_OFFSET..STATE_SLOT_OFFSET + 8].copy_from_slice(&slot.to_le_bytes());
+        state
+    }
+
+    /// SSZ encoding of a signed block with a body of the given fixed size and an execution
+    /// payload holding the given number and hash.
+    ///
+    /// Deneb blocks hold a single blob commitment of `0x11` bytes and Electra blocks a single
+    /// withdrawal request of `0x22` bytes.
+    fn signed_block(slot: u64, body_fixed_size: usize, number: u64, hash: B256) -> Vec<u8> {
+        let mut payload = vec![0u8; 508];
+        payload[404..412].copy_from_slice(&number.to_le_bytes());
+        payload[472..504].copy_from_slice(hash.as_slice());
+
+        let mut requests = vec![0u8; 12];
+        for (at, offset) in [(0, 12u32), (4, 12), (8, 12 + 76)] {
+            requests[at..at + 4].copy_from_slice(&offset.to_le_bytes());
+        }
+        requests.extend_from_slice(&[0x22; 76]);
+
+        // variable size fields since bellatrix, in the order of their offsets
+        let fields = [
+            (BELLATRIX_BODY_FIXED_SIZE, payload),
+            (CAPELLA_BODY_FIXED_SIZE, Vec::new()),
+            (DENEB_BODY_FIXED_SIZE, vec![0x11; KZG_COMMITMENT_SIZE]),
+            (ELECTRA_BODY_FIXED_SIZE, requests),
+        ];
+
+        let mut body = vec![0u8; body_fixed_size];
+        for offset in (200..220).step_by(4) {
+            body[offset..offset + 4].copy_from_slice(&(body_fixed_size as u32).to_le_bytes());
+        }
+        let mut offset = EXECUTION_PAYLOAD_OFFSET;
+        for (introduced_fixed_size, field) in fields {
+            if body_fixed_size < introduced_fixed_size {
+                break
+            }
+            let start = body.len() as u32;
+            body[offset..offset + 4].copy_from_slice(&start.to_le_bytes());
+            body.extend_from_slice(&field);
+            offset += 4;
+        }
+
+        let mut message = vec![0u8; 84];
+        message[..8].copy_from_slice(&slot.to_le_bytes());
+        message[16..48].copy_from_slice(B256::with_last_byte(7).as_slice());
+        message[80..84].copy_from_slice(&84u32.to_le_bytes());
+        message.extend_from_slice(&body);
+
+        let mut block = vec![0u8; 100];
+        block[..4].copy_from_slice(&100u32.to_le_bytes());
+        block.extend_from_slice(&message);
+        block
+    }
+
+    #[test]
+    fn genesis_era_is_named_after_genesis_validators_root() {
+        let (root, buf) = EraWriter::new(Vec::new(), 0).unwrap().finish(&state(0)).unwrap();
+        assert_eq!(root[0], 0xaa);
+        assert_eq!(era_file_name("mainnet", 0, root), "mainnet-00000-aa000000.era");
+
+        // version, state and state index
+        let mut reader = buf.as_slice();
+        Entry::read(&mut reader).unwrap().unwrap().expect_type(VERSION).unwrap();
+        Entry::read(&mut reader).unwrap().unwrap().expect_type(COMPRESSED_BEACON_STATE).unwrap();
+        let index = Entry::read(&mut reader).unwrap().unwrap();
+        index.expect_type(SLOT_INDEX).unwrap();
+        assert_eq!(index.data.len(), 24);
+        assert!(Entry::read(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_state_of_another_era() {
+        let writer = EraWriter::new(Vec::new(), 2).unwrap();
+        assert!(writer.finish(&state(SLOTS_PER_HISTORICAL_ROOT)).is_err());
+    }
+
+    #[test]
+    fn slot_index_skips_empty_slots() {
+        let mut writer = EraWriter::new(Vec::new(), 1).unwrap();
+        let block = signed_block(1, 392, 1, B256::ZERO);
+        writer.write_block(1, &block).unwrap();
+        writer.write_block(3, &block).unwrap();
+        assert!(writer.write_block(2, &block).is_err());
+        assert!(writer.write_block(SLOTS_PER_HISTORICAL_ROOT, &block).is_err());
+        let (_, buf) = writer.finish(&state(SLOTS_PER_HISTORICAL_ROOT)).unwrap();
+
+        // the block index precedes the state index of 24 data bytes
+        let block_index_len = 16 + SLOTS_PER_HISTORICAL_ROOT as usize * 8;
+        let index_start = buf.len() - (8 + 24) - (8 + block_index_len);
+        let index = Entry::read(&mut &buf[index_start..]).unwrap().unwrap();
+        index.expect_type(SLOT_INDEX).unwrap();
+        let offset = |slot: usize| {
+            i64::from_le_bytes(index.data[8 + slot * 8..16 + slot * 8].try_into().unwrap())
+        };
+        assert_eq!(offset(0), 0);
+        assert_eq!(offset(2), 0);
+        assert_ne!(offset(1), 0);
+
+        let entry =
+            Entry::read(&mut &buf[(index_start as i64 + offset(3)) as usize..]).unwrap().unwrap();
+        entry.expect_type(COMPRESSED_SIGNED_BEACON_BLOCK).unwrap();
+        assert_eq!(decompress(&entry.data).unwrap(), block);
+    }
+
+    #[test]
+    fn historical_root_of_roots() {
+        // root of a vector of 8192 zero chunks
+        let mut zero = B256::ZERO;
+        for _ in 0..SLOTS_PER_HISTORICAL_ROOT.trailing_zeros() {
+            zero = hash_pair(&zero.0, &zero.0);
+        }
+        let mut state = state(SLOTS_PER_HISTORICAL_ROOT);
+        assert_eq!(era_historical_root(1, &state).unwrap(), hash_pair(&zero.0, &zero.0));
+
+        state[BLOCK_ROOTS_OFFSET + ROOTS_SIZE] = 1;
+        assert_ne!(era_historical_root(1, &state).unwrap(), hash_pair(&zero.0, &zero.0));
+    }
+
+    #[test]
+    fn execution_block_of_signed_block() {
+        let hash = B256::with_last_byte(0x42);
+        for body_fixed_size in [384, 388, 392, 396] {
+            let block = signed_block(5, body_fixed_size, 17, hash);
+            let view = SignedBeaconBlockView::new(&block).unwrap();
+            assert_eq!(view.slot().unwrap(), 5);
+            assert_eq!(view.parent_root().unwrap(), B256::with_last_byte(7));
+            assert_eq!(view.execution_block().unwrap(), Some((17, hash)));
+        }
+
+        // altair blocks have no execution payload
+        let block = signed_block(5, 380, 17, hash);
+        let view = SignedBeaconBlockView::new(&block).unwrap();
+        assert_eq!(view.execution_block().unwrap(), None);
+        assert_eq!(view.execution_payload_version().unwrap(), None);
+    }
+
+    #[test]
+    fn fork_specific_fields_of_signed_block() {
+        let block = signed_block(5, 388, 17, B256::ZERO);
+        let view = SignedBeaconBlockView::new(&block).unwrap();
+        assert_eq!(view.execution_payload_version().unwrap(), Some(2));
+        assert!(view.blob_kzg_commitments().unwrap().is_none());
+        assert_eq!(view.execution_requests().unwrap(), None);
+
+        let block = signed_block(5, 392, 17, B256::ZERO);
+        let view = SignedBeaconBlockView::new(&block).unwrap();
+        assert_eq!(view.execution_payload_version().unwrap(), Some(3));
+        let commitments = view.blob_kzg_commitments().unwrap().unwrap().collect::<Vec<_>>();
+        assert_eq!(commitments, [[0x11; KZG_COMMITMENT_SIZE].as_slice()]);
+        assert_eq!(view.execution_requests().unwrap(), None);
+
+        let block = signed_block(5, 396, 17, B256::ZERO);
+        let view = SignedBeaconBlockView::new(&block).unwrap();
+        assert_eq!(view.execution_payload_version().unwrap(), Some(3));
+        assert_eq!(view.blob_kzg_commitments().unwrap().unwrap().count(), 1);
+        let [deposits, withdrawals, consolidations] = view.execution_requests().unwrap().unwrap();
+        assert!(deposits.is_empty() && consolidations.is_empty());
+        assert_eq!(withdrawals, [0x22; 76]);
+    }
+
+    #[test]
+    fn read_era_file() {
+        let slots = era_block_slots(2);
+        let blocks = [slots.start, slots.start + 3, slots.end - 1]
+            .map(|slot| signed_block(slot, 392, slot, B256::ZERO));
+        let mut writer = EraWriter::new(Vec::new(), 2).unwrap();
+        for (slot, block) in [slots.start, slots.start + 3, slots.end - 1].iter().zip(&blocks) {
+            writer.write_block(*slot, block).unwrap();
+        }
+        let (root, buf) = writer.finish(&state(slots.end)).unwrap();
+
+        let mut reader = EraReader::new(buf.as_slice()).unwrap();
+        for block in &blocks {
+            assert_eq!(reader.next_block().unwrap().as_ref(), Some(block));
+        }
+        assert_eq!(reader.next_block().unwrap(), None);
+        assert_eq!(reader.finish().unwrap(), EraFooter { era: 2, historical_root: root });
+
+        // the state index is missing
+        let mut reader = EraReader::new(&buf[..buf.len() - (8 + 24)]).unwrap();
+        while reader.next_block().unwrap().is_some() {}
+        assert!(reader.finish().is_err());
+    }
+}
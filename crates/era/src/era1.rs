@@ -0,0 +1,307 @@
+//! Era1 files: archives of up to [`MAX_BLOCKS_PER_ERA1`] pre-merge blocks.
+//!
+//! ```text
+//! era1 := Version | block-tuple* | other-entries* | Accumulator | BlockIndex
+//! block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+//! ```
+//!
+//! Headers, bodies and receipts are stored as snappy framed RLP, the total difficulty as a
+//! little-endian `uint256`.
+
+use crate::{
+    e2s::{compress, decompress, E2sError, Entry, VERSION},
+    Accumulator,
+};
+use alloy_primitives::{keccak256, BlockNumber, B256, U256};
+use std::io::{Read, Write};
+
+/// Maximum number of blocks stored in a single era1 file.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// Entry type of a snappy compressed RLP header.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// Entry type of a snappy compressed RLP block body.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Entry type of snappy compressed RLP receipts.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Entry type of the total difficulty at a block.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Entry type of the header accumulator root.
+pub const ACCUMULATOR: u16 = 0x07;
+/// Entry type of the block index.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// Returns the canonical file name of an era1 file, e.g. `mainnet-00000-5ec1ffb8.era1`.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator: B256) -> String {
+    format!("{network}-{epoch:05}-{}.era1", alloy_primitives::hex::encode(&accumulator[..4]))
+}
+
+/// Uncompressed contents of a single block in an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTuple {
+    /// RLP encoded header.
+    pub header: Vec<u8>,
+    /// RLP encoded block body.
+    pub body: Vec<u8>,
+    /// RLP encoded list of receipts, including their blooms.
+    pub receipts: Vec<u8>,
+    /// Total difficulty at this block.
+    pub total_difficulty: U256,
+}
+
+impl BlockTuple {
+    /// Returns the hash of the block.
+    pub fn hash(&self) -> B256 {
+        keccak256(&self.header)
+    }
+}
+
+/// Writes blocks into an era1 file.
+///
+/// [`Era1Writer::finish`] must be called to write the accumulator and block index.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: W,
+    starting_number: BlockNumber,
+    written: u64,
+    offsets: Vec<u64>,
+    accumulator: Accumulator,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer for an era1 file starting at the given block number and writes the
+    /// version record.
+    pub fn new(mut writer: W, starting_number: BlockNumber) -> Result<Self, E2sError> {
+        let version = Entry::new(VERSION, Vec::new());
+        version.write(&mut writer)?;
+        Ok(Self {
+            writer,
+            starting_number,
+            written: version.encoded_len() as u64,
+            offsets: Vec::new(),
+            accumulator: Accumulator::default(),
+        })
+    }
+
+    /// Returns the number of the next block to be written.
+    pub fn next_block_number(&self) -> BlockNumber {
+        self.starting_number + self.offsets.len() as u64
+    }
+
+    /// Returns `true` if the file holds the maximum number of blocks.
+    pub fn is_full(&self) -> bool {
+        self.offsets.len() >= MAX_BLOCKS_PER_ERA1
+    }
+
+    /// Appends the next block.
+    pub fn write_block(&mut self, block: &BlockTuple) -> Result<(), E2sError> {
+        if self.is_full() {
+            return Err(E2sError::Malformed(format!(
+                "era1 file cannot hold more than {MAX_BLOCKS_PER_ERA1} blocks"
+            )))
+        }
+
+        self.offsets.push(self.written);
+        self.accumulator.push(block.hash(), block.total_difficulty);
+
+        self.write_entry(Entry::new(COMPRESSED_HEADER, compress(&block.header)?))?;
+        self.write_entry(Entry::new(COMPRESSED_BODY, compress(&block.body)?))?;
+        self.write_entry(Entry::new(COMPRESSED_RECEIPTS, compress(&block.receipts)?))?;
+        self.write_entry(Entry::new(
+            TOTAL_DIFFICULTY,
+            block.total_difficulty.to_le_bytes::<32>().to_vec(),
+        ))
+    }
+
+    /// Writes the accumulator and the block index, and returns the accumulator root together
+    /// with the underlying writer.
+    pub fn finish(mut self) -> Result<(B256, W), E2sError> {
+        let root = self.accumulator.root();
+        self.write_entry(Entry::new(ACCUMULATOR, root.to_vec()))?;
+
+        // offsets are relative to the start of the block index entry
+        let index_offset = self.written as i64;
+        let mut index = Vec::with_capacity(16 + self.offsets.len() * 8);
+        index.extend_from_slice(&self.starting_number.to_le_bytes());
+        for offset in &self.offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        self.write_entry(Entry::new(BLOCK_INDEX, index))?;
+
+        self.writer.flush()?;
+        Ok((root, self.writer))
+    }
+
+    fn write_entry(&mut self, entry: Entry) -> Result<(), E2sError> {
+        entry.write(&mut self.writer)?;
+        self.written += entry.encoded_len() as u64;
+        Ok(())
+    }
+}
+
+/// Trailing metadata of an era1 file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Era1Footer {
+    /// The accumulator root stored in the file.
+    pub accumulator: B256,
+    /// Number of the first block in the file.
+    pub starting_number: BlockNumber,
+    /// Number of blocks in the file.
+    pub count: u64,
+}
+
+/// Streams blocks out of an era1 file.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    reader: R,
+    accumulator: Accumulator,
+    /// The stored accumulator root, set once all block tuples were read.
+    stored_root: Option<B256>,
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader and consumes the version record.
+    pub fn new(mut reader: R) -> Result<Self, E2sError> {
+        let version = Entry::read(&mut reader)?.ok_or(E2sError::MissingVersion)?;
+        if version.entry_type != VERSION {
+            return Err(E2sError::MissingVersion)
+        }
+        Ok(Self { reader, accumulator: Accumulator::default(), stored_root: None })
+    }
+
+    /// Returns the next block, or `None` once all blocks were read.
+    pub fn next_block(&mut self) -> Result<Option<BlockTuple>, E2sError> {
+        if self.stored_root.is_some() {
+            return Ok(None)
+        }
+
+        let entry = self.next_entry()?;
+        if entry.entry_type == ACCUMULATOR {
+            self.stored_root = Some(read_b256(&entry.data)?);
+            return Ok(None)
+        }
+        entry.expect_type(COMPRESSED_HEADER)?;
+        let header = decompress(&entry.data)?;
+
+        let entry = self.next_entry()?;
+        entry.expect_type(COMPRESSED_BODY)?;
+        let body = decompress(&entry.data)?;
+
+        let entry = self.next_entry()?;
+        entry.expect_type(COMPRESSED_RECEIPTS)?;
+        let receipts = decompress(&entry.data)?;
+
+        let entry = self.next_entry()?;
+        entry.expect_type(TOTAL_DIFFICULTY)?;
+        let total_difficulty = U256::from_le_bytes(read_b256(&entry.data)?.0);
+
+        let block = BlockTuple { header, body, receipts, total_difficulty };
+        self.accumulator.push(block.hash(), block.total_difficulty);
+        Ok(Some(block))
+    }
+
+    /// Reads the block index and verifies that the stored accumulator matches the blocks that
+    /// were read.
+    ///
+    /// Must be called after [`Era1Reader::next_block`] returned `None`.
+    pub fn finish(mut self) -> Result<Era1Footer, E2sError> {
+        let accumulator = self
+            .stored_root
+            .ok_or_else(|| E2sError::Malformed("not all blocks were read".to_string()))?;
+        if accumulator != self.accumulator.root() {
+            return Err(E2sError::Malformed(format!(
+                "accumulator mismatch: stored {accumulator}, computed {}",
+                self.accumulator.root()
+            )))
+        }
+
+        let entry = self.next_entry()?;
+        entry.expect_type(BLOCK_INDEX)?;
+        let data = entry.data;
+        if data.len() < 16 || data.len() % 8 != 0 {
+            return Err(E2sError::Malformed(format!("invalid block index length {}", data.len())))
+        }
+        let starting_number = u64::from_le_bytes(data[..8].try_into().expect("checked length"));
+        let count = u64::from_le_bytes(data[data.len() - 8..].try_into().expect("checked length"));
+        if count != self.accumulator.len() as u64 || (data.len() - 16) / 8 != count as usize {
+            return Err(E2sError::Malformed(format!(
+                "block index holds {count} entries, file holds {} blocks",
+                self.accumulator.len()
+            )))
+        }
+
+        Ok(Era1Footer { accumulator, starting_number, count })
+    }
+
+    fn next_entry(&mut self) -> Result<Entry, E2sError> {
+        Entry::read(&mut self.reader)?
+            .ok_or_else(|| E2sError::Malformed("unexpected end of file".to_string()))
+    }
+}
+
+fn read_b256(data: &[u8]) -> Result<B256, E2sError> {
+    B256::try_from(data)
+        .map_err(|_| E2sError::Malformed(format!("expected 32 bytes, got {}", data.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(n: u8) -> BlockTuple {
+        BlockTuple {
+            header: vec![0xc1, n],
+            body: vec![0xc2, 0xc0, 0xc0],
+            receipts: vec![0xc0],
+            total_difficulty: U256::from(n) * U256::from(1_000),
+        }
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = (0..5).map(block).collect::<Vec<_>>();
+
+        let mut writer = Era1Writer::new(Vec::new(), 8192).unwrap();
+        for block in &blocks {
+            writer.write_block(block).unwrap();
+        }
+        assert_eq!(writer.next_block_number(), 8197);
+        let (root, buf) = writer.finish().unwrap();
+
+        let mut reader = Era1Reader::new(buf.as_slice()).unwrap();
+        let mut read = Vec::new();
+        while let Some(block) = reader.next_block().unwrap() {
+            read.push(block);
+        }
+        assert_eq!(read, blocks);
+
+        let footer = reader.finish().unwrap();
+        assert_eq!(footer, Era1Footer { accumulator: root, starting_number: 8192, count: 5 });
+    }
+
+    #[test]
+    fn block_index_offsets_point_to_headers() {
+        let mut writer = Era1Writer::new(Vec::new(), 0).unwrap();
+        writer.write_block(&block(1)).unwrap();
+        writer.write_block(&block(2)).unwrap();
+        let (_, buf) = writer.finish().unwrap();
+
+        // the block index is the last entry: header + start + 2 offsets + count
+        let index_start = buf.len() - (8 + 8 * 4);
+        let second = index_start + 8 + 16;
+        let offset = i64::from_le_bytes(buf[second..second + 8].try_into().unwrap());
+        let header_pos = (index_start as i64 + offset) as usize;
+        let entry = Entry::read(&mut &buf[header_pos..]).unwrap().unwrap();
+        assert_eq!(entry.entry_type, COMPRESSED_HEADER);
+        assert_eq!(decompress(&entry.data).unwrap(), block(2).header);
+    }
+
+    #[test]
+    fn file_name() {
+        assert_eq!(
+            era1_file_name("mainnet", 1, B256::with_last_byte(1)),
+            "mainnet-00001-00000000.era1"
+        );
+    }
+}
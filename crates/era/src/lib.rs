@@ -0,0 +1,35 @@
+//! Era and era1 history archive formats.
+//!
+//! Both formats are built on top of the `e2store` container: a flat sequence of typed,
+//! length-prefixed entries. Era1 files store up to [`MAX_BLOCKS_PER_ERA1`] pre-merge blocks
+//! together with their receipts, total difficulty and a header accumulator, and are interchanged
+//! between execution clients and the Portal network. Era files store the signed beacon blocks and
+//! the beacon state of an era of [`SLOTS_PER_HISTORICAL_ROOT`] slots, which embed the execution
+//! payloads after the merge.
+//!
+//! See also <https://github.com/eth-clients/e2store-format-specs>
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{header_record_root, Accumulator};
+
+pub mod e2s;
+pub use e2s::{E2sError, Entry};
+
+pub mod era;
+pub use era::{
+    era_block_slots, era_file_name, era_historical_root, EraFooter, EraReader, EraWriter,
+    SignedBeaconBlockView, SLOTS_PER_HISTORICAL_ROOT,
+};
+
+pub mod era1;
+pub use era1::{
+    era1_file_name, BlockTuple, Era1Footer, Era1Reader, Era1Writer, MAX_BLOCKS_PER_ERA1,
+};
@@ -5,50 +5,77 @@ use crate::{hardforks::Hardforks, EthereumHardfork, ForkCondition};
 /// Helper methods for Ethereum forks.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait EthereumHardforks: Hardforks {
+    /// Retrieves the activation condition of the given [`EthereumHardfork`].
+    ///
+    /// All other methods of this trait resolve Ethereum forks through this method. Chains whose
+    /// fork schedule diverges from mainnet, e.g. with different fork names or ordering, can
+    /// override it to map each Ethereum fork onto their own schedule.
+    fn ethereum_fork_activation(&self, fork: EthereumHardfork) -> ForkCondition {
+        self.fork(fork)
+    }
+
+    /// Convenience method to check if the given [`EthereumHardfork`] is active at a given
+    /// timestamp.
+    fn is_ethereum_fork_active_at_timestamp(&self, fork: EthereumHardfork, timestamp: u64) -> bool {
+        self.ethereum_fork_activation(fork).active_at_timestamp(timestamp)
+    }
+
+    /// Convenience method to check if the given [`EthereumHardfork`] is active at a given block
+    /// number.
+    fn is_ethereum_fork_active_at_block(&self, fork: EthereumHardfork, block_number: u64) -> bool {
+        self.ethereum_fork_activation(fork).active_at_block(block_number)
+    }
+
     /// Convenience method to check if [`EthereumHardfork::Shanghai`] is active at a given
     /// timestamp.
     fn is_shanghai_active_at_timestamp(&self, timestamp: u64) -> bool {
-        self.is_fork_active_at_timestamp(EthereumHardfork::Shanghai, timestamp)
+        self.is_ethereum_fork_active_at_timestamp(EthereumHardfork::Shanghai, timestamp)
     }
 
     /// Convenience method to check if [`EthereumHardfork::Cancun`] is active at a given timestamp.
     fn is_cancun_active_at_timestamp(&self, timestamp: u64) -> bool {
-        self.is_fork_active_at_timestamp(EthereumHardfork::Cancun, timestamp)
+        self.is_ethereum_fork_active_at_timestamp(EthereumHardfork::Cancun, timestamp)
     }
 
     /// Convenience method to check if [`EthereumHardfork::Prague`] is active at a given timestamp.
     fn is_prague_active_at_timestamp(&self, timestamp: u64) -> bool {
-        self.is_fork_active_at_timestamp(EthereumHardfork::Prague, timestamp)
+        self.is_ethereum_fork_active_at_timestamp(EthereumHardfork::Prague, timestamp)
     }
 
     /// Convenience method to check if [`EthereumHardfork::Osaka`] is active at a given timestamp.
     fn is_osaka_active_at_timestamp(&self, timestamp: u64) -> bool {
-        self.is_fork_active_at_timestamp(EthereumHardfork::Osaka, timestamp)
+        self.is_ethereum_fork_active_at_timestamp(EthereumHardfork::Osaka, timestamp)
+    }
+
+    /// Convenience method to check if [`EthereumHardfork::London`] is active at a given block
+    /// number.
+    fn is_london_active_at_block(&self, block_number: u64) -> bool {
+        self.is_ethereum_fork_active_at_block(EthereumHardfork::London, block_number)
     }
 
     /// Convenience method to check if [`EthereumHardfork::Byzantium`] is active at a given block
     /// number.
     fn is_byzantium_active_at_block(&self, block_number: u64) -> bool {
-        self.fork(EthereumHardfork::Byzantium).active_at_block(block_number)
+        self.is_ethereum_fork_active_at_block(EthereumHardfork::Byzantium, block_number)
     }
 
     /// Convenience method to check if [`EthereumHardfork::SpuriousDragon`] is active at a given
     /// block number.
     fn is_spurious_dragon_active_at_block(&self, block_number: u64) -> bool {
-        self.fork(EthereumHardfork::SpuriousDragon).active_at_block(block_number)
+        self.is_ethereum_fork_active_at_block(EthereumHardfork::SpuriousDragon, block_number)
     }
 
     /// Convenience method to check if [`EthereumHardfork::Homestead`] is active at a given block
     /// number.
     fn is_homestead_active_at_block(&self, block_number: u64) -> bool {
-        self.fork(EthereumHardfork::Homestead).active_at_block(block_number)
+        self.is_ethereum_fork_active_at_block(EthereumHardfork::Homestead, block_number)
     }
 
     /// The Paris hardfork (merge) is activated via block number. If we have knowledge of the block,
     /// this function will return true if the block number is greater than or equal to the Paris
     /// (merge) block.
     fn is_paris_active_at_block(&self, block_number: u64) -> Option<bool> {
-        match self.fork(EthereumHardfork::Paris) {
+        match self.ethereum_fork_activation(EthereumHardfork::Paris) {
             ForkCondition::Block(paris_block) => Some(block_number >= paris_block),
             ForkCondition::TTD { fork_block, .. } => {
                 fork_block.map(|paris_block| block_number >= paris_block)
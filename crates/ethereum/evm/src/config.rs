@@ -1,4 +1,4 @@
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::EthereumHardforks;
 use reth_ethereum_forks::{EthereumHardfork, Head};
 
 /// Returns the revm [`SpecId`](revm_primitives::SpecId) at the given timestamp.
@@ -8,7 +8,7 @@ use reth_ethereum_forks::{EthereumHardfork, Head};
 /// This is only intended to be used after the merge, when hardforks are activated by
 /// timestamp.
 pub fn revm_spec_by_timestamp_after_merge(
-    chain_spec: &ChainSpec,
+    chain_spec: &impl EthereumHardforks,
     timestamp: u64,
 ) -> revm_primitives::SpecId {
     if chain_spec.is_osaka_active_at_timestamp(timestamp) {
@@ -25,38 +25,47 @@ pub fn revm_spec_by_timestamp_after_merge(
 }
 
 /// Map the latest active hardfork at the given block to a revm [`SpecId`](revm_primitives::SpecId).
-pub fn revm_spec(chain_spec: &ChainSpec, block: &Head) -> revm_primitives::SpecId {
-    if chain_spec.fork(EthereumHardfork::Prague).active_at_head(block) {
+pub fn revm_spec(chain_spec: &impl EthereumHardforks, block: &Head) -> revm_primitives::SpecId {
+    if chain_spec.ethereum_fork_activation(EthereumHardfork::Prague).active_at_head(block) {
         revm_primitives::PRAGUE
-    } else if chain_spec.fork(EthereumHardfork::Cancun).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Cancun).active_at_head(block) {
         revm_primitives::CANCUN
-    } else if chain_spec.fork(EthereumHardfork::Shanghai).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Shanghai).active_at_head(block)
+    {
         revm_primitives::SHANGHAI
-    } else if chain_spec.fork(EthereumHardfork::Paris).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Paris).active_at_head(block) {
         revm_primitives::MERGE
-    } else if chain_spec.fork(EthereumHardfork::London).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::London).active_at_head(block) {
         revm_primitives::LONDON
-    } else if chain_spec.fork(EthereumHardfork::Berlin).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Berlin).active_at_head(block) {
         revm_primitives::BERLIN
-    } else if chain_spec.fork(EthereumHardfork::Istanbul).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Istanbul).active_at_head(block)
+    {
         revm_primitives::ISTANBUL
-    } else if chain_spec.fork(EthereumHardfork::Petersburg).active_at_head(block) {
+    } else if chain_spec
+        .ethereum_fork_activation(EthereumHardfork::Petersburg)
+        .active_at_head(block)
+    {
         revm_primitives::PETERSBURG
-    } else if chain_spec.fork(EthereumHardfork::Byzantium).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Byzantium).active_at_head(block)
+    {
         revm_primitives::BYZANTIUM
-    } else if chain_spec.fork(EthereumHardfork::SpuriousDragon).active_at_head(block) {
+    } else if chain_spec
+        .ethereum_fork_activation(EthereumHardfork::SpuriousDragon)
+        .active_at_head(block)
+    {
         revm_primitives::SPURIOUS_DRAGON
-    } else if chain_spec.fork(EthereumHardfork::Tangerine).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Tangerine).active_at_head(block)
+    {
         revm_primitives::TANGERINE
-    } else if chain_spec.fork(EthereumHardfork::Homestead).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Homestead).active_at_head(block)
+    {
         revm_primitives::HOMESTEAD
-    } else if chain_spec.fork(EthereumHardfork::Frontier).active_at_head(block) {
+    } else if chain_spec.ethereum_fork_activation(EthereumHardfork::Frontier).active_at_head(block)
+    {
         revm_primitives::FRONTIER
     } else {
-        panic!(
-            "invalid hardfork chainspec: expected at least one hardfork, got {:?}",
-            chain_spec.hardforks
-        )
+        panic!("invalid hardfork chainspec: expected at least one hardfork")
     }
 }
 
@@ -64,7 +73,8 @@ pub fn revm_spec(chain_spec: &ChainSpec, block: &Head) -> revm_primitives::SpecI
 mod tests {
     use super::*;
     use crate::U256;
-    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+    use reth_ethereum_forks::{ForkCondition, ForkFilter, ForkId, Hardfork, Hardforks};
 
     #[test]
     fn test_revm_spec_by_timestamp_after_merge() {
@@ -152,17 +162,17 @@ mod tests {
     #[test]
     fn test_eth_spec() {
         assert_eq!(
-            revm_spec(&MAINNET, &Head { timestamp: 1710338135, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { timestamp: 1710338135, ..Default::default() }),
             revm_primitives::CANCUN
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { timestamp: 1681338455, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { timestamp: 1681338455, ..Default::default() }),
             revm_primitives::SHANGHAI
         );
 
         assert_eq!(
             revm_spec(
-                &MAINNET,
+                MAINNET.as_ref(),
                 &Head {
                     total_difficulty: U256::from(58_750_000_000_000_000_000_010_u128),
                     difficulty: U256::from(10_u128),
@@ -174,7 +184,7 @@ mod tests {
         // TTD trumps the block number
         assert_eq!(
             revm_spec(
-                &MAINNET,
+                MAINNET.as_ref(),
                 &Head {
                     number: 15537394 - 10,
                     total_difficulty: U256::from(58_750_000_000_000_000_000_010_u128),
@@ -185,40 +195,108 @@ mod tests {
             revm_primitives::MERGE
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 15537394 - 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 15537394 - 10, ..Default::default() }),
             revm_primitives::LONDON
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 12244000 + 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 12244000 + 10, ..Default::default() }),
             revm_primitives::BERLIN
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 12244000 - 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 12244000 - 10, ..Default::default() }),
             revm_primitives::ISTANBUL
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 7280000 + 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 7280000 + 10, ..Default::default() }),
             revm_primitives::PETERSBURG
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 7280000 - 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 7280000 - 10, ..Default::default() }),
             revm_primitives::BYZANTIUM
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 2675000 + 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 2675000 + 10, ..Default::default() }),
             revm_primitives::SPURIOUS_DRAGON
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 2675000 - 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 2675000 - 10, ..Default::default() }),
             revm_primitives::TANGERINE
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 1150000 + 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 1150000 + 10, ..Default::default() }),
             revm_primitives::HOMESTEAD
         );
         assert_eq!(
-            revm_spec(&MAINNET, &Head { number: 1150000 - 10, ..Default::default() }),
+            revm_spec(MAINNET.as_ref(), &Head { number: 1150000 - 10, ..Default::default() }),
             revm_primitives::FRONTIER
         );
     }
+
+    /// A chain that follows mainnet's fork schedule, except for a delayed Shanghai.
+    #[derive(Debug, Clone)]
+    struct DelayedShanghai {
+        inner: ChainSpec,
+        shanghai: ForkCondition,
+    }
+
+    impl Hardforks for DelayedShanghai {
+        fn fork<H: Hardfork>(&self, fork: H) -> ForkCondition {
+            self.inner.fork(fork)
+        }
+
+        fn forks_iter(&self) -> impl Iterator<Item = (&dyn Hardfork, ForkCondition)> {
+            self.inner.forks_iter()
+        }
+
+        fn fork_id(&self, head: &Head) -> ForkId {
+            self.inner.fork_id(head)
+        }
+
+        fn latest_fork_id(&self) -> ForkId {
+            self.inner.latest_fork_id()
+        }
+
+        fn fork_filter(&self, head: Head) -> ForkFilter {
+            self.inner.fork_filter(head)
+        }
+    }
+
+    impl EthereumHardforks for DelayedShanghai {
+        fn ethereum_fork_activation(&self, fork: EthereumHardfork) -> ForkCondition {
+            match fork {
+                EthereumHardfork::Shanghai => self.shanghai,
+                fork => self.inner.ethereum_fork_activation(fork),
+            }
+        }
+
+        fn get_final_paris_total_difficulty(&self) -> Option<U256> {
+            self.inner.get_final_paris_total_difficulty()
+        }
+
+        fn final_paris_total_difficulty(&self, block_number: u64) -> Option<U256> {
+            EthereumHardforks::final_paris_total_difficulty(&self.inner, block_number)
+        }
+    }
+
+    #[test]
+    fn test_revm_spec_custom_fork_schedule() {
+        let chain_spec = DelayedShanghai {
+            inner: ChainSpecBuilder::mainnet().shanghai_activated().build(),
+            shanghai: ForkCondition::Timestamp(1000),
+        };
+
+        assert_eq!(revm_spec_by_timestamp_after_merge(&chain_spec, 999), revm_primitives::MERGE);
+        assert_eq!(
+            revm_spec_by_timestamp_after_merge(&chain_spec, 1000),
+            revm_primitives::SHANGHAI
+        );
+        assert_eq!(
+            revm_spec(&chain_spec, &Head { timestamp: 999, ..Default::default() }),
+            revm_primitives::MERGE
+        );
+        assert_eq!(
+            revm_spec(&chain_spec, &Head { timestamp: 1000, ..Default::default() }),
+            revm_primitives::SHANGHAI
+        );
+    }
 }
@@ -232,7 +232,11 @@ where
             post_block_balance_increments(&self.chain_spec, block, total_difficulty);
 
         // Irregular state change at Ethereum DAO hardfork
-        if self.chain_spec.fork(EthereumHardfork::Dao).transitions_at_block(block.number) {
+        if self
+            .chain_spec
+            .ethereum_fork_activation(EthereumHardfork::Dao)
+            .transitions_at_block(block.number)
+        {
             // drain balances from hardcoded addresses.
             let drained_balance: u128 = self
                 .state
@@ -22,7 +22,7 @@ use core::convert::Infallible;
 use alloc::{sync::Arc, vec::Vec};
 use alloy_consensus::Header;
 use alloy_primitives::{Address, Bytes, TxKind, U256};
-use reth_chainspec::{ChainSpec, Head};
+use reth_chainspec::{ChainSpec, EthereumHardforks, Head};
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv, NextBlockEnvAttributes};
 use reth_primitives::{transaction::FillTxEnv, TransactionSigned};
 use revm_primitives::{
@@ -157,7 +157,11 @@ impl ConfigureEvmEnv for EthEvmConfig {
 
         // If we are on the London fork boundary, we need to multiply the parent's gas limit by the
         // elasticity multiplier to get the new gas limit.
-        if self.chain_spec.fork(EthereumHardfork::London).transitions_at_block(parent.number + 1) {
+        if self
+            .chain_spec
+            .ethereum_fork_activation(EthereumHardfork::London)
+            .transitions_at_block(parent.number + 1)
+        {
             let elasticity_multiplier = self
                 .chain_spec
                 .base_fee_params_at_timestamp(attributes.timestamp)
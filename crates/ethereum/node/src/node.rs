@@ -3,7 +3,9 @@
 use std::sync::Arc;
 
 use alloy_consensus::Header;
-use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_basic_payload_builder::{
+    BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig, ParallelPayloadBuilder,
+};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::ChainSpec;
 use reth_ethereum_engine_primitives::{
@@ -31,8 +33,10 @@ use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    alt::{maintain_alt_mempool, AltMempool},
+    blobstore::DiskFileBlobStore,
+    validate::KzgSettingsHandle,
+    EthTransactionPool, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 
@@ -44,6 +48,7 @@ pub struct EthPrimitives;
 
 impl NodePrimitives for EthPrimitives {
     type Block = Block;
+    type BlockHeader = Header;
     type SignedTx = TransactionSigned;
     type TxType = TxType;
     type Receipt = Receipt;
@@ -178,11 +183,14 @@ where
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let kzg_settings = KzgSettingsHandle::new(ctx.kzg_settings()?);
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
-            .kzg_settings(ctx.kzg_settings()?)
+            .kzg_settings_handle(kzg_settings.clone())
+            .with_lazy_blob_verification(ctx.config().txpool.lazy_blob_verification)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+            .with_validation_batch_size(ctx.config().txpool.validation_batch_size)
             .build_with_tasks(
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
@@ -213,6 +221,12 @@ where
                 },
             );
 
+            // apply changes of the reth.toml txpool section while the node is running
+            ctx.spawn_pool_config_reload(pool.clone());
+
+            // verify the proofs of blob transactions that were added without verification
+            ctx.spawn_lazy_blob_verification(pool.clone(), kzg_settings)?;
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -234,9 +248,18 @@ where
 /// A basic ethereum payload service.
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder<Alt = ()> {
+    /// The alternative mempool whose transactions are included ahead of the pool transactions.
+    pub alt_mempool: Alt,
+}
+
+impl<Alt: AltMempool> EthereumPayloadBuilder<Alt> {
+    /// Configures the [`AltMempool`] whose transactions are included in payloads ahead of the
+    /// transactions of the pool.
+    pub fn with_alt_mempool<A: AltMempool>(self, alt_mempool: A) -> EthereumPayloadBuilder<A> {
+        EthereumPayloadBuilder { alt_mempool }
+    }
 
-impl EthereumPayloadBuilder {
     /// A helper method initializing [`PayloadBuilderService`] with the given EVM config.
     pub fn spawn<Types, Node, Evm, Pool>(
         self,
@@ -255,9 +278,27 @@ impl EthereumPayloadBuilder {
             PayloadBuilderAttributes = EthPayloadBuilderAttributes,
         >,
     {
-        let payload_builder =
-            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config);
+        // the mempool tracks the tip from the next canonical notification on
+        self.alt_mempool.on_canonical_tip(ctx.head().number);
+        ctx.task_executor().spawn(Box::pin(maintain_alt_mempool(
+            self.alt_mempool.clone(),
+            ctx.provider().canonical_state_stream(),
+        )));
+
         let conf = ctx.payload_builder_config();
+        let payload_builder =
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config)
+                .with_alt_mempool(self.alt_mempool);
+        let candidates = if conf.parallel() {
+            vec![
+                payload_builder.clone(),
+                payload_builder.clone().without_alt_mempool(),
+                payload_builder.without_blobs(),
+            ]
+        } else {
+            vec![payload_builder]
+        };
+        let payload_builder = ParallelPayloadBuilder::new(candidates);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
@@ -281,8 +322,9 @@ impl EthereumPayloadBuilder {
     }
 }
 
-impl<Types, Node, Pool> PayloadServiceBuilder<Node, Pool> for EthereumPayloadBuilder
+impl<Types, Node, Pool, Alt> PayloadServiceBuilder<Node, Pool> for EthereumPayloadBuilder<Alt>
 where
+    Alt: AltMempool,
     Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
     Node: FullNodeTypes<Types = Types>,
     Pool: TransactionPool + Unpin + 'static,
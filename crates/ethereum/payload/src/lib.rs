@@ -9,7 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
-use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_consensus::{Header, Transaction, EMPTY_OMMER_ROOT_HASH};
 use alloy_eips::{eip4844::MAX_DATA_GAS_PER_BLOCK, eip7685::Requests, merge::BEACON_NONCE};
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
@@ -25,15 +25,16 @@ use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_util::{PayloadTransactions, PayloadTransactionsFixed};
 use reth_primitives::{
     proofs::{self},
-    Block, BlockBody, EthereumHardforks, Receipt,
+    Block, BlockBody, EthereumHardforks, Receipt, TransactionSignedEcRecovered,
 };
 use reth_provider::{ChainSpecProvider, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
 use reth_transaction_pool::{
-    noop::NoopTransactionPool, BestTransactions, BestTransactionsAttributes, TransactionPool,
-    ValidPoolTransaction,
+    alt::AltMempool, noop::NoopTransactionPool, BestTransactions, BestTransactionsAttributes,
+    PoolTransaction, TransactionPool, ValidPoolTransaction,
 };
 use reth_trie::HashedPostState;
 use revm::{
@@ -53,19 +54,55 @@ type BestTransactionsIter<Pool> = Box<
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig, Alt = ()> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// The alternative mempool whose transactions are included ahead of the pool transactions.
+    alt_mempool: Alt,
+    /// Whether the transactions of the alternative mempool are left out.
+    skip_alt_mempool: bool,
+    /// Whether blob transactions are left out.
+    skip_blobs: bool,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, alt_mempool: (), skip_alt_mempool: false, skip_blobs: false }
     }
 }
 
-impl<EvmConfig> EthereumPayloadBuilder<EvmConfig>
+impl<EvmConfig, Alt> EthereumPayloadBuilder<EvmConfig, Alt> {
+    /// Configures the [`AltMempool`] whose transactions are included in payloads ahead of the
+    /// transactions of the pool.
+    pub fn with_alt_mempool<A: AltMempool>(
+        self,
+        alt_mempool: A,
+    ) -> EthereumPayloadBuilder<EvmConfig, A> {
+        EthereumPayloadBuilder {
+            evm_config: self.evm_config,
+            alt_mempool,
+            skip_alt_mempool: self.skip_alt_mempool,
+            skip_blobs: self.skip_blobs,
+        }
+    }
+
+    /// Leaves out the transactions of the [`AltMempool`], e.g. for a candidate payload of a
+    /// [`ParallelPayloadBuilder`](reth_basic_payload_builder::ParallelPayloadBuilder).
+    pub const fn without_alt_mempool(mut self) -> Self {
+        self.skip_alt_mempool = true;
+        self
+    }
+
+    /// Leaves out blob transactions, e.g. for a candidate payload of a
+    /// [`ParallelPayloadBuilder`](reth_basic_payload_builder::ParallelPayloadBuilder).
+    pub const fn without_blobs(mut self) -> Self {
+        self.skip_blobs = true;
+        self
+    }
+}
+
+impl<EvmConfig, Alt> EthereumPayloadBuilder<EvmConfig, Alt>
 where
     EvmConfig: ConfigureEvm<Header = Header>,
 {
@@ -86,9 +123,11 @@ where
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<EvmConfig, Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder<EvmConfig>
+impl<EvmConfig, Alt, Pool, Client> PayloadBuilder<Pool, Client>
+    for EthereumPayloadBuilder<EvmConfig, Alt>
 where
     EvmConfig: ConfigureEvm<Header = Header>,
+    Alt: AltMempool,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec>,
     Pool: TransactionPool,
 {
@@ -104,9 +143,26 @@ where
             .map_err(PayloadBuilderError::other)?;
 
         let pool = args.pool.clone();
-        default_ethereum_payload(self.evm_config.clone(), args, cfg_env, block_env, |attributes| {
-            pool.best_transactions_with_attributes(attributes)
-        })
+        let best_txs = |attributes| {
+            let mut best_txs = pool.best_transactions_with_attributes(attributes);
+            if self.skip_blobs {
+                best_txs.skip_blobs();
+            }
+            best_txs
+        };
+
+        if self.skip_alt_mempool {
+            default_ethereum_payload(self.evm_config.clone(), args, cfg_env, block_env, best_txs)
+        } else {
+            default_ethereum_payload_with_alt_txs(
+                self.evm_config.clone(),
+                args,
+                cfg_env,
+                block_env,
+                best_txs,
+                |attributes| self.alt_mempool.best_transactions(attributes),
+            )
+        }
     }
 
     fn build_empty_payload(
@@ -156,6 +212,38 @@ where
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec>,
     Pool: TransactionPool,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+{
+    default_ethereum_payload_with_alt_txs(
+        evm_config,
+        args,
+        initialized_cfg,
+        initialized_block_env,
+        best_txs,
+        |_| PayloadTransactionsFixed::<TransactionSignedEcRecovered>::new(Vec::new()),
+    )
+}
+
+/// Constructs an Ethereum transaction payload using the transactions returned by `alt_txs`, e.g.
+/// from an [`AltMempool`], and the best transactions from the pool.
+///
+/// The transactions of `alt_txs` are included ahead of the transactions of the pool, see
+/// [`AltMempool::best_transactions`].
+#[inline]
+pub fn default_ethereum_payload_with_alt_txs<EvmConfig, Pool, Client, F, A, Txs>(
+    evm_config: EvmConfig,
+    args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    initialized_cfg: CfgEnvWithHandlerCfg,
+    initialized_block_env: BlockEnv,
+    best_txs: F,
+    alt_txs: A,
+) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec>,
+    Pool: TransactionPool,
+    F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+    A: FnOnce(BestTransactionsAttributes) -> Txs,
+    Txs: PayloadTransactions,
 {
     let BuildArguments { client, pool, mut cached_reads, config, cancel, best_payload } = args;
 
@@ -175,10 +263,12 @@ where
     let mut executed_txs = Vec::new();
     let mut executed_senders = Vec::new();
 
-    let mut best_txs = best_txs(BestTransactionsAttributes::new(
+    let best_attributes = BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut alt_txs = alt_txs(best_attributes);
+    let mut best_txs = best_txs(best_attributes);
     let mut total_fees = U256::ZERO;
 
     let block_number = initialized_block_env.number.to::<u64>();
@@ -222,13 +312,23 @@ where
     let mut evm = evm_config.evm_with_env(&mut db, env);
 
     let mut receipts = Vec::new();
-    while let Some(pool_tx) = best_txs.next() {
+    loop {
+        // transactions of the alternative mempool are included first
+        let (tx, pool_tx) = if let Some(tx) = alt_txs.next(()) {
+            (tx, None)
+        } else if let Some(pool_tx) = best_txs.next() {
+            // convert tx to a signed transaction
+            (pool_tx.to_recovered_transaction(), Some(pool_tx))
+        } else {
+            break
+        };
+
         // ensure we still have capacity for this transaction
-        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+        if cumulative_gas_used + tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
-            best_txs.mark_invalid(&pool_tx);
+            mark_invalid(&mut best_txs, &mut alt_txs, &tx, pool_tx.as_ref());
             continue
         }
 
@@ -237,8 +337,12 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
-        // convert tx to a signed transaction
-        let tx = pool_tx.to_recovered_transaction();
+        // the sidecars of blob transactions are only available for pool transactions
+        if pool_tx.is_none() && tx.is_eip4844() {
+            trace!(target: "payload_builder", tx=?tx.hash, "skipping blob transaction of the alternative mempool");
+            alt_txs.mark_invalid(tx.signer(), tx.nonce());
+            continue
+        }
 
         // There's only limited amount of blob space available per block, so we need to check if
         // the EIP-4844 can still fit in the block
@@ -250,7 +354,7 @@ where
                 // the iterator. This is similar to the gas limit condition
                 // for regular transactions above.
                 trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
-                best_txs.mark_invalid(&pool_tx);
+                mark_invalid(&mut best_txs, &mut alt_txs, &tx, pool_tx.as_ref());
                 continue
             }
         }
@@ -270,7 +374,7 @@ where
                             // if the transaction is invalid, we can skip it and all of its
                             // descendants
                             trace!(target: "payload_builder", %err, ?tx, "skipping invalid transaction and its descendants");
-                            best_txs.mark_invalid(&pool_tx);
+                            mark_invalid(&mut best_txs, &mut alt_txs, &tx, pool_tx.as_ref());
                         }
 
                         continue
@@ -313,9 +417,8 @@ where
         }));
 
         // update add to total fees
-        let miner_fee = tx
-            .effective_tip_per_gas(Some(base_fee))
-            .expect("fee is always valid; execution succeeded");
+        let miner_fee =
+            tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
         total_fees += U256::from(miner_fee) * U256::from(gas_used);
 
         // append sender and transaction to the respective lists
@@ -466,3 +569,17 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// Marks a transaction as invalid in the iterator it was pulled from, which also excludes its
+/// descendants.
+fn mark_invalid<T: PoolTransaction>(
+    best_txs: &mut Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+    alt_txs: &mut impl PayloadTransactions,
+    tx: &TransactionSignedEcRecovered,
+    pool_tx: Option<&Arc<ValidPoolTransaction<T>>>,
+) {
+    match pool_tx {
+        Some(pool_tx) => best_txs.mark_invalid(pool_tx),
+        None => alt_txs.mark_invalid(tx.signer(), tx.nonce()),
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::ExecutionOutcome;
 use alloc::{borrow::Cow, collections::BTreeMap};
+use alloy_consensus::BlockHeader;
 use alloy_eips::{eip1898::ForkBlock, BlockNumHash};
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash};
 use core::{fmt, ops::RangeInclusive};
@@ -10,6 +11,7 @@ use reth_primitives::{
     Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionSigned,
     TransactionSignedEcRecovered,
 };
+use reth_primitives_traits::Block;
 use reth_trie::updates::TrieUpdates;
 use revm::db::BundleState;
 
@@ -25,9 +27,13 @@ use revm::db::BundleState;
 /// A chain of blocks should not be empty.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Chain {
+pub struct Chain<B: Block = reth_primitives::Block> {
     /// All blocks in this chain.
-    blocks: BTreeMap<BlockNumber, SealedBlockWithSenders>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound = "SealedBlockWithSenders<B>: serde::Serialize + serde::de::DeserializeOwned")
+    )]
+    blocks: BTreeMap<BlockNumber, SealedBlockWithSenders<B>>,
     /// The outcome of block execution for this chain.
     ///
     /// This field contains the state of all accounts after the execution of all blocks in this
@@ -41,18 +47,18 @@ pub struct Chain {
     trie_updates: Option<TrieUpdates>,
 }
 
-impl Chain {
+impl<B: Block> Chain<B> {
     /// Create new Chain from blocks and state.
     ///
     /// # Warning
     ///
     /// A chain of blocks should not be empty.
     pub fn new(
-        blocks: impl IntoIterator<Item = SealedBlockWithSenders>,
+        blocks: impl IntoIterator<Item = SealedBlockWithSenders<B>>,
         execution_outcome: ExecutionOutcome,
         trie_updates: Option<TrieUpdates>,
     ) -> Self {
-        let blocks = blocks.into_iter().map(|b| (b.number, b)).collect::<BTreeMap<_, _>>();
+        let blocks = blocks.into_iter().map(|b| (b.number(), b)).collect::<BTreeMap<_, _>>();
         debug_assert!(!blocks.is_empty(), "Chain should have at least one block");
 
         Self { blocks, execution_outcome, trie_updates }
@@ -60,7 +66,7 @@ impl Chain {
 
     /// Create new Chain from a single block and its state.
     pub fn from_block(
-        block: SealedBlockWithSenders,
+        block: SealedBlockWithSenders<B>,
         execution_outcome: ExecutionOutcome,
         trie_updates: Option<TrieUpdates>,
     ) -> Self {
@@ -68,17 +74,17 @@ impl Chain {
     }
 
     /// Get the blocks in this chain.
-    pub const fn blocks(&self) -> &BTreeMap<BlockNumber, SealedBlockWithSenders> {
+    pub const fn blocks(&self) -> &BTreeMap<BlockNumber, SealedBlockWithSenders<B>> {
         &self.blocks
     }
 
     /// Consumes the type and only returns the blocks in this chain.
-    pub fn into_blocks(self) -> BTreeMap<BlockNumber, SealedBlockWithSenders> {
+    pub fn into_blocks(self) -> BTreeMap<BlockNumber, SealedBlockWithSenders<B>> {
         self.blocks
     }
 
     /// Returns an iterator over all headers in the block with increasing block numbers.
-    pub fn headers(&self) -> impl Iterator<Item = SealedHeader> + '_ {
+    pub fn headers(&self) -> impl Iterator<Item = SealedHeader<B::Header>> + '_ {
         self.blocks.values().map(|block| block.header.clone())
     }
 
@@ -119,12 +125,12 @@ impl Chain {
     }
 
     /// Returns the block with matching hash.
-    pub fn block(&self, block_hash: BlockHash) -> Option<&SealedBlock> {
+    pub fn block(&self, block_hash: BlockHash) -> Option<&SealedBlock<B::Header, B::Body>> {
         self.block_with_senders(block_hash).map(|block| &block.block)
     }
 
     /// Returns the block with matching hash.
-    pub fn block_with_senders(&self, block_hash: BlockHash) -> Option<&SealedBlockWithSenders> {
+    pub fn block_with_senders(&self, block_hash: BlockHash) -> Option<&SealedBlockWithSenders<B>> {
         self.blocks.iter().find_map(|(_num, block)| (block.hash() == block_hash).then_some(block))
     }
 
@@ -133,7 +139,7 @@ impl Chain {
         &self,
         block_number: BlockNumber,
     ) -> Option<ExecutionOutcome> {
-        if self.tip().number == block_number {
+        if self.tip().number() == block_number {
             return Some(self.execution_outcome.clone())
         }
 
@@ -149,14 +155,14 @@ impl Chain {
     /// 1. The blocks contained in the chain.
     /// 2. The execution outcome representing the final state.
     /// 3. The optional trie updates.
-    pub fn into_inner(self) -> (ChainBlocks<'static>, ExecutionOutcome, Option<TrieUpdates>) {
+    pub fn into_inner(self) -> (ChainBlocks<'static, B>, ExecutionOutcome, Option<TrieUpdates>) {
         (ChainBlocks { blocks: Cow::Owned(self.blocks) }, self.execution_outcome, self.trie_updates)
     }
 
     /// Destructure the chain into its inner components:
     /// 1. A reference to the blocks contained in the chain.
     /// 2. A reference to the execution outcome representing the final state.
-    pub const fn inner(&self) -> (ChainBlocks<'_>, &ExecutionOutcome) {
+    pub const fn inner(&self) -> (ChainBlocks<'_, B>, &ExecutionOutcome) {
         (ChainBlocks { blocks: Cow::Borrowed(&self.blocks) }, &self.execution_outcome)
     }
 
@@ -166,14 +172,14 @@ impl Chain {
     }
 
     /// Returns an iterator over all blocks in the chain with increasing block number.
-    pub fn blocks_iter(&self) -> impl Iterator<Item = &SealedBlockWithSenders> + '_ {
+    pub fn blocks_iter(&self) -> impl Iterator<Item = &SealedBlockWithSenders<B>> + '_ {
         self.blocks().iter().map(|block| block.1)
     }
 
     /// Returns an iterator over all blocks and their receipts in the chain.
     pub fn blocks_and_receipts(
         &self,
-    ) -> impl Iterator<Item = (&SealedBlockWithSenders, &Vec<Option<Receipt>>)> + '_ {
+    ) -> impl Iterator<Item = (&SealedBlockWithSenders<B>, &Vec<Option<Receipt>>)> + '_ {
         self.blocks_iter().zip(self.block_receipts_iter())
     }
 
@@ -181,7 +187,7 @@ impl Chain {
     #[track_caller]
     pub fn fork_block(&self) -> ForkBlock {
         let first = self.first();
-        ForkBlock { number: first.number.saturating_sub(1), hash: first.parent_hash }
+        ForkBlock { number: first.number().saturating_sub(1), hash: first.parent_hash() }
     }
 
     /// Get the first block in this chain.
@@ -190,7 +196,7 @@ impl Chain {
     ///
     /// If chain doesn't have any blocks.
     #[track_caller]
-    pub fn first(&self) -> &SealedBlockWithSenders {
+    pub fn first(&self) -> &SealedBlockWithSenders<B> {
         self.blocks.first_key_value().expect("Chain should have at least one block").1
     }
 
@@ -200,7 +206,7 @@ impl Chain {
     ///
     /// If chain doesn't have any blocks.
     #[track_caller]
-    pub fn tip(&self) -> &SealedBlockWithSenders {
+    pub fn tip(&self) -> &SealedBlockWithSenders<B> {
         self.blocks.last_key_value().expect("Chain should have at least one block").1
     }
 
@@ -215,7 +221,7 @@ impl Chain {
     ///
     /// If chain doesn't have any blocks.
     pub fn range(&self) -> RangeInclusive<BlockNumber> {
-        self.first().number..=self.tip().number
+        self.first().number()..=self.tip().number()
     }
 
     /// Get all receipts for the given block.
@@ -224,35 +230,14 @@ impl Chain {
         self.execution_outcome.receipts_by_block(num).iter().map(Option::as_ref).collect()
     }
 
-    /// Get all receipts with attachment.
-    ///
-    /// Attachment includes block number, block hash, transaction hash and transaction index.
-    pub fn receipts_with_attachment(&self) -> Vec<BlockReceipts> {
-        let mut receipt_attach = Vec::with_capacity(self.blocks().len());
-        for ((block_num, block), receipts) in
-            self.blocks().iter().zip(self.execution_outcome.receipts().iter())
-        {
-            let mut tx_receipts = Vec::with_capacity(receipts.len());
-            for (tx, receipt) in block.body.transactions().zip(receipts.iter()) {
-                tx_receipts.push((
-                    tx.hash(),
-                    receipt.as_ref().expect("receipts have not been pruned").clone(),
-                ));
-            }
-            let block_num_hash = BlockNumHash::new(*block_num, block.hash());
-            receipt_attach.push(BlockReceipts { block: block_num_hash, tx_receipts });
-        }
-        receipt_attach
-    }
-
     /// Append a single block with state to the chain.
     /// This method assumes that blocks attachment to the chain has already been validated.
     pub fn append_block(
         &mut self,
-        block: SealedBlockWithSenders,
+        block: SealedBlockWithSenders<B>,
         execution_outcome: ExecutionOutcome,
     ) {
-        self.blocks.insert(block.number, block);
+        self.blocks.insert(block.number(), block);
         self.execution_outcome.extend(execution_outcome);
         self.trie_updates.take(); // reset
     }
@@ -300,7 +285,7 @@ impl Chain {
     ///
     /// If chain doesn't have any blocks.
     #[track_caller]
-    pub fn split(mut self, split_at: ChainSplitTarget) -> ChainSplit {
+    pub fn split(mut self, split_at: ChainSplitTarget) -> ChainSplit<B> {
         let chain_tip = *self.blocks.last_entry().expect("chain is never empty").key();
         let block_number = match split_at {
             ChainSplitTarget::Hash(block_hash) => {
@@ -351,11 +336,36 @@ impl Chain {
     }
 }
 
+impl Chain {
+    /// Get all receipts with attachment.
+    ///
+    /// Attachment includes block number, block hash, transaction hash and transaction index.
+    pub fn receipts_with_attachment(&self) -> Vec<BlockReceipts> {
+        let mut receipt_attach = Vec::with_capacity(self.blocks().len());
+        for ((block_num, block), receipts) in
+            self.blocks().iter().zip(self.execution_outcome.receipts().iter())
+        {
+            let mut tx_receipts = Vec::with_capacity(receipts.len());
+            for (tx, receipt) in block.body.transactions().zip(receipts.iter()) {
+                tx_receipts.push((
+                    tx.hash(),
+                    receipt.as_ref().expect("receipts have not been pruned").clone(),
+                ));
+            }
+            let block_num_hash = BlockNumHash::new(*block_num, block.hash());
+            receipt_attach.push(BlockReceipts { block: block_num_hash, tx_receipts });
+        }
+        receipt_attach
+    }
+}
+
 /// Wrapper type for `blocks` display in `Chain`
 #[derive(Debug)]
-pub struct DisplayBlocksChain<'a>(pub &'a BTreeMap<BlockNumber, SealedBlockWithSenders>);
+pub struct DisplayBlocksChain<'a, B: Block = reth_primitives::Block>(
+    pub &'a BTreeMap<BlockNumber, SealedBlockWithSenders<B>>,
+);
 
-impl fmt::Display for DisplayBlocksChain<'_> {
+impl<B: Block> fmt::Display for DisplayBlocksChain<'_, B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut list = f.debug_list();
         let mut values = self.0.values().map(|block| block.num_hash());
@@ -372,22 +382,22 @@ impl fmt::Display for DisplayBlocksChain<'_> {
 
 /// All blocks in the chain
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct ChainBlocks<'a> {
-    blocks: Cow<'a, BTreeMap<BlockNumber, SealedBlockWithSenders>>,
+pub struct ChainBlocks<'a, B: Block = reth_primitives::Block> {
+    blocks: Cow<'a, BTreeMap<BlockNumber, SealedBlockWithSenders<B>>>,
 }
 
-impl ChainBlocks<'_> {
+impl<B: Block> ChainBlocks<'_, B> {
     /// Creates a consuming iterator over all blocks in the chain with increasing block number.
     ///
     /// Note: this always yields at least one block.
     #[inline]
-    pub fn into_blocks(self) -> impl Iterator<Item = SealedBlockWithSenders> {
+    pub fn into_blocks(self) -> impl Iterator<Item = SealedBlockWithSenders<B>> {
         self.blocks.into_owned().into_values()
     }
 
     /// Creates an iterator over all blocks in the chain with increasing block number.
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = (&BlockNumber, &SealedBlockWithSenders)> {
+    pub fn iter(&self) -> impl Iterator<Item = (&BlockNumber, &SealedBlockWithSenders<B>)> {
         self.blocks.iter()
     }
 
@@ -397,7 +407,7 @@ impl ChainBlocks<'_> {
     ///
     /// Chains always have at least one block.
     #[inline]
-    pub fn tip(&self) -> &SealedBlockWithSenders {
+    pub fn tip(&self) -> &SealedBlockWithSenders<B> {
         self.blocks.last_key_value().expect("Chain should have at least one block").1
     }
 
@@ -407,10 +417,12 @@ impl ChainBlocks<'_> {
     ///
     /// Chains always have at least one block.
     #[inline]
-    pub fn first(&self) -> &SealedBlockWithSenders {
+    pub fn first(&self) -> &SealedBlockWithSenders<B> {
         self.blocks.first_key_value().expect("Chain should have at least one block").1
     }
+}
 
+impl ChainBlocks<'_> {
     /// Returns an iterator over all transactions in the chain.
     #[inline]
     pub fn transactions(&self) -> impl Iterator<Item = &TransactionSigned> + '_ {
@@ -442,9 +454,9 @@ impl ChainBlocks<'_> {
     }
 }
 
-impl IntoIterator for ChainBlocks<'_> {
-    type Item = (BlockNumber, SealedBlockWithSenders);
-    type IntoIter = std::collections::btree_map::IntoIter<BlockNumber, SealedBlockWithSenders>;
+impl<B: Block> IntoIterator for ChainBlocks<'_, B> {
+    type Item = (BlockNumber, SealedBlockWithSenders<B>);
+    type IntoIter = std::collections::btree_map::IntoIter<BlockNumber, SealedBlockWithSenders<B>>;
 
     fn into_iter(self) -> Self::IntoIter {
         #[allow(clippy::unnecessary_to_owned)]
@@ -484,26 +496,26 @@ impl From<BlockHash> for ChainSplitTarget {
 
 /// Result of a split chain.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum ChainSplit {
+pub enum ChainSplit<B: Block = reth_primitives::Block> {
     /// Chain is not split. Pending chain is returned.
     /// Given block split is higher than last block.
     /// Or in case of split by hash when hash is unknown.
-    NoSplitPending(Chain),
+    NoSplitPending(Chain<B>),
     /// Chain is not split. Canonical chain is returned.
     /// Given block split is lower than first block.
-    NoSplitCanonical(Chain),
+    NoSplitCanonical(Chain<B>),
     /// Chain is split into two: `[canonical]` and `[pending]`
     /// The target of this chain split [`ChainSplitTarget`] belongs to the `canonical` chain.
     Split {
         /// Contains lower block numbers that are considered canonicalized. It ends with
         /// the [`ChainSplitTarget`] block. The state of this chain is now empty and no longer
         /// usable.
-        canonical: Chain,
+        canonical: Chain<B>,
         /// Right contains all subsequent blocks __after__ the [`ChainSplitTarget`] that are still
         /// pending.
         ///
         /// The state of the original chain is moved here.
-        pending: Chain,
+        pending: Chain<B>,
     },
 }
 
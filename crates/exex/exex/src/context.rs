@@ -4,6 +4,7 @@ use reth_node_api::{FullNodeComponents, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_primitives::Head;
 use reth_tasks::TaskExecutor;
+use reth_transaction_pool::{AllTransactionsEvents, TransactionPool};
 use std::fmt::Debug;
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -74,6 +75,14 @@ where
         self.components.pool()
     }
 
+    /// Returns a stream of the lifecycle events of all transactions in the pool, e.g. the reason
+    /// transactions were discarded.
+    pub fn pool_events(
+        &self,
+    ) -> AllTransactionsEvents<<Node::Pool as TransactionPool>::Transaction> {
+        self.pool().all_transactions_event_listener()
+    }
+
     /// Returns the node's evm config.
     pub fn evm_config(&self) -> &Node::Evm {
         self.components.evm_config()
@@ -10,9 +10,9 @@ use reth_chainspec::Head;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::SealedHeader;
 use reth_provider::HeaderProvider;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     fmt::Debug,
     future::{poll_fn, Future},
     ops::Not,
@@ -211,6 +211,15 @@ pub struct ExExManager<P> {
 
     /// [`ExExNotification`] channel from the [`ExExManagerHandle`]s.
     handle_rx: UnboundedReceiver<(ExExNotificationSource, ExExNotification)>,
+    /// Channel of [`ExExHandle`]s of restarted `ExEx`'s from the [`ExExManagerHandle`]s.
+    exex_handles_rx: UnboundedReceiver<ExExHandle>,
+    /// Channel of IDs of `ExEx`'s that won't be restarted from the [`ExExManagerHandle`]s.
+    removed_exexs_rx: UnboundedReceiver<String>,
+    /// Finished heights of the `ExEx`'s that stopped receiving notifications, by their ID.
+    ///
+    /// They hold back the WAL finalization until the `ExEx` is attached again or removed, so that
+    /// the restarted `ExEx` can resume from its finished height.
+    detached_exexs: HashMap<String, Option<BlockNumHash>>,
 
     /// The minimum notification ID currently present in the buffer.
     min_id: usize,
@@ -233,6 +242,8 @@ pub struct ExExManager<P> {
 
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
+    /// The last finished height of each `ExEx`, by its ID.
+    exex_finished_heights: watch::Sender<HashMap<String, BlockNumHash>>,
 
     /// Write-Ahead Log for the [`ExExNotification`]s.
     wal: Wal,
@@ -263,12 +274,15 @@ impl<P> ExExManager<P> {
         let num_exexs = handles.len();
 
         let (handle_tx, handle_rx) = mpsc::unbounded_channel();
+        let (exex_handles_tx, exex_handles_rx) = mpsc::unbounded_channel();
+        let (removed_exexs_tx, removed_exexs_rx) = mpsc::unbounded_channel();
         let (is_ready_tx, is_ready_rx) = watch::channel(true);
         let (finished_height_tx, finished_height_rx) = watch::channel(if num_exexs == 0 {
             FinishedExExHeight::NoExExs
         } else {
             FinishedExExHeight::NotReady
         });
+        let (exex_finished_heights_tx, exex_finished_heights_rx) = watch::channel(HashMap::new());
 
         let current_capacity = Arc::new(AtomicUsize::new(max_capacity));
 
@@ -282,6 +296,9 @@ impl<P> ExExManager<P> {
             exex_handles: handles,
 
             handle_rx,
+            exex_handles_rx,
+            removed_exexs_rx,
+            detached_exexs: HashMap::new(),
 
             min_id: 0,
             next_id: 0,
@@ -291,17 +308,21 @@ impl<P> ExExManager<P> {
 
             is_ready: is_ready_tx,
             finished_height: finished_height_tx,
+            exex_finished_heights: exex_finished_heights_tx,
 
             wal,
             finalized_header_stream,
 
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
+                exex_handles_tx,
+                removed_exexs_tx,
                 num_exexs,
                 is_ready_receiver: is_ready_rx.clone(),
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
                 finished_height: finished_height_rx,
+                exex_finished_heights: exex_finished_heights_rx,
             },
             metrics,
         }
@@ -340,8 +361,8 @@ where
 {
     /// Finalizes the WAL according to the passed finalized header.
     ///
-    /// This function checks if all ExExes are on the canonical chain and finalizes the WAL if
-    /// necessary.
+    /// This function checks if all ExExes, including the detached ones, are on the canonical chain
+    /// and finalizes the WAL if necessary.
     fn finalize_wal(&self, finalized_header: SealedHeader) -> eyre::Result<()> {
         debug!(target: "exex::manager", header = ?finalized_header.num_hash(), "Received finalized header");
 
@@ -351,6 +372,7 @@ where
             .iter()
             // Get ID and finished height for each ExEx
             .map(|exex_handle| (&exex_handle.id, exex_handle.finished_height))
+            .chain(self.detached_exexs.iter().map(|(exex_id, num_hash)| (exex_id, *num_hash)))
             // Deduplicate all hashes
             .unique_by(|(_, num_hash)| num_hash.map(|num_hash| num_hash.hash))
             // Check if hashes are canonical
@@ -420,13 +442,45 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        // Attach restarted ExExes. They only receive new notifications, and catch up on the
+        // earlier ones from their last finished height, which they inherit from the handle they
+        // replace, so that the WAL isn't finalized past it in the meantime.
+        while let Poll::Ready(Some(mut exex)) = this.exex_handles_rx.poll_recv(cx) {
+            debug!(target: "exex::manager", exex_id = %exex.id, "Attaching ExEx");
+            exex.next_notification_id = this.next_id;
+            let previous = this.exex_handles.iter().position(|handle| handle.id == exex.id);
+            exex.finished_height = match previous {
+                Some(idx) => this.exex_handles.swap_remove(idx).finished_height,
+                None => this.detached_exexs.remove(&exex.id).flatten(),
+            };
+            this.exex_handles.push(exex);
+            this.metrics.num_exexs.set(this.exex_handles.len() as f64);
+        }
+
+        // Forget ExExes that won't be restarted, so that they don't hold back the WAL
+        // finalization anymore
+        while let Poll::Ready(Some(exex_id)) = this.removed_exexs_rx.poll_recv(cx) {
+            debug!(target: "exex::manager", %exex_id, "Removing ExEx");
+            this.exex_handles.retain(|handle| handle.id != exex_id);
+            this.detached_exexs.remove(&exex_id);
+            this.exex_finished_heights.send_modify(|heights| {
+                heights.remove(&exex_id);
+            });
+            this.metrics.num_exexs.set(this.exex_handles.len() as f64);
+        }
+
         // Handle incoming ExEx events
         for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(target: "exex::manager", exex_id = %exex.id, ?event, "Received event from ExEx");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.finished_height = Some(height);
+                        this.exex_finished_heights.send_modify(|heights| {
+                            heights.insert(exex.id.clone(), height);
+                        });
+                    }
                 }
             }
         }
@@ -469,7 +523,7 @@ where
         this.update_capacity();
 
         // Advance all poll senders
-        let mut min_id = usize::MAX;
+        let mut min_id = this.next_id;
         for idx in (0..this.exex_handles.len()).rev() {
             let mut exex = this.exex_handles.swap_remove(idx);
 
@@ -480,9 +534,14 @@ where
                 .checked_sub(this.min_id)
                 .expect("exex expected notification ID outside the manager's range");
             if let Some(notification) = this.buffer.get(notification_index) {
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
-                    // The channel was closed, which is irrecoverable for the manager
-                    return Poll::Ready(Err(err.into()))
+                if let Poll::Ready(Err(_)) = exex.send(cx, notification) {
+                    // The channel was closed, i.e. the ExEx stopped or crashed. Detach it, so that
+                    // it doesn't hold back the other ExExes. Its finished height is kept until it's
+                    // restarted or removed.
+                    warn!(target: "exex::manager", exex_id = %exex.id, "ExEx stopped receiving notifications, detaching it");
+                    this.detached_exexs.insert(exex.id, exex.finished_height);
+                    this.metrics.num_exexs.set(this.exex_handles.len() as f64);
+                    continue
                 }
             }
             min_id = min_id.min(exex.next_notification_id);
@@ -501,7 +560,9 @@ where
         let finished_height = this.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
             exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
         });
-        if let Ok(finished_height) = finished_height {
+        if let Some(finished_height) =
+            finished_height.ok().filter(|_| !this.exex_handles.is_empty())
+        {
             let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
         }
 
@@ -514,6 +575,10 @@ where
 pub struct ExExManagerHandle {
     /// Channel to send notifications to the `ExEx` manager.
     exex_tx: UnboundedSender<(ExExNotificationSource, ExExNotification)>,
+    /// Channel to send handles of restarted `ExEx`'s to the `ExEx` manager.
+    exex_handles_tx: UnboundedSender<ExExHandle>,
+    /// Channel to send IDs of `ExEx`'s that won't be restarted to the `ExEx` manager.
+    removed_exexs_tx: UnboundedSender<String>,
     /// The number of `ExEx`'s running on the node.
     num_exexs: usize,
     /// A watch channel denoting whether the manager is ready for new notifications or not.
@@ -529,6 +594,8 @@ pub struct ExExManagerHandle {
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Receiver<FinishedExExHeight>,
+    /// The last finished height of each `ExEx`, by its ID.
+    exex_finished_heights: watch::Receiver<HashMap<String, BlockNumHash>>,
 }
 
 impl ExExManagerHandle {
@@ -539,19 +606,50 @@ impl ExExManagerHandle {
     /// The handle will always be ready, and have a capacity of 0.
     pub fn empty() -> Self {
         let (exex_tx, _) = mpsc::unbounded_channel();
+        let (exex_handles_tx, _) = mpsc::unbounded_channel();
+        let (removed_exexs_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
+        let (_, exex_finished_heights_rx) = watch::channel(HashMap::new());
 
         Self {
             exex_tx,
+            exex_handles_tx,
+            removed_exexs_tx,
             num_exexs: 0,
             is_ready_receiver: is_ready_rx.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
             finished_height: finished_height_rx,
+            exex_finished_heights: exex_finished_heights_rx,
         }
     }
 
+    /// Attaches the handle of a restarted `ExEx` to the manager, replacing the handle with the
+    /// same ID.
+    ///
+    /// The `ExEx` only receives notifications that are sent after it was attached. Earlier
+    /// notifications can be replayed by the `ExEx` from the WAL by resuming from its
+    /// [`Self::exex_finished_height`], see
+    /// [`ExExNotificationsStream::set_with_head`](crate::ExExNotificationsStream::set_with_head).
+    pub fn attach_exex(&self, handle: ExExHandle) -> Result<(), SendError<ExExHandle>> {
+        self.exex_handles_tx.send(handle)
+    }
+
+    /// Removes the `ExEx` with the given ID from the manager, if it won't be restarted.
+    ///
+    /// Until then, the WAL isn't finalized past the finished height of a stopped `ExEx`.
+    pub fn remove_exex(&self, id: String) -> Result<(), SendError<String>> {
+        self.removed_exexs_tx.send(id)
+    }
+
+    /// Returns the last finished height that the `ExEx` with the given ID has reported, if any.
+    ///
+    /// The height is kept after the `ExEx` stopped, until it's removed.
+    pub fn exex_finished_height(&self, id: &str) -> Option<BlockNumHash> {
+        self.exex_finished_heights.borrow().get(id).copied()
+    }
+
     /// Synchronously send a notification over the channel to all execution extensions.
     ///
     /// Senders should call [`Self::has_capacity`] first.
@@ -624,11 +722,14 @@ impl Clone for ExExManagerHandle {
     fn clone(&self) -> Self {
         Self {
             exex_tx: self.exex_tx.clone(),
+            exex_handles_tx: self.exex_handles_tx.clone(),
+            removed_exexs_tx: self.removed_exexs_tx.clone(),
             num_exexs: self.num_exexs,
             is_ready_receiver: self.is_ready_receiver.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
             finished_height: self.finished_height.clone(),
+            exex_finished_heights: self.exex_finished_heights.clone(),
         }
     }
 }
@@ -961,7 +1062,7 @@ mod tests {
 
         let provider_factory = create_test_provider_factory();
 
-        let (exex_handle_1, _, _) =
+        let (exex_handle_1, _, _notifications) =
             ExExHandle::new("test_exex_1".to_string(), Head::default(), (), (), wal.handle());
 
         // Create an ExExManager with a small max capacity
@@ -1011,9 +1112,162 @@ mod tests {
 
         let _ = pinned_manager.as_mut().poll(&mut cx);
 
-        // After polling, the next notification ID and buffer size should be updated
+        // After polling, the next notification ID and buffer size should be updated. Only two
+        // notifications fit into the buffer, and the first one was already delivered to the ExEx.
         assert_eq!(pinned_manager.next_id, 2);
-        assert_eq!(pinned_manager.buffer.len(), 2);
+        assert_eq!(pinned_manager.buffer.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_detach_and_attach_exex() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider2::new(provider_factory.clone()).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let new_exex_handle = |id: &str| {
+            ExExHandle::new(
+                id.to_string(),
+                Head::default(),
+                provider.clone(),
+                EthExecutorProvider::mainnet(),
+                wal.handle(),
+            )
+        };
+        let (exex_handle_1, _, notifications_1) = new_exex_handle("test_exex_1");
+        let (exex_handle_2, _, mut notifications_2) = new_exex_handle("test_exex_2");
+
+        let exex_manager = ExExManager::new(
+            provider_factory,
+            vec![exex_handle_1, exex_handle_2],
+            10,
+            Wal::new(temp_dir.path()).unwrap(),
+            empty_finalized_header_stream(),
+        );
+        let mut exex_manager = std::pin::pin!(exex_manager);
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![Default::default()],
+                Default::default(),
+                Default::default(),
+            )),
+        };
+
+        // The first ExEx crashes, which must not affect the second one
+        drop(notifications_1);
+        exex_manager.handle().send(ExExNotificationSource::Pipeline, notification.clone()).unwrap();
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(exex_manager.exex_handles.len(), 1);
+        assert_eq!(exex_manager.exex_handles[0].id, "test_exex_2");
+        assert_eq!(notifications_2.next().await.unwrap().unwrap(), notification);
+        assert!(exex_manager.buffer.is_empty());
+
+        // The restarted ExEx only receives new notifications
+        let (exex_handle_1, _, mut notifications_1) = new_exex_handle("test_exex_1");
+        exex_manager.handle().attach_exex(exex_handle_1).unwrap();
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(exex_manager.exex_handles.len(), 2);
+
+        exex_manager.handle().send(ExExNotificationSource::Pipeline, notification.clone()).unwrap();
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(notifications_1.next().await.unwrap().unwrap(), notification);
+        assert_eq!(notifications_2.next().await.unwrap().unwrap(), notification);
+    }
+
+    #[tokio::test]
+    async fn test_restarted_exex_resumes_from_finished_height() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .sealed_block_with_senders(genesis_hash.into(), TransactionVariant::NoHash)?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+
+        let block = random_block(
+            &mut rng,
+            genesis_block.number + 1,
+            BlockParams { parent: Some(genesis_hash), ..Default::default() },
+        )
+        .seal_with_senders()
+        .unwrap();
+        let provider_rw = provider_factory.database_provider_rw()?;
+        provider_rw.insert_block(block.clone())?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider2::new(provider_factory)?;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let new_exex_handle = || {
+            ExExHandle::new(
+                "test_exex".to_string(),
+                Head::default(),
+                provider.clone(),
+                EthExecutorProvider::mainnet(),
+                wal.handle(),
+            )
+        };
+        let (exex_handle, events_tx, notifications) = new_exex_handle();
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block.clone()], Default::default(), None)),
+        };
+
+        let (finalized_headers_tx, rx) = watch::channel(None);
+        let finalized_header_stream = ForkChoiceStream::new(rx);
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider.clone(),
+            vec![exex_handle],
+            2,
+            wal.clone(),
+            finalized_header_stream
+        ));
+        let handle = exex_manager.handle();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The ExEx finishes the genesis block and crashes before processing the next block
+        events_tx.send(ExExEvent::FinishedHeight(genesis_block.num_hash()))?;
+        drop(notifications);
+        handle.send(ExExNotificationSource::BlockchainTree, notification.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(exex_manager.exex_handles.is_empty());
+        assert_eq!(handle.exex_finished_height("test_exex"), Some(genesis_block.num_hash()));
+
+        // The WAL isn't finalized past the finished height of the detached ExEx, so that it can
+        // replay the block after restarting
+        finalized_headers_tx.send(Some(block.header.clone()))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(
+            exex_manager.wal.iter_notifications()?.collect::<eyre::Result<Vec<_>>>()?,
+            [notification]
+        );
+
+        // The restarted ExEx inherits the finished height until it reports a new one
+        let (exex_handle, _, _notifications) = new_exex_handle();
+        handle.attach_exex(exex_handle).unwrap();
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(exex_manager.exex_handles.len(), 1);
+        assert_eq!(exex_manager.exex_handles[0].finished_height, Some(genesis_block.num_hash()));
+        assert!(exex_manager.detached_exexs.is_empty());
+
+        // Once the ExEx is removed, it doesn't hold back the WAL finalization anymore
+        handle.remove_exex("test_exex".to_string()).unwrap();
+        finalized_headers_tx.send(Some(block.header.clone()))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(exex_manager.exex_handles.is_empty());
+        assert_eq!(handle.exex_finished_height("test_exex"), None);
+        assert_eq!(exex_manager.wal.iter_notifications()?.next().transpose()?, None);
+
+        Ok(())
     }
 
     #[tokio::test]
@@ -1,11 +1,13 @@
 use std::{
-    fs::File,
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
 use eyre::OptionExt;
-use reth_exex_types::ExExNotification;
+use reth_exex_types::{
+    serde_bincode_compat::{self, VersionedExExNotification},
+    ExExNotification,
+};
 use reth_tracing::tracing::debug;
 use tracing::instrument;
 
@@ -14,7 +16,8 @@ static FILE_EXTENSION: &str = "wal";
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
+/// [`VersionedExExNotification`]. Files written before the representation was versioned are still
+/// readable.
 #[derive(Debug, Clone)]
 pub struct Storage {
     /// The path to the WAL file.
@@ -128,20 +131,25 @@ impl Storage {
         let file_path = self.file_path(file_id);
         debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
 
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
+        let data = match std::fs::read(&file_path) {
+            Ok(data) => data,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+            Err(err) => return Err(reth_fs_util::FsPathError::read(err, &file_path).into()),
+        };
+        let size = data.len() as u64;
+
+        // Deserialize using the versioned bincode- and msgpack-compatible serde wrapper, falling
+        // back to the unversioned representation of older WAL files
+        let notification = match rmp_serde::from_slice::<VersionedExExNotification<'_>>(&data) {
+            Ok(notification) => notification.into(),
+            Err(err) => rmp_serde::from_slice::<serde_bincode_compat::ExExNotification<'_>>(&data)
+                .map(Into::into)
+                .map_err(|_| {
+                    eyre::eyre!("failed to decode notification from {file_path:?}: {err:?}")
+                })?,
         };
-        let size = file.metadata()?.len();
-
-        // Deserialize using the bincode- and msgpack-compatible serde wrapper
-        let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
-            rmp_serde::decode::from_read(&mut file).map_err(|err| {
-                eyre::eyre!("failed to decode notification from {file_path:?}: {err:?}")
-            })?;
 
-        Ok(Some((notification.into(), size)))
+        Ok(Some((notification, size)))
     }
 
     /// Writes the notification to the file with the given ID.
@@ -158,9 +166,8 @@ impl Storage {
         let file_path = self.file_path(file_id);
         debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
 
-        // Serialize using the bincode- and msgpack-compatible serde wrapper
-        let notification =
-            reth_exex_types::serde_bincode_compat::ExExNotification::from(notification);
+        // Serialize using the versioned bincode- and msgpack-compatible serde wrapper
+        let notification = VersionedExExNotification::from(notification);
 
         reth_fs_util::atomic_write_file(&file_path, |file| {
             rmp_serde::encode::write(file, &notification)
@@ -212,6 +219,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_unversioned() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage = Storage::new(&temp_dir)?;
+
+        let block = random_block(&mut rng, 0, Default::default())
+            .seal_with_senders()
+            .ok_or_eyre("failed to recover senders")?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+        };
+
+        // Write the notification the way it was written before the representation was versioned
+        let file_id = 0;
+        let unversioned =
+            reth_exex_types::serde_bincode_compat::ExExNotification::from(&notification);
+        std::fs::write(storage.file_path(file_id), rmp_serde::to_vec(&unversioned)?)?;
+
+        let deserialized_notification = storage.read_notification(file_id)?;
+        assert_eq!(
+            deserialized_notification.map(|(notification, _)| notification),
+            Some(notification)
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_files_range() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir()?;
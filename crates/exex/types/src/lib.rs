@@ -23,6 +23,9 @@ pub use notification::ExExNotification;
 /// all fields are serialized.
 ///
 /// Read more: <https://github.com/bincode-org/bincode/issues/326>
+///
+/// [`VersionedExExNotification`](serde_bincode_compat::VersionedExExNotification) is the stable,
+/// versioned representation of [`ExExNotification`] that is persisted in the ExEx WAL.
 #[cfg(all(feature = "serde", feature = "serde-bincode-compat"))]
 pub mod serde_bincode_compat {
     pub use super::notification::serde_bincode_compat::*;
@@ -157,10 +157,88 @@ pub(super) mod serde_bincode_compat {
         }
     }
 
+    /// Latest version of the [`VersionedExExNotification`] representation.
+    pub const EXEX_NOTIFICATION_VERSION: u64 = 1;
+
+    /// Versioned serde representation of [`super::ExExNotification`].
+    ///
+    /// This is the representation persisted by the ExEx WAL, and the one external consumers should
+    /// parse. It is an externally tagged enum keyed by the version, wrapping the bincode-compatible
+    /// [`ExExNotification`] and [`Chain`] representations. In JSON, a committed chain looks like:
+    ///
+    /// ```json
+    /// {
+    ///   "v1": {
+    ///     "ChainCommitted": {
+    ///       "new": {
+    ///         "blocks": { "<block number>": { "block": { .. }, "senders": [..] } },
+    ///         "execution_outcome": { "bundle": { .. }, "receipts": { .. }, "first_block": 0, .. },
+    ///         "trie_updates": null
+    ///       }
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// The representation of a released version never changes. Breaking changes to the
+    /// notification or chain types are introduced as a new variant, so that data written by older
+    /// versions of reth can still be decoded.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum VersionedExExNotification<'a> {
+        /// Version 1.
+        #[serde(rename = "v1")]
+        V1(ExExNotification<'a>),
+    }
+
+    impl VersionedExExNotification<'_> {
+        /// Returns the version of the representation.
+        pub const fn version(&self) -> u64 {
+            match self {
+                Self::V1(_) => 1,
+            }
+        }
+    }
+
+    impl<'a> From<&'a super::ExExNotification> for VersionedExExNotification<'a> {
+        fn from(value: &'a super::ExExNotification) -> Self {
+            Self::V1(value.into())
+        }
+    }
+
+    impl<'a> From<VersionedExExNotification<'a>> for super::ExExNotification {
+        fn from(value: VersionedExExNotification<'a>) -> Self {
+            match value {
+                VersionedExExNotification::V1(notification) => notification.into(),
+            }
+        }
+    }
+
+    impl SerializeAs<super::ExExNotification> for VersionedExExNotification<'_> {
+        fn serialize_as<S>(
+            source: &super::ExExNotification,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            VersionedExExNotification::from(source).serialize(serializer)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, super::ExExNotification> for VersionedExExNotification<'de> {
+        fn deserialize_as<D>(deserializer: D) -> Result<super::ExExNotification, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            VersionedExExNotification::deserialize(deserializer).map(Into::into)
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use std::sync::Arc;
 
+        use alloy_primitives::Address;
         use arbitrary::Arbitrary;
         use rand::Rng;
         use reth_execution_types::Chain;
@@ -206,5 +284,55 @@ pub(super) mod serde_bincode_compat {
             let decoded: Data = bincode::deserialize(&encoded).unwrap();
             assert_eq!(decoded, data);
         }
+
+        #[test]
+        fn test_versioned_exex_notification_bincode_roundtrip() {
+            #[serde_as]
+            #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+            struct Data {
+                #[serde_as(as = "serde_bincode_compat::VersionedExExNotification")]
+                notification: ExExNotification,
+            }
+
+            let mut bytes = [0u8; 1024];
+            rand::thread_rng().fill(bytes.as_mut_slice());
+            let data = Data {
+                notification: ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(
+                        vec![SealedBlockWithSenders::arbitrary(&mut arbitrary::Unstructured::new(
+                            &bytes,
+                        ))
+                        .unwrap()],
+                        Default::default(),
+                        None,
+                    )),
+                },
+            };
+
+            let encoded = bincode::serialize(&data).unwrap();
+            let decoded: Data = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+
+        /// Ensures that the JSON representation of version 1 doesn't change.
+        #[test]
+        fn test_versioned_exex_notification_v1_json() {
+            let fixture = include_str!("../testdata/exex_notification_v1.json");
+
+            let mut block = SealedBlockWithSenders::default();
+            block.senders.push(Address::with_last_byte(1));
+            let notification = ExExNotification::ChainReorged {
+                old: Arc::new(Chain::new(vec![block.clone()], Default::default(), None)),
+                new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+            };
+
+            let versioned = serde_bincode_compat::VersionedExExNotification::from(&notification);
+            assert_eq!(versioned.version(), serde_bincode_compat::EXEX_NOTIFICATION_VERSION);
+            assert_eq!(serde_json::to_string_pretty(&versioned).unwrap(), fixture.trim_end());
+
+            let decoded: serde_bincode_compat::VersionedExExNotification<'_> =
+                serde_json::from_str(fixture).unwrap();
+            assert_eq!(ExExNotification::from(decoded), notification);
+        }
     }
 }
@@ -103,6 +103,28 @@ pub enum FsPathError {
         to: PathBuf,
     },
 
+    /// Error variant for failed file copy operation with additional path context.
+    #[error("failed to copy {from:?} to {to:?}: {source}")]
+    Copy {
+        /// The source `io::Error`.
+        source: io::Error,
+        /// The original path.
+        from: PathBuf,
+        /// The target path.
+        to: PathBuf,
+    },
+
+    /// Error variant for failed hard link creation with additional path context.
+    #[error("failed to hard link {from:?} to {to:?}: {source}")]
+    HardLink {
+        /// The source `io::Error`.
+        source: io::Error,
+        /// The original path.
+        from: PathBuf,
+        /// The target path.
+        to: PathBuf,
+    },
+
     /// Error variant for failed file opening operation with additional path context.
     #[error("failed to open file {path:?}: {source}")]
     Open {
@@ -199,6 +221,16 @@ impl FsPathError {
         Self::Rename { source, from: from.into(), to: to.into() }
     }
 
+    /// Returns the complementary error variant for [`std::fs::copy`].
+    pub fn copy(source: io::Error, from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
+        Self::Copy { source, from: from.into(), to: to.into() }
+    }
+
+    /// Returns the complementary error variant for [`std::fs::hard_link`].
+    pub fn hard_link(source: io::Error, from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
+        Self::HardLink { source, from: from.into(), to: to.into() }
+    }
+
     /// Returns the complementary error variant for [`std::fs::File::metadata`].
     pub fn metadata(source: io::Error, path: impl Into<PathBuf>) -> Self {
         Self::Metadata { source, path: path.into() }
@@ -267,6 +299,20 @@ pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
     fs::rename(from, to).map_err(|err| FsPathError::rename(err, from, to))
 }
 
+/// Wrapper for `std::fs::copy`
+pub fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<u64> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+    fs::copy(from, to).map_err(|err| FsPathError::copy(err, from, to))
+}
+
+/// Wrapper for `std::fs::hard_link`
+pub fn hard_link(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+    fs::hard_link(from, to).map_err(|err| FsPathError::hard_link(err, from, to))
+}
+
 /// Wrapper for `std::fs::metadata`
 pub fn metadata(path: impl AsRef<Path>) -> Result<fs::Metadata> {
     let path = path.as_ref();
@@ -112,6 +112,18 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
+    /// Returns until when the peer is banned, `Some(None)` if it's banned indefinitely.
+    #[inline]
+    pub fn peer_banned_until(&self, peer_id: &PeerId) -> Option<Option<Instant>> {
+        self.banned_peers.get(peer_id).copied()
+    }
+
+    /// Returns an iterator over all banned peers and until when they're banned, `None` if they're
+    /// banned indefinitely.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer_id, until)| (*peer_id, *until))
+    }
+
     /// Unbans the ip address
     pub fn unban_ip(&mut self, ip: &IpAddr) {
         self.banned_ips.remove(ip);
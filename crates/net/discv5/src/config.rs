@@ -85,6 +85,9 @@ pub struct ConfigBuilder {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     discovered_peer_filter: Option<MustNotIncludeKeys>,
+    /// Whether discovered peers must advertise the fork kv-pair of the local node record, in order
+    /// to be passed up to rlpx.
+    require_fork: bool,
 }
 
 impl ConfigBuilder {
@@ -100,6 +103,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork,
         } = discv5_config;
 
         Self {
@@ -112,6 +116,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
             discovered_peer_filter: Some(discovered_peer_filter),
+            require_fork,
         }
     }
 
@@ -214,6 +219,14 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets whether discovered peers must advertise the same fork kv-pair key as the local node
+    /// record, e.g. b"eth", in order to be passed to rlpx. Peers on other networks are dropped
+    /// before a session is attempted.
+    pub const fn require_fork(mut self, require_fork: bool) -> Self {
+        self.require_fork = require_fork;
+        self
+    }
+
     /// Returns a new [`Config`].
     pub fn build(self) -> Config {
         let Self {
@@ -226,6 +239,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork,
         } = self;
 
         let mut discv5_config = discv5_config.unwrap_or_else(|| {
@@ -256,6 +270,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork,
         }
     }
 }
@@ -289,6 +304,9 @@ pub struct Config {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     pub(super) discovered_peer_filter: MustNotIncludeKeys,
+    /// Whether discovered peers must advertise the fork kv-pair of the local node record, in order
+    /// to be passed up to rlpx.
+    pub(super) require_fork: bool,
 }
 
 impl Config {
@@ -305,6 +323,7 @@ impl Config {
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
             discovered_peer_filter: None,
+            require_fork: false,
         }
     }
 
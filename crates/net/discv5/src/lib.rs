@@ -45,7 +45,7 @@ pub use config::{
 };
 pub use enr::enr_to_discv4_id;
 pub use error::Error;
-pub use filter::{FilterOutcome, MustNotIncludeKeys};
+pub use filter::{FilterOutcome, MustIncludeKey, MustNotIncludeKeys};
 pub use network_stack_id::NetworkStackId;
 
 use metrics::{DiscoveredPeersMetrics, Discv5Metrics};
@@ -73,6 +73,8 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Whether discovered peers must advertise the [`fork_key`](Self::fork_key) kv-pair.
+    require_fork: bool,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
 }
@@ -122,6 +124,22 @@ impl Discv5 {
         self.set_eip868_in_local_enr(key, buf.into())
     }
 
+    /// Updates the [`ForkId`] in the local [`Enr`](discv5::Enr), e.g. after a fork transition.
+    ///
+    /// This is a noop, unless the fork kv-pair of the local node record uses the
+    /// [`NetworkStackId::ETH`] key.
+    pub fn update_fork_id(&self, fork_id: ForkId) {
+        if self.fork_key != Some(NetworkStackId::ETH) {
+            return
+        }
+        if let Err(err) = self.discv5.enr_insert("eth", &EnrForkIdEntry::from(fork_id)) {
+            error!(target: "net::discv5",
+                %err,
+                "failed to update fork id in local enr"
+            );
+        }
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -182,6 +200,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            require_fork,
             ..
         } = discv5_config;
 
@@ -217,7 +236,7 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, require_fork, metrics },
             discv5_updates,
             bc_enr,
         ))
@@ -359,6 +378,14 @@ impl Discv5 {
     /// Applies filtering rules on an ENR. Returns [`Ok`](FilterOutcome::Ok) if peer should be
     /// passed up to app, and [`Ignore`](FilterOutcome::Ignore) if peer should instead be dropped.
     pub fn filter_discovered_peer(&self, enr: &discv5::Enr) -> FilterOutcome {
+        if self.require_fork {
+            if let Some(fork_key) = self.fork_key {
+                let outcome = MustIncludeKey::new(fork_key).filter(enr);
+                if !outcome.is_ok() {
+                    return outcome
+                }
+            }
+        }
         self.discovered_peer_filter.filter(enr)
     }
 
@@ -673,6 +700,7 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            require_fork: false,
             metrics: Discv5Metrics::default(),
         }
     }
@@ -774,6 +802,41 @@ mod test {
         )
     }
 
+    #[test]
+    fn discovered_enr_fork_required() {
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder().tcp4(30303).build(&remote_key).unwrap();
+        let fork_id = MAINNET.latest_fork_id();
+        let remote_enr_with_fork = Enr::builder()
+            .tcp4(30303)
+            .add_value(NetworkStackId::ETH, &EnrForkIdEntry::from(fork_id))
+            .build(&remote_key)
+            .unwrap();
+
+        let mut discv5 = discv5_noop();
+        discv5.fork_key = Some(NetworkStackId::ETH);
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_some());
+
+        discv5.require_fork = true;
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_none());
+        let peer = discv5.on_discovered_peer(&remote_enr_with_fork, remote_socket).unwrap();
+        assert_eq!(peer.fork_id, Some(fork_id));
+    }
+
+    #[test]
+    fn update_local_enr_fork_id() {
+        let mut discv5 = discv5_noop();
+        let fork_id = MAINNET.latest_fork_id();
+        discv5.update_fork_id(fork_id);
+        assert!(discv5.with_discv5(|discv5| discv5.local_enr()).get_raw_rlp("eth").is_none());
+
+        discv5.fork_key = Some(NetworkStackId::ETH);
+        discv5.update_fork_id(fork_id);
+        let enr = discv5.with_discv5(|discv5| discv5.local_enr());
+        assert_eq!(discv5.get_fork_id(&enr).unwrap(), fork_id);
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[allow(unreachable_pub)]
@@ -1,4 +1,4 @@
-//! Implements Ethereum wire protocol for versions 66, 67, and 68.
+//! Implements Ethereum wire protocol for versions 66, 67, 68 and 69.
 //! Defines structs/enums for messages, request-response pairs, and broadcasts.
 //! Handles compatibility with [`EthVersion`].
 //!
@@ -7,9 +7,10 @@
 //! Reference: [Ethereum Wire Protocol](https://github.com/ethereum/wiki/wiki/Ethereum-Wire-Protocol).
 
 use super::{
-    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders,
-    GetNodeData, GetPooledTransactions, GetReceipts, NewBlock, NewPooledTransactionHashes66,
-    NewPooledTransactionHashes68, NodeData, PooledTransactions, Receipts, Status, Transactions,
+    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, BlockRangeUpdate, GetBlockBodies,
+    GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, NewBlock,
+    NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData, PooledTransactions,
+    Receipts, Receipts69, Status, StatusEth69, Transactions,
 };
 use crate::{EthNetworkPrimitives, EthVersion, NetworkPrimitives, SharedTransactions};
 
@@ -52,7 +53,13 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
         let message_type = EthMessageID::decode(buf)?;
 
         let message = match message_type {
-            EthMessageID::Status => EthMessage::Status(Status::decode(buf)?),
+            EthMessageID::Status => {
+                if version.is_eth69() {
+                    EthMessage::StatusEth69(StatusEth69::decode(buf)?)
+                } else {
+                    EthMessage::Status(Status::decode(buf)?)
+                }
+            }
             EthMessageID::NewBlockHashes => {
                 if version.is_eth69() {
                     return Err(MessageError::Invalid(version, EthMessageID::NewBlockHashes));
@@ -100,7 +107,19 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
                 EthMessage::NodeData(RequestPair::decode(buf)?)
             }
             EthMessageID::GetReceipts => EthMessage::GetReceipts(RequestPair::decode(buf)?),
-            EthMessageID::Receipts => EthMessage::Receipts(RequestPair::decode(buf)?),
+            EthMessageID::Receipts => {
+                if version.is_eth69() {
+                    EthMessage::Receipts69(RequestPair::decode(buf)?)
+                } else {
+                    EthMessage::Receipts(RequestPair::decode(buf)?)
+                }
+            }
+            EthMessageID::BlockRangeUpdate => {
+                if !version.is_eth69() {
+                    return Err(MessageError::Invalid(version, EthMessageID::BlockRangeUpdate))
+                }
+                EthMessage::BlockRangeUpdate(BlockRangeUpdate::decode(buf)?)
+            }
         };
         Ok(Self { message_type, message })
     }
@@ -152,7 +171,7 @@ impl<N: NetworkPrimitives> From<EthBroadcastMessage<N>> for ProtocolBroadcastMes
     }
 }
 
-/// Represents a message in the eth wire protocol, versions 66, 67 and 68.
+/// Represents a message in the eth wire protocol, versions 66, 67, 68 and 69.
 ///
 /// The ethereum wire protocol is a set of messages that are broadcast to the network in two
 /// styles:
@@ -169,11 +188,18 @@ impl<N: NetworkPrimitives> From<EthBroadcastMessage<N>> for ProtocolBroadcastMes
 /// The `eth/68` changes only `NewPooledTransactionHashes` to include `types` and `sized`. For
 /// it, `NewPooledTransactionHashes` is renamed as [`NewPooledTransactionHashes66`] and
 /// [`NewPooledTransactionHashes68`] is defined.
+///
+/// The `eth/69` replaces the total difficulty and head in the status with the block range the
+/// peer can serve ([`StatusEth69`]), which is updated with [`BlockRangeUpdate`] messages. It
+/// removes the bloom from receipts ([`Receipts69`]), and the `NewBlockHashes` and `NewBlock`
+/// messages.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EthMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Represents a Status message required for the protocol handshake.
     Status(Status),
+    /// Represents a Status message required for the protocol handshake, for eth/69.
+    StatusEth69(StatusEth69),
     /// Represents a `NewBlockHashes` message broadcast to the network.
     NewBlockHashes(NewBlockHashes),
     /// Represents a `NewBlock` message broadcast to the network.
@@ -217,13 +243,17 @@ pub enum EthMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
     GetReceipts(RequestPair<GetReceipts>),
     /// Represents a Receipts request-response pair.
     Receipts(RequestPair<Receipts>),
+    /// Represents a Receipts request-response pair for eth/69, without the receipt blooms.
+    Receipts69(RequestPair<Receipts69>),
+    /// Represents a `BlockRangeUpdate` message broadcast to the network, for eth/69.
+    BlockRangeUpdate(BlockRangeUpdate),
 }
 
 impl<N: NetworkPrimitives> EthMessage<N> {
     /// Returns the message's ID.
     pub const fn message_id(&self) -> EthMessageID {
         match self {
-            Self::Status(_) => EthMessageID::Status,
+            Self::Status(_) | Self::StatusEth69(_) => EthMessageID::Status,
             Self::NewBlockHashes(_) => EthMessageID::NewBlockHashes,
             Self::NewBlock(_) => EthMessageID::NewBlock,
             Self::Transactions(_) => EthMessageID::Transactions,
@@ -239,7 +269,8 @@ impl<N: NetworkPrimitives> EthMessage<N> {
             Self::GetNodeData(_) => EthMessageID::GetNodeData,
             Self::NodeData(_) => EthMessageID::NodeData,
             Self::GetReceipts(_) => EthMessageID::GetReceipts,
-            Self::Receipts(_) => EthMessageID::Receipts,
+            Self::Receipts(_) | Self::Receipts69(_) => EthMessageID::Receipts,
+            Self::BlockRangeUpdate(_) => EthMessageID::BlockRangeUpdate,
         }
     }
 }
@@ -248,6 +279,7 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
     fn encode(&self, out: &mut dyn BufMut) {
         match self {
             Self::Status(status) => status.encode(out),
+            Self::StatusEth69(status) => status.encode(out),
             Self::NewBlockHashes(new_block_hashes) => new_block_hashes.encode(out),
             Self::NewBlock(new_block) => new_block.encode(out),
             Self::Transactions(transactions) => transactions.encode(out),
@@ -263,11 +295,14 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
             Self::NodeData(data) => data.encode(out),
             Self::GetReceipts(request) => request.encode(out),
             Self::Receipts(receipts) => receipts.encode(out),
+            Self::Receipts69(receipts) => receipts.encode(out),
+            Self::BlockRangeUpdate(block_range) => block_range.encode(out),
         }
     }
     fn length(&self) -> usize {
         match self {
             Self::Status(status) => status.length(),
+            Self::StatusEth69(status) => status.length(),
             Self::NewBlockHashes(new_block_hashes) => new_block_hashes.length(),
             Self::NewBlock(new_block) => new_block.length(),
             Self::Transactions(transactions) => transactions.length(),
@@ -283,6 +318,8 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
             Self::NodeData(data) => data.length(),
             Self::GetReceipts(request) => request.length(),
             Self::Receipts(receipts) => receipts.length(),
+            Self::Receipts69(receipts) => receipts.length(),
+            Self::BlockRangeUpdate(block_range) => block_range.length(),
         }
     }
 }
@@ -365,12 +402,18 @@ pub enum EthMessageID {
     GetReceipts = 0x0f,
     /// Represents receipts.
     Receipts = 0x10,
+    /// Block range update, since eth/69.
+    BlockRangeUpdate = 0x11,
 }
 
 impl EthMessageID {
-    /// Returns the max value.
-    pub const fn max() -> u8 {
-        Self::Receipts as u8
+    /// Returns the max value for the given version.
+    pub const fn max(version: EthVersion) -> u8 {
+        if version.is_eth69() {
+            Self::BlockRangeUpdate as u8
+        } else {
+            Self::Receipts as u8
+        }
     }
 }
 
@@ -401,6 +444,7 @@ impl Decodable for EthMessageID {
             0x0e => Self::NodeData,
             0x0f => Self::GetReceipts,
             0x10 => Self::Receipts,
+            0x11 => Self::BlockRangeUpdate,
             _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
         };
         buf.advance(1);
@@ -428,6 +472,7 @@ impl TryFrom<usize> for EthMessageID {
             0x0e => Ok(Self::NodeData),
             0x0f => Ok(Self::GetReceipts),
             0x10 => Ok(Self::Receipts),
+            0x11 => Ok(Self::BlockRangeUpdate),
             _ => Err("Invalid message ID"),
         }
     }
@@ -446,6 +491,14 @@ pub struct RequestPair<T> {
     pub message: T,
 }
 
+impl<T> RequestPair<T> {
+    /// Converts the message with the given function, keeping the request id.
+    pub fn map<R>(self, f: impl FnOnce(T) -> R) -> RequestPair<R> {
+        let Self { request_id, message } = self;
+        RequestPair { request_id, message: f(message) }
+    }
+}
+
 /// Allows messages with request ids to be serialized into RLP bytes.
 impl<T> Encodable for RequestPair<T>
 where
@@ -496,8 +549,8 @@ where
 mod tests {
     use super::MessageError;
     use crate::{
-        message::RequestPair, EthMessage, EthMessageID, EthNetworkPrimitives, EthVersion,
-        GetNodeData, NodeData, ProtocolMessage,
+        message::RequestPair, BlockRangeUpdate, EthMessage, EthMessageID, EthNetworkPrimitives,
+        EthVersion, GetNodeData, NodeData, ProtocolMessage, Receipts69,
     };
     use alloy_primitives::hex;
     use alloy_rlp::{Decodable, Encodable, Error};
@@ -593,4 +646,33 @@ mod tests {
             ProtocolMessage::decode_message(EthVersion::Eth68, &mut buf.as_slice()).unwrap();
         assert_eq!(empty_block_bodies, decoded);
     }
+
+    #[test]
+    fn eth69_messages() {
+        let block_range = ProtocolMessage::from(
+            EthMessage::<EthNetworkPrimitives>::BlockRangeUpdate(BlockRangeUpdate::default()),
+        );
+        let buf = encode(block_range.clone());
+        let decoded =
+            ProtocolMessage::decode_message(EthVersion::Eth69, &mut buf.as_slice()).unwrap();
+        assert_eq!(block_range, decoded);
+        let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut buf.as_slice(),
+        );
+        assert!(matches!(msg, Err(MessageError::Invalid(..))));
+
+        let receipts =
+            ProtocolMessage::from(EthMessage::<EthNetworkPrimitives>::Receipts69(RequestPair {
+                request_id: 0,
+                message: Receipts69(vec![vec![Default::default()]]),
+            }));
+        let buf = encode(receipts.clone());
+        let decoded =
+            ProtocolMessage::decode_message(EthVersion::Eth69, &mut buf.as_slice()).unwrap();
+        assert_eq!(receipts, decoded);
+
+        assert_eq!(EthMessageID::max(EthVersion::Eth68), EthMessageID::Receipts as u8);
+        assert_eq!(EthMessageID::max(EthVersion::Eth69), EthMessageID::BlockRangeUpdate as u8);
+    }
 }
@@ -3,7 +3,7 @@
 use alloy_primitives::B256;
 use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::add_arbitrary_tests;
-use reth_primitives::ReceiptWithBloom;
+use reth_primitives::{Receipt, ReceiptWithBloom};
 
 /// A request for transaction receipts from the given block hashes.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
@@ -26,9 +26,47 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+/// The eth/69 response to [`GetReceipts`], which omits the bloom of the receipts, see
+/// [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+///
+/// Each receipt is encoded as `[tx-type, status, cumulative-gas, logs]`.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct Receipts69(
+    /// Each receipt list should correspond to a block hash in the request.
+    pub Vec<Vec<Receipt>>,
+);
+
+impl From<Receipts69> for Receipts {
+    /// Recomputes the bloom of the receipts.
+    fn from(receipts: Receipts69) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(Receipt::with_bloom).collect())
+                .collect(),
+        )
+    }
+}
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_primitives::{hex, Log};
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{Receipt, ReceiptWithBloom, TxType};
@@ -152,4 +190,33 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    #[allow(clippy::needless_update)]
+    fn roundtrip_eth69_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                hex!("0000000000000000000000000000000000000011").into(),
+                vec![
+                    hex!("000000000000000000000000000000000000000000000000000000000000dead").into()
+                ],
+                hex!("0100ff")[..].into(),
+            )],
+            ..Default::default()
+        };
+        let receipts = Receipts69(vec![vec![receipt.clone()], vec![]]);
+
+        let mut out = vec![];
+        receipts.encode(&mut out);
+        let decoded = Receipts69::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(receipts, decoded);
+
+        // the bloom is recomputed from the logs
+        let with_bloom = Receipts::from(decoded);
+        assert_eq!(with_bloom.0[0][0].bloom, receipt.bloom_slow());
+        assert_eq!(Receipts69::from(with_bloom), receipts);
+    }
 }
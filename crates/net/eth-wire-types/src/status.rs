@@ -213,9 +213,104 @@ impl StatusBuilder {
     }
 }
 
+/// The eth/69 status message, see [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+///
+/// Replaces the total difficulty and head of [`Status`] with the range of blocks the peer can
+/// serve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StatusEth69 {
+    /// The current protocol version, i.e. 69.
+    pub version: EthVersion,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block.
+    pub blockhash: B256,
+}
+
+impl StatusEth69 {
+    /// Creates the eth/69 status message from the given [`Status`] and the range of blocks the
+    /// node can serve.
+    pub const fn new(status: &Status, block_range: BlockRangeUpdate) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            earliest: block_range.earliest,
+            latest: block_range.latest,
+            blockhash: block_range.latest_hash,
+        }
+    }
+
+    /// Returns the range of blocks the peer can serve.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest,
+            latest: self.latest,
+            latest_hash: self.blockhash,
+        }
+    }
+}
+
+/// Converts the eth/69 status into a [`Status`] with zero total difficulty, since eth/69 peers
+/// don't advertise it anymore.
+impl From<StatusEth69> for Status {
+    fn from(status: StatusEth69) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: status.blockhash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
+/// The range of blocks a peer can serve, announced in the eth/69 status and with the
+/// `BlockRangeUpdate` message.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+    /// The latest block the peer can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns true if the earliest block isn't after the latest block.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_primitives::{hex, B256, U256};
@@ -387,4 +482,30 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn eth69_status_roundtrip() {
+        let status = Status::builder()
+            .version(EthVersion::Eth69)
+            .chain(Chain::mainnet())
+            .total_difficulty(U256::from(100))
+            .blockhash(B256::repeat_byte(1))
+            .genesis(MAINNET_GENESIS_HASH)
+            .build();
+        let block_range =
+            BlockRangeUpdate { earliest: 10, latest: 20, latest_hash: B256::repeat_byte(2) };
+        let eth69 = StatusEth69::new(&status, block_range);
+        assert_eq!(eth69.block_range(), block_range);
+
+        let mut rlp_status = vec![];
+        eth69.encode(&mut rlp_status);
+        let decoded = StatusEth69::decode(&mut &rlp_status[..]).unwrap();
+        assert_eq!(decoded, eth69);
+
+        // the total difficulty isn't advertised anymore
+        let legacy = Status::from(decoded);
+        assert_eq!(legacy.total_difficulty, U256::ZERO);
+        assert_eq!(legacy.blockhash, block_range.latest_hash);
+        assert_eq!(legacy.forkid, status.forkid);
+    }
 }
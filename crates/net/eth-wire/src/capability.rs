@@ -163,7 +163,7 @@ impl SharedCapability {
     /// Returns the number of protocol messages supported by this capability.
     pub const fn num_messages(&self) -> u8 {
         match self {
-            Self::Eth { version: _version, .. } => EthMessageID::max() + 1,
+            Self::Eth { version, .. } => EthMessageID::max(*version) + 1,
             Self::UnknownCapability { messages, .. } => *messages,
         }
     }
@@ -476,6 +476,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_eth69_fallback_to_eth68() {
+        let snap = Capability::new_static("snap", 1);
+        let local_capabilities: Vec<Protocol> = vec![
+            EthVersion::Eth69.into(),
+            EthVersion::Eth68.into(),
+            Protocol::new(snap.clone(), 8),
+        ];
+
+        // peers that don't support eth/69 yet negotiate eth/68
+        let peer_capabilities = vec![EthVersion::Eth68.into(), snap.clone()];
+        let shared =
+            SharedCapabilities::try_new(local_capabilities.clone(), peer_capabilities).unwrap();
+        assert_eq!(shared.eth_version().unwrap(), EthVersion::Eth68);
+        assert_eq!(shared.find(&snap).unwrap().message_id_offset(), MAX_RESERVED_MESSAGE_ID + 18);
+
+        // eth/69 reserves another message id for `BlockRangeUpdate`
+        let peer_capabilities =
+            vec![EthVersion::Eth68.into(), EthVersion::Eth69.into(), snap.clone()];
+        let shared = SharedCapabilities::try_new(local_capabilities, peer_capabilities).unwrap();
+        assert_eq!(shared.eth_version().unwrap(), EthVersion::Eth69);
+        assert_eq!(shared.find(&snap).unwrap().message_id_offset(), MAX_RESERVED_MESSAGE_ID + 19);
+    }
+
     #[test]
     fn test_peer_capability_version_too_low() {
         let local: Vec<Protocol> = vec![EthVersion::Eth67.into()];
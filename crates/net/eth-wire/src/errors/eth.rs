@@ -38,6 +38,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    #[error("invalid block range update: earliest {earliest}, latest {latest}")]
+    /// Received a `BlockRangeUpdate` whose earliest block is after the latest block.
+    InvalidBlockRange {
+        /// The earliest block the peer can serve.
+        earliest: u64,
+        /// The latest block the peer can serve.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -102,4 +110,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("invalid block range in status message: earliest {earliest}, latest {latest}")]
+    /// The earliest block of the eth/69 status is after the latest block.
+    InvalidBlockRange {
+        /// The earliest block the peer can serve.
+        earliest: u64,
+        /// The latest block the peer can serve.
+        latest: u64,
+    },
 }
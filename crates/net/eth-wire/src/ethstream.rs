@@ -2,8 +2,8 @@ use crate::{
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, ProtocolMessage,
-    Status,
+    BlockRangeUpdate, CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives,
+    EthVersion, ProtocolMessage, Status, StatusEth69,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use futures::{ready, Sink, SinkExt, StreamExt};
@@ -34,12 +34,23 @@ pub(crate) const MAX_STATUS_SIZE: usize = 500 * 1024;
 pub struct UnauthedEthStream<S> {
     #[pin]
     inner: S,
+    /// The range of blocks we can serve, advertised in the eth/69 status.
+    block_range: Option<BlockRangeUpdate>,
 }
 
 impl<S> UnauthedEthStream<S> {
     /// Create a new `UnauthedEthStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self { inner, block_range: None }
+    }
+
+    /// Sets the range of blocks advertised in the eth/69 status.
+    ///
+    /// If not set, only the block hash of the [`Status`] is advertised, with a range of
+    /// `[0, 0]`.
+    pub const fn with_block_range(mut self, block_range: BlockRangeUpdate) -> Self {
+        self.block_range = Some(block_range);
+        self
     }
 
     /// Consumes the type and returns the wrapped stream
@@ -87,14 +98,20 @@ where
             "sending eth status to peer"
         );
 
+        // eth/69 peers exchange the block range instead of the total difficulty and head
+        let our_status = if status.version.is_eth69() {
+            let block_range = self.block_range.unwrap_or_else(|| BlockRangeUpdate {
+                latest_hash: status.blockhash,
+                ..Default::default()
+            });
+            EthMessage::<N>::StatusEth69(StatusEth69::new(&status, block_range))
+        } else {
+            EthMessage::<N>::Status(status)
+        };
+
         // we need to encode and decode here on our own because we don't have an `EthStream` yet
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
-        self.inner
-            .send(
-                alloy_rlp::encode(ProtocolMessage::<N>::from(EthMessage::<N>::Status(status)))
-                    .into(),
-            )
-            .await?;
+        self.inner.send(alloy_rlp::encode(ProtocolMessage::<N>::from(our_status)).into()).await?;
 
         let their_msg_res = self.inner.next().await;
 
@@ -123,69 +140,80 @@ where
 
         // The following checks should match the checks in go-ethereum:
         // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
-        match msg.message {
-            EthMessage::Status(resp) => {
-                trace!(
-                    status=%resp,
-                    "validating incoming eth status from peer"
-                );
-                if status.genesis != resp.genesis {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: resp.genesis }.into(),
-                    )
-                    .into())
-                }
-
-                if status.version != resp.version {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: resp.version,
-                        expected: status.version,
-                    })
-                    .into())
-                }
-
-                if status.chain != resp.chain {
+        let resp = match msg.message {
+            EthMessage::Status(resp) => resp,
+            EthMessage::StatusEth69(resp) => {
+                let block_range = resp.block_range();
+                if !block_range.is_valid() {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: resp.chain,
-                        expected: status.chain,
-                    })
-                    .into())
-                }
-
-                // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
-                // larger, it will still fit within 100 bits
-                if status.total_difficulty.bit_len() > 100 {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 100,
+                    return Err(EthHandshakeError::InvalidBlockRange {
+                        earliest: block_range.earliest,
+                        latest: block_range.latest,
                     }
                     .into())
                 }
-
-                if let Err(err) =
-                    fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
-                {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(err.into())
-                }
-
-                // now we can create the `EthStream` because the peer has successfully completed
-                // the handshake
-                let stream = EthStream::new(version, self.inner);
-
-                Ok((stream, resp))
+                resp.into()
             }
             _ => {
                 self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                Err(EthStreamError::EthHandshakeError(
+                return Err(EthStreamError::EthHandshakeError(
                     EthHandshakeError::NonStatusMessageInHandshake,
                 ))
             }
+        };
+
+        trace!(
+            status=%resp,
+            "validating incoming eth status from peer"
+        );
+        if status.genesis != resp.genesis {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedGenesis(
+                GotExpected { expected: status.genesis, got: resp.genesis }.into(),
+            )
+            .into())
+        }
+
+        if status.version != resp.version {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
+                got: resp.version,
+                expected: status.version,
+            })
+            .into())
+        }
+
+        if status.chain != resp.chain {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedChain(GotExpected {
+                got: resp.chain,
+                expected: status.chain,
+            })
+            .into())
+        }
+
+        // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
+        // larger, it will still fit within 100 bits
+        if status.total_difficulty.bit_len() > 100 {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                got: status.total_difficulty.bit_len(),
+                maximum: 100,
+            }
+            .into())
         }
+
+        if let Err(err) = fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
+        {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(err.into())
+        }
+
+        // now we can create the `EthStream` because the peer has successfully completed
+        // the handshake
+        let stream = EthStream::new(version, self.inner);
+
+        Ok((stream, resp))
     }
 }
 
@@ -292,7 +320,7 @@ where
             }
         };
 
-        if matches!(msg.message, EthMessage::Status(_)) {
+        if matches!(msg.message, EthMessage::Status(_) | EthMessage::StatusEth69(_)) {
             return Poll::Ready(Some(Err(EthStreamError::EthHandshakeError(
                 EthHandshakeError::StatusNotInHandshake,
             ))))
@@ -315,7 +343,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: EthMessage<N>) -> Result<(), Self::Error> {
-        if matches!(item, EthMessage::Status(_)) {
+        if matches!(item, EthMessage::Status(_) | EthMessage::StatusEth69(_)) {
             // TODO: to disconnect here we would need to do something similar to P2PStream's
             // start_disconnect, which would ideally be a part of the CanDisconnect trait, or at
             // least similar.
@@ -363,8 +391,8 @@ mod tests {
         errors::{EthHandshakeError, EthStreamError},
         hello::DEFAULT_TCP_PORT,
         p2pstream::UnauthedP2PStream,
-        EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec,
-        ProtocolVersion, Status,
+        BlockRangeUpdate, EthMessage, EthStream, EthVersion, HelloMessageWithProtocols,
+        PassthroughCodec, ProtocolVersion, Status,
     };
     use alloy_chains::NamedChain;
     use alloy_primitives::{B256, U256};
@@ -427,6 +455,101 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::from(100),
+            blockhash: B256::random(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+        };
+        let block_range =
+            BlockRangeUpdate { earliest: 0, latest: 100, latest_hash: status.blockhash };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let status_clone = status;
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (_, their_status) = UnauthedEthStream::new(stream)
+                .with_block_range(block_range)
+                .handshake::<EthNetworkPrimitives>(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+
+            // the total difficulty isn't exchanged on eth/69
+            assert_eq!(their_status, Status { total_difficulty: U256::ZERO, ..status_clone });
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (_, their_status) = UnauthedEthStream::new(sink)
+            .with_block_range(block_range)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await
+            .unwrap();
+        assert_eq!(their_status, Status { total_difficulty: U256::ZERO, ..status });
+
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn handshake_eth69_invalid_block_range() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::ZERO,
+            blockhash: B256::random(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let block_range =
+                BlockRangeUpdate { earliest: 10, latest: 5, latest_hash: status.blockhash };
+            let _ = UnauthedEthStream::new(stream)
+                .with_block_range(block_range)
+                .handshake::<EthNetworkPrimitives>(status, fork_filter_clone)
+                .await;
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let handshake_res = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await;
+        assert!(matches!(
+            handshake_res,
+            Err(EthStreamError::EthHandshakeError(EthHandshakeError::InvalidBlockRange {
+                earliest: 10,
+                latest: 5
+            }))
+        ));
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
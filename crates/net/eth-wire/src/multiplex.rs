@@ -20,7 +20,8 @@ use crate::{
     capability::{SharedCapabilities, SharedCapability, UnsupportedCapabilityError},
     errors::{EthStreamError, P2PStreamError},
     p2pstream::DisconnectP2P,
-    CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status, UnauthedEthStream,
+    BlockRangeUpdate, CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status,
+    UnauthedEthStream,
 };
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
@@ -210,6 +211,32 @@ impl<St> RlpxProtocolMultiplexer<St> {
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy, N>>, Status), EthStreamError>
+    where
+        St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
+    {
+        self.eth_satellite_stream(status, None, fork_filter).await
+    }
+
+    /// Same as [`Self::into_eth_satellite_stream`], but advertises the given range of blocks in
+    /// the eth/69 status, see [`UnauthedEthStream::with_block_range`].
+    pub async fn into_eth_satellite_stream_with_block_range<N: NetworkPrimitives>(
+        self,
+        status: Status,
+        block_range: BlockRangeUpdate,
+        fork_filter: ForkFilter,
+    ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy, N>>, Status), EthStreamError>
+    where
+        St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
+    {
+        self.eth_satellite_stream(status, Some(block_range), fork_filter).await
+    }
+
+    async fn eth_satellite_stream<N: NetworkPrimitives>(
+        self,
+        status: Status,
+        block_range: Option<BlockRangeUpdate>,
+        fork_filter: ForkFilter,
+    ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy, N>>, Status), EthStreamError>
     where
         St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
     {
@@ -217,7 +244,11 @@ impl<St> RlpxProtocolMultiplexer<St> {
         self.into_satellite_stream_with_tuple_handshake(
             &Capability::eth(eth_cap),
             move |proxy| async move {
-                UnauthedEthStream::new(proxy).handshake(status, fork_filter).await
+                let mut unauthed = UnauthedEthStream::new(proxy);
+                if let Some(block_range) = block_range {
+                    unauthed = unauthed.with_block_range(block_range);
+                }
+                unauthed.handshake(status, fork_filter).await
             },
         )
         .await
@@ -275,6 +275,11 @@ impl<S> P2PStream<S> {
         &self.inner
     }
 
+    /// Returns a mutable reference to the inner stream.
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
     /// Sets a custom outgoing message buffer capacity.
     ///
     /// # Panics
@@ -0,0 +1,24 @@
+//! Light client proof server.
+//!
+//! Implements the `lproof` `RLPx` subprotocol, which serves block headers as well as account and
+//! storage proofs (as returned by `eth_getProof`) to light clients. Headers are served from the
+//! static files, proofs are generated from the state trie for blocks within a configurable
+//! window behind the tip. Light clients verify headers by their parent hashes and proofs against
+//! the state root of the requested header.
+//!
+//! The server is added to the network with
+//! [`IntoRlpxSubProtocol`](reth_network::protocol::IntoRlpxSubProtocol).
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod message;
+pub use message::LightProofsMessage;
+
+mod server;
+pub use server::*;
@@ -0,0 +1,324 @@
+//! Messages of the `lproof` protocol.
+//!
+//! Every message is encoded as its message id followed by the RLP encoding of its payload.
+
+use alloy_consensus::{constants::KECCAK_EMPTY, Header};
+use alloy_primitives::{
+    bytes::{Buf, BufMut, BytesMut},
+    Address, BlockNumber, Bytes, B256, U256,
+};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_eth_wire::{protocol::Protocol, Capability};
+use reth_primitives::Account;
+use reth_trie::{AccountProof, StorageProof};
+
+/// Message ids of the `lproof` protocol.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightProofsMessageId {
+    /// Request for a range of block headers.
+    GetBlockHeaders = 0x00,
+    /// Response with block headers.
+    BlockHeaders = 0x01,
+    /// Request for an account proof and storage proofs at a block.
+    GetProof = 0x02,
+    /// Response with an account proof and storage proofs.
+    Proof = 0x03,
+    /// Response to a request that can't be served.
+    Error = 0x04,
+}
+
+impl TryFrom<u8> for LightProofsMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(id: u8) -> Result<Self, alloy_rlp::Error> {
+        Ok(match id {
+            0x00 => Self::GetBlockHeaders,
+            0x01 => Self::BlockHeaders,
+            0x02 => Self::GetProof,
+            0x03 => Self::Proof,
+            0x04 => Self::Error,
+            _ => return Err(alloy_rlp::Error::Custom("unknown lproof message id")),
+        })
+    }
+}
+
+/// Requests `limit` consecutive headers starting at `start_block`.
+///
+/// Light clients verify the returned headers by their parent hashes, starting from a trusted
+/// header.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetBlockHeaders {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The number of the first header.
+    pub start_block: BlockNumber,
+    /// The maximum number of headers to return.
+    pub limit: u64,
+}
+
+/// Block headers in ascending order. Can hold fewer headers than requested.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct BlockHeaders {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The headers.
+    pub headers: Vec<Header>,
+}
+
+/// Requests the proof of an account and the given storage slots at a block, like `eth_getProof`.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetProof {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The block whose state root the proof is for.
+    pub block_number: BlockNumber,
+    /// The account address.
+    pub address: Address,
+    /// The storage slots to prove.
+    pub storage_keys: Vec<B256>,
+}
+
+/// The account and storage proofs requested by [`GetProof`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct Proof {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The proof.
+    pub proof: AccountProofMessage,
+}
+
+/// A request that can't be served.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ErrorResponse {
+    /// The id of the request.
+    pub request_id: u64,
+    /// Why the request can't be served.
+    pub message: String,
+}
+
+/// Wire representation of an [`AccountProof`], verifiable against the state root of the
+/// requested block with [`AccountProof::verify`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountProofMessage {
+    /// The account address.
+    pub address: Address,
+    /// Whether the account exists. If not, the proof is a proof of absence.
+    pub exists: bool,
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance.
+    pub balance: U256,
+    /// The hash of the account code.
+    pub code_hash: B256,
+    /// The root of the account storage trie.
+    pub storage_root: B256,
+    /// The RLP encoded trie nodes from the state root to the account.
+    pub proof: Vec<Bytes>,
+    /// The storage proofs.
+    pub storage_proofs: Vec<StorageProofMessage>,
+}
+
+/// Wire representation of a [`StorageProof`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageProofMessage {
+    /// The storage slot.
+    pub key: B256,
+    /// The storage value.
+    pub value: U256,
+    /// The RLP encoded trie nodes from the storage root to the slot.
+    pub proof: Vec<Bytes>,
+}
+
+impl From<AccountProof> for AccountProofMessage {
+    fn from(proof: AccountProof) -> Self {
+        let info = proof.info.unwrap_or_default();
+        Self {
+            address: proof.address,
+            exists: proof.info.is_some(),
+            nonce: info.nonce,
+            balance: info.balance,
+            code_hash: info.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+            storage_root: proof.storage_root,
+            proof: proof.proof,
+            storage_proofs: proof
+                .storage_proofs
+                .into_iter()
+                .map(|storage| StorageProofMessage {
+                    key: storage.key,
+                    value: storage.value,
+                    proof: storage.proof,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<AccountProofMessage> for AccountProof {
+    fn from(message: AccountProofMessage) -> Self {
+        Self {
+            address: message.address,
+            info: message.exists.then_some(Account {
+                nonce: message.nonce,
+                balance: message.balance,
+                bytecode_hash: (message.code_hash != KECCAK_EMPTY).then_some(message.code_hash),
+            }),
+            proof: message.proof,
+            storage_root: message.storage_root,
+            storage_proofs: message
+                .storage_proofs
+                .into_iter()
+                .map(|storage| {
+                    let mut proof = StorageProof::new(storage.key);
+                    proof.value = storage.value;
+                    proof.proof = storage.proof;
+                    proof
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A message of the `lproof` protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LightProofsMessage {
+    /// See [`GetBlockHeaders`].
+    GetBlockHeaders(GetBlockHeaders),
+    /// See [`BlockHeaders`].
+    BlockHeaders(BlockHeaders),
+    /// See [`GetProof`].
+    GetProof(GetProof),
+    /// See [`Proof`].
+    Proof(Proof),
+    /// See [`ErrorResponse`].
+    Error(ErrorResponse),
+}
+
+impl LightProofsMessage {
+    /// Returns the capability of the `lproof` protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("lproof", 1)
+    }
+
+    /// Returns the `lproof` protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), 5)
+    }
+
+    /// Returns the id of the message.
+    pub const fn message_id(&self) -> LightProofsMessageId {
+        match self {
+            Self::GetBlockHeaders(_) => LightProofsMessageId::GetBlockHeaders,
+            Self::BlockHeaders(_) => LightProofsMessageId::BlockHeaders,
+            Self::GetProof(_) => LightProofsMessageId::GetProof,
+            Self::Proof(_) => LightProofsMessageId::Proof,
+            Self::Error(_) => LightProofsMessageId::Error,
+        }
+    }
+
+    /// Returns the id of the request the message belongs to.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetBlockHeaders(msg) => msg.request_id,
+            Self::BlockHeaders(msg) => msg.request_id,
+            Self::GetProof(msg) => msg.request_id,
+            Self::Proof(msg) => msg.request_id,
+            Self::Error(msg) => msg.request_id,
+        }
+    }
+
+    /// Encodes the message id followed by the RLP encoded payload.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetBlockHeaders(msg) => msg.encode(&mut buf),
+            Self::BlockHeaders(msg) => msg.encode(&mut buf),
+            Self::GetProof(msg) => msg.encode(&mut buf),
+            Self::Proof(msg) => msg.encode(&mut buf),
+            Self::Error(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the given buffer.
+    pub fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.is_empty() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let id = LightProofsMessageId::try_from(buf[0])?;
+        buf.advance(1);
+
+        Ok(match id {
+            LightProofsMessageId::GetBlockHeaders => {
+                Self::GetBlockHeaders(GetBlockHeaders::decode(buf)?)
+            }
+            LightProofsMessageId::BlockHeaders => Self::BlockHeaders(BlockHeaders::decode(buf)?),
+            LightProofsMessageId::GetProof => Self::GetProof(GetProof::decode(buf)?),
+            LightProofsMessageId::Proof => Self::Proof(Proof::decode(buf)?),
+            LightProofsMessageId::Error => Self::Error(ErrorResponse::decode(buf)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_roundtrip() {
+        let messages = [
+            LightProofsMessage::GetBlockHeaders(GetBlockHeaders {
+                request_id: 1,
+                start_block: 100,
+                limit: 10,
+            }),
+            LightProofsMessage::BlockHeaders(BlockHeaders {
+                request_id: 1,
+                headers: vec![Header { number: 100, ..Default::default() }],
+            }),
+            LightProofsMessage::GetProof(GetProof {
+                request_id: 2,
+                block_number: 100,
+                address: Address::with_last_byte(1),
+                storage_keys: vec![B256::with_last_byte(2)],
+            }),
+            LightProofsMessage::Proof(Proof {
+                request_id: 2,
+                proof: AccountProof::new(Address::with_last_byte(1)).into(),
+            }),
+            LightProofsMessage::Error(ErrorResponse {
+                request_id: 3,
+                message: "unavailable".to_string(),
+            }),
+        ];
+
+        for message in messages {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], message.message_id() as u8);
+            assert_eq!(LightProofsMessage::decode(&mut &encoded[..]).unwrap(), message);
+        }
+
+        LightProofsMessage::decode(&mut &[0x05][..]).unwrap_err();
+        LightProofsMessage::decode(&mut &[][..]).unwrap_err();
+    }
+
+    #[test]
+    fn account_proof_roundtrip() {
+        let mut proof = AccountProof::new(Address::with_last_byte(1));
+        proof.info = Some(Account {
+            nonce: 1,
+            balance: U256::from(2),
+            bytecode_hash: Some(B256::with_last_byte(3)),
+        });
+        let mut storage = StorageProof::new(B256::with_last_byte(4));
+        storage.value = U256::from(5);
+        storage.proof = vec![Bytes::from_static(&[6])];
+        proof.storage_proofs.push(storage);
+
+        assert_eq!(AccountProof::from(AccountProofMessage::from(proof.clone())), proof);
+
+        let absent = AccountProof::new(Address::with_last_byte(1));
+        assert_eq!(AccountProof::from(AccountProofMessage::from(absent.clone())), absent);
+    }
+}
@@ -0,0 +1,368 @@
+//! Serves `lproof` requests from the node's storage.
+
+use crate::message::{
+    BlockHeaders, ErrorResponse, GetBlockHeaders, GetProof, LightProofsMessage, Proof,
+};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::bytes::BytesMut;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{Direction, PeerId};
+use reth_storage_api::{BlockNumReader, HeaderProvider, StateProviderFactory};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::TrieInput;
+use std::{
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, trace};
+
+/// Default number of blocks behind the tip for which state proofs are served.
+pub const DEFAULT_PROOF_WINDOW: u64 = 64;
+
+/// Default maximum number of headers served per request.
+pub const DEFAULT_MAX_HEADERS: u64 = 192;
+
+/// Default maximum number of storage slots proven per request.
+pub const DEFAULT_MAX_STORAGE_KEYS: usize = 64;
+
+/// Default maximum number of requests served concurrently per peer.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Limits of the light client proof server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightProofsConfig {
+    /// Number of blocks behind the tip for which state proofs are served.
+    pub proof_window: u64,
+    /// Maximum number of headers served per request.
+    pub max_headers: u64,
+    /// Maximum number of storage slots proven per request.
+    pub max_storage_keys: usize,
+    /// Maximum number of requests served concurrently per peer. Further requests are not read
+    /// from the connection until one of them completes.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for LightProofsConfig {
+    fn default() -> Self {
+        Self {
+            proof_window: DEFAULT_PROOF_WINDOW,
+            max_headers: DEFAULT_MAX_HEADERS,
+            max_storage_keys: DEFAULT_MAX_STORAGE_KEYS,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+/// The [`ProtocolHandler`] of the `lproof` protocol, announcing it on every connection.
+#[derive(Clone)]
+pub struct LightProofsHandler<P> {
+    provider: P,
+    config: LightProofsConfig,
+}
+
+impl<P> LightProofsHandler<P> {
+    /// Creates a new handler serving requests from the given provider.
+    pub const fn new(provider: P, config: LightProofsConfig) -> Self {
+        Self { provider, config }
+    }
+}
+
+impl<P> fmt::Debug for LightProofsHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightProofsHandler").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+impl<P> ProtocolHandler for LightProofsHandler<P>
+where
+    P: HeaderProvider + BlockNumReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+}
+
+impl<P> ConnectionHandler for LightProofsHandler<P>
+where
+    P: HeaderProvider + BlockNumReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Connection = LightProofsConnection<P>;
+
+    fn protocol(&self) -> Protocol {
+        LightProofsMessage::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        trace!(target: "net::lproof", %peer_id, "Light client connected");
+        LightProofsConnection {
+            conn,
+            peer_id,
+            provider: self.provider,
+            config: self.config,
+            pending: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// An `lproof` connection to a single peer, yielding the responses to its requests.
+///
+/// Requests are served on blocking tasks, since proofs are generated from the database.
+pub struct LightProofsConnection<P> {
+    conn: ProtocolConnection,
+    peer_id: PeerId,
+    provider: P,
+    config: LightProofsConfig,
+    pending: FuturesUnordered<JoinHandle<LightProofsMessage>>,
+}
+
+impl<P> fmt::Debug for LightProofsConnection<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightProofsConnection")
+            .field("peer_id", &self.peer_id)
+            .field("config", &self.config)
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> Stream for LightProofsConnection<P>
+where
+    P: HeaderProvider + BlockNumReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending.poll_next_unpin(cx) {
+                match response {
+                    Ok(response) => return Poll::Ready(Some(response.encoded())),
+                    Err(err) => {
+                        debug!(target: "net::lproof", peer_id = %this.peer_id, %err, "Failed to serve request");
+                        continue
+                    }
+                }
+            }
+
+            if this.pending.len() >= this.config.max_concurrent_requests {
+                return Poll::Pending
+            }
+
+            let Some(msg) = futures::ready!(this.conn.poll_next_unpin(cx)) else {
+                return Poll::Ready(None)
+            };
+            let msg = match LightProofsMessage::decode(&mut &msg[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    debug!(target: "net::lproof", peer_id = %this.peer_id, %err, "Invalid message, closing connection");
+                    return Poll::Ready(None)
+                }
+            };
+
+            match msg {
+                LightProofsMessage::GetBlockHeaders(_) | LightProofsMessage::GetProof(_) => {
+                    let provider = this.provider.clone();
+                    let config = this.config;
+                    this.pending.push(tokio::task::spawn_blocking(move || {
+                        serve_request(&provider, &config, msg)
+                    }));
+                }
+                // this side only serves requests
+                LightProofsMessage::BlockHeaders(_) |
+                LightProofsMessage::Proof(_) |
+                LightProofsMessage::Error(_) => {}
+            }
+        }
+    }
+}
+
+/// Serves a request, returning the response message.
+///
+/// Requests that can't be served are answered with an [`ErrorResponse`].
+pub fn serve_request<P>(
+    provider: &P,
+    config: &LightProofsConfig,
+    request: LightProofsMessage,
+) -> LightProofsMessage
+where
+    P: HeaderProvider + BlockNumReader + StateProviderFactory,
+{
+    let request_id = request.request_id();
+    let response = match request {
+        LightProofsMessage::GetBlockHeaders(request) => {
+            serve_headers(provider, config, request).map(LightProofsMessage::BlockHeaders)
+        }
+        LightProofsMessage::GetProof(request) => {
+            serve_proof(provider, config, request).map(|response| {
+                response.map_or_else(
+                    |message| LightProofsMessage::Error(ErrorResponse { request_id, message }),
+                    LightProofsMessage::Proof,
+                )
+            })
+        }
+        _ => Ok(LightProofsMessage::Error(ErrorResponse {
+            request_id,
+            message: "not a request".to_string(),
+        })),
+    };
+
+    response.unwrap_or_else(|err| {
+        LightProofsMessage::Error(ErrorResponse { request_id, message: err.to_string() })
+    })
+}
+
+fn serve_headers<P: HeaderProvider>(
+    provider: &P,
+    config: &LightProofsConfig,
+    request: GetBlockHeaders,
+) -> ProviderResult<BlockHeaders> {
+    let limit = request.limit.min(config.max_headers);
+    let headers = if limit == 0 {
+        Vec::new()
+    } else {
+        provider.headers_range(request.start_block..request.start_block.saturating_add(limit))?
+    };
+    Ok(BlockHeaders { request_id: request.request_id, headers })
+}
+
+/// Returns the proof, or the reason why it's not served.
+fn serve_proof<P>(
+    provider: &P,
+    config: &LightProofsConfig,
+    request: GetProof,
+) -> ProviderResult<Result<Proof, String>>
+where
+    P: BlockNumReader + StateProviderFactory,
+{
+    if request.storage_keys.len() > config.max_storage_keys {
+        return Ok(Err(format!(
+            "too many storage keys, at most {} are served",
+            config.max_storage_keys
+        )))
+    }
+
+    let tip = provider.best_block_number()?;
+    if request.block_number > tip || tip - request.block_number > config.proof_window {
+        return Ok(Err(format!(
+            "block {} is outside of the proof window of {} blocks behind the tip {tip}",
+            request.block_number, config.proof_window
+        )))
+    }
+
+    let state =
+        provider.state_by_block_number_or_tag(BlockNumberOrTag::Number(request.block_number))?;
+    let proof = state.proof(TrieInput::default(), request.address, &request.storage_keys)?;
+    Ok(Ok(Proof { request_id: request.request_id, proof: proof.into() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, B256};
+    use reth_provider::test_utils::MockEthProvider;
+
+    fn provider(tip: u64) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        for number in 0..=tip {
+            let header = Header { number, ..Default::default() };
+            provider.add_header(header.hash_slow(), header);
+        }
+        provider
+    }
+
+    #[test]
+    fn serves_headers() {
+        let provider = provider(10);
+        let config = LightProofsConfig { max_headers: 4, ..Default::default() };
+
+        let request = |start_block, limit| {
+            LightProofsMessage::GetBlockHeaders(GetBlockHeaders {
+                request_id: 7,
+                start_block,
+                limit,
+            })
+        };
+
+        let LightProofsMessage::BlockHeaders(response) =
+            serve_request(&provider, &config, request(2, 100))
+        else {
+            panic!("expected headers")
+        };
+        assert_eq!(response.request_id, 7);
+        assert_eq!(
+            response.headers.iter().map(|header| header.number).collect::<Vec<_>>(),
+            vec![2, 3, 4, 5]
+        );
+
+        let LightProofsMessage::BlockHeaders(response) =
+            serve_request(&provider, &config, request(9, 4))
+        else {
+            panic!("expected headers")
+        };
+        assert_eq!(response.headers.len(), 2);
+    }
+
+    #[test]
+    fn serves_proofs_within_window() {
+        let provider = provider(100);
+        let config =
+            LightProofsConfig { proof_window: 10, max_storage_keys: 1, ..Default::default() };
+        let address = Address::with_last_byte(1);
+
+        let request = |block_number, storage_keys| {
+            LightProofsMessage::GetProof(GetProof {
+                request_id: 1,
+                block_number,
+                address,
+                storage_keys,
+            })
+        };
+
+        let response = serve_request(&provider, &config, request(95, vec![B256::ZERO]));
+        assert!(
+            matches!(response, LightProofsMessage::Proof(Proof { proof, .. }) if proof.address == address)
+        );
+
+        for request in
+            [request(89, vec![]), request(101, vec![]), request(100, vec![B256::ZERO, B256::ZERO])]
+        {
+            assert!(matches!(
+                serve_request(&provider, &config, request),
+                LightProofsMessage::Error(ErrorResponse { request_id: 1, .. })
+            ));
+        }
+    }
+}
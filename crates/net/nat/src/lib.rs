@@ -1,4 +1,4 @@
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports on the gateway.
 //!
 //! ## Feature Flags
 //!
@@ -12,9 +12,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod natpmp;
 pub mod net_if;
+pub mod port_mapping;
+pub mod upnp;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
+pub use port_mapping::{PortMapper, PortMapping, PortMappingError};
 
 use std::{
     fmt,
@@ -44,7 +48,10 @@ pub enum NatResolver {
     /// Resolve with any available resolver.
     #[default]
     Any,
-    /// Resolve external IP via `UPnP`.
+    /// Resolve external IP via the `UPnP` or NAT-PMP gateway, falling back to
+    /// [`NatResolver::PublicIp`].
+    ///
+    /// The network additionally maps its ports on the gateway with a [`PortMapper`].
     Upnp,
     /// Resolve external IP via a network request.
     PublicIp,
@@ -197,7 +204,11 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp => match port_mapping::resolve_gateway_external_ip().await {
+            Some(ip) => Some(ip),
+            None => resolve_external_ip().await,
+        },
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
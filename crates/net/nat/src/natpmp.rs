@@ -0,0 +1,187 @@
+//! Port mapping via NAT-PMP, see [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+
+use crate::port_mapping::{PortMappingError, Protocol};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The port gateways listen on for NAT-PMP requests.
+const NAT_PMP_PORT: u16 = 5351;
+
+/// The timeout of the first attempt of a request, which is doubled on every retry.
+const INITIAL_REQUEST_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// How often a request is sent before giving up on the gateway.
+const MAX_REQUEST_ATTEMPTS: u32 = 4;
+
+/// The opcode of the external address request.
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// The opcode bit that is set in responses.
+const OP_RESPONSE: u8 = 128;
+
+/// A NAT-PMP gateway.
+#[derive(Debug, Clone, Copy)]
+pub struct Gateway {
+    addr: SocketAddr,
+}
+
+impl Gateway {
+    /// Returns the default gateway of this host, if it responds to NAT-PMP requests.
+    pub async fn discover() -> Result<Self, PortMappingError> {
+        let ip = default_gateway().ok_or(PortMappingError::NoGateway)?;
+        let gateway = Self { addr: SocketAddr::new(IpAddr::V4(ip), NAT_PMP_PORT) };
+        gateway.external_ip().await?;
+        Ok(gateway)
+    }
+
+    /// Returns the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.request(&[0, OP_EXTERNAL_ADDRESS], 12).await?;
+        let ip = Ipv4Addr::new(response[8], response[9], response[10], response[11]);
+        Ok(IpAddr::V4(ip))
+    }
+
+    /// Maps the external `port` to the same port of this host for the given lease.
+    ///
+    /// Returns the lease granted by the gateway.
+    pub async fn add_port(
+        &self,
+        protocol: Protocol,
+        port: u16,
+        lease: Duration,
+    ) -> Result<Duration, PortMappingError> {
+        let response = self.request(&map_request(protocol, port, port, lease), 16).await?;
+        let mapped_port = u16::from_be_bytes([response[10], response[11]]);
+        if mapped_port != port {
+            // the requested port is taken, release the mapping we got instead
+            let _ = self.request(&map_request(protocol, port, 0, Duration::ZERO), 16).await;
+            return Err(PortMappingError::PortTaken(mapped_port))
+        }
+        let lease = u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+        Ok(Duration::from_secs(lease.into()))
+    }
+
+    /// Removes the mapping of the `port`.
+    pub async fn remove_port(&self, protocol: Protocol, port: u16) -> Result<(), PortMappingError> {
+        self.request(&map_request(protocol, port, 0, Duration::ZERO), 16).await?;
+        Ok(())
+    }
+
+    /// Sends the request to the gateway and returns the successful response of length `len`.
+    async fn request(&self, request: &[u8], len: usize) -> Result<Vec<u8>, PortMappingError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+        socket.connect(self.addr).await?;
+
+        let mut buf = [0u8; 16];
+        let mut timeout = INITIAL_REQUEST_TIMEOUT;
+        for _ in 0..MAX_REQUEST_ATTEMPTS {
+            socket.send(request).await?;
+            match tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+                Ok(received) => {
+                    let received = received?;
+                    return parse_response(&buf[..received], request[1], len).map(<[u8]>::to_vec)
+                }
+                Err(_) => timeout *= 2,
+            }
+        }
+        Err(PortMappingError::NoGateway)
+    }
+}
+
+/// Returns a mapping request of the `port` to the `external_port`.
+///
+/// A zero lease and external port removes the mapping.
+fn map_request(protocol: Protocol, port: u16, external_port: u16, lease: Duration) -> [u8; 12] {
+    let opcode = match protocol {
+        Protocol::Udp => 1,
+        Protocol::Tcp => 2,
+    };
+    let lease = u32::try_from(lease.as_secs()).unwrap_or(u32::MAX);
+
+    let mut request = [0u8; 12];
+    request[1] = opcode;
+    request[4..6].copy_from_slice(&port.to_be_bytes());
+    request[6..8].copy_from_slice(&external_port.to_be_bytes());
+    request[8..12].copy_from_slice(&lease.to_be_bytes());
+    request
+}
+
+/// Validates the response to a request with the given opcode.
+fn parse_response(response: &[u8], opcode: u8, len: usize) -> Result<&[u8], PortMappingError> {
+    if response.len() < len || response[0] != 0 || response[1] != opcode | OP_RESPONSE {
+        return Err(PortMappingError::InvalidResponse)
+    }
+    match u16::from_be_bytes([response[2], response[3]]) {
+        0 => Ok(&response[..len]),
+        code => Err(PortMappingError::Rejected(code.to_string())),
+    }
+}
+
+/// Returns the IPv4 default gateway of this host.
+#[cfg(target_os = "linux")]
+fn default_gateway() -> Option<Ipv4Addr> {
+    parse_default_gateway(&std::fs::read_to_string("/proc/net/route").ok()?)
+}
+
+/// Returns the IPv4 default gateway of this host.
+#[cfg(not(target_os = "linux"))]
+const fn default_gateway() -> Option<Ipv4Addr> {
+    None
+}
+
+/// Returns the default gateway from the routing table in the format of `/proc/net/route`.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace().skip(1);
+        let destination = fields.next()?;
+        let gateway = u32::from_str_radix(fields.next()?, 16).ok()?;
+        // the addresses are in host byte order
+        (destination == "00000000" && gateway != 0).then(|| Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_map_request() {
+        let request = map_request(Protocol::Tcp, 30303, 30303, Duration::from_secs(7200));
+        assert_eq!(request, [0, 2, 0, 0, 0x76, 0x5f, 0x76, 0x5f, 0, 0, 0x1c, 0x20]);
+
+        let request = map_request(Protocol::Udp, 30303, 0, Duration::ZERO);
+        assert_eq!(request, [0, 1, 0, 0, 0x76, 0x5f, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn decode_response() {
+        let response = [0, 130, 0, 0, 0, 0, 0, 1, 0x76, 0x5f, 0x76, 0x5f, 0, 0, 0x1c, 0x20];
+        assert_eq!(parse_response(&response, 2, 16).unwrap(), &response);
+
+        // wrong opcode
+        assert!(matches!(parse_response(&response, 1, 16), Err(PortMappingError::InvalidResponse)));
+
+        // not authorized
+        let response = [0, 128, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0];
+        assert!(matches!(
+            parse_response(&response, OP_EXTERNAL_ADDRESS, 12),
+            Err(PortMappingError::Rejected(code)) if code == "2"
+        ));
+    }
+
+    #[test]
+    fn parse_route_table() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+            eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+            eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(parse_default_gateway(routes), Some(Ipv4Addr::new(192, 168, 1, 1)));
+
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+            eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n";
+        assert_eq!(parse_default_gateway(routes), None);
+    }
+}
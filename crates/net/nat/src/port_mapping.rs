@@ -0,0 +1,298 @@
+//! Automatic port mapping on the gateway via `UPnP` or NAT-PMP.
+//!
+//! This makes a node behind a consumer router reachable without manual port forwarding, see
+//! [`NatResolver::Upnp`](crate::NatResolver::Upnp).
+
+use crate::{natpmp, upnp};
+use std::{
+    fmt, io,
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// The lease that is requested for port mappings.
+pub const DEFAULT_PORT_MAPPING_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait before looking for a gateway again if none was found or a mapping failed.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How long to wait for a response to the `UPnP` gateway discovery.
+const UPNP_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Errors when mapping ports on the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No gateway responded.
+    #[error("no UPnP or NAT-PMP gateway found")]
+    NoGateway,
+    /// The gateway doesn't offer a service to map ports.
+    #[error("gateway does not support port mapping")]
+    UnsupportedGateway,
+    /// The gateway sent a response that can't be decoded.
+    #[error("invalid response from gateway")]
+    InvalidResponse,
+    /// The gateway rejected the request with the given error code.
+    #[error("gateway rejected request with error code {0}")]
+    Rejected(String),
+    /// The gateway mapped a different external port, because the port is already mapped to
+    /// another host.
+    #[error("port is taken, gateway offered port {0} instead")]
+    PortTaken(u16),
+    /// Failed to reach the gateway.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to reach the `UPnP` gateway.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
+
+/// The transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl Protocol {
+    /// Returns the name of the protocol, as used by `UPnP`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tcp => "TCP",
+            Self::Udp => "UDP",
+        }
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A port of this host that should be reachable on the same port of the gateway's external IP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortMapping {
+    /// The transport protocol of the port.
+    pub protocol: Protocol,
+    /// The port.
+    pub port: u16,
+}
+
+impl PortMapping {
+    /// Creates a TCP port mapping.
+    pub const fn tcp(port: u16) -> Self {
+        Self { protocol: Protocol::Tcp, port }
+    }
+
+    /// Creates a UDP port mapping.
+    pub const fn udp(port: u16) -> Self {
+        Self { protocol: Protocol::Udp, port }
+    }
+}
+
+/// A gateway that can map ports.
+#[derive(Debug, Clone)]
+pub enum Gateway {
+    /// A `UPnP` Internet Gateway Device.
+    Upnp(upnp::Gateway),
+    /// A NAT-PMP gateway.
+    NatPmp(natpmp::Gateway),
+}
+
+impl Gateway {
+    /// Discovers the gateway of this host, trying `UPnP` first and NAT-PMP second.
+    pub async fn discover() -> Result<Self, PortMappingError> {
+        match upnp::Gateway::discover(UPNP_DISCOVERY_TIMEOUT).await {
+            Ok(gateway) => Ok(Self::Upnp(gateway)),
+            Err(err) => {
+                debug!(target: "net::nat", %err, "No UPnP gateway found, trying NAT-PMP");
+                natpmp::Gateway::discover().await.map(Self::NatPmp)
+            }
+        }
+    }
+
+    /// Returns the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.external_ip().await,
+            Self::NatPmp(gateway) => gateway.external_ip().await,
+        }
+    }
+
+    /// Maps the port on the gateway for the given lease.
+    ///
+    /// Returns the granted lease, which is [`Duration::ZERO`] if the mapping is permanent.
+    pub async fn add_port(
+        &self,
+        mapping: PortMapping,
+        lease: Duration,
+    ) -> Result<Duration, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.add_port(mapping.protocol, mapping.port, lease).await,
+            Self::NatPmp(gateway) => gateway.add_port(mapping.protocol, mapping.port, lease).await,
+        }
+    }
+
+    /// Removes the mapping of the port on the gateway.
+    pub async fn remove_port(&self, mapping: PortMapping) -> Result<(), PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => gateway.remove_port(mapping.protocol, mapping.port).await,
+            Self::NatPmp(gateway) => gateway.remove_port(mapping.protocol, mapping.port).await,
+        }
+    }
+
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Upnp(_) => "UPnP",
+            Self::NatPmp(_) => "NAT-PMP",
+        }
+    }
+}
+
+/// Maps ports on the gateway and keeps renewing them.
+///
+/// The mappings are renewed after half of the granted lease. If no gateway is found or a mapping
+/// fails, the gateway is looked up again after a while. The mappings are not removed when the
+/// mapper is dropped, but expire with their lease.
+#[derive(Debug, Clone)]
+pub struct PortMapper {
+    mappings: Vec<PortMapping>,
+    lease: Duration,
+}
+
+impl PortMapper {
+    /// Creates a new [`PortMapper`] for the given ports with [`DEFAULT_PORT_MAPPING_LEASE`].
+    pub fn new(mappings: impl IntoIterator<Item = PortMapping>) -> Self {
+        let mut mappings = mappings.into_iter().collect::<Vec<_>>();
+        mappings.dedup();
+        Self { mappings, lease: DEFAULT_PORT_MAPPING_LEASE }
+    }
+
+    /// Sets the lease that is requested for the port mappings.
+    pub const fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    /// Returns the ports that are mapped.
+    pub fn mappings(&self) -> &[PortMapping] {
+        &self.mappings
+    }
+
+    /// Maps the ports and keeps renewing them, this never returns.
+    pub async fn run(self) {
+        let mut warned = false;
+        loop {
+            match Gateway::discover().await {
+                Ok(gateway) => {
+                    let mut mapped = false;
+                    while let Some(lease) = self.map_ports(&gateway, !mapped).await {
+                        mapped = true;
+                        tokio::time::sleep(self.renew_interval(lease)).await;
+                    }
+                }
+                Err(err) if !warned => {
+                    warned = true;
+                    warn!(target: "net::nat", %err, "Failed to discover gateway for port mapping, forward the ports manually or use --nat extip:<IP>");
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", %err, "Failed to discover gateway for port mapping");
+                }
+            }
+            tokio::time::sleep(RETRY_INTERVAL).await;
+        }
+    }
+
+    /// Maps all ports on the gateway and returns the shortest granted lease, or `None` if a
+    /// mapping failed.
+    async fn map_ports(&self, gateway: &Gateway, log: bool) -> Option<Duration> {
+        let mut min_lease = self.lease;
+        for mapping in &self.mappings {
+            match gateway.add_port(*mapping, self.lease).await {
+                Ok(lease) => {
+                    if log {
+                        info!(target: "net::nat", gateway=gateway.kind(), port=mapping.port, protocol=%mapping.protocol, ?lease, "Mapped port on gateway");
+                    }
+                    if !lease.is_zero() {
+                        min_lease = min_lease.min(lease);
+                    }
+                }
+                Err(err) => {
+                    warn!(target: "net::nat", gateway=gateway.kind(), %err, port=mapping.port, protocol=%mapping.protocol, "Failed to map port on gateway, forward the port manually or use --nat extip:<IP>");
+                    return None
+                }
+            }
+        }
+        Some(min_lease)
+    }
+
+    /// Returns when the mappings with the given lease should be renewed.
+    fn renew_interval(&self, lease: Duration) -> Duration {
+        // permanent mappings are checked with the requested lease in case the gateway restarted
+        let lease = if lease.is_zero() { self.lease } else { lease };
+        (lease / 2).max(Duration::from_secs(1))
+    }
+}
+
+/// Attempts to resolve the external IP address via the gateway.
+///
+/// Returns `None` if no gateway was found, or if the gateway's external address is private,
+/// e.g. because the gateway itself is behind a NAT.
+pub async fn resolve_gateway_external_ip() -> Option<IpAddr> {
+    let gateway = Gateway::discover()
+        .await
+        .inspect_err(|err| debug!(target: "net::nat", %err, "Failed to discover gateway"))
+        .ok()?;
+    let ip = gateway
+        .external_ip()
+        .await
+        .inspect_err(
+            |err| debug!(target: "net::nat", %err, "Failed to get external IP from gateway"),
+        )
+        .ok()?;
+    match ip {
+        IpAddr::V4(v4) if v4.is_private() || v4.is_unspecified() || is_shared(v4) => {
+            debug!(target: "net::nat", %ip, "Gateway has no public IP");
+            None
+        }
+        ip => Some(ip),
+    }
+}
+
+/// Returns whether the address is in the shared address space of carrier-grade NATs
+/// (`100.64.0.0/10`).
+const fn is_shared(ip: Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    octets[0] == 100 && (octets[1] & 0b1100_0000) == 0b0100_0000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renew_at_half_lease() {
+        let mapper = PortMapper::new([PortMapping::tcp(30303), PortMapping::udp(30303)]);
+        assert_eq!(mapper.renew_interval(Duration::from_secs(600)), Duration::from_secs(300));
+        // permanent leases are renewed at half of the requested lease
+        assert_eq!(mapper.renew_interval(Duration::ZERO), DEFAULT_PORT_MAPPING_LEASE / 2);
+        assert_eq!(mapper.renew_interval(Duration::from_secs(1)), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn dedup_mappings() {
+        let mapper = PortMapper::new([PortMapping::tcp(30303), PortMapping::tcp(30303)]);
+        assert_eq!(mapper.mappings(), &[PortMapping::tcp(30303)]);
+    }
+
+    #[test]
+    fn shared_address_space() {
+        assert!(is_shared(Ipv4Addr::new(100, 64, 0, 1)));
+        assert!(is_shared(Ipv4Addr::new(100, 127, 255, 255)));
+        assert!(!is_shared(Ipv4Addr::new(100, 128, 0, 1)));
+        assert!(!is_shared(Ipv4Addr::new(203, 0, 113, 7)));
+    }
+}
@@ -0,0 +1,289 @@
+//! Port mapping via `UPnP` Internet Gateway Devices (IGD).
+//!
+//! The gateway is discovered via SSDP, its device description is fetched from the advertised
+//! location and the port mappings are managed with SOAP requests to the control URL of its
+//! `WANIPConnection` or `WANPPPConnection` service.
+
+use crate::port_mapping::{PortMappingError, Protocol};
+use reqwest::Url;
+use std::{
+    fmt::Write,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+use tracing::trace;
+
+/// The SSDP multicast address gateways listen on.
+const SSDP_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250)), 1900);
+
+/// The SSDP search request for Internet Gateway Devices.
+const SSDP_SEARCH: &str = "M-SEARCH * HTTP/1.1\r\n\
+    HOST: 239.255.255.250:1900\r\n\
+    ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+    MAN: \"ssdp:discover\"\r\n\
+    MX: 2\r\n\r\n";
+
+/// The services of a gateway that can map ports, in order of preference.
+const WAN_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// The SOAP error code of gateways that only support permanent leases.
+const ONLY_PERMANENT_LEASES_SUPPORTED: &str = "725";
+
+/// The description of the port mappings on the gateway.
+const PORT_MAPPING_DESCRIPTION: &str = "reth";
+
+/// A `UPnP` Internet Gateway Device.
+#[derive(Debug, Clone)]
+pub struct Gateway {
+    /// The URL of the SOAP endpoint of the WAN connection service.
+    control_url: Url,
+    /// The type of the WAN connection service.
+    service: &'static str,
+    /// The address of this host on the gateway's network.
+    local_ip: Ipv4Addr,
+}
+
+impl Gateway {
+    /// Discovers a gateway on the local network via SSDP, waiting at most `timeout` for a
+    /// response.
+    pub async fn discover(timeout: Duration) -> Result<Self, PortMappingError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+        socket.send_to(SSDP_SEARCH.as_bytes(), SSDP_ADDR).await?;
+
+        let mut buf = [0u8; 1536];
+        let (len, gateway_addr) = tokio::time::timeout(timeout, socket.recv_from(&mut buf))
+            .await
+            .map_err(|_| PortMappingError::NoGateway)??;
+        let response = String::from_utf8_lossy(&buf[..len]);
+        let location = parse_ssdp_location(&response).ok_or(PortMappingError::NoGateway)?;
+        trace!(target: "net::nat", %gateway_addr, %location, "Discovered UPnP gateway");
+
+        let location = Url::parse(location).map_err(|_| PortMappingError::NoGateway)?;
+        let description = reqwest::get(location.clone()).await?.error_for_status()?.text().await?;
+        let (service, control_url) =
+            parse_control_url(&description).ok_or(PortMappingError::UnsupportedGateway)?;
+        let control_url =
+            location.join(control_url).map_err(|_| PortMappingError::UnsupportedGateway)?;
+
+        Ok(Self { control_url, service, local_ip: local_ip_for(gateway_addr).await? })
+    }
+
+    /// Returns the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.request("GetExternalIPAddress", &[]).await?;
+        parse_element(&response, "NewExternalIPAddress")
+            .and_then(|ip| ip.trim().parse().ok())
+            .ok_or(PortMappingError::InvalidResponse)
+    }
+
+    /// Maps the external `port` to the same port of this host for the given lease.
+    ///
+    /// Returns the granted lease, which is [`Duration::ZERO`] if the gateway only supports
+    /// permanent mappings.
+    pub async fn add_port(
+        &self,
+        protocol: Protocol,
+        port: u16,
+        lease: Duration,
+    ) -> Result<Duration, PortMappingError> {
+        match self.add_port_with_lease(protocol, port, lease).await {
+            Err(PortMappingError::Rejected(code)) if code == ONLY_PERMANENT_LEASES_SUPPORTED => {
+                self.add_port_with_lease(protocol, port, Duration::ZERO).await?;
+                Ok(Duration::ZERO)
+            }
+            res => res.map(|_| lease),
+        }
+    }
+
+    /// Removes the mapping of the external `port`.
+    pub async fn remove_port(&self, protocol: Protocol, port: u16) -> Result<(), PortMappingError> {
+        let port = port.to_string();
+        self.request(
+            "DeletePortMapping",
+            &[
+                ("NewRemoteHost", ""),
+                ("NewExternalPort", &port),
+                ("NewProtocol", protocol.as_str()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn add_port_with_lease(
+        &self,
+        protocol: Protocol,
+        port: u16,
+        lease: Duration,
+    ) -> Result<(), PortMappingError> {
+        let port = port.to_string();
+        let local_ip = self.local_ip.to_string();
+        let lease = lease.as_secs().to_string();
+        self.request(
+            "AddPortMapping",
+            &[
+                ("NewRemoteHost", ""),
+                ("NewExternalPort", &port),
+                ("NewProtocol", protocol.as_str()),
+                ("NewInternalPort", &port),
+                ("NewInternalClient", &local_ip),
+                ("NewEnabled", "1"),
+                ("NewPortMappingDescription", PORT_MAPPING_DESCRIPTION),
+                ("NewLeaseDuration", &lease),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Invokes the `action` of the WAN connection service and returns the response body.
+    async fn request(
+        &self,
+        action: &str,
+        args: &[(&str, &str)],
+    ) -> Result<String, PortMappingError> {
+        let response = reqwest::Client::new()
+            .post(self.control_url.clone())
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{action}\"", self.service))
+            .body(soap_envelope(self.service, action, args))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            let code = parse_element(&body, "errorCode").unwrap_or_else(|| status.as_str());
+            return Err(PortMappingError::Rejected(code.trim().to_string()))
+        }
+        Ok(body)
+    }
+}
+
+/// Returns the address of this host that is used to reach the gateway.
+async fn local_ip_for(gateway: SocketAddr) -> Result<Ipv4Addr, PortMappingError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect(gateway).await?;
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(ip) => Ok(ip),
+        IpAddr::V6(_) => Err(PortMappingError::NoGateway),
+    }
+}
+
+/// Returns the `LOCATION` header of an SSDP response.
+fn parse_ssdp_location(response: &str) -> Option<&str> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim())
+    })
+}
+
+/// Returns the preferred WAN connection service and its control URL from a device description.
+fn parse_control_url(description: &str) -> Option<(&'static str, &str)> {
+    WAN_SERVICES.iter().find_map(|service| {
+        let start = description.find(&format!("<serviceType>{service}</serviceType>"))?;
+        let control_url = parse_element(&description[start..], "controlURL")?;
+        Some((*service, control_url.trim()))
+    })
+}
+
+/// Returns the content of the first element with the given name.
+fn parse_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = xml[start..].find(&format!("</{name}>"))?;
+    Some(&xml[start..start + end])
+}
+
+/// Returns the SOAP request body that invokes the `action` of the `service`.
+fn soap_envelope(service: &str, action: &str, args: &[(&str, &str)]) -> String {
+    let mut body = String::from(
+        "<?xml version=\"1.0\"?>\
+        <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+        s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\"><s:Body>",
+    );
+    let _ = write!(body, "<u:{action} xmlns:u=\"{service}\">");
+    for (name, value) in args {
+        let _ = write!(body, "<{name}>{value}</{name}>");
+    }
+    let _ = write!(body, "</u:{action}></s:Body></s:Envelope>");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ssdp_response() {
+        let response = "HTTP/1.1 200 OK\r\n\
+            CACHE-CONTROL: max-age=120\r\n\
+            ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+            Location: http://192.168.1.1:5000/rootDesc.xml\r\n\r\n";
+        assert_eq!(parse_ssdp_location(response), Some("http://192.168.1.1:5000/rootDesc.xml"));
+        assert_eq!(parse_ssdp_location("HTTP/1.1 200 OK\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn parse_device_description() {
+        let description = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:WANCommonInterfaceConfig:1</serviceType>
+        <controlURL>/ctl/CmnIfCfg</controlURL>
+      </service>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+        <controlURL>/ctl/IPConn</controlURL>
+      </service>
+    </serviceList>
+  </device>
+</root>"#;
+        let (service, control_url) = parse_control_url(description).unwrap();
+        assert_eq!(service, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(control_url, "/ctl/IPConn");
+
+        let location = Url::parse("http://192.168.1.1:5000/rootDesc.xml").unwrap();
+        assert_eq!(
+            location.join(control_url).unwrap().as_str(),
+            "http://192.168.1.1:5000/ctl/IPConn"
+        );
+
+        assert_eq!(parse_control_url("<root></root>"), None);
+    }
+
+    #[test]
+    fn parse_soap_response() {
+        let response = r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body>
+<u:GetExternalIPAddressResponse xmlns:u="urn:schemas-upnp-org:service:WANIPConnection:1">
+<NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>
+</u:GetExternalIPAddressResponse></s:Body></s:Envelope>"#;
+        assert_eq!(parse_element(response, "NewExternalIPAddress"), Some("203.0.113.7"));
+
+        let fault = "<s:Fault><detail><UPnPError><errorCode>725</errorCode>\
+            <errorDescription>OnlyPermanentLeasesSupported</errorDescription></UPnPError>\
+            </detail></s:Fault>";
+        assert_eq!(parse_element(fault, "errorCode"), Some(ONLY_PERMANENT_LEASES_SUPPORTED));
+    }
+
+    #[test]
+    fn encode_soap_request() {
+        let body = soap_envelope(
+            WAN_SERVICES[1],
+            "DeletePortMapping",
+            &[("NewExternalPort", "30303"), ("NewProtocol", "TCP")],
+        );
+        assert!(body.contains(
+            "<u:DeletePortMapping xmlns:u=\"urn:schemas-upnp-org:service:WANIPConnection:1\">\
+            <NewExternalPort>30303</NewExternalPort><NewProtocol>TCP</NewProtocol>\
+            </u:DeletePortMapping>"
+        ));
+    }
+}
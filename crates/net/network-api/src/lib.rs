@@ -24,7 +24,9 @@ pub mod test_utils;
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{
+    PeerDiagnostics, PeerKind, PeerReputationRecord, Reputation, ReputationChangeKind,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -208,6 +210,20 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Get the reputation and ban of the given peer, or of all peers that were penalized or are
+    /// banned temporarily if no peer is given.
+    fn peer_reputations(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> impl Future<Output = Result<Vec<PeerReputationRecord>, NetworkError>> + Send;
+
+    /// Get the diagnostics of the given peer, or of all connected peers and all peers that
+    /// disconnected recently if no peer is given.
+    fn peer_diagnostics(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> impl Future<Output = Result<Vec<PeerDiagnostics>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -9,7 +9,9 @@ use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
 use reth_network_peers::NodeRecord;
-use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{
+    PeerDiagnostics, PeerKind, PeerReputationRecord, Reputation, ReputationChangeKind,
+};
 
 use crate::{NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, Peers, PeersInfo};
 
@@ -116,4 +118,18 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn peer_reputations(
+        &self,
+        _peer_id: Option<PeerId>,
+    ) -> Result<Vec<PeerReputationRecord>, NetworkError> {
+        Ok(Vec::new())
+    }
+
+    async fn peer_diagnostics(
+        &self,
+        _peer_id: Option<PeerId>,
+    ) -> Result<Vec<PeerDiagnostics>, NetworkError> {
+        Ok(Vec::new())
+    }
 }
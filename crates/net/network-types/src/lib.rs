@@ -19,14 +19,18 @@ pub mod session;
 /// [`BackoffKind`] definition.
 mod backoff;
 
-pub use peers::reputation::{Reputation, ReputationChangeKind, ReputationChangeWeights};
+pub use peers::reputation::{
+    PeerReputationRecord, Reputation, ReputationChangeKind, ReputationChangeWeights,
+    ReputationDecay,
+};
 
 pub use backoff::BackoffKind;
 pub use peers::{
     addr::PeerAddr,
+    diagnostics::{DisconnectRecord, PeerDiagnostics, SessionDiagnostics, MAX_DISCONNECT_HISTORY},
     kind::PeerKind,
     reputation::{is_banned_reputation, ReputationChangeOutcome, DEFAULT_REPUTATION},
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    ConnectionsConfig, Peer, PeerReconnectBackoff, PeersConfig,
 };
-pub use session::{SessionLimits, SessionsConfig};
+pub use session::{BandwidthLimits, SessionLimits, SessionsConfig};
@@ -11,7 +11,7 @@ use reth_net_banlist::BanList;
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PeerReputationRecord, ReputationChangeWeights, ReputationDecay};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -95,6 +95,52 @@ impl Default for PeerBackoffDurations {
     }
 }
 
+/// The delays before redialing trusted and static peers that we failed to connect to.
+///
+/// These peers are never dropped from the peer set, instead the delay doubles with every failed
+/// attempt, starting at [`Self::initial`] and capped at [`Self::max`], until a session is
+/// established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerReconnectBackoff {
+    /// The delay after the first failed attempt.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub initial: Duration,
+    /// The maximum delay.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub max: Duration,
+}
+
+impl PeerReconnectBackoff {
+    /// Returns the delay after the given number of failed attempts.
+    pub fn backoff(&self, attempts: u8) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        self.initial.saturating_mul(1 << exponent).min(self.max)
+    }
+
+    /// Returns the timestamp until which we should wait after the given number of failed
+    /// attempts.
+    pub fn backoff_until(&self, attempts: u8) -> std::time::Instant {
+        std::time::Instant::now() + self.backoff(attempts)
+    }
+
+    /// Returns durations for testing.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub const fn test() -> Self {
+        Self { initial: Duration::from_millis(100), max: Duration::from_millis(200) }
+    }
+}
+
+impl Default for PeerReconnectBackoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(5),
+            // 5min
+            max: Duration::from_secs(60 * 5),
+        }
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -154,10 +200,20 @@ pub struct PeersConfig {
     pub connection_info: ConnectionsConfig,
     /// How to weigh reputation changes.
     pub reputation_weights: ReputationChangeWeights,
+    /// How the penalties of peers decay over time.
+    pub reputation_decay: ReputationDecay,
+    /// Reputations and bans of peers to restore at launch, e.g. from a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub peer_reputations: Vec<PeerReputationRecord>,
     /// How long to backoff peers that we are failed to connect to for non-fatal reasons.
     ///
     /// The backoff duration increases with number of backoff attempts.
     pub backoff_durations: PeerBackoffDurations,
+    /// How long to wait before redialing trusted and static peers that we failed to connect to.
+    ///
+    /// This replaces the [`PeerBackoffDurations`] for these peers, so that they are reconnected
+    /// quickly, e.g. after a restart.
+    pub reconnect_backoff: PeerReconnectBackoff,
     /// How long to temporarily ban ips on incoming connection attempts.
     ///
     /// This acts as an IP based rate limit.
@@ -171,10 +227,13 @@ impl Default for PeersConfig {
             refill_slots_interval: Duration::from_millis(5_000),
             connection_info: Default::default(),
             reputation_weights: Default::default(),
+            reputation_decay: Default::default(),
+            peer_reputations: Default::default(),
             ban_list: Default::default(),
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
             backoff_durations: Default::default(),
+            reconnect_backoff: Default::default(),
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
@@ -256,20 +315,35 @@ impl PeersConfig {
     }
 
     /// Configures how to weigh reputation changes.
-    pub const fn with_reputation_weights(
-        mut self,
-        reputation_weights: ReputationChangeWeights,
-    ) -> Self {
+    pub fn with_reputation_weights(mut self, reputation_weights: ReputationChangeWeights) -> Self {
         self.reputation_weights = reputation_weights;
         self
     }
 
+    /// Configures how the penalties of peers decay over time.
+    pub const fn with_reputation_decay(mut self, reputation_decay: ReputationDecay) -> Self {
+        self.reputation_decay = reputation_decay;
+        self
+    }
+
+    /// Reputations and bans of peers to restore at launch.
+    pub fn with_peer_reputations(mut self, peer_reputations: Vec<PeerReputationRecord>) -> Self {
+        self.peer_reputations = peer_reputations;
+        self
+    }
+
     /// Configures how long to backoff peers that are we failed to connect to for non-fatal reasons
     pub const fn with_backoff_durations(mut self, backoff_durations: PeerBackoffDurations) -> Self {
         self.backoff_durations = backoff_durations;
         self
     }
 
+    /// Configures how long to wait before redialing trusted and static peers.
+    pub const fn with_reconnect_backoff(mut self, reconnect_backoff: PeerReconnectBackoff) -> Self {
+        self.reconnect_backoff = reconnect_backoff;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -291,12 +365,30 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Read from file the reputations and bans of peers to restore at launch. Ignored if None.
+    #[cfg(feature = "serde")]
+    pub fn with_peer_reputations_from_file(
+        self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peer reputations");
+        let peer_reputations: Vec<PeerReputationRecord> = serde_json::from_reader(reader)?;
+        Ok(self.with_peer_reputations(peer_reputations))
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
         Self {
             refill_slots_interval: Duration::from_millis(100),
             backoff_durations: PeerBackoffDurations::test(),
+            reconnect_backoff: PeerReconnectBackoff::test(),
             ban_duration: Duration::from_millis(200),
             ..Default::default()
         }
@@ -0,0 +1,70 @@
+//! Diagnostics of peers for debugging peering problems.
+
+use std::net::SocketAddr;
+
+use alloy_primitives::B256;
+use reth_network_peers::PeerId;
+
+use crate::PeerKind;
+
+/// The maximum number of disconnects that are remembered per peer.
+pub const MAX_DISCONNECT_HISTORY: usize = 8;
+
+/// The diagnostics of a peer, see also `admin_peerDiagnostics`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerDiagnostics {
+    /// The id of the peer.
+    pub peer_id: PeerId,
+    /// The active session to the peer, if the peer is connected.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub session: Option<SessionDiagnostics>,
+    /// The most recent disconnects of the peer, oldest first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disconnects: Vec<DisconnectRecord>,
+}
+
+/// The diagnostics of an active session to a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SessionDiagnostics {
+    /// The client's name and version.
+    pub client_version: String,
+    /// The peer's address we're connected to.
+    pub remote_addr: SocketAddr,
+    /// Whether the peer connected to us.
+    pub inbound: bool,
+    /// The peer's connection kind.
+    pub kind: PeerKind,
+    /// The capabilities the peer announced, e.g. `eth/68`.
+    pub capabilities: Vec<String>,
+    /// The negotiated eth version.
+    pub eth_version: u8,
+    /// The smoothed round trip time of requests to the peer in milliseconds, if any request
+    /// completed.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub latency_ms: Option<u64>,
+    /// The hash of the best block the peer announced.
+    pub best_hash: B256,
+    /// The number of the best block the peer announced, if known.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub best_number: Option<u64>,
+    /// For how long the session has been established in seconds.
+    pub connected_secs: u64,
+    /// The total size of the `eth` messages received from the peer.
+    pub bytes_in: u64,
+    /// The total size of the `eth` messages sent to the peer.
+    pub bytes_out: u64,
+}
+
+/// A disconnect of a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisconnectRecord {
+    /// When the peer disconnected as unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Why the peer disconnected, e.g. the disconnect reason or the error that closed the session.
+    pub reason: String,
+}
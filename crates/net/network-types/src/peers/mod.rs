@@ -1,11 +1,16 @@
 pub mod addr;
 pub mod config;
+pub mod diagnostics;
 pub mod kind;
 pub mod reputation;
 pub mod state;
 
-pub use config::{ConnectionsConfig, PeersConfig};
-pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use config::{ConnectionsConfig, PeerReconnectBackoff, PeersConfig};
+pub use diagnostics::{DisconnectRecord, PeerDiagnostics, SessionDiagnostics};
+pub use reputation::{
+    PeerReputationRecord, Reputation, ReputationChange, ReputationChangeKind,
+    ReputationChangeWeights, ReputationDecay,
+};
 
 use reth_ethereum_forks::ForkId;
 use tracing::trace;
@@ -35,6 +40,9 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Counts the failed attempts to connect to a trusted or static peer since the last
+    /// established session.
+    pub reconnect_attempts: u8,
 }
 
 // === impl Peer ===
@@ -66,6 +74,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            reconnect_attempts: 0,
         }
     }
 
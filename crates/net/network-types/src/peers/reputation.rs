@@ -1,5 +1,9 @@
 //! Peer reputation management
 
+use std::{collections::BTreeMap, time::Duration};
+
+use reth_network_peers::PeerId;
+
 /// The default reputation of a peer
 pub const DEFAULT_REPUTATION: Reputation = 0;
 
@@ -35,6 +39,12 @@ const BAD_PROTOCOL_REPUTATION_CHANGE: i32 = i32::MIN;
 // todo: current value is a hint, needs to be set properly
 const BAD_ANNOUNCEMENT_REPUTATION_CHANGE: i32 = REPUTATION_UNIT;
 
+/// The reputation that is restored for penalized peers per [`DEFAULT_REPUTATION_DECAY_INTERVAL`].
+const DEFAULT_REPUTATION_DECAY: i32 = -REPUTATION_UNIT;
+
+/// How often the reputation of penalized peers is restored by default.
+pub const DEFAULT_REPUTATION_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
 /// The maximum reputation change that can be applied to a trusted peer.
 /// This is used to prevent a single bad message from a trusted peer to cause a significant change.
 /// This gives a trusted peer more leeway when interacting with the node, which is useful for in
@@ -75,6 +85,11 @@ pub enum ReputationChangeKind {
     /// > originally). This is usually achieved by remembering a set of transaction hashes recently
     /// > relayed by the peer.
     AlreadySeenTransaction,
+    /// Peer sent a bad message on an additional `RLPx` sub-protocol, e.g. a custom protocol.
+    ///
+    /// The name of the protocol determines the penalty, see
+    /// [`ReputationChangeWeights::subprotocols`].
+    BadSubprotocolMessage(&'static str),
     /// Peer failed to respond in time.
     Timeout,
     /// Peer does not adhere to network protocol rules.
@@ -124,6 +139,11 @@ pub struct ReputationChangeWeights {
     pub dropped: Reputation,
     /// Weight for [`ReputationChangeKind::BadAnnouncement`]
     pub bad_announcement: Reputation,
+    /// Weight for [`ReputationChangeKind::BadSubprotocolMessage`] of protocols without a weight in
+    /// [`Self::subprotocols`]
+    pub bad_subprotocol_message: Reputation,
+    /// Weights for [`ReputationChangeKind::BadSubprotocolMessage`] by the name of the protocol
+    pub subprotocols: BTreeMap<String, Reputation>,
 }
 
 // === impl ReputationChangeWeights ===
@@ -141,6 +161,8 @@ impl ReputationChangeWeights {
             failed_to_connect: 0,
             dropped: 0,
             bad_announcement: 0,
+            bad_subprotocol_message: 0,
+            subprotocols: BTreeMap::new(),
         }
     }
 
@@ -159,6 +181,12 @@ impl ReputationChangeWeights {
             ReputationChangeKind::Reset => DEFAULT_REPUTATION.into(),
             ReputationChangeKind::Other(val) => val.into(),
             ReputationChangeKind::BadAnnouncement => self.bad_announcement.into(),
+            ReputationChangeKind::BadSubprotocolMessage(protocol) => self
+                .subprotocols
+                .get(protocol)
+                .copied()
+                .unwrap_or(self.bad_subprotocol_message)
+                .into(),
         }
     }
 }
@@ -175,10 +203,64 @@ impl Default for ReputationChangeWeights {
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
             dropped: REMOTE_DISCONNECT_REPUTATION_CHANGE,
             bad_announcement: BAD_ANNOUNCEMENT_REPUTATION_CHANGE,
+            bad_subprotocol_message: BAD_MESSAGE_REPUTATION_CHANGE,
+            subprotocols: BTreeMap::new(),
+        }
+    }
+}
+
+/// How the penalties of peers decay over time.
+///
+/// Unlike the reward for the time a peer is connected, this also applies to peers that are not
+/// connected, so that peers which were penalized in the past eventually get another chance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReputationDecay {
+    /// How often the reputation of penalized peers is restored.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub interval: Duration,
+    /// How much reputation is restored per interval, up to the default reputation.
+    ///
+    /// Decay is disabled if this is `0`.
+    pub amount: Reputation,
+}
+
+impl ReputationDecay {
+    /// Returns the reputation after one interval of decay.
+    pub const fn apply(&self, reputation: Reputation) -> Reputation {
+        if reputation >= DEFAULT_REPUTATION {
+            return reputation
         }
+        let decayed = reputation.saturating_add(self.amount);
+        if decayed > DEFAULT_REPUTATION {
+            DEFAULT_REPUTATION
+        } else {
+            decayed
+        }
+    }
+}
+
+impl Default for ReputationDecay {
+    fn default() -> Self {
+        Self { interval: DEFAULT_REPUTATION_DECAY_INTERVAL, amount: DEFAULT_REPUTATION_DECAY }
     }
 }
 
+/// The reputation and ban of a peer, e.g. to persist them across restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerReputationRecord {
+    /// The id of the peer.
+    pub peer_id: PeerId,
+    /// The reputation of the peer.
+    pub reputation: Reputation,
+    /// Until when the peer is banned as unix timestamp in seconds, if it's banned temporarily.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub banned_until: Option<u64>,
+}
+
 /// Represents a change in a peer's reputation.
 #[derive(Debug, Copy, Clone, Default)]
 pub struct ReputationChange(Reputation);
@@ -217,3 +299,32 @@ pub enum ReputationChangeOutcome {
     /// Unban the peer
     Unban,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reputation_decay() {
+        let decay = ReputationDecay { interval: Duration::from_secs(1), amount: 100 };
+        assert_eq!(decay.apply(-250), -150);
+        assert_eq!(decay.apply(-50), DEFAULT_REPUTATION);
+        assert_eq!(decay.apply(50), 50);
+        assert_eq!(decay.apply(i32::MIN), i32::MIN + 100);
+    }
+
+    #[test]
+    fn subprotocol_weights() {
+        let mut weights = ReputationChangeWeights::default();
+        weights.subprotocols.insert("snap".to_string(), BANNED_REPUTATION);
+
+        assert_eq!(
+            weights.change(ReputationChangeKind::BadSubprotocolMessage("snap")).as_i32(),
+            BANNED_REPUTATION
+        );
+        assert_eq!(
+            weights.change(ReputationChangeKind::BadSubprotocolMessage("custom")).as_i32(),
+            BAD_MESSAGE_REPUTATION_CHANGE
+        );
+    }
+}
@@ -50,6 +50,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// Bandwidth limits of the sessions.
+    ///
+    /// By default, the bandwidth is not limited.
+    pub bandwidth: BandwidthLimits,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +73,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            bandwidth: Default::default(),
         }
     }
 }
@@ -100,6 +105,12 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Sets the bandwidth limits of the sessions.
+    pub const fn with_bandwidth_limits(mut self, bandwidth: BandwidthLimits) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
 }
 
 /// Limits for sessions.
@@ -144,6 +155,65 @@ impl SessionLimits {
     }
 }
 
+/// Bandwidth limits of all sessions, in bytes per second.
+///
+/// The total limits are shared by all sessions, whichever session is ready first gets the
+/// available bandwidth. The per peer limits ensure that a single peer, e.g. one that is
+/// downloading the history from this node, can't use all of it.
+///
+/// By default, the bandwidth is not limited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BandwidthLimits {
+    /// Maximum bytes per second sent to all peers.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_upload: Option<u64>,
+    /// Maximum bytes per second received from all peers.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_download: Option<u64>,
+    /// Maximum bytes per second sent to a single peer.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_peer_upload: Option<u64>,
+    /// Maximum bytes per second received from a single peer.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_peer_download: Option<u64>,
+}
+
+impl BandwidthLimits {
+    /// Sets the maximum bytes per second sent to all peers.
+    pub const fn with_max_upload(mut self, limit: u64) -> Self {
+        self.max_upload = Some(limit);
+        self
+    }
+
+    /// Sets the maximum bytes per second received from all peers.
+    pub const fn with_max_download(mut self, limit: u64) -> Self {
+        self.max_download = Some(limit);
+        self
+    }
+
+    /// Sets the maximum bytes per second sent to a single peer.
+    pub const fn with_max_peer_upload(mut self, limit: u64) -> Self {
+        self.max_peer_upload = Some(limit);
+        self
+    }
+
+    /// Sets the maximum bytes per second received from a single peer.
+    pub const fn with_max_peer_download(mut self, limit: u64) -> Self {
+        self.max_peer_download = Some(limit);
+        self
+    }
+
+    /// Returns `true` if no limit is set.
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_upload.is_none() &&
+            self.max_download.is_none() &&
+            self.max_peer_upload.is_none() &&
+            self.max_peer_download.is_none()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
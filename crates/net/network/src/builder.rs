@@ -69,6 +69,9 @@ impl<Tx, Eth> NetworkBuilder<Tx, Eth> {
     }
 
     /// Creates a new [`EthRequestHandler`] and wires it to the network.
+    ///
+    /// The handler serves headers and bodies as configured by
+    /// [`NetworkConfig::eth_requests_config`](crate::NetworkConfig::eth_requests_config).
     pub fn request_handler<Client>(
         self,
         client: Client,
@@ -77,7 +80,8 @@ impl<Tx, Eth> NetworkBuilder<Tx, Eth> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let request_handler = EthRequestHandler::new(client, peers, rx)
+            .with_config(network.eth_requests_config().clone());
         NetworkBuilder { network, request_handler, transactions }
     }
 }
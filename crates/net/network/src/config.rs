@@ -18,6 +18,7 @@ use secp256k1::SECP256K1;
 
 use crate::{
     error::NetworkError,
+    eth_requests::EthRequestHandlerConfig,
     import::{BlockImport, ProofOfStakeBlockImport},
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
@@ -83,6 +84,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// How headers and bodies are served to peers.
+    pub eth_requests_config: EthRequestHandlerConfig,
     /// The NAT resolver for external IP
     pub nat: Option<NatResolver>,
 }
@@ -202,6 +205,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     block_import: Option<Box<dyn BlockImport<N::Block>>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// How headers and bodies are served to peers.
+    eth_requests_config: EthRequestHandlerConfig,
     /// The NAT resolver for external IP
     nat: Option<NatResolver>,
 }
@@ -235,6 +240,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            eth_requests_config: Default::default(),
             nat: None,
         }
     }
@@ -314,6 +320,12 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Configures how headers and bodies are served to peers.
+    pub const fn eth_requests_config(mut self, config: EthRequestHandlerConfig) -> Self {
+        self.eth_requests_config = config;
+        self
+    }
+
     /// Sets the discovery and listener address
     ///
     /// This is a convenience function for both [`NetworkConfigBuilder::listener_addr`] and
@@ -536,6 +548,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            eth_requests_config,
             nat,
         } = self;
 
@@ -603,6 +616,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             fork_filter,
             tx_gossip_disabled,
             transactions_manager_config,
+            eth_requests_config,
             nat,
         }
     }
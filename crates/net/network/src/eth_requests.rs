@@ -1,10 +1,11 @@
 //! Blocks/Headers management for the p2p network.
 
 use std::{
+    collections::{HashMap, VecDeque},
     future::Future,
     pin::Pin,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use alloy_consensus::Header;
@@ -24,8 +25,8 @@ use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
 
 use crate::{
-    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
-    metrics::EthRequestHandlerMetrics,
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, builder::ETH_REQUEST_CHANNEL_CAPACITY,
+    metered_poll_nested_stream_with_budget, metrics::EthRequestHandlerMetrics,
 };
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
@@ -49,6 +50,57 @@ const MAX_BODIES_SERVE: usize = 1024;
 /// Maximum size of replies to data retrievals.
 const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 
+/// Default number of blocks below the head that are considered recent.
+pub const DEFAULT_RECENT_BLOCKS: u64 = 1024;
+
+/// Default interval after which the history budget of a peer is reset.
+pub const DEFAULT_HISTORY_BUDGET_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Policies for serving block headers and bodies to peers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EthRequestHandlerConfig {
+    /// Number of blocks below the head that are considered recent.
+    ///
+    /// Requests that start at a recent block are served before requests for older blocks, and
+    /// recent headers and bodies don't count towards the history budget of a peer.
+    pub recent_blocks: u64,
+    /// Max number of headers and bodies older than the recent blocks that are served to a single
+    /// peer per [`history_budget_interval`](Self::history_budget_interval).
+    ///
+    /// `None` serves the history without limits.
+    pub max_history_per_peer: Option<u64>,
+    /// Interval after which the history budget of a peer is reset.
+    pub history_budget_interval: Duration,
+    /// The earliest block whose header and body are served, e.g. because older blocks were
+    /// pruned.
+    ///
+    /// Requests for older blocks are answered without them, and the block is advertised as the
+    /// earliest available block to `eth/69` peers.
+    pub earliest_block: u64,
+}
+
+impl Default for EthRequestHandlerConfig {
+    fn default() -> Self {
+        Self {
+            recent_blocks: DEFAULT_RECENT_BLOCKS,
+            max_history_per_peer: None,
+            history_budget_interval: DEFAULT_HISTORY_BUDGET_INTERVAL,
+            earliest_block: 0,
+        }
+    }
+}
+
+/// The headers and bodies older than the recent blocks served to a peer in the current interval.
+#[derive(Debug, Clone, Copy)]
+struct HistoryBudget {
+    /// When the current interval started.
+    interval_start: Instant,
+    /// The number of historical headers and bodies served in the current interval.
+    served: u64,
+}
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -65,6 +117,12 @@ pub struct EthRequestHandler<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     incoming_requests: ReceiverStream<IncomingEthRequest<N>>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// Policies for serving headers and bodies.
+    config: EthRequestHandlerConfig,
+    /// The history served to peers in the current interval.
+    history_budgets: HashMap<PeerId, HistoryBudget>,
+    /// Requests for historical blocks, served once there are no more recent requests.
+    history_requests: VecDeque<IncomingEthRequest<N>>,
 }
 
 // === impl EthRequestHandler ===
@@ -76,6 +134,43 @@ impl<C, N: NetworkPrimitives> EthRequestHandler<C, N> {
             peers,
             incoming_requests: ReceiverStream::new(incoming),
             metrics: Default::default(),
+            config: Default::default(),
+            history_budgets: Default::default(),
+            history_requests: Default::default(),
+        }
+    }
+
+    /// Sets the policies for serving headers and bodies.
+    pub const fn with_config(mut self, config: EthRequestHandlerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the number of historical headers and bodies that can still be served to the peer.
+    fn history_budget(&mut self, peer_id: PeerId) -> u64 {
+        let Some(max_history) = self.config.max_history_per_peer else { return u64::MAX };
+
+        let now = Instant::now();
+        let interval = self.config.history_budget_interval;
+        if self.history_budgets.len() > ETH_REQUEST_CHANNEL_CAPACITY {
+            // forget peers whose interval is over, e.g. because they disconnected
+            self.history_budgets.retain(|_, budget| now - budget.interval_start < interval);
+        }
+
+        let budget = self
+            .history_budgets
+            .entry(peer_id)
+            .or_insert(HistoryBudget { interval_start: now, served: 0 });
+        if now - budget.interval_start >= interval {
+            *budget = HistoryBudget { interval_start: now, served: 0 };
+        }
+        max_history.saturating_sub(budget.served)
+    }
+
+    /// Records historical headers or bodies served to the peer.
+    fn on_history_served(&mut self, peer_id: PeerId, served: u64) {
+        if let Some(budget) = self.history_budgets.get_mut(&peer_id) {
+            budget.served += served;
         }
     }
 }
@@ -84,8 +179,39 @@ impl<C> EthRequestHandler<C>
 where
     C: BlockReader + HeaderProvider + ReceiptProvider,
 {
+    /// Returns the first block that is considered recent.
+    fn recent_start(&self) -> u64 {
+        self.client
+            .best_block_number()
+            .unwrap_or_default()
+            .saturating_sub(self.config.recent_blocks)
+    }
+
+    /// Returns `true` if the request starts at a block older than the recent blocks.
+    fn is_history_request(&self, request: &IncomingEthRequest) -> bool {
+        let start = match request {
+            IncomingEthRequest::GetBlockHeaders { request, .. } => match request.start_block {
+                BlockHashOrNumber::Number(num) => Some(num),
+                BlockHashOrNumber::Hash(hash) => self.client.block_number(hash).unwrap_or_default(),
+            },
+            IncomingEthRequest::GetBlockBodies { request, .. } => request
+                .0
+                .first()
+                .and_then(|hash| self.client.block_number(*hash).unwrap_or_default()),
+            IncomingEthRequest::GetNodeData { .. } | IncomingEthRequest::GetReceipts { .. } => None,
+        };
+        start.is_some_and(|start| start < self.recent_start())
+    }
+
     /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
+    ///
+    /// At most `history_budget` headers older than `recent_start` are included.
+    fn get_headers_response(
+        &self,
+        request: GetBlockHeaders,
+        recent_start: u64,
+        mut history_budget: u64,
+    ) -> Vec<Header> {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
 
         let mut headers = Vec::new();
@@ -105,6 +231,16 @@ where
 
         for _ in 0..limit {
             if let Some(header) = self.client.header_by_hash_or_number(block).unwrap_or_default() {
+                if header.number < self.config.earliest_block {
+                    break
+                }
+                if header.number < recent_start {
+                    if history_budget == 0 {
+                        break
+                    }
+                    history_budget -= 1;
+                }
+
                 match direction {
                     HeadersDirection::Rising => {
                         if let Some(next) = (header.number + 1).checked_add(skip) {
@@ -145,29 +281,50 @@ where
     }
 
     fn on_headers_request(
-        &self,
-        _peer_id: PeerId,
+        &mut self,
+        peer_id: PeerId,
         request: GetBlockHeaders,
         response: oneshot::Sender<RequestResult<BlockHeaders<Header>>>,
     ) {
         self.metrics.eth_headers_requests_received_total.increment(1);
-        let headers = self.get_headers_response(request);
+        let recent_start = self.recent_start();
+        let history_budget = self.history_budget(peer_id);
+        let headers = self.get_headers_response(request, recent_start, history_budget);
+
+        let history = headers.iter().filter(|header| header.number < recent_start).count();
+        self.on_history_served(peer_id, history as u64);
+
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
     fn on_bodies_request(
-        &self,
-        _peer_id: PeerId,
+        &mut self,
+        peer_id: PeerId,
         request: GetBlockBodies,
         response: oneshot::Sender<RequestResult<BlockBodies<BlockBody>>>,
     ) {
         self.metrics.eth_bodies_requests_received_total.increment(1);
+        let recent_start = self.recent_start();
+        let mut history_budget = self.history_budget(peer_id);
+
         let mut bodies = Vec::new();
 
         let mut total_bytes = 0;
+        let mut history = 0;
 
         for hash in request.0 {
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
+                if block.number < self.config.earliest_block {
+                    break
+                }
+                if block.number < recent_start {
+                    if history_budget == 0 {
+                        break
+                    }
+                    history_budget -= 1;
+                    history += 1;
+                }
+
                 let body: BlockBody = block.into();
 
                 total_bytes += body.length();
@@ -181,6 +338,8 @@ where
             }
         }
 
+        self.on_history_served(peer_id, history);
+
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
@@ -218,6 +377,23 @@ where
 
         let _ = response.send(Ok(Receipts(receipts)));
     }
+
+    fn on_request(&mut self, incoming: IncomingEthRequest) {
+        match incoming {
+            IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
+                self.on_headers_request(peer_id, request, response)
+            }
+            IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
+                self.on_bodies_request(peer_id, request, response)
+            }
+            IncomingEthRequest::GetNodeData { .. } => {
+                self.metrics.eth_node_data_requests_received_total.increment(1);
+            }
+            IncomingEthRequest::GetReceipts { peer_id, request, response } => {
+                self.on_receipts_request(peer_id, request, response)
+            }
+        }
+    }
 }
 
 /// An endless future.
@@ -240,27 +416,28 @@ where
             DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
             this.incoming_requests.poll_next_unpin(cx),
             |incoming| {
-                match incoming {
-                    IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
-                        this.on_headers_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
-                        this.on_bodies_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetNodeData { .. } => {
-                        this.metrics.eth_node_data_requests_received_total.increment(1);
-                    }
-                    IncomingEthRequest::GetReceipts { peer_id, request, response } => {
-                        this.on_receipts_request(peer_id, request, response)
-                    }
+                // requests for the history wait until all recent requests are served, unless
+                // too many of them are queued already
+                if this.history_requests.len() < ETH_REQUEST_CHANNEL_CAPACITY &&
+                    this.is_history_request(&incoming)
+                {
+                    this.history_requests.push_back(incoming)
+                } else {
+                    this.on_request(incoming)
                 }
             },
         );
 
+        if !maybe_more_incoming_requests {
+            if let Some(incoming) = this.history_requests.pop_front() {
+                this.on_request(incoming)
+            }
+        }
+
         this.metrics.acc_duration_poll_eth_req_handler.set(acc.as_secs_f64());
 
         // stream is fully drained and import futures pending
-        if maybe_more_incoming_requests {
+        if maybe_more_incoming_requests || !this.history_requests.is_empty() {
             // make sure we're woken up again
             cx.waker().wake_by_ref();
             return Poll::Pending
@@ -318,3 +495,71 @@ pub enum IncomingEthRequest<N: NetworkPrimitives = EthNetworkPrimitives> {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_provider::test_utils::MockEthProvider;
+    use tokio::sync::mpsc;
+
+    /// Returns a handler for a chain of 100 headers, where the last 10 are recent.
+    fn handler(config: EthRequestHandlerConfig) -> EthRequestHandler<MockEthProvider> {
+        let client = MockEthProvider::default();
+        client.extend_headers((0..100u64).map(|number| {
+            let parent_hash = B256::with_last_byte(number.saturating_sub(1) as u8);
+            (
+                B256::with_last_byte(number as u8),
+                Header { number, parent_hash, ..Default::default() },
+            )
+        }));
+        let (_, rx) = mpsc::channel(1);
+        EthRequestHandler::new(client, PeersHandle::new(mpsc::unbounded_channel().0), rx)
+            .with_config(EthRequestHandlerConfig { recent_blocks: 9, ..config })
+    }
+
+    fn request_headers(
+        handler: &mut EthRequestHandler<MockEthProvider>,
+        peer_id: PeerId,
+        start: u64,
+    ) -> Vec<u64> {
+        let request = GetBlockHeaders {
+            start_block: B256::with_last_byte(start as u8).into(),
+            limit: 20,
+            skip: 0,
+            direction: HeadersDirection::Falling,
+        };
+        let (tx, mut rx) = oneshot::channel();
+        handler.on_headers_request(peer_id, request, tx);
+        rx.try_recv().unwrap().unwrap().0.into_iter().map(|header| header.number).collect()
+    }
+
+    #[test]
+    fn limits_history_per_peer() {
+        let mut handler = handler(EthRequestHandlerConfig {
+            max_history_per_peer: Some(15),
+            ..Default::default()
+        });
+        let peer = PeerId::random();
+
+        // 10 recent headers and 10 of the history budget
+        assert_eq!(request_headers(&mut handler, peer, 99), (80..=99).rev().collect::<Vec<_>>());
+        // only 5 of the history budget remain
+        assert_eq!(request_headers(&mut handler, peer, 79), (75..=79).rev().collect::<Vec<_>>());
+        assert!(request_headers(&mut handler, peer, 74).is_empty());
+        // recent headers are always served
+        assert_eq!(request_headers(&mut handler, peer, 95), (90..=95).rev().collect::<Vec<_>>());
+
+        // other peers have their own budget
+        assert_eq!(request_headers(&mut handler, PeerId::random(), 74).len(), 15);
+    }
+
+    #[test]
+    fn refuses_pruned_history() {
+        let mut handler =
+            handler(EthRequestHandlerConfig { earliest_block: 85, ..Default::default() });
+
+        assert_eq!(request_headers(&mut handler, PeerId::random(), 99).len(), 15);
+        assert!(request_headers(&mut handler, PeerId::random(), 84).is_empty());
+    }
+}
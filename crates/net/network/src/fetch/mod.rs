@@ -125,6 +125,11 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         false
     }
 
+    /// Returns the best block hash and number of the peer, if it's an active peer.
+    pub(crate) fn peer_best_block(&self, peer_id: &PeerId) -> Option<(B256, u64)> {
+        self.peers.get(peer_id).map(|peer| (peer.best_hash, peer.best_number))
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -146,7 +146,7 @@ pub use reth_network_api::{
     NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
 };
 pub use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
-pub use reth_network_types::{PeersConfig, SessionsConfig};
+pub use reth_network_types::{BandwidthLimits, PeerDiagnostics, PeersConfig, SessionsConfig};
 pub use session::{
     ActiveSessionHandle, ActiveSessionMessage, Direction, EthRlpxConnection, PeerInfo,
     PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
@@ -30,16 +30,17 @@ use std::{
 use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_eth_wire::{
-    capability::CapabilityMessage, Capabilities, DisconnectReason, EthNetworkPrimitives,
-    NetworkPrimitives,
+    capability::CapabilityMessage, BlockRangeUpdate, Capabilities, DisconnectReason,
+    EthNetworkPrimitives, NetworkPrimitives,
 };
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
+use reth_net_nat::{NatResolver, PortMapper, PortMapping};
 use reth_network_api::{
     test_utils::PeersHandle, EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerDiagnostics, ReputationChangeKind};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
@@ -53,7 +54,7 @@ use crate::{
     config::NetworkConfig,
     discovery::Discovery,
     error::{NetworkError, ServiceKind},
-    eth_requests::IncomingEthRequest,
+    eth_requests::{EthRequestHandlerConfig, IncomingEthRequest},
     import::{BlockImport, BlockImportOutcome, BlockValidation},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
@@ -107,6 +108,9 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest<N>>>,
+    /// How the [`EthRequestHandler`](crate::eth_requests::EthRequestHandler) serves headers and
+    /// bodies.
+    eth_requests_config: EthRequestHandlerConfig,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -133,6 +137,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Returns how the [`EthRequestHandler`](crate::eth_requests::EthRequestHandler) serves
+    /// headers and bodies, the earliest served block is advertised to peers.
+    pub const fn eth_requests_config(&self) -> &EthRequestHandlerConfig {
+        &self.eth_requests_config
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -191,6 +201,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
+            eth_requests_config,
             nat,
         } = config;
 
@@ -219,6 +230,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
 
+        let discv5_addr = discovery_v5_config.as_ref().map(|discv5| discv5.discovery_socket());
+
         let discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
@@ -233,13 +246,29 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let discv4 = discovery.discv4();
         let discv5 = discovery.discv5();
 
+        if nat == Some(NatResolver::Upnp) {
+            // forward the p2p ports on the gateway, the external IP is resolved by discovery
+            let mappings = std::iter::once(PortMapping::tcp(listener_addr.port()))
+                .chain(discv4.as_ref().map(|discv4| PortMapping::udp(discv4.local_addr().port())))
+                .chain(discv5_addr.map(|addr| PortMapping::udp(addr.port())));
+            executor.spawn(Box::pin(PortMapper::new(mappings).run()));
+        }
+
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
+        // the node serves all blocks from the earliest served block up to its head
+        let latest = client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
+        let block_range = BlockRangeUpdate {
+            earliest: eth_requests_config.earliest_block.min(latest),
+            latest,
+            latest_hash: status.blockhash,
+        };
         let sessions = SessionManager::new(
             secret_key,
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -282,6 +311,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            eth_requests_config,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
@@ -369,6 +399,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         Ok(())
     }
 
+    /// Collect the reputations and bans of the peers that were penalized or are banned
+    /// temporarily and write them to the given `persistent_reputations_file`, so that they can be
+    /// restored after a restart.
+    pub fn write_peer_reputations_to_file(
+        &self,
+        persistent_reputations_file: &Path,
+    ) -> Result<(), FsPathError> {
+        let reputations = self.swarm.state().peers().peer_reputations(None);
+        persistent_reputations_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(persistent_reputations_file, &reputations)?;
+        Ok(())
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -614,6 +657,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::GetPeerReputations(peer_id, tx) => {
+                let _ = tx.send(self.swarm.state().peers().peer_reputations(peer_id));
+            }
+            NetworkHandleMessage::GetPeerDiagnostics(peer_id, tx) => {
+                let _ = tx.send(self.get_peer_diagnostics(peer_id));
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
@@ -757,6 +806,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     // Gracefully disconnected
                     self.swarm.state_mut().peers_mut().on_active_session_gracefully_closed(peer_id);
                 }
+                let disconnect = match (reason, &error) {
+                    (Some(reason), _) => reason.to_string(),
+                    (None, Some(err)) => err.to_string(),
+                    (None, None) => "session closed".to_string(),
+                };
+                self.swarm.state_mut().peers_mut().record_disconnect(peer_id, disconnect);
                 self.metrics.closed_sessions.increment(1);
                 self.update_active_connection_metrics();
 
@@ -824,6 +879,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                         &peer_id,
                         err,
                     );
+                    self.swarm.state_mut().peers_mut().record_disconnect(peer_id, err.to_string());
                     self.metrics.pending_session_failures.increment(1);
                     if let Some(reason) = err.as_disconnected() {
                         self.disconnect_metrics.increment(reason);
@@ -923,6 +979,36 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         peer_ids.into_iter().filter_map(|peer_id| self.get_peer_info_by_id(peer_id)).collect()
     }
 
+    /// Returns the [`PeerDiagnostics`] of the given peer, or of all connected peers and all peers
+    /// with a disconnect history if no peer is given.
+    fn get_peer_diagnostics(&self, peer_id: Option<PeerId>) -> Vec<PeerDiagnostics> {
+        let state = self.swarm.state();
+        let diagnostics = |peer_id: PeerId| {
+            let session = self.swarm.sessions().active_sessions().get(&peer_id).map(|session| {
+                let kind =
+                    state.peers().peer_by_id(peer_id).map(|(_, kind)| kind).unwrap_or_default();
+                session.diagnostics(kind, state.peer_best_block(&peer_id))
+            });
+            let disconnects = state.peers().disconnect_history(&peer_id);
+            (session.is_some() || !disconnects.is_empty()).then_some(PeerDiagnostics {
+                peer_id,
+                session,
+                disconnects,
+            })
+        };
+
+        if let Some(peer_id) = peer_id {
+            return diagnostics(peer_id).into_iter().collect()
+        }
+
+        let connected = self.swarm.sessions().active_sessions().keys().copied();
+        let disconnected = state
+            .peers()
+            .disconnected_peers()
+            .filter(|peer_id| !self.swarm.sessions().active_sessions().contains_key(peer_id));
+        connected.chain(disconnected).filter_map(diagnostics).collect()
+    }
+
     /// Updates the metrics for active,established connections
     #[inline]
     fn update_active_connection_metrics(&self) {
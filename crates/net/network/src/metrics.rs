@@ -96,6 +96,16 @@ pub struct TransactionsManagerMetrics {
     /* ================ BROADCAST ================ */
     /// Total number of propagated transactions
     pub(crate) propagated_transactions: Counter,
+    /// Total number of transactions sent in full to a peer, counted once per peer.
+    pub(crate) propagated_full_transactions: Counter,
+    /// Total number of transaction hashes announced to a peer, counted once per peer.
+    pub(crate) propagated_transaction_hashes: Counter,
+    /// Total number of transactions that were only announced because they exceed the max size
+    /// of a transaction that is broadcasted in full.
+    pub(crate) announced_oversized_transactions: Counter,
+    /// Total number of blob transactions that were not propagated because blob propagation is
+    /// disabled.
+    pub(crate) withheld_blob_transactions: Counter,
     /// Total number of reported bad transactions
     pub(crate) reported_bad_transactions: Counter,
 
@@ -23,7 +23,9 @@ use reth_network_api::{
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{
+    PeerAddr, PeerDiagnostics, PeerKind, PeerReputationRecord, Reputation, ReputationChangeKind,
+};
 use reth_primitives::{Head, TransactionSigned};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
@@ -338,6 +340,24 @@ impl Peers for NetworkHandle {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn peer_reputations(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> Result<Vec<PeerReputationRecord>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetPeerReputations(peer_id, tx));
+        Ok(rx.await?)
+    }
+
+    async fn peer_diagnostics(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> Result<Vec<PeerDiagnostics>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetPeerDiagnostics(peer_id, tx));
+        Ok(rx.await?)
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -504,6 +524,10 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Retrieves the reputation and ban of a peer, or of all penalized or banned peers.
+    GetPeerReputations(Option<PeerId>, oneshot::Sender<Vec<PeerReputationRecord>>),
+    /// Retrieves the diagnostics of a peer, or of all connected and recently disconnected peers.
+    GetPeerDiagnostics(Option<PeerId>, oneshot::Sender<Vec<PeerDiagnostics>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
@@ -6,7 +6,7 @@ use std::{
     io::{self},
     net::{IpAddr, SocketAddr},
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use futures::StreamExt;
@@ -15,12 +15,14 @@ use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
+    is_banned_reputation,
     peers::{
-        config::PeerBackoffDurations,
+        config::{PeerBackoffDurations, PeerReconnectBackoff},
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ConnectionsConfig, DisconnectRecord, Peer, PeerAddr, PeerConnectionState, PeerKind,
+    PeerReputationRecord, PeersConfig, Reputation, ReputationChangeKind, ReputationChangeOutcome,
+    ReputationChangeWeights, ReputationDecay, MAX_DISCONNECT_HISTORY,
 };
 use reth_primitives::ForkId;
 use thiserror::Error;
@@ -29,14 +31,18 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 use crate::{
+    cache::LruMap,
     error::SessionError,
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
 };
 
+/// The maximum number of peers for which the disconnect history is kept.
+pub const DEFAULT_MAX_DISCONNECT_HISTORY_PEERS: u32 = 1_000;
+
 /// Maintains the state of _all_ the peers known to the network.
 ///
 /// This is supposed to be owned by the network itself, but can be reached via the [`PeersHandle`].
@@ -62,6 +68,12 @@ pub struct PeersManager {
     refill_slots_interval: Interval,
     /// How to weigh reputation changes
     reputation_weights: ReputationChangeWeights,
+    /// How the penalties of peers decay over time.
+    reputation_decay: ReputationDecay,
+    /// Interval at which the penalties of peers decay.
+    reputation_decay_interval: Interval,
+    /// Reputations of a previous run that are restored once the peer is added.
+    restored_reputations: HashMap<PeerId, Reputation>,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
@@ -75,6 +87,8 @@ pub struct PeersManager {
     /// How long peers to which we could not connect for non-fatal reasons, e.g.
     /// [`DisconnectReason::TooManyPeers`], are put in time out.
     backoff_durations: PeerBackoffDurations,
+    /// How long to wait before redialing trusted and static peers that we failed to connect to.
+    reconnect_backoff: PeerReconnectBackoff,
     /// If non-trusted peers should be connected to, or the connection from non-trusted
     /// incoming peers should be accepted.
     trusted_nodes_only: bool,
@@ -86,6 +100,8 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// The most recent disconnects of peers.
+    disconnect_history: LruMap<PeerId, VecDeque<DisconnectRecord>>,
 }
 
 impl PeersManager {
@@ -95,9 +111,12 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            reputation_decay,
+            peer_reputations,
+            mut ban_list,
             ban_duration,
             backoff_durations,
+            reconnect_backoff,
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
@@ -108,7 +127,8 @@ impl PeersManager {
         let now = Instant::now();
 
         // We use half of the interval to decrease the max duration to `150%` in worst case
-        let unban_interval = ban_duration.min(backoff_durations.low) / 2;
+        let unban_interval =
+            ban_duration.min(backoff_durations.low).min(reconnect_backoff.initial) / 2;
 
         let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
@@ -133,6 +153,28 @@ impl PeersManager {
             });
         }
 
+        // restore the bans that haven't expired yet and the reputations of the previous run
+        let mut restored_reputations = HashMap::with_capacity(peer_reputations.len());
+        let now_unix = unix_timestamp(SystemTime::now());
+        for PeerReputationRecord { peer_id, reputation, banned_until } in peer_reputations {
+            let remaining_ban = banned_until.and_then(|until| until.checked_sub(now_unix));
+            if let Some(remaining_ban) = remaining_ban.filter(|remaining| *remaining > 0) {
+                ban_list.ban_peer_until(
+                    peer_id,
+                    std::time::Instant::now() + Duration::from_secs(remaining_ban),
+                );
+            } else if is_banned_reputation(reputation) {
+                // the ban expired, which resets the reputation
+                continue
+            }
+
+            if let Some(peer) = peers.get_mut(&peer_id) {
+                peer.reputation = reputation;
+            } else {
+                restored_reputations.insert(peer_id, reputation);
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -140,6 +182,12 @@ impl PeersManager {
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
             reputation_weights,
+            reputation_decay_interval: tokio::time::interval_at(
+                now + reputation_decay.interval,
+                reputation_decay.interval,
+            ),
+            reputation_decay,
+            restored_reputations,
             refill_slots_interval: tokio::time::interval(refill_slots_interval),
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
@@ -147,11 +195,13 @@ impl PeersManager {
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
+            reconnect_backoff,
             trusted_nodes_only,
             last_tick: Instant::now(),
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            disconnect_history: LruMap::new(DEFAULT_MAX_DISCONNECT_HISTORY_PEERS),
         }
     }
 
@@ -178,6 +228,73 @@ impl PeersManager {
         })
     }
 
+    /// Returns the reputation and ban of the given peer, or of all peers that have been penalized
+    /// or are banned temporarily if no peer is given.
+    ///
+    /// Peers that are banned indefinitely are not included, since they are banned by the config.
+    pub(crate) fn peer_reputations(&self, peer_id: Option<PeerId>) -> Vec<PeerReputationRecord> {
+        let now = std::time::Instant::now();
+        let now_unix = unix_timestamp(SystemTime::now());
+        let banned_until = |peer_id: &PeerId| {
+            self.ban_list
+                .peer_banned_until(peer_id)
+                .flatten()
+                .map(|until| now_unix + until.saturating_duration_since(now).as_secs())
+        };
+        let record = |peer_id: PeerId| {
+            let reputation = self.get_reputation(&peer_id);
+            let banned_until = banned_until(&peer_id);
+            (reputation.is_some() || banned_until.is_some()).then(|| PeerReputationRecord {
+                peer_id,
+                reputation: reputation.unwrap_or(DEFAULT_REPUTATION),
+                banned_until,
+            })
+        };
+
+        if let Some(peer_id) = peer_id {
+            return record(peer_id).into_iter().collect()
+        }
+
+        let penalized = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.reputation < DEFAULT_REPUTATION)
+            .map(|(peer_id, _)| *peer_id);
+        let banned = self
+            .ban_list
+            .banned_peers()
+            .filter(|(peer_id, until)| until.is_some() && !self.peers.contains_key(peer_id))
+            .map(|(peer_id, _)| peer_id);
+        penalized.chain(banned).filter_map(record).collect()
+    }
+
+    /// Records that the given peer disconnected for the given reason.
+    ///
+    /// Only the [`MAX_DISCONNECT_HISTORY`] most recent disconnects are kept per peer.
+    pub(crate) fn record_disconnect(&mut self, peer_id: PeerId, reason: String) {
+        let record = DisconnectRecord { timestamp: unix_timestamp(SystemTime::now()), reason };
+        let Some(history) = self.disconnect_history.get_or_insert(peer_id, VecDeque::new) else {
+            return
+        };
+        if history.len() == MAX_DISCONNECT_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(record);
+    }
+
+    /// Returns the most recent disconnects of the given peer, oldest first.
+    pub(crate) fn disconnect_history(&self, peer_id: &PeerId) -> Vec<DisconnectRecord> {
+        self.disconnect_history
+            .peek(peer_id)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all peers that have a disconnect history.
+    pub(crate) fn disconnected_peers(&self) -> impl Iterator<Item = PeerId> + '_ {
+        self.disconnect_history.iter().map(|(peer_id, _)| *peer_id)
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -348,6 +465,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.reconnect_attempts = 0;
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -356,6 +474,7 @@ impl PeersManager {
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(PeerAddr::from_tcp(addr), PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                restore_reputation(&mut self.restored_reputations, peer_id, &mut peer);
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -435,6 +554,15 @@ impl PeersManager {
         }
     }
 
+    /// Restores the reputation of all penalized peers that are not banned by one decay interval.
+    fn decay_reputations(&mut self) {
+        for (peer_id, peer) in &mut self.peers {
+            if peer.reputation < DEFAULT_REPUTATION && !self.ban_list.is_banned_peer(peer_id) {
+                peer.reputation = self.reputation_decay.apply(peer.reputation);
+            }
+        }
+    }
+
     /// Returns the tracked reputation for a peer.
     pub(crate) fn get_reputation(&self, peer_id: &PeerId) -> Option<i32> {
         self.peers.get(peer_id).map(|peer| peer.reputation)
@@ -542,6 +670,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.reconnect_attempts = 0;
         }
     }
 
@@ -594,8 +723,8 @@ impl PeersManager {
             // issues.
             if let Entry::Occupied(mut entry) = self.peers.entry(*peer_id) {
                 self.connection_info.decr_state(entry.get().state);
-                // only remove if the peer is not trusted
-                if entry.get().is_trusted() {
+                // only remove if the peer is neither trusted nor static
+                if entry.get().is_trusted() || entry.get().is_static() {
                     entry.get_mut().state = PeerConnectionState::Idle;
                 } else {
                     entry.remove();
@@ -617,7 +746,14 @@ impl PeersManager {
             let mut remove_peer = false;
 
             if let Some(peer) = self.peers.get_mut(peer_id) {
-                if let Some(kind) = err.should_backoff() {
+                if peer.is_trusted() || peer.is_static() {
+                    // peers we should always be connected to are neither penalized nor removed,
+                    // but redialed with an increasing delay until a session is established
+                    peer.reconnect_attempts = peer.reconnect_attempts.saturating_add(1);
+                    debug!(target: "net::peers", ?peer_id, %err, attempts=peer.reconnect_attempts, "failed to connect to persistent peer, retrying");
+                    backoff_until =
+                        Some(self.reconnect_backoff.backoff_until(peer.reconnect_attempts));
+                } else if let Some(kind) = err.should_backoff() {
                     // Increment peer.backoff_counter
                     if kind.is_severe() {
                         peer.severe_backoff_counter = peer.severe_backoff_counter.saturating_add(1);
@@ -639,9 +775,12 @@ impl PeersManager {
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
-                if peer.severe_backoff_counter > self.max_backoff_count && !peer.is_trusted() {
-                    // mark peer for removal if it has been backoff too many times and is _not_
-                    // trusted
+                if peer.severe_backoff_counter > self.max_backoff_count &&
+                    !peer.is_trusted() &&
+                    !peer.is_static()
+                {
+                    // mark peer for removal if it has been backoff too many times and is neither
+                    // trusted nor static
                     remove_peer = true;
                 }
             }
@@ -740,6 +879,7 @@ impl PeersManager {
                 trace!(target: "net::peers", ?peer_id, addr=?addr.tcp(), "discovered new node");
                 let mut peer = Peer::with_kind(addr, kind);
                 peer.fork_id = fork_id;
+                restore_reputation(&mut self.restored_reputations, peer_id, &mut peer);
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -807,6 +947,7 @@ impl PeersManager {
                 let mut peer = Peer::with_kind(addr, kind);
                 peer.state = PeerConnectionState::PendingOut;
                 peer.fork_id = fork_id;
+                restore_reputation(&mut self.restored_reputations, peer_id, &mut peer);
                 entry.insert(peer);
                 self.queued_actions
                     .push_back(PeerAction::Connect { peer_id, remote_addr: addr.tcp() });
@@ -971,6 +1112,10 @@ impl PeersManager {
                 })
             }
 
+            if self.reputation_decay_interval.poll_tick(cx).is_ready() {
+                self.decay_reputations();
+            }
+
             while self.refill_slots_interval.poll_tick(cx).is_ready() {
                 self.fill_outbound_slots();
             }
@@ -982,6 +1127,22 @@ impl PeersManager {
     }
 }
 
+/// Sets the reputation of a previous run if there is one for the newly added peer.
+fn restore_reputation(
+    restored_reputations: &mut HashMap<PeerId, Reputation>,
+    peer_id: PeerId,
+    peer: &mut Peer,
+) {
+    if let Some(reputation) = restored_reputations.remove(&peer_id) {
+        peer.reputation = reputation;
+    }
+}
+
+/// Returns the seconds since the unix epoch.
+fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 impl Default for PeersManager {
     fn default() -> Self {
         Self::new(Default::default())
@@ -1151,7 +1312,8 @@ mod tests {
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, PeerReputationRecord,
+        ReputationChangeKind, ReputationDecay, MAX_DISCONNECT_HISTORY,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1159,11 +1321,11 @@ mod tests {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         pin::Pin,
         task::{Context, Poll},
-        time::Duration,
+        time::{Duration, SystemTime},
     };
     use url::Host;
 
-    use super::PeersManager;
+    use super::{unix_timestamp, PeersManager};
     use crate::{
         error::SessionError,
         peers::{
@@ -1304,6 +1466,70 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_restore_and_decay_reputations() {
+        let (penalized, banned, expired) = (PeerId::random(), PeerId::random(), PeerId::random());
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let now = unix_timestamp(SystemTime::now());
+        let config = PeersConfig::test()
+            .with_reputation_decay(ReputationDecay {
+                interval: Duration::from_millis(50),
+                amount: 1_000,
+            })
+            .with_peer_reputations(vec![
+                PeerReputationRecord { peer_id: penalized, reputation: -1_500, banned_until: None },
+                PeerReputationRecord {
+                    peer_id: banned,
+                    reputation: i32::MIN,
+                    banned_until: Some(now + 3_600),
+                },
+                PeerReputationRecord {
+                    peer_id: expired,
+                    reputation: i32::MIN,
+                    banned_until: Some(now - 1),
+                },
+            ]);
+        let mut peers = PeersManager::new(config);
+        assert!(peers.ban_list.is_banned_peer(&banned));
+        assert!(!peers.ban_list.is_banned_peer(&expired));
+
+        // reputations are restored once the peers are added
+        peers.add_peer(penalized, PeerAddr::from_tcp(socket_addr), None);
+        peers.add_peer(expired, PeerAddr::from_tcp(socket_addr), None);
+        assert_eq!(peers.get_reputation(&penalized), Some(-1_500));
+        assert_eq!(peers.get_reputation(&expired), Some(DEFAULT_REPUTATION));
+
+        let records = peers.peer_reputations(None);
+        assert_eq!(records.len(), 2);
+        let record = peers.peer_reputations(Some(banned)).pop().unwrap();
+        assert!(record.banned_until.unwrap() >= now + 3_599);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        poll_fn(|cx| {
+            while peers.poll(cx).is_ready() {}
+            Poll::Ready(())
+        })
+        .await;
+        assert_eq!(peers.get_reputation(&penalized), Some(-500));
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_history() {
+        let peer = PeerId::random();
+        let mut peers = PeersManager::default();
+        assert!(peers.disconnect_history(&peer).is_empty());
+
+        for i in 0..MAX_DISCONNECT_HISTORY + 2 {
+            peers.record_disconnect(peer, i.to_string());
+        }
+
+        let history = peers.disconnect_history(&peer);
+        assert_eq!(history.len(), MAX_DISCONNECT_HISTORY);
+        assert_eq!(history.first().unwrap().reason, "2");
+        assert_eq!(history.last().unwrap().reason, (MAX_DISCONNECT_HISTORY + 1).to_string());
+        assert_eq!(peers.disconnected_peers().collect::<Vec<_>>(), vec![peer]);
+    }
+
     #[tokio::test]
     async fn test_backoff_on_busy() {
         let peer = PeerId::random();
@@ -2024,6 +2250,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reconnect_trusted_peer_with_backoff() {
+        let peer = PeerId::random();
+        let config = PeersConfig::test()
+            .with_trusted_nodes(vec![TrustedPeer {
+                host: Host::Ipv4(Ipv4Addr::new(127, 0, 1, 2)),
+                tcp_port: 8008,
+                udp_port: 8008,
+                id: peer,
+            }])
+            .with_trusted_nodes_only(true)
+            .with_max_backoff_count(0);
+        let mut peers = PeersManager::new(config);
+        let socket_addr = peers.peers.get(&peer).unwrap().addr.tcp();
+
+        for attempts in 1..=3 {
+            match event!(peers) {
+                PeerAction::Connect { peer_id, remote_addr } => {
+                    assert_eq!(peer_id, peer);
+                    assert_eq!(remote_addr, socket_addr);
+                }
+                err => unreachable!("{err:?}"),
+            }
+
+            // unreachable peers are usually backed off for a long time and eventually removed
+            peers.on_outgoing_connection_failure(
+                &socket_addr,
+                &peer,
+                &io::Error::new(io::ErrorKind::ConnectionRefused, ""),
+            );
+
+            let p = peers.peers.get(&peer).unwrap();
+            assert_eq!(p.state, PeerConnectionState::Idle);
+            assert_eq!(p.reputation, DEFAULT_REPUTATION);
+            assert_eq!(p.reconnect_attempts, attempts);
+            assert!(p.is_backed_off());
+        }
+        let backoff = peers.reconnect_backoff;
+        assert_eq!(backoff.backoff(1), backoff.initial);
+        assert_eq!(backoff.backoff(2), backoff.initial * 2);
+        assert_eq!(backoff.backoff(3), backoff.max);
+
+        // the peer is redialed once the backoff expired
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            err => unreachable!("{err:?}"),
+        }
+        peers.on_active_outgoing_established(peer);
+        assert_eq!(peers.peers.get(&peer).unwrap().reconnect_attempts, 0);
+    }
+
     #[tokio::test]
     async fn test_outgoing_connection_error() {
         let peer = PeerId::random();
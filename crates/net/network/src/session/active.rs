@@ -12,6 +12,7 @@ use std::{
 };
 
 use alloy_primitives::Sealable;
+use alloy_rlp::Encodable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use metrics::Gauge;
 use reth_eth_wire::{
@@ -37,8 +38,10 @@ use tracing::{debug, trace};
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::SessionBandwidth,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
+        stats::SessionStats,
         SessionId,
     },
 };
@@ -95,6 +98,10 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     pub(crate) internal_request_timeout: Arc<AtomicU64>,
     /// Interval when to check for timed out requests.
     pub(crate) internal_request_timeout_interval: Interval,
+    /// Traffic and latency statistics of the session, shared with its handle.
+    pub(crate) stats: Arc<SessionStats>,
+    /// Upload and download limits of the session.
+    pub(crate) bandwidth: SessionBandwidth,
     /// If an [`ActiveSession`] does not receive a response at all within this duration then it is
     /// considered a protocol violation and the session will initiate a drop.
     pub(crate) protocol_breach_request_timeout: Duration,
@@ -116,6 +123,12 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         id
     }
 
+    /// Records a message of the given size sent to the peer.
+    fn on_sent(&self, len: usize) {
+        self.stats.on_sent(len);
+        self.bandwidth.on_sent(len);
+    }
+
     /// Shrinks the capacity of the internal buffers.
     pub fn shrink_to_fit(&mut self) {
         self.received_requests_from_remote.shrink_to_fit();
@@ -176,10 +189,27 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         }
 
         match msg {
-            message @ EthMessage::Status(_) => OnIncomingMessageOutcome::BadMessage {
-                error: EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
-                message,
-            },
+            message @ (EthMessage::Status(_) | EthMessage::StatusEth69(_)) => {
+                OnIncomingMessageOutcome::BadMessage {
+                    error: EthStreamError::EthHandshakeError(
+                        EthHandshakeError::StatusNotInHandshake,
+                    ),
+                    message,
+                }
+            }
+            EthMessage::BlockRangeUpdate(block_range) => {
+                if !block_range.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRange {
+                            earliest: block_range.earliest,
+                            latest: block_range.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(block_range),
+                    }
+                }
+                trace!(target: "net::session", peer_id=?self.remote_peer_id, ?block_range, "received block range update");
+                OnIncomingMessageOutcome::Ok
+            }
             EthMessage::NewBlockHashes(msg) => {
                 self.try_emit_broadcast(PeerMessage::NewBlockHashes(msg)).into()
             }
@@ -237,6 +267,11 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // eth/69 receipts don't include the bloom, so it's recomputed from the logs
+                let resp = resp.map(Into::into);
+                on_response!(resp, GetReceipts)
+            }
         }
     }
 
@@ -257,10 +292,15 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     fn on_internal_peer_message(&mut self, msg: PeerMessage<N>) {
         match msg {
             PeerMessage::NewBlockHashes(msg) => {
-                self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                // block announcements were removed in eth/69
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                }
             }
             PeerMessage::NewBlock(msg) => {
-                self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                }
             }
             PeerMessage::PooledTransactions(msg) => {
                 if msg.is_valid_for_version(self.conn.version()) {
@@ -294,6 +334,10 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     /// This will queue the response to be sent to the peer
     fn handle_outgoing_response(&mut self, id: u64, resp: PeerResponseResult<N>) {
         match resp.try_into_message(id) {
+            Ok(EthMessage::Receipts(receipts)) if self.conn.version().is_eth69() => {
+                self.queued_outgoing
+                    .push_back(EthMessage::Receipts69(receipts.map(Into::into)).into());
+            }
             Ok(msg) => {
                 self.queued_outgoing.push_back(msg.into());
             }
@@ -446,6 +490,7 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     /// Updates the request timeout with a request's timestamps
     fn update_request_timeout(&mut self, sent: Instant, received: Instant) {
         let elapsed = received.saturating_duration_since(sent);
+        self.stats.on_round_trip(elapsed);
 
         let current = Duration::from_millis(self.internal_request_timeout.load(Ordering::Relaxed));
         let request_timeout = calculate_new_timeout(current, elapsed);
@@ -555,11 +600,21 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
 
             // Send messages by advancing the sink and queuing in buffered messages
             while this.conn.poll_ready_unpin(cx).is_ready() {
+                // wait until the upload limits allow sending more, this keeps the messages queued
+                if !this.queued_outgoing.is_empty() && this.bandwidth.poll_upload(cx).is_pending() {
+                    break
+                }
                 if let Some(msg) = this.queued_outgoing.pop_front() {
                     progress = true;
                     let res = match msg {
-                        OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
-                        OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
+                        OutgoingMessage::Eth(msg) => {
+                            this.on_sent(msg.length());
+                            this.conn.start_send_unpin(msg)
+                        }
+                        OutgoingMessage::Broadcast(msg) => {
+                            this.on_sent(msg.length());
+                            this.conn.start_send_broadcast(msg)
+                        }
                     };
                     if let Err(err) = res {
                         debug!(target: "net::session", %err, remote_peer_id=?this.remote_peer_id, "failed to send message");
@@ -598,6 +653,12 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                     };
                 }
 
+                // stop reading from the wire until the download limits allow receiving more, which
+                // throttles the peer via TCP backpressure
+                if this.bandwidth.poll_download(cx).is_pending() {
+                    break 'receive
+                }
+
                 match this.conn.poll_next_unpin(cx) {
                     Poll::Pending => break,
                     Poll::Ready(None) => {
@@ -611,6 +672,9 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                let len = msg.length();
+                                this.stats.on_received(len);
+                                this.bandwidth.on_received(len);
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -784,6 +848,10 @@ impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
         self.messages.pop_front().inspect(|_| self.count.decrement(1))
     }
 
+    pub(crate) fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
     pub(crate) fn shrink_to_fit(&mut self) {
         self.messages.shrink_to_fit();
     }
@@ -843,7 +911,7 @@ mod tests {
             F: FnOnce(EthStream<P2PStream<ECIESStream<TcpStream>>>) -> O + Send + 'static,
             O: Future<Output = ()> + Send + Sync,
         {
-            let status = self.status;
+            let mut status = self.status;
             let fork_filter = self.fork_filter.clone();
             let local_peer_id = self.local_peer_id;
             let mut hello = self.hello.clone();
@@ -854,6 +922,7 @@ mod tests {
                 let sink = ECIESStream::connect(outgoing, key, local_peer_id).await.unwrap();
 
                 let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await.unwrap();
+                status.set_eth_version(p2p_stream.shared_capabilities().eth_version().unwrap());
 
                 let (client_stream, _) = UnauthedEthStream::new(p2p_stream)
                     .handshake(status, fork_filter)
@@ -878,6 +947,7 @@ mod tests {
                 self.secret_key,
                 self.hello.clone(),
                 self.status,
+                Default::default(),
                 self.fork_filter.clone(),
                 Default::default(),
             ));
@@ -922,6 +992,8 @@ mod tests {
                         internal_request_timeout: Arc::new(AtomicU64::new(
                             INITIAL_REQUEST_TIMEOUT.as_millis() as u64,
                         )),
+                        stats: Default::default(),
+                        bandwidth: Default::default(),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
                     }
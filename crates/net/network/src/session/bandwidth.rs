@@ -0,0 +1,233 @@
+//! Bandwidth limits of sessions.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use reth_network_types::BandwidthLimits;
+use tokio::time::{Instant, Sleep};
+
+/// A token bucket that refills at a fixed number of bytes per second.
+///
+/// Transfers are allowed as long as the bucket is not empty, a transfer larger than the available
+/// budget puts the bucket into debt which has to be paid off before the next transfer. This way
+/// messages larger than the per second limit can still be transferred.
+#[derive(Debug)]
+pub(crate) struct RateLimit {
+    /// Bytes per second.
+    rate: f64,
+    /// Available bytes and when they were last refilled.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimit {
+    /// Creates a new rate limit with a full bucket of one second worth of bytes.
+    pub(crate) fn new(bytes_per_sec: u64) -> Self {
+        let rate = bytes_per_sec.max(1) as f64;
+        Self { rate, state: Mutex::new((rate, Instant::now())) }
+    }
+
+    /// Returns how long to wait until bytes can be transferred again, which is zero if the bucket
+    /// is not empty.
+    pub(crate) fn delay(&self, now: Instant) -> Duration {
+        let available = self.refill(now);
+        if available >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - available) / self.rate)
+        }
+    }
+
+    /// Records a transfer of the given number of bytes.
+    pub(crate) fn consume(&self, bytes: usize, now: Instant) {
+        self.refill(now);
+        self.state.lock().0 -= bytes as f64;
+    }
+
+    /// Refills the bucket for the time that passed and returns the available bytes.
+    fn refill(&self, now: Instant) -> f64 {
+        let mut state = self.state.lock();
+        let (available, updated) = &mut *state;
+        let elapsed = now.saturating_duration_since(*updated).as_secs_f64();
+        *available = elapsed.mul_add(self.rate, *available).min(self.rate);
+        *updated = now.max(*updated);
+        *available
+    }
+}
+
+/// The bandwidth limits shared by all sessions of the
+/// [`SessionManager`](super::SessionManager).
+#[derive(Debug, Default)]
+pub(crate) struct BandwidthLimiter {
+    /// Limit of the bytes sent to all peers.
+    upload: Option<Arc<RateLimit>>,
+    /// Limit of the bytes received from all peers.
+    download: Option<Arc<RateLimit>>,
+    /// Bytes per second that can be sent to a single peer.
+    peer_upload: Option<u64>,
+    /// Bytes per second that can be received from a single peer.
+    peer_download: Option<u64>,
+}
+
+impl BandwidthLimiter {
+    /// Creates the shared limits from the configured limits.
+    pub(crate) fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            upload: limits.max_upload.map(|rate| Arc::new(RateLimit::new(rate))),
+            download: limits.max_download.map(|rate| Arc::new(RateLimit::new(rate))),
+            peer_upload: limits.max_peer_upload,
+            peer_download: limits.max_peer_download,
+        }
+    }
+
+    /// Returns the limits of a new session.
+    pub(crate) fn session(&self) -> SessionBandwidth {
+        SessionBandwidth {
+            upload: DirectionLimit::new(self.upload.clone(), self.peer_upload),
+            download: DirectionLimit::new(self.download.clone(), self.peer_download),
+        }
+    }
+}
+
+/// The bandwidth limits of an active session.
+#[derive(Debug, Default)]
+pub(crate) struct SessionBandwidth {
+    upload: DirectionLimit,
+    download: DirectionLimit,
+}
+
+impl SessionBandwidth {
+    /// Returns [`Poll::Ready`] if messages can be sent to the peer.
+    pub(crate) fn poll_upload(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.upload.poll_ready(cx)
+    }
+
+    /// Returns [`Poll::Ready`] if messages can be received from the peer.
+    pub(crate) fn poll_download(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.download.poll_ready(cx)
+    }
+
+    /// Records a message of the given size sent to the peer.
+    pub(crate) fn on_sent(&self, len: usize) {
+        self.upload.consume(len)
+    }
+
+    /// Records a message of the given size received from the peer.
+    pub(crate) fn on_received(&self, len: usize) {
+        self.download.consume(len)
+    }
+}
+
+/// The limits of one direction of a session.
+#[derive(Debug, Default)]
+struct DirectionLimit {
+    /// The limit shared by all sessions.
+    total: Option<Arc<RateLimit>>,
+    /// The limit of this session.
+    peer: Option<RateLimit>,
+    /// Fires once the limits allow transfers again.
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl DirectionLimit {
+    fn new(total: Option<Arc<RateLimit>>, peer: Option<u64>) -> Self {
+        Self { total, peer: peer.map(RateLimit::new), delay: None }
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.total.is_none() && self.peer.is_none() {
+            return Poll::Ready(())
+        }
+
+        loop {
+            if let Some(delay) = self.delay.as_mut() {
+                ready!(delay.as_mut().poll(cx));
+                self.delay = None;
+            }
+
+            let now = Instant::now();
+            let delay = self
+                .total
+                .iter()
+                .map(|limit| limit.delay(now))
+                .chain(self.peer.iter().map(|limit| limit.delay(now)))
+                .max()
+                .unwrap_or_default();
+            if delay.is_zero() {
+                return Poll::Ready(())
+            }
+            self.delay = Some(Box::pin(tokio::time::sleep(delay)));
+        }
+    }
+
+    fn consume(&self, len: usize) {
+        let now = Instant::now();
+        if let Some(limit) = &self.total {
+            limit.consume(len, now);
+        }
+        if let Some(limit) = &self.peer {
+            limit.consume(len, now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_refills() {
+        let limit = RateLimit::new(1000);
+        let now = Instant::now();
+        assert_eq!(limit.delay(now), Duration::ZERO);
+
+        // messages larger than the budget put the bucket into debt
+        limit.consume(1499, now);
+        assert_eq!(limit.delay(now), Duration::from_millis(500));
+        assert_eq!(limit.delay(now + Duration::from_millis(250)), Duration::from_millis(250));
+        assert_eq!(limit.delay(now + Duration::from_millis(500)), Duration::ZERO);
+    }
+
+    #[test]
+    fn rate_limit_caps_burst() {
+        let limit = RateLimit::new(1000);
+        let now = Instant::now();
+
+        // idle time doesn't accumulate more than one second worth of bytes
+        let later = now + Duration::from_secs(10);
+        limit.consume(1000, later);
+        assert_eq!(limit.delay(later), Duration::from_millis(1));
+    }
+
+    #[tokio::test]
+    async fn session_shares_total_limit() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default().with_max_upload(1000));
+        let mut a = limiter.session();
+        let mut b = limiter.session();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(a.poll_upload(&mut cx).is_ready());
+        a.on_sent(1000);
+        assert!(b.poll_upload(&mut cx).is_pending());
+        assert!(a.poll_download(&mut cx).is_ready());
+    }
+
+    #[tokio::test]
+    async fn session_limits_peer() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default().with_max_peer_download(100));
+        let mut a = limiter.session();
+        let mut b = limiter.session();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        a.on_received(200);
+        assert!(a.poll_download(&mut cx).is_pending());
+        assert!(b.poll_download(&mut cx).is_ready());
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 
+use alloy_primitives::B256;
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, Capabilities, DisconnectReason,
@@ -9,7 +10,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::PeerInfo;
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::PeerKind;
+use reth_network_types::{PeerKind, SessionDiagnostics};
 use tokio::sync::{
     mpsc::{self, error::SendError},
     oneshot,
@@ -17,7 +18,7 @@ use tokio::sync::{
 
 use crate::{
     message::PeerMessage,
-    session::{conn::EthRlpxConnection, Direction, SessionId},
+    session::{conn::EthRlpxConnection, stats::SessionStats, Direction, SessionId},
     PendingSessionHandshakeError,
 };
 
@@ -77,6 +78,8 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Traffic and latency statistics of the session.
+    pub(crate) stats: Arc<SessionStats>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -154,6 +157,40 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             kind,
         }
     }
+
+    /// Returns the [`SessionDiagnostics`] of the session.
+    ///
+    /// The best block is the one the peer announced last, falling back to the block of the
+    /// `eth` handshake.
+    pub(crate) fn diagnostics(
+        &self,
+        kind: PeerKind,
+        best_block: Option<(B256, u64)>,
+    ) -> SessionDiagnostics {
+        let (best_hash, best_number) = match best_block {
+            Some((hash, number)) => (hash, Some(number)),
+            None => (self.status.blockhash, None),
+        };
+        SessionDiagnostics {
+            client_version: self.client_version.to_string(),
+            remote_addr: self.remote_addr,
+            inbound: self.direction.is_incoming(),
+            kind,
+            capabilities: self
+                .capabilities
+                .capabilities()
+                .iter()
+                .map(|cap| cap.to_string())
+                .collect(),
+            eth_version: self.version as u8,
+            latency_ms: self.stats.latency().map(|latency| latency.as_millis() as u64),
+            best_hash,
+            best_number,
+            connected_secs: self.established.elapsed().as_secs(),
+            bytes_in: self.stats.bytes_in(),
+            bytes_out: self.stats.bytes_out(),
+        }
+    }
 }
 
 /// Events a pending session can produce.
@@ -1,9 +1,11 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
+mod stats;
 
 use active::QueuedOutgoingMessages;
 pub use conn::EthRlpxConnection;
@@ -28,8 +30,8 @@ use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, multiplex::RlpxProtocolMultiplexer,
-    Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols, NetworkPrimitives,
-    Status, UnauthedEthStream, UnauthedP2PStream,
+    BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols,
+    NetworkPrimitives, Status, UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
@@ -52,7 +54,7 @@ use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
     protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols},
-    session::active::ActiveSession,
+    session::{active::ActiveSession, bandwidth::BandwidthLimiter, stats::SessionStats},
 };
 
 /// Internal identifier for active sessions.
@@ -79,6 +81,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
     status: Status,
+    /// The range of blocks advertised in the eth/69 `Status` message.
+    block_range: BlockRangeUpdate,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -114,6 +118,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     disconnections_counter: DisconnectionsCounter,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// The bandwidth limits shared by all sessions.
+    bandwidth: BandwidthLimiter,
 }
 
 // === impl SessionManager ===
@@ -126,6 +132,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
         status: Status,
+        block_range: BlockRangeUpdate,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -137,11 +144,13 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         Self {
             next_id: 0,
             counter: SessionCounter::new(config.limits),
+            bandwidth: BandwidthLimiter::new(config.bandwidth),
             initial_internal_request_timeout: config.initial_internal_request_timeout,
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            block_range,
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
@@ -218,6 +227,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
         self.status.blockhash = head.hash;
         self.status.total_difficulty = head.total_difficulty;
+        self.block_range.latest = head.number;
+        self.block_range.latest_hash = head.hash;
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
         transition
@@ -248,6 +259,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
+        let block_range = self.block_range;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
@@ -265,6 +277,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 secret_key,
                 hello_message,
                 status,
+                block_range,
                 fork_filter,
                 extra_handlers,
             ),
@@ -290,6 +303,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -306,6 +320,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     secret_key,
                     hello_message,
                     status,
+                    block_range,
                     fork_filter,
                     extra_handlers,
                 ),
@@ -516,6 +531,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 // negotiated version
                 let version = conn.version();
 
+                let stats = Arc::new(SessionStats::default());
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -536,6 +553,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                         self.initial_internal_request_timeout,
                     ),
                     internal_request_timeout: Arc::clone(&timeout),
+                    stats: Arc::clone(&stats),
+                    bandwidth: self.bandwidth.session(),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
                 };
@@ -555,6 +574,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    stats,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -832,6 +852,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -845,6 +866,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
         Direction::Incoming,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -863,6 +885,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -895,6 +918,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
         Direction::Outgoing(remote_peer_id),
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -913,6 +937,7 @@ async fn authenticate<N: NetworkPrimitives>(
     direction: Direction,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -942,6 +967,7 @@ async fn authenticate<N: NetworkPrimitives>(
         direction,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -994,6 +1020,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent<N> {
@@ -1031,7 +1058,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         //
         // Before trying status handshake, set up the version to negotiated shared version
         status.set_eth_version(eth_version);
-        let eth_unauthed = UnauthedEthStream::new(p2p_stream);
+        let eth_unauthed = UnauthedEthStream::new(p2p_stream).with_block_range(block_range);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
             Err(err) => {
@@ -1059,18 +1086,20 @@ async fn authenticate_stream<N: NetworkPrimitives>(
                 .ok();
         }
 
-        let (multiplex_stream, their_status) =
-            match multiplex_stream.into_eth_satellite_stream(status, fork_filter).await {
-                Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
-                Err(err) => {
-                    return PendingSessionEvent::Disconnected {
-                        remote_addr,
-                        session_id,
-                        direction,
-                        error: Some(PendingSessionHandshakeError::Eth(err)),
-                    }
+        let (multiplex_stream, their_status) = match multiplex_stream
+            .into_eth_satellite_stream_with_block_range(status, block_range, fork_filter)
+            .await
+        {
+            Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err)),
                 }
-            };
+            }
+        };
 
         (multiplex_stream.into(), their_status)
     };
@@ -0,0 +1,93 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The weight of a new round trip sample for the smoothed latency of a session.
+const LATENCY_SAMPLE_IMPACT: f64 = 0.125;
+
+/// Traffic and latency statistics of an active session.
+///
+/// This is shared between the spawned [`ActiveSession`](super::active::ActiveSession) which
+/// updates it and the [`ActiveSessionHandle`](super::ActiveSessionHandle) which reads it.
+#[derive(Debug, Default)]
+pub(crate) struct SessionStats {
+    /// Total size of the `eth` messages received from the peer.
+    bytes_in: AtomicU64,
+    /// Total size of the `eth` messages sent to the peer.
+    bytes_out: AtomicU64,
+    /// Smoothed round trip time of requests to the peer in microseconds, `0` if no request
+    /// completed yet.
+    latency: AtomicU64,
+}
+
+// === impl SessionStats ===
+
+impl SessionStats {
+    /// Records a message of the given size received from the peer.
+    pub(crate) fn on_received(&self, len: usize) {
+        self.bytes_in.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Records a message of the given size sent to the peer.
+    pub(crate) fn on_sent(&self, len: usize) {
+        self.bytes_out.fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Records the round trip time of a request to the peer.
+    pub(crate) fn on_round_trip(&self, rtt: Duration) {
+        let sample = rtt.as_micros() as u64;
+        let current = self.latency.load(Ordering::Relaxed);
+        let latency = if current == 0 {
+            sample
+        } else {
+            (current as f64)
+                .mul_add(1.0 - LATENCY_SAMPLE_IMPACT, sample as f64 * LATENCY_SAMPLE_IMPACT)
+                as u64
+        };
+        self.latency.store(latency, Ordering::Relaxed);
+    }
+
+    /// Returns the total size of the messages received from the peer.
+    pub(crate) fn bytes_in(&self) -> u64 {
+        self.bytes_in.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total size of the messages sent to the peer.
+    pub(crate) fn bytes_out(&self) -> u64 {
+        self.bytes_out.load(Ordering::Relaxed)
+    }
+
+    /// Returns the smoothed round trip time of requests to the peer, if any request completed.
+    pub(crate) fn latency(&self) -> Option<Duration> {
+        let latency = self.latency.load(Ordering::Relaxed);
+        (latency > 0).then(|| Duration::from_micros(latency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooths_latency() {
+        let stats = SessionStats::default();
+        assert_eq!(stats.latency(), None);
+
+        stats.on_round_trip(Duration::from_millis(100));
+        assert_eq!(stats.latency(), Some(Duration::from_millis(100)));
+
+        stats.on_round_trip(Duration::from_millis(900));
+        assert_eq!(stats.latency(), Some(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn counts_bytes() {
+        let stats = SessionStats::default();
+        stats.on_received(10);
+        stats.on_received(5);
+        stats.on_sent(7);
+        assert_eq!(stats.bytes_in(), 15);
+        assert_eq!(stats.bytes_out(), 7);
+    }
+}
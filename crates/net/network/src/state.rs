@@ -257,6 +257,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Returns the best block hash and number of the peer, if it's an active peer.
+    pub(crate) fn peer_best_block(&self, peer_id: &PeerId) -> Option<(B256, u64)> {
+        self.state_fetcher.peer_best_block(peer_id)
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
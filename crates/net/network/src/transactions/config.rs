@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use derive_more::Constructor;
 
 use super::{
+    DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS,
     DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
@@ -21,6 +24,29 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Interval at which pending local transactions are rebroadcast to all peers, until they are
+    /// included or replaced.
+    ///
+    /// `None` disables the rebroadcast.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_local_transactions_rebroadcast_interval")
+    )]
+    pub local_transactions_rebroadcast_interval: Option<Duration>,
+    /// Max encoded size of a transaction that is propagated in full, larger transactions are only
+    /// announced by hash.
+    ///
+    /// `None` only limits the size of a whole broadcast message.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_full_broadcast_transaction_size: Option<usize>,
+    /// Whether new pending transactions are only announced by hash, and never propagated in full.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub announce_only: bool,
+    /// Whether blob transactions are neither announced nor propagated to peers.
+    ///
+    /// They are still served to peers that request them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disable_blob_propagation: bool,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -29,10 +55,20 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            local_transactions_rebroadcast_interval:
+                default_local_transactions_rebroadcast_interval(),
+            max_full_broadcast_transaction_size: None,
+            announce_only: false,
+            disable_blob_propagation: false,
         }
     }
 }
 
+/// Returns the default [`TransactionsManagerConfig::local_transactions_rebroadcast_interval`].
+const fn default_local_transactions_rebroadcast_interval() -> Option<Duration> {
+    Some(Duration::from_secs(DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS))
+}
+
 /// Determines how new pending transactions are propagated to other peers in full.
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -44,6 +80,8 @@ pub enum TransactionPropagationMode {
     All,
     /// Send full transactions to a maximum number of peers
     Max(usize),
+    /// Send full transactions to sqrt of current peers, scaled by the given factor.
+    ScaledSqrt(f64),
 }
 
 impl TransactionPropagationMode {
@@ -53,6 +91,9 @@ impl TransactionPropagationMode {
             Self::Sqrt => (peer_count as f64).sqrt().round() as usize,
             Self::All => peer_count,
             Self::Max(max) => peer_count.min(*max),
+            Self::ScaledSqrt(factor) => {
+                ((peer_count as f64).sqrt() * factor).round().min(peer_count as f64) as usize
+            }
         }
     }
 }
@@ -53,6 +53,12 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default interval in seconds at which pending local transactions are rebroadcast to all
+    /// peers.
+    ///
+    /// Default is 60 seconds.
+    pub const DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS: u64 = 60;
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
@@ -56,7 +56,10 @@ use reth_transaction_pool::{
     GetPooledTransactionLimit, PoolTransaction, PropagateKind, PropagatedTransactions,
     TransactionPool, ValidPoolTransaction,
 };
-use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
+use tokio::{
+    sync::{mpsc, oneshot, oneshot::error::RecvError},
+    time::{Interval, MissedTickBehavior},
+};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
@@ -255,6 +258,8 @@ pub struct TransactionsManager<Pool, N: NetworkPrimitives = EthNetworkPrimitives
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Interval at which pending local transactions are rebroadcast, if enabled.
+    local_transactions_rebroadcast_interval: Option<Interval>,
     /// How the `TransactionsManager` is configured.
     config: TransactionsManagerConfig,
     /// `TransactionsManager` metrics
@@ -288,6 +293,14 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
             .capacity_pending_pool_imports
             .increment(pending_pool_imports_info.max_pending_pool_imports as u64);
 
+        let local_transactions_rebroadcast_interval =
+            transactions_manager_config.local_transactions_rebroadcast_interval.map(|interval| {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                interval
+            });
+
         Self {
             pool,
             network,
@@ -307,6 +320,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            local_transactions_rebroadcast_interval,
             config: transactions_manager_config,
             metrics,
         }
@@ -424,6 +438,35 @@ where
         self.pool.on_propagated(propagated);
     }
 
+    /// Rebroadcasts the pending local transactions to all peers, regardless of whether they have
+    /// seen them before.
+    ///
+    /// Peers may have dropped these transactions, so this makes sure transactions that were
+    /// submitted to this node eventually get included.
+    fn rebroadcast_local_transactions(&mut self) {
+        if self.network.is_initially_syncing() || self.network.tx_gossip_disabled() {
+            return
+        }
+
+        let txs = self
+            .pool
+            .get_local_pending_transactions()
+            .into_iter()
+            .filter(|tx| tx.propagate)
+            .map(PropagateTransaction::new)
+            .collect::<Vec<_>>();
+        if txs.is_empty() {
+            return
+        }
+
+        trace!(target: "net::tx", num_txs=?txs.len(), "Rebroadcasting local transactions");
+
+        let propagated = self.propagate_transactions(txs, PropagationMode::Forced);
+
+        // notify pool so events get fired
+        self.pool.on_propagated(propagated);
+    }
+
     /// Propagate the transactions to all connected peers either as full objects or hashes.
     ///
     /// The message for new pooled hashes depends on the negotiated version of the stream.
@@ -432,7 +475,7 @@ where
     /// Note: EIP-4844 are disallowed from being broadcast in full and are only ever sent as hashes, see also <https://eips.ethereum.org/EIPS/eip-4844#networking>.
     fn propagate_transactions(
         &mut self,
-        to_propagate: Vec<PropagateTransaction>,
+        mut to_propagate: Vec<PropagateTransaction>,
         propagation_mode: PropagationMode,
     ) -> PropagatedTransactions {
         let mut propagated = PropagatedTransactions::default();
@@ -440,16 +483,32 @@ where
             return propagated
         }
 
+        if self.config.disable_blob_propagation {
+            let num_txs = to_propagate.len();
+            to_propagate.retain(|tx| !tx.transaction.is_eip4844());
+            self.metrics
+                .withheld_blob_transactions
+                .increment((num_txs - to_propagate.len()) as u64);
+        }
+
+        if let Some(max_size) = self.config.max_full_broadcast_transaction_size {
+            let oversized = to_propagate.iter().filter(|tx| tx.size > max_size).count();
+            self.metrics.announced_oversized_transactions.increment(oversized as u64);
+        }
+
         // send full transactions to a set of the connected peers based on the configured mode
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
             // determine whether to send full tx objects or hashes.
-            let mut builder = if peer_idx > max_num_full {
+            let mut builder = if self.config.announce_only || peer_idx > max_num_full {
                 PropagateTransactionsBuilder::pooled(peer.version)
             } else {
-                PropagateTransactionsBuilder::full(peer.version)
+                PropagateTransactionsBuilder::full(
+                    peer.version,
+                    self.config.max_full_broadcast_transaction_size,
+                )
             };
 
             if propagation_mode.is_forced() {
@@ -489,6 +548,10 @@ where
 
                 trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
 
+                self.metrics
+                    .propagated_transaction_hashes
+                    .increment(new_pooled_hashes.len() as u64);
+
                 // send hashes of transactions
                 self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
             }
@@ -503,6 +566,10 @@ where
 
                 trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
 
+                self.metrics
+                    .propagated_full_transactions
+                    .increment(new_full_transactions.len() as u64);
+
                 // send full transactions
                 self.network.send_transactions(*peer_id, new_full_transactions);
             }
@@ -1393,6 +1460,15 @@ where
             poll_durations.acc_pending_fetch
         );
 
+        // Rebroadcast pending local transactions if the interval elapsed.
+        if this
+            .local_transactions_rebroadcast_interval
+            .as_mut()
+            .is_some_and(|interval| interval.poll_tick(cx).is_ready())
+        {
+            this.rebroadcast_local_transactions();
+        }
+
         // Advance commands (propagate/fetch/serve txns).
         let maybe_more_commands = metered_poll_nested_stream_with_budget!(
             poll_durations.acc_cmds,
@@ -1488,9 +1564,12 @@ impl PropagateTransactionsBuilder {
         Self::Pooled(PooledTransactionsHashesBuilder::new(version))
     }
 
-    /// Create a builder that sends transactions in full and records transactions that don't fit.
-    fn full(version: EthVersion) -> Self {
-        Self::Full(FullTransactionsBuilder::new(version))
+    /// Create a builder that sends transactions in full and records transactions that don't fit,
+    /// or that are larger than the given max transaction size.
+    fn full(version: EthVersion, max_transaction_size: Option<usize>) -> Self {
+        Self::Full(
+            FullTransactionsBuilder::new(version).with_max_transaction_size(max_transaction_size),
+        )
     }
 
     /// Appends all transactions
@@ -1543,6 +1622,8 @@ struct PropagateTransactions {
 struct FullTransactionsBuilder {
     /// The soft limit to enforce for a single broadcast message of full transactions.
     total_size: usize,
+    /// Transactions larger than this are not broadcasted in full, if set.
+    max_transaction_size: Option<usize>,
     /// All transactions to be broadcasted.
     transactions: Vec<Arc<TransactionSigned>>,
     /// Transactions that didn't fit into the broadcast message
@@ -1556,11 +1637,18 @@ impl FullTransactionsBuilder {
     fn new(version: EthVersion) -> Self {
         Self {
             total_size: 0,
+            max_transaction_size: None,
             pooled: PooledTransactionsHashesBuilder::new(version),
             transactions: vec![],
         }
     }
 
+    /// Sets the max size of a transaction that is broadcasted in full.
+    const fn with_max_transaction_size(mut self, max_transaction_size: Option<usize>) -> Self {
+        self.max_transaction_size = max_transaction_size;
+        self
+    }
+
     /// Appends all transactions.
     fn extend(&mut self, txs: impl IntoIterator<Item = PropagateTransaction>) {
         for tx in txs {
@@ -1590,6 +1678,12 @@ impl FullTransactionsBuilder {
             return
         }
 
+        if self.max_transaction_size.is_some_and(|max_size| transaction.size > max_size) {
+            // transaction is too large to be broadcasted in full
+            self.pooled.push(transaction);
+            return
+        }
+
         let new_size = self.total_size + transaction.size;
         if new_size > DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE &&
             self.total_size > 0
@@ -2381,7 +2475,7 @@ mod tests {
 
     #[test]
     fn test_transaction_builder_large() {
-        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68);
+        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68, None);
         assert!(builder.is_empty());
 
         let mut factory = MockTransactionFactory::default();
@@ -2409,7 +2503,7 @@ mod tests {
 
     #[test]
     fn test_transaction_builder_eip4844() {
-        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68);
+        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68, None);
         assert!(builder.is_empty());
 
         let mut factory = MockTransactionFactory::default();
@@ -2432,6 +2526,33 @@ mod tests {
         assert_eq!(txs.len(), 1);
     }
 
+    #[test]
+    fn test_transaction_builder_max_transaction_size() {
+        let mut factory = MockTransactionFactory::default();
+        let mut tx = factory.create_eip1559();
+        tx.transaction.set_size(1_000);
+        let tx = PropagateTransaction::new(Arc::new(tx));
+
+        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68, Some(tx.size));
+        builder.push(&tx);
+        let txs = builder.build();
+        assert!(txs.pooled.is_none());
+        assert_eq!(txs.full.unwrap().len(), 1);
+
+        let mut builder = PropagateTransactionsBuilder::full(EthVersion::Eth68, Some(tx.size - 1));
+        builder.push(&tx);
+        let txs = builder.build();
+        assert!(txs.full.is_none());
+        assert_eq!(txs.pooled.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_scaled_sqrt_full_peer_count() {
+        assert_eq!(TransactionPropagationMode::ScaledSqrt(1.0).full_peer_count(100), 10);
+        assert_eq!(TransactionPropagationMode::ScaledSqrt(0.5).full_peer_count(100), 5);
+        assert_eq!(TransactionPropagationMode::ScaledSqrt(2.0).full_peer_count(4), 4);
+    }
+
     #[tokio::test]
     async fn test_propagate_full() {
         reth_tracing::init_test_tracing();
@@ -2481,4 +2602,44 @@ mod tests {
         let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
         assert!(propagated.0.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_propagate_announce_only_without_blobs() {
+        reth_tracing::init_test_tracing();
+
+        let (mut tx_manager, network) = new_tx_manager().await;
+        tx_manager.config.announce_only = true;
+        tx_manager.config.disable_blob_propagation = true;
+        let peer_id = PeerId::random();
+
+        // ensure not syncing
+        network.handle().update_sync_state(SyncState::Idle);
+
+        // mock a peer
+        let (tx, _rx) = mpsc::channel(1);
+        tx_manager.on_network_event(NetworkEvent::SessionEstablished {
+            peer_id,
+            remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            client_version: Arc::from(""),
+            capabilities: Arc::new(vec![].into()),
+            messages: PeerRequestSender::new(peer_id, tx),
+            status: Arc::new(Default::default()),
+            version: EthVersion::Eth68,
+        });
+
+        let mut factory = MockTransactionFactory::default();
+        let eip1559_tx = Arc::new(factory.create_eip1559());
+        let eip4844_tx = Arc::new(factory.create_eip4844());
+        let propagate = vec![
+            PropagateTransaction::new(eip1559_tx.clone()),
+            PropagateTransaction::new(eip4844_tx.clone()),
+        ];
+
+        let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
+        assert_eq!(propagated.0.len(), 1);
+        let prop_txs = propagated.0.get(eip1559_tx.transaction.hash()).unwrap();
+        assert_eq!(prop_txs.len(), 1);
+        assert!(prop_txs[0].is_hash());
+        assert!(!propagated.0.contains_key(eip4844_tx.transaction.hash()));
+    }
 }
@@ -33,7 +33,7 @@ async fn test_session_established_with_highest_version() {
             }
             NetworkEvent::SessionEstablished { peer_id, status, .. } => {
                 assert_eq!(handle1.peer_id(), &peer_id);
-                assert_eq!(status.version, EthVersion::Eth68);
+                assert_eq!(status.version, EthVersion::Eth69);
             }
             ev => {
                 panic!("unexpected event {ev:?}")
@@ -43,6 +43,52 @@ async fn test_session_established_with_highest_version() {
     handle.terminate().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_peer_diagnostics() {
+    reth_tracing::init_test_tracing();
+
+    let net = Testnet::create(2).await;
+
+    let mut handles = net.handles();
+    let handle0 = handles.next().unwrap();
+    let handle1 = handles.next().unwrap();
+    drop(handles);
+
+    let handle = net.spawn();
+
+    let mut events = handle0.event_listener();
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+
+    while let Some(event) = events.next().await {
+        if let NetworkEvent::SessionEstablished { .. } = event {
+            break
+        }
+    }
+
+    let diagnostics = handle0.peer_diagnostics(None).await.unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].peer_id, *handle1.peer_id());
+    assert!(diagnostics[0].disconnects.is_empty());
+    let session = diagnostics[0].session.as_ref().unwrap();
+    assert!(!session.inbound);
+    assert_eq!(session.eth_version, EthVersion::Eth69 as u8);
+    assert!(session.capabilities.contains(&"eth/69".to_string()));
+
+    handle0.disconnect_peer(*handle1.peer_id());
+    while let Some(event) = events.next().await {
+        if let NetworkEvent::SessionClosed { .. } = event {
+            break
+        }
+    }
+
+    let diagnostics = handle0.peer_diagnostics(Some(*handle1.peer_id())).await.unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].session.is_none());
+    assert_eq!(diagnostics[0].disconnects.len(), 1);
+
+    handle.terminate().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_session_established_with_different_capability() {
     reth_tracing::init_test_tracing();
@@ -81,3 +127,41 @@ async fn test_session_established_with_different_capability() {
 
     handle.terminate().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_established_with_eth68_fallback() {
+    reth_tracing::init_test_tracing();
+
+    let mut net = Testnet::create(1).await;
+
+    // a peer that doesn't support eth/69 yet
+    let p1 = PeerConfig::with_protocols(NoopProvider::default(), Some(EthVersion::Eth68.into()));
+    net.add_peer_with_config(p1).await.unwrap();
+
+    let mut handles = net.handles();
+    let handle0 = handles.next().unwrap();
+    let handle1 = handles.next().unwrap();
+    drop(handles);
+
+    let handle = net.spawn();
+
+    let mut events = handle0.event_listener().take(2);
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+
+    while let Some(event) = events.next().await {
+        match event {
+            NetworkEvent::PeerAdded(peer_id) => {
+                assert_eq!(handle1.peer_id(), &peer_id);
+            }
+            NetworkEvent::SessionEstablished { peer_id, status, .. } => {
+                assert_eq!(handle1.peer_id(), &peer_id);
+                assert_eq!(status.version, EthVersion::Eth68);
+            }
+            ev => {
+                panic!("unexpected event: {ev:?}")
+            }
+        }
+    }
+
+    handle.terminate().await;
+}
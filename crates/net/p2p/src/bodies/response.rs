@@ -3,7 +3,7 @@ use reth_primitives::{BlockBody, SealedBlock, SealedHeader};
 use reth_primitives_traits::InMemorySize;
 
 /// The block response
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, InMemorySize)]
 pub enum BlockResponse<B = BlockBody> {
     /// Full block response (with transactions or ommers)
     Full(SealedBlock<alloy_consensus::Header, B>),
@@ -41,13 +41,3 @@ impl<B> BlockResponse<B> {
         }
     }
 }
-
-impl<B: InMemorySize> InMemorySize for BlockResponse<B> {
-    #[inline]
-    fn size(&self) -> usize {
-        match self {
-            Self::Full(block) => SealedBlock::size(block),
-            Self::Empty(header) => SealedHeader::size(header),
-        }
-    }
-}
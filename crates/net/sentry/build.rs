@@ -0,0 +1,36 @@
+#![allow(missing_docs)]
+
+use tonic_build::manual::{Builder, Method, Service};
+
+/// Generates the gRPC client and server of the `sentry.Sentry` service.
+///
+/// The messages are defined by hand in `src/proto.rs`, mirroring `proto/sentry.proto`, so that
+/// building doesn't require `protoc`.
+fn main() {
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("super::{input}"))
+            .output_type(format!("super::{output}"))
+            .codec_path("tonic::codec::ProstCodec")
+    };
+
+    let service = Service::builder()
+        .name("Sentry")
+        .package("sentry")
+        .method(method("get_headers", "GetHeaders", "GetHeadersRequest", "HeadersReply").build())
+        .method(method("get_bodies", "GetBodies", "GetBodiesRequest", "BodiesReply").build())
+        .method(method("penalize_peer", "PenalizePeer", "PenalizePeerRequest", "Empty").build())
+        .method(method("set_status", "SetStatus", "SetStatusRequest", "Empty").build())
+        .method(method("set_sync_state", "SetSyncState", "SetSyncStateRequest", "Empty").build())
+        .method(method("peer_count", "PeerCount", "Empty", "PeerCountReply").build())
+        .method(method("node_info", "NodeInfo", "Empty", "NodeInfoReply").build())
+        .method(
+            method("peer_events", "PeerEvents", "Empty", "PeerEvent").server_streaming().build(),
+        )
+        .build();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    Builder::new().compile(&[service]);
+}
@@ -0,0 +1,280 @@
+//! Downloads blocks through one or more remote sentries.
+
+use crate::proto::{
+    decode_peer_id, sentry_client, status_to_request_error, Empty, GetBodiesRequest,
+    GetHeadersRequest, PenalizePeerRequest, SetStatusRequest, SetSyncStateRequest,
+};
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use alloy_rlp::Decodable;
+use futures::Future;
+use reth_network_p2p::{
+    bodies::client::{BodiesClient, BodiesFut},
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    headers::client::{HeadersClient, HeadersFut, HeadersRequest},
+    priority::Priority,
+    sync::{NetworkSyncUpdater, SyncState},
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_primitives::{BlockBody, Head};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+use tokio::sync::oneshot;
+use tonic::{
+    transport::{Channel, Endpoint},
+    Code, Response, Status,
+};
+use tracing::{debug, trace};
+
+/// The gRPC client of a single sentry.
+type GrpcClient = sentry_client::SentryClient<Channel>;
+
+/// The interval at which the number of connected peers of the sentries is refreshed.
+const PEER_COUNT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A [`BlockClient`](reth_network_p2p::BlockClient) that downloads blocks through remote
+/// sentries.
+///
+/// Requests are distributed over the sentries round-robin. If a sentry is unavailable, the
+/// request is sent to the next one, so a single sentry going down doesn't interrupt the sync.
+///
+/// The head of the execution node is announced to all sentries with the
+/// [`NetworkSyncUpdater`] implementation.
+#[derive(Debug, Clone)]
+pub struct SentryClient {
+    inner: Arc<SentryClientInner>,
+}
+
+impl SentryClient {
+    /// Creates a client for the sentries at the given endpoints, e.g. `http://10.0.0.2:9091`.
+    ///
+    /// Connections are established lazily, so the sentries don't need to be reachable yet. This
+    /// must be called from within a tokio runtime.
+    pub fn new(
+        endpoints: impl IntoIterator<Item = String>,
+    ) -> Result<Self, tonic::transport::Error> {
+        let sentries = endpoints
+            .into_iter()
+            .map(|endpoint| {
+                let channel = Endpoint::from_shared(endpoint.clone())?.connect_lazy();
+                Ok(RemoteSentry {
+                    endpoint,
+                    client: GrpcClient::new(channel),
+                    connected_peers: AtomicUsize::new(0),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let inner = Arc::new(SentryClientInner { sentries, next: AtomicUsize::new(0) });
+        tokio::spawn(update_peer_counts(Arc::downgrade(&inner)));
+
+        Ok(Self { inner })
+    }
+
+    /// Returns the endpoints of the sentries.
+    pub fn endpoints(&self) -> impl Iterator<Item = &str> + '_ {
+        self.inner.sentries.iter().map(|sentry| sentry.endpoint.as_str())
+    }
+
+    /// Sends a request to all sentries, without waiting for the responses.
+    fn broadcast<F, Fut>(&self, f: F)
+    where
+        F: Fn(GrpcClient) -> Fut,
+        Fut: Future<Output = Result<Response<Empty>, Status>> + Send + 'static,
+    {
+        for sentry in &self.inner.sentries {
+            let endpoint = sentry.endpoint.clone();
+            let request = f(sentry.client.clone());
+            tokio::spawn(async move {
+                if let Err(status) = request.await {
+                    debug!(target: "net::sentry", %endpoint, %status, "Failed to update sentry");
+                }
+            });
+        }
+    }
+}
+
+impl DownloadClient for SentryClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        // the peer is only known to the sentry that's connected to it, the others ignore it
+        self.broadcast(move |mut client| async move {
+            client.penalize_peer(PenalizePeerRequest { peer_id: peer_id.to_vec() }).await
+        });
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.inner
+            .sentries
+            .iter()
+            .map(|sentry| sentry.connected_peers.load(Ordering::Relaxed))
+            .sum()
+    }
+}
+
+impl HeadersClient for SentryClient {
+    type Header = Header;
+    type Output = HeadersFut;
+
+    /// Sends the request to the next sentry. The priority isn't forwarded to the sentry.
+    fn get_headers_with_priority(
+        &self,
+        request: HeadersRequest,
+        _priority: Priority,
+    ) -> Self::Output {
+        let inner = self.inner.clone();
+        spawn_request(async move { inner.get_headers(request).await })
+    }
+}
+
+impl BodiesClient for SentryClient {
+    type Body = BlockBody;
+    type Output = BodiesFut;
+
+    /// Sends the request to the next sentry. The priority isn't forwarded to the sentry.
+    fn get_block_bodies_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        _priority: Priority,
+    ) -> Self::Output {
+        let inner = self.inner.clone();
+        spawn_request(async move { inner.get_block_bodies(hashes).await })
+    }
+}
+
+impl NetworkSyncUpdater for SentryClient {
+    fn update_sync_state(&self, state: SyncState) {
+        let request = SetSyncStateRequest { syncing: state.is_syncing() };
+        self.broadcast(move |mut client| async move { client.set_sync_state(request).await });
+    }
+
+    fn update_status(&self, head: Head) {
+        let request = SetStatusRequest::from(head);
+        self.broadcast(|mut client| {
+            let request = request.clone();
+            async move { client.set_status(request).await }
+        });
+    }
+}
+
+/// A remote sentry.
+#[derive(Debug)]
+struct RemoteSentry {
+    /// The endpoint of the sentry.
+    endpoint: String,
+    /// The gRPC client of the sentry.
+    client: GrpcClient,
+    /// The number of peers the sentry was last connected to.
+    connected_peers: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct SentryClientInner {
+    /// All configured sentries.
+    sentries: Vec<RemoteSentry>,
+    /// The index of the sentry the next request is sent to first.
+    next: AtomicUsize,
+}
+
+impl SentryClientInner {
+    /// Sends the request to the next sentry, falling back to the other sentries if it's
+    /// unavailable.
+    ///
+    /// Returns the sentry that responded and the response.
+    async fn request<T, F, Fut>(&self, f: F) -> Result<(&RemoteSentry, T), Status>
+    where
+        F: Fn(GrpcClient) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let mut last_status = Status::unavailable("no sentries configured");
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        for i in 0..self.sentries.len() {
+            let sentry = &self.sentries[(start + i) % self.sentries.len()];
+            match f(sentry.client.clone()).await {
+                Ok(response) => return Ok((sentry, response.into_inner())),
+                Err(status) if status.code() == Code::Unavailable => {
+                    debug!(target: "net::sentry", endpoint = %sentry.endpoint, %status, "Sentry unavailable");
+                    last_status = status;
+                }
+                Err(status) => return Err(status),
+            }
+        }
+        Err(last_status)
+    }
+
+    async fn get_headers(&self, request: HeadersRequest) -> PeerRequestResult<Vec<Header>> {
+        trace!(target: "net::sentry", ?request, "Requesting headers");
+        let request = GetHeadersRequest::from(request);
+        let (sentry, reply) = self
+            .request(|mut client| {
+                let request = request.clone();
+                async move { client.get_headers(request).await }
+            })
+            .await
+            .map_err(|status| status_to_request_error(&status))?;
+        sentry.connected_peers.store(reply.connected_peers as usize, Ordering::Relaxed);
+        decode_reply(&reply.peer_id, &reply.headers)
+    }
+
+    async fn get_block_bodies(&self, hashes: Vec<B256>) -> PeerRequestResult<Vec<BlockBody>> {
+        trace!(target: "net::sentry", len = hashes.len(), "Requesting bodies");
+        let request =
+            GetBodiesRequest { hashes: hashes.iter().map(|hash| hash.to_vec()).collect() };
+        let (sentry, reply) = self
+            .request(|mut client| {
+                let request = request.clone();
+                async move { client.get_bodies(request).await }
+            })
+            .await
+            .map_err(|status| status_to_request_error(&status))?;
+        sentry.connected_peers.store(reply.connected_peers as usize, Ordering::Relaxed);
+        decode_reply(&reply.peer_id, &reply.bodies)
+    }
+}
+
+/// Decodes the peer and the RLP encoded items of a reply.
+fn decode_reply<T: Decodable>(peer_id: &[u8], items: &[Vec<u8>]) -> PeerRequestResult<Vec<T>> {
+    let peer_id = decode_peer_id(peer_id).map_err(|_| RequestError::BadResponse)?;
+    let items = items
+        .iter()
+        .map(|item| T::decode(&mut item.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| RequestError::BadResponse)?;
+    Ok(WithPeerId::new(peer_id, items))
+}
+
+/// Spawns the request, since the gRPC futures aren't `Sync`.
+fn spawn_request<T: Send + 'static>(
+    request: impl Future<Output = PeerRequestResult<T>> + Send + 'static,
+) -> Pin<Box<dyn Future<Output = PeerRequestResult<T>> + Send + Sync>> {
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let _ = tx.send(request.await);
+    });
+    Box::pin(async move { rx.await.map_err(|_| RequestError::ConnectionDropped)? })
+}
+
+/// Refreshes the number of connected peers of the sentries until the client is dropped.
+async fn update_peer_counts(inner: Weak<SentryClientInner>) {
+    let mut interval = tokio::time::interval(PEER_COUNT_INTERVAL);
+    loop {
+        interval.tick().await;
+        let Some(inner) = inner.upgrade() else { return };
+        for sentry in &inner.sentries {
+            let count = match sentry.client.clone().peer_count(Empty {}).await {
+                Ok(reply) => reply.into_inner().count as usize,
+                Err(status) => {
+                    trace!(target: "net::sentry", endpoint = %sentry.endpoint, %status, "Failed to get peer count");
+                    0
+                }
+            };
+            sentry.connected_peers.store(count, Ordering::Relaxed);
+        }
+    }
+}
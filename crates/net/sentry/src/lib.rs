@@ -0,0 +1,28 @@
+//! Sentry gRPC interface for the devp2p network.
+//!
+//! A sentry runs the devp2p network of a node and exposes it over gRPC, so the p2p layer can run
+//! on a separate host from execution. The [`SentryService`] serves the network of a node, the
+//! [`SentryClient`] downloads blocks through one or more remote sentries and keeps the head they
+//! announce to their peers up to date. With multiple sentries, a single sentry going down doesn't
+//! interrupt the sync.
+//!
+//! The interface is defined in `proto/sentry.proto`.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod proto;
+
+mod client;
+pub use client::SentryClient;
+
+mod server;
+pub use server::SentryService;
+
+/// The default port of the sentry gRPC server.
+pub const DEFAULT_SENTRY_GRPC_PORT: u16 = 9091;
@@ -0,0 +1,340 @@
+//! Messages of the `sentry.Sentry` gRPC service, see `proto/sentry.proto`.
+//!
+//! The generated [`sentry_client`] and [`sentry_server`] modules contain the gRPC client and
+//! server of the service.
+
+use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{B256, U256};
+use reth_network_api::{NetworkEvent, PeerId};
+use reth_network_p2p::{
+    error::RequestError,
+    headers::client::{HeadersDirection, HeadersRequest},
+};
+use reth_primitives::Head;
+use tonic::{Code, Status};
+
+/// The generated client and server of the service.
+#[allow(clippy::missing_const_for_fn)]
+mod generated {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/sentry.Sentry.rs"));
+}
+pub use generated::{sentry_client, sentry_server};
+
+/// An empty message.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct Empty {}
+
+/// Requests headers from a peer of the sentry.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct GetHeadersRequest {
+    /// The number of the first header, used if `start_hash` is empty.
+    #[prost(uint64, tag = "1")]
+    pub start_number: u64,
+    /// The hash of the first header.
+    #[prost(bytes = "vec", tag = "2")]
+    pub start_hash: Vec<u8>,
+    /// The maximum number of headers to return.
+    #[prost(uint64, tag = "3")]
+    pub limit: u64,
+    /// Whether to return the headers in descending order.
+    #[prost(bool, tag = "4")]
+    pub falling: bool,
+}
+
+impl From<HeadersRequest> for GetHeadersRequest {
+    fn from(request: HeadersRequest) -> Self {
+        let (start_number, start_hash) = match request.start {
+            BlockHashOrNumber::Hash(hash) => (0, hash.to_vec()),
+            BlockHashOrNumber::Number(number) => (number, Vec::new()),
+        };
+        Self {
+            start_number,
+            start_hash,
+            limit: request.limit,
+            falling: request.direction.is_falling(),
+        }
+    }
+}
+
+impl TryFrom<GetHeadersRequest> for HeadersRequest {
+    type Error = Status;
+
+    fn try_from(request: GetHeadersRequest) -> Result<Self, Self::Error> {
+        let start = if request.start_hash.is_empty() {
+            BlockHashOrNumber::Number(request.start_number)
+        } else {
+            BlockHashOrNumber::Hash(decode_hash(&request.start_hash)?)
+        };
+        let direction =
+            if request.falling { HeadersDirection::Falling } else { HeadersDirection::Rising };
+        Ok(Self { start, limit: request.limit, direction })
+    }
+}
+
+/// Headers sent by a peer of the sentry.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct HeadersReply {
+    /// The peer that sent the headers.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+    /// The RLP encoded headers.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub headers: Vec<Vec<u8>>,
+    /// The number of peers the sentry is connected to.
+    #[prost(uint64, tag = "3")]
+    pub connected_peers: u64,
+}
+
+/// Requests block bodies from a peer of the sentry.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct GetBodiesRequest {
+    /// The hashes of the blocks.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub hashes: Vec<Vec<u8>>,
+}
+
+/// Block bodies sent by a peer of the sentry.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct BodiesReply {
+    /// The peer that sent the bodies.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+    /// The RLP encoded block bodies.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub bodies: Vec<Vec<u8>>,
+    /// The number of peers the sentry is connected to.
+    #[prost(uint64, tag = "3")]
+    pub connected_peers: u64,
+}
+
+/// Reports a peer that sent a response that failed validation.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct PenalizePeerRequest {
+    /// The reported peer.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+}
+
+/// Updates the head the sentry announces to its peers.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct SetStatusRequest {
+    /// The number of the head block.
+    #[prost(uint64, tag = "1")]
+    pub number: u64,
+    /// The hash of the head block.
+    #[prost(bytes = "vec", tag = "2")]
+    pub hash: Vec<u8>,
+    /// The big endian difficulty of the head block.
+    #[prost(bytes = "vec", tag = "3")]
+    pub difficulty: Vec<u8>,
+    /// The big endian total difficulty at the head block.
+    #[prost(bytes = "vec", tag = "4")]
+    pub total_difficulty: Vec<u8>,
+    /// The timestamp of the head block.
+    #[prost(uint64, tag = "5")]
+    pub timestamp: u64,
+}
+
+impl From<Head> for SetStatusRequest {
+    fn from(head: Head) -> Self {
+        Self {
+            number: head.number,
+            hash: head.hash.to_vec(),
+            difficulty: head.difficulty.to_be_bytes_vec(),
+            total_difficulty: head.total_difficulty.to_be_bytes_vec(),
+            timestamp: head.timestamp,
+        }
+    }
+}
+
+impl TryFrom<SetStatusRequest> for Head {
+    type Error = Status;
+
+    fn try_from(request: SetStatusRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            number: request.number,
+            hash: decode_hash(&request.hash)?,
+            difficulty: decode_u256(&request.difficulty)?,
+            total_difficulty: decode_u256(&request.total_difficulty)?,
+            timestamp: request.timestamp,
+        })
+    }
+}
+
+/// Updates whether the execution node is syncing.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct SetSyncStateRequest {
+    /// Whether the execution node is syncing.
+    #[prost(bool, tag = "1")]
+    pub syncing: bool,
+}
+
+/// The number of peers the sentry is connected to.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct PeerCountReply {
+    /// The number of connected peers.
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+}
+
+/// The identity of the sentry in the network.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct NodeInfoReply {
+    /// The peer id of the sentry.
+    #[prost(bytes = "vec", tag = "1")]
+    pub peer_id: Vec<u8>,
+    /// The enode URL of the sentry.
+    #[prost(string, tag = "2")]
+    pub enode: String,
+    /// The chain id of the network.
+    #[prost(uint64, tag = "3")]
+    pub chain_id: u64,
+    /// The address the sentry listens on for incoming connections.
+    #[prost(string, tag = "4")]
+    pub listen_addr: String,
+}
+
+/// The kind of a [`PeerEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum PeerEventKind {
+    /// A session with the peer was established.
+    Connected = 0,
+    /// The session with the peer was closed.
+    Disconnected = 1,
+}
+
+/// A session the sentry established or closed.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct PeerEvent {
+    /// The [`PeerEventKind`].
+    #[prost(enumeration = "PeerEventKind", tag = "1")]
+    pub kind: i32,
+    /// The peer of the session.
+    #[prost(bytes = "vec", tag = "2")]
+    pub peer_id: Vec<u8>,
+    /// The remote address of the peer, only set for connected peers.
+    #[prost(string, tag = "3")]
+    pub remote_addr: String,
+    /// The client version of the peer, only set for connected peers.
+    #[prost(string, tag = "4")]
+    pub client_version: String,
+}
+
+impl PeerEvent {
+    /// Returns the event for the given [`NetworkEvent`], if it opens or closes a session.
+    pub fn from_network_event<R>(event: NetworkEvent<R>) -> Option<Self> {
+        match event {
+            NetworkEvent::SessionEstablished { peer_id, remote_addr, client_version, .. } => {
+                Some(Self {
+                    kind: PeerEventKind::Connected as i32,
+                    peer_id: peer_id.to_vec(),
+                    remote_addr: remote_addr.to_string(),
+                    client_version: client_version.to_string(),
+                })
+            }
+            NetworkEvent::SessionClosed { peer_id, .. } => Some(Self {
+                kind: PeerEventKind::Disconnected as i32,
+                peer_id: peer_id.to_vec(),
+                ..Default::default()
+            }),
+            NetworkEvent::PeerAdded(_) | NetworkEvent::PeerRemoved(_) => None,
+        }
+    }
+}
+
+/// Decodes a 32 byte hash.
+pub(crate) fn decode_hash(bytes: &[u8]) -> Result<B256, Status> {
+    B256::try_from(bytes).map_err(|_| Status::invalid_argument("invalid hash length"))
+}
+
+/// Decodes a big endian integer of at most 32 bytes.
+fn decode_u256(bytes: &[u8]) -> Result<U256, Status> {
+    U256::try_from_be_slice(bytes).ok_or_else(|| Status::invalid_argument("integer too large"))
+}
+
+/// Decodes a 64 byte peer id.
+pub(crate) fn decode_peer_id(bytes: &[u8]) -> Result<PeerId, Status> {
+    PeerId::try_from(bytes).map_err(|_| Status::invalid_argument("invalid peer id length"))
+}
+
+/// Returns the gRPC status a sentry responds with if a request to its peers failed.
+pub(crate) fn request_error_to_status(err: RequestError) -> Status {
+    let code = match err {
+        // the network of the sentry shut down
+        RequestError::ChannelClosed => Code::Unavailable,
+        RequestError::ConnectionDropped => Code::Aborted,
+        RequestError::UnsupportedCapability => Code::Unimplemented,
+        RequestError::Timeout => Code::DeadlineExceeded,
+        RequestError::BadResponse => Code::DataLoss,
+    };
+    Status::new(code, err.to_string())
+}
+
+/// Returns the [`RequestError`] for a failed request to a sentry.
+///
+/// Errors that are unrelated to the peers of the sentry, e.g. because the sentry isn't
+/// reachable, are mapped to the retryable [`RequestError::ConnectionDropped`], since the request
+/// can be sent to another sentry.
+pub(crate) fn status_to_request_error(status: &Status) -> RequestError {
+    match status.code() {
+        Code::Unimplemented => RequestError::UnsupportedCapability,
+        Code::DeadlineExceeded => RequestError::Timeout,
+        Code::DataLoss | Code::InvalidArgument => RequestError::BadResponse,
+        _ => RequestError::ConnectionDropped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_request_roundtrip() {
+        let requests = [
+            HeadersRequest::rising(BlockHashOrNumber::Number(100), 192),
+            HeadersRequest::falling(BlockHashOrNumber::Hash(B256::repeat_byte(1)), 1),
+        ];
+        for request in requests {
+            let decoded = HeadersRequest::try_from(GetHeadersRequest::from(request.clone()))
+                .expect("valid request");
+            assert_eq!(decoded.start, request.start);
+            assert_eq!(decoded.limit, request.limit);
+            assert_eq!(decoded.direction, request.direction);
+        }
+
+        let request = GetHeadersRequest { start_hash: vec![1; 31], ..Default::default() };
+        assert_eq!(HeadersRequest::try_from(request).unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn head_roundtrip() {
+        let head = Head {
+            number: 21_000_000,
+            hash: B256::repeat_byte(2),
+            difficulty: U256::ZERO,
+            total_difficulty: U256::from(58_750_003_716_598_352_816_469u128),
+            timestamp: 1_730_000_000,
+        };
+        assert_eq!(Head::try_from(SetStatusRequest::from(head)).unwrap(), head);
+    }
+
+    #[test]
+    fn request_errors_map_to_status() {
+        for err in [
+            RequestError::UnsupportedCapability,
+            RequestError::Timeout,
+            RequestError::BadResponse,
+            RequestError::ConnectionDropped,
+        ] {
+            assert_eq!(status_to_request_error(&request_error_to_status(err.clone())), err);
+        }
+
+        // a sentry whose network shut down can be replaced by another sentry
+        let status = request_error_to_status(RequestError::ChannelClosed);
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(status_to_request_error(&status), RequestError::ConnectionDropped);
+    }
+}
@@ -0,0 +1,155 @@
+//! Serves the network of a node over the sentry gRPC interface.
+
+use crate::proto::{
+    decode_hash, decode_peer_id, request_error_to_status,
+    sentry_server::{Sentry, SentryServer},
+    BodiesReply, Empty, GetBodiesRequest, GetHeadersRequest, HeadersReply, NodeInfoReply,
+    PeerCountReply, PeerEvent, PenalizePeerRequest, SetStatusRequest, SetSyncStateRequest,
+};
+use futures::Stream;
+use reth_network_api::{BlockDownloaderProvider, FullNetwork};
+use reth_network_p2p::{
+    bodies::client::BodiesClient,
+    download::DownloadClient,
+    headers::client::{HeadersClient, HeadersRequest},
+    sync::SyncState,
+};
+use reth_primitives::Head;
+use std::{future::Future, io, net::SocketAddr, pin::Pin};
+use tokio::{net::TcpListener, sync::oneshot};
+use tokio_stream::StreamExt;
+use tonic::{
+    transport::{server::TcpIncoming, Server},
+    Request, Response, Status,
+};
+use tracing::trace;
+
+/// The sentry gRPC service, backed by the network of a node.
+///
+/// Requests for headers and bodies are sent to the peers of the network. The head the network
+/// announces to its peers is set by the execution node, see [`Sentry::set_status`].
+#[derive(Debug, Clone)]
+pub struct SentryService<N: BlockDownloaderProvider> {
+    /// The network of the sentry.
+    network: N,
+    /// The client that sends header and body requests to the peers of the network.
+    client: N::Client,
+}
+
+impl<N: FullNetwork> SentryService<N> {
+    /// Creates the service for the given network.
+    pub async fn new(network: N) -> Result<Self, oneshot::error::RecvError> {
+        let client = network.fetch_client().await?;
+        Ok(Self { network, client })
+    }
+
+    /// Binds the gRPC server to the given address.
+    ///
+    /// Returns the local address of the server and the future that serves requests.
+    pub async fn bind(
+        self,
+        addr: SocketAddr,
+    ) -> io::Result<(SocketAddr, impl Future<Output = Result<(), tonic::transport::Error>> + Send)>
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let incoming =
+            TcpIncoming::from_listener(listener, true, None).map_err(io::Error::other)?;
+        let server =
+            Server::builder().add_service(SentryServer::new(self)).serve_with_incoming(incoming);
+        Ok((local_addr, server))
+    }
+}
+
+#[tonic::async_trait]
+impl<N: FullNetwork> Sentry for SentryService<N> {
+    async fn get_headers(
+        &self,
+        request: Request<GetHeadersRequest>,
+    ) -> Result<Response<HeadersReply>, Status> {
+        let request = HeadersRequest::try_from(request.into_inner())?;
+        trace!(target: "net::sentry", ?request, "Received headers request");
+        let (peer_id, headers) =
+            self.client.get_headers(request).await.map_err(request_error_to_status)?.split();
+        Ok(Response::new(HeadersReply {
+            peer_id: peer_id.to_vec(),
+            headers: headers.iter().map(alloy_rlp::encode).collect(),
+            connected_peers: self.network.num_connected_peers() as u64,
+        }))
+    }
+
+    async fn get_bodies(
+        &self,
+        request: Request<GetBodiesRequest>,
+    ) -> Result<Response<BodiesReply>, Status> {
+        let hashes = request
+            .into_inner()
+            .hashes
+            .iter()
+            .map(|hash| decode_hash(hash))
+            .collect::<Result<Vec<_>, _>>()?;
+        trace!(target: "net::sentry", len = hashes.len(), "Received bodies request");
+        let (peer_id, bodies) =
+            self.client.get_block_bodies(hashes).await.map_err(request_error_to_status)?.split();
+        Ok(Response::new(BodiesReply {
+            peer_id: peer_id.to_vec(),
+            bodies: bodies.iter().map(alloy_rlp::encode).collect(),
+            connected_peers: self.network.num_connected_peers() as u64,
+        }))
+    }
+
+    async fn penalize_peer(
+        &self,
+        request: Request<PenalizePeerRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let peer_id = decode_peer_id(&request.into_inner().peer_id)?;
+        self.client.report_bad_message(peer_id);
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn set_status(
+        &self,
+        request: Request<SetStatusRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let head = Head::try_from(request.into_inner())?;
+        trace!(target: "net::sentry", number = head.number, hash = %head.hash, "Updating status");
+        self.network.update_status(head);
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn set_sync_state(
+        &self,
+        request: Request<SetSyncStateRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let state = if request.into_inner().syncing { SyncState::Syncing } else { SyncState::Idle };
+        self.network.update_sync_state(state);
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn peer_count(&self, _: Request<Empty>) -> Result<Response<PeerCountReply>, Status> {
+        Ok(Response::new(PeerCountReply { count: self.network.num_connected_peers() as u64 }))
+    }
+
+    async fn node_info(&self, _: Request<Empty>) -> Result<Response<NodeInfoReply>, Status> {
+        let record = self.network.local_node_record();
+        Ok(Response::new(NodeInfoReply {
+            peer_id: record.id.to_vec(),
+            enode: record.to_string(),
+            chain_id: self.network.chain_id(),
+            listen_addr: self.network.local_addr().to_string(),
+        }))
+    }
+
+    type PeerEventsStream = Pin<Box<dyn Stream<Item = Result<PeerEvent, Status>> + Send>>;
+
+    async fn peer_events(
+        &self,
+        _: Request<Empty>,
+    ) -> Result<Response<Self::PeerEventsStream>, Status> {
+        let events = self
+            .network
+            .event_listener()
+            .filter_map(|event| PeerEvent::from_network_event(event).map(Ok));
+        Ok(Response::new(Box::pin(events)))
+    }
+}
@@ -0,0 +1,209 @@
+//! Sends `snap/1` requests to peers.
+
+use crate::message::SnapMessage;
+use alloy_primitives::map::HashSet;
+use parking_lot::RwLock;
+use reth_network_api::PeerId;
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// Default time a peer has to respond to a request.
+pub const DEFAULT_SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors of a request to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SnapRequestError {
+    /// There's no connected peer the request can be sent to.
+    #[error("no snap peers to send the request to")]
+    NoPeers,
+    /// The peer disconnected before responding.
+    #[error("snap peer {0} disconnected")]
+    Disconnected(PeerId),
+    /// The peer didn't respond in time.
+    #[error("snap peer {0} didn't respond in time")]
+    Timeout(PeerId),
+}
+
+/// A request that's sent to a peer, along with the sender of its response.
+#[derive(Debug)]
+pub(crate) struct PeerRequest {
+    pub(crate) request: SnapMessage,
+    pub(crate) response: oneshot::Sender<SnapMessage>,
+}
+
+/// Sends `snap/1` requests to the connected peers that support the protocol.
+///
+/// Peers are registered by the connections of the [`SnapHandler`](crate::SnapHandler) that the
+/// client is added to, see [`SnapHandler::with_client`](crate::SnapHandler::with_client). Requests
+/// are sent to the peers in turns.
+#[derive(Debug, Clone)]
+pub struct SnapClient {
+    inner: Arc<SnapClientInner>,
+}
+
+#[derive(Debug)]
+struct SnapClientInner {
+    peers: RwLock<Vec<(PeerId, mpsc::UnboundedSender<PeerRequest>)>>,
+    next_peer: AtomicUsize,
+    next_request_id: AtomicU64,
+    timeout: Duration,
+}
+
+impl SnapClient {
+    /// Creates a new client, giving peers the given time to respond to requests.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(SnapClientInner {
+                peers: Default::default(),
+                next_peer: AtomicUsize::new(0),
+                next_request_id: AtomicU64::new(0),
+                timeout,
+            }),
+        }
+    }
+
+    /// Returns the number of connected peers.
+    pub fn num_peers(&self) -> usize {
+        self.inner.peers.read().len()
+    }
+
+    /// Sends the request to the next connected peer that's not excluded and returns the peer along
+    /// with its response.
+    ///
+    /// The request id is replaced with a unique one. Note: the response is not validated, it may be
+    /// of another type than requested.
+    pub async fn request(
+        &self,
+        mut request: SnapMessage,
+        excluded: &HashSet<PeerId>,
+    ) -> Result<(PeerId, SnapMessage), SnapRequestError> {
+        let (peer_id, sender) = self.next_peer(excluded).ok_or(SnapRequestError::NoPeers)?;
+
+        request.set_request_id(self.inner.next_request_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = oneshot::channel();
+        sender
+            .send(PeerRequest { request, response: tx })
+            .map_err(|_| SnapRequestError::Disconnected(peer_id))?;
+
+        match tokio::time::timeout(self.inner.timeout, rx).await {
+            Ok(Ok(response)) => Ok((peer_id, response)),
+            Ok(Err(_)) => Err(SnapRequestError::Disconnected(peer_id)),
+            Err(_) => Err(SnapRequestError::Timeout(peer_id)),
+        }
+    }
+
+    fn next_peer(
+        &self,
+        excluded: &HashSet<PeerId>,
+    ) -> Option<(PeerId, mpsc::UnboundedSender<PeerRequest>)> {
+        let peers = self.inner.peers.read();
+        let start = self.inner.next_peer.fetch_add(1, Ordering::Relaxed);
+        (0..peers.len())
+            .map(|offset| &peers[(start + offset) % peers.len()])
+            .find(|(peer_id, _)| !excluded.contains(peer_id))
+            .cloned()
+    }
+
+    /// Registers a connected peer, which receives its requests on the given channel.
+    pub(crate) fn connect(&self, peer_id: PeerId, sender: mpsc::UnboundedSender<PeerRequest>) {
+        let mut peers = self.inner.peers.write();
+        peers.retain(|(id, _)| *id != peer_id);
+        peers.push((peer_id, sender));
+    }
+
+    /// Removes a peer that disconnected, unless it already reconnected on another channel.
+    pub(crate) fn disconnect(&self, peer_id: PeerId, sender: &mpsc::UnboundedSender<PeerRequest>) {
+        self.inner
+            .peers
+            .write()
+            .retain(|(id, peer_sender)| *id != peer_id || !peer_sender.same_channel(sender));
+    }
+}
+
+impl Default for SnapClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_SNAP_REQUEST_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{ByteCodes, GetByteCodes};
+
+    fn request() -> SnapMessage {
+        SnapMessage::GetByteCodes(GetByteCodes { request_id: 0, hashes: vec![], response_bytes: 0 })
+    }
+
+    /// Connects a peer that responds to all requests with empty bytecodes.
+    fn connect(client: &SnapClient) -> PeerId {
+        let peer_id = PeerId::random();
+        let (tx, mut rx) = mpsc::unbounded_channel::<PeerRequest>();
+        client.connect(peer_id, tx);
+        tokio::spawn(async move {
+            while let Some(PeerRequest { request, response }) = rx.recv().await {
+                let _ = response.send(SnapMessage::ByteCodes(ByteCodes {
+                    request_id: request.request_id(),
+                    codes: vec![],
+                }));
+            }
+        });
+        peer_id
+    }
+
+    #[tokio::test]
+    async fn requests_peers_in_turns() {
+        let client = SnapClient::default();
+        assert_eq!(
+            client.request(request(), &HashSet::default()).await,
+            Err(SnapRequestError::NoPeers)
+        );
+
+        let first = connect(&client);
+        let second = connect(&client);
+        let (peer_id, response) = client.request(request(), &HashSet::default()).await.unwrap();
+        assert_eq!(response.request_id(), 0);
+        let (other_peer_id, _) = client.request(request(), &HashSet::default()).await.unwrap();
+        assert_ne!(peer_id, other_peer_id);
+
+        let excluded = HashSet::from_iter([first]);
+        for _ in 0..2 {
+            assert_eq!(client.request(request(), &excluded).await.unwrap().0, second);
+        }
+        let excluded = HashSet::from_iter([first, second]);
+        assert_eq!(client.request(request(), &excluded).await, Err(SnapRequestError::NoPeers));
+    }
+
+    #[tokio::test]
+    async fn peer_errors() {
+        let client = SnapClient::new(Duration::from_millis(10));
+
+        // requests of a peer that never responds time out
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let peer_id = PeerId::random();
+        client.connect(peer_id, tx.clone());
+        assert_eq!(
+            client.request(request(), &HashSet::default()).await,
+            Err(SnapRequestError::Timeout(peer_id))
+        );
+        assert!(rx.try_recv().is_ok());
+
+        // the peer is only removed if it didn't reconnect
+        let (other_tx, _other_rx) = mpsc::unbounded_channel();
+        client.disconnect(peer_id, &other_tx);
+        assert_eq!(client.num_peers(), 1);
+        drop(rx);
+        assert_eq!(
+            client.request(request(), &HashSet::default()).await,
+            Err(SnapRequestError::Disconnected(peer_id))
+        );
+        client.disconnect(peer_id, &tx);
+        assert_eq!(client.num_peers(), 0);
+    }
+}
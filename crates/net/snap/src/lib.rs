@@ -0,0 +1,37 @@
+//! Snap protocol server and client.
+//!
+//! Implements the [`snap/1`](https://github.com/ethereum/devp2p/blob/master/caps/snap.md) `RLPx`
+//! subprotocol, which serves account ranges, storage ranges, bytecodes and trie nodes of the
+//! latest state to peers that snap sync, e.g. geth or nethermind. Ranges are served from the
+//! hashed state tables, range proofs and trie nodes are generated from the trie tables.
+//!
+//! Only the state of the latest persisted block is served, requests for other state roots are
+//! answered with empty responses, as specified by the protocol.
+//!
+//! The protocol is added to the network with
+//! [`IntoRlpxSubProtocol`](reth_network::protocol::IntoRlpxSubProtocol). The connected peers can
+//! be sent requests with a [`SnapClient`], which [`SnapSync`] uses to download the state of a
+//! block instead of executing all blocks up to it, see [`SnapSyncStage`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod message;
+pub use message::SnapMessage;
+
+mod server;
+pub use server::*;
+
+mod client;
+pub use client::*;
+
+mod sync;
+pub use sync::*;
+
+mod stage;
+pub use stage::*;
@@ -0,0 +1,510 @@
+//! Messages of the `snap/1` protocol.
+//!
+//! Every message is encoded as its message id followed by the RLP encoding of its payload, see
+//! the [specification](https://github.com/ethereum/devp2p/blob/master/caps/snap.md).
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    bytes::{Buf, BufMut, BytesMut},
+    Bytes, B256, U256,
+};
+use alloy_rlp::{Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
+use reth_eth_wire::{protocol::Protocol, Capability};
+use reth_trie::EMPTY_ROOT_HASH;
+
+/// Message ids of the `snap/1` protocol.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapMessageId {
+    /// Request for a range of accounts.
+    GetAccountRange = 0x00,
+    /// Response with a range of accounts.
+    AccountRange = 0x01,
+    /// Request for the storage slots of accounts.
+    GetStorageRanges = 0x02,
+    /// Response with the storage slots of accounts.
+    StorageRanges = 0x03,
+    /// Request for bytecodes by their hashes.
+    GetByteCodes = 0x04,
+    /// Response with bytecodes.
+    ByteCodes = 0x05,
+    /// Request for trie nodes by their paths.
+    GetTrieNodes = 0x06,
+    /// Response with trie nodes.
+    TrieNodes = 0x07,
+}
+
+impl TryFrom<u8> for SnapMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(id: u8) -> Result<Self, alloy_rlp::Error> {
+        Ok(match id {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return Err(alloy_rlp::Error::Custom("unknown snap message id")),
+        })
+    }
+}
+
+/// Requests the accounts of the state trie with the given root, starting at `starting_hash`.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetAccountRange {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The hash of the first account to return.
+    pub starting_hash: B256,
+    /// The hash after which no more accounts should be returned.
+    pub limit_hash: B256,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// Consecutive accounts in ascending order of their hashes, along with the proofs of the first
+/// requested hash and of the last returned account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountRange {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The accounts.
+    pub accounts: Vec<AccountData>,
+    /// The RLP encoded trie nodes proving the boundaries of the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account and its hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account.
+    pub body: SlimAccount,
+}
+
+/// An account in the slim format of the snap protocol, which encodes the empty storage root and
+/// the empty code hash as empty strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlimAccount {
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance.
+    pub balance: U256,
+    /// The root of the account storage trie.
+    pub storage_root: B256,
+    /// The hash of the account code.
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    fn slim_storage_root(&self) -> &[u8] {
+        if self.storage_root == EMPTY_ROOT_HASH {
+            &[]
+        } else {
+            self.storage_root.as_slice()
+        }
+    }
+
+    fn slim_code_hash(&self) -> &[u8] {
+        if self.code_hash == KECCAK_EMPTY {
+            &[]
+        } else {
+            self.code_hash.as_slice()
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.slim_storage_root().length() +
+            self.slim_code_hash().length()
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        self.slim_storage_root().encode(out);
+        self.slim_code_hash().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let remaining = buf.len();
+
+        let account = Self {
+            nonce: u64::decode(buf)?,
+            balance: U256::decode(buf)?,
+            storage_root: decode_slim_hash(buf, EMPTY_ROOT_HASH)?,
+            code_hash: decode_slim_hash(buf, KECCAK_EMPTY)?,
+        };
+
+        let consumed = remaining - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(account)
+    }
+}
+
+/// Decodes a hash that is encoded as an empty string if it's equal to `empty`.
+fn decode_slim_hash(buf: &mut &[u8], empty: B256) -> alloy_rlp::Result<B256> {
+    let bytes = Header::decode_bytes(buf, false)?;
+    match bytes.len() {
+        0 => Ok(empty),
+        32 => Ok(B256::from_slice(bytes)),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
+/// Requests the storage slots of the given accounts of the state trie with the given root.
+///
+/// The starting and limit hashes only apply to the first account. They're encoded as byte strings
+/// that are empty if the whole storage is requested.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetStorageRanges {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The hashes of the account addresses.
+    pub account_hashes: Vec<B256>,
+    /// The hash of the first storage slot to return.
+    pub starting_hash: Bytes,
+    /// The hash after which no more storage slots should be returned.
+    pub limit_hash: Bytes,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The storage slots of the requested accounts, along with the proof of the last storage range if
+/// it's incomplete.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageRanges {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The storage slots of each account, in the order of the request.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The RLP encoded trie nodes proving the boundaries of the last storage range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot and its hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageData {
+    /// The hash of the storage slot.
+    pub hash: B256,
+    /// The RLP encoded storage value.
+    pub body: Bytes,
+}
+
+/// Requests bytecodes by their hashes.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetByteCodes {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The code hashes.
+    pub hashes: Vec<B256>,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The requested bytecodes, in the order of the request. Unknown bytecodes are skipped.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ByteCodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests trie nodes of the state trie with the given root by their paths.
+///
+/// Every path set either holds the compact encoded path of a node of the account trie, or the
+/// hash of an account followed by the compact encoded paths of nodes of its storage trie.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetTrieNodes {
+    /// The id of the request, echoed in the response.
+    pub request_id: u64,
+    /// The root of the state trie to serve.
+    pub root_hash: B256,
+    /// The path sets.
+    pub paths: Vec<Vec<Bytes>>,
+    /// Soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The RLP encoded trie nodes, in the order of the request.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct TrieNodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// A message of the `snap/1` protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapMessage {
+    /// See [`GetAccountRange`].
+    GetAccountRange(GetAccountRange),
+    /// See [`AccountRange`].
+    AccountRange(AccountRange),
+    /// See [`GetStorageRanges`].
+    GetStorageRanges(GetStorageRanges),
+    /// See [`StorageRanges`].
+    StorageRanges(StorageRanges),
+    /// See [`GetByteCodes`].
+    GetByteCodes(GetByteCodes),
+    /// See [`ByteCodes`].
+    ByteCodes(ByteCodes),
+    /// See [`GetTrieNodes`].
+    GetTrieNodes(GetTrieNodes),
+    /// See [`TrieNodes`].
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the capability of the `snap/1` protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("snap", 1)
+    }
+
+    /// Returns the `snap/1` protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), 8)
+    }
+
+    /// Returns the id of the message.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the id of the request the message belongs to.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Sets the id of the request the message belongs to.
+    pub fn set_request_id(&mut self, request_id: u64) {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id = request_id,
+            Self::AccountRange(msg) => msg.request_id = request_id,
+            Self::GetStorageRanges(msg) => msg.request_id = request_id,
+            Self::StorageRanges(msg) => msg.request_id = request_id,
+            Self::GetByteCodes(msg) => msg.request_id = request_id,
+            Self::ByteCodes(msg) => msg.request_id = request_id,
+            Self::GetTrieNodes(msg) => msg.request_id = request_id,
+            Self::TrieNodes(msg) => msg.request_id = request_id,
+        }
+    }
+
+    /// Returns `true` if the message is a request.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange(_) |
+                Self::GetStorageRanges(_) |
+                Self::GetByteCodes(_) |
+                Self::GetTrieNodes(_)
+        )
+    }
+
+    /// Returns the empty response to the request, which is sent if the request can't be served,
+    /// e.g. because the requested state is not available.
+    ///
+    /// Returns `None` if the message is not a request.
+    pub const fn empty_response(&self) -> Option<Self> {
+        let request_id = self.request_id();
+        Some(match self {
+            Self::GetAccountRange(_) => {
+                Self::AccountRange(AccountRange { request_id, accounts: vec![], proof: vec![] })
+            }
+            Self::GetStorageRanges(_) => {
+                Self::StorageRanges(StorageRanges { request_id, slots: vec![], proof: vec![] })
+            }
+            Self::GetByteCodes(_) => Self::ByteCodes(ByteCodes { request_id, codes: vec![] }),
+            Self::GetTrieNodes(_) => Self::TrieNodes(TrieNodes { request_id, nodes: vec![] }),
+            _ => return None,
+        })
+    }
+
+    /// Encodes the message id followed by the RLP encoded payload.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the given buffer.
+    pub fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.is_empty() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let id = SnapMessageId::try_from(buf[0])?;
+        buf.advance(1);
+
+        Ok(match id {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(GetAccountRange::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(AccountRange::decode(buf)?),
+            SnapMessageId::GetStorageRanges => {
+                Self::GetStorageRanges(GetStorageRanges::decode(buf)?)
+            }
+            SnapMessageId::StorageRanges => Self::StorageRanges(StorageRanges::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(GetByteCodes::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(ByteCodes::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(GetTrieNodes::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(TrieNodes::decode(buf)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_roundtrip() {
+        let messages = [
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::with_last_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::with_last_byte(2),
+                    body: SlimAccount {
+                        nonce: 1,
+                        balance: U256::from(2),
+                        storage_root: EMPTY_ROOT_HASH,
+                        code_hash: KECCAK_EMPTY,
+                    },
+                }],
+                proof: vec![Bytes::from_static(&[0xc0])],
+            }),
+            SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 2,
+                root_hash: B256::with_last_byte(1),
+                account_hashes: vec![B256::with_last_byte(2)],
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![vec![StorageData {
+                    hash: B256::with_last_byte(3),
+                    body: Bytes::from_static(&[0x01]),
+                }]],
+                proof: vec![],
+            }),
+            SnapMessage::GetByteCodes(GetByteCodes {
+                request_id: 3,
+                hashes: vec![KECCAK_EMPTY],
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::ByteCodes(ByteCodes { request_id: 3, codes: vec![Bytes::new()] }),
+            SnapMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 4,
+                root_hash: B256::with_last_byte(1),
+                paths: vec![vec![Bytes::from_static(&[0x00])]],
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::TrieNodes(TrieNodes { request_id: 4, nodes: vec![] }),
+        ];
+
+        for message in messages {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], message.message_id() as u8);
+            assert_eq!(SnapMessage::decode(&mut &encoded[..]).unwrap(), message);
+            assert_eq!(
+                message.empty_response().map(|response| response.request_id()),
+                message.is_request().then(|| message.request_id())
+            );
+
+            let mut message = message;
+            message.set_request_id(5);
+            assert_eq!(message.request_id(), 5);
+        }
+
+        SnapMessage::decode(&mut &[0x08][..]).unwrap_err();
+        SnapMessage::decode(&mut &[][..]).unwrap_err();
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let empty = SlimAccount {
+            nonce: 0,
+            balance: U256::ZERO,
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        // empty storage root and code hash are encoded as empty strings
+        assert_eq!(alloy_rlp::encode(empty), vec![0xc4, 0x80, 0x80, 0x80, 0x80]);
+
+        let contract = SlimAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: B256::with_last_byte(3),
+            code_hash: B256::with_last_byte(4),
+        };
+        for account in [empty, contract] {
+            let encoded = alloy_rlp::encode(account);
+            assert_eq!(encoded.len(), account.length());
+            assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+        }
+    }
+}
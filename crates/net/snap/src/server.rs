@@ -0,0 +1,843 @@
+//! Handles `snap/1` connections, serving the requests of peers from the node's database and
+//! sending them the requests of a [`SnapClient`].
+
+use crate::{
+    client::PeerRequest,
+    message::{
+        AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+        GetTrieNodes, SlimAccount, SnapMessage, StorageData, StorageRanges, TrieNodes,
+    },
+    SnapClient,
+};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    bytes::BytesMut,
+    map::{HashMap, HashSet},
+    Bytes, B256,
+};
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_db::tables;
+use reth_db_api::{cursor::DbDupCursorRO, transaction::DbTx, DatabaseError};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{Direction, PeerId};
+use reth_stages_types::StageId;
+use reth_storage_api::{DBProvider, DatabaseProviderFactory, HeaderProvider};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory},
+    proof::{Proof, StorageProof},
+    Nibbles, StorageRoot, StoredNibblesSubKey,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStorageRoot, DatabaseTrieCursorFactory};
+use std::{
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tracing::{debug, trace};
+
+/// Default maximum size of a response in bytes. Requests with a larger soft limit are capped.
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Default maximum number of bytecodes served per request.
+pub const DEFAULT_MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Default maximum number of trie nodes served per request.
+pub const DEFAULT_MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Default maximum number of requests served concurrently per peer.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Limits of the snap server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapServerConfig {
+    /// Maximum size of a response in bytes.
+    pub max_response_bytes: u64,
+    /// Maximum number of bytecodes served per request.
+    pub max_code_lookups: usize,
+    /// Maximum number of trie nodes served per request.
+    pub max_trie_node_lookups: usize,
+    /// Maximum number of requests served concurrently per peer. Further requests are not read
+    /// from the connection until one of them completes.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for SnapServerConfig {
+    fn default() -> Self {
+        Self {
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            max_code_lookups: DEFAULT_MAX_CODE_LOOKUPS,
+            max_trie_node_lookups: DEFAULT_MAX_TRIE_NODE_LOOKUPS,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+/// The [`ProtocolHandler`] of the `snap/1` protocol, announcing it on every connection.
+#[derive(Clone)]
+pub struct SnapHandler<P> {
+    provider: P,
+    config: SnapServerConfig,
+    serve: bool,
+    client: Option<SnapClient>,
+}
+
+impl<P> SnapHandler<P> {
+    /// Creates a new handler serving requests from the given provider.
+    pub const fn new(provider: P, config: SnapServerConfig) -> Self {
+        Self { provider, config, serve: true, client: None }
+    }
+
+    /// Sets whether the requests of peers are served. If not, they're answered with empty
+    /// responses.
+    pub const fn with_serving(mut self, serve: bool) -> Self {
+        self.serve = serve;
+        self
+    }
+
+    /// Registers the connected peers with the given client, so that it can send them requests.
+    pub fn with_client(mut self, client: SnapClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the client the connected peers are registered with, if any.
+    pub const fn client(&self) -> Option<&SnapClient> {
+        self.client.as_ref()
+    }
+}
+
+impl<P> fmt::Debug for SnapHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapHandler")
+            .field("config", &self.config)
+            .field("serve", &self.serve)
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ProtocolHandler for SnapHandler<P>
+where
+    P: DatabaseProviderFactory + HeaderProvider + Clone + Unpin + 'static,
+{
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+}
+
+impl<P> ConnectionHandler for SnapHandler<P>
+where
+    P: DatabaseProviderFactory + HeaderProvider + Clone + Unpin + 'static,
+{
+    type Connection = SnapConnection<P>;
+
+    fn protocol(&self) -> Protocol {
+        SnapMessage::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        trace!(target: "net::snap", %peer_id, "Snap peer connected");
+        let client = self.client.map(|client| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            client.connect(peer_id, sender.clone());
+            ClientRequests { peer_id, client, sender, receiver, inflight: HashMap::default() }
+        });
+        SnapConnection {
+            conn,
+            peer_id,
+            provider: self.provider,
+            config: self.config,
+            serve: self.serve,
+            pending: FuturesUnordered::new(),
+            client,
+        }
+    }
+}
+
+/// A `snap/1` connection to a single peer, yielding the responses to its requests and the requests
+/// of the [`SnapClient`].
+///
+/// Requests are served on blocking tasks, since they're served from the database.
+pub struct SnapConnection<P> {
+    conn: ProtocolConnection,
+    peer_id: PeerId,
+    provider: P,
+    config: SnapServerConfig,
+    serve: bool,
+    pending: FuturesUnordered<JoinHandle<Option<SnapMessage>>>,
+    client: Option<ClientRequests>,
+}
+
+impl<P> fmt::Debug for SnapConnection<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("config", &self.config)
+            .field("serve", &self.serve)
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The requests of the [`SnapClient`] to a connected peer.
+///
+/// The peer is unregistered from the client once the connection is dropped.
+struct ClientRequests {
+    peer_id: PeerId,
+    client: SnapClient,
+    sender: mpsc::UnboundedSender<PeerRequest>,
+    receiver: mpsc::UnboundedReceiver<PeerRequest>,
+    /// The senders of the responses, by request id.
+    inflight: HashMap<u64, oneshot::Sender<SnapMessage>>,
+}
+
+impl Drop for ClientRequests {
+    fn drop(&mut self) {
+        self.client.disconnect(self.peer_id, &self.sender);
+    }
+}
+
+impl<P> Stream for SnapConnection<P>
+where
+    P: DatabaseProviderFactory + HeaderProvider + Clone + Unpin + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending.poll_next_unpin(cx) {
+                match response {
+                    Ok(Some(response)) => return Poll::Ready(Some(response.encoded())),
+                    Ok(None) => continue,
+                    Err(err) => {
+                        debug!(target: "net::snap", peer_id = %this.peer_id, %err, "Failed to serve request");
+                        continue
+                    }
+                }
+            }
+
+            if let Some(client) = &mut this.client {
+                if let Poll::Ready(Some(PeerRequest { request, response })) =
+                    client.receiver.poll_recv(cx)
+                {
+                    // forget the requests that timed out
+                    client.inflight.retain(|_, response| !response.is_closed());
+                    client.inflight.insert(request.request_id(), response);
+                    return Poll::Ready(Some(request.encoded()))
+                }
+            }
+
+            if this.pending.len() >= this.config.max_concurrent_requests {
+                return Poll::Pending
+            }
+
+            let Some(msg) = futures::ready!(this.conn.poll_next_unpin(cx)) else {
+                return Poll::Ready(None)
+            };
+            let msg = match SnapMessage::decode(&mut &msg[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    debug!(target: "net::snap", peer_id = %this.peer_id, %err, "Invalid message, closing connection");
+                    return Poll::Ready(None)
+                }
+            };
+
+            if !msg.is_request() {
+                // unsolicited responses are ignored
+                if let Some(response) = this
+                    .client
+                    .as_mut()
+                    .and_then(|client| client.inflight.remove(&msg.request_id()))
+                {
+                    let _ = response.send(msg);
+                }
+            } else if this.serve {
+                let provider = this.provider.clone();
+                let config = this.config;
+                this.pending.push(tokio::task::spawn_blocking(move || {
+                    serve_request(&provider, &config, msg)
+                }));
+            } else if let Some(response) = msg.empty_response() {
+                return Poll::Ready(Some(response.encoded()))
+            }
+        }
+    }
+}
+
+/// Serves a request from the state of the latest persisted block, returning the response message.
+///
+/// Requests for another state root, or requests that fail, are answered with an empty response.
+/// Returns `None` if the message is not a request.
+pub fn serve_request<P>(
+    provider: &P,
+    config: &SnapServerConfig,
+    request: SnapMessage,
+) -> Option<SnapMessage>
+where
+    P: DatabaseProviderFactory + HeaderProvider,
+{
+    let response = provider.database_provider_ro().and_then(|database| {
+        let tx = database.tx_ref();
+        // the hashed state and the tries are those of the last block that finished all stages
+        let best_block = tx
+            .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+            .unwrap_or_default()
+            .block_number;
+        let Some(header) = provider.header_by_number(best_block)? else {
+            return Ok(request.empty_response())
+        };
+        serve_state_request(tx, header.state_root, config, &request)
+    });
+
+    response.unwrap_or_else(|err| {
+        debug!(target: "net::snap", %err, request_id = request.request_id(), "Failed to serve request");
+        request.empty_response()
+    })
+}
+
+/// Serves a request from the state in the database, which has the given root.
+pub(crate) fn serve_state_request<TX: DbTx>(
+    tx: &TX,
+    state_root: B256,
+    config: &SnapServerConfig,
+    request: &SnapMessage,
+) -> ProviderResult<Option<SnapMessage>> {
+    let response = match request {
+        SnapMessage::GetAccountRange(request) if request.root_hash == state_root => {
+            SnapMessage::AccountRange(serve_account_range(tx, config, request)?)
+        }
+        SnapMessage::GetStorageRanges(request) if request.root_hash == state_root => {
+            SnapMessage::StorageRanges(serve_storage_ranges(tx, config, request)?)
+        }
+        SnapMessage::GetByteCodes(request) => {
+            SnapMessage::ByteCodes(serve_bytecodes(tx, config, request)?)
+        }
+        SnapMessage::GetTrieNodes(request) if request.root_hash == state_root => {
+            SnapMessage::TrieNodes(serve_trie_nodes(tx, config, request)?)
+        }
+        _ => return Ok(request.empty_response()),
+    };
+    Ok(Some(response))
+}
+
+fn serve_account_range<TX: DbTx>(
+    tx: &TX,
+    config: &SnapServerConfig,
+    request: &GetAccountRange,
+) -> ProviderResult<AccountRange> {
+    let response_bytes = request.response_bytes.min(config.max_response_bytes) as usize;
+    let mut cursor = DatabaseHashedCursorFactory::new(tx).hashed_account_cursor()?;
+    let mut storage_trie_cursor = tx.cursor_dup_read::<tables::StoragesTrie>()?;
+
+    let mut accounts = Vec::new();
+    let mut size = 0;
+    let mut entry = cursor.seek(request.starting_hash)?;
+    while let Some((hash, account)) = entry {
+        let body = SlimAccount {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root: storage_root(tx, &mut storage_trie_cursor, hash)?,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        };
+        size += B256::len_bytes() + alloy_rlp::Encodable::length(&body);
+        accounts.push(AccountData { hash, body });
+
+        // the first account after the limit is included, so that the range can be proven
+        if hash >= request.limit_hash || size > response_bytes {
+            break
+        }
+        entry = cursor.next()?;
+    }
+
+    let targets = std::iter::once(request.starting_hash)
+        .chain(accounts.last().map(|account| account.hash))
+        .map(|hash| (hash, HashSet::default()))
+        .collect::<HashMap<_, _>>();
+    let proof =
+        Proof::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+            .multiproof(targets)?;
+
+    Ok(AccountRange {
+        request_id: request.request_id,
+        accounts,
+        proof: proof
+            .account_subtree
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect(),
+    })
+}
+
+/// Returns the storage root of the account with the given hashed address.
+///
+/// The root is read from the root node of the storage trie. The root node isn't stored for small
+/// tries, whose root is cheap to compute from the hashed storage, and for tries that weren't
+/// written since root nodes are kept.
+fn storage_root<TX: DbTx>(
+    tx: &TX,
+    cursor: &mut impl DbDupCursorRO<tables::StoragesTrie>,
+    hashed_address: B256,
+) -> ProviderResult<B256> {
+    let stored = cursor
+        .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(Nibbles::default()))?
+        .filter(|entry| entry.nibbles.0.is_empty())
+        .and_then(|entry| entry.node.root_hash);
+    match stored {
+        Some(root) => Ok(root),
+        None => Ok(StorageRoot::from_tx_hashed(tx, hashed_address)
+            .root()
+            .map_err(DatabaseError::from)?),
+    }
+}
+
+fn serve_storage_ranges<TX: DbTx>(
+    tx: &TX,
+    config: &SnapServerConfig,
+    request: &GetStorageRanges,
+) -> ProviderResult<StorageRanges> {
+    let mut response =
+        StorageRanges { request_id: request.request_id, slots: Vec::new(), proof: Vec::new() };
+    let (Some(mut origin), Some(mut limit)) = (
+        hash_from_bytes(&request.starting_hash, B256::ZERO),
+        hash_from_bytes(&request.limit_hash, B256::repeat_byte(0xff)),
+    ) else {
+        return Ok(response)
+    };
+
+    let response_bytes = request.response_bytes.min(config.max_response_bytes) as usize;
+    let factory = DatabaseHashedCursorFactory::new(tx);
+    let mut size = 0;
+    for &account in &request.account_hashes {
+        if size >= response_bytes {
+            break
+        }
+
+        let mut cursor = factory.hashed_storage_cursor(account)?;
+        let mut slots = Vec::new();
+        let mut aborted = false;
+        let mut entry = cursor.seek(origin)?;
+        while let Some((hash, value)) = entry {
+            if size >= response_bytes {
+                aborted = true;
+                break
+            }
+            let body = Bytes::from(alloy_rlp::encode(value));
+            size += B256::len_bytes() + body.len();
+            slots.push(StorageData { hash, body });
+
+            if hash >= limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        // an incomplete storage range is the last one of the response and has to be proven
+        let proven = origin != B256::ZERO || (aborted && !slots.is_empty());
+        if proven {
+            let targets = std::iter::once(origin).chain(slots.last().map(|slot| slot.hash));
+            let proof = StorageProof::new_hashed(
+                DatabaseTrieCursorFactory::new(tx),
+                DatabaseHashedCursorFactory::new(tx),
+                account,
+            )
+            .storage_multiproof(targets.collect())?;
+            response.proof =
+                proof.subtree.into_nodes_sorted().into_iter().map(|(_, node)| node).collect();
+        }
+        if !slots.is_empty() {
+            response.slots.push(slots);
+        }
+        if proven {
+            break
+        }
+
+        // the bounds only apply to the first account
+        origin = B256::ZERO;
+        limit = B256::repeat_byte(0xff);
+    }
+
+    Ok(response)
+}
+
+fn serve_bytecodes<TX: DbTx>(
+    tx: &TX,
+    config: &SnapServerConfig,
+    request: &GetByteCodes,
+) -> ProviderResult<ByteCodes> {
+    let response_bytes = request.response_bytes.min(config.max_response_bytes) as usize;
+
+    let mut codes = Vec::new();
+    let mut size = 0;
+    for &hash in request.hashes.iter().take(config.max_code_lookups) {
+        if hash == KECCAK_EMPTY {
+            codes.push(Bytes::new());
+        } else if let Some(code) = tx.get::<tables::Bytecodes>(hash)? {
+            let code = code.original_bytes();
+            size += code.len();
+            codes.push(code);
+        }
+        if size > response_bytes {
+            break
+        }
+    }
+
+    Ok(ByteCodes { request_id: request.request_id, codes })
+}
+
+pub(crate) fn serve_trie_nodes<TX: DbTx>(
+    tx: &TX,
+    config: &SnapServerConfig,
+    request: &GetTrieNodes,
+) -> ProviderResult<TrieNodes> {
+    let response_bytes = request.response_bytes.min(config.max_response_bytes) as usize;
+
+    let mut nodes = Vec::new();
+    let mut size = 0;
+    for path_set in &request.paths {
+        if nodes.len() >= config.max_trie_node_lookups || size > response_bytes {
+            break
+        }
+
+        let Some((first, storage_paths)) = path_set.split_first() else { continue };
+        let paths = if storage_paths.is_empty() {
+            std::slice::from_ref(first)
+        } else {
+            &storage_paths[..storage_paths.len().min(config.max_trie_node_lookups - nodes.len())]
+        };
+        let paths = paths.iter().map(|path| decode_compact_path(path)).collect::<Vec<_>>();
+        let targets = paths.iter().flatten().map(path_target).collect::<HashSet<_>>();
+
+        // a node is retained in the proof of every key that it's on the path of
+        let proof_nodes = if storage_paths.is_empty() {
+            Proof::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+                .multiproof(
+                    targets.into_iter().map(|target| (target, HashSet::default())).collect(),
+                )?
+                .account_subtree
+        } else {
+            let Some(account) = hash_from_bytes(first, B256::ZERO) else { continue };
+            StorageProof::new_hashed(
+                DatabaseTrieCursorFactory::new(tx),
+                DatabaseHashedCursorFactory::new(tx),
+                account,
+            )
+            .storage_multiproof(targets)?
+            .subtree
+        };
+
+        for path in paths {
+            // unknown nodes are answered with empty bytes
+            let node = path.and_then(|path| proof_nodes.get(&path).cloned()).unwrap_or_default();
+            size += node.len();
+            nodes.push(node);
+        }
+    }
+
+    Ok(TrieNodes { request_id: request.request_id, nodes })
+}
+
+/// Returns the hash in the given bytes, left padded with zeros, or `default` if the bytes are
+/// empty.
+///
+/// Returns `None` if there are more than 32 bytes.
+fn hash_from_bytes(bytes: &[u8], default: B256) -> Option<B256> {
+    match bytes.len() {
+        0 => Some(default),
+        1..=32 => Some(B256::left_padding_from(bytes)),
+        _ => None,
+    }
+}
+
+/// Decodes a path that's compact (hex-prefix) encoded.
+///
+/// Returns `None` if the path is longer than a key.
+pub(crate) fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = path.split_first()?;
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // the flag is set for paths with an odd number of nibbles
+    if first & 0x10 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns the key of the trie that the given path is a prefix of, padded with zeros.
+fn path_target(path: &Nibbles) -> B256 {
+    B256::right_padding_from(&path.pack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{Account, Bytecode, StorageEntry};
+    use reth_provider::{test_utils::create_test_provider_factory, TrieWriter};
+    use reth_trie::StateRoot;
+    use reth_trie_db::DatabaseStateRoot;
+
+    const CODE: &[u8] = &[0x60, 0x00];
+
+    /// Writes 16 accounts, the first of them with 16 storage slots and code, and returns the
+    /// account hashes and the state root.
+    fn state<TX: DbTxMut + DbTx>(tx: &TX) -> (Vec<B256>, B256) {
+        let mut hashes = (0..16u8).map(|i| keccak256([i])).collect::<Vec<_>>();
+        hashes.sort();
+        for (i, &hash) in hashes.iter().enumerate() {
+            let account = Account {
+                nonce: i as u64,
+                balance: U256::from(i),
+                bytecode_hash: (i == 0).then(|| keccak256(CODE)),
+            };
+            tx.put::<tables::HashedAccounts>(hash, account).unwrap();
+        }
+        for slot in 0..16u8 {
+            let entry = StorageEntry { key: keccak256([slot]), value: U256::from(slot + 1) };
+            tx.put::<tables::HashedStorages>(hashes[0], entry).unwrap();
+        }
+        tx.put::<tables::Bytecodes>(keccak256(CODE), Bytecode::new_raw(Bytes::from_static(CODE)))
+            .unwrap();
+
+        let root = StateRoot::from_tx(tx).root().unwrap();
+        (hashes, root)
+    }
+
+    #[test]
+    fn serves_account_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let (hashes, root) = state(tx);
+        let config = SnapServerConfig::default();
+
+        let request = |root_hash, response_bytes| {
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes,
+            })
+        };
+
+        let Some(SnapMessage::AccountRange(response)) =
+            serve_state_request(tx, root, &config, &request(root, 1024 * 1024)).unwrap()
+        else {
+            panic!("expected account range")
+        };
+        assert_eq!(
+            response.accounts.iter().map(|account| account.hash).collect::<Vec<_>>(),
+            hashes
+        );
+        assert_ne!(response.accounts[0].body.storage_root, reth_trie::EMPTY_ROOT_HASH);
+        assert_eq!(response.accounts[0].body.code_hash, keccak256(CODE));
+        assert!(response.proof.iter().any(|node| keccak256(node) == root));
+
+        // the soft limit is exceeded by at most one account
+        let Some(SnapMessage::AccountRange(response)) =
+            serve_state_request(tx, root, &config, &request(root, 1)).unwrap()
+        else {
+            panic!("expected account range")
+        };
+        assert_eq!(response.accounts.len(), 1);
+
+        // other state roots are not served
+        assert_eq!(
+            serve_state_request(tx, root, &config, &request(B256::ZERO, 1024)).unwrap(),
+            request(B256::ZERO, 1024).empty_response()
+        );
+    }
+
+    #[test]
+    fn serves_stored_storage_roots() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let hash = keccak256([0]);
+        tx.put::<tables::HashedAccounts>(hash, Account::default()).unwrap();
+        // enough slots for the root of the storage trie to be stored
+        for slot in 0..=255u8 {
+            let entry = StorageEntry { key: keccak256([slot]), value: U256::from(1) };
+            tx.put::<tables::HashedStorages>(hash, entry).unwrap();
+        }
+        let (root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+
+        let mut cursor = tx.cursor_dup_read::<tables::StoragesTrie>().unwrap();
+        let mut entry = cursor
+            .seek_by_key_subkey(hash, StoredNibblesSubKey(Nibbles::default()))
+            .unwrap()
+            .unwrap();
+        let storage_root = StorageRoot::from_tx_hashed(tx, hash).root().unwrap();
+        assert!(entry.nibbles.0.is_empty());
+        assert_eq!(entry.node.root_hash, Some(storage_root));
+
+        // the stored root is served, without recomputing it
+        tx.delete::<tables::StoragesTrie>(hash, Some(entry.clone())).unwrap();
+        entry.node.root_hash = Some(B256::repeat_byte(1));
+        tx.put::<tables::StoragesTrie>(hash, entry).unwrap();
+        let request = SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: 1,
+            root_hash: root,
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 1024,
+        });
+        let Some(SnapMessage::AccountRange(response)) =
+            serve_state_request(tx, root, &SnapServerConfig::default(), &request).unwrap()
+        else {
+            panic!("expected account range")
+        };
+        assert_eq!(response.accounts[0].body.storage_root, B256::repeat_byte(1));
+    }
+
+    #[test]
+    fn serves_storage_ranges() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let (hashes, root) = state(tx);
+        let config = SnapServerConfig::default();
+
+        let request = |starting_hash: Bytes, response_bytes| {
+            SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 2,
+                root_hash: root,
+                account_hashes: vec![hashes[0], hashes[1]],
+                starting_hash,
+                limit_hash: Bytes::new(),
+                response_bytes,
+            })
+        };
+
+        // complete storage ranges aren't proven, empty storages are skipped
+        let Some(SnapMessage::StorageRanges(response)) =
+            serve_state_request(tx, root, &config, &request(Bytes::new(), 1024 * 1024)).unwrap()
+        else {
+            panic!("expected storage ranges")
+        };
+        assert_eq!(response.slots.len(), 1);
+        assert_eq!(response.slots[0].len(), 16);
+        assert!(response.proof.is_empty());
+
+        // incomplete storage ranges are proven
+        let Some(SnapMessage::StorageRanges(response)) =
+            serve_state_request(tx, root, &config, &request(Bytes::new(), 64)).unwrap()
+        else {
+            panic!("expected storage ranges")
+        };
+        assert_eq!(response.slots.len(), 1);
+        assert_eq!(response.slots[0].len(), 2);
+        assert!(!response.proof.is_empty());
+
+        let origin = response.slots[0][1].hash;
+        let Some(SnapMessage::StorageRanges(response)) =
+            serve_state_request(tx, root, &config, &request(origin.into(), 1024 * 1024)).unwrap()
+        else {
+            panic!("expected storage ranges")
+        };
+        assert_eq!(response.slots[0].len(), 15);
+        assert!(!response.proof.is_empty());
+    }
+
+    #[test]
+    fn serves_bytecodes_and_trie_nodes() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let (hashes, root) = state(tx);
+        let config = SnapServerConfig::default();
+
+        let request = SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: 3,
+            hashes: vec![keccak256(CODE), B256::ZERO, KECCAK_EMPTY],
+            response_bytes: 1024,
+        });
+        let Some(SnapMessage::ByteCodes(response)) =
+            serve_state_request(tx, root, &config, &request).unwrap()
+        else {
+            panic!("expected bytecodes")
+        };
+        assert_eq!(response.codes, vec![Bytes::from_static(CODE), Bytes::new()]);
+
+        let storage_root = StorageRoot::from_tx_hashed(tx, hashes[0]).root().unwrap();
+        let request = SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: 4,
+            root_hash: root,
+            paths: vec![
+                vec![Bytes::from_static(&[0x00])],
+                vec![
+                    hashes[0].into(),
+                    Bytes::from_static(&[0x00]),
+                    Bytes::from_static(&[0x00; 33]),
+                ],
+            ],
+            response_bytes: 1024 * 1024,
+        });
+        let Some(SnapMessage::TrieNodes(response)) =
+            serve_state_request(tx, root, &config, &request).unwrap()
+        else {
+            panic!("expected trie nodes")
+        };
+        assert_eq!(response.nodes.len(), 3);
+        assert_eq!(keccak256(&response.nodes[0]), root);
+        assert_eq!(keccak256(&response.nodes[1]), storage_root);
+        assert!(response.nodes[2].is_empty());
+    }
+
+    #[test]
+    fn compact_paths() {
+        for nibbles in [vec![], vec![1], vec![1, 2], vec![0xf; 64]] {
+            let path = Nibbles::from_nibbles_unchecked(&nibbles);
+            assert_eq!(decode_compact_path(&path.encode_path_leaf(false)), Some(path.clone()));
+            assert_eq!(decode_compact_path(&path.encode_path_leaf(true)), Some(path));
+        }
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(path_target(&Nibbles::from_nibbles_unchecked([1, 2, 3])), {
+            let mut target = B256::ZERO;
+            target[0] = 0x12;
+            target[1] = 0x30;
+            target
+        });
+    }
+}
@@ -0,0 +1,196 @@
+//! The stage that snap syncs the state instead of executing all blocks.
+
+use crate::{SnapClient, SnapSync, SnapSyncConfig, SnapSyncOutcome};
+use alloy_primitives::BlockNumber;
+use futures::FutureExt;
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockReader, DBProvider, HeaderProvider, ProviderError,
+    ProviderFactory, PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    EntitiesCheckpoint, ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    fmt,
+    task::{ready, Context, Poll},
+};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// The stages that are finished once the state of the pivot block is downloaded, since they only
+/// build the state, or data that's derived from executing the blocks.
+const SNAP_SYNCED_STAGES: [StageId; 9] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::PruneSenderRecovery,
+    StageId::MerkleUnwind,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::IndexStorageHistory,
+    StageId::IndexAccountHistory,
+];
+
+/// The data of the blocks up to the pivot block that's not available on a snap synced node.
+const SNAP_SYNCED_PRUNE_SEGMENTS: [PruneSegment; 4] = [
+    PruneSegment::SenderRecovery,
+    PruneSegment::Receipts,
+    PruneSegment::AccountHistory,
+    PruneSegment::StorageHistory,
+];
+
+/// The snap sync stage.
+///
+/// Downloads the state of the target block from `snap/1` peers with [`SnapSync`], instead of
+/// executing all blocks up to it. Runs after the bodies stage, on a node that didn't execute any
+/// blocks yet, and is skipped otherwise.
+///
+/// Once the state is downloaded, the checkpoints of the stages that build the state are set to
+/// the target block, which becomes the pivot block, so that only the following blocks are
+/// executed. The senders, receipts and history of the blocks up to the pivot block are marked as
+/// pruned.
+///
+/// If the download is incomplete, e.g. because the peers don't serve the state of the target
+/// block anymore, the checkpoint of the stage isn't advanced, so that the following stages don't
+/// run. The download is then continued for the target of the next pipeline run.
+pub struct SnapSyncStage<N: ProviderNodeTypes> {
+    factory: ProviderFactory<N>,
+    client: SnapClient,
+    config: SnapSyncConfig,
+    /// The running download, along with its pivot block.
+    task: Option<(BlockNumber, JoinHandle<ProviderResult<SnapSyncOutcome>>)>,
+    /// The outcome of the finished download, along with its pivot block.
+    outcome: Option<(BlockNumber, SnapSyncOutcome)>,
+}
+
+impl<N: ProviderNodeTypes> SnapSyncStage<N> {
+    /// Creates a new snap sync stage, downloading the state from the peers of the given client.
+    pub const fn new(
+        factory: ProviderFactory<N>,
+        client: SnapClient,
+        config: SnapSyncConfig,
+    ) -> Self {
+        Self { factory, client, config, task: None, outcome: None }
+    }
+
+    /// Returns the block whose state is downloaded, or `None` if the stage is skipped.
+    fn pivot(&self, input: &ExecInput) -> ProviderResult<Option<BlockNumber>> {
+        let target = input.target();
+        if target == 0 || input.checkpoint().snap_sync_stage_checkpoint().is_some() {
+            return Ok(None)
+        }
+        let executed =
+            self.factory.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        Ok((executed == 0).then_some(target))
+    }
+}
+
+impl<N: ProviderNodeTypes> fmt::Debug for SnapSyncStage<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapSyncStage")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("pivot", &self.task.as_ref().map(|(pivot, _)| pivot))
+            .field("outcome", &self.outcome)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N, Provider> Stage<Provider> for SnapSyncStage<N>
+where
+    N: ProviderNodeTypes,
+    Provider: DBProvider + StageCheckpointWriter + PruneCheckpointWriter + BlockReader,
+{
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if self.outcome.is_some() {
+            return Poll::Ready(Ok(()))
+        }
+
+        if self.task.is_none() {
+            let Some(pivot) = self.pivot(&input)? else { return Poll::Ready(Ok(())) };
+            let header = self
+                .factory
+                .header_by_number(pivot)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(pivot.into()))?;
+            info!(target: "sync::stages::snap_sync", pivot, state_root = %header.state_root, "Snap syncing state");
+
+            // the download writes to the database, so it runs on a blocking thread
+            let sync = SnapSync::new(
+                self.factory.clone(),
+                self.client.clone(),
+                self.config,
+                header.state_root,
+            );
+            let handle = tokio::runtime::Handle::current();
+            let task = tokio::task::spawn_blocking(move || handle.block_on(sync.run()));
+            self.task = Some((pivot, task));
+        }
+
+        let (pivot, task) = self.task.as_mut().expect("download is spawned");
+        let outcome = ready!(task.poll_unpin(cx));
+        let pivot = *pivot;
+        self.task = None;
+
+        let outcome = outcome.map_err(|err| StageError::Fatal(Box::new(err)))??;
+        self.outcome = Some((pivot, outcome));
+        Poll::Ready(Ok(()))
+    }
+
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        let Some((pivot, outcome)) = self.outcome.take() else {
+            // the state is built by executing the blocks, or was already snap synced
+            return Ok(ExecOutput::done(input.checkpoint().with_block_number(input.target())))
+        };
+
+        let SnapSyncOutcome::Synced { accounts } = outcome else {
+            warn!(target: "sync::stages::snap_sync", pivot, "Snap sync incomplete, continuing with the next target");
+            return Ok(ExecOutput::done(input.checkpoint()))
+        };
+
+        for stage_id in SNAP_SYNCED_STAGES {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(pivot))?;
+        }
+
+        let tx_number = provider.block_body_indices(pivot)?.map(|indices| indices.last_tx_num());
+        for segment in SNAP_SYNCED_PRUNE_SEGMENTS {
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(pivot),
+                    tx_number,
+                    prune_mode: PruneMode::Before(pivot + 1),
+                },
+            )?;
+        }
+
+        provider.save_stage_checkpoint_progress(StageId::SnapSync, Vec::new())?;
+        info!(target: "sync::stages::snap_sync", pivot, accounts, "Snap synced state");
+
+        Ok(ExecOutput::done(StageCheckpoint::new(pivot).with_snap_sync_stage_checkpoint(
+            SnapSyncCheckpoint {
+                pivot,
+                progress: EntitiesCheckpoint { processed: accounts, total: accounts },
+            },
+        )))
+    }
+
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // the state below the pivot block is never unwound, see the execution stage
+        Ok(UnwindOutput { checkpoint: input.checkpoint.with_block_number(input.unwind_to) })
+    }
+}
@@ -0,0 +1,965 @@
+//! Downloads the state of a block from `snap/1` peers.
+//!
+//! The state is downloaded in account ranges, along with the storages and bytecodes of the
+//! accounts, and written to the hashed state tables. The trie is then built from the downloaded
+//! state and healed: trie nodes that differ from the ones of the peers are found top-down and the
+//! account ranges below them are downloaded again, until the state root matches the one of the
+//! pivot block.
+//!
+//! Ranges that were downloaded for an older pivot are kept, since most of the state doesn't change
+//! between two blocks, healing fixes the rest.
+
+use crate::{
+    message::{
+        AccountData, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes, StorageData,
+        TrieNodes,
+    },
+    server::serve_trie_nodes,
+    SnapClient, SnapMessage, SnapRequestError, SnapServerConfig,
+};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    keccak256,
+    map::{HashMap, HashSet},
+    Bytes, B256, U256,
+};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use futures::{stream, StreamExt};
+use parking_lot::Mutex;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_network_api::PeerId;
+use reth_primitives::{Account, Bytecode};
+use reth_provider::{
+    providers::ProviderNodeTypes, ProviderFactory, StageCheckpointReader, StageCheckpointWriter,
+    StateChangeWriter, TrieWriter,
+};
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut, HashedPostState, HashedStorage, Nibbles, StateRoot,
+    StateRootProgress, StorageRoot, TrieNode, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Default number of account ranges that are downloaded concurrently.
+pub const DEFAULT_SNAP_SYNC_CONCURRENCY: usize = 16;
+
+/// Default soft limit of the size of the responses requested from peers, in bytes.
+pub const DEFAULT_SNAP_SYNC_RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// Default maximum number of rounds for healing the trie.
+pub const DEFAULT_SNAP_SYNC_MAX_HEAL_ROUNDS: usize = 16;
+
+/// Default number of trie nodes that are requested at once while healing the trie.
+pub const DEFAULT_SNAP_SYNC_TRIE_NODES_BATCH: usize = 256;
+
+/// Maximum number of accounts whose storages are requested at once.
+const MAX_STORAGE_ACCOUNTS: usize = 128;
+
+/// Maximum number of bytecodes that are requested at once.
+const MAX_BYTECODES: usize = 256;
+
+/// Time to wait for peers to connect, or to recover, before a request is sent again.
+const PEER_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration of [`SnapSync`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapSyncConfig {
+    /// Number of account ranges that are downloaded concurrently.
+    pub concurrency: usize,
+    /// Soft limit of the size of the responses requested from peers, in bytes.
+    pub response_bytes: u64,
+    /// Maximum number of rounds for healing the trie, before the download is considered
+    /// incomplete.
+    pub max_heal_rounds: usize,
+    /// Number of trie nodes that are requested at once while healing the trie.
+    pub trie_nodes_batch: usize,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_SNAP_SYNC_CONCURRENCY,
+            response_bytes: DEFAULT_SNAP_SYNC_RESPONSE_BYTES,
+            max_heal_rounds: DEFAULT_SNAP_SYNC_MAX_HEAL_ROUNDS,
+            trie_nodes_batch: DEFAULT_SNAP_SYNC_TRIE_NODES_BATCH,
+        }
+    }
+}
+
+/// The outcome of [`SnapSync::run`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapSyncOutcome {
+    /// The state was downloaded and its root matches the state root of the pivot block.
+    Synced {
+        /// Number of accounts in the state.
+        accounts: u64,
+    },
+    /// None of the connected peers serve the state of the pivot block anymore, or the trie
+    /// couldn't be healed. The progress is kept, so that the download can be continued for a
+    /// newer pivot block.
+    Incomplete,
+}
+
+/// A range of account hashes that's not downloaded yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct AccountHashRange {
+    /// The first account hash that's not downloaded yet.
+    next: B256,
+    /// The last account hash of the range.
+    limit: B256,
+}
+
+impl AccountHashRange {
+    /// Returns the range of the account hashes with the given prefix.
+    fn with_prefix(prefix: &Nibbles) -> Self {
+        let pad = |nibble| {
+            let mut nibbles = prefix.to_vec();
+            nibbles.resize(64, nibble);
+            B256::from_slice(&Nibbles::from_nibbles_unchecked(nibbles).pack())
+        };
+        Self { next: pad(0), limit: pad(0xf) }
+    }
+}
+
+/// The persisted progress of the download, saved as the checkpoint progress of
+/// [`StageId::SnapSync`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct SnapSyncProgress {
+    /// The account ranges that are not downloaded yet.
+    ranges: Vec<AccountHashRange>,
+    /// Whether the trie is built from the downloaded state.
+    trie_built: bool,
+}
+
+/// How the changes written while downloading an account range are tracked.
+#[derive(Debug, Clone, Copy)]
+enum Changes<'a> {
+    /// The range is the one at the given index of the persisted ranges of the initial download.
+    Download(usize),
+    /// The range is downloaded again while healing the trie, the prefix sets of the changes are
+    /// collected to update the trie.
+    Heal(&'a Mutex<TriePrefixSetsMut>),
+}
+
+/// Downloads the state of the pivot block from the peers of a [`SnapClient`].
+///
+/// The state is written to the hashed state tables and the tries, the plain state tables are
+/// cleared. See the [module](self) docs for how the state is downloaded.
+#[derive(Debug)]
+pub struct SnapSync<N: ProviderNodeTypes> {
+    factory: ProviderFactory<N>,
+    client: SnapClient,
+    config: SnapSyncConfig,
+    /// The state root of the pivot block.
+    root: B256,
+    /// The account ranges of the initial download, `None` once downloaded.
+    ranges: Mutex<Vec<Option<AccountHashRange>>>,
+    /// The peers that don't serve the state of the pivot block.
+    unavailable: Mutex<HashSet<PeerId>>,
+}
+
+impl<N: ProviderNodeTypes> SnapSync<N> {
+    /// Creates a new download of the state with the given root.
+    pub fn new(
+        factory: ProviderFactory<N>,
+        client: SnapClient,
+        config: SnapSyncConfig,
+        root: B256,
+    ) -> Self {
+        Self {
+            factory,
+            client,
+            config,
+            root,
+            ranges: Mutex::default(),
+            unavailable: Mutex::default(),
+        }
+    }
+
+    /// Downloads the state, continuing a previous download if there is one.
+    pub async fn run(&self) -> ProviderResult<SnapSyncOutcome> {
+        let progress = match self.load_progress()? {
+            Some(progress) => progress,
+            None => self.start()?,
+        };
+
+        if !progress.trie_built {
+            *self.ranges.lock() = progress.ranges.iter().copied().map(Some).collect();
+            info!(target: "sync::snap", root = %self.root, ranges = progress.ranges.len(), "Downloading state");
+            let downloaded = stream::iter(0..progress.ranges.len())
+                .map(|index| self.download_range(progress.ranges[index], Changes::Download(index)))
+                .buffer_unordered(self.config.concurrency.max(1))
+                .collect::<Vec<_>>()
+                .await;
+            for downloaded in downloaded {
+                if !downloaded? {
+                    return Ok(SnapSyncOutcome::Incomplete)
+                }
+            }
+
+            info!(target: "sync::snap", "Building trie");
+            self.build_trie()?;
+        }
+
+        for round in 0..self.config.max_heal_rounds {
+            let root = StateRoot::from_tx(self.factory.provider()?.tx_ref())
+                .root()
+                .map_err(DatabaseError::from)?;
+            if root == self.root {
+                let accounts =
+                    self.factory.provider()?.tx_ref().entries::<tables::HashedAccounts>()?;
+                info!(target: "sync::snap", %root, accounts, "Downloaded state");
+                return Ok(SnapSyncOutcome::Synced { accounts: accounts as u64 })
+            }
+
+            info!(target: "sync::snap", round, local_root = %root, root = %self.root, "Healing trie");
+            if !self.heal().await? {
+                return Ok(SnapSyncOutcome::Incomplete)
+            }
+        }
+
+        warn!(target: "sync::snap", root = %self.root, rounds = self.config.max_heal_rounds, "Failed to heal trie");
+        Ok(SnapSyncOutcome::Incomplete)
+    }
+
+    /// Loads the progress of a previous download.
+    fn load_progress(&self) -> ProviderResult<Option<SnapSyncProgress>> {
+        let buf =
+            self.factory.get_stage_checkpoint_progress(StageId::SnapSync)?.unwrap_or_default();
+        if buf.is_empty() {
+            return Ok(None)
+        }
+        Ok(Some(SnapSyncProgress::decode(&mut &buf[..]).map_err(|_| DatabaseError::Decode)?))
+    }
+
+    /// Saves the progress of the download.
+    fn save_progress(
+        &self,
+        provider: &impl StageCheckpointWriter,
+        trie_built: bool,
+    ) -> ProviderResult<()> {
+        let ranges = self.ranges.lock().iter().flatten().copied().collect();
+        provider.save_stage_checkpoint_progress(
+            StageId::SnapSync,
+            alloy_rlp::encode(SnapSyncProgress { ranges, trie_built }),
+        )
+    }
+
+    /// Clears the state of the database and splits the account hashes into the ranges that are
+    /// downloaded concurrently.
+    fn start(&self) -> ProviderResult<SnapSyncProgress> {
+        let concurrency = self.config.concurrency.max(1);
+        let step = U256::MAX / U256::from(concurrency);
+        let ranges = (0..concurrency)
+            .map(|i| {
+                let next = step * U256::from(i);
+                let limit = if i + 1 == concurrency {
+                    U256::MAX
+                } else {
+                    step * U256::from(i + 1) - U256::from(1)
+                };
+                AccountHashRange { next: next.into(), limit: limit.into() }
+            })
+            .collect::<Vec<_>>();
+
+        let provider = self.factory.provider_rw()?;
+        let tx = provider.tx_ref();
+        tx.clear::<tables::PlainAccountState>()?;
+        tx.clear::<tables::PlainStorageState>()?;
+        tx.clear::<tables::HashedAccounts>()?;
+        tx.clear::<tables::HashedStorages>()?;
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        *self.ranges.lock() = ranges.iter().copied().map(Some).collect();
+        self.save_progress(&*provider, false)?;
+        provider.commit()?;
+
+        Ok(SnapSyncProgress { ranges, trie_built: false })
+    }
+
+    /// Builds the trie from the downloaded state.
+    fn build_trie(&self) -> ProviderResult<()> {
+        let provider = self.factory.provider_rw()?;
+        provider.tx_ref().clear::<tables::AccountsTrie>()?;
+        provider.tx_ref().clear::<tables::StoragesTrie>()?;
+        provider.commit()?;
+
+        let mut intermediate_state = None;
+        loop {
+            let provider = self.factory.provider_rw()?;
+            let progress = StateRoot::from_tx(provider.tx_ref())
+                .with_intermediate_state(intermediate_state.take())
+                .root_with_progress()
+                .map_err(DatabaseError::from)?;
+            match progress {
+                StateRootProgress::Progress(state, _, updates) => {
+                    provider.write_trie_updates(&updates)?;
+                    provider.commit()?;
+                    intermediate_state = Some(*state);
+                }
+                StateRootProgress::Complete(_, _, updates) => {
+                    provider.write_trie_updates(&updates)?;
+                    self.save_progress(&*provider, true)?;
+                    provider.commit()?;
+                    return Ok(())
+                }
+            }
+        }
+    }
+
+    /// Downloads the account ranges below the trie nodes that differ from the ones of the peers
+    /// and updates the trie.
+    ///
+    /// Returns `false` if none of the peers serve the trie nodes or the accounts.
+    async fn heal(&self) -> ProviderResult<bool> {
+        let Some(prefixes) = self.find_stale_prefixes().await? else { return Ok(false) };
+        debug!(target: "sync::snap", prefixes = prefixes.len(), "Downloading stale account ranges");
+
+        // the trie is rebuilt if the node stops before it's updated
+        let provider = self.factory.provider_rw()?;
+        self.save_progress(&*provider, false)?;
+        provider.commit()?;
+
+        let prefix_sets = Mutex::new(TriePrefixSetsMut::default());
+        let downloaded = stream::iter(prefixes)
+            .map(|prefix| {
+                self.download_range(
+                    AccountHashRange::with_prefix(&prefix),
+                    Changes::Heal(&prefix_sets),
+                )
+            })
+            .buffer_unordered(self.config.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        for downloaded in downloaded {
+            if !downloaded? {
+                return Ok(false)
+            }
+        }
+
+        let provider = self.factory.provider_rw()?;
+        let (_, updates) = StateRoot::from_tx(provider.tx_ref())
+            .with_prefix_sets(prefix_sets.into_inner().freeze())
+            .root_with_updates()
+            .map_err(DatabaseError::from)?;
+        provider.write_trie_updates(&updates)?;
+        self.save_progress(&*provider, true)?;
+        provider.commit()?;
+
+        Ok(true)
+    }
+
+    /// Compares the local account trie with the one of the peers, top-down, and returns the
+    /// prefixes of the subtries that differ.
+    ///
+    /// Returns `None` if none of the peers serve the trie nodes.
+    async fn find_stale_prefixes(&self) -> ProviderResult<Option<Vec<Nibbles>>> {
+        let local_config = SnapServerConfig {
+            max_response_bytes: u64::MAX,
+            max_trie_node_lookups: usize::MAX,
+            ..Default::default()
+        };
+
+        let mut queue = vec![(Nibbles::default(), self.root)];
+        let mut stale = Vec::new();
+        while !queue.is_empty() {
+            let batch = queue.split_off(queue.len().saturating_sub(self.config.trie_nodes_batch));
+            let paths = batch
+                .iter()
+                .map(|(path, _)| vec![Bytes::copy_from_slice(&path.encode_path_leaf(false))])
+                .collect::<Vec<_>>();
+
+            let request = SnapMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 0,
+                root_hash: self.root,
+                paths: paths.clone(),
+                response_bytes: self.config.response_bytes,
+            });
+            let Some(nodes) = self
+                .request(request, |response| match response {
+                    SnapMessage::TrieNodes(TrieNodes { nodes, .. })
+                        if !nodes.is_empty() &&
+                            nodes.len() <= batch.len() &&
+                            nodes
+                                .iter()
+                                .zip(&batch)
+                                .all(|(node, (_, hash))| keccak256(node) == *hash) =>
+                    {
+                        Some(nodes)
+                    }
+                    _ => None,
+                })
+                .await
+            else {
+                return Ok(None)
+            };
+
+            let local_nodes = serve_trie_nodes(
+                self.factory.provider()?.tx_ref(),
+                &local_config,
+                &GetTrieNodes {
+                    request_id: 0,
+                    root_hash: self.root,
+                    paths: paths[..nodes.len()].to_vec(),
+                    response_bytes: u64::MAX,
+                },
+            )?
+            .nodes;
+
+            // the nodes that didn't fit into the response are requested again
+            let mut batch = batch.into_iter();
+            for ((path, _), (node, local_node)) in
+                batch.by_ref().zip(nodes.iter().zip(&local_nodes))
+            {
+                compare_trie_nodes(path, node, local_node, &mut queue, &mut stale);
+            }
+            queue.extend(batch);
+        }
+
+        Ok(Some(stale))
+    }
+
+    /// Downloads the accounts of the range, with their storages and bytecodes, and writes them to
+    /// the database.
+    ///
+    /// Local accounts of the range that are not in the state of the peers are deleted. Returns
+    /// `false` if none of the peers serve the range.
+    async fn download_range(
+        &self,
+        mut range: AccountHashRange,
+        changes: Changes<'_>,
+    ) -> ProviderResult<bool> {
+        loop {
+            let request = SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 0,
+                root_hash: self.root,
+                starting_hash: range.next,
+                limit_hash: range.limit,
+                response_bytes: self.config.response_bytes,
+            });
+            // peers that don't serve the state respond with neither accounts nor a proof
+            let Some(accounts) = self
+                .request(request, |response| match response {
+                    SnapMessage::AccountRange(response)
+                        if (!response.accounts.is_empty() || !response.proof.is_empty()) &&
+                            response.accounts.first().is_none_or(|a| a.hash >= range.next) &&
+                            response.accounts.windows(2).all(|w| w[0].hash < w[1].hash) =>
+                    {
+                        Some(response.accounts)
+                    }
+                    _ => None,
+                })
+                .await
+            else {
+                return Ok(false)
+            };
+
+            // the range is complete once an account at or after the limit is returned, or none
+            let (end, remaining) = match accounts.last() {
+                Some(last) if last.hash < range.limit => {
+                    (last.hash, Some(AccountHashRange { next: increment(last.hash), ..range }))
+                }
+                _ => (range.limit, None),
+            };
+            let accounts = accounts
+                .into_iter()
+                .take_while(|account| account.hash <= range.limit)
+                .collect::<Vec<_>>();
+
+            let Some((state, codes)) =
+                self.download_accounts(range.next, end, accounts, changes, range).await?
+            else {
+                return Ok(false)
+            };
+            self.write_state(state, codes, changes, remaining)?;
+
+            match remaining {
+                Some(remaining) => range = remaining,
+                None => return Ok(true),
+            }
+        }
+    }
+
+    /// Downloads the storages and bytecodes of the accounts of a range that are not in the
+    /// database yet, and returns the state of the range.
+    ///
+    /// Returns `None` if none of the peers serve the storages or bytecodes.
+    async fn download_accounts(
+        &self,
+        from: B256,
+        to: B256,
+        accounts: Vec<AccountData>,
+        changes: Changes<'_>,
+        range: AccountHashRange,
+    ) -> ProviderResult<Option<(HashedPostState, Vec<(B256, Bytes)>)>> {
+        let mut state = HashedPostState::default();
+        let mut storage_accounts = Vec::new();
+        let mut code_hashes = HashSet::<B256>::default();
+        {
+            let provider = self.factory.provider()?;
+            let tx = provider.tx_ref();
+
+            let remote = accounts.iter().map(|account| account.hash).collect::<HashSet<_>>();
+            let mut cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+            let mut entry = cursor.seek(from)?;
+            while let Some((hash, _)) = entry.filter(|(hash, _)| *hash <= to) {
+                if !remote.contains(&hash) {
+                    state.accounts.insert(hash, None);
+                    state.storages.insert(hash, HashedStorage::new(true));
+                }
+                entry = cursor.next()?;
+            }
+
+            for AccountData { hash, body } in accounts {
+                // only storages that differ from the local ones are downloaded
+                let storage_root =
+                    StorageRoot::from_tx_hashed(tx, hash).root().map_err(DatabaseError::from)?;
+                if body.storage_root == EMPTY_ROOT_HASH && storage_root != EMPTY_ROOT_HASH {
+                    state.storages.insert(hash, HashedStorage::new(true));
+                } else if body.storage_root != storage_root {
+                    storage_accounts.push(hash);
+                }
+
+                if body.code_hash != KECCAK_EMPTY &&
+                    tx.get::<tables::Bytecodes>(body.code_hash)?.is_none()
+                {
+                    code_hashes.insert(body.code_hash);
+                }
+
+                let account = Account {
+                    nonce: body.nonce,
+                    balance: body.balance,
+                    bytecode_hash: (body.code_hash != KECCAK_EMPTY).then_some(body.code_hash),
+                };
+                state.accounts.insert(hash, Some(account));
+            }
+        }
+
+        let Some(storages) = self.download_storages(&storage_accounts, changes, range).await?
+        else {
+            return Ok(None)
+        };
+        state.storages.extend(storages);
+
+        let Some(codes) = self.download_bytecodes(code_hashes.into_iter().collect()).await else {
+            return Ok(None)
+        };
+
+        Ok(Some((state, codes)))
+    }
+
+    /// Downloads the storages of the given accounts.
+    ///
+    /// Storages that don't fit into a single response are written to the database in chunks
+    /// instead of being returned. Returns `None` if none of the peers serve the storages.
+    async fn download_storages(
+        &self,
+        mut accounts: &[B256],
+        changes: Changes<'_>,
+        range: AccountHashRange,
+    ) -> ProviderResult<Option<HashMap<B256, HashedStorage>>> {
+        let mut storages = HashMap::default();
+        while !accounts.is_empty() {
+            let batch = &accounts[..accounts.len().min(MAX_STORAGE_ACCOUNTS)];
+            let request = SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 0,
+                root_hash: self.root,
+                account_hashes: batch.to_vec(),
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: self.config.response_bytes,
+            });
+            // the last storage of the response is incomplete if it's proven
+            let Some((slots, incomplete)) = self
+                .request(request, |response| match response {
+                    SnapMessage::StorageRanges(response)
+                        if !response.slots.is_empty() && response.slots.len() <= batch.len() =>
+                    {
+                        let slots = response
+                            .slots
+                            .iter()
+                            .map(|slots| decode_slots(slots))
+                            .collect::<Option<Vec<_>>>()?;
+                        Some((slots, !response.proof.is_empty()))
+                    }
+                    _ => None,
+                })
+                .await
+            else {
+                return Ok(None)
+            };
+
+            accounts = &accounts[slots.len()..];
+            let last = slots.len() - 1;
+            for (i, (&account, slots)) in batch.iter().zip(slots).enumerate() {
+                if incomplete && i == last {
+                    if !self.download_large_storage(account, slots, changes, range).await? {
+                        return Ok(None)
+                    }
+                } else {
+                    storages.insert(account, HashedStorage::from_iter(true, slots));
+                }
+            }
+        }
+
+        Ok(Some(storages))
+    }
+
+    /// Downloads a storage that doesn't fit into a single response, starting after the given
+    /// slots, and writes it to the database in chunks.
+    ///
+    /// Returns `false` if none of the peers serve the storage.
+    async fn download_large_storage(
+        &self,
+        account: B256,
+        mut slots: Vec<(B256, U256)>,
+        changes: Changes<'_>,
+        range: AccountHashRange,
+    ) -> ProviderResult<bool> {
+        let mut wiped = true;
+        loop {
+            let last = slots.last().map(|(hash, _)| *hash);
+            let chunk = HashedStorage::from_iter(wiped, slots);
+            self.write_state(
+                HashedPostState::from_hashed_storage(account, chunk),
+                Vec::new(),
+                changes,
+                Some(range),
+            )?;
+            wiped = false;
+
+            let Some(next) = last.filter(|last| *last != B256::repeat_byte(0xff)).map(increment)
+            else {
+                return Ok(true)
+            };
+            let request = SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 0,
+                root_hash: self.root,
+                account_hashes: vec![account],
+                starting_hash: next.into(),
+                limit_hash: Bytes::new(),
+                response_bytes: self.config.response_bytes,
+            });
+            // ranges that don't start at the first slot are always proven, the end of the storage
+            // is reached once no more slots are returned
+            let Some(next_slots) = self
+                .request(request, |response| match response {
+                    SnapMessage::StorageRanges(response)
+                        if response.slots.len() <= 1 &&
+                            (!response.slots.is_empty() || !response.proof.is_empty()) =>
+                    {
+                        decode_slots(response.slots.first().map_or(&[], |slots| slots))
+                            .filter(|slots| slots.first().is_none_or(|(hash, _)| *hash >= next))
+                    }
+                    _ => None,
+                })
+                .await
+            else {
+                return Ok(false)
+            };
+            if next_slots.is_empty() {
+                return Ok(true)
+            }
+            slots = next_slots;
+        }
+    }
+
+    /// Downloads the bytecodes with the given hashes.
+    ///
+    /// Returns `None` if none of the peers serve the bytecodes.
+    async fn download_bytecodes(&self, mut hashes: Vec<B256>) -> Option<Vec<(B256, Bytes)>> {
+        let mut codes = Vec::with_capacity(hashes.len());
+        while !hashes.is_empty() {
+            let requested = hashes[..hashes.len().min(MAX_BYTECODES)].to_vec();
+            let request = SnapMessage::GetByteCodes(GetByteCodes {
+                request_id: 0,
+                hashes: requested.clone(),
+                response_bytes: self.config.response_bytes,
+            });
+            // the codes are returned in the order of the request, but may be skipped
+            let received = self
+                .request(request, |response| match response {
+                    SnapMessage::ByteCodes(response) => {
+                        let received = response
+                            .codes
+                            .into_iter()
+                            .map(|code| (keccak256(&code), code))
+                            .filter(|(hash, _)| requested.contains(hash))
+                            .collect::<HashMap<_, _>>();
+                        (!received.is_empty()).then_some(received)
+                    }
+                    _ => None,
+                })
+                .await?;
+
+            hashes.retain(|hash| !received.contains_key(hash));
+            codes.extend(received);
+        }
+        Some(codes)
+    }
+
+    /// Writes the state and the bytecodes to the database and tracks the changes. The remaining
+    /// part of the range is saved as progress of the initial download, in the same transaction.
+    fn write_state(
+        &self,
+        state: HashedPostState,
+        codes: Vec<(B256, Bytes)>,
+        changes: Changes<'_>,
+        remaining: Option<AccountHashRange>,
+    ) -> ProviderResult<()> {
+        if let Changes::Heal(prefix_sets) = changes {
+            prefix_sets.lock().extend(state.construct_prefix_sets());
+        }
+
+        let provider = self.factory.provider_rw()?;
+        provider.write_hashed_state(&state.into_sorted())?;
+        for (hash, code) in codes {
+            provider.tx_ref().put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+        }
+
+        if let Changes::Download(index) = changes {
+            self.ranges.lock()[index] = remaining;
+            self.save_progress(&*provider, false)?;
+        }
+
+        provider.commit()?;
+        Ok(())
+    }
+
+    /// Sends the request to the connected peers until the response of one of them is valid.
+    ///
+    /// Peers that respond with an invalid response, e.g. because they don't serve the state of
+    /// the pivot block, are not asked again. Returns `None` if none of the connected peers serve
+    /// the request.
+    async fn request<T>(
+        &self,
+        request: SnapMessage,
+        mut validate: impl FnMut(SnapMessage) -> Option<T>,
+    ) -> Option<T> {
+        let mut excluded = self.unavailable.lock().clone();
+        loop {
+            match self.client.request(request.clone(), &excluded).await {
+                Ok((peer_id, response)) => {
+                    if let Some(response) = validate(response) {
+                        return Some(response)
+                    }
+                    debug!(target: "sync::snap", %peer_id, "Peer doesn't serve the state");
+                    self.unavailable.lock().insert(peer_id);
+                    excluded.insert(peer_id);
+                }
+                Err(SnapRequestError::NoPeers) => {
+                    let unavailable = self.unavailable.lock().clone();
+                    if !unavailable.is_empty() && excluded.len() == unavailable.len() {
+                        return None
+                    }
+                    // wait for peers to connect, or for the peers that failed to recover
+                    excluded = unavailable;
+                    tokio::time::sleep(PEER_RETRY_INTERVAL).await;
+                }
+                Err(
+                    err @ (SnapRequestError::Disconnected(peer_id) |
+                    SnapRequestError::Timeout(peer_id)),
+                ) => {
+                    debug!(target: "sync::snap", %peer_id, %err, "Request failed");
+                    excluded.insert(peer_id);
+                }
+            }
+        }
+    }
+}
+
+/// Compares a trie node of the peers with the local node at the same path.
+///
+/// Children that differ are queued for comparison if they're hashed, the subtries that can't be
+/// compared any further are collected as stale.
+fn compare_trie_nodes(
+    path: Nibbles,
+    node: &[u8],
+    local_node: &[u8],
+    queue: &mut Vec<(Nibbles, B256)>,
+    stale: &mut Vec<Nibbles>,
+) {
+    if node == local_node {
+        return
+    }
+
+    let decode = |node: &[u8]| TrieNode::decode(&mut &node[..]).ok();
+    match (decode(node), decode(local_node)) {
+        (Some(TrieNode::Branch(branch)), Some(TrieNode::Branch(local_branch))) => {
+            for ((nibble, child), (_, local_child)) in
+                branch.as_ref().children().zip(local_branch.as_ref().children())
+            {
+                if child == local_child {
+                    continue
+                }
+                let mut child_path = path.clone();
+                child_path.push(nibble);
+                match child.and_then(|child| child.as_hash()) {
+                    Some(hash) => queue.push((child_path, hash)),
+                    None => stale.push(child_path),
+                }
+            }
+        }
+        (Some(TrieNode::Extension(extension)), Some(TrieNode::Extension(local_extension)))
+            if extension.key == local_extension.key =>
+        {
+            let child_path = path.join(&extension.key);
+            match extension.child.as_hash() {
+                Some(hash) => queue.push((child_path, hash)),
+                None => stale.push(child_path),
+            }
+        }
+        _ => stale.push(path),
+    }
+}
+
+/// Decodes the storage slots of a response, which have to be ordered.
+fn decode_slots(slots: &[StorageData]) -> Option<Vec<(B256, U256)>> {
+    if !slots.windows(2).all(|w| w[0].hash < w[1].hash) {
+        return None
+    }
+    slots.iter().map(|slot| Some((slot.hash, U256::decode(&mut &slot.body[..]).ok()?))).collect()
+}
+
+/// Returns the hash that follows the given one. The hash must not be the maximum.
+fn increment(hash: B256) -> B256 {
+    (U256::from_be_bytes(hash.0) + U256::from(1)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::PeerRequest, server::serve_state_request};
+    use reth_primitives::StorageEntry;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use tokio::sync::mpsc;
+
+    /// Writes 64 accounts, the first of them with 64 storage slots and code, and returns the
+    /// state root.
+    fn source_state<TX: DbTxMut + DbTx>(tx: &TX) -> B256 {
+        let mut hashes = (0..64u8).map(|i| keccak256([i])).collect::<Vec<_>>();
+        hashes.sort();
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        for (i, &hash) in hashes.iter().enumerate() {
+            let account = Account {
+                nonce: i as u64,
+                balance: U256::from(i),
+                bytecode_hash: (i == 0).then(|| keccak256(&code)),
+            };
+            tx.put::<tables::HashedAccounts>(hash, account).unwrap();
+        }
+        for slot in 0..64u8 {
+            let entry = StorageEntry { key: keccak256([slot]), value: U256::from(slot + 1) };
+            tx.put::<tables::HashedStorages>(hashes[0], entry).unwrap();
+        }
+        tx.put::<tables::Bytecodes>(keccak256(&code), Bytecode::new_raw(code)).unwrap();
+
+        StateRoot::from_tx(tx).root().unwrap()
+    }
+
+    /// Connects a peer that serves requests from the given database.
+    fn connect<N: ProviderNodeTypes>(client: &SnapClient, factory: ProviderFactory<N>, root: B256) {
+        let (tx, mut rx) = mpsc::unbounded_channel::<PeerRequest>();
+        client.connect(PeerId::random(), tx);
+        tokio::spawn(async move {
+            let config = SnapServerConfig::default();
+            while let Some(PeerRequest { request, response }) = rx.recv().await {
+                let provider = factory.provider().unwrap();
+                let message =
+                    serve_state_request(provider.tx_ref(), root, &config, &request).unwrap();
+                let _ = response.send(message.unwrap());
+            }
+        });
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn downloads_and_heals_state() {
+        let source = create_test_provider_factory();
+        let provider = source.provider_rw().unwrap();
+        let root = source_state(provider.tx_ref());
+        provider.commit().unwrap();
+
+        let client = SnapClient::default();
+        connect(&client, source, root);
+
+        // small responses, so that ranges and storages are downloaded in several requests
+        let config = SnapSyncConfig {
+            concurrency: 4,
+            response_bytes: 256,
+            max_heal_rounds: 4,
+            trie_nodes_batch: 4,
+        };
+        let factory = create_test_provider_factory();
+        let sync = SnapSync::new(factory.clone(), client.clone(), config, root);
+        assert_eq!(sync.run().await.unwrap(), SnapSyncOutcome::Synced { accounts: 64 });
+        assert_eq!(
+            factory.provider().unwrap().tx_ref().entries::<tables::HashedStorages>().unwrap(),
+            64
+        );
+
+        // local changes are healed
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let mut cursor = tx.cursor_read::<tables::HashedAccounts>().unwrap();
+        let (first, _) = cursor.first().unwrap().unwrap();
+        let (last, _) = cursor.last().unwrap().unwrap();
+        drop(cursor);
+        tx.delete::<tables::HashedStorages>(first, None).unwrap();
+        tx.put::<tables::HashedAccounts>(last, Account::default()).unwrap();
+        tx.put::<tables::HashedAccounts>(B256::ZERO, Account::default()).unwrap();
+        tx.clear::<tables::AccountsTrie>().unwrap();
+        tx.clear::<tables::StoragesTrie>().unwrap();
+        let (local_root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        assert_ne!(local_root, root);
+        provider.write_trie_updates(&updates).unwrap();
+        provider.commit().unwrap();
+
+        let sync = SnapSync::new(factory.clone(), client, config, root);
+        assert_eq!(sync.run().await.unwrap(), SnapSyncOutcome::Synced { accounts: 64 });
+        assert_eq!(
+            factory.provider().unwrap().tx_ref().entries::<tables::HashedStorages>().unwrap(),
+            64
+        );
+    }
+
+    #[tokio::test]
+    async fn incomplete_without_state() {
+        let client = SnapClient::default();
+        connect(&client, create_test_provider_factory(), B256::ZERO);
+
+        let sync = SnapSync::new(
+            create_test_provider_factory(),
+            client,
+            SnapSyncConfig::default(),
+            B256::repeat_byte(1),
+        );
+        assert_eq!(sync.run().await.unwrap(), SnapSyncOutcome::Incomplete);
+    }
+
+    #[test]
+    fn prefix_ranges() {
+        let range =
+            AccountHashRange::with_prefix(&Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc]));
+        let mut next = B256::ZERO;
+        next[0] = 0xab;
+        next[1] = 0xc0;
+        let mut limit = B256::repeat_byte(0xff);
+        limit[0] = 0xab;
+        limit[1] = 0xcf;
+        assert_eq!(range, AccountHashRange { next, limit });
+        assert_eq!(increment(limit), {
+            let mut hash = B256::ZERO;
+            hash[0] = 0xab;
+            hash[1] = 0xd0;
+            hash
+        });
+    }
+}
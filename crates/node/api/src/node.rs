@@ -4,6 +4,7 @@ use crate::ConfigureEvm;
 use alloy_consensus::Header;
 use alloy_rpc_types_engine::JwtSecret;
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
+use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_evm::execute::BlockExecutorProvider;
 use reth_network_api::FullNetwork;
@@ -96,6 +97,8 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub node: N,
     /// Node configuration.
     pub config: &'a NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+    /// Node configuration from the `reth.toml` file.
+    pub toml_config: &'a Config,
     /// Handle to the beacon consensus engine.
     pub beacon_engine_handle:
         BeaconConsensusEngineHandle<<N::Types as NodeTypesWithEngine>::Engine>,
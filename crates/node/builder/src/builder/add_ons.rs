@@ -1,8 +1,14 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
+use std::sync::Arc;
+
+use reth_network::protocol::RlpxSubProtocol;
 use reth_node_api::{FullNodeComponents, NodeAddOns};
 
-use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
+use crate::{exex::InstalledExEx, hooks::NodeHooks};
+
+/// Creates an additional `RLPx` subprotocol whenever the network of the node is configured.
+pub type RlpxSubProtocolFactory = Arc<dyn Fn() -> RlpxSubProtocol + Send + Sync>;
 
 /// Additional node extensions.
 ///
@@ -11,7 +17,9 @@ pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     /// Additional `NodeHooks` that are called at specific points in the node's launch lifecycle.
     pub hooks: NodeHooks<Node, AddOns>,
     /// The `ExExs` (execution extensions) of the node.
-    pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    pub exexs: Vec<(String, InstalledExEx<Node>)>,
+    /// The additional `RLPx` subprotocols the network of the node offers to peers.
+    pub rlpx_sub_protocols: Vec<RlpxSubProtocolFactory>,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
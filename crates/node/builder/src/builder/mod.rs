@@ -4,10 +4,11 @@
 
 use crate::{
     common::WithConfigs,
-    components::NodeComponentsBuilder,
+    components::{pool_runtime_config, reload_pool_config, NodeComponentsBuilder},
+    exex::ExExRestartPolicy,
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
-    DefaultNodeLauncher, LaunchNode, Node, NodeHandle,
+    DefaultNodeLauncher, LaunchNode, Node, NodeHandle, RlpxSubProtocolFactory,
 };
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
@@ -17,9 +18,10 @@ use reth_db_api::{
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
 use reth_exex::ExExContext;
+use reth_light_proofs::LightProofsHandler;
 use reth_network::{
-    transactions::TransactionsManagerConfig, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
-    NetworkHandle, NetworkManager,
+    protocol::IntoRlpxSubProtocol, transactions::TransactionsManagerConfig, NetworkBuilder,
+    NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
 use reth_node_api::{
     FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes, NodeTypesWithDBAdapter,
@@ -31,9 +33,15 @@ use reth_node_core::{
     node_config::NodeConfig,
     primitives::Head,
 };
+use reth_primitives::kzg::KzgSettings;
 use reth_provider::{providers::BlockchainProvider, ChainSpecProvider, FullProvider};
-use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_snap::{SnapClient, SnapHandler};
+use reth_tasks::{pool::BlockingTaskPool, TaskExecutor};
+use reth_transaction_pool::{
+    blobstore::BlobArchiveConfig,
+    validate::{verify_pending_blob_transactions, KzgSettingsHandle},
+    AdmissionLogConfig, PoolConfig, TransactionPool,
+};
 use revm_primitives::EnvKzgSettings;
 use secp256k1::SecretKey;
 use std::sync::Arc;
@@ -487,6 +495,20 @@ where
         Self { builder: self.builder.extend_rpc_modules(hook), task_executor: self.task_executor }
     }
 
+    /// Adds an additional `RLPx` subprotocol that the network of the node offers to peers.
+    ///
+    /// This allows custom capabilities, e.g. rollup specific gossip, without a custom network
+    /// builder. See also [`NodeBuilderWithComponents::add_rlpx_sub_protocol`].
+    pub fn add_rlpx_sub_protocol<P>(self, protocol: P) -> Self
+    where
+        P: IntoRlpxSubProtocol + Clone + Send + Sync + 'static,
+    {
+        Self {
+            builder: self.builder.add_rlpx_sub_protocol(protocol),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node.
     ///
     /// # Note
@@ -504,6 +526,29 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node that is handled according to the
+    /// given [`ExExRestartPolicy`] when it crashes.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_restart_policy<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        exex: F,
+        restart_policy: ExExRestartPolicy,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Clone + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_restart_policy(exex_id, exex, restart_policy),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node if the condition is true.
     ///
     /// # Note
@@ -574,17 +619,34 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// The client sending `snap/1` requests to the peers of the network.
+    pub(crate) snap_client: SnapClient,
+    /// The additional `RLPx` subprotocols added to the node's network.
+    pub(crate) rlpx_sub_protocols: Vec<RlpxSubProtocolFactory>,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Create a new instance of [`BuilderContext`]
-    pub const fn new(
+    pub fn new(
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            snap_client: SnapClient::default(),
+            rlpx_sub_protocols: Vec::new(),
+        }
+    }
+
+    /// Sets the additional `RLPx` subprotocols that are added to the network of the node.
+    pub fn with_rlpx_sub_protocols(mut self, protocols: Vec<RlpxSubProtocolFactory>) -> Self {
+        self.rlpx_sub_protocols = protocols;
+        self
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -624,14 +686,84 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the client that sends `snap/1` requests to the peers of the network.
+    ///
+    /// Peers are only registered if the snap subprotocol is enabled, see
+    /// [`Self::network_config_builder`].
+    pub const fn snap_client(&self) -> &SnapClient {
+        &self.snap_client
+    }
+
     /// Returns the transaction pool config of the node.
+    ///
+    /// The `txpool` section of the reth.toml config takes precedence over the CLI arguments.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let txpool = &self.config().txpool;
+        let mut config = txpool.pool_config();
+        config.apply_runtime_config(&pool_runtime_config(&self.reth_config().txpool));
+        if txpool.admission_log {
+            config.admission_log = Some(
+                AdmissionLogConfig::new(self.config().datadir().txpool_admission_log())
+                    .with_max_size(txpool.admission_log_max_size.saturating_mul(1024 * 1024)),
+            );
+        }
+        if txpool.blob_archive {
+            config.blob_archive = Some(
+                BlobArchiveConfig::new(self.config().datadir().blob_archive())
+                    .with_retention_blocks(txpool.blob_retention_blocks),
+            );
+        }
+        config
+    }
+
+    /// Spawns a task that applies changes of the `txpool` section of the reth.toml config to the
+    /// pool while the node is running, see [`reload_pool_config`].
+    pub fn spawn_pool_config_reload<Pool>(&self, pool: Pool)
+    where
+        Pool: TransactionPool + 'static,
+    {
+        let path = self.config().config.clone().unwrap_or_else(|| self.config().datadir().config());
+        self.executor.spawn(reload_pool_config(pool, path));
     }
 
-    /// Loads `EnvKzgSettings::Default`.
-    pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
-        Ok(EnvKzgSettings::Default)
+    /// Loads the KZG trusted setup configured with `--txpool.kzg-trusted-setup`, or
+    /// `EnvKzgSettings::Default` if none is configured.
+    pub fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
+        let Some(path) = &self.config().txpool.kzg_trusted_setup else {
+            return Ok(EnvKzgSettings::Default)
+        };
+        let settings = KzgSettings::load_trusted_setup_file(path).map_err(|err| {
+            eyre::eyre!("failed to load KZG trusted setup {}: {err}", path.display())
+        })?;
+        info!(target: "reth::cli", path = %path.display(), "Loaded KZG trusted setup");
+        Ok(EnvKzgSettings::Custom(Arc::new(settings)))
+    }
+
+    /// Spawns the task that verifies the KZG proofs of pending blob transactions if
+    /// `--txpool.lazy-blob-verification` is enabled, see [`verify_pending_blob_transactions`].
+    ///
+    /// The given settings must be the ones the validator of the pool uses.
+    pub fn spawn_lazy_blob_verification<Pool>(
+        &self,
+        pool: Pool,
+        kzg_settings: KzgSettingsHandle,
+    ) -> eyre::Result<()>
+    where
+        Pool: TransactionPool + 'static,
+    {
+        if !self.config().txpool.lazy_blob_verification {
+            return Ok(())
+        }
+        let workers = BlockingTaskPool::new(
+            BlockingTaskPool::builder()
+                .thread_name(|i| format!("blob-verification-{i}"))
+                .build()?,
+        );
+        self.executor.spawn_critical(
+            "txpool blob verification",
+            verify_pending_blob_transactions(pool, kzg_settings, workers),
+        );
+        Ok(())
     }
 
     /// Returns the config for payload building.
@@ -674,6 +806,8 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical("p2p eth request handler", eth);
 
         let default_peers_path = self.config().datadir().known_peers();
+        let peer_reputations_file =
+            self.config().network.persistent_peer_reputations_file(default_peers_path.clone());
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
@@ -691,6 +825,17 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(reputations_file) = peer_reputations_file {
+                        trace!(target: "reth::cli", reputations_file=?reputations_file, "Saving peer reputations");
+                        match network.write_peer_reputations_to_file(reputations_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", reputations_file=?reputations_file, "Wrote peer reputations to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write peer reputations to file");
+                            }
+                        }
+                    }
                 })
             },
         );
@@ -736,7 +881,7 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
     pub fn network_config_builder(&self) -> eyre::Result<NetworkConfigBuilder> {
         let secret_key = self.network_secret(&self.config().datadir())?;
         let default_peers_path = self.config().datadir().known_peers();
-        let builder = self
+        let mut builder = self
             .config()
             .network
             .network_config(
@@ -748,6 +893,31 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
             .with_task_executor(Box::new(self.executor.clone()))
             .set_head(self.head);
 
+        if let Some(config) = self.config().network.light_proofs_config() {
+            info!(target: "reth::cli", proof_window = config.proof_window, "Serving proofs to light clients");
+            builder = builder.add_rlpx_sub_protocol(
+                LightProofsHandler::new(self.provider().clone(), config).into_rlpx_sub_protocol(),
+            );
+        }
+
+        let network = &self.config().network;
+        if network.snap_serve || network.snap_sync {
+            if network.snap_serve {
+                info!(target: "reth::cli", "Serving snap sync requests");
+            }
+            let config = network.snap_server_config().unwrap_or_default();
+            builder = builder.add_rlpx_sub_protocol(
+                SnapHandler::new(self.provider().clone(), config)
+                    .with_serving(network.snap_serve)
+                    .with_client(self.snap_client.clone())
+                    .into_rlpx_sub_protocol(),
+            );
+        }
+
+        for protocol in &self.rlpx_sub_protocols {
+            builder = builder.add_rlpx_sub_protocol(protocol());
+        }
+
         Ok(builder)
     }
 }
@@ -7,18 +7,20 @@
 
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
+    exex::{ExExRestartPolicy, InstalledExEx},
     hooks::NodeHooks,
     launch::LaunchNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     AddOns, FullNode,
 };
 use reth_exex::ExExContext;
+use reth_network::protocol::IntoRlpxSubProtocol;
 use reth_node_api::{
     FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes, NodeTypesWithDB, PayloadBuilder,
 };
 use reth_node_core::node_config::NodeConfig;
 use reth_tasks::TaskExecutor;
-use std::{fmt, future::Future};
+use std::{fmt, future::Future, sync::Arc};
 
 /// A node builder that also has the configured types.
 pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
@@ -48,7 +50,12 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons: () },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                rlpx_sub_protocols: Vec::new(),
+                add_ons: (),
+            },
         }
     }
 }
@@ -171,13 +178,23 @@ where
     where
         AO: NodeAddOns<NodeAdapter<T, CB::Components>>,
     {
-        let Self { config, adapter, components_builder, .. } = self;
+        let Self {
+            config,
+            adapter,
+            components_builder,
+            add_ons: AddOns { rlpx_sub_protocols, .. },
+        } = self;
 
         NodeBuilderWithComponents {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                rlpx_sub_protocols,
+                add_ons,
+            },
         }
     }
 }
@@ -208,6 +225,20 @@ where
         self
     }
 
+    /// Adds an additional `RLPx` subprotocol that the network of the node offers to peers.
+    ///
+    /// The handler is cloned for every network that is configured for the node, so the protocol
+    /// is negotiated with all peers from the first connection on.
+    pub fn add_rlpx_sub_protocol<P>(mut self, protocol: P) -> Self
+    where
+        P: IntoRlpxSubProtocol + Clone + Send + Sync + 'static,
+    {
+        self.add_ons
+            .rlpx_sub_protocols
+            .push(Arc::new(move || protocol.clone().into_rlpx_sub_protocol()));
+        self
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node.
     ///
     /// # Note
@@ -219,7 +250,30 @@ where
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.add_ons.exexs.push((exex_id.into(), InstalledExEx::new(exex)));
+        self
+    }
+
+    /// Installs an `ExEx` (Execution Extension) in the node that is handled according to the
+    /// given [`ExExRestartPolicy`] when it crashes.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_restart_policy<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        exex: F,
+        restart_policy: ExExRestartPolicy,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Clone + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons
+            .exexs
+            .push((exex_id.into(), InstalledExEx::with_restart_policy(exex, restart_policy)));
         self
     }
 
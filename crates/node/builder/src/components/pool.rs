@@ -1,8 +1,16 @@
 //! Pool component for the node builder.
 
 use alloy_primitives::Address;
-use reth_transaction_pool::{PoolConfig, SubPoolLimit, TransactionPool};
-use std::{collections::HashSet, future::Future};
+use reth_config::{SubPoolLimitConfig, TxPoolConfig};
+use reth_transaction_pool::{PoolConfig, PoolRuntimeConfig, SubPoolLimit, TransactionPool};
+use std::{
+    collections::HashSet,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{info, warn};
 
 use crate::{BuilderContext, FullNodeTypes};
 
@@ -93,3 +101,64 @@ impl PoolBuilderConfigOverrides {
         config
     }
 }
+
+/// The interval in which [`reload_pool_config`] checks the reth.toml config for changes.
+pub const POOL_CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Returns the [`PoolRuntimeConfig`] of the `txpool` section of the reth.toml config.
+pub fn pool_runtime_config(config: &TxPoolConfig) -> PoolRuntimeConfig {
+    let limit = |limit: SubPoolLimitConfig| {
+        SubPoolLimit::new(limit.max_count, limit.max_size_mb.saturating_mul(1024 * 1024))
+    };
+    PoolRuntimeConfig {
+        price_bump: config.price_bump.map(u128::from),
+        blob_price_bump: config.blob_price_bump.map(u128::from),
+        max_account_slots: config.max_account_slots,
+        pending_limit: config.pending.map(limit),
+        basefee_limit: config.basefee.map(limit),
+        queued_limit: config.queued.map(limit),
+        blob_limit: config.blob.map(limit),
+    }
+}
+
+/// Applies the `txpool` section of the reth.toml config at the given path to the pool whenever
+/// the section changes.
+///
+/// Settings that are removed from the section keep their current value.
+pub async fn reload_pool_config<Pool>(pool: Pool, path: PathBuf)
+where
+    Pool: TransactionPool,
+{
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    let mut last_modified = modified(&path);
+    let mut last_config = reth_config::Config::from_path(&path).ok().map(|config| config.txpool);
+    let mut interval = tokio::time::interval(POOL_CONFIG_RELOAD_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        let current = modified(&path);
+        if current.is_none() || current == last_modified {
+            continue
+        }
+        last_modified = current;
+
+        let config = match reth_config::Config::from_path(&path) {
+            Ok(config) => config.txpool,
+            Err(err) => {
+                warn!(target: "reth::cli", ?path, %err, "Failed to reload transaction pool config");
+                continue
+            }
+        };
+        if last_config.replace(config) == Some(config) {
+            continue
+        }
+
+        let discarded = pool.update_runtime_config(pool_runtime_config(&config));
+        info!(
+            target: "reth::cli",
+            ?path,
+            discarded = discarded.len(),
+            "Reloaded transaction pool config"
+        );
+    }
+}
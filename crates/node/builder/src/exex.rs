@@ -1,6 +1,6 @@
 //! Types for launching execution extensions (ExEx).
 
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 use futures::{future::BoxFuture, FutureExt};
 use reth_exex::ExExContext;
@@ -65,3 +65,73 @@ where
         self(ctx)
     }
 }
+
+/// What to do when an `ExEx` crashes, i.e. its future returns an error, finishes or panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExRestartPolicy {
+    /// Shut down the node.
+    #[default]
+    ShutdownNode,
+    /// Stop the `ExEx` and keep the node running without it.
+    Stop,
+    /// Launch the `ExEx` again with a new [`ExExContext`], waiting for `backoff` before each
+    /// restart. The `ExEx` is stopped once it crashed more than `max_restarts` times.
+    Restart {
+        /// Maximum number of restarts.
+        max_restarts: usize,
+        /// Delay before each restart.
+        backoff: Duration,
+    },
+}
+
+/// An `ExEx` installed in the node, together with its [`ExExRestartPolicy`].
+pub struct InstalledExEx<Node: FullNodeComponents> {
+    /// Returns the [`BoxedLaunchExEx`] for the next launch of the `ExEx`, or `None` if it can't be
+    /// launched again.
+    launcher: Box<dyn FnMut() -> Option<Box<dyn BoxedLaunchExEx<Node>>> + Send>,
+    /// What to do when the `ExEx` crashes.
+    restart_policy: ExExRestartPolicy,
+}
+
+impl<Node: FullNodeComponents> InstalledExEx<Node> {
+    /// Creates a new [`InstalledExEx`] that can be launched once and shuts down the node when it
+    /// crashes.
+    pub fn new<E: LaunchExEx<Node> + 'static>(exex: E) -> Self {
+        let mut exex = Some(exex);
+        Self {
+            launcher: Box::new(move || {
+                exex.take().map(|exex| Box::new(exex) as Box<dyn BoxedLaunchExEx<Node>>)
+            }),
+            restart_policy: ExExRestartPolicy::default(),
+        }
+    }
+
+    /// Creates a new [`InstalledExEx`] that is handled according to the given
+    /// [`ExExRestartPolicy`] when it crashes.
+    pub fn with_restart_policy<E: LaunchExEx<Node> + Clone + 'static>(
+        exex: E,
+        restart_policy: ExExRestartPolicy,
+    ) -> Self {
+        Self { launcher: Box::new(move || Some(Box::new(exex.clone()))), restart_policy }
+    }
+
+    /// Returns the [`BoxedLaunchExEx`] for the next launch of the `ExEx`, if it can be launched
+    /// again.
+    pub fn launcher(&mut self) -> Option<Box<dyn BoxedLaunchExEx<Node>>> {
+        (self.launcher)()
+    }
+
+    /// Returns the [`ExExRestartPolicy`] of the `ExEx`.
+    pub const fn restart_policy(&self) -> ExExRestartPolicy {
+        self.restart_policy
+    }
+}
+
+impl<Node: FullNodeComponents> std::fmt::Debug for InstalledExEx<Node> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstalledExEx")
+            .field("launcher", &"...")
+            .field("restart_policy", &self.restart_policy)
+            .finish()
+    }
+}
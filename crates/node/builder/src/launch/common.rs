@@ -13,15 +13,17 @@ use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::Consensus;
 use reth_db_api::database::Database;
-use reth_db_common::init::{init_genesis, InitDatabaseError};
+use reth_db_common::init::{init_genesis, validate_chain_spec, InitDatabaseError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_fork_dry_run::ForkDryRun;
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{InvalidBlockPreStateHook, InvalidBlockWitnessHook};
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB};
+use reth_network_sentry::{SentryClient, SentryService};
+use reth_node_api::{FullNodeTypes, NodePrimitives, NodeTypes, NodeTypesWithDB};
 use reth_node_core::{
     args::InvalidBlockHookType,
     dirs::{ChainPath, DataDirPath},
@@ -40,14 +42,15 @@ use reth_node_metrics::{
 use reth_primitives::Head;
 use reth_provider::{
     providers::{BlockchainProvider, BlockchainProvider2, ProviderNodeTypes, StaticFileProvider},
-    BlockHashReader, BlockNumReader, CanonStateNotificationSender, ChainSpecProvider,
-    ProviderError, ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TreeViewer,
+    BlockHashReader, BlockNumReader, CanonStateNotificationSender, CanonStateSubscriptions,
+    ChainSpecProvider, ProviderError, ProviderFactory, ProviderResult, StageCheckpointReader,
+    StateProviderFactory, StaticFileProviderFactory, TreeViewer,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
+use reth_snap::SnapClient;
 use reth_stages::{sets::DefaultStages, MetricEvent, PipelineBuilder, PipelineTarget, StageId};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
@@ -61,7 +64,7 @@ use tokio::sync::{
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
-    BuilderContext, NodeAdapter,
+    BuilderContext, NodeAdapter, RlpxSubProtocolFactory,
 };
 
 /// Allows to set a tree viewer for a configured blockchain provider.
@@ -258,6 +261,11 @@ impl<ChainSpec> LaunchContextWith<WithConfigs<ChainSpec>> {
                 .trusted_nodes
                 .extend(self.attachment.config.network.trusted_peers.clone());
         }
+
+        let peers = &self.attachment.toml_config.peers;
+        if peers.trusted_nodes_only && peers.trusted_nodes.is_empty() {
+            eyre::bail!("No trusted nodes. Set trusted peer with `--trusted-peers <enode record>` or set `--trusted-only` to `false`")
+        }
         Ok(self)
     }
 }
@@ -407,13 +415,16 @@ where
     pub async fn create_provider_factory<N: NodeTypesWithDB<DB = DB, ChainSpec = ChainSpec>>(
         &self,
     ) -> eyre::Result<ProviderFactory<N>> {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?;
+        if let Some(url) = self.node_config().datadir.static_files_remote.clone() {
+            static_file_provider = with_remote_static_files(static_file_provider, url).await?;
+        }
+
+        let factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_static_files_metrics();
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -535,13 +546,16 @@ where
 
     /// Convenience function to [`Self::init_genesis`]
     pub fn with_genesis(self) -> Result<Self, InitDatabaseError> {
-        init_genesis(self.provider_factory())?;
+        self.init_genesis()?;
         Ok(self)
     }
 
-    /// Write the genesis block and state if it has not already been written
+    /// Write the genesis block and state if it has not already been written, then validate the
+    /// chainspec against the existing blocks.
     pub fn init_genesis(&self) -> Result<B256, InitDatabaseError> {
-        init_genesis(self.provider_factory())
+        let genesis_hash = init_genesis(self.provider_factory())?;
+        validate_chain_spec(self.provider_factory())?;
+        Ok(genesis_hash)
     }
 
     /// Creates a new `WithMeteredProvider` container and attaches it to the
@@ -668,6 +682,7 @@ where
         on_component_initialized: Box<
             dyn OnComponentInitializedHook<NodeAdapter<T, CB::Components>>,
         >,
+        rlpx_sub_protocols: Vec<RlpxSubProtocolFactory>,
     ) -> eyre::Result<
         LaunchContextWith<
             Attached<WithConfigs<<T::Types as NodeTypes>::ChainSpec>, WithComponents<T, CB>>,
@@ -684,7 +699,8 @@ where
             self.blockchain_db().clone(),
             self.task_executor().clone(),
             self.configs().clone(),
-        );
+        )
+        .with_rlpx_sub_protocols(rlpx_sub_protocols);
 
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
@@ -729,6 +745,7 @@ where
             node_adapter,
             head,
             consensus,
+            snap_client: builder_ctx.snap_client().clone(),
         };
 
         let ctx = LaunchContextWith {
@@ -887,6 +904,43 @@ where
         self.right().consensus.clone()
     }
 
+    /// Returns the client that sends `snap/1` requests to the peers of the network.
+    pub const fn snap_client(&self) -> &SnapClient {
+        &self.right().snap_client
+    }
+
+    /// Returns the client that downloads blocks through the remote sentries configured with
+    /// `--sentry.remote`, if any.
+    pub fn sentry_client(&self) -> eyre::Result<Option<SentryClient>> {
+        let endpoints = &self.node_config().network.sentry_remote;
+        if endpoints.is_empty() {
+            return Ok(None)
+        }
+
+        info!(target: "reth::cli", ?endpoints, "Downloading blocks through remote sentries");
+        Ok(Some(SentryClient::new(endpoints.clone())?))
+    }
+
+    /// Starts the sentry gRPC server that serves the network of the node, if it's enabled with
+    /// `--sentry.grpc`.
+    pub async fn start_sentry_server(&self) -> eyre::Result<()> {
+        let network = &self.node_config().network;
+        if !network.sentry_grpc {
+            return Ok(())
+        }
+
+        let service = SentryService::new(self.components().network().clone()).await?;
+        let (addr, server) = service.bind(network.sentry_grpc_socket_addr()).await?;
+        info!(target: "reth::cli", %addr, "Sentry gRPC server started");
+        self.task_executor().spawn_critical("sentry grpc server", async move {
+            if let Err(err) = server.await {
+                error!(target: "reth::cli", %err, "Sentry gRPC server failed");
+            }
+        });
+
+        Ok(())
+    }
+
     /// Returns the metrics sender.
     pub fn sync_metrics_tx(&self) -> UnboundedSender<MetricEvent> {
         self.right().db_provider_container.metrics_sender.clone()
@@ -929,23 +983,44 @@ where
                 let output_directory = output_directory.join(hook.to_string());
                 fs::create_dir_all(&output_directory)?;
 
-                Ok(match hook {
+                let hook: Box<dyn InvalidBlockHook> = match hook {
                     InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
                         self.blockchain_db().clone(),
                         self.components().evm_config().clone(),
                         output_directory,
                         healthy_node_rpc_client.clone(),
                     )),
-                    InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
+                    InvalidBlockHookType::PreState => Box::new(InvalidBlockPreStateHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    )),
+                    InvalidBlockHookType::Opcode => {
                         eyre::bail!("invalid block hook {hook:?} is not implemented yet")
                     }
-                } as Box<dyn InvalidBlockHook>)
+                };
+                Ok(hook)
             })
             .collect::<Result<_, _>>()?;
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
 
+    /// Spawns the fork dry run, if configured in the node config.
+    pub fn spawn_fork_dry_run(&self) {
+        let Some(hardfork) = self.node_config().debug.fork_dry_run.clone() else { return };
+        info!(target: "reth::cli", hardfork = hardfork.as_deref().unwrap_or("next scheduled"), "Dry running canonical blocks under upcoming hardfork");
+
+        let fork_dry_run = ForkDryRun::new(
+            self.blockchain_db().clone(),
+            self.components().evm_config().clone(),
+            hardfork,
+        );
+        let notifications = self.blockchain_db().canonical_state_stream();
+        self.task_executor().spawn(Box::pin(fork_dry_run.run(notifications)));
+    }
+
     /// Returns an RPC client for the healthy node, if configured in the node config.
     fn get_healthy_node_client(&self) -> eyre::Result<Option<jsonrpsee::http_client::HttpClient>> {
         self.node_config()
@@ -1069,6 +1144,33 @@ where
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
     consensus: Arc<dyn Consensus>,
+    snap_client: SnapClient,
+}
+
+/// Indexes the static files of the remote location at `url` that are missing on disk, their data
+/// is only downloaded once it's accessed.
+#[cfg(feature = "remote-static-files")]
+async fn with_remote_static_files<N: NodePrimitives>(
+    static_file_provider: StaticFileProvider<N>,
+    url: String,
+) -> eyre::Result<StaticFileProvider<N>> {
+    use reth_provider::providers::RemoteStaticFiles;
+
+    info!(target: "reth::cli", %url, "Indexing remote static files");
+    // the manifest and the configuration files of the missing jars are downloaded right away
+    Ok(tokio::task::spawn_blocking(move || {
+        static_file_provider.with_remote(RemoteStaticFiles::new(&url)?)
+    })
+    .await??)
+}
+
+/// Remote static files are not supported without the `remote-static-files` feature.
+#[cfg(not(feature = "remote-static-files"))]
+async fn with_remote_static_files<N: NodePrimitives>(
+    _static_file_provider: StaticFileProvider<N>,
+    url: String,
+) -> eyre::Result<StaticFileProvider<N>> {
+    eyre::bail!("fetching static files from {url} requires the `remote-static-files` feature")
 }
 
 #[cfg(test)]
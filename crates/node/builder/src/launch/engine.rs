@@ -7,7 +7,9 @@ use reth_beacon_consensus::{
 };
 use reth_blockchain_tree::BlockchainTreeConfig;
 use reth_chainspec::EthChainSpec;
-use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider};
+use reth_consensus_debug_client::{
+    BeaconLightClient, DebugConsensusClient, EtherscanBlockProvider, RpcPollingBlockProvider,
+};
 use reth_engine_local::{LocalEngineService, LocalPayloadAttributesBuilder};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
@@ -88,7 +90,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, rlpx_sub_protocols, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -132,7 +134,7 @@ where
             .with_blockchain_db::<T, _>(move |provider_factory| {
                 Ok(BlockchainProvider2::new(provider_factory)?)
             }, tree_config, canon_state_notification_sender)?
-            .with_components(components_builder, on_component_initialized).await?;
+            .with_components(components_builder, on_component_initialized, rlpx_sub_protocols).await?;
 
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
@@ -144,8 +146,14 @@ where
         .launch()
         .await?;
 
-        // create pipeline
-        let network_client = ctx.components().network().fetch_client().await?;
+        ctx.start_sentry_server().await?;
+
+        // create pipeline, downloading blocks through the remote sentries if there are any
+        let sentry_client = ctx.sentry_client()?;
+        let network_client = match sentry_client.clone() {
+            Some(sentry_client) => Either::Right(sentry_client),
+            None => Either::Left(ctx.components().network().fetch_client().await?),
+        };
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
@@ -190,6 +198,7 @@ where
             static_file_producer,
             ctx.components().block_executor().clone(),
             pipeline_exex_handle,
+            ctx.node_config().network.snap_sync.then(|| ctx.snap_client().clone()),
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
@@ -277,12 +286,15 @@ where
             ),
         );
 
+        ctx.spawn_fork_dry_run();
+
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
+            toml_config: ctx.toml_config(),
             beacon_engine_handle,
             jwt_secret,
         };
@@ -320,6 +332,34 @@ where
             });
         }
 
+        if let Some(rpc_url) = ctx.node_config().debug.rpc_consensus_url.clone() {
+            info!(target: "reth::cli", %rpc_url, "Using rpc provider as consensus client");
+
+            // Every block after the local head is executed, so the node doesn't need to sync.
+            let block_provider =
+                RpcPollingBlockProvider::new(rpc_url).with_start_block(ctx.head().number + 1);
+            let rpc_consensus_client = DebugConsensusClient::new(
+                rpc_server_handles.auth.clone(),
+                Arc::new(block_provider),
+            );
+            ctx.task_executor().spawn_critical("rpc consensus client", async move {
+                rpc_consensus_client.run::<<Types as NodeTypesWithEngine>::Engine>().await
+            });
+        }
+
+        if let Some(beacon_api_url) = ctx.node_config().debug.beacon_light_client.clone() {
+            info!(target: "reth::cli", %beacon_api_url, "Using beacon light client as consensus client");
+
+            let mut light_client =
+                BeaconLightClient::new(rpc_server_handles.auth.clone(), beacon_api_url);
+            if let Some(checkpoint) = ctx.node_config().debug.beacon_checkpoint {
+                light_client = light_client.with_checkpoint(checkpoint);
+            }
+            ctx.task_executor().spawn_critical("beacon light client", async move {
+                light_client.run::<<Types as NodeTypesWithEngine>::Engine>().await
+            });
+        }
+
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let network_handle = ctx.components().network().clone();
@@ -368,9 +408,15 @@ where
                                 }
 
                                 network_handle.update_sync_state(SyncState::Idle);
+                                if let Some(sentry_client) = &sentry_client {
+                                    sentry_client.update_sync_state(SyncState::Idle);
+                                }
                             }
                             ChainEvent::BackfillSyncStarted => {
                                 network_handle.update_sync_state(SyncState::Syncing);
+                                if let Some(sentry_client) = &sentry_client {
+                                    sentry_client.update_sync_state(SyncState::Syncing);
+                                }
                             }
                             ChainEvent::FatalError => {
                                 error!(target: "reth::cli", "Fatal error in consensus engine");
@@ -389,6 +435,9 @@ where
                                             .unwrap_or_default(),
                                     };
                                     network_handle.update_status(head_block);
+                                    if let Some(sentry_client) = &sentry_client {
+                                        sentry_client.update_status(head_block);
+                                    }
                                 }
                                 event_sender.notify(ev);
                             }
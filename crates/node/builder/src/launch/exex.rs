@@ -1,26 +1,29 @@
 //! Support for launching execution extensions.
 
-use std::{fmt, fmt::Debug};
+use std::{fmt, fmt::Debug, panic::AssertUnwindSafe};
 
-use futures::future;
+use futures::{future, FutureExt};
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY,
+    ExExContext, ExExHandle, ExExHead, ExExManager, ExExManagerHandle, ExExNotificationSource,
+    ExExNotificationsStream, Wal, DEFAULT_EXEX_MANAGER_CAPACITY,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes};
 use reth_primitives::Head;
-use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
+use reth_provider::{BlockReaderIdExt, CanonStateSubscriptions};
+use reth_tracing::tracing::{debug, error, info, warn};
 use tracing::Instrument;
 
-use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
+use crate::{
+    common::WithConfigs,
+    exex::{BoxExEx, BoxedLaunchExEx, ExExRestartPolicy, InstalledExEx},
+};
 
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, InstalledExEx<Node>)>,
     components: Node,
     config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
 }
@@ -30,7 +33,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, InstalledExEx<Node>)>,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -58,65 +61,132 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 .exex_wal(),
         )?;
 
+        // Creates the handle and launch context of an exex with the given node head
+        let new_exex = {
+            let components = components.clone();
+            let wal = exex_wal.clone();
+            move |id: &str, head: Head| {
+                let (handle, events, notifications) = ExExHandle::new(
+                    id.to_string(),
+                    head,
+                    components.provider().clone(),
+                    components.block_executor().clone(),
+                    wal.handle(),
+                );
+                let context = ExExContext {
+                    head,
+                    config: config_container.config.clone(),
+                    reth_config: config_container.toml_config.clone(),
+                    components: components.clone(),
+                    events,
+                    notifications,
+                };
+                (handle, context)
+            }
+        };
+
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
         for (id, exex) in extensions {
-            // create a new exex handle
-            let (handle, events, notifications) = ExExHandle::new(
-                id.clone(),
-                head,
-                components.provider().clone(),
-                components.block_executor().clone(),
-                exex_wal.handle(),
-            );
+            // create a new exex handle and the launch context for the exex
+            let (handle, context) = new_exex(&id, head);
             exex_handles.push(handle);
+            exexes.push((id, exex, context));
+        }
 
-            // create the launch context for the exex
-            let context = ExExContext {
-                head,
-                config: config_container.config.clone(),
-                reth_config: config_container.toml_config.clone(),
-                components: components.clone(),
-                events,
-                notifications,
-            };
+        // create exex manager
+        debug!(target: "reth::cli", "creating exex manager");
+        let exex_manager = ExExManager::new(
+            components.provider().clone(),
+            exex_handles,
+            DEFAULT_EXEX_MANAGER_CAPACITY,
+            exex_wal,
+            components.provider().finalized_block_stream(),
+        );
+        let exex_manager_handle = exex_manager.handle();
 
+        future::join_all(exexes.into_iter().map(|(id, mut exex, context)| {
             let executor = components.task_executor().clone();
-            exexes.push(async move {
+            let provider = components.provider().clone();
+            let manager_handle = exex_manager_handle.clone();
+            let new_exex = new_exex.clone();
+            async move {
                 debug!(target: "reth::cli", id, "spawning exex");
                 let span = reth_tracing::tracing::info_span!("exex", id);
 
                 // init the exex
-                let exex = exex.launch(context).instrument(span.clone()).await.unwrap();
+                let launcher = exex.launcher().expect("exex is launched for the first time");
+                let exex_fut = launcher.launch(context).instrument(span.clone()).await.unwrap();
 
-                // spawn it as a crit task
+                // spawn it as a crit task, supervised according to its restart policy
                 executor.spawn_critical(
                     "exex",
                     async move {
-                        info!(target: "reth::cli", "ExEx started");
-                        match exex.await {
-                            Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
-                            Err(err) => panic!("ExEx {id} crashed: {err}"),
+                        let mut exex_fut = Some(exex_fut);
+                        let mut restarts = 0;
+                        loop {
+                            let result = run_exex(exex_fut.take(), || {
+                                // relaunch the exex with a fresh context that resumes from its
+                                // last finished height, so that it catches up on the blocks it
+                                // missed from the WAL and the database
+                                let launcher = exex.launcher()?;
+                                let context = current_head(&provider).and_then(|head| {
+                                    let (handle, mut context) = new_exex(&id, head);
+                                    if let Some(block) = manager_handle.exex_finished_height(&id) {
+                                        debug!(target: "reth::cli", ?block, "Resuming ExEx from its finished height");
+                                        context.notifications.set_with_head(ExExHead { block });
+                                    }
+                                    manager_handle
+                                        .attach_exex(handle)
+                                        .map_err(|_| eyre::eyre!("exex manager is not running"))?;
+                                    Ok(context)
+                                });
+                                Some(context.map(|context| (launcher, context)))
+                            })
+                            .await;
+                            let err = match result {
+                                Some(Ok(())) => {
+                                    eyre::eyre!("ExEx finished. ExExes should run indefinitely")
+                                }
+                                Some(Err(err)) => err,
+                                None => {
+                                    error!(target: "reth::cli", "ExEx can't be launched again, stopping it");
+                                    let _ = manager_handle.remove_exex(id);
+                                    return
+                                }
+                            };
+
+                            match exex.restart_policy() {
+                                ExExRestartPolicy::ShutdownNode => {
+                                    panic!("ExEx {id} crashed: {err}")
+                                }
+                                ExExRestartPolicy::Stop => {
+                                    error!(target: "reth::cli", %err, "ExEx crashed, stopping it");
+                                    let _ = manager_handle.remove_exex(id);
+                                    return
+                                }
+                                ExExRestartPolicy::Restart { max_restarts, backoff } => {
+                                    if restarts >= max_restarts {
+                                        error!(target: "reth::cli", %err, restarts, "ExEx crashed too many times, stopping it");
+                                        let _ = manager_handle.remove_exex(id);
+                                        return
+                                    }
+                                    restarts += 1;
+                                    warn!(target: "reth::cli", %err, restarts, ?backoff, "ExEx crashed, restarting it");
+                                    tokio::time::sleep(backoff).await;
+                                }
+                            }
                         }
                     }
                     .instrument(span),
                 );
-            });
-        }
-
-        future::join_all(exexes).await;
+            }
+        }))
+        .await;
 
         // spawn exex manager
         debug!(target: "reth::cli", "spawning exex manager");
-        let exex_manager = ExExManager::new(
-            components.provider().clone(),
-            exex_handles,
-            DEFAULT_EXEX_MANAGER_CAPACITY,
-            exex_wal,
-            components.provider().finalized_block_stream(),
-        );
-        let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
         });
@@ -142,6 +212,59 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     }
 }
 
+/// Runs the exex future, or launches the exex again with the context returned by `relaunch` if
+/// there's none.
+///
+/// Panics of the exex are caught and returned as errors. Returns `None` if the exex can't be
+/// launched again.
+async fn run_exex<Node: FullNodeComponents>(
+    exex_fut: Option<BoxExEx>,
+    relaunch: impl FnOnce() -> Option<eyre::Result<(Box<dyn BoxedLaunchExEx<Node>>, ExExContext<Node>)>>,
+) -> Option<eyre::Result<()>> {
+    let run = async move {
+        let exex_fut = match exex_fut {
+            Some(exex_fut) => exex_fut,
+            None => {
+                let (launcher, context) = match relaunch()? {
+                    Ok(relaunch) => relaunch,
+                    Err(err) => return Some(Err(err)),
+                };
+                match launcher.launch(context).await {
+                    Ok(exex_fut) => exex_fut,
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+        };
+        info!(target: "reth::cli", "ExEx started");
+        Some(exex_fut.await)
+    };
+
+    AssertUnwindSafe(run).catch_unwind().await.unwrap_or_else(|panic| {
+        let panic = panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic.downcast_ref::<&str>().copied())
+            .unwrap_or("unknown panic");
+        Some(Err(eyre::eyre!("ExEx panicked: {panic}")))
+    })
+}
+
+/// Returns the current canonical head of the node.
+fn current_head<P>(provider: &P) -> eyre::Result<Head>
+where
+    P: BlockReaderIdExt,
+{
+    let header = provider.latest_header()?.ok_or_else(|| eyre::eyre!("latest header not found"))?;
+    let total_difficulty = provider.header_td_by_number(header.number)?.unwrap_or_default();
+    Ok(Head {
+        number: header.number,
+        hash: header.hash(),
+        difficulty: header.difficulty,
+        total_difficulty,
+        timestamp: header.timestamp,
+    })
+}
+
 impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
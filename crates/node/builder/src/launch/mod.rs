@@ -18,7 +18,10 @@ use reth_beacon_consensus::{
 };
 use reth_blockchain_tree::{noop::NoopBlockchainTree, BlockchainTreeConfig};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
-use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
+use reth_consensus_debug_client::{
+    BeaconLightClient, DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider,
+    RpcPollingBlockProvider,
+};
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{BlockDownloaderProvider, NetworkEventListenerProvider};
@@ -113,7 +116,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, rlpx_sub_protocols, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -161,7 +164,7 @@ where
             .with_blockchain_db::<T, _>(move |provider_factory| {
                 Ok(BlockchainProvider::new(provider_factory, tree)?)
             }, tree_config, canon_state_notification_sender)?
-            .with_components(components_builder, on_component_initialized).await?;
+            .with_components(components_builder, on_component_initialized, rlpx_sub_protocols).await?;
 
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
@@ -173,8 +176,13 @@ where
         .launch()
         .await?;
 
-        // create pipeline
-        let network_client = ctx.components().network().fetch_client().await?;
+        ctx.start_sentry_server().await?;
+
+        // create pipeline, downloading blocks through the remote sentries if there are any
+        let network_client = match ctx.sentry_client()? {
+            Some(sentry_client) => Either::Right(sentry_client),
+            None => Either::Left(ctx.components().network().fetch_client().await?),
+        };
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
@@ -222,6 +230,7 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
+                ctx.node_config().network.snap_sync.then(|| ctx.snap_client().clone()),
             )?;
 
             (pipeline, network_client.clone())
@@ -283,12 +292,15 @@ where
             ),
         );
 
+        ctx.spawn_fork_dry_run();
+
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
+            toml_config: ctx.toml_config(),
             beacon_engine_handle,
             jwt_secret,
         };
@@ -346,6 +358,34 @@ where
             });
         }
 
+        if let Some(rpc_url) = ctx.node_config().debug.rpc_consensus_url.clone() {
+            info!(target: "reth::cli", %rpc_url, "Using rpc provider as consensus client");
+
+            // Every block after the local head is executed, so the node doesn't need to sync.
+            let block_provider =
+                RpcPollingBlockProvider::new(rpc_url).with_start_block(ctx.head().number + 1);
+            let rpc_consensus_client = DebugConsensusClient::new(
+                rpc_server_handles.auth.clone(),
+                Arc::new(block_provider),
+            );
+            ctx.task_executor().spawn_critical("rpc consensus client", async move {
+                rpc_consensus_client.run::<Types::Engine>().await
+            });
+        }
+
+        if let Some(beacon_api_url) = ctx.node_config().debug.beacon_light_client.clone() {
+            info!(target: "reth::cli", %beacon_api_url, "Using beacon light client as consensus client");
+
+            let mut light_client =
+                BeaconLightClient::new(rpc_server_handles.auth.clone(), beacon_api_url);
+            if let Some(checkpoint) = ctx.node_config().debug.beacon_checkpoint {
+                light_client = light_client.with_checkpoint(checkpoint);
+            }
+            ctx.task_executor().spawn_critical("beacon light client", async move {
+                light_client.run::<Types::Engine>().await
+            });
+        }
+
         let full_node = FullNode {
             evm_config: ctx.components().evm_config().clone(),
             block_executor: ctx.components().block_executor().clone(),
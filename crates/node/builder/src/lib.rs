@@ -20,7 +20,10 @@ pub mod components;
 pub use components::{NodeComponents, NodeComponentsBuilder};
 
 mod builder;
-pub use builder::{add_ons::AddOns, *};
+pub use builder::{
+    add_ons::{AddOns, RlpxSubProtocolFactory},
+    *,
+};
 
 mod launch;
 pub use launch::{engine::EngineNodeLauncher, *};
@@ -4,7 +4,9 @@ use std::{
     fmt::{self, Debug},
     future::Future,
     marker::PhantomData,
+    net::SocketAddr,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 use alloy_rpc_types::engine::ClientVersionV1;
@@ -18,18 +20,23 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::PayloadStore;
+use reth_profiling::cpu_profiler;
 use reth_provider::providers::ProviderNodeTypes;
 use reth_rpc::{
-    eth::{EthApiTypes, FullEthApiServer},
-    EthApi,
+    eth::{DatabaseLogIndex, EthApiTypes, FullEthApiServer, LogIndexer},
+    AddressIndexer, BlockIndexTask, DatabaseAddressIndex, DatabaseTraceIndex, EthApi, OtterscanApi,
+    TraceIndexer,
 };
-use reth_rpc_api::eth::helpers::AddDevSigners;
+use reth_rpc_api::{eth::helpers::AddDevSigners, OtterscanServer, StatelessValidationApiServer};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
+    client_limits::RpcClientLimits,
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    tenancy::{RpcTenants, RpcTenantsConfig, DEFAULT_TENANT_USAGE_PERSIST_INTERVAL},
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
+use reth_rpc_graphql::start_graphql_server;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
 
@@ -421,7 +428,7 @@ where
         let Self { eth_api_builder, engine_validator_builder, hooks, _pd: _ } = self;
 
         let engine_validator = engine_validator_builder.build(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret } = ctx;
+        let AddOnsContext { node, config, toml_config, beacon_engine_handle, jwt_secret } = ctx;
 
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
@@ -447,7 +454,7 @@ where
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
-        let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
+        let mut rpc_module_builder = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
             .with_network(node.network().clone())
@@ -455,14 +462,63 @@ where
             .with_executor(node.task_executor().clone())
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
-            .with_consensus(node.consensus().clone())
-            .build_with_auth_server(module_config, engine_api, eth_api_builder);
+            .with_consensus(node.consensus().clone());
+
+        if config.rpc.rpc_log_index {
+            info!(target: "reth::cli", "RPC log index enabled");
+            let task = BlockIndexTask::new(
+                node.provider().clone(),
+                LogIndexer::new(node.provider().clone()),
+            );
+            node.task_executor().spawn_critical_blocking("rpc log index", task.run());
+            rpc_module_builder = rpc_module_builder
+                .with_log_index(Arc::new(DatabaseLogIndex::new(node.provider().clone())));
+        }
+        if config.rpc.rpc_trace_index {
+            rpc_module_builder = rpc_module_builder
+                .with_trace_index(Arc::new(DatabaseTraceIndex::new(node.provider().clone())));
+        }
+
+        let (mut modules, mut auth_module, registry) =
+            rpc_module_builder.build_with_auth_server(module_config, engine_api, eth_api_builder);
+
+        // the trace index is built with a trace API of its own, so indexing doesn't evict the
+        // block traces cached for requests
+        if config.rpc.rpc_trace_index {
+            info!(target: "reth::cli", "RPC trace index enabled");
+            let task = BlockIndexTask::new(
+                node.provider().clone(),
+                TraceIndexer::new(registry.trace_api()),
+            );
+            node.task_executor().spawn_critical_blocking("rpc trace index", task.run());
+        }
+
+        // validation of payloads against an execution witness is served next to the engine API
+        auth_module.merge_auth_methods(registry.stateless_validation_api().into_rpc())?;
 
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
         }
 
+        // search the transactions of an address with the address index
+        if config.rpc.rpc_address_index {
+            info!(target: "reth::cli", "RPC address index enabled");
+            let task = BlockIndexTask::new(
+                node.provider().clone(),
+                AddressIndexer::new(node.provider().clone()),
+            );
+            node.task_executor().spawn_critical_blocking("rpc address index", task.run());
+            modules.replace_if_module_configured(
+                RethRpcModule::Ots,
+                OtterscanApi::with_address_index(
+                    registry.eth_api().clone(),
+                    Arc::new(DatabaseAddressIndex::new(node.provider().clone())),
+                )
+                .into_rpc(),
+            )?;
+        }
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -477,7 +533,28 @@ where
         ext(ctx.modules)?;
         extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-        let server_config = config.rpc.rpc_server_config();
+        let mut server_config = config.rpc.rpc_server_config();
+        if config.rpc.rpc_tenants.is_some() || !toml_config.rpc.tenants.is_empty() {
+            let tenants = RpcTenants::load_with(
+                config.rpc.rpc_tenants.as_deref(),
+                RpcTenantsConfig { tenants: toml_config.rpc.tenants.clone() },
+                Some(config.datadir().rpc_tenant_usage()),
+            )?;
+            info!(target: "reth::cli", path=?config.rpc.rpc_tenants, tenants=tenants.usage().len(), "RPC multi-tenancy enabled");
+            let persisted = tenants.clone();
+            node.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "rpc tenant usage",
+                |shutdown| {
+                    persisted.persist_usage_task(DEFAULT_TENANT_USAGE_PERSIST_INTERVAL, shutdown)
+                },
+            );
+            server_config = server_config.with_tenants(tenants);
+        }
+        if let Some(client_limits) = toml_config.rpc.client_limits.clone() {
+            info!(target: "reth::cli", ?client_limits, "RPC client rate limits enabled");
+            server_config = server_config.with_client_limits(RpcClientLimits::new(client_limits));
+        }
+        cpu_profiler().set_output_dir(config.datadir().cpu_profiles());
         let cloned_modules = modules.clone();
         let launch_rpc = server_config.start(&cloned_modules).map_ok(|handle| {
             if let Some(path) = handle.ipc_endpoint() {
@@ -505,6 +582,14 @@ where
         // launch servers concurrently
         let (rpc, auth) = futures::future::try_join(launch_rpc, launch_auth).await?;
 
+        if config.rpc.graphql {
+            let addr = SocketAddr::new(config.rpc.graphql_addr, config.rpc.graphql_port);
+            let addr =
+                start_graphql_server(addr, node.provider().clone(), node.task_executor().clone())
+                    .await?;
+            info!(target: "reth::cli", url=%addr, "GraphQL server started");
+        }
+
         let handles = RethRpcServerHandles { rpc, auth };
 
         let ctx = RpcContext {
@@ -15,7 +15,8 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, EthBlockClient,
 };
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_snap::{SnapClient, SnapSyncStage};
+use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageId, StageSet};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
@@ -35,6 +36,7 @@ pub fn build_networked_pipeline<N, Client, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<SnapClient>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -62,12 +64,16 @@ where
         static_file_producer,
         executor,
         exex_manager_handle,
+        snap_client,
     )?;
 
     Ok(pipeline)
 }
 
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
+///
+/// If a [`SnapClient`] is given, the state is snap synced by the [`SnapSyncStage`] instead of
+/// executing the blocks, on a node that didn't execute any blocks yet.
 #[allow(clippy::too_many_arguments)]
 pub fn build_pipeline<N, H, B, Executor>(
     provider_factory: ProviderFactory<N>,
@@ -81,6 +87,7 @@ pub fn build_pipeline<N, H, B, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<SnapClient>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -99,28 +106,41 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    // the receipts of the blocks up to the snap synced block are not available
+    if snap_client.is_some() && prune_modes.receipts.is_none() {
+        eyre::bail!("snap sync requires receipts pruning")
+    }
+
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(ExecutionStage::new(
+        executor,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        prune_modes,
+        exex_manager_handle,
+    ));
+    if let Some(snap_client) = snap_client {
+        debug!(target: "reth::cli", "Configuring pipeline to snap sync the state");
+        stages = stages.add_before(
+            SnapSyncStage::new(provider_factory.clone(), snap_client, Default::default()),
+            StageId::SenderRecovery,
+        );
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(ExecutionStage::new(
-                executor,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                prune_modes,
-                exex_manager_handle,
-            )),
-        )
+        .with_state_root_check_interval(stage_config.merkle.state_root_check_interval)
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
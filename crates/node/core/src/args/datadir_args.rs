@@ -27,6 +27,14 @@ pub struct DatadirArgs {
         verbatim_doc_comment
     )]
     pub static_files_path: Option<PathBuf>,
+
+    /// HTTP(S) or `s3://` URL to fetch static files from when they are missing locally.
+    ///
+    /// The location must serve the static files next to a `SHA256SUMS` manifest. Missing files
+    /// are downloaded on first access and validated against the manifest, so only recent static
+    /// files have to be kept on disk. Requires the `remote-static-files` feature.
+    #[arg(long = "datadir.static-files-remote", value_name = "URL")]
+    pub static_files_remote: Option<String>,
 }
 
 impl DatadirArgs {
@@ -55,4 +63,15 @@ mod tests {
         let args = CommandParser::<DatadirArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_static_files_remote() {
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--datadir.static-files-remote",
+            "s3://bucket/mainnet",
+        ])
+        .args;
+        assert_eq!(args.static_files_remote.as_deref(), Some("s3://bucket/mainnet"));
+    }
 }
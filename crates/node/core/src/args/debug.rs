@@ -33,6 +33,7 @@ pub struct DebugArgs {
         help_heading = "Debug",
         conflicts_with = "tip",
         conflicts_with = "rpc_consensus_ws",
+        conflicts_with = "rpc_consensus_url",
         value_name = "ETHERSCAN_API_URL"
     )]
     pub etherscan: Option<Option<String>>,
@@ -42,10 +43,50 @@ pub struct DebugArgs {
         long = "debug.rpc-consensus-ws",
         help_heading = "Debug",
         conflicts_with = "tip",
-        conflicts_with = "etherscan"
+        conflicts_with = "etherscan",
+        conflicts_with = "rpc_consensus_url"
     )]
     pub rpc_consensus_ws: Option<String>,
 
+    /// Runs a fake consensus client that follows the chain of another node, by polling its HTTP
+    /// RPC endpoint for blocks with `eth_getBlockByNumber` and executing them locally, starting
+    /// at the block after the local head.
+    #[arg(
+        long = "debug.rpc-consensus-url",
+        help_heading = "Debug",
+        conflicts_with = "tip",
+        conflicts_with = "etherscan",
+        conflicts_with = "rpc_consensus_ws",
+        value_name = "URL"
+    )]
+    pub rpc_consensus_url: Option<String>,
+
+    /// Runs an embedded beacon light client that follows the chain using the sync committee
+    /// signed light client updates of the given beacon node API, instead of an external consensus
+    /// client. The beacon node doesn't need to be trusted, but the node can't be used for
+    /// staking.
+    #[arg(
+        long = "debug.beacon-light-client",
+        help_heading = "Debug",
+        conflicts_with = "tip",
+        conflicts_with = "etherscan",
+        conflicts_with = "rpc_consensus_ws",
+        conflicts_with = "rpc_consensus_url",
+        value_name = "BEACON_API_URL",
+        verbatim_doc_comment
+    )]
+    pub beacon_light_client: Option<String>,
+
+    /// The trusted beacon block root to bootstrap the beacon light client from, e.g. a recent
+    /// finalized checkpoint. If not set, the finalized block of the beacon node is trusted.
+    #[arg(
+        long = "debug.beacon-checkpoint",
+        help_heading = "Debug",
+        requires = "beacon_light_client",
+        value_name = "BLOCK_ROOT"
+    )]
+    pub beacon_checkpoint: Option<B256>,
+
     /// If provided, the engine will skip `n` consecutive FCUs.
     #[arg(long = "debug.skip-fcu", help_heading = "Debug")]
     pub skip_fcu: Option<usize>,
@@ -87,6 +128,19 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// Executes every new canonical block a second time under the rules of the given hardfork,
+    /// or of the next scheduled hardfork of the chain if none is given, and reports transactions
+    /// that behave differently.
+    ///
+    /// Example: `--debug.fork-dry-run Prague`
+    #[arg(
+        long = "debug.fork-dry-run",
+        help_heading = "Debug",
+        value_name = "HARDFORK",
+        verbatim_doc_comment
+    )]
+    pub fork_dry_run: Option<Option<String>>,
 }
 
 impl Default for DebugArgs {
@@ -97,6 +151,9 @@ impl Default for DebugArgs {
             max_block: None,
             etherscan: None,
             rpc_consensus_ws: None,
+            rpc_consensus_url: None,
+            beacon_light_client: None,
+            beacon_checkpoint: None,
             skip_fcu: None,
             skip_new_payload: None,
             reorg_frequency: None,
@@ -104,6 +161,7 @@ impl Default for DebugArgs {
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            fork_dry_run: None,
         }
     }
 }
@@ -334,6 +392,56 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_parse_fork_dry_run_args() {
+        let args = CommandParser::<DebugArgs>::parse_from(["reth", "--debug.fork-dry-run"]).args;
+        assert_eq!(args.fork_dry_run, Some(None));
+
+        let args =
+            CommandParser::<DebugArgs>::parse_from(["reth", "--debug.fork-dry-run", "Prague"]).args;
+        assert_eq!(args.fork_dry_run, Some(Some("Prague".to_string())));
+    }
+
+    #[test]
+    fn test_parse_rpc_consensus_url_args() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.rpc-consensus-url",
+            "http://localhost:8545",
+        ])
+        .args;
+        assert_eq!(args.rpc_consensus_url, Some("http://localhost:8545".to_string()));
+
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.rpc-consensus-url",
+            "http://localhost:8545",
+            "--debug.etherscan",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_beacon_light_client_args() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.beacon-light-client",
+            "http://localhost:5052",
+            "--debug.beacon-checkpoint",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .args;
+        assert_eq!(args.beacon_light_client, Some("http://localhost:5052".to_string()));
+        assert_eq!(args.beacon_checkpoint, Some(B256::with_last_byte(1)));
+
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.beacon-checkpoint",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_invalid_block_args() {
         let expected_args = DebugArgs {
@@ -4,6 +4,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use clap::Args;
@@ -14,8 +15,10 @@ use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
     DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL, DEFAULT_SECONDS_LOOKUP_INTERVAL,
 };
+use reth_light_proofs::{LightProofsConfig, DEFAULT_PROOF_WINDOW};
 use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
 use reth_network::{
+    eth_requests::{EthRequestHandlerConfig, DEFAULT_RECENT_BLOCKS},
     transactions::{
         constants::{
             tx_fetcher::{
@@ -23,21 +26,29 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS,
+                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        TransactionFetcherConfig, TransactionPropagationMode, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
+    BandwidthLimits, HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
+use reth_network_sentry::DEFAULT_SENTRY_GRPC_PORT;
+use reth_snap::SnapServerConfig;
 use secp256k1::SecretKey;
 use tracing::error;
 
+use super::database::ByteSize;
 use crate::version::P2P_CLIENT_VERSION;
 
+/// Name of the file the peer reputations are persisted to, next to the known peers file.
+const PEER_REPUTATIONS_FILE_NAME: &str = "peer-reputations.json";
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -88,6 +99,8 @@ pub struct NetworkArgs {
     pub no_persist_peers: bool,
 
     /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    ///
+    /// With `upnp`, the p2p ports are also forwarded on the router via `UPnP` or NAT-PMP.
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -107,6 +120,45 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
 
+    /// Maximum bytes per second sent to all peers, e.g. `10MB`. Unlimited by default.
+    #[arg(long = "bandwidth.max-upload", value_name = "BYTES")]
+    pub max_upload: Option<ByteSize>,
+
+    /// Maximum bytes per second received from all peers, e.g. `10MB`. Unlimited by default.
+    #[arg(long = "bandwidth.max-download", value_name = "BYTES")]
+    pub max_download: Option<ByteSize>,
+
+    /// Maximum bytes per second sent to a single peer. Unlimited by default.
+    ///
+    /// This keeps a single peer, e.g. one that is syncing the history from this node, from using
+    /// all of the upload bandwidth.
+    #[arg(long = "bandwidth.max-peer-upload", value_name = "BYTES")]
+    pub max_peer_upload: Option<ByteSize>,
+
+    /// Maximum bytes per second received from a single peer. Unlimited by default.
+    #[arg(long = "bandwidth.max-peer-download", value_name = "BYTES")]
+    pub max_peer_download: Option<ByteSize>,
+
+    /// Number of blocks below the head whose headers and bodies are served before older ones.
+    ///
+    /// Headers and bodies of these blocks don't count towards `--serve.max-history-per-peer`.
+    #[arg(long = "serve.recent-blocks", value_name = "BLOCKS", default_value_t = DEFAULT_RECENT_BLOCKS)]
+    pub serve_recent_blocks: u64,
+
+    /// Max number of headers and bodies older than the recent blocks that are served to a single
+    /// peer per minute. Unlimited by default.
+    ///
+    /// This keeps peers from syncing the entire history from this node.
+    #[arg(long = "serve.max-history-per-peer", value_name = "COUNT")]
+    pub serve_max_history_per_peer: Option<u64>,
+
+    /// The earliest block whose header and body are served to peers, e.g. because older blocks
+    /// were pruned.
+    ///
+    /// The block is advertised as the earliest available block to `eth/69` peers.
+    #[arg(long = "serve.earliest-block", value_name = "BLOCK", default_value_t = 0)]
+    pub serve_earliest_block: u64,
+
     /// Max concurrent `GetPooledTransactions` requests.
     #[arg(long = "max-tx-reqs", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS, verbatim_doc_comment)]
     pub max_concurrent_tx_requests: u32,
@@ -149,11 +201,58 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Interval in seconds at which pending transactions that were submitted to this node are
+    /// rebroadcast to all peers, until they are included or replaced. 0 disables the rebroadcast.
+    #[arg(long = "local-tx-rebroadcast-interval", value_name = "SECONDS", default_value_t = DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS)]
+    pub local_tx_rebroadcast_interval: u64,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
     #[arg(long = "net-if.experimental", conflicts_with = "addr", value_name = "IF_NAME")]
     pub net_if: Option<String>,
+
+    /// Serve block headers and account and storage proofs to light clients over the `lproof`
+    /// `RLPx` subprotocol.
+    #[arg(long = "light.serve")]
+    pub light_serve: bool,
+
+    /// The number of blocks behind the tip for which light clients are served state proofs.
+    #[arg(long = "light.proof-window", value_name = "BLOCKS", default_value_t = DEFAULT_PROOF_WINDOW, requires = "light_serve")]
+    pub light_proof_window: u64,
+
+    /// Serve account ranges, storage ranges, bytecodes and trie nodes of the latest state to
+    /// peers that snap sync, over the `snap/1` `RLPx` subprotocol.
+    #[arg(long = "snap.serve")]
+    pub snap_serve: bool,
+
+    /// Download the state of a recent block from peers over `snap/1` instead of executing all
+    /// blocks up to it, if no blocks were executed yet.
+    ///
+    /// The senders, receipts and history of the blocks up to that block are not available
+    /// afterwards, which requires pruning them.
+    #[arg(long = "snap.sync")]
+    pub snap_sync: bool,
+
+    /// Serve the network over the sentry gRPC interface, so that other nodes can download
+    /// blocks through the peers of this node.
+    #[arg(long = "sentry.grpc")]
+    pub sentry_grpc: bool,
+
+    /// The address of the sentry gRPC server.
+    #[arg(long = "sentry.grpc.addr", value_name = "ADDR", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub sentry_grpc_addr: IpAddr,
+
+    /// The port of the sentry gRPC server.
+    #[arg(long = "sentry.grpc.port", value_name = "PORT", default_value_t = DEFAULT_SENTRY_GRPC_PORT)]
+    pub sentry_grpc_port: u16,
+
+    /// Comma separated gRPC endpoints of remote sentries, e.g. `http://10.0.0.2:9091`.
+    ///
+    /// Blocks are downloaded through the sentries instead of the peers of this node. If a sentry
+    /// is unavailable, requests are sent to the other sentries.
+    #[arg(long = "sentry.remote", value_name = "URL", value_delimiter = ',')]
+    pub sentry_remote: Vec<String>,
 }
 
 impl NetworkArgs {
@@ -208,6 +307,7 @@ impl NetworkArgs {
             .resolved_bootnodes()
             .unwrap_or_else(|| chain_spec.bootnodes().unwrap_or_else(mainnet_nodes));
         let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
+        let peer_reputations_file = self.persistent_peer_reputations_file(peers_file.clone());
 
         // Configure peer connections
         let peers_config = config
@@ -215,8 +315,13 @@ impl NetworkArgs {
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
+        let peers_config = peers_config
+            .clone()
+            .with_peer_reputations_from_file(peer_reputations_file)
+            .unwrap_or(peers_config);
 
         // Configure transactions manager
+        let tx_propagation = &config.tx_propagation;
         let transactions_manager_config = TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::new(
                 self.max_concurrent_tx_requests,
@@ -226,7 +331,15 @@ impl NetworkArgs {
                 self.max_capacity_cache_txns_pending_fetch,
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
-            propagation_mode: Default::default(),
+            propagation_mode: tx_propagation
+                .sqrt_fanout_percent
+                .map(|percent| TransactionPropagationMode::ScaledSqrt(percent as f64 / 100.0))
+                .unwrap_or_default(),
+            local_transactions_rebroadcast_interval: (self.local_tx_rebroadcast_interval > 0)
+                .then(|| Duration::from_secs(self.local_tx_rebroadcast_interval)),
+            max_full_broadcast_transaction_size: tx_propagation.max_full_broadcast_size,
+            announce_only: tx_propagation.announce_only,
+            disable_blob_propagation: tx_propagation.disable_blob_propagation,
         };
 
         // Configure basic network stack
@@ -236,11 +349,14 @@ impl NetworkArgs {
             ))
             .external_ip_resolver(self.nat)
             .sessions_config(
-                SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
+                SessionsConfig::default()
+                    .with_upscaled_event_buffer(peers_config.max_peers())
+                    .with_bandwidth_limits(self.bandwidth_limits(config.sessions.bandwidth)),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .transactions_manager_config(transactions_manager_config)
+            .eth_requests_config(self.eth_requests_config())
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -266,11 +382,57 @@ impl NetworkArgs {
             ))
     }
 
+    /// Returns the bandwidth limits of the sessions, the limits set via CLI take precedence over
+    /// the configured limits.
+    pub fn bandwidth_limits(&self, config: BandwidthLimits) -> BandwidthLimits {
+        let bytes = |size: Option<ByteSize>| size.map(|size| size.0 as u64);
+        BandwidthLimits {
+            max_upload: bytes(self.max_upload).or(config.max_upload),
+            max_download: bytes(self.max_download).or(config.max_download),
+            max_peer_upload: bytes(self.max_peer_upload).or(config.max_peer_upload),
+            max_peer_download: bytes(self.max_peer_download).or(config.max_peer_download),
+        }
+    }
+
+    /// Returns how headers and bodies are served to peers.
+    pub fn eth_requests_config(&self) -> EthRequestHandlerConfig {
+        EthRequestHandlerConfig {
+            recent_blocks: self.serve_recent_blocks,
+            max_history_per_peer: self.serve_max_history_per_peer,
+            earliest_block: self.serve_earliest_block,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the configuration of the light client proof server, if it's enabled.
+    pub fn light_proofs_config(&self) -> Option<LightProofsConfig> {
+        self.light_serve.then(|| LightProofsConfig {
+            proof_window: self.light_proof_window,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the configuration of the snap server, if it's enabled.
+    pub fn snap_server_config(&self) -> Option<SnapServerConfig> {
+        self.snap_serve.then(SnapServerConfig::default)
+    }
+
+    /// Returns the address of the sentry gRPC server.
+    pub const fn sentry_grpc_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.sentry_grpc_addr, self.sentry_grpc_port)
+    }
+
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
     pub fn persistent_peers_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the file the peer reputations
+    /// are persisted to, which is stored next to the given peers file.
+    pub fn persistent_peer_reputations_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
+        self.persistent_peers_file(peers_file.with_file_name(PEER_REPUTATIONS_FILE_NAME))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -294,6 +456,7 @@ impl NetworkArgs {
     pub fn adjust_instance_ports(&mut self, instance: u16) {
         debug_assert_ne!(instance, 0, "instance must be non-zero");
         self.port += instance - 1;
+        self.sentry_grpc_port += instance - 1;
         self.discovery.adjust_instance_ports(instance);
     }
 
@@ -323,6 +486,13 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            max_upload: None,
+            max_download: None,
+            max_peer_upload: None,
+            max_peer_download: None,
+            serve_recent_blocks: DEFAULT_RECENT_BLOCKS,
+            serve_max_history_per_peer: None,
+            serve_earliest_block: 0,
             max_concurrent_tx_requests: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
             max_concurrent_tx_requests_per_peer: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             soft_limit_byte_size_pooled_transactions_response:
@@ -331,7 +501,16 @@ impl Default for NetworkArgs {
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            local_tx_rebroadcast_interval: DEFAULT_LOCAL_TRANSACTIONS_REBROADCAST_INTERVAL_SECS,
             net_if: None,
+            light_serve: false,
+            light_proof_window: DEFAULT_PROOF_WINDOW,
+            snap_serve: false,
+            snap_sync: false,
+            sentry_grpc: false,
+            sentry_grpc_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            sentry_grpc_port: DEFAULT_SENTRY_GRPC_PORT,
+            sentry_remote: vec![],
         }
     }
 }
@@ -404,6 +583,11 @@ pub struct DiscoveryArgs {
     #[arg(id = "discovery.v5.bootstrap.lookup-countdown", long = "discovery.v5.bootstrap.lookup-countdown", value_name = "DISCOVERY_V5_BOOTSTRAP_LOOKUP_COUNTDOWN",
         default_value_t = DEFAULT_COUNT_BOOTSTRAP_LOOKUPS)]
     pub discv5_bootstrap_lookup_countdown: u64,
+
+    /// Only connect to peers discovered via discv5 that advertise the fork ID of the chain in
+    /// their node record.
+    #[arg(id = "discovery.v5.require-fork-id", long = "discovery.v5.require-fork-id")]
+    pub discv5_require_fork_id: bool,
 }
 
 impl DiscoveryArgs {
@@ -448,6 +632,7 @@ impl DiscoveryArgs {
             discv5_lookup_interval,
             discv5_bootstrap_lookup_interval,
             discv5_bootstrap_lookup_countdown,
+            discv5_require_fork_id,
             ..
         } = self;
 
@@ -473,6 +658,7 @@ impl DiscoveryArgs {
             .lookup_interval(*discv5_lookup_interval)
             .bootstrap_lookup_interval(*discv5_bootstrap_lookup_interval)
             .bootstrap_lookup_countdown(*discv5_bootstrap_lookup_countdown)
+            .require_fork(*discv5_require_fork_id)
     }
 
     /// Set the discovery port to zero, to allow the OS to assign a random unused port when
@@ -512,6 +698,7 @@ impl Default for DiscoveryArgs {
             discv5_lookup_interval: DEFAULT_SECONDS_LOOKUP_INTERVAL,
             discv5_bootstrap_lookup_interval: DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL,
             discv5_bootstrap_lookup_countdown: DEFAULT_COUNT_BOOTSTRAP_LOOKUPS,
+            discv5_require_fork_id: false,
         }
     }
 }
@@ -535,6 +722,9 @@ mod tests {
         let args =
             CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "extip:0.0.0.0"]).args;
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "upnp"]).args;
+        assert_eq!(args.nat, NatResolver::Upnp);
     }
 
     #[test]
@@ -556,6 +746,29 @@ mod tests {
         assert_eq!(args.max_inbound_peers, Some(15));
     }
 
+    #[test]
+    fn parse_bandwidth_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--bandwidth.max-upload",
+            "10MB",
+            "--bandwidth.max-peer-upload",
+            "512KB",
+        ])
+        .args;
+
+        let config = BandwidthLimits::default().with_max_upload(1).with_max_download(2048);
+        assert_eq!(
+            args.bandwidth_limits(config),
+            BandwidthLimits {
+                max_upload: Some(10 * 1024 * 1024),
+                max_download: Some(2048),
+                max_peer_upload: Some(512 * 1024),
+                max_peer_download: None,
+            }
+        );
+    }
+
     #[test]
     fn parse_trusted_peer_args() {
         let args =
@@ -591,6 +804,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_light_serve_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.light_proofs_config(), None);
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--light.serve",
+            "--light.proof-window",
+            "128",
+        ])
+        .args;
+        assert_eq!(
+            args.light_proofs_config(),
+            Some(LightProofsConfig { proof_window: 128, ..Default::default() })
+        );
+
+        // the proof window requires the server to be enabled
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--light.proof-window",
+            "128"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_sentry_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.sentry_grpc);
+        assert_eq!(
+            args.sentry_grpc_socket_addr(),
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), DEFAULT_SENTRY_GRPC_PORT)
+        );
+        assert!(args.sentry_remote.is_empty());
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--sentry.grpc",
+            "--sentry.grpc.addr",
+            "0.0.0.0",
+            "--sentry.remote",
+            "http://10.0.0.2:9091,http://10.0.0.3:9091",
+        ])
+        .args;
+        assert!(args.sentry_grpc);
+        assert_eq!(
+            args.sentry_grpc_socket_addr(),
+            SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), DEFAULT_SENTRY_GRPC_PORT)
+        );
+        assert_eq!(args.sentry_remote, vec!["http://10.0.0.2:9091", "http://10.0.0.3:9091"]);
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
@@ -35,6 +35,11 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Build candidate payloads with different transaction selections in parallel and keep the
+    /// most valuable one.
+    #[arg(long = "builder.parallel", default_value_t = false)]
+    pub parallel: bool,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +50,7 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            parallel: false,
         }
     }
 }
@@ -69,6 +75,10 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn parallel(&self) -> bool {
+        self.parallel
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -14,7 +14,7 @@ use clap::{
     Arg, Args, Command,
 };
 use rand::Rng;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{constants, RethRpcModule, RpcMethodLimits, RpcModuleSelection};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -79,6 +79,18 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Enable the read-only GraphQL server, see EIP-1767
+    #[arg(long)]
+    pub graphql: bool,
+
+    /// GraphQL server address to listen on
+    #[arg(long = "graphql.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub graphql_addr: IpAddr,
+
+    /// GraphQL server port to listen on
+    #[arg(long = "graphql.port", default_value_t = constants::DEFAULT_GRAPHQL_PORT)]
+    pub graphql_port: u16,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -87,6 +99,10 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Rpc Modules to be configured for the IPC server, all modules if not set
+    #[arg(long = "ipc.api", value_parser = RpcModuleSelectionValueParser::default())]
+    pub ipc_api: Option<RpcModuleSelection>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -153,6 +169,29 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maintain an index of the blocks that contain logs of every address and topic, which is
+    /// used to serve `eth_getLogs` and log filters.
+    ///
+    /// The index is stored in the database and built in the background, starting at genesis.
+    #[arg(long = "rpc.log-index")]
+    pub rpc_log_index: bool,
+
+    /// Maintain an index of the blocks that contain traces from or to every address, which is
+    /// used to serve `trace_filter`.
+    ///
+    /// The index is stored in the database and built in the background by tracing every block,
+    /// starting at genesis, which requires the historical state of all blocks.
+    #[arg(long = "rpc.trace-index")]
+    pub rpc_trace_index: bool,
+
+    /// Maintain an index of the blocks that contain transactions of every address, which is used
+    /// to serve `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`.
+    ///
+    /// The index is stored in the database and built in the background, starting at genesis.
+    /// Without it, the address search of the `ots` namespace is not available.
+    #[arg(long = "rpc.address-index")]
+    pub rpc_address_index: bool,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -185,6 +224,35 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Bind the HTTP and WS servers with `SO_REUSEPORT`.
+    ///
+    /// Allows a restarted node to start listening on the same ports while the old instance is
+    /// still shutting down, so clients are not refused during rolling restarts.
+    #[arg(long = "rpc.reuse-port")]
+    pub rpc_reuse_port: bool,
+
+    /// Use the HTTP and WS listeners passed by systemd socket activation.
+    ///
+    /// Listeners bound to the configured HTTP or WS address are taken from the `LISTEN_FDS`
+    /// inherited file descriptors instead of binding new sockets.
+    #[arg(long = "rpc.socket-activation")]
+    pub rpc_socket_activation: bool,
+
+    /// Path to a TOML file of API key tenants, enables multi-tenancy on the HTTP and WS servers.
+    ///
+    /// Requests must carry a known API key in the `x-api-key` header. Each tenant can be
+    /// restricted to a set of namespaces, a rate limit and a total request quota.
+    #[arg(long = "rpc.tenants", value_name = "PATH")]
+    pub rpc_tenants: Option<PathBuf>,
+
+    /// Limit the number of concurrent calls per method on the HTTP and WS servers, e.g.
+    /// `trace_*=4,eth_call=64`.
+    ///
+    /// A method is either a method name or a prefix followed by `*`. Calls beyond the limit of
+    /// their method are queued until a call of the same method completes.
+    #[arg(long = "rpc.method-limits", value_name = "LIMITS")]
+    pub rpc_method_limits: Option<RpcMethodLimits>,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
@@ -267,6 +335,13 @@ impl RpcServerArgs {
         self
     }
 
+    /// Set the GraphQL port to zero, to allow the OS to assign a random unused port when the
+    /// server binds to a socket.
+    pub const fn with_graphql_unused_port(mut self) -> Self {
+        self.graphql_port = 0;
+        self
+    }
+
     /// Set the auth port to zero, to allow the OS to assign a random unused port when the rpc
     /// server binds to a socket.
     pub const fn with_auth_unused_port(mut self) -> Self {
@@ -291,6 +366,7 @@ impl RpcServerArgs {
     pub fn with_unused_ports(mut self) -> Self {
         self = self.with_http_unused_port();
         self = self.with_ws_unused_port();
+        self = self.with_graphql_unused_port();
         self = self.with_auth_unused_port();
         self = self.with_ipc_random_path();
         self
@@ -310,8 +386,12 @@ impl Default for RpcServerArgs {
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            graphql: false,
+            graphql_addr: Ipv4Addr::LOCALHOST.into(),
+            graphql_port: constants::DEFAULT_GRAPHQL_PORT,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_api: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
@@ -325,12 +405,19 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_log_index: false,
+            rpc_trace_index: false,
+            rpc_address_index: false,
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_reuse_port: false,
+            rpc_socket_activation: false,
+            rpc_tenants: None,
+            rpc_method_limits: None,
             builder_disallow: Default::default(),
         }
     }
@@ -412,6 +499,71 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_socket_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.reuse-port",
+            "--rpc.socket-activation",
+        ])
+        .args;
+        assert!(args.rpc_reuse_port);
+        assert!(args.rpc_socket_activation);
+    }
+
+    #[test]
+    fn test_rpc_server_ipc_api_args() {
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.api", "eth,debug"]).args;
+        let apis = args.ipc_api.unwrap();
+        let expected = RpcModuleSelection::try_from_selection(["eth", "debug"]).unwrap();
+        assert_eq!(apis, expected);
+    }
+
+    #[test]
+    fn test_rpc_server_graphql_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--graphql",
+            "--graphql.addr",
+            "0.0.0.0",
+            "--graphql.port",
+            "9547",
+        ])
+        .args;
+        assert!(args.graphql);
+        assert_eq!(args.graphql_addr, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(args.graphql_port, 9547);
+    }
+
+    #[test]
+    fn test_rpc_server_tenants_args() {
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.tenants", "tenants.toml"])
+                .args;
+        assert_eq!(args.rpc_tenants, Some(PathBuf::from("tenants.toml")));
+    }
+
+    #[test]
+    fn test_rpc_server_method_limits_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.method-limits",
+            "trace_*=4,eth_call=64",
+        ])
+        .args;
+        let limits = args.rpc_method_limits.unwrap();
+        assert_eq!(limits.get("trace_filter").unwrap().max_concurrent.get(), 4);
+        assert_eq!(limits.get("eth_call").unwrap().max_concurrent.get(), 64);
+
+        let res = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.method-limits",
+            "eth_call=0",
+        ]);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
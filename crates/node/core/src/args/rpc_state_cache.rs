@@ -1,7 +1,7 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
+    DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Parameters to configure RPC state cache.
@@ -29,6 +29,13 @@ pub struct RpcStateCacheArgs {
     )]
     pub max_envs: u32,
 
+    /// Max number of blocks whose `eth_getBlockReceipts` responses are cached.
+    #[arg(
+        long = "rpc-cache.max-block-receipts",
+        default_value_t = DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
+    )]
+    pub max_block_receipts: u32,
+
     /// Max number of concurrent database requests.
     #[arg(
         long = "rpc-cache.max-concurrent-db-requests",
@@ -43,6 +50,7 @@ impl Default for RpcStateCacheArgs {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_block_receipts: DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
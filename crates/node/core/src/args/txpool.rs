@@ -5,14 +5,17 @@ use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
 use clap::Args;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    blobstore::{disk::DEFAULT_MAX_CACHED_BLOBS, DEFAULT_BLOB_RETENTION_BLOCKS},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    ADMISSION_LOG_MAX_SIZE_MB_DEFAULT, DEFAULT_PRICE_BUMP, DEFAULT_PRIVATE_TX_LIFETIME,
+    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
+    MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::path::PathBuf;
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -76,9 +79,18 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
     /// Number of additional transaction validation tasks to spawn.
+    ///
+    /// Batches of transactions that are larger than `--txpool.validation-batch-size` are
+    /// validated concurrently by these tasks.
     #[arg(long = "txpool.additional-validation-tasks", alias = "txpool.additional_validation_tasks", default_value_t = DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS)]
     pub additional_validation_tasks: usize,
 
+    /// Max number of transactions that are validated together by one validation task.
+    ///
+    /// The accounts of the senders of a batch are fetched together.
+    #[arg(long = "txpool.validation-batch-size", default_value_t = DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE)]
+    pub validation_batch_size: usize,
+
     /// Maximum number of pending transactions from the network to buffer
     #[arg(long = "txpool.max-pending-txns", alias = "txpool.max_pending_txns", default_value_t = PENDING_TX_LISTENER_BUFFER_SIZE)]
     pub pending_tx_listener_buffer_size: usize,
@@ -91,6 +103,41 @@ pub struct TxPoolArgs {
     /// iterators.
     #[arg(long = "txpool.max-new-pending-txs-notifications", alias = "txpool.max-new-pending-txs-notifications", default_value_t = MAX_NEW_PENDING_TXS_NOTIFICATIONS)]
     pub max_new_pending_txs_notifications: usize,
+
+    /// Record the admission decisions of the pool in a log file in the data directory, queryable
+    /// via `reth_getTxAdmissionHistory`.
+    #[arg(long = "txpool.admission-log")]
+    pub admission_log: bool,
+
+    /// Max size of the admission log in megabytes. The oldest decisions are dropped once the log
+    /// exceeds this size.
+    #[arg(long = "txpool.admission-log-max-size", default_value_t = ADMISSION_LOG_MAX_SIZE_MB_DEFAULT, requires = "admission_log")]
+    pub admission_log_max_size: u64,
+
+    /// Retain the blob sidecars of finalized blocks in the data directory, so they can still be
+    /// served via `engine_getBlobsV1` and `eth_getBlobSidecars` after they left the pool.
+    #[arg(long = "txpool.blob-archive")]
+    pub blob_archive: bool,
+
+    /// The number of blocks below the finalized block the archived blob sidecars are retained
+    /// for.
+    #[arg(long = "txpool.blob-retention-blocks", default_value_t = DEFAULT_BLOB_RETENTION_BLOCKS, requires = "blob_archive")]
+    pub blob_retention_blocks: u64,
+
+    /// The number of blocks private transactions are offered to the local payload builder for,
+    /// unless they specify a max block number. Private transactions are never propagated.
+    #[arg(long = "txpool.private-tx-lifetime", default_value_t = DEFAULT_PRIVATE_TX_LIFETIME)]
+    pub private_tx_lifetime: u64,
+
+    /// Path to a KZG trusted setup file that's used instead of the mainnet setup, e.g. for
+    /// devnets.
+    #[arg(long = "txpool.kzg-trusted-setup", value_name = "PATH")]
+    pub kzg_trusted_setup: Option<PathBuf>,
+
+    /// Verify the KZG proofs of blob transactions on a worker pool once they're promoted to
+    /// pending, instead of when they're added to the pool.
+    #[arg(long = "txpool.lazy-blob-verification")]
+    pub lazy_blob_verification: bool,
 }
 
 impl Default for TxPoolArgs {
@@ -113,9 +160,17 @@ impl Default for TxPoolArgs {
             locals: Default::default(),
             no_local_transactions_propagation: false,
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+            validation_batch_size: DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            admission_log: false,
+            admission_log_max_size: ADMISSION_LOG_MAX_SIZE_MB_DEFAULT,
+            blob_archive: false,
+            blob_retention_blocks: DEFAULT_BLOB_RETENTION_BLOCKS,
+            private_tx_lifetime: DEFAULT_PRIVATE_TX_LIFETIME,
+            kzg_trusted_setup: None,
+            lazy_blob_verification: false,
         }
     }
 }
@@ -155,6 +210,11 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
+            // the log file and the blob archive are placed in the data directory, which is set by
+            // the node builder
+            admission_log: None,
+            blob_archive: None,
+            private_tx_lifetime: self.private_tx_lifetime,
         }
     }
 }
@@ -29,6 +29,9 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Whether candidate payloads should be built in parallel.
+    fn parallel(&self) -> bool;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -323,6 +323,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the blob archive directory for this chain where blobs of finalized
+    /// transactions are retained.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blob-archive`
+    pub fn blob_archive(&self) -> PathBuf {
+        self.data_dir().join("blob-archive")
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
@@ -330,6 +338,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool admission log
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-admission.log`
+    pub fn txpool_admission_log(&self) -> PathBuf {
+        self.data_dir().join("txpool-admission.log")
+    }
+
+    /// Returns the path to the persisted request counters of the RPC tenants
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-tenant-usage.toml`
+    pub fn rpc_tenant_usage(&self) -> PathBuf {
+        self.data_dir().join("rpc-tenant-usage.toml")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,6 +373,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the CPU profiles directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/cpu-profiles`
+    pub fn cpu_profiles(&self) -> PathBuf {
+        self.data_dir().join("cpu-profiles")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -320,6 +320,23 @@ impl NodeState {
             StaticFileProducerEvent::Finished { targets, elapsed } => {
                 info!(?targets, ?elapsed, "Static File Producer finished");
             }
+            StaticFileProducerEvent::Progress { segment, rows_done, rows_total, eta } => {
+                let progress = EntitiesCheckpoint { processed: rows_done, total: rows_total }
+                    .fmt_percentage()
+                    .unwrap_or_default();
+                if let Some(eta) = eta {
+                    info!(
+                        %segment,
+                        rows_done,
+                        rows_total,
+                        %progress,
+                        eta = %humantime::format_duration(Duration::from_secs(eta.as_secs())),
+                        "Static File Producer in progress"
+                    );
+                } else {
+                    info!(%segment, rows_done, rows_total, %progress, "Static File Producer in progress");
+                }
+            }
         }
     }
 }
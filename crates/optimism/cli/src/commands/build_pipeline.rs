@@ -78,6 +78,7 @@ where
         // we want to sync all blocks the file client provides or 0 if empty
         .with_max_block(max_block)
         .with_fail_on_unwind(true)
+        .with_state_root_check_interval(config.stages.merkle.state_root_check_interval)
         .add_stages(
             DefaultStages::new(
                 provider_factory.clone(),
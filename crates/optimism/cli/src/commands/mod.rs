@@ -4,7 +4,7 @@ use import::ImportOpCommand;
 use import_receipts::ImportReceiptsOpCommand;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    config_cmd, db, doctor, dump_genesis, init_cmd,
     node::{self, NoArgs},
     p2p, prune, recover, stage,
 };
@@ -58,8 +58,11 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<Spec>),
+    /// Diagnose the datadir and suggest recovery commands
+    #[command(name = "doctor")]
+    Doctor(doctor::DoctorCommand<Spec>),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
-    TestVectors(test_vectors::Command),
+    TestVectors(test_vectors::Command<Spec>),
 }
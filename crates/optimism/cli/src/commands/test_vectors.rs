@@ -3,7 +3,9 @@
 use clap::{Parser, Subcommand};
 use op_alloy_consensus::TxDeposit;
 use proptest::test_runner::TestRunner;
+use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
+    common::CliNodeTypes,
     compact_types,
     test_vectors::{
         compact,
@@ -11,20 +13,22 @@ use reth_cli_commands::{
             generate_vector, read_vector, GENERATE_VECTORS as ETH_GENERATE_VECTORS,
             READ_VECTORS as ETH_READ_VECTORS,
         },
-        tables,
+        execution, tables,
     },
 };
+use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_evm::OpExecutorProvider;
 
 /// Generate test-vectors for different data types.
 #[derive(Debug, Parser)]
-pub struct Command {
+pub struct Command<C: ChainSpecParser> {
     #[command(subcommand)]
-    command: Subcommands,
+    command: Subcommands<C>,
 }
 
 #[derive(Subcommand, Debug)]
 /// `reth test-vectors` subcommands
-pub enum Subcommands {
+pub enum Subcommands<C: ChainSpecParser> {
     /// Generates test vectors for specified tables. If no table is specified, generate for all.
     Tables {
         /// List of table names. Case-sensitive.
@@ -42,11 +46,14 @@ pub enum Subcommands {
         #[arg(long)]
         read: bool,
     },
+    /// Generates an execution test vector for a range of blocks of the chain in the datadir.
+    /// Executes the previously generated vectors with `--read`.
+    Execution(execution::Command<C>),
 }
 
-impl Command {
+impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> Command<C> {
     /// Execute the command
-    pub async fn execute(self) -> eyre::Result<()> {
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         match self.command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
@@ -66,6 +73,9 @@ impl Command {
                     compact::read_vectors_with(READ_VECTORS)?;
                 }
             }
+            Subcommands::Execution(command) => {
+                command.execute::<N, _, _>(OpExecutorProvider::optimism).await?
+            }
         }
         Ok(())
     }
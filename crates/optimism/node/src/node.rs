@@ -49,6 +49,7 @@ pub struct OpPrimitives;
 
 impl NodePrimitives for OpPrimitives {
     type Block = Block;
+    type BlockHeader = Header;
     type SignedTx = TransactionSigned;
     type TxType = TxType;
     type Receipt = Receipt;
@@ -250,6 +251,7 @@ where
                 .additional_validation_tasks
                 .unwrap_or_else(|| ctx.config().txpool.additional_validation_tasks),
         )
+        .with_validation_batch_size(ctx.config().txpool.validation_batch_size)
         .build_with_tasks(ctx.provider().clone(), ctx.task_executor().clone(), blob_store.clone())
         .map(|validator| {
             OpTransactionValidator::new(validator)
@@ -286,6 +288,9 @@ where
                 },
             );
 
+            // apply changes of the reth.toml txpool section while the node is running
+            ctx.spawn_pool_config_reload(pool.clone());
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -124,6 +124,53 @@ where
 
         let outcome = self.inner.validate_one(origin, transaction);
 
+        self.ensure_l1_data_gas_fee(outcome)
+    }
+
+    /// Validates all given transactions.
+    ///
+    /// Returns all outcomes for the given transactions in the same order.
+    ///
+    /// This behaves the same as [`EthTransactionValidator::validate_all`], but in addition,
+    /// ensures that the accounts have enough balance to cover the L1 gas cost.
+    pub fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        // blob transactions are rejected right away, the others are validated as a batch
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut supported = Vec::new();
+        for (origin, transaction) in transactions {
+            if transaction.is_eip4844() {
+                outcomes.push(Some(TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidTransactionError::TxTypeNotSupported.into(),
+                )));
+            } else {
+                outcomes.push(None);
+                supported.push((origin, transaction));
+            }
+        }
+
+        let mut validated = self.inner.validate_all(supported).into_iter();
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| {
+                    self.ensure_l1_data_gas_fee(
+                        validated.next().expect("one outcome per transaction"),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Ensures that the account of a valid transaction has enough balance to also cover the L1
+    /// gas cost.
+    fn ensure_l1_data_gas_fee(
+        &self,
+        outcome: TransactionValidationOutcome<Tx>,
+    ) -> TransactionValidationOutcome<Tx> {
         if !self.requires_l1_data_gas_fee() {
             // no need to check L1 gas fee
             return outcome
@@ -176,18 +223,6 @@ where
 
         outcome
     }
-
-    /// Validates all given transactions.
-    ///
-    /// Returns all outcomes for the given transactions in the same order.
-    ///
-    /// See also [`Self::validate_one`]
-    pub fn validate_all(
-        &self,
-        transactions: Vec<(TransactionOrigin, Tx)>,
-    ) -> Vec<TransactionValidationOutcome<Tx>> {
-        transactions.into_iter().map(|(origin, tx)| self.validate_one(origin, tx)).collect()
-    }
 }
 
 impl<Client, Tx> TransactionValidator for OpTransactionValidator<Client, Tx>
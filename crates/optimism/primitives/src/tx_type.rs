@@ -26,8 +26,11 @@ use reth_primitives::transaction::{
 };
 
 /// Wrapper type for `AlloyOpTxType` to implement `TxType` trait.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Display, Ord, Hash, From, Into)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Display, Ord, Hash, From, Into, InMemorySize,
+)]
 #[into(u8)]
+#[in_mem_size(size_of)]
 pub struct OpTxType(AlloyOpTxType);
 
 impl TxType for OpTxType {
@@ -57,14 +60,6 @@ impl TxType for OpTxType {
     }
 }
 
-impl InMemorySize for OpTxType {
-    /// Calculates a heuristic for the in-memory size of the [`OpTxType`].
-    #[inline]
-    fn size(&self) -> usize {
-        core::mem::size_of::<Self>()
-    }
-}
-
 impl From<OpTxType> for U8 {
     fn from(tx_type: OpTxType) -> Self {
         Self::from(u8::from(tx_type))
@@ -42,8 +42,10 @@ use tokio::{
 use tracing::{debug, trace, warn};
 
 mod metrics;
+mod parallel;
 mod stack;
 
+pub use parallel::ParallelPayloadBuilder;
 pub use stack::PayloadBuilderStack;
 
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
@@ -176,6 +178,7 @@ where
             pool: self.pool.clone(),
             executor: self.executor.clone(),
             deadline,
+            deadline_extension: self.config.deadline,
             // ticks immediately
             interval: tokio::time::interval(self.config.interval),
             best_payload: PayloadState::Missing,
@@ -322,6 +325,8 @@ where
     executor: Tasks,
     /// The deadline when this job should resolve.
     deadline: Pin<Box<Sleep>>,
+    /// How long the job keeps building after its deadline is extended.
+    deadline_extension: Duration,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
     /// The best payload so far and its state.
@@ -421,6 +426,7 @@ where
                     BuildOutcome::Better { payload, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
                         debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                        this.metrics.set_best_payload_value(f64::from(payload.fees()));
                         this.best_payload = PayloadState::Best(payload);
                     }
                     BuildOutcome::Freeze(payload) => {
@@ -548,6 +554,13 @@ where
 
         (fut, KeepPayloadJobAlive::No)
     }
+
+    fn extend_deadline(&mut self) {
+        let until =
+            (tokio::time::Instant::now() + self.deadline_extension).max(self.deadline.deadline());
+        trace!(target: "payload_builder", id=%self.config.payload_id(), "extending payload building deadline");
+        self.deadline.as_mut().reset(until);
+    }
 }
 
 /// Represents the current state of a payload being built.
@@ -1,6 +1,9 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -12,6 +15,8 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts.
     pub(crate) failed_payload_builds: Counter,
+    /// The value, in wei, of the best payload built by the latest job.
+    pub(crate) best_payload_value: Gauge,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +31,8 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn set_best_payload_value(&self, value: f64) {
+        self.best_payload_value.set(value);
+    }
 }
@@ -0,0 +1,145 @@
+use crate::{
+    BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder, PayloadBuilderError,
+    PayloadConfig,
+};
+
+use alloy_primitives::U256;
+use reth_payload_primitives::BuiltPayload;
+use reth_revm::cached::CachedReads;
+use std::{panic, thread};
+
+/// A [`PayloadBuilder`] that builds a candidate payload with each of its builders in parallel and
+/// keeps the most valuable one.
+///
+/// The builders can for example order the transactions differently or leave out blob
+/// transactions. All candidates start from the cached reads of the payload job, and the reads of
+/// all candidates are cached for the next attempt.
+#[derive(Debug, Clone)]
+pub struct ParallelPayloadBuilder<B> {
+    candidates: Vec<B>,
+}
+
+impl<B> ParallelPayloadBuilder<B> {
+    /// Creates a new builder that builds a candidate payload with each of the given builders.
+    ///
+    /// # Panics
+    ///
+    /// If no builder is given.
+    pub fn new(candidates: Vec<B>) -> Self {
+        assert!(!candidates.is_empty(), "at least one payload builder is required");
+        Self { candidates }
+    }
+
+    /// Returns the builder that builds the first candidate.
+    fn first(&self) -> &B {
+        &self.candidates[0]
+    }
+}
+
+impl<B, Pool, Client> PayloadBuilder<Pool, Client> for ParallelPayloadBuilder<B>
+where
+    B: PayloadBuilder<Pool, Client>,
+    Client: Clone + Send,
+    Pool: Clone + Send,
+    B::Attributes: Clone + Send,
+    B::BuiltPayload: Clone + Send,
+{
+    type Attributes = B::Attributes;
+    type BuiltPayload = B::BuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<Pool, Client, Self::Attributes, Self::BuiltPayload>,
+    ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
+        let (first, rest) = self.candidates.split_first().expect("at least one payload builder");
+        if rest.is_empty() {
+            return first.try_build(args)
+        }
+
+        let outcomes = thread::scope(|scope| {
+            let handles = rest
+                .iter()
+                .map(|candidate| {
+                    let args = BuildArguments {
+                        client: args.client.clone(),
+                        pool: args.pool.clone(),
+                        cached_reads: args.cached_reads.clone(),
+                        config: args.config.clone(),
+                        cancel: args.cancel.clone(),
+                        best_payload: args.best_payload.clone(),
+                    };
+                    scope.spawn(move || candidate.try_build(args))
+                })
+                .collect::<Vec<_>>();
+
+            let mut outcomes = vec![first.try_build(args)];
+            outcomes.extend(
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|err| panic::resume_unwind(err))),
+            );
+            outcomes
+        });
+
+        best_outcome(outcomes)
+    }
+
+    fn on_missing_payload(
+        &self,
+        args: BuildArguments<Pool, Client, Self::Attributes, Self::BuiltPayload>,
+    ) -> MissingPayloadBehaviour<Self::BuiltPayload> {
+        self.first().on_missing_payload(args)
+    }
+
+    fn build_empty_payload(
+        &self,
+        client: &Client,
+        config: PayloadConfig<Self::Attributes>,
+    ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+        self.first().build_empty_payload(client, config)
+    }
+}
+
+/// Returns the most valuable of the outcomes of the candidates, with the cached reads of all
+/// candidates.
+///
+/// An error is only returned if all candidates failed.
+fn best_outcome<Payload: BuiltPayload>(
+    outcomes: Vec<Result<BuildOutcome<Payload>, PayloadBuilderError>>,
+) -> Result<BuildOutcome<Payload>, PayloadBuilderError> {
+    let mut cached_reads = CachedReads::default();
+    let mut best_payload: Option<Payload> = None;
+    let mut aborted_fees: Option<U256> = None;
+    let mut cancelled = false;
+    let mut error = None;
+
+    for outcome in outcomes {
+        match outcome {
+            Ok(BuildOutcome::Better { payload, cached_reads: reads }) => {
+                cached_reads.extend(reads);
+                if best_payload.as_ref().is_none_or(|best| payload.fees() > best.fees()) {
+                    best_payload = Some(payload);
+                }
+            }
+            Ok(BuildOutcome::Aborted { fees, cached_reads: reads }) => {
+                cached_reads.extend(reads);
+                aborted_fees = aborted_fees.max(Some(fees));
+            }
+            Ok(BuildOutcome::Freeze(payload)) => return Ok(BuildOutcome::Freeze(payload)),
+            Ok(BuildOutcome::Cancelled) => cancelled = true,
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    if let Some(payload) = best_payload {
+        Ok(BuildOutcome::Better { payload, cached_reads })
+    } else if let Some(fees) = aborted_fees {
+        Ok(BuildOutcome::Aborted { fees, cached_reads })
+    } else if cancelled {
+        Ok(BuildOutcome::Cancelled)
+    } else {
+        error.map_or(Ok(BuildOutcome::Cancelled), Err)
+    }
+}
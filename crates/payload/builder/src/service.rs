@@ -270,11 +270,6 @@ where
         PayloadBuilderHandle::new(self.service_tx.clone())
     }
 
-    /// Returns true if the given payload is currently being built.
-    fn contains_payload(&self, id: PayloadId) -> bool {
-        self.payload_jobs.iter().any(|(_, job_id)| *job_id == id)
-    }
-
     /// Returns the best payload for the given identifier that has been built so far.
     fn best_payload(&self, id: PayloadId) -> Option<Result<T::BuiltPayload, PayloadBuilderError>> {
         let res = self
@@ -405,8 +400,11 @@ where
                         let id = attr.payload_id();
                         let mut res = Ok(id);
 
-                        if this.contains_payload(id) {
-                            debug!(%id, parent = %attr.parent(), "Payload job already in progress, ignoring.");
+                        if let Some((job, _)) =
+                            this.payload_jobs.iter_mut().find(|(_, job_id)| *job_id == id)
+                        {
+                            debug!(%id, parent = %attr.parent(), "Payload job already in progress, extending deadline.");
+                            job.extend_deadline();
                         } else {
                             // no job for this payload yet, create one
                             let parent = attr.parent();
@@ -69,6 +69,14 @@ pub trait PayloadJob: Future<Output = Result<(), PayloadBuilderError>> + Send +
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
         self.resolve_kind(PayloadKind::Earliest)
     }
+
+    /// Called when a payload with the same attributes is requested again, e.g. because the CL
+    /// repeated the fork choice update to give the job more time before it calls
+    /// `engine_getPayload`.
+    ///
+    /// Jobs with a deadline can extend it here and keep improving their best payload. By default
+    /// this does nothing.
+    fn extend_deadline(&mut self) {}
 }
 
 /// Whether the payload job should be kept alive or terminated after the payload was requested by
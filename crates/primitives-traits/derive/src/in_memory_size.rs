@@ -0,0 +1,196 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields, GenericArgument,
+    PathArguments, Result, Type,
+};
+
+/// How the size of a field is accounted for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SizeKind {
+    /// Calls `size()` on the field.
+    Size,
+    /// Only counts `size_of::<T>()`.
+    SizeOf,
+    /// Counts the size of every element and the allocated capacity of a vector.
+    Heap,
+    /// Only counts the allocated capacity of a vector.
+    Capacity,
+    /// Ignores the field.
+    Skip,
+}
+
+/// Parses the `#[in_mem_size(..)]` attribute, if any.
+fn parse_kind(attrs: &[Attribute]) -> Result<SizeKind> {
+    let mut kind = SizeKind::Size;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("in_mem_size")) {
+        attr.parse_nested_meta(|meta| {
+            kind = if meta.path.is_ident("size_of") {
+                SizeKind::SizeOf
+            } else if meta.path.is_ident("heap") {
+                SizeKind::Heap
+            } else if meta.path.is_ident("capacity") {
+                SizeKind::Capacity
+            } else if meta.path.is_ident("skip") {
+                SizeKind::Skip
+            } else {
+                return Err(meta.error("expected one of `size_of`, `heap`, `capacity` or `skip`"))
+            };
+            Ok(())
+        })?;
+    }
+    Ok(kind)
+}
+
+/// Returns all `#[cfg(..)]` attributes, so they can be forwarded to the generated code.
+fn cfg_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("cfg"))
+}
+
+/// Returns the element type of a vector type, e.g. `T` for `Vec<T>`.
+fn element_type(ty: &Type) -> Result<&Type> {
+    if let Type::Path(path) = ty {
+        if let Some(PathArguments::AngleBracketed(args)) =
+            path.path.segments.last().map(|segment| &segment.arguments)
+        {
+            if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                return Ok(ty)
+            }
+        }
+    }
+    Err(Error::new_spanned(ty, "expected a vector type like `Vec<T>`"))
+}
+
+/// Generates the statement adding the size of the given field, accessed through `access`.
+fn field_size(field: &Field, access: &TokenStream) -> Result<TokenStream> {
+    let ty = &field.ty;
+    let size = match parse_kind(&field.attrs)? {
+        SizeKind::Size => quote! { #access.size() },
+        SizeKind::SizeOf => quote! { ::core::mem::size_of::<#ty>() },
+        SizeKind::Heap => {
+            let element = element_type(ty)?;
+            quote! {
+                #access.iter().map(|element| element.size()).sum::<usize>() +
+                    #access.capacity() * ::core::mem::size_of::<#element>()
+            }
+        }
+        SizeKind::Capacity => {
+            let element = element_type(ty)?;
+            quote! { #access.capacity() * ::core::mem::size_of::<#element>() }
+        }
+        SizeKind::Skip => return Ok(TokenStream::new()),
+    };
+    let cfgs = cfg_attrs(&field.attrs);
+    Ok(quote! {
+        #(#cfgs)*
+        {
+            size += #size;
+        }
+    })
+}
+
+/// Generates the body of the `size` function.
+fn size_body(input: &DeriveInput) -> Result<TokenStream> {
+    if parse_kind(&input.attrs)? == SizeKind::SizeOf {
+        return Ok(quote! { ::core::mem::size_of::<Self>() })
+    }
+
+    match &input.data {
+        Data::Struct(data) => {
+            let fields = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let access = match &field.ident {
+                        Some(ident) => quote! { self.#ident },
+                        None => {
+                            let index = syn::Index::from(index);
+                            quote! { self.#index }
+                        }
+                    };
+                    field_size(field, &access)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! {
+                #[allow(unused_mut)]
+                let mut size = 0usize;
+                #(#fields)*
+                size
+            })
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let cfgs = cfg_attrs(&variant.attrs);
+                    let bindings = (0..variant.fields.len())
+                        .map(|index| format_ident!("__field_{index}"))
+                        .collect::<Vec<_>>();
+                    let fields = variant
+                        .fields
+                        .iter()
+                        .zip(&bindings)
+                        .map(|(field, binding)| field_size(field, &quote! { #binding }))
+                        .collect::<Result<Vec<_>>>()?;
+                    let pattern = match &variant.fields {
+                        Fields::Named(named) => {
+                            let names = named.named.iter().map(|field| &field.ident);
+                            let field_cfgs = named
+                                .named
+                                .iter()
+                                .map(|field| cfg_attrs(&field.attrs).collect::<Vec<_>>());
+                            quote! { Self::#ident { #(#(#field_cfgs)* #names: #bindings),* } }
+                        }
+                        Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+                        Fields::Unit => quote! { Self::#ident },
+                    };
+                    Ok(quote! {
+                        #(#cfgs)*
+                        #[allow(unused_mut, unused_variables)]
+                        #pattern => {
+                            let mut size = 0usize;
+                            #(#fields)*
+                            size
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! {
+                match self {
+                    #(#arms)*
+                }
+            })
+        }
+        Data::Union(_) => Err(Error::new_spanned(
+            input,
+            "`InMemorySize` can only be derived for structs and enums, or with \
+             `#[in_mem_size(size_of)]`",
+        )),
+    }
+}
+
+pub(crate) fn derive(mut input: DeriveInput) -> Result<TokenStream> {
+    let body = size_body(&input)?;
+
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::reth_primitives_traits::InMemorySize));
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::reth_primitives_traits::InMemorySize for #ident #ty_generics
+            #where_clause
+        {
+            #[inline]
+            fn size(&self) -> usize {
+                #[allow(unused_imports)]
+                use ::reth_primitives_traits::InMemorySize as _;
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,47 @@
+//! Derive macros for the reth primitive traits.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![allow(unreachable_pub, missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod in_memory_size;
+
+/// Derives the `InMemorySize` trait by summing up the sizes of all fields.
+///
+/// By default every field contributes its own `size()`. The heuristic of a field can be changed
+/// with the `#[in_mem_size(..)]` attribute:
+/// - `size_of`: the field has no heap allocations, only `size_of::<T>()` is counted.
+/// - `heap`: the field is a vector, the size of every element and the allocated capacity are
+///   counted.
+/// - `capacity`: the field is a vector, only the allocated capacity is counted.
+/// - `skip`: the field is ignored.
+///
+/// Enums sum up the fields of the current variant. Placing `#[in_mem_size(size_of)]` on the type
+/// itself counts `size_of::<Self>()` instead, which is useful for fieldless enums.
+///
+/// Every generic type parameter is required to implement `InMemorySize`.
+///
+/// Example:
+/// ```ignore
+/// #[derive(InMemorySize)]
+/// struct Receipt {
+///     tx_type: TxType,
+///     #[in_mem_size(size_of)]
+///     success: bool,
+///     #[in_mem_size(capacity)]
+///     logs: Vec<Log>,
+/// }
+/// ```
+#[proc_macro_derive(InMemorySize, attributes(in_mem_size))]
+pub fn derive_in_memory_size(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    in_memory_size::derive(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
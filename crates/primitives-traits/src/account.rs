@@ -1,7 +1,6 @@
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_genesis::GenesisAccount;
 use alloy_primitives::{keccak256, Bytes, B256, U256};
-use byteorder::{BigEndian, ReadBytesExt};
 use bytes::Buf;
 use derive_more::Deref;
 use reth_codecs::{add_arbitrary_tests, Compact};
@@ -127,9 +126,9 @@ impl Compact for Bytecode {
     // A panic will be triggered if a bytecode variant of 1 or greater than 2 is passed from the
     // database.
     fn from_compact(mut buf: &[u8], _: usize) -> (Self, &[u8]) {
-        let len = buf.read_u32::<BigEndian>().expect("could not read bytecode length");
+        let len = buf.get_u32();
         let bytes = Bytes::from(buf.copy_to_bytes(len as usize));
-        let variant = buf.read_u8().expect("could not read bytecode variant");
+        let variant = buf.get_u8();
         let decoded = match variant {
             LEGACY_RAW_BYTECODE_ID => Self(RevmBytecode::new_raw(bytes)),
             REMOVED_BYTECODE_ID => {
@@ -138,7 +137,7 @@ impl Compact for Bytecode {
             LEGACY_ANALYZED_BYTECODE_ID => Self(unsafe {
                 RevmBytecode::new_analyzed(
                     bytes,
-                    buf.read_u64::<BigEndian>().unwrap() as usize,
+                    buf.get_u64() as usize,
                     JumpTable::from_slice(buf),
                 )
             }),
@@ -1,8 +1,19 @@
 //! Block body abstraction.
 
-use crate::InMemorySize;
-use alloc::fmt;
+use crate::{BlockHeader, FullSignedTx, InMemorySize, SignedTransaction};
+use alloc::{fmt, vec::Vec};
 use alloy_consensus::Transaction;
+use alloy_eips::{
+    eip2718::Encodable2718, eip4844::DATA_GAS_PER_BLOB, eip4895::Withdrawals, eip7685::Requests,
+};
+use alloy_primitives::{Address, B256};
+use alloy_trie::root::ordered_trie_root_with_encoder;
+use revm_primitives::{eip7702::PER_EMPTY_ACCOUNT_COST, SpecId};
+
+/// Helper trait that unifies all behaviour required by transaction to support full node operations.
+pub trait FullBlockBody: BlockBody<Transaction: FullSignedTx> {}
+
+impl<T> FullBlockBody for T where T: BlockBody<Transaction: FullSignedTx> {}
 
 /// Abstraction for block's body.
 #[auto_impl::auto_impl(&, Arc)]
@@ -22,9 +33,114 @@ pub trait BlockBody:
     + InMemorySize
 {
     /// Ordered list of signed transactions as committed in block.
-    // todo: requires trait for signed transaction
-    type Transaction: Transaction;
+    type Transaction: SignedTransaction;
+
+    /// Header type of the block's ommers.
+    type OmmerHeader: BlockHeader;
 
     /// Returns reference to transactions in block.
     fn transactions(&self) -> &[Self::Transaction];
+
+    /// Returns the number of transactions in block.
+    fn transaction_count(&self) -> usize {
+        self.transactions().len()
+    }
+
+    /// Calculates the transactions root of the block, from the EIP-2718 encoded transactions.
+    fn calculate_tx_root(&self) -> B256 {
+        ordered_trie_root_with_encoder(self.transactions(), |tx, buf| tx.encode_2718(buf))
+    }
+
+    /// Recovers the signers of all transactions in block.
+    ///
+    /// Returns `None` if some transaction's signature is invalid, see also
+    /// [`SignedTransaction::recover_signer`].
+    fn recover_signers(&self) -> Option<Vec<Address>> {
+        self.transactions().iter().map(SignedTransaction::recover_signer).collect()
+    }
+
+    /// Recovers the signers of all transactions in block _without ensuring that the signatures
+    /// have a low `s` value_.
+    ///
+    /// Returns `None` if some transaction's signature is invalid, see also
+    /// [`SignedTransaction::recover_signer_unchecked`].
+    fn recover_signers_unchecked(&self) -> Option<Vec<Address>> {
+        self.transactions().iter().map(SignedTransaction::recover_signer_unchecked).collect()
+    }
+
+    /// Returns reference to withdrawals in block, if any.
+    fn withdrawals(&self) -> Option<&Withdrawals>;
+
+    /// Returns reference to ommers in block, or `None` if the body type has no ommers.
+    fn ommers(&self) -> Option<&[Self::OmmerHeader]>;
+
+    /// Returns reference to execution layer requests in block, if any.
+    ///
+    /// Bodies that don't carry requests return `None`.
+    fn requests(&self) -> Option<&Requests> {
+        None
+    }
+
+    /// Returns an iterator over all blob versioned hashes of the transactions in block.
+    fn blob_versioned_hashes_iter<'a>(&'a self) -> impl Iterator<Item = &'a B256> + 'a
+    where
+        Self::Transaction: 'a,
+    {
+        self.transactions().iter().filter_map(|tx| tx.blob_versioned_hashes()).flatten()
+    }
+
+    /// Returns all blob versioned hashes of the transactions in block.
+    fn blob_versioned_hashes(&self) -> Vec<&B256> {
+        self.blob_versioned_hashes_iter().collect()
+    }
+
+    /// Calculates the total gas used by blob transactions in block.
+    fn blob_gas_used(&self) -> u64 {
+        self.blob_versioned_hashes_iter().count() as u64 * DATA_GAS_PER_BLOB
+    }
+
+    /// Calculates the sum of the intrinsic gas of all transactions in block, under the rules of
+    /// the given spec.
+    ///
+    /// This is a lower bound for the gas used by the block.
+    fn intrinsic_gas_used(&self, spec_id: SpecId) -> u64 {
+        self.transactions().iter().map(|tx| intrinsic_gas(tx, spec_id)).sum()
+    }
+}
+
+/// Calculates the gas a transaction is charged before execution, see also
+/// `revm_interpreter::gas::validate_initial_tx_gas`.
+fn intrinsic_gas<T: Transaction>(tx: &T, spec_id: SpecId) -> u64 {
+    let input = tx.input();
+    let non_zero_data_len = input.iter().filter(|byte| **byte != 0).count() as u64;
+    let zero_data_len = input.len() as u64 - non_zero_data_len;
+
+    let mut gas = zero_data_len * 4;
+    // EIP-2028: Transaction data gas cost reduction
+    gas += non_zero_data_len * if spec_id.is_enabled_in(SpecId::ISTANBUL) { 16 } else { 68 };
+
+    // EIP-2930: Optional access lists
+    if spec_id.is_enabled_in(SpecId::BERLIN) {
+        if let Some(access_list) = tx.access_list() {
+            let storage_keys: usize = access_list.iter().map(|item| item.storage_keys.len()).sum();
+            gas += access_list.len() as u64 * 2400 + storage_keys as u64 * 1900;
+        }
+    }
+
+    let is_create = tx.kind().is_create();
+    // EIP-2: Homestead Hard-fork Changes
+    gas += if is_create && spec_id.is_enabled_in(SpecId::HOMESTEAD) { 53000 } else { 21000 };
+
+    // EIP-3860: Limit and meter initcode
+    if is_create && spec_id.is_enabled_in(SpecId::SHANGHAI) {
+        gas += (input.len() as u64).div_ceil(32) * 2;
+    }
+
+    // EIP-7702: Set EOA account code
+    if spec_id.is_enabled_in(SpecId::PRAGUE) {
+        let authorizations = tx.authorization_list().map_or(0, |list| list.len() as u64);
+        gas += authorizations * PER_EMPTY_ACCOUNT_COST;
+    }
+
+    gas
 }
@@ -21,6 +21,8 @@ pub trait BlockHeader:
     + fmt::Debug
     + PartialEq
     + Eq
+    + serde::Serialize
+    + for<'de> serde::Deserialize<'de>
     + alloy_rlp::Encodable
     + alloy_rlp::Decodable
     + alloy_consensus::BlockHeader
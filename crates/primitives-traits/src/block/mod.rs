@@ -3,22 +3,21 @@
 pub mod body;
 pub mod header;
 
-use alloc::fmt;
+use alloc::{fmt, vec::Vec};
 
-use reth_codecs::Compact;
+use alloy_primitives::{Address, Sealable, B256};
 
-use crate::{BlockHeader, FullBlockHeader, InMemorySize};
+use crate::{BlockBody, BlockHeader, FullBlockBody, FullBlockHeader, InMemorySize};
 
 /// Helper trait that unifies all behaviour required by block to support full node operations.
-pub trait FullBlock: Block<Header: Compact> + Compact {}
+pub trait FullBlock: Block<Header: FullBlockHeader, Body: FullBlockBody> {}
 
-impl<T> FullBlock for T where T: Block<Header: FullBlockHeader> + Compact {}
+impl<T> FullBlock for T where T: Block<Header: FullBlockHeader, Body: FullBlockBody> {}
 
 /// Abstraction of block data type.
 // todo: make sealable super-trait, depends on <https://github.com/paradigmxyz/reth/issues/11449>
 // todo: make with senders extension trait, so block can be impl by block type already containing
 // senders
-#[auto_impl::auto_impl(&, Arc)]
 pub trait Block:
     Send
     + Sync
@@ -36,11 +35,30 @@ pub trait Block:
     type Header: BlockHeader + 'static;
 
     /// The block's body contains the transactions in the block.
-    type Body: Send + Sync + Unpin + 'static;
+    type Body: BlockBody + 'static;
+
+    /// Create new block instance.
+    fn new(header: Self::Header, body: Self::Body) -> Self;
 
     /// Returns reference to block header.
     fn header(&self) -> &Self::Header;
 
     /// Returns reference to block body.
     fn body(&self) -> &Self::Body;
+
+    /// Splits the block into its header and body.
+    fn split(self) -> (Self::Header, Self::Body);
+
+    /// Calculates the hash of the block header.
+    fn hash_slow(&self) -> B256 {
+        self.header().hash_slow()
+    }
+
+    /// Recovers the signers of all transactions in the block.
+    ///
+    /// Returns `None` if some transaction's signature is invalid, see also
+    /// [`BlockBody::recover_signers`].
+    fn recover_signers(&self) -> Option<Vec<Address>> {
+        self.body().recover_signers()
+    }
 }
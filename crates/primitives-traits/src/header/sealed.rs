@@ -1,5 +1,3 @@
-use core::mem;
-
 use alloy_consensus::{Header, Sealed};
 use alloy_eips::BlockNumHash;
 use alloy_primitives::{keccak256, BlockHash, Sealable, B256};
@@ -22,10 +20,11 @@ pub struct BlockWithParent {
 
 /// A [`Header`] that is sealed at a precalculated hash, use [`SealedHeader::unseal()`] if you want
 /// to modify header.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, Deref, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, Deref, Serialize, Deserialize, InMemorySize)]
 #[add_arbitrary_tests(rlp)]
 pub struct SealedHeader<H = Header> {
     /// Locked Header hash.
+    #[in_mem_size(size_of)]
     hash: BlockHash,
     /// Locked Header fields.
     #[as_ref]
@@ -78,14 +77,6 @@ impl<H: alloy_consensus::BlockHeader> SealedHeader<H> {
     }
 }
 
-impl InMemorySize for SealedHeader {
-    /// Calculates a heuristic for the in-memory size of the [`SealedHeader`].
-    #[inline]
-    fn size(&self) -> usize {
-        self.header.size() + mem::size_of::<BlockHash>()
-    }
-}
-
 impl<H: Sealable + Default> Default for SealedHeader<H> {
     fn default() -> Self {
         Self::seal(H::default())
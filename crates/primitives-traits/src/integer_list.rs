@@ -1,5 +1,4 @@
 use alloc::vec::Vec;
-use bytes::BufMut;
 use core::fmt;
 use derive_more::Deref;
 use roaring::RoaringTreemap;
@@ -72,21 +71,186 @@ impl IntegerList {
 
     /// Serializes a [`IntegerList`] into a sequence of bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
+        #[cfg(feature = "std")]
         let mut vec = Vec::with_capacity(self.0.serialized_size());
-        self.0.serialize_into(&mut vec).expect("not able to encode IntegerList");
+        #[cfg(not(feature = "std"))]
+        let mut vec = Vec::new();
+        self.to_mut_bytes(&mut vec);
         vec
     }
 
     /// Serializes a [`IntegerList`] into a sequence of bytes.
     pub fn to_mut_bytes<B: bytes::BufMut>(&self, buf: &mut B) {
-        self.0.serialize_into(buf.writer()).unwrap();
+        #[cfg(feature = "std")]
+        {
+            use bytes::BufMut;
+            self.0.serialize_into(buf.writer()).unwrap();
+        }
+        #[cfg(not(feature = "std"))]
+        portable::serialize_into(&self.0, buf);
     }
 
     /// Deserializes a sequence of bytes into a proper [`IntegerList`].
     pub fn from_bytes(data: &[u8]) -> Result<Self, IntegerListError> {
-        RoaringTreemap::deserialize_from(data)
-            .map(Self)
-            .map_err(|_| IntegerListError::FailedToDeserialize)
+        #[cfg(feature = "std")]
+        let list = RoaringTreemap::deserialize_from(data).ok();
+        #[cfg(not(feature = "std"))]
+        let list = portable::deserialize_from(data);
+        list.map(Self).ok_or(IntegerListError::FailedToDeserialize)
+    }
+}
+
+/// Implementation of the portable roaring serialization format for `no_std` environments, where
+/// the `std::io` based implementation of `roaring` is not available.
+///
+/// See also <https://github.com/RoaringBitmap/RoaringFormatSpec>
+#[cfg_attr(feature = "std", allow(dead_code))]
+mod portable {
+    use alloc::vec::Vec;
+    use bytes::{Buf, BufMut};
+    use roaring::{RoaringBitmap, RoaringTreemap};
+
+    const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+    const SERIAL_COOKIE: u16 = 12347;
+    const NO_OFFSET_THRESHOLD: usize = 4;
+    /// Maximum cardinality of a container that is stored as a sorted array.
+    const ARRAY_LIMIT: usize = 4096;
+    /// Number of `u64` words of a container that is stored as a bitmap.
+    const BITMAP_WORDS: usize = 1024;
+
+    /// Writes the treemap in the same layout as `RoaringTreemap::serialize_into`.
+    pub(super) fn serialize_into<B: BufMut>(treemap: &RoaringTreemap, buf: &mut B) {
+        buf.put_u64_le(treemap.bitmaps().count() as u64);
+        for (key, bitmap) in treemap.bitmaps() {
+            buf.put_u32_le(key);
+            serialize_bitmap(bitmap, buf);
+        }
+    }
+
+    fn serialize_bitmap<B: BufMut>(bitmap: &RoaringBitmap, buf: &mut B) {
+        // group the values into containers of the same 16 high bits
+        let mut containers: Vec<(u16, Vec<u16>)> = Vec::new();
+        for value in bitmap {
+            let (key, low) = ((value >> 16) as u16, value as u16);
+            match containers.last_mut() {
+                Some((last, values)) if *last == key => values.push(low),
+                _ => containers.push((key, alloc::vec![low])),
+            }
+        }
+
+        buf.put_u32_le(SERIAL_COOKIE_NO_RUNCONTAINER);
+        buf.put_u32_le(containers.len() as u32);
+        for (key, values) in &containers {
+            buf.put_u16_le(*key);
+            buf.put_u16_le((values.len() - 1) as u16);
+        }
+
+        let mut offset = 8 + 8 * containers.len() as u32;
+        for (_, values) in &containers {
+            buf.put_u32_le(offset);
+            offset += if values.len() <= ARRAY_LIMIT {
+                values.len() as u32 * 2
+            } else {
+                BITMAP_WORDS as u32 * 8
+            };
+        }
+
+        for (_, values) in &containers {
+            if values.len() <= ARRAY_LIMIT {
+                for value in values {
+                    buf.put_u16_le(*value);
+                }
+            } else {
+                let mut words = [0u64; BITMAP_WORDS];
+                for value in values {
+                    words[*value as usize / 64] |= 1 << (value % 64);
+                }
+                for word in words {
+                    buf.put_u64_le(word);
+                }
+            }
+        }
+    }
+
+    /// Reads a treemap written by `RoaringTreemap::serialize_into`.
+    pub(super) fn deserialize_from(mut data: &[u8]) -> Option<RoaringTreemap> {
+        let mut treemap = RoaringTreemap::new();
+        let bitmaps = read_u64(&mut data)?;
+        for _ in 0..bitmaps {
+            let high = u64::from(read_u32(&mut data)?) << 32;
+            deserialize_bitmap(&mut data, |low| treemap.push(high | u64::from(low)).then_some(()))?;
+        }
+        Some(treemap)
+    }
+
+    /// Reads a bitmap and passes its values in ascending order to `push`.
+    fn deserialize_bitmap(data: &mut &[u8], mut push: impl FnMut(u32) -> Option<()>) -> Option<()> {
+        let cookie = read_u32(data)?;
+        let (size, has_offsets, run_bitmap) = if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            (read_u32(data)? as usize, true, None)
+        } else if cookie as u16 == SERIAL_COOKIE {
+            let size = (cookie >> 16) as usize + 1;
+            let run_bitmap = take(data, size.div_ceil(8))?;
+            (size, size >= NO_OFFSET_THRESHOLD, Some(run_bitmap))
+        } else {
+            return None
+        };
+        if size > u16::MAX as usize + 1 {
+            return None
+        }
+
+        let mut descriptions = take(data, size * 4)?;
+        if has_offsets {
+            take(data, size * 4)?;
+        }
+
+        for i in 0..size {
+            let key = u32::from(read_u16(&mut descriptions)?) << 16;
+            let cardinality = read_u16(&mut descriptions)? as usize + 1;
+            let is_run = run_bitmap.is_some_and(|runs| runs[i / 8] & (1 << (i % 8)) != 0);
+
+            if is_run {
+                for _ in 0..read_u16(data)? {
+                    let start = read_u16(data)?;
+                    let end = start.checked_add(read_u16(data)?)?;
+                    (start..=end).try_for_each(|low| push(key | u32::from(low)))?;
+                }
+            } else if cardinality <= ARRAY_LIMIT {
+                for _ in 0..cardinality {
+                    push(key | u32::from(read_u16(data)?))?;
+                }
+            } else {
+                for word_index in 0..BITMAP_WORDS as u32 {
+                    let mut word = read_u64(data)?;
+                    while word != 0 {
+                        push(key | (word_index * 64) | word.trailing_zeros())?;
+                        word &= word - 1;
+                    }
+                }
+            }
+        }
+        Some(())
+    }
+
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if data.len() < len {
+            return None
+        }
+        let (head, tail) = data.split_at(len);
+        *data = tail;
+        Some(head)
+    }
+
+    fn read_u16(data: &mut &[u8]) -> Option<u16> {
+        (data.remaining() >= 2).then(|| data.get_u16_le())
+    }
+
+    fn read_u32(data: &mut &[u8]) -> Option<u32> {
+        (data.remaining() >= 4).then(|| data.get_u32_le())
+    }
+
+    fn read_u64(data: &mut &[u8]) -> Option<u64> {
+        (data.remaining() >= 8).then(|| data.get_u64_le())
     }
 }
 
@@ -181,6 +345,25 @@ mod tests {
         assert_eq!(IntegerList::from_bytes(&blist).unwrap(), ef_list)
     }
 
+    #[test]
+    fn portable_serialization_matches_roaring() {
+        let lists = [
+            IntegerList::empty(),
+            IntegerList::new([1, 2, 3]).unwrap(),
+            // a dense container is stored as a bitmap
+            IntegerList::new((0..10_000).map(|n| n * 3)).unwrap(),
+            IntegerList::new([7, 1 << 20, (1 << 40) + 5, u64::MAX]).unwrap(),
+        ];
+        for list in lists {
+            let mut portable = Vec::new();
+            portable::serialize_into(&list.0, &mut portable);
+            assert_eq!(portable, list.to_bytes());
+            assert_eq!(portable::deserialize_from(&portable), Some(list.0.clone()));
+        }
+
+        assert_eq!(portable::deserialize_from(&[1, 0, 0]), None);
+    }
+
     #[test]
     fn serde_serialize_deserialize() {
         let original_list = [1, 2, 3];
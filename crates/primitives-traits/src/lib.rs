@@ -1,4 +1,12 @@
 //! Common abstracted types in Reth.
+//!
+//! ## Feature Flags
+//!
+//! - `std`: Enables the standard library. Without it the crate only depends on `alloc`, so the
+//!   types can be reused in `no_std` environments.
+//! - `arbitrary`: Adds `proptest` and `arbitrary` support for primitive types.
+//! - `test-utils`: Export utilities for testing
+//! - `serde-bincode-compat`: Adds `serde_with` based bincode compatible serialization.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -12,6 +20,9 @@
 #[macro_use]
 extern crate alloc;
 
+// Allows the derive macros to refer to this crate by name when used internally.
+extern crate self as reth_primitives_traits;
+
 /// Common constants.
 pub mod constants;
 
@@ -35,7 +46,7 @@ pub use integer_list::{IntegerList, IntegerListError};
 
 pub mod block;
 pub use block::{
-    body::BlockBody,
+    body::{BlockBody, FullBlockBody},
     header::{BlockHeader, FullBlockHeader},
     Block, FullBlock,
 };
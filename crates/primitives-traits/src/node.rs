@@ -1,11 +1,13 @@
 use core::fmt;
 
-use crate::{BlockBody, FullBlock, FullReceipt, FullSignedTx, FullTxType};
+use crate::{BlockBody, FullBlock, FullBlockHeader, FullReceipt, FullSignedTx, FullTxType};
 
 /// Configures all the primitive types of the node.
 pub trait NodePrimitives: Send + Sync + Unpin + Clone + Default + fmt::Debug + 'static {
     /// Block primitive.
     type Block: Send + Sync + Unpin + Clone + Default + fmt::Debug + 'static;
+    /// Block header primitive.
+    type BlockHeader: Send + Sync + Unpin + Clone + Default + fmt::Debug + 'static;
     /// Signed version of the transaction type.
     type SignedTx: Send + Sync + Unpin + Clone + Default + fmt::Debug + 'static;
     /// Transaction envelope type ID.
@@ -16,29 +18,34 @@ pub trait NodePrimitives: Send + Sync + Unpin + Clone + Default + fmt::Debug + '
 
 impl NodePrimitives for () {
     type Block = ();
+    type BlockHeader = ();
     type SignedTx = ();
     type TxType = ();
     type Receipt = ();
 }
 
 /// Helper trait that sets trait bounds on [`NodePrimitives`].
-pub trait FullNodePrimitives: Send + Sync + Unpin + Clone + Default + fmt::Debug + 'static {
-    /// Block primitive.
-    type Block: FullBlock<Body: BlockBody<Transaction = Self::SignedTx>>;
-    /// Signed version of the transaction type.
-    type SignedTx: FullSignedTx;
-    /// Transaction envelope type ID.
-    type TxType: FullTxType;
-    /// A receipt.
-    type Receipt: FullReceipt;
+///
+/// This is implemented for all [`NodePrimitives`] whose types can be persisted and served by the
+/// full node.
+pub trait FullNodePrimitives:
+    NodePrimitives<
+    Block: FullBlock<Header = Self::BlockHeader, Body: BlockBody<Transaction = Self::SignedTx>>,
+    BlockHeader: FullBlockHeader,
+    SignedTx: FullSignedTx,
+    TxType: FullTxType,
+    Receipt: FullReceipt,
+>
+{
 }
 
-impl<T> NodePrimitives for T
-where
-    T: FullNodePrimitives<Block: 'static, SignedTx: 'static, Receipt: 'static, TxType: 'static>,
+impl<T> FullNodePrimitives for T where
+    T: NodePrimitives<
+        Block: FullBlock<Header = T::BlockHeader, Body: BlockBody<Transaction = T::SignedTx>>,
+        BlockHeader: FullBlockHeader,
+        SignedTx: FullSignedTx,
+        TxType: FullTxType,
+        Receipt: FullReceipt,
+    >
 {
-    type Block = T::Block;
-    type SignedTx = T::SignedTx;
-    type TxType = T::TxType;
-    type Receipt = T::Receipt;
 }
@@ -2,7 +2,9 @@
 
 use alloc::vec::Vec;
 use alloy_consensus::TxReceipt;
-use alloy_primitives::B256;
+use alloy_primitives::{Bloom, B256};
+use alloy_rlp::BufMut;
+use alloy_trie::root::ordered_trie_root_with_encoder;
 use core::fmt;
 use reth_codecs::Compact;
 use serde::{Deserialize, Serialize};
@@ -15,6 +17,9 @@ pub trait FullReceipt: Receipt + Compact {}
 impl<T> FullReceipt for T where T: ReceiptExt + Compact {}
 
 /// Abstraction of a receipt.
+///
+/// The [`alloy_rlp::Encodable`] implementation encodes the receipt _without_ the bloom filter, see
+/// [`Receipt::rlp_encode_with_bloom`] for the encoding that includes it.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait Receipt:
     Send
@@ -32,6 +37,19 @@ pub trait Receipt:
 {
     /// Returns transaction type.
     fn tx_type(&self) -> u8;
+
+    /// Returns the length of the [`Receipt::rlp_encode_with_bloom`] encoding.
+    fn rlp_encoded_length_with_bloom(&self, bloom: &Bloom) -> usize;
+
+    /// RLP-encodes the receipt together with the given bloom filter.
+    ///
+    /// Typed receipts are wrapped in an RLP string header, as they are in a list of receipts on
+    /// the network.
+    fn rlp_encode_with_bloom(&self, bloom: &Bloom, out: &mut dyn BufMut);
+
+    /// Encodes the receipt together with the given bloom filter in the EIP-2718 binary format,
+    /// which is the leaf value in the receipts trie.
+    fn eip2718_encode_with_bloom(&self, bloom: &Bloom, out: &mut dyn BufMut);
 }
 
 /// Extension if [`Receipt`] used in block execution.
@@ -40,6 +58,13 @@ pub trait ReceiptExt: Receipt {
     fn receipts_root(receipts: &[&Self]) -> B256;
 }
 
+/// Calculates the receipts root of the given receipts, computing the bloom filter of each receipt.
+pub fn calculate_receipt_root<R: Receipt>(receipts: &[R]) -> B256 {
+    ordered_trie_root_with_encoder(receipts, |receipt, buf| {
+        receipt.eip2718_encode_with_bloom(&receipt.bloom(), buf)
+    })
+}
+
 /// Retrieves gas spent by transactions as a vector of tuples (transaction index, gas used).
 pub fn gas_spent_by_transactions<I, T>(receipts: I) -> Vec<(u64, u64)>
 where
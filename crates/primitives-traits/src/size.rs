@@ -1,3 +1,7 @@
+use alloy_eips::eip4895::Withdrawals;
+
+pub use reth_primitives_traits_derive::InMemorySize;
+
 /// Trait for calculating a heuristic for the in-memory size of a struct.
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait InMemorySize {
@@ -10,3 +14,83 @@ impl InMemorySize for alloy_consensus::Header {
         self.size()
     }
 }
+
+impl InMemorySize for Withdrawals {
+    fn size(&self) -> usize {
+        self.total_size()
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Option<T> {
+    fn size(&self) -> usize {
+        self.as_ref().map_or(core::mem::size_of::<Self>(), InMemorySize::size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use alloy_primitives::{Address, B256};
+    use core::mem::size_of;
+
+    #[derive(InMemorySize)]
+    #[allow(dead_code)]
+    struct Container<T> {
+        inner: T,
+        #[in_mem_size(size_of)]
+        hash: B256,
+        #[in_mem_size(heap)]
+        headers: Vec<alloy_consensus::Header>,
+        #[in_mem_size(capacity)]
+        addresses: Vec<Address>,
+        #[in_mem_size(skip)]
+        skipped: Vec<u8>,
+    }
+
+    #[derive(InMemorySize)]
+    #[allow(clippy::large_enum_variant)]
+    enum Either {
+        Header(alloy_consensus::Header),
+        Hashes {
+            #[in_mem_size(capacity)]
+            hashes: Vec<B256>,
+        },
+        Empty,
+    }
+
+    #[derive(InMemorySize)]
+    #[in_mem_size(size_of)]
+    #[allow(dead_code)]
+    struct Fixed(u64);
+
+    #[test]
+    fn derive_in_memory_size() {
+        let header = alloy_consensus::Header::default();
+        let value = Container {
+            inner: Fixed(1),
+            hash: B256::ZERO,
+            headers: vec![header.clone(); 2],
+            addresses: Vec::with_capacity(4),
+            skipped: vec![0; 100],
+        };
+        assert_eq!(
+            value.size(),
+            size_of::<u64>() +
+                size_of::<B256>() +
+                2 * header.size() +
+                value.headers.capacity() * size_of::<alloy_consensus::Header>() +
+                4 * size_of::<Address>()
+        );
+
+        assert_eq!(Either::Header(header.clone()).size(), header.size());
+        assert_eq!(Either::Hashes { hashes: Vec::with_capacity(3) }.size(), 3 * size_of::<B256>());
+        assert_eq!(Either::Empty.size(), 0);
+
+        assert_eq!(Some(header.clone()).size(), header.size());
+        assert_eq!(
+            None::<alloy_consensus::Header>.size(),
+            size_of::<Option<alloy_consensus::Header>>()
+        );
+    }
+}
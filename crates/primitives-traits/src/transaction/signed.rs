@@ -8,7 +8,9 @@ use alloy_primitives::{keccak256, Address, PrimitiveSignature, TxHash, B256};
 use reth_codecs::Compact;
 use revm_primitives::TxEnv;
 
-use crate::{FullTransaction, InMemorySize, MaybeArbitrary, Transaction};
+use crate::{
+    transaction::TransactionExt, FullTransaction, InMemorySize, MaybeArbitrary, Transaction,
+};
 
 /// Helper trait that unifies all behaviour required by block to support full node operations.
 pub trait FullSignedTx: SignedTransaction<Transaction: FullTransaction> + Compact {}
@@ -49,6 +51,15 @@ pub trait SignedTransaction:
     /// Returns reference to signature.
     fn signature(&self) -> &PrimitiveSignature;
 
+    /// RLP-encodes the transaction _without_ the signature, for creating the hash that is signed.
+    fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut);
+
+    /// Heavy operation that returns the signature hash over the rlp encoded transaction.
+    /// It is only for signature signing or signer recovery.
+    fn signature_hash(&self) -> B256 {
+        self.transaction().signature_hash()
+    }
+
     /// Recover signer from signature and hash.
     ///
     /// Returns `None` if the transaction's signature is invalid following [EIP-2](https://eips.ethereum.org/EIPS/eip-2), see also `reth_primitives::transaction::recover_signer`.
@@ -7,14 +7,14 @@ use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
 use derive_more::{Deref, DerefMut};
 #[cfg(any(test, feature = "arbitrary"))]
 pub use reth_primitives_traits::test_utils::{generate_valid_header, valid_header_strategy};
-use reth_primitives_traits::InMemorySize;
+use reth_primitives_traits::{BlockBody as _, InMemorySize};
 use serde::{Deserialize, Serialize};
 
 /// Ethereum full block.
 ///
 /// Withdrawals can be optionally included at the end of the RLP encoded message.
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(rlp, 25))]
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Deref)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Deref, InMemorySize)]
 pub struct Block {
     /// Block header.
     #[deref]
@@ -90,6 +90,10 @@ impl reth_primitives_traits::Block for Block {
     type Header = Header;
     type Body = BlockBody;
 
+    fn new(header: Self::Header, body: Self::Body) -> Self {
+        Self { header, body }
+    }
+
     fn header(&self) -> &Self::Header {
         &self.header
     }
@@ -97,13 +101,9 @@ impl reth_primitives_traits::Block for Block {
     fn body(&self) -> &Self::Body {
         &self.body
     }
-}
 
-impl InMemorySize for Block {
-    /// Calculates a heuristic for the in-memory size of the [`Block`].
-    #[inline]
-    fn size(&self) -> usize {
-        self.header.size() + self.body.size()
+    fn split(self) -> (Self::Header, Self::Body) {
+        (self.header, self.body)
     }
 }
 
@@ -204,42 +204,53 @@ impl<'a> arbitrary::Arbitrary<'a> for Block {
 
 /// Sealed block with senders recovered from transactions.
 #[derive(Debug, Clone, PartialEq, Eq, Default, Deref, DerefMut)]
-pub struct BlockWithSenders {
+pub struct BlockWithSenders<B = Block> {
     /// Block
     #[deref]
     #[deref_mut]
-    pub block: Block,
+    pub block: B,
     /// List of senders that match the transactions in the block
     pub senders: Vec<Address>,
 }
 
-impl BlockWithSenders {
+impl<B: reth_primitives_traits::Block> BlockWithSenders<B> {
     /// New block with senders. Return none if len of tx and senders does not match
-    pub fn new(block: Block, senders: Vec<Address>) -> Option<Self> {
-        (block.body.transactions.len() == senders.len()).then_some(Self { block, senders })
+    pub fn new(block: B, senders: Vec<Address>) -> Option<Self> {
+        (block.body().transaction_count() == senders.len()).then_some(Self { block, senders })
     }
 
     /// Seal the block with a known hash.
     ///
     /// WARNING: This method does not perform validation whether the hash is correct.
     #[inline]
-    pub fn seal(self, hash: B256) -> SealedBlockWithSenders {
+    pub fn seal(self, hash: B256) -> SealedBlockWithSenders<B> {
         let Self { block, senders } = self;
-        SealedBlockWithSenders { block: block.seal(hash), senders }
+        let (header, body) = block.split();
+        SealedBlockWithSenders {
+            block: SealedBlock::new(SealedHeader::new(header, hash), body),
+            senders,
+        }
     }
 
     /// Calculate the header hash and seal the block with senders so that it can't be changed.
     #[inline]
-    pub fn seal_slow(self) -> SealedBlockWithSenders {
-        SealedBlockWithSenders { block: self.block.seal_slow(), senders: self.senders }
+    pub fn seal_slow(self) -> SealedBlockWithSenders<B> {
+        let Self { block, senders } = self;
+        let (header, body) = block.split();
+        SealedBlockWithSenders {
+            block: SealedBlock::new(SealedHeader::seal(header), body),
+            senders,
+        }
     }
 
     /// Split Structure to its components
     #[inline]
-    pub fn into_components(self) -> (Block, Vec<Address>) {
+    pub fn into_components(self) -> (B, Vec<Address>) {
         (self.block, self.senders)
     }
+}
 
+impl BlockWithSenders {
     /// Returns an iterator over all transactions and their sender.
     #[inline]
     pub fn transactions_with_sender(
@@ -272,7 +283,7 @@ impl BlockWithSenders {
 ///
 /// Withdrawals can be optionally included at the end of the RLP encoded message.
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(rlp, 32))]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deref, DerefMut)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deref, DerefMut, InMemorySize)]
 pub struct SealedBlock<H = Header, B = BlockBody> {
     /// Locked block header.
     #[deref]
@@ -440,13 +451,6 @@ impl SealedBlock {
     }
 }
 
-impl<H: InMemorySize, B: InMemorySize> InMemorySize for SealedBlock<H, B> {
-    #[inline]
-    fn size(&self) -> usize {
-        self.header.size() + self.body.size()
-    }
-}
-
 impl From<SealedBlock> for Block {
     fn from(block: SealedBlock) -> Self {
         block.unseal()
@@ -472,6 +476,10 @@ where
     type Header = H;
     type Body = B;
 
+    fn new(header: Self::Header, body: Self::Body) -> Self {
+        Self { header: SealedHeader::seal(header), body }
+    }
+
     fn header(&self) -> &Self::Header {
         self.header.header()
     }
@@ -479,6 +487,10 @@ where
     fn body(&self) -> &Self::Body {
         &self.body
     }
+
+    fn split(self) -> (Self::Header, Self::Body) {
+        (self.header.unseal(), self.body)
+    }
 }
 
 #[cfg(any(test, feature = "arbitrary"))]
@@ -493,48 +505,59 @@ where
 }
 
 /// Sealed block with senders recovered from transactions.
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Deref, DerefMut)]
-pub struct SealedBlockWithSenders {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deref, DerefMut)]
+pub struct SealedBlockWithSenders<B: reth_primitives_traits::Block = Block> {
     /// Sealed block
     #[deref]
     #[deref_mut]
-    pub block: SealedBlock,
+    #[serde(bound = "SealedBlock<B::Header, B::Body>: Serialize + serde::de::DeserializeOwned")]
+    pub block: SealedBlock<B::Header, B::Body>,
     /// List of senders that match transactions from block.
     pub senders: Vec<Address>,
 }
 
-impl SealedBlockWithSenders {
-    /// New sealed block with sender. Return none if len of tx and senders does not match
-    pub fn new(block: SealedBlock, senders: Vec<Address>) -> Option<Self> {
-        (block.body.transactions.len() == senders.len()).then_some(Self { block, senders })
-    }
-
+impl<B: reth_primitives_traits::Block> SealedBlockWithSenders<B> {
     /// Split Structure to its components
     #[inline]
-    pub fn into_components(self) -> (SealedBlock, Vec<Address>) {
+    pub fn into_components(self) -> (SealedBlock<B::Header, B::Body>, Vec<Address>) {
         (self.block, self.senders)
     }
 
     /// Returns the unsealed [`BlockWithSenders`]
     #[inline]
-    pub fn unseal(self) -> BlockWithSenders {
+    pub fn unseal(self) -> BlockWithSenders<B> {
         let Self { block, senders } = self;
-        BlockWithSenders { block: block.unseal(), senders }
-    }
-
-    /// Returns an iterator over all transactions in the block.
-    #[inline]
-    pub fn transactions(&self) -> impl Iterator<Item = &TransactionSigned> + '_ {
-        self.block.body.transactions()
+        let (header, body) = block.split_header_body();
+        BlockWithSenders { block: B::new(header.unseal(), body), senders }
     }
 
     /// Returns an iterator over all transactions and their sender.
     #[inline]
     pub fn transactions_with_sender(
         &self,
-    ) -> impl Iterator<Item = (&Address, &TransactionSigned)> + '_ {
+    ) -> impl Iterator<Item = (&Address, &<B::Body as reth_primitives_traits::BlockBody>::Transaction)>
+           + '_ {
         self.senders.iter().zip(self.block.body.transactions())
     }
+}
+
+impl Default for SealedBlockWithSenders {
+    fn default() -> Self {
+        Self { block: SealedBlock::default(), senders: Vec::new() }
+    }
+}
+
+impl SealedBlockWithSenders {
+    /// New sealed block with sender. Return none if len of tx and senders does not match
+    pub fn new(block: SealedBlock, senders: Vec<Address>) -> Option<Self> {
+        (block.body.transactions.len() == senders.len()).then_some(Self { block, senders })
+    }
+
+    /// Returns an iterator over all transactions in the block.
+    #[inline]
+    pub fn transactions(&self) -> impl Iterator<Item = &TransactionSigned> + '_ {
+        self.block.body.transactions()
+    }
 
     /// Consumes the block and returns the transactions of the block.
     #[inline]
@@ -577,13 +600,24 @@ impl<'a> arbitrary::Arbitrary<'a> for SealedBlockWithSenders {
 /// Withdrawals can be optionally included at the end of the RLP encoded message.
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(rlp, 10))]
 #[derive(
-    Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize, RlpEncodable, RlpDecodable,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    RlpEncodable,
+    RlpDecodable,
+    InMemorySize,
 )]
 #[rlp(trailing)]
 pub struct BlockBody {
     /// Transactions in the block
+    #[in_mem_size(heap)]
     pub transactions: Vec<TransactionSigned>,
     /// Uncle headers for the given block
+    #[in_mem_size(heap)]
     pub ommers: Vec<Header>,
     /// Withdrawals in the block.
     pub withdrawals: Option<Withdrawals>,
@@ -661,26 +695,21 @@ impl BlockBody {
     }
 }
 
-impl InMemorySize for BlockBody {
-    /// Calculates a heuristic for the in-memory size of the [`BlockBody`].
-    #[inline]
-    fn size(&self) -> usize {
-        self.transactions.iter().map(TransactionSigned::size).sum::<usize>() +
-            self.transactions.capacity() * core::mem::size_of::<TransactionSigned>() +
-            self.ommers.iter().map(Header::size).sum::<usize>() +
-            self.ommers.capacity() * core::mem::size_of::<Header>() +
-            self.withdrawals
-                .as_ref()
-                .map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size)
-    }
-}
-
 impl reth_primitives_traits::BlockBody for BlockBody {
     type Transaction = TransactionSigned;
+    type OmmerHeader = Header;
 
     fn transactions(&self) -> &[Self::Transaction] {
         &self.transactions
     }
+
+    fn withdrawals(&self) -> Option<&Withdrawals> {
+        self.withdrawals.as_ref()
+    }
+
+    fn ommers(&self) -> Option<&[Self::OmmerHeader]> {
+        Some(&self.ommers)
+    }
 }
 
 impl From<Block> for BlockBody {
@@ -1170,4 +1199,115 @@ mod tests {
         let decoded = BlockBody::decode(&mut buf.as_slice()).unwrap();
         assert_eq!(body, decoded);
     }
+
+    #[test]
+    fn block_body_trait_accessors() {
+        use crate::Transaction;
+        use alloy_consensus::TxEip4844;
+        use alloy_eips::eip4895::Withdrawal;
+        use alloy_primitives::PrimitiveSignature;
+        use reth_primitives_traits::BlockBody as BlockBodyT;
+
+        let hashes = vec![B256::with_last_byte(1), B256::with_last_byte(2)];
+        let blob_tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip4844(TxEip4844 {
+                blob_versioned_hashes: hashes.clone(),
+                ..Default::default()
+            }),
+            PrimitiveSignature::test_signature(),
+        );
+        let body = BlockBody {
+            transactions: vec![blob_tx, TransactionSigned::default()],
+            ommers: vec![Header::default()],
+            withdrawals: Some(Withdrawals::new(vec![Withdrawal::default()])),
+        };
+
+        assert_eq!(BlockBodyT::blob_versioned_hashes(&body), hashes.iter().collect::<Vec<_>>());
+        assert_eq!(BlockBodyT::ommers(&body).map(<[_]>::len), Some(1));
+        assert_eq!(BlockBodyT::withdrawals(&body).map(|w| w.len()), Some(1));
+        assert!(BlockBodyT::requests(&body).is_none());
+    }
+
+    #[test]
+    fn block_body_trait_helpers() {
+        use crate::Transaction;
+        use alloy_consensus::{TxEip4844, TxLegacy};
+        use alloy_primitives::{Bytes, PrimitiveSignature, TxKind};
+        use reth_primitives_traits::BlockBody as BlockBodyT;
+        use revm_primitives::SpecId;
+
+        let blob_tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip4844(TxEip4844 {
+                blob_versioned_hashes: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+                ..Default::default()
+            }),
+            PrimitiveSignature::test_signature(),
+        );
+        let create_tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                to: TxKind::Create,
+                input: Bytes::from_static(&[0, 1, 2]),
+                ..Default::default()
+            }),
+            PrimitiveSignature::test_signature(),
+        );
+        let body = BlockBody { transactions: vec![blob_tx, create_tx], ..Default::default() };
+
+        assert_eq!(BlockBodyT::transaction_count(&body), 2);
+        assert_eq!(BlockBodyT::calculate_tx_root(&body), body.calculate_tx_root());
+        assert_eq!(BlockBodyT::blob_gas_used(&body), 2 * alloy_eips::eip4844::DATA_GAS_PER_BLOB);
+        // 21000 for the call, 53000 + 4 + 2 * 16 + 2 initcode words gas for the create
+        assert_eq!(BlockBodyT::intrinsic_gas_used(&body, SpecId::CANCUN), 21000 + 53038);
+        assert_eq!(BlockBodyT::intrinsic_gas_used(&body, SpecId::FRONTIER), 21000 + 21140);
+    }
+
+    #[test]
+    fn block_body_trait_recover_signers() {
+        use crate::{public_key_to_address, sign_message, Transaction};
+        use alloy_consensus::TxEip1559;
+        use reth_primitives_traits::{BlockBody as BlockBodyT, SignedTransaction};
+        use secp256k1::{Keypair, SECP256K1};
+
+        let key_pair = Keypair::new(SECP256K1, &mut rand::thread_rng());
+        let transactions = (0..3)
+            .map(|nonce| {
+                let tx = Transaction::Eip1559(TxEip1559 { nonce, ..Default::default() });
+                let signature = sign_message(
+                    B256::from_slice(&key_pair.secret_bytes()[..]),
+                    tx.signature_hash(),
+                )
+                .unwrap();
+                TransactionSigned::from_transaction_and_signature(tx, signature)
+            })
+            .collect::<Vec<_>>();
+        let body = BlockBody { transactions, ..Default::default() };
+
+        let mut buf = Vec::new();
+        SignedTransaction::encode_for_signing(&body.transactions[0], &mut buf);
+        assert_eq!(
+            SignedTransaction::signature_hash(&body.transactions[0]),
+            alloy_primitives::keccak256(buf)
+        );
+
+        let signer = public_key_to_address(key_pair.public_key());
+        assert_eq!(BlockBodyT::recover_signers(&body), Some(vec![signer; 3]));
+        assert_eq!(BlockBodyT::recover_signers_unchecked(&body), body.recover_signers());
+    }
+
+    #[test]
+    fn block_trait_split_and_seal() {
+        use reth_primitives_traits::Block as BlockT;
+
+        let header = Header { number: 7, gas_limit: 30_000_000, ..Default::default() };
+        let block = <Block as BlockT>::new(header.clone(), BlockBody::default());
+        assert_eq!(BlockT::hash_slow(&block), header.hash_slow());
+        assert_eq!(BlockT::recover_signers(&block), Some(vec![]));
+
+        let sealed = block.clone().seal_slow();
+        assert_eq!(BlockT::split(sealed.clone()), (header.clone(), BlockBody::default()));
+        assert_eq!(<SealedBlock as BlockT>::new(header, BlockBody::default()), sealed);
+
+        let with_senders = SealedBlockWithSenders::new(sealed, vec![]).unwrap();
+        assert_eq!(with_senders.unseal(), BlockWithSenders::new(block, vec![]).unwrap());
+    }
 }
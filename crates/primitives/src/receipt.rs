@@ -23,7 +23,16 @@ pub use reth_primitives_traits::receipt::gas_spent_by_transactions;
 
 /// Receipt containing result of transaction execution.
 #[derive(
-    Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable, Serialize, Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    RlpEncodable,
+    RlpDecodable,
+    Serialize,
+    Deserialize,
+    InMemorySize,
 )]
 #[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::CompactZstd))]
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests)]
@@ -34,13 +43,17 @@ pub struct Receipt {
     /// If transaction is executed successfully.
     ///
     /// This is the `statusCode`
+    #[in_mem_size(size_of)]
     pub success: bool,
     /// Gas used
+    #[in_mem_size(size_of)]
     pub cumulative_gas_used: u64,
     /// Log send from contracts.
+    #[in_mem_size(capacity)]
     pub logs: Vec<Log>,
     /// Deposit nonce for Optimism deposit transactions
     #[cfg(feature = "optimism")]
+    #[in_mem_size(size_of)]
     pub deposit_nonce: Option<u64>,
     /// Deposit receipt version for Optimism deposit transactions
     ///
@@ -49,6 +62,7 @@ pub struct Receipt {
     /// receipt hashes should be computed when set. The state transition process
     /// ensures this is only set for post-Canyon deposit transactions.
     #[cfg(feature = "optimism")]
+    #[in_mem_size(size_of)]
     pub deposit_receipt_version: Option<u64>,
 }
 
@@ -99,6 +113,18 @@ impl reth_primitives_traits::Receipt for Receipt {
     fn tx_type(&self) -> u8 {
         self.tx_type as u8
     }
+
+    fn rlp_encoded_length_with_bloom(&self, bloom: &Bloom) -> usize {
+        ReceiptWithBloomEncoder { receipt: self, bloom }.length()
+    }
+
+    fn rlp_encode_with_bloom(&self, bloom: &Bloom, out: &mut dyn BufMut) {
+        ReceiptWithBloomEncoder { receipt: self, bloom }.encode_inner(out, true)
+    }
+
+    fn eip2718_encode_with_bloom(&self, bloom: &Bloom, out: &mut dyn BufMut) {
+        ReceiptWithBloomEncoder { receipt: self, bloom }.encode_inner(out, false)
+    }
 }
 
 impl ReceiptExt for Receipt {
@@ -110,22 +136,6 @@ impl ReceiptExt for Receipt {
     }
 }
 
-impl InMemorySize for Receipt {
-    /// Calculates a heuristic for the in-memory size of the [Receipt].
-    #[inline]
-    fn size(&self) -> usize {
-        let total_size = self.tx_type.size() +
-            core::mem::size_of::<bool>() +
-            core::mem::size_of::<u64>() +
-            self.logs.capacity() * core::mem::size_of::<Log>();
-
-        #[cfg(feature = "optimism")]
-        return total_size + 2 * core::mem::size_of::<Option<u64>>();
-        #[cfg(not(feature = "optimism"))]
-        total_size
-    }
-}
-
 /// A collection of receipts organized as a two-dimensional vector.
 #[derive(
     Clone,
@@ -772,4 +782,47 @@ mod tests {
             "Encoded length for legacy receipt should match the actual encoded data length"
         );
     }
+
+    #[test]
+    fn receipt_trait_bloom_encoding() {
+        use reth_primitives_traits::{receipt::calculate_receipt_root, Receipt as _};
+
+        let receipts = [TxType::Legacy, TxType::Eip1559]
+            .into_iter()
+            .map(|tx_type| Receipt {
+                tx_type,
+                success: true,
+                cumulative_gas_used: 21000,
+                logs: vec![Log::new_unchecked(
+                    address!("0000000000000000000000000000000000000011"),
+                    vec![b256!("000000000000000000000000000000000000000000000000000000000000dead")],
+                    bytes!("0100ff"),
+                )],
+                #[cfg(feature = "optimism")]
+                deposit_nonce: None,
+                #[cfg(feature = "optimism")]
+                deposit_receipt_version: None,
+            })
+            .collect::<Vec<_>>();
+
+        for receipt in &receipts {
+            let with_bloom = receipt.clone().with_bloom();
+
+            let mut encoded = Vec::new();
+            receipt.rlp_encode_with_bloom(&with_bloom.bloom, &mut encoded);
+            assert_eq!(encoded, alloy_rlp::encode(&with_bloom));
+            assert_eq!(encoded.len(), receipt.rlp_encoded_length_with_bloom(&with_bloom.bloom));
+
+            let mut encoded = Vec::new();
+            receipt.eip2718_encode_with_bloom(&with_bloom.bloom, &mut encoded);
+            assert_eq!(encoded, with_bloom.encoded_2718());
+        }
+
+        let receipts_with_bloom =
+            receipts.iter().cloned().map(Receipt::with_bloom).collect::<Vec<_>>();
+        assert_eq!(
+            calculate_receipt_root(&receipts),
+            crate::proofs::calculate_receipt_root(&receipts_with_bloom)
+        );
+    }
 }
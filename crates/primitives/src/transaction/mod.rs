@@ -16,7 +16,6 @@ use alloy_primitives::{
     keccak256, Address, Bytes, ChainId, PrimitiveSignature as Signature, TxHash, TxKind, B256, U256,
 };
 use alloy_rlp::{Decodable, Encodable, Error as RlpError, Header};
-use core::mem;
 use derive_more::{AsRef, Deref};
 use once_cell as _;
 #[cfg(not(feature = "std"))]
@@ -86,7 +85,9 @@ pub static PARALLEL_SENDER_RECOVERY_THRESHOLD: LazyLock<usize> =
 /// A raw transaction.
 ///
 /// Transaction types were introduced in [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::From)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::From, InMemorySize,
+)]
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
 pub enum Transaction {
     /// Legacy transaction (type `0x0`).
@@ -533,22 +534,6 @@ impl Transaction {
     }
 }
 
-impl InMemorySize for Transaction {
-    /// Calculates a heuristic for the in-memory size of the [Transaction].
-    #[inline]
-    fn size(&self) -> usize {
-        match self {
-            Self::Legacy(tx) => tx.size(),
-            Self::Eip2930(tx) => tx.size(),
-            Self::Eip1559(tx) => tx.size(),
-            Self::Eip4844(tx) => tx.size(),
-            Self::Eip7702(tx) => tx.size(),
-            #[cfg(feature = "optimism")]
-            Self::Deposit(tx) => tx.size(),
-        }
-    }
-}
-
 #[cfg(any(test, feature = "reth-codec"))]
 impl reth_codecs::Compact for Transaction {
     // Serializes the TxType to the buffer if necessary, returning 2 bits of the type as an
@@ -1088,11 +1073,13 @@ impl From<TransactionSigned> for TransactionSignedNoHash {
 
 /// Signed transaction.
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(rlp))]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, Deref, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, Deref, Serialize, Deserialize, InMemorySize)]
 pub struct TransactionSigned {
     /// Transaction hash
+    #[in_mem_size(size_of)]
     pub hash: TxHash,
     /// The transaction signature values
+    #[in_mem_size(size_of)]
     pub signature: Signature,
     /// Raw transaction info
     #[deref]
@@ -1351,6 +1338,14 @@ impl SignedTransaction for TransactionSigned {
         &self.signature
     }
 
+    fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.transaction.encode_for_signing(out)
+    }
+
+    fn signature_hash(&self) -> B256 {
+        self.transaction.signature_hash()
+    }
+
     fn recover_signer(&self) -> Option<Address> {
         let signature_hash = self.signature_hash();
         recover_signer(&self.signature, signature_hash)
@@ -1441,14 +1436,6 @@ impl SignedTransaction for TransactionSigned {
     }
 }
 
-impl InMemorySize for TransactionSigned {
-    /// Calculate a heuristic for the in-memory size of the [`TransactionSigned`].
-    #[inline]
-    fn size(&self) -> usize {
-        mem::size_of::<TxHash>() + self.transaction.size() + mem::size_of::<Signature>()
-    }
-}
-
 impl alloy_consensus::Transaction for TransactionSigned {
     fn chain_id(&self) -> Option<ChainId> {
         self.deref().chain_id()
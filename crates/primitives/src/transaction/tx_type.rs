@@ -50,10 +50,12 @@ pub const DEPOSIT_TX_TYPE_ID: u8 = 126;
     Deserialize,
     Hash,
     Display,
+    InMemorySize,
 )]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
 #[display("tx type: {_variant}")]
+#[in_mem_size(size_of)]
 pub enum TxType {
     /// Legacy transaction pre EIP-2929
     #[default]
@@ -119,14 +121,6 @@ impl reth_primitives_traits::TxType for TxType {
     }
 }
 
-impl InMemorySize for TxType {
-    /// Calculates a heuristic for the in-memory size of the [`TxType`].
-    #[inline]
-    fn size(&self) -> usize {
-        core::mem::size_of::<Self>()
-    }
-}
-
 impl From<TxType> for u8 {
     fn from(value: TxType) -> Self {
         match value {
@@ -0,0 +1,392 @@
+//! On-demand CPU profiling.
+//!
+//! The [`CpuProfiler`] samples the call stacks of all threads of the process while it is running,
+//! and produces a [`CpuProfile`] that can be written as folded stacks or as a flamegraph SVG. The
+//! profiler can be started and stopped at runtime, e.g. around a single stage run, via the
+//! `admin_startCpuProfile` and `admin_stopCpuProfile` RPC methods, or around the next call of an
+//! RPC method, so no external profiler has to be attached to the node.
+//!
+//! Profiles requested over RPC are written to the output directory of the profiler, see
+//! [`CpuProfiler::set_output_dir`], under names chosen by the node.
+//!
+//! ## Feature Flags
+//!
+//! - `pprof`: Enables the sampling profiler, only supported on unix platforms. Without it, starting
+//!   the profiler returns [`ProfilerError::Unsupported`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use parking_lot::Mutex;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// Default sampling frequency of the [`CpuProfiler`] in Hz.
+pub const DEFAULT_PROFILING_FREQUENCY: u32 = 99;
+
+/// The process wide [`CpuProfiler`].
+static CPU_PROFILER: CpuProfiler = CpuProfiler {
+    active: Mutex::new(None),
+    output_dir: Mutex::new(None),
+    next_call_armed: AtomicBool::new(false),
+    next_call: Mutex::new(None),
+};
+
+/// Returns the process wide [`CpuProfiler`].
+///
+/// There is only one profiler per process, since samples are collected with a process wide
+/// signal handler.
+pub fn cpu_profiler() -> &'static CpuProfiler {
+    &CPU_PROFILER
+}
+
+/// Errors of the [`CpuProfiler`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProfilerError {
+    /// The profiler is already running.
+    #[error("CPU profiler is already running")]
+    AlreadyRunning,
+    /// The profiler is not running.
+    #[error("CPU profiler is not running")]
+    NotRunning,
+    /// No output directory is configured for profiles requested at runtime.
+    #[error("no CPU profile output directory is configured")]
+    NoOutputDir,
+    /// CPU profiling is not supported by this build.
+    #[error("CPU profiling is not supported, reth must be built with the `cpu-profiling` feature on a unix platform")]
+    Unsupported,
+    /// The sampling profiler failed.
+    #[error("CPU profiler error: {0}")]
+    Profiler(String),
+    /// Writing the profile failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// A sampling CPU profiler, see [`cpu_profiler`].
+#[derive(Debug)]
+pub struct CpuProfiler {
+    active: Mutex<Option<ActiveProfile>>,
+    /// Directory profiles requested at runtime are written to.
+    output_dir: Mutex<Option<PathBuf>>,
+    /// Whether [`Self::next_call`] is set, checked before locking it on every call.
+    next_call_armed: AtomicBool,
+    /// Method whose next call is profiled, with the sampling frequency.
+    next_call: Mutex<Option<(String, u32)>>,
+}
+
+impl CpuProfiler {
+    /// Starts sampling the call stacks of all threads with the given frequency in Hz.
+    pub fn start(&self, frequency: u32) -> Result<(), ProfilerError> {
+        let mut active = self.active.lock();
+        if active.is_some() {
+            return Err(ProfilerError::AlreadyRunning)
+        }
+        *active = Some(ActiveProfile::start(frequency)?);
+        info!(target: "reth::profiling", frequency, "Started CPU profiler");
+        Ok(())
+    }
+
+    /// Stops the profiler and returns the collected profile.
+    pub fn stop(&self) -> Result<CpuProfile, ProfilerError> {
+        let active = self.active.lock().take().ok_or(ProfilerError::NotRunning)?;
+        let profile = active.finish()?;
+        info!(target: "reth::profiling", duration = ?profile.duration, samples = profile.samples(), "Stopped CPU profiler");
+        Ok(profile)
+    }
+
+    /// Returns `true` if the profiler is running.
+    pub fn is_running(&self) -> bool {
+        self.active.lock().is_some()
+    }
+
+    /// Sets the directory that profiles requested at runtime are written to, see
+    /// [`Self::stop_and_save`].
+    pub fn set_output_dir(&self, dir: impl Into<PathBuf>) {
+        *self.output_dir.lock() = Some(dir.into());
+    }
+
+    /// Stops the profiler and writes the collected profile as folded stacks into the output
+    /// directory. The file is named after the given label and the current time.
+    ///
+    /// Returns the path of the written file.
+    pub fn stop_and_save(&self, label: &str) -> Result<PathBuf, ProfilerError> {
+        let dir = self.output_dir.lock().clone().ok_or(ProfilerError::NoOutputDir)?;
+        let profile = self.stop()?;
+
+        std::fs::create_dir_all(&dir)?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let label = label.replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "-");
+        let path = dir.join(format!("cpu-{label}-{}.folded", timestamp.as_millis()));
+        profile.write_to_file(&path)?;
+        Ok(path)
+    }
+
+    /// Profiles the next call of the given RPC method with the given frequency in Hz.
+    ///
+    /// The profile is written into the output directory once the call completes or is cancelled,
+    /// see [`Self::start_for_call`].
+    pub fn profile_next_call(&self, method: String, frequency: u32) -> Result<(), ProfilerError> {
+        if self.output_dir.lock().is_none() {
+            return Err(ProfilerError::NoOutputDir)
+        }
+        if self.is_running() {
+            return Err(ProfilerError::AlreadyRunning)
+        }
+        info!(target: "reth::profiling", %method, "Profiling next call");
+        *self.next_call.lock() = Some((method, frequency));
+        self.next_call_armed.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Starts the profiler if the next call of `method` should be profiled, see
+    /// [`Self::profile_next_call`].
+    ///
+    /// Returns a guard if the profiler was started. The profiler is stopped and the profile is
+    /// saved when the guard is dropped, so it must be held until the call completes.
+    pub fn start_for_call(&self, method: &str) -> Option<CallProfileGuard<'_>> {
+        if !self.next_call_armed.load(Ordering::Acquire) {
+            return None
+        }
+        let frequency = {
+            let mut next_call = self.next_call.lock();
+            next_call.take_if(|(next, _)| next == method)?.1
+        };
+        self.next_call_armed.store(false, Ordering::Release);
+
+        match self.start(frequency) {
+            Ok(()) => Some(CallProfileGuard { profiler: self, method: method.to_string() }),
+            Err(err) => {
+                warn!(target: "reth::profiling", %method, %err, "Failed to profile call");
+                None
+            }
+        }
+    }
+}
+
+/// Stops the profiler of a call and saves the profile when dropped, see
+/// [`CpuProfiler::start_for_call`].
+#[derive(Debug)]
+#[must_use = "the profiler is stopped when the guard is dropped"]
+pub struct CallProfileGuard<'a> {
+    profiler: &'a CpuProfiler,
+    /// The profiled method.
+    method: String,
+}
+
+impl Drop for CallProfileGuard<'_> {
+    fn drop(&mut self) {
+        let method = &self.method;
+        match self.profiler.stop_and_save(method) {
+            Ok(path) => {
+                info!(target: "reth::profiling", %method, path = %path.display(), "Wrote CPU profile of call")
+            }
+            Err(err) => {
+                warn!(target: "reth::profiling", %method, %err, "Failed to write CPU profile of call")
+            }
+        }
+    }
+}
+
+/// A running profile.
+struct ActiveProfile {
+    started_at: Instant,
+    #[cfg(all(unix, feature = "pprof"))]
+    guard: pprof::ProfilerGuard<'static>,
+}
+
+impl core::fmt::Debug for ActiveProfile {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ActiveProfile")
+            .field("started_at", &self.started_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ActiveProfile {
+    #[cfg(all(unix, feature = "pprof"))]
+    fn start(frequency: u32) -> Result<Self, ProfilerError> {
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(frequency.try_into().unwrap_or(i32::MAX))
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+            .map_err(|err| ProfilerError::Profiler(err.to_string()))?;
+        Ok(Self { started_at: Instant::now(), guard })
+    }
+
+    #[cfg(not(all(unix, feature = "pprof")))]
+    const fn start(_frequency: u32) -> Result<Self, ProfilerError> {
+        Err(ProfilerError::Unsupported)
+    }
+
+    #[cfg(all(unix, feature = "pprof"))]
+    fn finish(self) -> Result<CpuProfile, ProfilerError> {
+        let report =
+            self.guard.report().build().map_err(|err| ProfilerError::Profiler(err.to_string()))?;
+        let stacks = report
+            .data
+            .iter()
+            .map(|(frames, count)| {
+                let mut stack = vec![frames.thread_name_or_id()];
+                for frame in frames.frames.iter().rev() {
+                    stack.extend(frame.iter().rev().map(|symbol| symbol.to_string()));
+                }
+                (stack.join(";"), (*count).max(0) as u64)
+            })
+            .collect();
+        Ok(CpuProfile { duration: self.started_at.elapsed(), stacks })
+    }
+
+    #[cfg(not(all(unix, feature = "pprof")))]
+    const fn finish(self) -> Result<CpuProfile, ProfilerError> {
+        Err(ProfilerError::Unsupported)
+    }
+}
+
+/// A collected CPU profile.
+#[derive(Debug, Clone, Default)]
+pub struct CpuProfile {
+    /// Duration the profiler was running.
+    pub duration: Duration,
+    /// Sampled call stacks, as `;` separated frames from the thread to the leaf function, with
+    /// the number of samples.
+    pub stacks: Vec<(String, u64)>,
+}
+
+impl CpuProfile {
+    /// Returns the total number of samples.
+    pub fn samples(&self) -> u64 {
+        self.stacks.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Writes the profile in the folded stacks format, which can be rendered by flamegraph tools,
+    /// e.g. `inferno-flamegraph` or speedscope.
+    pub fn write_folded<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for (stack, count) in &self.stacks {
+            writeln!(writer, "{stack} {count}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the profile to the file at `path`.
+    ///
+    /// Files with an `svg` extension are written as a flamegraph, all others as folded stacks.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), ProfilerError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext == "svg") {
+            self.write_flamegraph(&mut writer)?;
+        } else {
+            self.write_folded(&mut writer)?;
+        }
+        writer.flush()?;
+        info!(target: "reth::profiling", ?path, "Wrote CPU profile");
+        Ok(())
+    }
+
+    #[cfg(all(unix, feature = "pprof"))]
+    fn write_flamegraph<W: Write>(&self, writer: W) -> Result<(), ProfilerError> {
+        use pprof::flamegraph;
+
+        let lines = self.stacks.iter().map(|(stack, count)| format!("{stack} {count}"));
+        let lines = lines.collect::<Vec<_>>();
+        flamegraph::from_lines(
+            &mut flamegraph::Options::default(),
+            lines.iter().map(String::as_str),
+            writer,
+        )
+        .map_err(|err| ProfilerError::Profiler(err.to_string()))
+    }
+
+    #[cfg(not(all(unix, feature = "pprof")))]
+    fn write_flamegraph<W: Write>(&self, _writer: W) -> Result<(), ProfilerError> {
+        Err(ProfilerError::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_folded_stacks() {
+        let profile = CpuProfile {
+            duration: Duration::from_secs(1),
+            stacks: vec![("main;a;b".to_string(), 3), ("main;c".to_string(), 1)],
+        };
+        assert_eq!(profile.samples(), 4);
+
+        let mut buf = Vec::new();
+        profile.write_folded(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "main;a;b 3\nmain;c 1\n");
+    }
+
+    #[cfg(all(unix, feature = "pprof"))]
+    #[test]
+    fn profile_busy_loop() {
+        let profiler = cpu_profiler();
+        profiler.start(DEFAULT_PROFILING_FREQUENCY).unwrap();
+        assert!(profiler.is_running());
+        assert!(matches!(
+            profiler.start(DEFAULT_PROFILING_FREQUENCY),
+            Err(ProfilerError::AlreadyRunning)
+        ));
+
+        let started_at = Instant::now();
+        let mut x = 0u64;
+        while started_at.elapsed() < Duration::from_millis(300) {
+            x = std::hint::black_box(x.wrapping_mul(31).wrapping_add(7));
+        }
+
+        let profile = profiler.stop().unwrap();
+        assert!(!profiler.is_running());
+        assert!(profile.samples() > 0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.svg");
+        profile.write_to_file(&path).unwrap();
+        assert!(std::fs::read_to_string(path).unwrap().starts_with("<?xml"));
+    }
+
+    #[test]
+    fn profile_next_call() {
+        let profiler = CpuProfiler {
+            active: Mutex::new(None),
+            output_dir: Mutex::new(None),
+            next_call_armed: AtomicBool::new(false),
+            next_call: Mutex::new(None),
+        };
+        assert!(matches!(
+            profiler.profile_next_call("eth_call".to_string(), DEFAULT_PROFILING_FREQUENCY),
+            Err(ProfilerError::NoOutputDir)
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        profiler.set_output_dir(dir.path());
+        profiler.profile_next_call("eth_call".to_string(), DEFAULT_PROFILING_FREQUENCY).unwrap();
+
+        assert!(profiler.start_for_call("eth_getBalance").is_none());
+
+        // the call is only profiled once, if the build supports profiling
+        let guard = profiler.start_for_call("eth_call");
+        assert_eq!(guard.is_some(), cfg!(all(unix, feature = "pprof")));
+        assert!(profiler.start_for_call("eth_call").is_none());
+        if guard.is_some() {
+            assert!(profiler.is_running());
+            drop(guard);
+            assert!(!profiler.is_running());
+            let path = std::fs::read_dir(dir.path()).unwrap().next().unwrap().unwrap().path();
+            assert!(path.file_name().unwrap().to_str().unwrap().starts_with("cpu-eth_call-"));
+        }
+    }
+}
@@ -1,11 +1,8 @@
 //! [`jsonrpsee`] transport adapter implementation for IPC.
 
-use crate::stream_codec::StreamCodec;
+use crate::{endpoint::socket_name, stream_codec::StreamCodec};
 use futures::{StreamExt, TryFutureExt};
-use interprocess::local_socket::{
-    tokio::{prelude::*, RecvHalf, SendHalf},
-    GenericFilePath,
-};
+use interprocess::local_socket::tokio::{prelude::*, RecvHalf, SendHalf};
 use jsonrpsee::{
     async_client::{Client, ClientBuilder},
     core::client::{ReceivedMessage, TransportReceiverT, TransportSenderT},
@@ -64,7 +61,7 @@ pub(crate) struct IpcTransportClientBuilder;
 
 impl IpcTransportClientBuilder {
     pub(crate) async fn build(self, path: &str) -> Result<(Sender, Receiver), IpcError> {
-        let conn = async { path.to_fs_name::<GenericFilePath>() }
+        let conn = async { socket_name(path) }
             .and_then(LocalSocketStream::connect)
             .await
             .map_err(|err| IpcError::FailedToConnect { path: path.to_string(), err })?;
@@ -144,10 +141,8 @@ mod tests {
     async fn test_connect() {
         let name = &dummy_name();
 
-        let binding = ListenerOptions::new()
-            .name(name.as_str().to_fs_name::<GenericFilePath>().unwrap())
-            .create_tokio()
-            .unwrap();
+        let binding =
+            ListenerOptions::new().name(socket_name(name).unwrap()).create_tokio().unwrap();
         tokio::spawn(async move {
             let _x = binding.accept().await;
         });
@@ -0,0 +1,40 @@
+//! Resolution of IPC endpoints to local socket names.
+
+use interprocess::local_socket::{GenericFilePath, Name, ToFsName};
+use std::io;
+
+/// The path prefix of Windows named pipes.
+pub const NAMED_PIPE_PREFIX: &str = r"\\.\pipe\";
+
+/// Resolves an IPC endpoint to the name of a local socket.
+///
+/// On Unix the endpoint is the path of a Unix domain socket. On Windows it is the path of a named
+/// pipe, and endpoints that aren't in the `\\.\pipe\` namespace, e.g. `reth.ipc`, are placed in it.
+pub fn socket_name(endpoint: &str) -> io::Result<Name<'static>> {
+    named_pipe_path(endpoint).to_fs_name::<GenericFilePath>()
+}
+
+/// Returns the path of the named pipe of the endpoint on Windows, or the endpoint on other
+/// platforms.
+pub fn named_pipe_path(endpoint: &str) -> String {
+    if cfg!(windows) && !endpoint.starts_with(NAMED_PIPE_PREFIX) {
+        let name = endpoint.trim_start_matches(['\\', '/']);
+        return format!("{NAMED_PIPE_PREFIX}{name}")
+    }
+    endpoint.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_named_pipe_path() {
+        assert_eq!(named_pipe_path(r"\\.\pipe\reth.ipc"), r"\\.\pipe\reth.ipc");
+        if cfg!(windows) {
+            assert_eq!(named_pipe_path("reth.ipc"), r"\\.\pipe\reth.ipc");
+        } else {
+            assert_eq!(named_pipe_path("/tmp/reth.ipc"), "/tmp/reth.ipc");
+        }
+    }
+}
@@ -6,7 +6,7 @@ use futures_util::future::Either;
 use interprocess::local_socket::{
     tokio::prelude::{LocalSocketListener, LocalSocketStream},
     traits::tokio::{Listener, Stream},
-    GenericFilePath, ListenerOptions, ToFsName,
+    ListenerOptions,
 };
 use jsonrpsee::{
     core::TEN_MB_SIZE_BYTES,
@@ -32,6 +32,7 @@ use tower::{layer::util::Identity, Layer, Service};
 use tracing::{debug, instrument, trace, warn, Instrument};
 // re-export so can be used during builder setup
 use crate::{
+    endpoint::socket_name,
     server::{connection::IpcConnDriver, rpc_service::RpcServiceCfg},
     stream_codec::StreamCodec,
 };
@@ -134,10 +135,7 @@ where
             }
         }
 
-        let listener = match self
-            .endpoint
-            .as_str()
-            .to_fs_name::<GenericFilePath>()
+        let listener = match socket_name(&self.endpoint)
             .and_then(|name| ListenerOptions::new().name(name).create_tokio())
         {
             Ok(listener) => listener,
@@ -1,6 +1,9 @@
+use alloy_primitives::TxHash;
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+use reth_network_types::{PeerDiagnostics, PeerReputationRecord};
+use reth_transaction_pool::PoolRuntimeConfig;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +37,22 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the reputation score of the given peer and until when it's banned, or the scores
+    /// of all peers that were penalized or are banned temporarily if no peer is given.
+    #[method(name = "peerReputation")]
+    async fn peer_reputation(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> RpcResult<Vec<PeerReputationRecord>>;
+
+    /// Returns the diagnostics of the given peer, or of all connected peers and all peers that
+    /// disconnected recently if no peer is given.
+    ///
+    /// This includes the negotiated capabilities, client version, latency, best block and traffic
+    /// of the session to the peer, as well as the peer's most recent disconnect reasons.
+    #[method(name = "peerDiagnostics")]
+    async fn peer_diagnostics(&self, peer_id: Option<PeerId>) -> RpcResult<Vec<PeerDiagnostics>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -45,4 +64,36 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Starts sampling the CPU usage of the node with the given frequency in Hz.
+    ///
+    /// Returns an error if a profile is already running or CPU profiling is not supported by the
+    /// build.
+    #[method(name = "startCpuProfile")]
+    fn start_cpu_profile(&self, frequency: Option<u32>) -> RpcResult<bool>;
+
+    /// Stops the running CPU profile and writes it as folded stacks into the profile directory of
+    /// the node.
+    ///
+    /// Returns the path of the written file.
+    #[method(name = "stopCpuProfile")]
+    fn stop_cpu_profile(&self) -> RpcResult<String>;
+
+    /// Profiles the next call of the given RPC method with the given frequency in Hz.
+    ///
+    /// Once the call completes, the profile is written as folded stacks into the profile directory
+    /// of the node.
+    #[method(name = "profileNextCall")]
+    fn profile_next_call(&self, method: String, frequency: Option<u32>) -> RpcResult<bool>;
+
+    /// Returns the replacement and size settings the transaction pool currently uses.
+    #[method(name = "txpoolConfig")]
+    fn txpool_config(&self) -> RpcResult<PoolRuntimeConfig>;
+
+    /// Updates the replacement and size settings of the transaction pool, settings that are not
+    /// set are left unchanged.
+    ///
+    /// Returns the hashes of the transactions that were discarded because of lowered size limits.
+    #[method(name = "setTxpoolConfig")]
+    fn set_txpool_config(&self, config: PoolRuntimeConfig) -> RpcResult<Vec<TxHash>>;
 }
@@ -25,11 +25,17 @@ mod net;
 mod otterscan;
 mod reth;
 mod rpc;
+mod stateless;
 mod trace;
 mod txpool;
 mod validation;
 mod web3;
 
+pub use reth::{
+    AccountStateDiff, ChainStateDiff, NonceGap, PoolEvent, PromotionBlocker, SenderPoolStatus,
+    SenderPoolTransaction,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -44,6 +50,7 @@ pub mod servers {
         otterscan::OtterscanServer,
         reth::RethApiServer,
         rpc::RpcApiServer,
+        stateless::StatelessValidationApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
         validation::BlockSubmissionValidationApiServer,
@@ -51,7 +58,8 @@ pub mod servers {
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer,
+        EthPrivateTransactionApiServer, EthPubSubApiServer, EthSendBundleApiServer,
+        EthUserOperationApiServer,
     };
 }
 
@@ -74,6 +82,7 @@ pub mod clients {
         otterscan::OtterscanClient,
         reth::RethApiClient,
         rpc::RpcApiServer,
+        stateless::StatelessValidationApiClient,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
         validation::BlockSubmissionValidationApiClient,
@@ -81,5 +90,6 @@ pub mod clients {
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
+        EthPrivateTransactionApiClient, EthSendBundleApiClient, EthUserOperationApiClient,
     };
 }
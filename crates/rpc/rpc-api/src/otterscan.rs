@@ -64,22 +64,28 @@ pub trait Otterscan<T: RpcObject> {
     ) -> RpcResult<OtsBlockTransactions<T>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Searches the blocks before the given block, or all blocks if it is `0`, from the newest to
+    /// the oldest, until at least `page_size` transactions are found.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
         address: Address,
         block_number: u64,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Searches the blocks after the given block, or all blocks if it is `0`, from the oldest to
+    /// the newest, until at least `page_size` transactions are found.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
         address: Address,
         block_number: u64,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<TransactionsWithReceipts<T>>;
 
     /// Gets the transaction hash for a certain sender address, given its nonce.
     #[method(name = "getTransactionBySenderAndNonce")]
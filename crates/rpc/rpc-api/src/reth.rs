@@ -1,7 +1,9 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumHash};
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use std::collections::HashMap;
+use reth_transaction_pool::{AdmissionRecord, DiscardReason, LocalTransaction};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
@@ -13,4 +15,224 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the recorded transaction pool admission decisions for the transaction, oldest
+    /// first.
+    ///
+    /// This is empty if the transaction pool admission log is disabled.
+    #[method(name = "getTxAdmissionHistory")]
+    async fn reth_get_tx_admission_history(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Vec<AdmissionRecord>>;
+
+    /// Returns the status of a transaction that was submitted to this node.
+    ///
+    /// Returns `None` if the transaction was not submitted to this node or is no longer tracked.
+    #[method(name = "getLocalTransactionStatus")]
+    async fn reth_get_local_transaction_status(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<LocalTransaction>>;
+
+    /// Returns the status of the most recent transactions that were submitted to this node.
+    #[method(name = "getLocalTransactions")]
+    async fn reth_get_local_transactions(&self) -> RpcResult<Vec<LocalTransaction>>;
+
+    /// Returns the pending and queued transactions of the sender, the gaps between their nonces
+    /// and what keeps the queued transactions from being promoted to pending.
+    ///
+    /// This helps to debug transactions that are stuck in the pool.
+    #[method(name = "getSenderPoolStatus")]
+    async fn reth_get_sender_pool_status(&self, sender: Address) -> RpcResult<SenderPoolStatus>;
+
+    /// Creates a subscription that sends the state changes of every canonical chain update.
+    ///
+    /// A reorg is sent as the diff of the reverted blocks, followed by the diff of the new blocks.
+    #[subscription(
+        name = "subscribeChainNotifications",
+        unsubscribe = "unsubscribeChainNotifications",
+        item = ChainStateDiff
+    )]
+    async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Creates a subscription that sends the lifecycle events of all transactions in the pool,
+    /// including why transactions left the pool.
+    #[subscription(
+        name = "subscribePoolEvents",
+        unsubscribe = "unsubscribePoolEvents",
+        item = PoolEvent
+    )]
+    async fn reth_subscribe_pool_events(&self) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// An event in the lifecycle of a transaction in the pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum PoolEvent {
+    /// The transaction was added to or moved to the pending sub-pool.
+    #[serde(rename_all = "camelCase")]
+    Pending {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+    },
+    /// The transaction was added to or moved to one of the queued sub-pools.
+    #[serde(rename_all = "camelCase")]
+    Queued {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+    },
+    /// The transaction was replaced by another transaction of the sender with the same nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// The hash of the replacement transaction.
+        replaced_by: TxHash,
+    },
+    /// The transaction was dropped from the pool.
+    #[serde(rename_all = "camelCase")]
+    Discarded {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was dropped.
+        reason: DiscardReason,
+    },
+    /// The transaction became invalid.
+    #[serde(rename_all = "camelCase")]
+    Invalid {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+    },
+    /// The transaction was included in a canonical block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// The hash of the block.
+        block_hash: B256,
+        /// The number of the block, if it could be looked up.
+        #[serde(default, with = "alloy_serde::quantity::opt")]
+        block_number: Option<u64>,
+        /// The index of the transaction in the block, if it could be looked up.
+        #[serde(default, with = "alloy_serde::quantity::opt")]
+        transaction_index: Option<u64>,
+    },
+}
+
+/// The transactions of a sender in the pool, see `reth_getSenderPoolStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderPoolStatus {
+    /// The sender of the transactions.
+    pub sender: Address,
+    /// The nonce of the sender in the latest state.
+    #[serde(with = "alloy_serde::quantity")]
+    pub state_nonce: u64,
+    /// The balance of the sender in the latest state.
+    pub balance: U256,
+    /// The transactions of the sender that are ready to be included, ordered by nonce.
+    pub pending: Vec<SenderPoolTransaction>,
+    /// The transactions of the sender that can't be included yet, ordered by nonce.
+    pub queued: Vec<SenderPoolTransaction>,
+    /// The missing nonces between the state nonce and the highest nonce of the sender's
+    /// transactions.
+    pub nonce_gaps: Vec<NonceGap>,
+}
+
+/// A transaction of a [`SenderPoolStatus`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderPoolTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Why the transaction can't be promoted to pending, empty for pending transactions.
+    pub blocked_by: Vec<PromotionBlocker>,
+}
+
+/// A range of nonces without a transaction in the pool, both ends inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceGap {
+    /// The first missing nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from: u64,
+    /// The last missing nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to: u64,
+}
+
+/// A requirement that keeps a queued transaction from being promoted to pending.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum PromotionBlocker {
+    /// A transaction with a lower nonce of the sender is missing.
+    NonceGap,
+    /// The balance doesn't cover the cost of the transaction and the sender's transactions with
+    /// lower nonces.
+    #[serde(rename_all = "camelCase")]
+    InsufficientBalance {
+        /// The cumulative cost of the transactions up to and including this one.
+        required: U256,
+        /// The balance of the sender.
+        balance: U256,
+    },
+    /// The max fee per gas is below the base fee of the next block.
+    #[serde(rename_all = "camelCase")]
+    FeeCapBelowBaseFee {
+        /// The max fee per gas of the transaction.
+        #[serde(with = "alloy_serde::quantity")]
+        max_fee_per_gas: u128,
+        /// The base fee of the next block.
+        #[serde(with = "alloy_serde::quantity")]
+        base_fee: u64,
+    },
+    /// The max fee per blob gas is below the blob fee of the next block.
+    #[serde(rename_all = "camelCase")]
+    BlobFeeCapBelowBlobFee {
+        /// The max fee per blob gas of the transaction.
+        #[serde(with = "alloy_serde::quantity")]
+        max_fee_per_blob_gas: u128,
+        /// The blob fee of the next block.
+        #[serde(with = "alloy_serde::quantity")]
+        blob_fee: u128,
+    },
+}
+
+/// The state changes of a range of blocks that were added to or removed from the canonical chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStateDiff {
+    /// The first block of the range.
+    pub first_block: BlockNumHash,
+    /// The last block of the range.
+    pub last_block: BlockNumHash,
+    /// Whether the blocks were removed from the canonical chain.
+    ///
+    /// If set, the diff restores the state before the first block of the range.
+    pub reverted: bool,
+    /// The changed accounts, `None` if the account does not exist anymore.
+    ///
+    /// The storage of accounts that do not exist anymore is cleared.
+    pub accounts: BTreeMap<Address, Option<AccountStateDiff>>,
+    /// The changed storage slots by account.
+    pub storage: BTreeMap<Address, BTreeMap<B256, U256>>,
+    /// The bytecode of contracts deployed in the range, by code hash.
+    pub code: BTreeMap<B256, Bytes>,
+}
+
+/// The state of an account after a [`ChainStateDiff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateDiff {
+    /// The account balance.
+    pub balance: U256,
+    /// The account nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The hash of the account bytecode.
+    pub code_hash: B256,
 }
@@ -0,0 +1,27 @@
+//! API for stateless block validation.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_engine::{ExecutionPayloadV3, PayloadStatus};
+use jsonrpsee::proc_macros::rpc;
+
+/// Stateless validation rpc interface.
+///
+/// This is a reth extension of the engine API that is served on the authenticated engine
+/// endpoint, it is not part of the engine API specification.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait StatelessValidationApi {
+    /// Validates the payload statelessly against the given execution witness, like
+    /// `engine_newPayloadV3` without reading the state of the parent block from the database.
+    ///
+    /// The payload is not inserted into the chain.
+    #[method(name = "newPayloadWithWitnessV3")]
+    async fn new_payload_with_witness_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        witness: ExecutionWitness,
+    ) -> jsonrpsee::core::RpcResult<PayloadStatus>;
+}
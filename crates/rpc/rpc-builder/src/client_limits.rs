@@ -0,0 +1,405 @@
+//! Per-client rate limiting of the HTTP and WS RPC servers.
+//!
+//! Every client has a token bucket of credits, see [`RpcClientLimitsConfig`]. Calls that cost more
+//! credits than the bucket of their client holds are rejected, and clients that keep sending
+//! calls while they are limited are banned temporarily.
+
+use http::HeaderName;
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, ConnectionId, HttpRequest, HttpResponse},
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+pub use reth_rpc_server_types::RpcClientLimitsConfig;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::debug;
+
+use crate::{socket::PeerAddr, tenancy::TenantFuture};
+
+/// Error code of calls that exceed the rate limit of their client.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The number of tracked clients above which idle clients are forgotten.
+const MAX_IDLE_CLIENTS: usize = 10_000;
+
+/// A client of the RPC server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcClient {
+    /// A client identified by its IP, or the IP set by a reverse proxy.
+    Ip(IpAddr),
+    /// A client identified by its connection, if its IP is unknown.
+    Connection(usize),
+}
+
+/// The IP of the client of an HTTP request, inserted into its extensions by the
+/// [`RpcClientIpLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClientIp(IpAddr);
+
+/// The rate limit state of a client.
+#[derive(Debug)]
+struct ClientState {
+    /// The credits of the client at `updated_at`.
+    credits: f64,
+    updated_at: Instant,
+    /// The number of consecutive rejected calls.
+    rejected: u32,
+    banned_until: Option<Instant>,
+}
+
+impl ClientState {
+    /// Returns the credits of the client at `now`.
+    fn refilled_credits(&self, config: &RpcClientLimitsConfig, now: Instant) -> f64 {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        let credits = elapsed.mul_add(config.credits_per_second.get() as f64, self.credits);
+        credits.min(config.burst.get() as f64)
+    }
+}
+
+/// The rate limits of the clients of the RPC server.
+///
+/// The limits are enforced by the [`RpcClientLimitLayer`]. Clients are identified by their IP, so
+/// the HTTP middleware returned by [`RpcClientLimits::http_layer`] must be installed as well.
+#[derive(Debug, Clone)]
+pub struct RpcClientLimits {
+    inner: Arc<RpcClientLimitsInner>,
+}
+
+#[derive(Debug)]
+struct RpcClientLimitsInner {
+    config: RpcClientLimitsConfig,
+    clients: Mutex<HashMap<RpcClient, ClientState>>,
+    metrics: RpcClientLimitMetrics,
+}
+
+impl RpcClientLimits {
+    /// Creates the rate limits of the given configuration.
+    pub fn new(config: RpcClientLimitsConfig) -> Self {
+        Self {
+            inner: Arc::new(RpcClientLimitsInner {
+                config,
+                clients: Default::default(),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the configuration of the limits.
+    pub fn config(&self) -> &RpcClientLimitsConfig {
+        &self.inner.config
+    }
+
+    /// Returns the HTTP middleware that reads the IP of the client from its [`PeerAddr`], or from
+    /// the configured header, if any.
+    pub fn http_layer(&self) -> RpcClientIpLayer {
+        let header = self.inner.config.client_ip_header.as_deref().and_then(|header| {
+            HeaderName::try_from(header)
+                .inspect_err(|err| {
+                    debug!(target: "rpc::client_limits", %header, %err, "Invalid client IP header");
+                })
+                .ok()
+        });
+        RpcClientIpLayer { header }
+    }
+
+    /// Returns `true` if the client is currently banned.
+    pub fn is_banned(&self, client: &RpcClient) -> bool {
+        let now = Instant::now();
+        self.inner
+            .clients
+            .lock()
+            .get(client)
+            .and_then(|state| state.banned_until)
+            .is_some_and(|until| until > now)
+    }
+
+    /// Checks whether the client may call the method and takes the credits of the call.
+    fn admit(
+        &self,
+        client: RpcClient,
+        method: &str,
+        now: Instant,
+    ) -> Result<(), ErrorObject<'static>> {
+        let config = &self.inner.config;
+        let burst = config.burst.get() as f64;
+        let mut clients = self.inner.clients.lock();
+        if clients.len() >= MAX_IDLE_CLIENTS {
+            // forget clients that are neither limited nor banned, they start with a full bucket
+            clients.retain(|_, state| {
+                state.banned_until.is_some_and(|until| until > now) ||
+                    state.refilled_credits(config, now) < burst
+            });
+        }
+        let state = clients.entry(client).or_insert_with(|| ClientState {
+            credits: burst,
+            updated_at: now,
+            rejected: 0,
+            banned_until: None,
+        });
+
+        if let Some(until) = state.banned_until {
+            if until > now {
+                self.inner.metrics.rejected_calls.increment(1);
+                return Err(ErrorObject::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    "client is temporarily banned",
+                    None::<()>,
+                ))
+            }
+            state.banned_until = None;
+            self.inner.metrics.banned_clients.decrement(1);
+        }
+
+        state.credits = state.refilled_credits(config, now);
+        state.updated_at = now;
+
+        let cost = config.cost(method) as f64;
+        if state.credits >= cost {
+            state.credits -= cost;
+            state.rejected = 0;
+            return Ok(())
+        }
+
+        self.inner.metrics.rejected_calls.increment(1);
+        state.rejected += 1;
+        if config.ban_threshold > 0 && state.rejected >= config.ban_threshold {
+            state.rejected = 0;
+            state.banned_until = Some(now + Duration::from_secs(config.ban_duration_secs));
+            self.inner.metrics.bans.increment(1);
+            self.inner.metrics.banned_clients.increment(1);
+            debug!(target: "rpc::client_limits", ?client, "Banned client");
+        }
+        Err(ErrorObject::owned(LIMIT_EXCEEDED_CODE, "rate limit exceeded", None::<()>))
+    }
+}
+
+/// HTTP middleware that reads the IP of the client from its [`PeerAddr`].
+///
+/// If a header is configured, the IP is read from the last address of the header instead. The
+/// last address is the one appended by the reverse proxy in front of the server, the addresses
+/// before it are set by the client and can't be trusted.
+#[derive(Debug, Clone)]
+pub struct RpcClientIpLayer {
+    header: Option<HeaderName>,
+}
+
+impl<S> Layer<S> for RpcClientIpLayer {
+    type Service = RpcClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientIpService { header: self.header.clone(), inner }
+    }
+}
+
+/// The service of the [`RpcClientIpLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcClientIpService<S> {
+    header: Option<HeaderName>,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcClientIpService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        let ip = self
+            .header
+            .as_ref()
+            .and_then(|header| req.headers().get(header))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse().ok())
+            .or_else(|| req.extensions().get::<PeerAddr>().map(|addr| addr.0.ip()));
+        if let Some(ip) = ip {
+            req.extensions_mut().insert(ClientIp(ip));
+        }
+        self.inner.call(req)
+    }
+}
+
+/// RPC middleware that enforces the [`RpcClientLimits`].
+///
+/// This is a no-op if no limits are configured.
+#[derive(Debug, Clone, Default)]
+pub struct RpcClientLimitLayer {
+    limits: Option<RpcClientLimits>,
+}
+
+impl RpcClientLimitLayer {
+    /// Creates a new layer that enforces the given limits, if any.
+    pub const fn new(limits: Option<RpcClientLimits>) -> Self {
+        Self { limits }
+    }
+}
+
+impl<S> Layer<S> for RpcClientLimitLayer {
+    type Service = RpcClientLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientLimitService { limits: self.limits.clone(), inner }
+    }
+}
+
+/// The service of the [`RpcClientLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcClientLimitService<S> {
+    limits: Option<RpcClientLimits>,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcClientLimitService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = TenantFuture<S::Future, MethodResponse>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(limits) = &self.limits else {
+            return TenantFuture::Future { future: self.inner.call(req) }
+        };
+
+        let client =
+            req.extensions().get::<ClientIp>().map(|ip| RpcClient::Ip(ip.0)).or_else(|| {
+                req.extensions().get::<ConnectionId>().map(|id| RpcClient::Connection(id.0))
+            });
+        let res = match client {
+            Some(client) => limits.admit(client, req.method_name(), Instant::now()),
+            None => Ok(()),
+        };
+        match res {
+            Ok(()) => TenantFuture::Future { future: self.inner.call(req) },
+            Err(err) => TenantFuture::Ready {
+                response: Some(MethodResponse::error(req.id().into_owned(), err)),
+            },
+        }
+    }
+}
+
+/// Metrics of the per-client rate limits
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.client_limits")]
+struct RpcClientLimitMetrics {
+    /// The number of calls rejected because of the rate limit or a ban of their client
+    rejected_calls: Counter,
+    /// The number of bans of abusive clients
+    bans: Counter,
+    /// The number of currently banned clients
+    banned_clients: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::server::HttpBody;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn token_bucket_and_bans() {
+        let limits = RpcClientLimits::new(RpcClientLimitsConfig {
+            credits_per_second: NonZeroU32::new(10).unwrap(),
+            burst: NonZeroU32::new(20).unwrap(),
+            ban_threshold: 3,
+            ban_duration_secs: 60,
+            ..Default::default()
+        });
+        let client = RpcClient::Connection(1);
+        let now = Instant::now();
+
+        // a trace call costs as much as 20 cheap calls
+        assert!(limits.admit(client, "trace_block", now).is_ok());
+        assert!(limits.admit(client, "eth_chainId", now).is_err());
+        // other clients have their own bucket
+        assert!(limits.admit(RpcClient::Connection(2), "eth_chainId", now).is_ok());
+
+        // the bucket refills with 10 credits per second
+        let later = now + Duration::from_millis(500);
+        for _ in 0..5 {
+            assert!(limits.admit(client, "eth_chainId", later).is_ok());
+        }
+        assert!(limits.admit(client, "eth_chainId", later).is_err());
+        assert!(limits.admit(client, "eth_chainId", later).is_err());
+        assert!(!limits.is_banned(&client));
+
+        // the third consecutive rejection bans the client, even after its bucket refilled
+        let err = limits.admit(client, "eth_chainId", later).unwrap_err();
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+        let refilled = later + Duration::from_secs(10);
+        assert_eq!(
+            limits.admit(client, "eth_chainId", refilled).unwrap_err().message(),
+            "client is temporarily banned"
+        );
+        assert!(limits.admit(client, "eth_chainId", later + Duration::from_secs(61)).is_ok());
+    }
+
+    /// Returns the [`ClientIp`] the layer of the limits inserts into the request.
+    async fn client_ip(limits: &RpcClientLimits, req: HttpRequest) -> Option<ClientIp> {
+        let mut service =
+            limits.http_layer().layer(tower::service_fn(|req: HttpRequest| async move {
+                let ip = req.extensions().get::<ClientIp>().copied();
+                let mut res = HttpResponse::new(HttpBody::empty());
+                res.extensions_mut().insert(ip);
+                Ok::<_, std::convert::Infallible>(res)
+            }));
+        service.call(req).await.unwrap().extensions_mut().remove::<Option<ClientIp>>().unwrap()
+    }
+
+    #[tokio::test]
+    async fn client_ip_from_peer_addr() {
+        let limits = RpcClientLimits::new(RpcClientLimitsConfig::default());
+        let peer = PeerAddr("10.0.0.1:54321".parse().unwrap());
+
+        let mut req = http::Request::builder()
+            .header("x-forwarded-for", "192.168.0.1")
+            .body(HttpBody::empty())
+            .unwrap();
+        req.extensions_mut().insert(peer);
+        // the header is ignored unless it's configured
+        assert_eq!(client_ip(&limits, req).await, Some(ClientIp("10.0.0.1".parse().unwrap())));
+
+        let req = http::Request::builder().body(HttpBody::empty()).unwrap();
+        assert_eq!(client_ip(&limits, req).await, None);
+    }
+
+    #[tokio::test]
+    async fn client_ip_from_header() {
+        let limits = RpcClientLimits::new(RpcClientLimitsConfig {
+            client_ip_header: Some("x-forwarded-for".to_string()),
+            ..Default::default()
+        });
+        let peer = PeerAddr("10.0.0.1:54321".parse().unwrap());
+
+        // the first address is set by the client, only the one appended by the proxy is used
+        let mut req = http::Request::builder()
+            .header("x-forwarded-for", "1.2.3.4, 192.168.0.1")
+            .body(HttpBody::empty())
+            .unwrap();
+        req.extensions_mut().insert(peer);
+        assert_eq!(client_ip(&limits, req).await, Some(ClientIp("192.168.0.1".parse().unwrap())));
+
+        // requests that didn't pass through the proxy are identified by their peer address
+        let mut req = http::Request::builder().body(HttpBody::empty()).unwrap();
+        req.extensions_mut().insert(peer);
+        assert_eq!(client_ip(&limits, req).await, Some(ClientIp("10.0.0.1".parse().unwrap())));
+    }
+}
@@ -10,8 +10,8 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, socket::inherited_tcp_listeners, IpcServerBuilder,
+    RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -114,6 +114,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
+            max_block_receipts: self.rpc_state_cache.max_block_receipts,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
         }
     }
@@ -151,7 +152,11 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
 
         if self.is_ipc_enabled() {
-            config = config.with_ipc(RpcModuleSelection::default_ipc_modules());
+            config = config.with_ipc(
+                self.ipc_api
+                    .clone()
+                    .unwrap_or_else(|| RpcModuleSelection::default_ipc_modules().into()),
+            );
         }
 
         config
@@ -174,7 +179,27 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_reuse_port(self.rpc_reuse_port);
+
+        if let Some(method_limits) = &self.rpc_method_limits {
+            config = config.with_method_limits(method_limits.clone());
+        }
+
+        if self.rpc_socket_activation {
+            match inherited_tcp_listeners() {
+                Ok(listeners) => {
+                    if listeners.is_empty() {
+                        warn!(target: "reth::cli", "No listeners passed by systemd socket activation");
+                    }
+                    config = config.with_inherited_listeners(listeners);
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, "Failed to take listeners passed by systemd socket activation");
+                }
+            }
+        }
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -0,0 +1,109 @@
+//! [`jsonrpsee`] middleware that profiles single calls of a method.
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use reth_profiling::{cpu_profiler, CallProfileGuard};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tower::Layer;
+
+/// RPC middleware that profiles the next call of a method requested via
+/// [`CpuProfiler::profile_next_call`](reth_profiling::CpuProfiler::profile_next_call).
+///
+/// The profile is written into the output directory of the profiler once the call completes, or
+/// once its response future is dropped if the call is cancelled.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct RpcCpuProfileLayer;
+
+impl<S> Layer<S> for RpcCpuProfileLayer {
+    type Service = RpcCpuProfileService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCpuProfileService { inner }
+    }
+}
+
+/// The service of the [`RpcCpuProfileLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcCpuProfileService<S> {
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcCpuProfileService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = CpuProfileFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let profile = cpu_profiler().start_for_call(req.method_name());
+        CpuProfileFuture { fut: self.inner.call(req), profile }
+    }
+}
+
+/// Response future of the [`RpcCpuProfileService`].
+#[pin_project::pin_project]
+pub struct CpuProfileFuture<F> {
+    #[pin]
+    fut: F,
+    /// Stops the profiler when the call completes or the future is dropped, if the call is
+    /// profiled.
+    profile: Option<CallProfileGuard<'static>>,
+}
+
+impl<F> std::fmt::Debug for CpuProfileFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CpuProfileFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for CpuProfileFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = ready!(this.fut.poll(cx));
+        this.profile.take();
+        Poll::Ready(res)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+    use jsonrpsee::types::Id;
+    use reth_profiling::DEFAULT_PROFILING_FREQUENCY;
+
+    /// A service whose calls never complete.
+    #[derive(Clone)]
+    struct PendingService;
+
+    impl<'a> RpcServiceT<'a> for PendingService {
+        type Future = std::future::Pending<MethodResponse>;
+
+        fn call(&self, _req: Request<'a>) -> Self::Future {
+            std::future::pending()
+        }
+    }
+
+    #[test]
+    fn profiler_stops_when_call_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let profiler = cpu_profiler();
+        profiler.set_output_dir(dir.path());
+        profiler.profile_next_call("eth_call".to_string(), DEFAULT_PROFILING_FREQUENCY).unwrap();
+
+        let service = RpcCpuProfileLayer.layer(PendingService);
+        let fut = service.call(Request::new("eth_call".into(), None, Id::Number(1)));
+        assert!(profiler.is_running());
+
+        // the call is cancelled before it completes
+        assert!(fut.now_or_never().is_none());
+        assert!(!profiler.is_running());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -1,12 +1,13 @@
 use alloy_consensus::Header;
 use reth_evm::ConfigureEvm;
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
-use reth_rpc::{EthFilter, EthPubSub};
+use reth_rpc::{eth::LogIndex, EthFilter, EthPubSub};
 use reth_rpc_eth_api::EthApiTypes;
 use reth_rpc_eth_types::{
     cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache,
 };
 use reth_tasks::TaskSpawner;
+use std::sync::Arc;
 
 /// Alias for `eth` namespace API builder.
 pub type DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi> =
@@ -35,7 +36,8 @@ where
 {
     /// Returns a new instance with handlers for `eth` namespace.
     ///
-    /// This will spawn all necessary tasks for the handlers.
+    /// This will spawn all necessary tasks for the handlers. If a [`LogIndex`] is given, the
+    /// filter handler looks up the blocks with matching logs in it.
     #[allow(clippy::too_many_arguments)]
     pub fn bootstrap<EvmConfig, Tasks>(
         provider: Provider,
@@ -54,6 +56,7 @@ where
             Events,
             EthApi,
         >,
+        log_index: Option<Arc<dyn LogIndex>>,
     ) -> Self
     where
         EvmConfig: ConfigureEvm<Header = Header>,
@@ -88,14 +91,25 @@ where
 
         let api = eth_api_builder(&ctx);
 
-        let filter = EthFilter::new(
-            ctx.provider.clone(),
-            ctx.pool.clone(),
-            ctx.cache.clone(),
-            ctx.config.filter_config(),
-            Box::new(ctx.executor.clone()),
-            api.tx_resp_builder().clone(),
-        );
+        let filter = match log_index {
+            Some(log_index) => EthFilter::with_log_index(
+                ctx.provider.clone(),
+                ctx.pool.clone(),
+                ctx.cache.clone(),
+                ctx.config.filter_config(),
+                Box::new(ctx.executor.clone()),
+                api.tx_resp_builder().clone(),
+                log_index,
+            ),
+            None => EthFilter::new(
+                ctx.provider.clone(),
+                ctx.pool.clone(),
+                ctx.cache.clone(),
+                ctx.config.filter_config(),
+                Box::new(ctx.executor.clone()),
+                api.tx_resp_builder().clone(),
+            ),
+        };
 
         let pubsub = EthPubSub::with_spawner(
             ctx.provider.clone(),
@@ -161,7 +161,7 @@
 use std::{
     collections::HashMap,
     fmt::Debug,
-    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener as StdTcpListener},
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -189,8 +189,10 @@ use reth_provider::{
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
+    eth::{sim_bundle::EthSimBundle, EthPrivateTransactions, LogIndex},
+    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi,
+    StatelessValidationApi, TraceApi, TraceIndex, TxPoolApi, UserOperationApi, ValidationApi,
+    ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -200,7 +202,7 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
+use reth_transaction_pool::{alt::UserOperationPool, noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
 use tower::Layer;
 use tower_http::cors::CorsLayer;
@@ -212,7 +214,7 @@ pub use jsonrpsee::server::ServerBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{constants, RethRpcModule, RpcMethodLimits, RpcModuleSelection};
 pub use tower::layer::util::{Identity, Stack};
 
 /// Auth server utilities.
@@ -233,11 +235,30 @@ pub use eth::EthHandlers;
 
 // Rpc server metrics
 mod metrics;
+use client_limits::{RpcClientLimitLayer, RpcClientLimitService, RpcClientLimits};
+use cpu_profile::{RpcCpuProfileLayer, RpcCpuProfileService};
+use method_limits::{RpcMethodLimitLayer, RpcMethodLimitService};
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
+use tenancy::{RpcTenantLayer, RpcTenantService, RpcTenants};
+
+/// Per-client rate limits for the RPC server.
+pub mod client_limits;
+
+/// Per-method concurrency limits for the RPC server.
+pub mod method_limits;
+
+/// CPU profiles of single calls for the RPC server.
+pub mod cpu_profile;
 
 // Rpc rate limiter
 pub mod rate_limiter;
 
+/// Listener socket utilities.
+pub mod socket;
+
+/// Multi-tenancy for the RPC server.
+pub mod tenancy;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, BlockExecutor>(
@@ -312,6 +333,10 @@ pub struct RpcModuleBuilder<
     block_executor: BlockExecutor,
     /// The consensus implementation.
     consensus: Consensus,
+    /// The index used to serve log filters, if any.
+    log_index: Option<Arc<dyn LogIndex>>,
+    /// The index used to serve `trace_filter`, if any.
+    trace_index: Option<Arc<dyn TraceIndex>>,
 }
 
 // === impl RpcBuilder ===
@@ -331,7 +356,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         block_executor: BlockExecutor,
         consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config, block_executor, consensus }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index: None,
+            trace_index: None,
+        }
     }
 
     /// Configure the provider instance.
@@ -342,8 +378,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -353,6 +399,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -365,7 +413,16 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         P: TransactionPool + 'static,
     {
         let Self {
-            provider, network, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -376,6 +433,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -397,7 +456,16 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, executor, events, network, evm_config, block_executor, consensus, ..
+            provider,
+            executor,
+            events,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -408,6 +476,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             block_executor,
             pool: NoopTransactionPool::default(),
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -420,7 +490,16 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         N: NetworkInfo + Peers + 'static,
     {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -431,6 +510,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -452,7 +533,16 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -463,6 +553,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -474,8 +566,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -485,6 +587,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -504,8 +608,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         BlockExecutor,
         Consensus,
     > {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -515,6 +629,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -527,7 +643,16 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         E: CanonStateSubscriptions + 'static,
     {
         let Self {
-            provider, pool, executor, network, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -538,6 +663,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -549,8 +676,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, block_executor, consensus, .. } =
-            self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            events,
+            block_executor,
+            consensus,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -560,6 +697,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -571,7 +710,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         BE: BlockExecutorProvider,
     {
-        let Self { provider, network, pool, executor, events, evm_config, consensus, .. } = self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            consensus,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -581,6 +731,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
 
@@ -589,8 +741,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         self,
         consensus: C,
     ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, C> {
-        let Self { provider, network, pool, executor, events, evm_config, block_executor, .. } =
-            self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            log_index,
+            trace_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -600,8 +762,30 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         }
     }
+
+    /// Configure the [`LogIndex`] that `eth_getLogs` and log filters look up the blocks with
+    /// matching logs in.
+    ///
+    /// The index is expected to be kept up to date, e.g. by a
+    /// [`BlockIndexTask`](reth_rpc::BlockIndexTask).
+    pub fn with_log_index(mut self, log_index: Arc<dyn LogIndex>) -> Self {
+        self.log_index = Some(log_index);
+        self
+    }
+
+    /// Configure the [`TraceIndex`] that `trace_filter` looks up the blocks with matching traces
+    /// in.
+    ///
+    /// The index is expected to be kept up to date, e.g. by a
+    /// [`BlockIndexTask`](reth_rpc::BlockIndexTask).
+    pub fn with_trace_index(mut self, trace_index: Arc<dyn TraceIndex>) -> Self {
+        self.trace_index = Some(trace_index);
+        self
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
@@ -647,6 +831,8 @@ where
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
@@ -662,6 +848,8 @@ where
             evm_config,
             eth,
             block_executor,
+            log_index,
+            trace_index,
         );
 
         let modules = registry.create_transport_rpc_modules(module_config);
@@ -722,6 +910,8 @@ where
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         } = self;
         RpcRegistryInner::new(
             provider,
@@ -734,6 +924,8 @@ where
             evm_config,
             eth,
             block_executor,
+            log_index,
+            trace_index,
         )
     }
 
@@ -758,6 +950,8 @@ where
             evm_config,
             block_executor,
             consensus,
+            log_index,
+            trace_index,
         } = self;
 
         if !module_config.is_empty() {
@@ -774,6 +968,8 @@ where
                 evm_config,
                 eth,
                 block_executor,
+                log_index,
+                trace_index,
             );
 
             modules.config = module_config;
@@ -894,6 +1090,8 @@ pub struct RpcRegistryInner<
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// The index used to serve `trace_filter`, if any.
+    trace_index: Option<Arc<dyn TraceIndex>>,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -932,6 +1130,8 @@ where
             EthApi,
         >,
         block_executor: BlockExecutor,
+        log_index: Option<Arc<dyn LogIndex>>,
+        trace_index: Option<Arc<dyn TraceIndex>>,
     ) -> Self
     where
         EvmConfig: ConfigureEvm<Header = Header>,
@@ -947,6 +1147,7 @@ where
             executor.clone(),
             events.clone(),
             eth_api_builder,
+            log_index,
         );
 
         Self {
@@ -959,6 +1160,7 @@ where
             config,
             modules: Default::default(),
             blocking_pool_guard,
+            trace_index,
             events,
             block_executor,
         }
@@ -1032,11 +1234,12 @@ where
     BlockExecutor: BlockExecutorProvider,
 {
     /// Instantiates `AdminApi`
-    pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec>
+    pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec, Pool>
     where
         Network: Peers,
+        Pool: Clone,
     {
-        AdminApi::new(self.network.clone(), self.provider.chain_spec())
+        AdminApi::new(self.network.clone(), self.provider.chain_spec(), self.pool.clone())
     }
 
     /// Instantiates `Web3Api`
@@ -1048,6 +1251,7 @@ where
     pub fn register_admin(&mut self) -> &mut Self
     where
         Network: Peers,
+        Pool: TransactionPool + 'static,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -1151,7 +1355,11 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Pool: TransactionPool + 'static,
+        Events: CanonStateSubscriptions + Clone + 'static,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
@@ -1186,11 +1394,12 @@ where
     where
         EthApi: TraceExt,
     {
-        TraceApi::new(
-            self.provider.clone(),
-            self.eth_api().clone(),
-            self.blocking_pool_guard.clone(),
-        )
+        let (provider, eth_api, guard) =
+            (self.provider.clone(), self.eth_api().clone(), self.blocking_pool_guard.clone());
+        match self.trace_index.clone() {
+            Some(trace_index) => TraceApi::with_trace_index(provider, eth_api, guard, trace_index),
+            None => TraceApi::new(provider, eth_api, guard),
+        }
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -1238,8 +1447,17 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&self) -> RethApi<Provider, Pool, Events>
+    where
+        Pool: TransactionPool + 'static,
+        Events: Clone,
+    {
+        RethApi::new(
+            self.provider.clone(),
+            self.pool.clone(),
+            self.events.clone(),
+            Box::new(self.executor.clone()),
+        )
     }
 
     /// Instantiates `ValidationApi`
@@ -1254,6 +1472,18 @@ where
             self.config.flashbots.clone(),
         )
     }
+
+    /// Instantiates `StatelessValidationApi`
+    pub fn stateless_validation_api(&self) -> StatelessValidationApi<Provider, BlockExecutor>
+    where
+        Consensus: reth_consensus::Consensus + Clone + 'static,
+    {
+        StatelessValidationApi::new(
+            self.provider.clone(),
+            Arc::new(self.consensus.clone()),
+            self.block_executor.clone(),
+        )
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
@@ -1350,11 +1580,13 @@ where
                 self.modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone(), self.provider.chain_spec())
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Admin => AdminApi::new(
+                            self.network.clone(),
+                            self.provider.chain_spec(),
+                            self.pool.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
@@ -1383,11 +1615,19 @@ where
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => TraceApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            self.blocking_pool_guard.clone(),
-                        )
+                        RethRpcModule::Trace => match self.trace_index.clone() {
+                            Some(trace_index) => TraceApi::with_trace_index(
+                                self.provider.clone(),
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                                trace_index,
+                            ),
+                            None => TraceApi::new(
+                                self.provider.clone(),
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            ),
+                        }
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
@@ -1406,11 +1646,14 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            self.pool.clone(),
+                            self.events.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Flashbots => ValidationApi::new(
                             self.provider.clone(),
                             Arc::new(self.consensus.clone()),
@@ -1419,6 +1662,26 @@ where
                         )
                         .into_rpc()
                         .into(),
+                        // bundles can only be submitted if the node provides the bundle pool of
+                        // its payload builder, see `MevBundleApi`
+                        RethRpcModule::Mev => {
+                            let mut module = EthSimBundle::new(
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(EthPrivateTransactions::new(self.pool.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
+                        }
+                        // user operations are only bundled if the node shares the pool with its
+                        // bundler, see `UserOperationApi`
+                        RethRpcModule::Bundler => {
+                            UserOperationApi::new(eth_api.clone(), UserOperationPool::default())
+                                .into_rpc()
+                                .into()
+                        }
                     })
                     .clone()
             })
@@ -1457,8 +1720,18 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Whether http and ws listeners are bound with `SO_REUSEPORT`
+    reuse_port: bool,
+    /// Listeners inherited from the parent process, used instead of binding new ones
+    inherited_listeners: Vec<StdTcpListener>,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
+    /// API key tenants of the http and ws servers, if multi-tenancy is enabled
+    tenants: Option<RpcTenants>,
+    /// Concurrency limits of methods called via the http and ws servers
+    method_limits: RpcMethodLimits,
+    /// Rate limits of the clients of the http and ws servers, if enabled
+    client_limits: Option<RpcClientLimits>,
 }
 
 // === impl RpcServerConfig ===
@@ -1476,7 +1749,12 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            reuse_port: false,
+            inherited_listeners: Vec::new(),
             rpc_middleware: RpcServiceBuilder::new(),
+            tenants: None,
+            method_limits: RpcMethodLimits::default(),
+            client_limits: None,
         }
     }
 }
@@ -1539,10 +1817,41 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            reuse_port: self.reuse_port,
+            inherited_listeners: self.inherited_listeners,
             rpc_middleware,
+            tenants: self.tenants,
+            method_limits: self.method_limits,
+            client_limits: self.client_limits,
         }
     }
 
+    /// Enables multi-tenancy for the http and ws servers.
+    ///
+    /// Requests without a known API key are rejected, and every tenant is limited to its
+    /// configured namespaces and rate limits. See [`tenancy`] for details.
+    pub fn with_tenants(mut self, tenants: RpcTenants) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
+    /// Limits the number of concurrent calls of methods via the http and ws servers.
+    ///
+    /// Calls beyond the limit of their method are queued. See [`RpcMethodLimits`] for details.
+    pub fn with_method_limits(mut self, method_limits: RpcMethodLimits) -> Self {
+        self.method_limits = method_limits;
+        self
+    }
+
+    /// Rate limits the clients of the http and ws servers.
+    ///
+    /// Calls of clients that exceed their limits are rejected, and abusive clients are banned
+    /// temporarily. See [`client_limits`] for details.
+    pub fn with_client_limits(mut self, client_limits: RpcClientLimits) -> Self {
+        self.client_limits = Some(client_limits);
+        self
+    }
+
     /// Configure the cors domains for http _and_ ws
     pub fn with_cors(self, cors_domain: Option<String>) -> Self {
         self.with_http_cors(cors_domain.clone()).with_ws_cors(cors_domain)
@@ -1612,6 +1921,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures whether the http and ws servers bind their addresses with `SO_REUSEPORT`.
+    ///
+    /// This allows a new node instance to start listening on the same addresses before the old
+    /// instance shuts down, so that restarts don't refuse client connections.
+    pub const fn with_reuse_port(mut self, reuse_port: bool) -> Self {
+        self.reuse_port = reuse_port;
+        self
+    }
+
+    /// Configures already bound listeners, e.g. inherited via systemd socket activation, see
+    /// [`socket::inherited_tcp_listeners`].
+    ///
+    /// The http and ws servers use the listener bound to their configured address instead of
+    /// binding a new one.
+    pub fn with_inherited_listeners(mut self, listeners: Vec<StdTcpListener>) -> Self {
+        self.inherited_listeners = listeners;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1659,13 +1987,27 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
     pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
-        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
-        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
-            Send + Sync + 'static + RpcServiceT<'a>,
+        RpcMiddleware: Layer<
+                RpcRequestMetricsService<
+                    RpcClientLimitService<
+                        RpcTenantService<RpcMethodLimitService<RpcCpuProfileService<RpcService>>>,
+                    >,
+                >,
+            > + Clone
+            + Send
+            + 'static,
+        for<'a> <RpcMiddleware as Layer<
+            RpcRequestMetricsService<
+                RpcClientLimitService<
+                    RpcTenantService<RpcMethodLimitService<RpcCpuProfileService<RpcService>>>,
+                >,
+            >,
+        >>::Service: Send + Sync + 'static + RpcServiceT<'a>,
     {
         let mut http_handle = None;
         let mut ws_handle = None;
         let mut ipc_handle = None;
+        let mut listeners = self.inherited_listeners;
 
         let http_socket_addr = self.http_addr.unwrap_or(SocketAddr::V4(SocketAddrV4::new(
             Ipv4Addr::LOCALHOST,
@@ -1678,6 +2020,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         )));
 
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
+        let client_limit_layer = RpcClientLimitLayer::new(self.client_limits.clone());
+        let tenant_layer = RpcTenantLayer::new(self.tenants.is_some());
+        let method_limit_layer = RpcMethodLimitLayer::new(self.method_limits);
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
 
@@ -1712,33 +2057,50 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(builder) = self.http_server_config {
-                let server = builder
+                let service_builder = builder
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(self.tenants.as_ref().map(RpcTenants::http_layer))
+                            .option_layer(
+                                self.client_limits.as_ref().map(RpcClientLimits::http_layer),
+                            )
                             .option_layer(Self::maybe_compression_layer()),
                     )
                     .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
-                        ),
+                        self.rpc_middleware
+                            .clone()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(RpcRequestMetrics::same_port)
+                                    .unwrap_or_default(),
+                            )
+                            .layer(client_limit_layer.clone())
+                            .layer(tenant_layer.clone())
+                            .layer(method_limit_layer.clone())
+                            .layer(RpcCpuProfileLayer::default()),
                     )
-                    .build(http_socket_addr)
-                    .await
+                    .to_service_builder();
+                let listener = socket::take_or_bind_tcp_listener(
+                    &mut listeners,
+                    http_socket_addr,
+                    self.reuse_port,
+                )
+                .map_err(|err| RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr)))?;
+                let addr = listener.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
+                if let Some(module) = modules.http.clone().or_else(|| modules.ws.clone()) {
+                    let handle = socket::serve(listener, move |stop_handle| {
+                        service_builder.clone().build(module.clone(), stop_handle)
+                    })
                     .map_err(|err| {
                         RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                     })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
-                if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1760,58 +2122,91 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(builder) = self.ws_server_config {
-            let server = builder
+            let service_builder = builder
                 .ws_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(self.tenants.as_ref().map(RpcTenants::http_layer))
+                        .option_layer(self.client_limits.as_ref().map(RpcClientLimits::http_layer)),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware
                         .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(client_limit_layer.clone())
+                        .layer(tenant_layer.clone())
+                        .layer(method_limit_layer.clone())
+                        .layer(RpcCpuProfileLayer::default()),
                 )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                .to_service_builder();
+            let listener =
+                socket::take_or_bind_tcp_listener(&mut listeners, ws_socket_addr, self.reuse_port)
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
-            let addr = server
+            let addr = listener
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
             ws_local_addr = Some(addr);
-            ws_server = Some(server);
+            ws_server = Some((listener, service_builder));
         }
 
         if let Some(builder) = self.http_server_config {
-            let server = builder
+            let service_builder = builder
                 .http_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(self.tenants.as_ref().map(RpcTenants::http_layer))
+                        .option_layer(self.client_limits.as_ref().map(RpcClientLimits::http_layer))
                         .option_layer(Self::maybe_compression_layer()),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    self.rpc_middleware
+                        .clone()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(client_limit_layer)
+                        .layer(tenant_layer)
+                        .layer(method_limit_layer)
+                        .layer(RpcCpuProfileLayer::default()),
                 )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
+                .to_service_builder();
+            let listener = socket::take_or_bind_tcp_listener(
+                &mut listeners,
+                http_socket_addr,
+                self.reuse_port,
+            )
+            .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+            let local_addr = listener
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             http_local_addr = Some(local_addr);
-            http_server = Some(server);
+            http_server = Some((listener, service_builder));
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        if let Some((listener, service_builder)) = http_server {
+            let module = modules.http.clone().expect("http server error");
+            http_handle = Some(
+                socket::serve(listener, move |stop_handle| {
+                    service_builder.clone().build(module.clone(), stop_handle)
+                })
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?,
+            );
+        }
+        if let Some((listener, service_builder)) = ws_server {
+            let module = modules.ws.clone().expect("ws server error");
+            ws_handle = Some(
+                socket::serve(listener, move |stop_handle| {
+                    service_builder.clone().build(module.clone(), stop_handle)
+                })
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?,
+            );
+        }
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -2022,6 +2417,29 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replace the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    ///
+    /// Fails if any of the methods in other is present already after the removal.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -2412,6 +2830,8 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "mev" => RethRpcModule::Mev,
+                "bundler" => RethRpcModule::Bundler,
             );
     }
 
@@ -0,0 +1,204 @@
+//! [`jsonrpsee`] middleware that limits the number of concurrent calls per method.
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use reth_metrics::{
+    metrics::{Gauge, Histogram},
+    Metrics,
+};
+use reth_rpc_server_types::RpcMethodLimits;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Instant,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::PollSemaphore;
+use tower::Layer;
+
+/// RPC middleware that enforces the [`RpcMethodLimits`].
+///
+/// Calls of a limited method are queued until a permit of its limit is available. The limits are
+/// shared by all servers the layer is used for.
+#[derive(Debug, Clone, Default)]
+pub struct RpcMethodLimitLayer {
+    limits: Arc<MethodLimiters>,
+}
+
+impl RpcMethodLimitLayer {
+    /// Creates a new layer that enforces the given limits.
+    pub fn new(limits: RpcMethodLimits) -> Self {
+        let limiters = limits
+            .limits()
+            .iter()
+            .map(|limit| {
+                Arc::new(MethodLimiter {
+                    semaphore: Arc::new(Semaphore::new(limit.max_concurrent.get())),
+                    metrics: RpcMethodLimitMetrics::new_with_labels(&[(
+                        "methods",
+                        limit.pattern.clone(),
+                    )]),
+                })
+            })
+            .collect();
+        Self { limits: Arc::new(MethodLimiters { limits, limiters }) }
+    }
+}
+
+impl<S> Layer<S> for RpcMethodLimitLayer {
+    type Service = RpcMethodLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcMethodLimitService { limits: self.limits.clone(), inner }
+    }
+}
+
+/// The configured limits with their semaphores, in the same order.
+#[derive(Debug, Default)]
+struct MethodLimiters {
+    limits: RpcMethodLimits,
+    limiters: Vec<Arc<MethodLimiter>>,
+}
+
+impl MethodLimiters {
+    /// Returns the limiter of the method, if it is limited.
+    fn get(&self, method: &str) -> Option<&Arc<MethodLimiter>> {
+        self.limits.position(method).map(|idx| &self.limiters[idx])
+    }
+}
+
+/// Limits the concurrent calls of the methods matching a pattern.
+#[derive(Debug)]
+struct MethodLimiter {
+    /// Semaphore with a permit per concurrent call
+    semaphore: Arc<Semaphore>,
+    /// Queue metrics of the methods
+    metrics: RpcMethodLimitMetrics,
+}
+
+/// The service of the [`RpcMethodLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcMethodLimitService<S> {
+    limits: Arc<MethodLimiters>,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcMethodLimitService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = MethodLimitFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let queued = self.limits.get(req.method_name()).cloned().map(QueuedCall::new);
+        MethodLimitFuture { fut: self.inner.call(req), queued, active: None }
+    }
+}
+
+/// A call that waits for a permit of its method limit.
+struct QueuedCall {
+    limiter: Arc<MethodLimiter>,
+    semaphore: PollSemaphore,
+    queued_at: Instant,
+}
+
+impl QueuedCall {
+    fn new(limiter: Arc<MethodLimiter>) -> Self {
+        limiter.metrics.queued_calls.increment(1);
+        let semaphore = PollSemaphore::new(limiter.semaphore.clone());
+        Self { limiter, semaphore, queued_at: Instant::now() }
+    }
+}
+
+impl Drop for QueuedCall {
+    fn drop(&mut self) {
+        self.limiter.metrics.queued_calls.decrement(1);
+    }
+}
+
+/// A call that holds a permit of its method limit.
+struct ActiveCall {
+    limiter: Arc<MethodLimiter>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl ActiveCall {
+    fn new(limiter: Arc<MethodLimiter>, permit: Option<OwnedSemaphorePermit>) -> Self {
+        limiter.metrics.active_calls.increment(1);
+        Self { limiter, _permit: permit }
+    }
+}
+
+impl Drop for ActiveCall {
+    fn drop(&mut self) {
+        self.limiter.metrics.active_calls.decrement(1);
+    }
+}
+
+/// Response future of the [`RpcMethodLimitService`].
+#[pin_project::pin_project]
+pub struct MethodLimitFuture<F> {
+    #[pin]
+    fut: F,
+    queued: Option<QueuedCall>,
+    active: Option<ActiveCall>,
+}
+
+impl<F> std::fmt::Debug for MethodLimitFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MethodLimitFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for MethodLimitFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(queued) = this.queued.as_mut() {
+            // the semaphore is never closed, so this always yields a permit
+            let permit = ready!(queued.semaphore.poll_acquire(cx));
+            let queued = this.queued.take().expect("is set");
+            queued
+                .limiter
+                .metrics
+                .queue_time_seconds
+                .record(queued.queued_at.elapsed().as_secs_f64());
+            *this.active = Some(ActiveCall::new(queued.limiter.clone(), permit));
+        }
+        let res = this.fut.poll(cx);
+        if res.is_ready() {
+            *this.active = None;
+        }
+        res
+    }
+}
+
+/// Metrics of the calls of methods with a concurrency limit
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.method_limits")]
+struct RpcMethodLimitMetrics {
+    /// The number of calls waiting for a permit
+    queued_calls: Gauge,
+    /// The number of calls being executed
+    active_calls: Gauge,
+    /// The time calls waited for a permit
+    queue_time_seconds: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_limiter() {
+        let layer = RpcMethodLimitLayer::new("trace_*=1,trace_block=2".parse().unwrap());
+        let trace_block = layer.limits.get("trace_block").unwrap();
+        assert_eq!(trace_block.semaphore.available_permits(), 2);
+        let trace_filter = layer.limits.get("trace_filter").unwrap();
+        assert_eq!(trace_filter.semaphore.available_permits(), 1);
+        assert!(Arc::ptr_eq(trace_filter, layer.limits.get("trace_call").unwrap()));
+        assert!(layer.limits.get("eth_sendRawTransaction").is_none());
+    }
+}
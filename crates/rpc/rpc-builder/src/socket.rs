@@ -0,0 +1,243 @@
+use hyper::body::{Body, Bytes, Incoming};
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, HttpBody, HttpRequest, ServerHandle, StopHandle,
+    },
+};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::{
+    io,
+    net::{SocketAddr, TcpListener},
+};
+use tower::Service;
+use tracing::debug;
+
+/// Backlog of listeners bound by [`bind_tcp_listener`], same as tokio's default.
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Binds a non-blocking [`TcpListener`] to the given address.
+///
+/// If `reuse_port` is set, the socket is bound with `SO_REUSEPORT`, so that a new instance of the
+/// node can bind the same address while the old one is still draining its connections.
+pub fn bind_tcp_listener(addr: SocketAddr, reuse_port: bool) -> io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    #[cfg(unix)]
+    {
+        socket.set_reuse_address(true)?;
+        if reuse_port {
+            socket.set_reuse_port(true)?;
+        }
+    }
+    #[cfg(not(unix))]
+    if reuse_port {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "SO_REUSEPORT is not supported on this platform",
+        ))
+    }
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    Ok(socket.into())
+}
+
+/// Returns the TCP listeners passed to the process via systemd socket activation.
+///
+/// The listeners are identified by the `LISTEN_PID` and `LISTEN_FDS` environment variables, see
+/// `sd_listen_fds(3)`. Inherited file descriptors that are not TCP sockets are ignored.
+///
+/// Ownership of the file descriptors is only taken once, subsequent calls return no listeners.
+#[cfg(unix)]
+pub fn inherited_tcp_listeners() -> io::Result<Vec<TcpListener>> {
+    use std::{
+        os::fd::RawFd,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    /// First file descriptor passed by systemd, `SD_LISTEN_FDS_START`.
+    const LISTEN_FDS_START: RawFd = 3;
+
+    static TAKEN: AtomicBool = AtomicBool::new(false);
+
+    let listen_pid = std::env::var("LISTEN_PID").ok().and_then(|pid| pid.parse::<u32>().ok());
+    if listen_pid != Some(std::process::id()) || TAKEN.swap(true, Ordering::SeqCst) {
+        return Ok(Vec::new())
+    }
+    let count = std::env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|fds| fds.parse::<RawFd>().ok())
+        .unwrap_or_default();
+
+    // SAFETY: systemd passes `LISTEN_FDS` open file descriptors starting at `LISTEN_FDS_START`,
+    // which stay open for the lifetime of the process and are owned by nobody else, ownership is
+    // only taken once, see `TAKEN`.
+    unsafe { tcp_listeners_from_fds(LISTEN_FDS_START..LISTEN_FDS_START + count) }
+}
+
+/// Takes ownership of the file descriptors that are TCP sockets and returns them as non-blocking
+/// listeners. Other file descriptors are skipped.
+///
+/// # Safety
+///
+/// The file descriptors must be open, and the TCP sockets among them must not be owned by anything
+/// else.
+#[cfg(unix)]
+unsafe fn tcp_listeners_from_fds(
+    fds: impl IntoIterator<Item = std::os::fd::RawFd>,
+) -> io::Result<Vec<TcpListener>> {
+    use std::os::fd::{BorrowedFd, FromRawFd};
+
+    let mut listeners = Vec::new();
+    for fd in fds {
+        // SAFETY: the caller guarantees that the file descriptor is open.
+        let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+        let socket = socket2::SockRef::from(&borrowed);
+        let is_tcp = match (socket.r#type(), socket.local_addr()) {
+            (Ok(ty), Ok(addr)) => ty == Type::STREAM && addr.as_socket().is_some(),
+            (Err(err), _) | (_, Err(err)) => {
+                debug!(target: "rpc", fd, %err, "Skipping inherited file descriptor that is not a socket");
+                false
+            }
+        };
+        if !is_tcp {
+            continue
+        }
+        socket.set_nonblocking(true)?;
+        // SAFETY: the caller guarantees that TCP sockets are owned by nobody else.
+        listeners.push(unsafe { TcpListener::from_raw_fd(fd) });
+    }
+    Ok(listeners)
+}
+
+/// Returns the TCP listeners passed to the process via systemd socket activation.
+///
+/// Socket activation is only supported on unix platforms, this always returns no listeners.
+#[cfg(not(unix))]
+pub fn inherited_tcp_listeners() -> io::Result<Vec<TcpListener>> {
+    Ok(Vec::new())
+}
+
+/// Removes and returns the listener bound to `addr` from `listeners`, or binds a new one.
+pub(crate) fn take_or_bind_tcp_listener(
+    listeners: &mut Vec<TcpListener>,
+    addr: SocketAddr,
+    reuse_port: bool,
+) -> io::Result<TcpListener> {
+    let inherited = listeners
+        .iter()
+        .position(|listener| listener.local_addr().is_ok_and(|local| local == addr));
+    match inherited {
+        Some(idx) => Ok(listeners.swap_remove(idx)),
+        None => bind_tcp_listener(addr, reuse_port),
+    }
+}
+
+/// The address of the peer of a connection of the HTTP and WS servers.
+///
+/// Inserted into the extensions of every HTTP request by [`serve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerAddr(pub SocketAddr);
+
+/// Accepts connections on the listener until the returned handle is stopped, and serves each of
+/// them with a service created by `make_service`.
+///
+/// Unlike the server of jsonrpsee, this inserts the [`PeerAddr`] of the connection into the
+/// extensions of its requests.
+pub(crate) fn serve<S, B>(
+    listener: TcpListener,
+    mut make_service: impl FnMut(StopHandle) -> S + Send + 'static,
+) -> io::Result<ServerHandle>
+where
+    S: Service<HttpRequest, Response = http::Response<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+    let (stop_handle, server_handle) = stop_channel();
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(err) => {
+                        debug!(target: "rpc", %err, "Failed to accept connection");
+                        continue
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+            if let Err(err) = stream.set_nodelay(true) {
+                debug!(target: "rpc", %err, "Failed to set TCP_NODELAY");
+            }
+
+            let service = tower::ServiceBuilder::new()
+                .map_request(move |req: http::Request<Incoming>| {
+                    let mut req = req.map(HttpBody::new);
+                    req.extensions_mut().insert(PeerAddr(peer_addr));
+                    req
+                })
+                .service(make_service(stop_handle.clone()));
+            tokio::spawn(serve_with_graceful_shutdown(
+                stream,
+                service,
+                stop_handle.clone().shutdown(),
+            ));
+        }
+    });
+    Ok(server_handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[cfg(unix)]
+    #[test]
+    fn bind_reuse_port() {
+        let first = bind_tcp_listener((Ipv4Addr::LOCALHOST, 0).into(), true).unwrap();
+        let addr = first.local_addr().unwrap();
+
+        // a second listener can bind the same address while the first one is still open
+        let second = bind_tcp_listener(addr, true).unwrap();
+        assert_eq!(second.local_addr().unwrap(), addr);
+
+        assert!(bind_tcp_listener(addr, false).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skip_inherited_non_tcp_fds() {
+        use std::os::{
+            fd::{AsRawFd, IntoRawFd},
+            unix::net::UnixStream,
+        };
+
+        let file = tempfile::tempfile().unwrap();
+        let (unix, _peer) = UnixStream::pair().unwrap();
+        let tcp = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = tcp.local_addr().unwrap();
+
+        // SAFETY: all file descriptors are open and ownership of the TCP listener is released.
+        let listeners = unsafe {
+            tcp_listeners_from_fds([file.as_raw_fd(), unix.as_raw_fd(), tcp.into_raw_fd()])
+        }
+        .unwrap();
+        assert_eq!(listeners.len(), 1);
+        assert_eq!(listeners[0].local_addr().unwrap(), addr);
+    }
+
+    #[test]
+    fn take_inherited_listener() {
+        let inherited = bind_tcp_listener((Ipv4Addr::LOCALHOST, 0).into(), false).unwrap();
+        let addr = inherited.local_addr().unwrap();
+        let mut listeners = vec![inherited];
+
+        let listener = take_or_bind_tcp_listener(&mut listeners, addr, false).unwrap();
+        assert_eq!(listener.local_addr().unwrap(), addr);
+        assert!(listeners.is_empty());
+    }
+}
@@ -0,0 +1,616 @@
+//! Optional multi-tenancy for the HTTP and WS RPC servers.
+//!
+//! Every tenant is identified by an API key, which is sent in the [`API_KEY_HEADER`] header. API
+//! keys are not accepted in the URL, where they would end up in access logs and proxy caches.
+//! Requests without a known API key are rejected with `401 Unauthorized`. For every tenant, the
+//! allowed namespaces and methods, a per second rate limit and a total request quota can be
+//! configured.
+//!
+//! The tenants are configured in a TOML file:
+//!
+//! ```toml
+//! [[tenant]]
+//! name = "alice"
+//! api_key = "d3adb33f"
+//! namespaces = ["eth", "net", "web3"]
+//! methods = ["eth_get*", "eth_call", "net_version", "web3_clientVersion"]
+//! requests_per_second = 50
+//! max_requests = 1000000
+//! ```
+//!
+//! The same tenants can also be configured in the `[[rpc.tenant]]` section of `reth.toml`.
+//!
+//! The usage counters of all tenants can be persisted to a file, so quotas survive restarts.
+
+use http::{header::HeaderValue, Response, StatusCode};
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, HttpBody, HttpRequest, HttpResponse},
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_rpc_server_types::RethRpcModule;
+pub use reth_rpc_server_types::{RpcTenantsConfig, TenantConfig};
+use reth_tasks::shutdown::GracefulShutdown;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::{debug, warn};
+
+/// The header that carries the API key of a request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// How often the usage counters are persisted by default.
+pub const DEFAULT_TENANT_USAGE_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Error code of requests that exceed the rate limit or quota of the tenant.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Error code of requests to a namespace the tenant is not allowed to call.
+const NAMESPACE_NOT_ALLOWED_CODE: i32 = -32601;
+
+/// Errors when loading the tenants.
+#[derive(Debug, thiserror::Error)]
+pub enum RpcTenantsError {
+    /// Failed to read or write a file.
+    #[error("failed to access {path:?}: {error}")]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The IO error.
+        error: io::Error,
+    },
+    /// Failed to parse a file.
+    #[error("failed to parse {path:?}: {error}")]
+    Parse {
+        /// The path of the file.
+        path: PathBuf,
+        /// The parse error.
+        error: toml::de::Error,
+    },
+    /// The same API key is used by multiple tenants.
+    #[error("API key of tenant {0} is already used by another tenant")]
+    DuplicateApiKey(String),
+    /// The same name is used by multiple tenants.
+    #[error("tenant name {0} is used more than once")]
+    DuplicateName(String),
+}
+
+/// The usage counters of a tenant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantUsage {
+    /// The number of served requests.
+    pub requests: u64,
+    /// The number of rejected requests.
+    pub rejected: u64,
+}
+
+/// A tenant with its limits and usage counters.
+#[derive(Debug)]
+pub struct Tenant {
+    config: TenantConfig,
+    requests: AtomicU64,
+    rejected: AtomicU64,
+    /// Start and number of requests of the current rate limit window.
+    window: Mutex<(Instant, u32)>,
+}
+
+impl Tenant {
+    fn new(config: TenantConfig, usage: TenantUsage) -> Self {
+        Self {
+            config,
+            requests: AtomicU64::new(usage.requests),
+            rejected: AtomicU64::new(usage.rejected),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Returns the name of the tenant.
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Returns the usage counters of the tenant.
+    pub fn usage(&self) -> TenantUsage {
+        TenantUsage {
+            requests: self.requests.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Checks whether the tenant may call the method and accounts for the request.
+    fn admit(&self, method: &str) -> Result<(), ErrorObject<'static>> {
+        let res = self.check(method);
+        match &res {
+            Ok(()) => self.requests.fetch_add(1, Ordering::Relaxed),
+            Err(_) => self.rejected.fetch_add(1, Ordering::Relaxed),
+        };
+        res
+    }
+
+    fn check(&self, method: &str) -> Result<(), ErrorObject<'static>> {
+        if let Some(namespaces) = &self.config.namespaces {
+            let namespace = method.split('_').next().unwrap_or_default();
+            let allowed =
+                RethRpcModule::from_str(namespace).is_ok_and(|module| namespaces.contains(&module));
+            if !allowed {
+                return Err(ErrorObject::owned(
+                    NAMESPACE_NOT_ALLOWED_CODE,
+                    format!("namespace {namespace} is not enabled for this API key"),
+                    None::<()>,
+                ))
+            }
+        }
+
+        if !self.config.is_method_allowed(method) {
+            return Err(ErrorObject::owned(
+                NAMESPACE_NOT_ALLOWED_CODE,
+                format!("method {method} is not enabled for this API key"),
+                None::<()>,
+            ))
+        }
+
+        if let Some(max_requests) = self.config.max_requests {
+            if self.requests.load(Ordering::Relaxed) >= max_requests {
+                return Err(ErrorObject::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    "request quota of this API key is exhausted",
+                    None::<()>,
+                ))
+            }
+        }
+
+        if let Some(requests_per_second) = self.config.requests_per_second {
+            let mut window = self.window.lock();
+            let now = Instant::now();
+            if now.duration_since(window.0) >= Duration::from_secs(1) {
+                *window = (now, 0);
+            }
+            if window.1 >= requests_per_second.get() {
+                return Err(ErrorObject::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    "rate limit of this API key exceeded",
+                    None::<()>,
+                ))
+            }
+            window.1 += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// The tenants of the RPC server.
+///
+/// The tenant of a request is authenticated by the HTTP middleware returned by
+/// [`RpcTenants::http_layer`], its limits are enforced by the [`RpcTenantLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcTenants {
+    inner: Arc<RpcTenantsInner>,
+}
+
+#[derive(Debug)]
+struct RpcTenantsInner {
+    by_api_key: HashMap<String, Arc<Tenant>>,
+    usage_path: Option<PathBuf>,
+}
+
+impl RpcTenants {
+    /// Creates the tenants with the given usage counters, keyed by tenant name.
+    pub fn new(
+        config: RpcTenantsConfig,
+        mut usage: BTreeMap<String, TenantUsage>,
+    ) -> Result<Self, RpcTenantsError> {
+        let mut by_api_key = HashMap::with_capacity(config.tenants.len());
+        let mut names = std::collections::HashSet::new();
+        for tenant in config.tenants {
+            if !names.insert(tenant.name.clone()) {
+                return Err(RpcTenantsError::DuplicateName(tenant.name))
+            }
+            if by_api_key.contains_key(&tenant.api_key) {
+                return Err(RpcTenantsError::DuplicateApiKey(tenant.name))
+            }
+            let usage = usage.remove(&tenant.name).unwrap_or_default();
+            by_api_key.insert(tenant.api_key.clone(), Arc::new(Tenant::new(tenant, usage)));
+        }
+        Ok(Self { inner: Arc::new(RpcTenantsInner { by_api_key, usage_path: None }) })
+    }
+
+    /// Loads the tenants from the given TOML file.
+    ///
+    /// If a usage file is given, the usage counters are restored from it if it exists, and
+    /// [`Self::persist_usage`] writes to it.
+    pub fn load(path: &Path, usage_path: Option<PathBuf>) -> Result<Self, RpcTenantsError> {
+        Self::load_with(Some(path), RpcTenantsConfig::default(), usage_path)
+    }
+
+    /// Loads the tenants of the given configuration and, if set, the given TOML file.
+    ///
+    /// See also [`Self::load`].
+    pub fn load_with(
+        path: Option<&Path>,
+        mut config: RpcTenantsConfig,
+        usage_path: Option<PathBuf>,
+    ) -> Result<Self, RpcTenantsError> {
+        if let Some(path) = path {
+            let file_config: RpcTenantsConfig = read_toml(path)?.unwrap_or_default();
+            config.tenants.extend(file_config.tenants);
+        }
+        let usage = match &usage_path {
+            Some(usage_path) => read_toml(usage_path)?.unwrap_or_default(),
+            None => Default::default(),
+        };
+        let mut tenants = Self::new(config, usage)?;
+        Arc::get_mut(&mut tenants.inner).expect("not shared yet").usage_path = usage_path;
+        Ok(tenants)
+    }
+
+    /// Returns the tenant with the given API key.
+    pub fn get(&self, api_key: &str) -> Option<&Arc<Tenant>> {
+        self.inner.by_api_key.get(api_key)
+    }
+
+    /// Returns the usage counters of all tenants, keyed by tenant name.
+    pub fn usage(&self) -> BTreeMap<String, TenantUsage> {
+        self.inner
+            .by_api_key
+            .values()
+            .map(|tenant| (tenant.name().to_string(), tenant.usage()))
+            .collect()
+    }
+
+    /// Writes the usage counters to the usage file, if configured.
+    pub fn persist_usage(&self) -> io::Result<()> {
+        let Some(path) = &self.inner.usage_path else { return Ok(()) };
+        let usage = toml::to_string(&self.usage()).map_err(io::Error::other)?;
+        // write to a temporary file first, so a crash never leaves a truncated file behind
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, usage)?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Persists the usage counters at the given interval, and a last time on shutdown.
+    pub async fn persist_usage_task(self, interval: Duration, shutdown: GracefulShutdown) {
+        let mut interval = tokio::time::interval(interval);
+        let mut shutdown = std::pin::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.persist_usage() {
+                        warn!(target: "rpc::tenancy", %err, "Failed to persist tenant usage");
+                    }
+                }
+                _guard = &mut shutdown => {
+                    if let Err(err) = self.persist_usage() {
+                        warn!(target: "rpc::tenancy", %err, "Failed to persist tenant usage");
+                    }
+                    break
+                }
+            }
+        }
+    }
+
+    /// Returns the HTTP middleware that rejects requests without a known API key.
+    pub fn http_layer(&self) -> RpcTenantAuthLayer {
+        RpcTenantAuthLayer { tenants: self.clone() }
+    }
+
+    /// Returns the tenant of the HTTP request.
+    fn authenticate<B>(&self, req: &http::Request<B>) -> Option<Arc<Tenant>> {
+        let api_key = req.headers().get(API_KEY_HEADER)?.to_str().ok()?;
+        self.get(api_key).cloned()
+    }
+}
+
+/// Reads a TOML file, returns `None` if it doesn't exist.
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, RpcTenantsError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(RpcTenantsError::Io { path: path.to_path_buf(), error }),
+    };
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|error| RpcTenantsError::Parse { path: path.to_path_buf(), error })
+}
+
+/// HTTP middleware that authenticates the tenant of a request.
+///
+/// Requests without a known API key are rejected, all others are forwarded with the [`Tenant`] in
+/// their extensions, where it is picked up by the [`RpcTenantLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcTenantAuthLayer {
+    tenants: RpcTenants,
+}
+
+impl<S> Layer<S> for RpcTenantAuthLayer {
+    type Service = RpcTenantAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcTenantAuthService { tenants: self.tenants.clone(), inner }
+    }
+}
+
+/// The service of the [`RpcTenantAuthLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcTenantAuthService<S> {
+    tenants: RpcTenants,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcTenantAuthService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = TenantFuture<S::Future, Result<HttpResponse, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        match self.tenants.authenticate(&req) {
+            Some(tenant) => {
+                req.extensions_mut().insert(tenant);
+                TenantFuture::Future { future: self.inner.call(req) }
+            }
+            None => {
+                let mut response = Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(HttpBody::new("invalid or missing API key".to_string()))
+                    .expect("This should never happen");
+                response
+                    .headers_mut()
+                    .insert(http::header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                TenantFuture::Ready { response: Some(Ok(response)) }
+            }
+        }
+    }
+}
+
+/// RPC middleware that enforces the namespaces and limits of the tenant of a request.
+///
+/// This is a no-op if multi-tenancy is disabled.
+#[derive(Debug, Clone, Default)]
+pub struct RpcTenantLayer {
+    enabled: bool,
+}
+
+impl RpcTenantLayer {
+    /// Creates a new layer, enforcing tenant limits if `enabled` is set.
+    pub const fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+impl<S> Layer<S> for RpcTenantLayer {
+    type Service = RpcTenantService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcTenantService { enabled: self.enabled, inner }
+    }
+}
+
+/// The service of the [`RpcTenantLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcTenantService<S> {
+    enabled: bool,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcTenantService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = TenantFuture<S::Future, MethodResponse>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        if !self.enabled {
+            return TenantFuture::Future { future: self.inner.call(req) }
+        }
+
+        let res = match req.extensions().get::<Arc<Tenant>>() {
+            Some(tenant) => tenant.admit(req.method_name()).inspect_err(|err| {
+                debug!(target: "rpc::tenancy", tenant = tenant.name(), method = req.method_name(), %err, "Rejected request");
+            }),
+            None => Err(ErrorObject::owned(
+                NAMESPACE_NOT_ALLOWED_CODE,
+                "request is not authenticated",
+                None::<()>,
+            )),
+        };
+        match res {
+            Ok(()) => TenantFuture::Future { future: self.inner.call(req) },
+            Err(err) => TenantFuture::Ready {
+                response: Some(MethodResponse::error(req.id().into_owned(), err)),
+            },
+        }
+    }
+}
+
+/// Response future of the tenant and client limit middlewares, either the inner future or a
+/// rejection.
+#[pin_project::pin_project(project = TenantFutureProj)]
+#[derive(Debug)]
+pub enum TenantFuture<F, T> {
+    /// The request was forwarded to the inner service.
+    Future {
+        /// The future of the inner service.
+        #[pin]
+        future: F,
+    },
+    /// The request was rejected.
+    Ready {
+        /// The rejection, taken when polled.
+        response: Option<T>,
+    },
+}
+
+impl<F, T> std::future::Future for TenantFuture<F, T>
+where
+    F: std::future::Future<Output = T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            TenantFutureProj::Future { future } => future.poll(cx),
+            TenantFutureProj::Ready { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[[tenant]]
+name = "alice"
+api_key = "alice-key"
+namespaces = ["eth", "net"]
+requests_per_second = 2
+
+[[tenant]]
+name = "bob"
+api_key = "bob-key"
+max_requests = 3
+
+[[tenant]]
+name = "carol"
+api_key = "carol-key"
+namespaces = ["eth"]
+methods = ["eth_get*", "eth_chainId"]
+"#;
+
+    #[test]
+    fn tenant_limits_and_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("tenants.toml");
+        let usage_path = dir.path().join("usage.toml");
+        fs::write(&config_path, CONFIG).unwrap();
+        fs::write(&usage_path, "[bob]\nrequests = 2\nrejected = 0\n").unwrap();
+
+        let tenants = RpcTenants::load(&config_path, Some(usage_path.clone())).unwrap();
+        assert!(tenants.get("unknown").is_none());
+
+        let alice = tenants.get("alice-key").unwrap();
+        assert!(alice.admit("debug_traceTransaction").is_err());
+        assert!(alice.admit("eth_blockNumber").is_ok());
+        assert!(alice.admit("net_version").is_ok());
+        assert_eq!(alice.admit("eth_chainId").unwrap_err().code(), LIMIT_EXCEEDED_CODE);
+        assert_eq!(alice.usage(), TenantUsage { requests: 2, rejected: 2 });
+
+        // the quota is restored from the usage file
+        let bob = tenants.get("bob-key").unwrap();
+        assert!(bob.admit("debug_traceTransaction").is_ok());
+        assert_eq!(bob.admit("eth_chainId").unwrap_err().code(), LIMIT_EXCEEDED_CODE);
+
+        tenants.persist_usage().unwrap();
+        let tenants = RpcTenants::load(&config_path, Some(usage_path)).unwrap();
+        assert_eq!(
+            tenants.usage(),
+            BTreeMap::from([
+                ("alice".to_string(), TenantUsage { requests: 2, rejected: 2 }),
+                ("bob".to_string(), TenantUsage { requests: 3, rejected: 1 }),
+                ("carol".to_string(), TenantUsage::default()),
+            ])
+        );
+    }
+
+    #[test]
+    fn tenant_method_allowlist() {
+        let config: RpcTenantsConfig = toml::from_str(CONFIG).unwrap();
+        let tenants = RpcTenants::new(config, Default::default()).unwrap();
+
+        let carol = tenants.get("carol-key").unwrap();
+        assert!(carol.admit("eth_getBalance").is_ok());
+        assert!(carol.admit("eth_chainId").is_ok());
+        assert_eq!(
+            carol.admit("eth_sendRawTransaction").unwrap_err().code(),
+            NAMESPACE_NOT_ALLOWED_CODE
+        );
+        assert!(carol.admit("debug_getRawBlock").is_err());
+    }
+
+    #[test]
+    fn load_tenants_with_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("tenants.toml");
+        fs::write(&config_path, CONFIG).unwrap();
+
+        let config = RpcTenantsConfig {
+            tenants: vec![TenantConfig {
+                name: "dave".to_string(),
+                api_key: "dave-key".to_string(),
+                namespaces: None,
+                methods: None,
+                requests_per_second: None,
+                max_requests: None,
+            }],
+        };
+        let tenants = RpcTenants::load_with(Some(&config_path), config, None).unwrap();
+        assert_eq!(tenants.get("dave-key").unwrap().name(), "dave");
+        assert_eq!(tenants.get("alice-key").unwrap().name(), "alice");
+    }
+
+    #[test]
+    fn authenticate_request() {
+        let config: RpcTenantsConfig = toml::from_str(CONFIG).unwrap();
+        let tenants = RpcTenants::new(config, Default::default()).unwrap();
+
+        let req = http::Request::builder().header(API_KEY_HEADER, "bob-key").body(()).unwrap();
+        assert_eq!(tenants.authenticate(&req).unwrap().name(), "bob");
+
+        // API keys in the URL are ignored
+        let req = http::Request::builder().uri("/?foo=bar&api_key=alice-key").body(()).unwrap();
+        assert!(tenants.authenticate(&req).is_none());
+
+        let req = http::Request::builder().header(API_KEY_HEADER, "nope").body(()).unwrap();
+        assert!(tenants.authenticate(&req).is_none());
+    }
+
+    #[test]
+    fn reject_duplicate_api_keys() {
+        let config = RpcTenantsConfig {
+            tenants: vec![
+                TenantConfig {
+                    name: "a".to_string(),
+                    api_key: "key".to_string(),
+                    namespaces: None,
+                    methods: None,
+                    requests_per_second: None,
+                    max_requests: None,
+                },
+                TenantConfig {
+                    name: "b".to_string(),
+                    api_key: "key".to_string(),
+                    namespaces: None,
+                    methods: None,
+                    requests_per_second: None,
+                    max_requests: None,
+                },
+            ],
+        };
+        assert!(matches!(
+            RpcTenants::new(config, Default::default()),
+            Err(RpcTenantsError::DuplicateApiKey(_))
+        ));
+    }
+}
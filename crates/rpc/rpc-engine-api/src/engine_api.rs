@@ -38,6 +38,11 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The number of consecutive blocks read at once when serving payload bodies by range.
+///
+/// This bounds the number of blocks that are held in memory in addition to the payload bodies.
+const PAYLOAD_BODIES_RANGE_CHUNK_SIZE: u64 = 64;
+
 /// The upper limit blobs `eth_getBlobs`.
 const MAX_BLOB_LIMIT: usize = 128;
 
@@ -517,17 +522,29 @@ where
                 }
             }
 
-            for num in start..=end {
-                let block_result = inner.provider.block(BlockHashOrNumber::Number(num));
-                match block_result {
-                    Ok(block) => {
-                        result.push(block.map(&f));
-                    }
+            // read the blocks in chunks of consecutive blocks, which the provider serves with
+            // sequential reads, e.g. from static files, instead of looking up every block
+            let mut chunk_start = start;
+            while chunk_start <= end {
+                let chunk_end =
+                    chunk_start.saturating_add(PAYLOAD_BODIES_RANGE_CHUNK_SIZE - 1).min(end);
+                let blocks = match inner.provider.block_range(chunk_start..=chunk_end) {
+                    Ok(blocks) => blocks,
                     Err(err) => {
                         tx.send(Err(EngineApiError::Internal(Box::new(err)))).ok();
                         return;
                     }
                 };
+
+                // the blocks are sorted by number, missing blocks are returned as `None`
+                for block in blocks {
+                    result.resize_with((block.number - start) as usize, || None);
+                    result.push(Some(f(block)));
+                }
+                result.resize_with((chunk_end - start + 1) as usize, || None);
+
+                let Some(next) = chunk_end.checked_add(1) else { break };
+                chunk_start = next;
             }
             tx.send(Ok(result)).ok();
         }));
@@ -22,6 +22,43 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// A subset of the [EthBundleApi] API interface that only supports `eth_sendBundle`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthSendBundleApi {
+    /// `eth_sendBundle` can be used to send your bundles to the builder.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, bundle: EthSendBundle)
+        -> jsonrpsee::core::RpcResult<EthBundleHash>;
+}
+
+/// A subset of the [EthBundleApi] API interface that only supports private transactions.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthPrivateTransactionApi {
+    /// `eth_sendPrivateTransaction` submits a transaction that is only included by the local
+    /// payload builder, up to the block with the given max block number.
+    #[method(name = "sendPrivateTransaction")]
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// `eth_sendPrivateRawTransaction` submits a transaction that is only included by the local
+    /// payload builder, like `eth_sendRawTransaction`.
+    #[method(name = "sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// `eth_cancelPrivateTransaction` removes a private transaction before it's included.
+    ///
+    /// Returns `false` if the transaction is not a pending private transaction.
+    #[method(name = "cancelPrivateTransaction")]
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<bool>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint>
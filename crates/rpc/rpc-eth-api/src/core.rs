@@ -13,6 +13,7 @@ use alloy_rpc_types_eth::{
 };
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::BlockBlobSidecar;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
@@ -105,6 +106,10 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject> {
     #[method(name = "getBlockReceipts")]
     async fn block_receipts(&self, block_id: BlockId) -> RpcResult<Option<Vec<R>>>;
 
+    /// Returns the blob sidecars of all blob transactions in the given block.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlockBlobSidecar>>>;
+
     /// Returns an uncle block of the given block and index.
     #[method(name = "getUncleByBlockHashAndIndex")]
     async fn uncle_by_block_hash_and_index(&self, hash: B256, index: Index)
@@ -225,15 +230,19 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject> {
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes>;
 
-    /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
-    /// optionality of state overrides
+    /// Simulate arbitrary number of bundles of transactions at an arbitrary blockchain index,
+    /// with the optionality of state overrides.
+    ///
+    /// The bundles are executed in order and the state changes of every transaction are visible
+    /// to all following transactions. Each bundle can override the block environment of its
+    /// transactions.
     #[method(name = "callMany")]
     async fn call_many(
         &self,
-        bundle: Bundle,
+        bundles: Vec<Bundle>,
         state_context: Option<StateContext>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<Vec<EthCallResponse>>;
+    ) -> RpcResult<Vec<Vec<EthCallResponse>>>;
 
     /// Generates an access list for a transaction.
     ///
@@ -467,6 +476,12 @@ where
         Ok(EthBlocks::block_receipts(self, block_id).await?)
     }
 
+    /// Handler for: `eth_getBlobSidecars`
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlockBlobSidecar>>> {
+        trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlobSidecars");
+        Ok(EthBlocks::blob_sidecars(self, block_id).await?)
+    }
+
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
     async fn uncle_by_block_hash_and_index(
         &self,
@@ -649,12 +664,12 @@ where
     /// Handler for: `eth_callMany`
     async fn call_many(
         &self,
-        bundle: Bundle,
+        bundles: Vec<Bundle>,
         state_context: Option<StateContext>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<Vec<EthCallResponse>> {
-        trace!(target: "rpc::eth", ?bundle, ?state_context, ?state_override, "Serving eth_callMany");
-        Ok(EthCall::call_many(self, bundle, state_context, state_override).await?)
+    ) -> RpcResult<Vec<Vec<EthCallResponse>>> {
+        trace!(target: "rpc::eth", ?bundles, ?state_context, ?state_override, "Serving eth_callMany");
+        Ok(EthCall::call_many(self, bundles, state_context, state_override).await?)
     }
 
     /// Handler for: `eth_createAccessList`
@@ -3,6 +3,7 @@
 use alloy_json_rpc::RpcObject;
 use alloy_rpc_types_eth::{Filter, FilterChanges, FilterId, Log, PendingTransactionFilterKind};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::logs_utils::{LogCursor, LogsResponse};
 
 /// Rpc Interface for poll-based ethereum filter API.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -36,6 +37,10 @@ pub trait EthFilterApi<T: RpcObject> {
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool>;
 
     /// Returns logs matching given filter object.
+    ///
+    /// If the matching logs exceed the maximum number of logs per response, only the first logs are
+    /// returned, together with a cursor. Passing the cursor with the same filter returns the next
+    /// logs.
     #[method(name = "getLogs")]
-    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+    async fn logs(&self, filter: Filter, cursor: Option<LogCursor>) -> RpcResult<LogsResponse>;
 }
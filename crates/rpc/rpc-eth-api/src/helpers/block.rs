@@ -1,13 +1,16 @@
 //! Database access for `eth_` block RPC methods. Loads block and receipt data w.r.t. network.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use alloy_eips::BlockId;
 use alloy_rpc_types_eth::{Block, Header, Index};
 use futures::Future;
+use reth_errors::RethError;
 use reth_primitives::{Receipt, SealedBlock, SealedBlockWithSenders};
 use reth_provider::{BlockIdReader, BlockReader, BlockReaderIdExt, HeaderProvider};
+use reth_rpc_eth_types::{BlockBlobSidecar, EthApiError};
 use reth_rpc_types_compat::block::from_block;
+use reth_transaction_pool::TransactionPool;
 
 use crate::{node::RpcNodeCoreExt, FromEthApiError, FullEthApiTypes, RpcBlock, RpcReceipt};
 
@@ -115,6 +118,54 @@ pub trait EthBlocks: LoadBlock {
     where
         Self: LoadReceipt;
 
+    /// Helper function for `eth_getBlobSidecars`.
+    ///
+    /// Returns the sidecars of all blob transactions in the block that are still kept by the
+    /// transaction pool or its blob archive, or `None` if the block wasn't found.
+    fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Option<Vec<BlockBlobSidecar>>, Self::Error>> + Send {
+        async move {
+            let Some(block) = self.block_with_senders(block_id).await? else { return Ok(None) };
+            let blob_txs = block
+                .body
+                .transactions()
+                .enumerate()
+                .filter(|(_, tx)| tx.is_eip4844())
+                .map(|(index, tx)| (index as u64, tx.hash()))
+                .collect::<Vec<_>>();
+            if blob_txs.is_empty() {
+                return Ok(Some(Vec::new()))
+            }
+
+            let mut sidecars = self
+                .pool()
+                .get_all_blobs(blob_txs.iter().map(|(_, tx_hash)| *tx_hash).collect())
+                .map_err(|err| {
+                    Self::Error::from_eth_err(EthApiError::Internal(RethError::other(err)))
+                })?
+                .into_iter()
+                .collect::<HashMap<_, _>>();
+
+            Ok(Some(
+                blob_txs
+                    .into_iter()
+                    .filter_map(|(tx_index, tx_hash)| {
+                        let sidecar = sidecars.remove(&tx_hash)?;
+                        Some(BlockBlobSidecar {
+                            blob_sidecar: Arc::unwrap_or_clone(sidecar),
+                            block_number: block.number,
+                            block_hash: block.hash(),
+                            tx_index,
+                            tx_hash,
+                        })
+                    })
+                    .collect(),
+            ))
+        }
+    }
+
     /// Helper method that loads a bock and all its receipts.
     #[allow(clippy::type_complexity)]
     fn load_block_and_receipts(
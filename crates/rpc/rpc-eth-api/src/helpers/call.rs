@@ -165,10 +165,9 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock {
                         &mut db,
                     )?;
 
-                    let mut calls = calls.into_iter().peekable();
                     let mut results = Vec::with_capacity(calls.len());
 
-                    while let Some(tx) = calls.next() {
+                    for tx in calls {
                         let env = this.build_call_evm_env(cfg.clone(), block_env.clone(), tx)?;
 
                         let (res, env) = {
@@ -183,11 +182,9 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock {
                             }
                         };
 
-                        if calls.peek().is_some() {
-                            // need to apply the state changes of this call before executing the
-                            // next call
-                            db.commit(res.state);
-                        }
+                        // need to apply the state changes of this call before executing the
+                        // next call, including the calls of the following blocks
+                        db.commit(res.state);
 
                         results.push((env.tx.caller, res.result));
                     }
@@ -231,20 +228,25 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock {
         }
     }
 
-    /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
-    /// optionality of state overrides
+    /// Simulate arbitrary number of bundles of transactions at an arbitrary blockchain index, with
+    /// the optionality of state overrides.
+    ///
+    /// The state overrides are applied before the first transaction, and the state changes of
+    /// every transaction are carried over to the following transactions and bundles.
     fn call_many(
         &self,
-        bundle: Bundle,
+        bundles: Vec<Bundle>,
         state_context: Option<StateContext>,
         mut state_override: Option<StateOverride>,
-    ) -> impl Future<Output = Result<Vec<EthCallResponse>, Self::Error>> + Send
+    ) -> impl Future<Output = Result<Vec<Vec<EthCallResponse>>, Self::Error>> + Send
     where
         Self: LoadBlock,
     {
         async move {
-            let Bundle { transactions, block_override } = bundle;
-            if transactions.is_empty() {
+            if bundles.is_empty() {
+                return Err(EthApiError::InvalidParams(String::from("bundles are empty.")).into())
+            }
+            if bundles.iter().all(|bundle| bundle.transactions.is_empty()) {
                 return Err(
                     EthApiError::InvalidParams(String::from("transactions are empty.")).into()
                 )
@@ -292,7 +294,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock {
 
             let this = self.clone();
             self.spawn_with_state_at_block(at.into(), move |state| {
-                let mut results = Vec::with_capacity(transactions.len());
+                let mut all_results = Vec::with_capacity(bundles.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
                 if replay_block_txs {
@@ -310,39 +312,51 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock {
                     }
                 }
 
-                let block_overrides = block_override.map(Box::new);
-
-                let mut transactions = transactions.into_iter().peekable();
-                while let Some(tx) = transactions.next() {
-                    // apply state overrides only once, before the first transaction
-                    let state_overrides = state_override.take();
-                    let overrides = EvmOverrides::new(state_overrides, block_overrides.clone());
-
-                    let env = this
-                        .prepare_call_env(cfg.clone(), block_env.clone(), tx, &mut db, overrides)
-                        .map(Into::into)?;
-                    let (res, _) = this.transact(&mut db, env)?;
+                let mut bundles = bundles.into_iter().peekable();
+                while let Some(Bundle { transactions, block_override }) = bundles.next() {
+                    let mut results = Vec::with_capacity(transactions.len());
+                    let block_overrides = block_override.map(Box::new);
+
+                    let mut transactions = transactions.into_iter().peekable();
+                    while let Some(tx) = transactions.next() {
+                        // apply state overrides only once, before the first transaction
+                        let state_overrides = state_override.take();
+                        let overrides = EvmOverrides::new(state_overrides, block_overrides.clone());
+
+                        let env = this
+                            .prepare_call_env(
+                                cfg.clone(),
+                                block_env.clone(),
+                                tx,
+                                &mut db,
+                                overrides,
+                            )
+                            .map(Into::into)?;
+                        let (res, _) = this.transact(&mut db, env)?;
 
-                    match ensure_success(res.result) {
-                        Ok(output) => {
-                            results.push(EthCallResponse { value: Some(output), error: None });
-                        }
-                        Err(err) => {
-                            results.push(EthCallResponse {
-                                value: None,
-                                error: Some(err.to_string()),
-                            });
+                        match ensure_success(res.result) {
+                            Ok(output) => {
+                                results.push(EthCallResponse { value: Some(output), error: None });
+                            }
+                            Err(err) => {
+                                results.push(EthCallResponse {
+                                    value: None,
+                                    error: Some(err.to_string()),
+                                });
+                            }
                         }
-                    }
 
-                    if transactions.peek().is_some() {
                         // need to apply the state changes of this call before executing the next
-                        // call
-                        db.commit(res.state);
+                        // call, which may be in the next bundle
+                        if transactions.peek().is_some() || bundles.peek().is_some() {
+                            db.commit(res.state);
+                        }
                     }
+
+                    all_results.push(results);
                 }
 
-                Ok(results)
+                Ok(all_results)
             })
             .await
         }
@@ -568,10 +582,14 @@ pub trait Call: LoadState<Evm: ConfigureEvm<Header = Header>> + SpawnBlocking {
         R: Send + 'static,
     {
         async move {
+            let pending_state = self.local_pending_state_at(Some(at)).await?;
             let (cfg, block_env, at) = self.evm_env_at(at).await?;
             let this = self.clone();
             self.spawn_blocking_io(move |_| {
-                let state = this.state_at_block_id(at)?;
+                let state = match pending_state {
+                    Some(state) => state,
+                    None => this.state_at_block_id(at)?,
+                };
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
 
@@ -255,10 +255,14 @@ pub trait EstimateCall: Call {
         Self: LoadPendingBlock,
     {
         async move {
+            let pending_state = self.local_pending_state_at(Some(at)).await?;
             let (cfg, block_env, at) = self.evm_env_at(at).await?;
 
             self.spawn_blocking_io(move |this| {
-                let state = this.state_at_block_id(at)?;
+                let state = match pending_state {
+                    Some(state) => state,
+                    None => this.state_at_block_id(at)?,
+                };
                 EstimateCall::estimate_gas_with(
                     &this,
                     cfg,
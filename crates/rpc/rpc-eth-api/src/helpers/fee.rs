@@ -1,13 +1,14 @@
 //! Loads fee history from database. Helper trait for `eth_` fee and transaction RPC methods.
 
+use alloy_eips::eip1559::INITIAL_BASE_FEE;
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
 use futures::Future;
-use reth_chainspec::EthChainSpec;
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks};
 use reth_provider::{BlockIdReader, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, EthApiError, FeeHistoryCache,
-    FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
+    GasPriceOracle, RpcInvalidTransactionError,
 };
 use tracing::debug;
 
@@ -136,11 +137,7 @@ pub trait EthFees: LoadFee {
                     blob_gas_used_ratio.push(entry.blob_gas_used_ratio);
 
                     if let Some(percentiles) = &reward_percentiles {
-                        let mut block_rewards = Vec::with_capacity(percentiles.len());
-                        for &percentile in percentiles {
-                            block_rewards.push(self.approximate_percentile(entry, percentile));
-                        }
-                        rewards.push(block_rewards);
+                        rewards.push(entry.rewards(percentiles));
                     }
                 }
                 let last_entry = fee_entries.last().expect("is not empty");
@@ -196,16 +193,20 @@ pub trait EthFees: LoadFee {
                 //
                 // The unwrap is safe since we checked earlier that we got at least 1 header.
                 let last_header = headers.last().expect("is present");
-                base_fee_per_gas.push(
-                    self.provider()
-                        .chain_spec()
-                        .base_fee_params_at_timestamp(last_header.timestamp)
-                        .next_block_base_fee(
-                            last_header.gas_used ,
-                            last_header.gas_limit,
-                            last_header.base_fee_per_gas.unwrap_or_default() ,
-                        ) as u128,
-                );
+                let chain_spec = self.provider().chain_spec();
+                let next_block_base_fee = if chain_spec
+                    .ethereum_fork_activation(EthereumHardfork::London)
+                    .transitions_at_block(last_header.number + 1)
+                {
+                    INITIAL_BASE_FEE
+                } else {
+                    chain_spec.base_fee_params_at_timestamp(last_header.timestamp).next_block_base_fee(
+                        last_header.gas_used,
+                        last_header.gas_limit,
+                        last_header.base_fee_per_gas.unwrap_or_default(),
+                    )
+                };
+                base_fee_per_gas.push(next_block_base_fee as u128);
 
                 // Same goes for the `base_fee_per_blob_gas`:
                 // > "[..] includes the next block after the newest of the returned range, because this value can be derived from the newest block.
@@ -222,20 +223,6 @@ pub trait EthFees: LoadFee {
             })
         }
     }
-
-    /// Approximates reward at a given percentile for a specific block
-    /// Based on the configured resolution
-    fn approximate_percentile(&self, entry: &FeeHistoryEntry, requested_percentile: f64) -> u128 {
-        let resolution = self.fee_history_cache().resolution();
-        let rounded_percentile =
-            (requested_percentile * resolution as f64).round() / resolution as f64;
-        let clamped_percentile = rounded_percentile.clamp(0.0, 100.0);
-
-        // Calculate the index in the precomputed rewards array
-        let index = (clamped_percentile / (1.0 / resolution as f64)).round() as usize;
-        // Fetch the reward from the FeeHistoryEntry
-        entry.rewards.get(index).copied().unwrap_or_default()
-    }
 }
 
 /// Loads fee from database.
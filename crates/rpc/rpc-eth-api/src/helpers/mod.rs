@@ -13,6 +13,16 @@
 //!
 //! [`EthApiServer`](crate::EthApiServer), is implemented for any type that implements
 //! all the `Eth` traits, e.g. `reth_rpc::EthApi`.
+//!
+//! ## Custom behaviour
+//!
+//! Nodes that need to change individual behaviours, e.g. the gas estimation of an L2, define their
+//! own `EthApi` type instead of forking the RPC crate. The type can wrap `reth_rpc::eth::core::
+//! EthApiInner` to reuse its data access, implements the `Load` traits by delegating to it, and
+//! overrides only the methods of the `Eth` traits that differ, e.g. [`EstimateCall`] or
+//! [`EthFees`], keeping the default implementations of all other methods. The node builder builds
+//! the type with its `EthApiBuilder` implementation. See `reth_optimism_rpc::OpEthApi` for an
+//! example.
 
 pub mod block;
 pub mod blocking_task;
@@ -30,6 +40,7 @@ pub mod transaction;
 pub use block::{EthBlocks, LoadBlock};
 pub use blocking_task::SpawnBlocking;
 pub use call::{Call, EthCall};
+pub use estimate::EstimateCall;
 pub use fee::{EthFees, LoadFee};
 pub use pending_block::LoadPendingBlock;
 pub use receipt::LoadReceipt;
@@ -21,8 +21,8 @@ use reth_primitives::{
     SealedHeader, TransactionSignedEcRecovered,
 };
 use reth_provider::{
-    BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, ProviderError,
-    ReceiptProvider, StateProviderFactory,
+    providers::BundleStateProvider, BlockReader, BlockReaderIdExt, ChainSpecProvider,
+    EvmEnvProvider, ProviderError, ReceiptProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_revm::{
     database::StateProviderDatabase,
@@ -152,7 +152,7 @@ pub trait LoadPendingBlock:
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let (sealed_block, receipts) = match self
+            let (sealed_block, receipts, execution_outcome) = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
                     this.build_block(pending)
@@ -171,12 +171,45 @@ pub trait LoadPendingBlock:
                 now + Duration::from_secs(1),
                 sealed_block.clone(),
                 receipts.clone(),
+                execution_outcome,
             ));
 
             Ok(Some((sealed_block, receipts)))
         }
     }
 
+    /// Returns the state of the locally built pending block.
+    ///
+    /// This is the state of the parent block with the state changes of the locally built pending
+    /// block applied on top. Returns `None` if the pending block was not built locally, e.g.
+    /// because the CL provided one, whose state is then served by the provider.
+    fn local_pending_state(
+        &self,
+    ) -> impl Future<Output = Result<Option<StateProviderBox>, Self::Error>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
+            let Some((block, _)) = self.local_pending_block().await? else { return Ok(None) };
+
+            let execution_outcome = match self.pending_block().lock().await.as_ref() {
+                Some(pending_block) if pending_block.block.hash() == block.hash() => {
+                    pending_block.execution_outcome.clone()
+                }
+                _ => return Ok(None),
+            };
+
+            self.spawn_blocking_io(move |this| {
+                let state = this
+                    .provider()
+                    .history_by_block_hash(block.parent_hash)
+                    .map_err(Self::Error::from_eth_err)?;
+                Ok(Some(Box::new(BundleStateProvider::new(state, execution_outcome)) as _))
+            })
+            .await
+        }
+    }
+
     /// Assembles a [`Receipt`] for a transaction, based on its [`ExecutionResult`].
     fn assemble_receipt(
         &self,
@@ -208,6 +241,9 @@ pub trait LoadPendingBlock:
 
     /// Builds a pending block using the configured provider and pool.
     ///
+    /// Returns the block together with its receipts and its state changes on top of the parent
+    /// block.
+    ///
     /// If the origin is the actual pending block, the block is built with withdrawals.
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
@@ -215,7 +251,7 @@ pub trait LoadPendingBlock:
     fn build_block(
         &self,
         env: PendingBlockEnv,
-    ) -> Result<(SealedBlockWithSenders, Vec<Receipt>), Self::Error>
+    ) -> Result<(SealedBlockWithSenders, Vec<Receipt>, ExecutionOutcome), Self::Error>
     where
         EthApiError: From<ProviderError>,
     {
@@ -442,6 +478,10 @@ pub trait LoadPendingBlock:
             header,
             body: BlockBody { transactions: executed_txs, ommers: vec![], withdrawals },
         };
-        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts))
+        Ok((
+            SealedBlockWithSenders { block: block.seal_slow(), senders },
+            receipts,
+            execution_outcome,
+        ))
     }
 }
@@ -19,7 +19,7 @@ use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
 
-use crate::{EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt};
+use crate::{AsEthApiError, EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt};
 
 use super::{EthApiSpec, LoadPendingBlock, SpawnBlocking};
 
@@ -45,7 +45,10 @@ pub trait EthState: LoadState + SpawnBlocking {
         &self,
         address: Address,
         block_id: Option<BlockId>,
-    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+    {
         LoadState::get_code(self, address, block_id)
     }
 
@@ -54,14 +57,24 @@ pub trait EthState: LoadState + SpawnBlocking {
         &self,
         address: Address,
         block_id: Option<BlockId>,
-    ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
-        self.spawn_blocking_io(move |this| {
-            Ok(this
-                .state_at_block_id_or_latest(block_id)?
-                .account_balance(address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default())
-        })
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+    {
+        async move {
+            let pending_state = self.local_pending_state_at(block_id).await?;
+            self.spawn_blocking_io(move |this| {
+                let state = match pending_state {
+                    Some(state) => state,
+                    None => this.state_at_block_id_or_latest(block_id)?,
+                };
+                Ok(state
+                    .account_balance(address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default())
+            })
+            .await
+        }
     }
 
     /// Returns values stored of given account, at given blocknumber.
@@ -70,16 +83,27 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         index: JsonStorageKey,
         block_id: Option<BlockId>,
-    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
-        self.spawn_blocking_io(move |this| {
-            Ok(B256::new(
-                this.state_at_block_id_or_latest(block_id)?
-                    .storage(address, index.as_b256())
-                    .map_err(Self::Error::from_eth_err)?
-                    .unwrap_or_default()
-                    .to_be_bytes(),
-            ))
-        })
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+    {
+        async move {
+            let pending_state = self.local_pending_state_at(block_id).await?;
+            self.spawn_blocking_io(move |this| {
+                let state = match pending_state {
+                    Some(state) => state,
+                    None => this.state_at_block_id_or_latest(block_id)?,
+                };
+                Ok(B256::new(
+                    state
+                        .storage(address, index.as_b256())
+                        .map_err(Self::Error::from_eth_err)?
+                        .unwrap_or_default()
+                        .to_be_bytes(),
+                ))
+            })
+            .await
+        }
     }
 
     /// Returns values stored of given account, with Merkle-proof, at given blocknumber.
@@ -209,6 +233,31 @@ pub trait LoadState:
         }
     }
 
+    /// Returns the state of the locally built pending block if the given [`BlockId`] is pending.
+    ///
+    /// Returns `None` for any other [`BlockId`] or if the pending block was not built locally, in
+    /// which case the state should be loaded with [`Self::state_at_block_id_or_latest`].
+    fn local_pending_state_at(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> impl Future<Output = Result<Option<StateProviderBox>, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock + SpawnBlocking,
+    {
+        async move {
+            if !block_id.is_some_and(|block_id| block_id.is_pending()) {
+                return Ok(None)
+            }
+            match self.local_pending_state().await {
+                // without a latest block there is nothing to build the pending block on
+                Err(err) if matches!(err.as_err(), Some(EthApiError::HeaderNotFound(_))) => {
+                    Ok(None)
+                }
+                res => res,
+            }
+        }
+    }
+
     /// Returns the revm evm env for the requested [`BlockId`]
     ///
     /// If the [`BlockId`] this will return the [`BlockId`] of the block the env was configured
@@ -354,15 +403,22 @@ pub trait LoadState:
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send
     where
-        Self: SpawnBlocking,
+        Self: LoadPendingBlock + SpawnBlocking,
     {
-        self.spawn_blocking_io(move |this| {
-            Ok(this
-                .state_at_block_id_or_latest(block_id)?
-                .account_code(address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default()
-                .original_bytes())
-        })
+        async move {
+            let pending_state = self.local_pending_state_at(block_id).await?;
+            self.spawn_blocking_io(move |this| {
+                let state = match pending_state {
+                    Some(state) => state,
+                    None => this.state_at_block_id_or_latest(block_id)?,
+                };
+                Ok(state
+                    .account_code(address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default()
+                    .original_bytes())
+            })
+            .await
+        }
     }
 }
@@ -19,22 +19,32 @@ pub mod helpers;
 pub mod node;
 pub mod pubsub;
 pub mod types;
+pub mod user_operation;
 
 pub use reth_rpc_eth_types::error::{
     AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError,
 };
 pub use reth_rpc_types_compat::TransactionCompat;
 
-pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
+pub use bundle::{
+    EthBundleApiServer, EthCallBundleApiServer, EthPrivateTransactionApiServer,
+    EthSendBundleApiServer,
+};
 pub use core::{EthApiServer, FullEthApiServer};
 pub use filter::EthFilterApiServer;
 pub use node::{RpcNodeCore, RpcNodeCoreExt};
-pub use pubsub::EthPubSubApiServer;
+pub use pubsub::{EthPubSubApiServer, PendingTransactionsFilter, SubscriptionParams};
 pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcReceipt, RpcTransaction};
+pub use user_operation::EthUserOperationApiServer;
 
 #[cfg(feature = "client")]
-pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
+pub use bundle::{
+    EthBundleApiClient, EthCallBundleApiClient, EthPrivateTransactionApiClient,
+    EthSendBundleApiClient,
+};
 #[cfg(feature = "client")]
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
 pub use filter::EthFilterApiClient;
+#[cfg(feature = "client")]
+pub use user_operation::EthUserOperationApiClient;
@@ -1,8 +1,10 @@
 //! `eth_` RPC API for pubsub subscription.
 
 use alloy_json_rpc::RpcObject;
+use alloy_primitives::Address;
 use alloy_rpc_types_eth::pubsub::{Params, SubscriptionKind};
 use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
@@ -16,6 +18,123 @@ pub trait EthPubSubApi<T: RpcObject> {
     async fn subscribe(
         &self,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Additional parameters of an `eth_subscribe` call.
+///
+/// Extends the standard [`Params`] with server-side filters for `newPendingTransactions`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionParams {
+    /// Standard subscription parameters.
+    Standard(Params),
+    /// Filter for `newPendingTransactions` subscriptions.
+    PendingTransactions(PendingTransactionsFilter),
+}
+
+impl From<Params> for SubscriptionParams {
+    fn from(params: Params) -> Self {
+        Self::Standard(params)
+    }
+}
+
+impl From<PendingTransactionsFilter> for SubscriptionParams {
+    fn from(filter: PendingTransactionsFilter) -> Self {
+        Self::PendingTransactions(filter)
+    }
+}
+
+impl<'de> Deserialize<'de> for SubscriptionParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let v = serde_json::Value::deserialize(deserializer)?;
+
+        // objects are log filters unless they set any of the pending transaction filter fields
+        let is_pending_transactions_filter = v.as_object().is_some_and(|obj| {
+            PendingTransactionsFilter::FIELDS.iter().any(|field| obj.contains_key(*field))
+        });
+        if is_pending_transactions_filter {
+            return serde_json::from_value(v)
+                .map(Self::PendingTransactions)
+                .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
+        }
+
+        serde_json::from_value(v).map(Self::Standard).map_err(D::Error::custom)
+    }
+}
+
+/// Server-side filter for `newPendingTransactions` subscriptions.
+///
+/// A transaction is only sent to the subscriber if it matches all configured conditions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionsFilter {
+    /// Whether to send full transaction objects instead of transaction hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+    /// Only match transactions sent by one of these addresses, if not empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<Address>,
+    /// Only match transactions sent to one of these addresses, if not empty.
+    ///
+    /// Contract creations never match a non-empty `to` filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<Address>,
+    /// Only match transactions with at least this priority fee, or gas price for legacy
+    /// transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub min_tip: Option<u128>,
+}
+
+impl PendingTransactionsFilter {
+    /// The fields of the filter, as they appear in the subscription parameters.
+    const FIELDS: [&'static str; 4] = ["fullTransactions", "from", "to", "minTip"];
+
+    /// Returns whether a transaction with the given sender, recipient and priority fee matches
+    /// the filter.
+    pub fn matches(&self, from: Address, to: Option<Address>, priority_fee_or_price: u128) -> bool {
+        (self.from.is_empty() || self.from.contains(&from)) &&
+            (self.to.is_empty() || to.is_some_and(|to| self.to.contains(&to))) &&
+            self.min_tip.is_none_or(|min_tip| priority_fee_or_price >= min_tip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_params_serde() {
+        let params: SubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, SubscriptionParams::Standard(Params::Bool(true)));
+
+        let params: SubscriptionParams = serde_json::from_str(r#"{"address":[]}"#).unwrap();
+        assert!(matches!(params, SubscriptionParams::Standard(Params::Logs(_))));
+
+        let params: SubscriptionParams = serde_json::from_str(
+            r#"{"fullTransactions":true,"to":["0x0000000000000000000000000000000000000001"],"minTip":"0x64"}"#,
+        )
+        .unwrap();
+        let filter = PendingTransactionsFilter {
+            full_transactions: true,
+            to: vec![Address::with_last_byte(1)],
+            min_tip: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(params, SubscriptionParams::PendingTransactions(filter.clone()));
+
+        assert!(filter.matches(Address::ZERO, Some(Address::with_last_byte(1)), 100));
+        assert!(!filter.matches(Address::ZERO, Some(Address::with_last_byte(1)), 99));
+        assert!(!filter.matches(Address::ZERO, None, 100));
+
+        assert!(
+            serde_json::from_str::<SubscriptionParams>(r#"{"minTip":"0x1","address":[]}"#).is_err()
+        );
+    }
+}
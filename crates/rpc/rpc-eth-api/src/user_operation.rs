@@ -0,0 +1,37 @@
+//! Additional `eth_` RPC API for ERC-4337 user operations.
+//!
+//! See also <https://eips.ethereum.org/EIPS/eip-4337#rpc-methods-eth-namespace>
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::erc4337::UserOperationGasEstimation;
+use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::user_operation::UserOperationRequest;
+
+/// The `eth_` methods a bundler serves for user operations.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthUserOperationApi {
+    /// Validates the user operation and adds it to the user operation pool of the given entry
+    /// point, returning the hash of the operation.
+    #[method(name = "sendUserOperation")]
+    async fn send_user_operation(
+        &self,
+        user_operation: UserOperationRequest,
+        entry_point: Address,
+    ) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// Estimates the gas limits of the user operation.
+    ///
+    /// The signature of the operation is not validated, the gas limits and fees of the operation
+    /// may be zero.
+    #[method(name = "estimateUserOperationGas")]
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: UserOperationRequest,
+        entry_point: Address,
+    ) -> jsonrpsee::core::RpcResult<UserOperationGasEstimation>;
+
+    /// Returns the entry points the node accepts user operations for.
+    #[method(name = "supportedEntryPoints")]
+    async fn supported_entry_points(&self) -> jsonrpsee::core::RpcResult<Vec<Address>>;
+}
@@ -0,0 +1,23 @@
+//! Blob sidecar types returned by `eth_getBlobSidecars`.
+
+use alloy_eips::eip4844::BlobTransactionSidecar;
+use alloy_primitives::{BlockHash, TxHash};
+use serde::{Deserialize, Serialize};
+
+/// The blob sidecar of a transaction included in a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockBlobSidecar {
+    /// The blobs, commitments and proofs of the transaction.
+    pub blob_sidecar: BlobTransactionSidecar,
+    /// The number of the block that includes the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The hash of the block that includes the transaction.
+    pub block_hash: BlockHash,
+    /// The index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub tx_index: u64,
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+}
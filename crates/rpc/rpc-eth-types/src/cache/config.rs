@@ -3,8 +3,8 @@
 use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
+    DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -23,6 +23,10 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_envs: u32,
+    /// Max number of blocks whose `eth_getBlockReceipts` responses are cached by the `eth` API.
+    ///
+    /// Default is 64.
+    pub max_block_receipts: u32,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
@@ -35,6 +39,7 @@ impl Default for EthStateCacheConfig {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_block_receipts: DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
@@ -126,8 +126,13 @@ impl EthStateCache {
         Tasks: TaskSpawner + Clone + 'static,
         EvmConfig: ConfigureEvm<Header = Header>,
     {
-        let EthStateCacheConfig { max_blocks, max_receipts, max_envs, max_concurrent_db_requests } =
-            config;
+        let EthStateCacheConfig {
+            max_blocks,
+            max_receipts,
+            max_envs,
+            max_concurrent_db_requests,
+            ..
+        } = config;
         let (this, service) = Self::create(
             provider,
             executor.clone(),
@@ -8,7 +8,6 @@ use std::{
 
 use alloy_eips::eip1559::calc_next_block_base_fee;
 use alloy_primitives::B256;
-use alloy_rpc_types_eth::TxGasAndReward;
 use futures::{
     future::{Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -16,7 +15,8 @@ use futures::{
 use metrics::atomics::AtomicU64;
 use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
-use reth_primitives::{Receipt, SealedBlock, TransactionSigned};
+use reth_primitives::{Receipt, SealedBlock};
+use reth_primitives_traits::SignedTransaction;
 use reth_storage_api::BlockReaderIdExt;
 use revm_primitives::{calc_blob_gasprice, calc_excess_blob_gas};
 use serde::{Deserialize, Serialize};
@@ -53,12 +53,6 @@ impl FeeHistoryCache {
         &self.inner.config
     }
 
-    /// Returns the configured resolution for percentile approximation.
-    #[inline]
-    pub fn resolution(&self) -> u64 {
-        self.config().resolution
-    }
-
     /// Returns all blocks that are missing in the cache in the [`lower_bound`, `upper_bound`]
     /// range.
     ///
@@ -79,18 +73,14 @@ impl FeeHistoryCache {
     {
         let mut entries = self.inner.entries.write().await;
 
-        let percentiles = self.predefined_percentiles();
-        // Insert all new blocks and calculate approximated rewards
+        // Insert all new blocks with their sorted rewards
         for (block, receipts) in blocks {
             let mut fee_history_entry = FeeHistoryEntry::new(block);
-            fee_history_entry.rewards = calculate_reward_percentiles_for_block(
-                &percentiles,
-                fee_history_entry.gas_used,
+            fee_history_entry.sorted_rewards = sorted_rewards_for_block(
                 fee_history_entry.base_fee_per_gas,
                 &block.body.transactions,
                 &receipts,
-            )
-            .unwrap_or_default();
+            );
             entries.insert(block.number, fee_history_entry);
         }
 
@@ -157,14 +147,6 @@ impl FeeHistoryCache {
             None
         }
     }
-
-    /// Generates predefined set of percentiles
-    ///
-    /// This returns 100 * resolution points
-    pub fn predefined_percentiles(&self) -> Vec<f64> {
-        let res = self.resolution() as f64;
-        (0..=100 * self.resolution()).map(|p| p as f64 / res).collect()
-    }
 }
 
 /// Settings for the [`FeeHistoryCache`].
@@ -176,15 +158,11 @@ pub struct FeeHistoryCacheConfig {
     /// Default is [`MAX_HEADER_HISTORY`] plus some change to also serve slightly older blocks from
     /// cache, since `fee_history` supports the entire range
     pub max_blocks: u64,
-    /// Percentile approximation resolution
-    ///
-    /// Default is 4 which means 0.25
-    pub resolution: u64,
 }
 
 impl Default for FeeHistoryCacheConfig {
     fn default() -> Self {
-        Self { max_blocks: MAX_HEADER_HISTORY + 100, resolution: 4 }
+        Self { max_blocks: MAX_HEADER_HISTORY + 100 }
     }
 }
 
@@ -195,8 +173,7 @@ struct FeeHistoryCacheInner {
     lower_bound: AtomicU64,
     /// Stores the upper bound of the cache
     upper_bound: AtomicU64,
-    /// Config for `FeeHistoryCache`, consists of resolution for percentile approximation
-    /// and max number of blocks
+    /// Config for `FeeHistoryCache`, consists of the max number of blocks
     config: FeeHistoryCacheConfig,
     /// Stores the entries of the cache
     entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry>>,
@@ -269,13 +246,30 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
 /// the corresponding rewards for the transactions at each percentile.
 ///
 /// The results are returned as a vector of U256 values.
-pub fn calculate_reward_percentiles_for_block(
+pub fn calculate_reward_percentiles_for_block<T>(
     percentiles: &[f64],
     gas_used: u64,
     base_fee_per_gas: u64,
-    transactions: &[TransactionSigned],
+    transactions: &[T],
+    receipts: &[Receipt],
+) -> Result<Vec<u128>, EthApiError>
+where
+    T: SignedTransaction,
+{
+    let sorted_rewards = sorted_rewards_for_block(base_fee_per_gas, transactions, receipts);
+    Ok(reward_percentiles(percentiles, gas_used, &sorted_rewards))
+}
+
+/// Returns the effective tips of the transactions in a block, sorted in ascending order, with the
+/// cumulative gas used by the transactions up to each tip.
+pub fn sorted_rewards_for_block<T>(
+    base_fee_per_gas: u64,
+    transactions: &[T],
     receipts: &[Receipt],
-) -> Result<Vec<u128>, EthApiError> {
+) -> Vec<CumulativeGasAndReward>
+where
+    T: SignedTransaction,
+{
     let mut transactions = transactions
         .iter()
         .zip(receipts)
@@ -289,9 +283,9 @@ pub fn calculate_reward_percentiles_for_block(
             let gas_used = receipt.cumulative_gas_used - *previous_gas;
             *previous_gas = receipt.cumulative_gas_used;
 
-            Some(TxGasAndReward {
-                gas_used,
-                reward: tx.effective_tip_per_gas(Some(base_fee_per_gas)).unwrap_or_default(),
+            Some(CumulativeGasAndReward {
+                cumulative_gas_used: gas_used,
+                reward: tx.effective_tip_per_gas(base_fee_per_gas).unwrap_or_default(),
             })
         })
         .collect::<Vec<_>>();
@@ -299,29 +293,47 @@ pub fn calculate_reward_percentiles_for_block(
     // Sort the transactions by their rewards in ascending order
     transactions.sort_by_key(|tx| tx.reward);
 
-    // Find the transaction that corresponds to the given percentile
-    //
-    // We use a `tx_index` here that is shared across all percentiles, since we know
-    // the percentiles are monotonically increasing.
-    let mut tx_index = 0;
-    let mut cumulative_gas_used = transactions.first().map(|tx| tx.gas_used).unwrap_or_default();
-    let mut rewards_in_block = Vec::with_capacity(percentiles.len());
-    for percentile in percentiles {
-        // Empty blocks should return in a zero row
-        if transactions.is_empty() {
-            rewards_in_block.push(0);
-            continue
-        }
-
-        let threshold = (gas_used as f64 * percentile / 100.) as u64;
-        while cumulative_gas_used < threshold && tx_index < transactions.len() - 1 {
-            tx_index += 1;
-            cumulative_gas_used += transactions[tx_index].gas_used;
-        }
-        rewards_in_block.push(transactions[tx_index].reward);
+    // Sum up the gas used in the sorted order
+    let mut cumulative_gas_used = 0;
+    for tx in &mut transactions {
+        cumulative_gas_used += tx.cumulative_gas_used;
+        tx.cumulative_gas_used = cumulative_gas_used;
     }
 
-    Ok(rewards_in_block)
+    transactions
+}
+
+/// Returns the rewards at the given percentiles of the gas used by a block, given its sorted
+/// rewards.
+///
+/// The reward at a percentile is the reward of the first transaction at which the cumulative gas
+/// used reaches the percentile. Empty blocks have a zero reward at every percentile.
+pub fn reward_percentiles(
+    percentiles: &[f64],
+    gas_used: u64,
+    sorted_rewards: &[CumulativeGasAndReward],
+) -> Vec<u128> {
+    let Some(last) = sorted_rewards.len().checked_sub(1) else { return vec![0; percentiles.len()] };
+
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = (gas_used as f64 * percentile / 100.) as u64;
+            let tx_index =
+                sorted_rewards.partition_point(|tx| tx.cumulative_gas_used < threshold).min(last);
+            sorted_rewards[tx_index].reward
+        })
+        .collect()
+}
+
+/// The effective tip of a transaction, with the gas used by all transactions of its block that pay
+/// at most the same tip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CumulativeGasAndReward {
+    /// The gas used by the transaction and all transactions sorted before it.
+    pub cumulative_gas_used: u64,
+    /// The effective tip per gas of the transaction.
+    pub reward: u128,
 }
 
 /// A cached entry for a block's fee history.
@@ -350,8 +362,8 @@ pub struct FeeHistoryEntry {
     pub gas_limit: u64,
     /// Hash of the block.
     pub header_hash: B256,
-    /// Approximated rewards for the configured percentiles.
-    pub rewards: Vec<u128>,
+    /// The effective tips of the transactions, sorted in ascending order.
+    pub sorted_rewards: Vec<CumulativeGasAndReward>,
     /// The timestamp of the block.
     pub timestamp: u64,
 }
@@ -359,7 +371,7 @@ pub struct FeeHistoryEntry {
 impl FeeHistoryEntry {
     /// Creates a new entry from a sealed block.
     ///
-    /// Note: This does not calculate the rewards for the block.
+    /// Note: This does not calculate the sorted rewards of the block.
     pub fn new(block: &SealedBlock) -> Self {
         Self {
             base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default(),
@@ -372,11 +384,16 @@ impl FeeHistoryEntry {
             gas_used: block.gas_used,
             header_hash: block.hash(),
             gas_limit: block.gas_limit,
-            rewards: Vec::new(),
+            sorted_rewards: Vec::new(),
             timestamp: block.timestamp,
         }
     }
 
+    /// Returns the rewards at the given percentiles of the gas used by the block.
+    pub fn rewards(&self, percentiles: &[f64]) -> Vec<u128> {
+        reward_percentiles(percentiles, self.gas_used, &self.sorted_rewards)
+    }
+
     /// Returns the base fee for the next block according to the EIP-1559 spec.
     pub fn next_block_base_fee(&self, chain_spec: impl EthChainSpec) -> u64 {
         calc_next_block_base_fee(
@@ -403,3 +420,22 @@ impl FeeHistoryEntry {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_percentiles_of_sorted_rewards() {
+        let sorted_rewards = [
+            CumulativeGasAndReward { cumulative_gas_used: 21_000, reward: 1 },
+            CumulativeGasAndReward { cumulative_gas_used: 71_000, reward: 5 },
+            CumulativeGasAndReward { cumulative_gas_used: 100_000, reward: 10 },
+        ];
+        assert_eq!(
+            reward_percentiles(&[0., 21., 21.5, 71., 90., 100.], 100_000, &sorted_rewards),
+            vec![1, 1, 5, 5, 10, 10]
+        );
+        assert_eq!(reward_percentiles(&[10., 50.], 0, &[]), vec![0, 0]);
+    }
+}
@@ -8,6 +8,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod blob;
 pub mod builder;
 pub mod cache;
 pub mod error;
@@ -20,8 +21,10 @@ pub mod receipt;
 pub mod revm_utils;
 pub mod simulate;
 pub mod transaction;
+pub mod user_operation;
 pub mod utils;
 
+pub use blob::BlockBlobSidecar;
 pub use builder::{
     config::{EthConfig, EthFilterConfig},
     ctx::EthApiBuilderCtx,
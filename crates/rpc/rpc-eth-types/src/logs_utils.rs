@@ -9,8 +9,50 @@ use reth_chainspec::ChainInfo;
 use reth_errors::ProviderError;
 use reth_primitives::{Receipt, SealedBlockWithSenders};
 use reth_storage_api::BlockReader;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// The position of a log in the chain, at which an `eth_getLogs` query whose logs exceed the
+/// maximum number of logs per response is continued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCursor {
+    /// The number of the block of the log.
+    pub block_number: u64,
+    /// The index of the log in its block.
+    pub log_index: u64,
+}
+
+impl LogCursor {
+    /// Returns the position of the given log.
+    pub fn of_log(log: &Log) -> Self {
+        Self {
+            block_number: log.block_number.unwrap_or_default(),
+            log_index: log.log_index.unwrap_or_default(),
+        }
+    }
+}
+
+/// Response of `eth_getLogs`.
+///
+/// If all matching logs are returned, this is serialized as the list of logs, like the response of
+/// other clients. Otherwise it is serialized as an object with the logs and the cursor at which the
+/// query continues.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LogsResponse {
+    /// All logs that match the filter.
+    All(Vec<Log>),
+    /// The first logs that match the filter, if they exceed the maximum number of logs per
+    /// response.
+    Partial {
+        /// The matching logs up to the maximum number of logs per response.
+        logs: Vec<Log>,
+        /// The position of the first matching log that is not part of the response.
+        cursor: LogCursor,
+    },
+}
+
 /// Returns all matching of a block's receipts when the transaction hashes are known.
 pub fn matching_block_logs_with_tx_hashes<'a, I>(
     filter: &FilteredParams,
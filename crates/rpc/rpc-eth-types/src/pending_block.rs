@@ -7,6 +7,7 @@ use std::time::Instant;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::B256;
 use derive_more::Constructor;
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{Receipt, SealedBlockWithSenders, SealedHeader};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 
@@ -90,4 +91,6 @@ pub struct PendingBlock {
     pub block: SealedBlockWithSenders,
     /// The receipts for the pending block
     pub receipts: Vec<Receipt>,
+    /// The state changes of the pending block on top of its parent block.
+    pub execution_outcome: ExecutionOutcome,
 }
@@ -1,41 +1,38 @@
 //! RPC receipt response builder, extends a layer one receipt with layer two data.
 
-use alloy_consensus::{ReceiptEnvelope, Transaction};
+use alloy_consensus::ReceiptEnvelope;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use alloy_primitives::{Address, TxKind};
 use alloy_rpc_types_eth::{Log, ReceiptWithBloom, TransactionReceipt};
-use reth_primitives::{Receipt, TransactionMeta, TransactionSigned, TxType};
+use reth_errors::RethError;
+use reth_primitives::{Receipt, SealedBlockWithSenders, TransactionMeta, TxType};
+use reth_primitives_traits::SignedTransaction;
 use revm_primitives::calc_blob_gasprice;
 
 use super::{EthApiError, EthResult};
 
 /// Builds an [`TransactionReceipt`] obtaining the inner receipt envelope from the given closure.
-pub fn build_receipt<T>(
-    transaction: &TransactionSigned,
+pub fn build_receipt<Tx, T>(
+    transaction: &Tx,
     meta: TransactionMeta,
     receipt: &Receipt,
     all_receipts: &[Receipt],
     build_envelope: impl FnOnce(ReceiptWithBloom<Log>) -> T,
-) -> EthResult<TransactionReceipt<T>> {
+) -> EthResult<TransactionReceipt<T>>
+where
+    Tx: SignedTransaction,
+{
     // Note: we assume this transaction is valid, because it's mined (or part of pending block)
     // and we don't need to check for pre EIP-2
     let from =
         transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
 
     // get the previous transaction cumulative gas used
-    let gas_used = if meta.index == 0 {
-        receipt.cumulative_gas_used
-    } else {
-        let prev_tx_idx = (meta.index - 1) as usize;
-        all_receipts
-            .get(prev_tx_idx)
-            .map(|prev_receipt| receipt.cumulative_gas_used - prev_receipt.cumulative_gas_used)
-            .unwrap_or_default()
-    };
-
-    let blob_gas_used = transaction.transaction.blob_gas_used();
-    // Blob gas price should only be present if the transaction is a blob transaction
-    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
-    let logs_bloom = receipt.bloom_slow();
+    let prev_cumulative_gas_used = meta
+        .index
+        .checked_sub(1)
+        .and_then(|prev_tx_idx| all_receipts.get(prev_tx_idx as usize))
+        .map(|prev_receipt| prev_receipt.cumulative_gas_used);
 
     // get number of logs in the block
     let mut num_logs = 0;
@@ -43,6 +40,101 @@ pub fn build_receipt<T>(
         num_logs += prev_receipt.logs.len();
     }
 
+    Ok(build_receipt_with_sender(
+        transaction,
+        from,
+        meta,
+        receipt,
+        prev_cumulative_gas_used,
+        num_logs,
+        build_envelope,
+    ))
+}
+
+/// Builds the [`TransactionReceipt`]s of all transactions of a block in a single pass.
+///
+/// Unlike calling [`EthReceiptBuilder::new`] for every transaction, this uses the recovered
+/// senders of the block and tracks the gas used and log index across the receipts, instead of
+/// recovering every signer and recounting the logs of all previous receipts.
+///
+/// Returns an error if the number of receipts doesn't match the number of transactions.
+pub fn build_block_receipts(
+    block: &SealedBlockWithSenders,
+    receipts: &[Receipt],
+) -> EthResult<Vec<TransactionReceipt>> {
+    if block.body.transactions.len() != receipts.len() {
+        return Err(EthApiError::Internal(RethError::msg(format!(
+            "block {} has {} transactions but {} receipts",
+            block.hash(),
+            block.body.transactions.len(),
+            receipts.len()
+        ))))
+    }
+
+    let mut prev_cumulative_gas_used = None;
+    let mut num_logs = 0;
+
+    let receipts = block
+        .body
+        .transactions
+        .iter()
+        .zip(&block.senders)
+        .zip(receipts)
+        .enumerate()
+        .map(|(idx, ((tx, from), receipt))| {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: idx as u64,
+                block_hash: block.hash(),
+                block_number: block.number,
+                base_fee: block.base_fee_per_gas,
+                excess_blob_gas: block.excess_blob_gas,
+                timestamp: block.timestamp,
+            };
+            let rpc_receipt = build_receipt_with_sender(
+                tx,
+                *from,
+                meta,
+                receipt,
+                prev_cumulative_gas_used,
+                num_logs,
+                |receipt_with_bloom| receipt_envelope(receipt.tx_type, receipt_with_bloom),
+            );
+            prev_cumulative_gas_used = Some(receipt.cumulative_gas_used);
+            num_logs += receipt.logs.len();
+            rpc_receipt
+        })
+        .collect();
+    Ok(receipts)
+}
+
+/// Builds an [`TransactionReceipt`] of a transaction with a known sender, given the cumulative gas
+/// used of the previous transaction and the number of logs of all previous transactions in the
+/// block.
+fn build_receipt_with_sender<Tx, T>(
+    transaction: &Tx,
+    from: Address,
+    meta: TransactionMeta,
+    receipt: &Receipt,
+    prev_cumulative_gas_used: Option<u64>,
+    num_logs: usize,
+    build_envelope: impl FnOnce(ReceiptWithBloom<Log>) -> T,
+) -> TransactionReceipt<T>
+where
+    Tx: SignedTransaction,
+{
+    let gas_used = match prev_cumulative_gas_used {
+        Some(prev) => receipt.cumulative_gas_used - prev,
+        None if meta.index == 0 => receipt.cumulative_gas_used,
+        None => 0,
+    };
+
+    let blob_gas_used =
+        transaction.blob_versioned_hashes().map(|hashes| hashes.len() as u64 * DATA_GAS_PER_BLOB);
+    // Blob gas price should only be present if the transaction is a blob transaction
+    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
+    let logs_bloom = receipt.bloom_slow();
+
     let logs: Vec<Log> = receipt
         .logs
         .iter()
@@ -65,12 +157,12 @@ pub fn build_receipt<T>(
         logs,
     };
 
-    let (contract_address, to) = match transaction.transaction.kind() {
-        TxKind::Create => (Some(from.create(transaction.transaction.nonce())), None),
+    let (contract_address, to) = match transaction.kind() {
+        TxKind::Create => (Some(from.create(transaction.nonce())), None),
         TxKind::Call(addr) => (None, Some(Address(*addr))),
     };
 
-    Ok(TransactionReceipt {
+    TransactionReceipt {
         inner: build_envelope(ReceiptWithBloom { receipt: rpc_receipt, logs_bloom }),
         transaction_hash: meta.tx_hash,
         transaction_index: Some(meta.index),
@@ -85,7 +177,23 @@ pub fn build_receipt<T>(
         blob_gas_price,
         blob_gas_used: blob_gas_used.map(u128::from),
         authorization_list: transaction.authorization_list().map(|l| l.to_vec()),
-    })
+    }
+}
+
+/// Wraps the receipt in the [`ReceiptEnvelope`] variant of its transaction type.
+fn receipt_envelope(
+    tx_type: TxType,
+    receipt_with_bloom: ReceiptWithBloom<Log>,
+) -> ReceiptEnvelope<Log> {
+    match tx_type {
+        TxType::Legacy => ReceiptEnvelope::Legacy(receipt_with_bloom),
+        TxType::Eip2930 => ReceiptEnvelope::Eip2930(receipt_with_bloom),
+        TxType::Eip1559 => ReceiptEnvelope::Eip1559(receipt_with_bloom),
+        TxType::Eip4844 => ReceiptEnvelope::Eip4844(receipt_with_bloom),
+        TxType::Eip7702 => ReceiptEnvelope::Eip7702(receipt_with_bloom),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    }
 }
 
 /// Receipt response builder.
@@ -101,21 +209,13 @@ impl EthReceiptBuilder {
     /// Note: This requires _all_ block receipts because we need to calculate the gas used by the
     /// transaction.
     pub fn new(
-        transaction: &TransactionSigned,
+        transaction: &impl SignedTransaction,
         meta: TransactionMeta,
         receipt: &Receipt,
         all_receipts: &[Receipt],
     ) -> EthResult<Self> {
         let base = build_receipt(transaction, meta, receipt, all_receipts, |receipt_with_bloom| {
-            match receipt.tx_type {
-                TxType::Legacy => ReceiptEnvelope::Legacy(receipt_with_bloom),
-                TxType::Eip2930 => ReceiptEnvelope::Eip2930(receipt_with_bloom),
-                TxType::Eip1559 => ReceiptEnvelope::Eip1559(receipt_with_bloom),
-                TxType::Eip4844 => ReceiptEnvelope::Eip4844(receipt_with_bloom),
-                TxType::Eip7702 => ReceiptEnvelope::Eip7702(receipt_with_bloom),
-                #[allow(unreachable_patterns)]
-                _ => unreachable!(),
-            }
+            receipt_envelope(receipt.tx_type, receipt_with_bloom)
         })?;
 
         Ok(Self { base })
@@ -126,3 +226,54 @@ impl EthReceiptBuilder {
         self.base
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use reth_testing_utils::generators::{self, random_block, random_receipt, BlockParams};
+
+    #[test]
+    fn block_receipts_match_receipt_builder() {
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(5), ..Default::default() })
+                .seal_with_senders()
+                .unwrap();
+
+        let mut cumulative_gas_used = 0;
+        let receipts = block
+            .body
+            .transactions
+            .iter()
+            .map(|tx| {
+                cumulative_gas_used += rng.gen_range(21_000..100_000);
+                Receipt { cumulative_gas_used, ..random_receipt(&mut rng, tx, Some(3)) }
+            })
+            .collect::<Vec<_>>();
+
+        let expected = block
+            .body
+            .transactions
+            .iter()
+            .zip(&receipts)
+            .enumerate()
+            .map(|(idx, (tx, receipt))| {
+                let meta = TransactionMeta {
+                    tx_hash: tx.hash,
+                    index: idx as u64,
+                    block_hash: block.hash(),
+                    block_number: block.number,
+                    base_fee: block.base_fee_per_gas,
+                    excess_blob_gas: block.excess_blob_gas,
+                    timestamp: block.timestamp,
+                };
+                EthReceiptBuilder::new(tx, meta, receipt, &receipts).unwrap().build()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(build_block_receipts(&block, &receipts).unwrap(), expected);
+
+        // receipts that don't match the transactions are rejected instead of truncated
+        assert!(build_block_receipts(&block, &receipts[1..]).is_err());
+    }
+}
@@ -307,5 +307,11 @@ pub fn build_block<T: TransactionCompat<Error: FromEthApiError>>(
         if full_transactions { BlockTransactionsKind::Full } else { BlockTransactionsKind::Hashes };
 
     let block = from_block(block, total_difficulty, txs_kind, None, tx_resp_builder)?;
+
+    // the block hash is only known once the header is complete
+    for log in calls.iter_mut().flat_map(|call| call.logs.iter_mut()) {
+        log.block_hash = Some(block.header.hash);
+    }
+
     Ok(SimulatedBlock { inner: block, calls })
 }
@@ -0,0 +1,672 @@
+//! Utilities for serving the ERC-4337 user operation methods of the `eth` namespace.
+//!
+//! See also <https://eips.ethereum.org/EIPS/eip-4337#rpc-methods-eth-namespace>
+
+mod rules;
+
+pub use rules::ValidationRulesInspector;
+
+use alloy_primitives::{address, keccak256, Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::erc4337::{PackedUserOperation, SendUserOperation, UserOperation};
+use alloy_sol_types::{SolCall, SolValue};
+use jsonrpsee_types::ErrorObject;
+use reth_rpc_server_types::result::rpc_err;
+use reth_transaction_pool::alt::UserOperationPoolError;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ToRpcError, EthApiError};
+
+/// The address of the v0.6 entry point.
+pub const ENTRY_POINT_V06: Address = address!("5ff137d4b0fdcd49dca30c7cf57e578a026d2789");
+
+/// The address of the v0.7 entry point.
+pub const ENTRY_POINT_V07: Address = address!("0000000071727de22e5e9d8baf0edac6f37da032");
+
+/// The address of the contract the v0.6 entry point deploys accounts through.
+pub const SENDER_CREATOR_V06: Address = address!("7fc98430eaedbb6070b35b39d798725049088348");
+
+/// The address of the contract the v0.7 entry point deploys accounts through.
+pub const SENDER_CREATOR_V07: Address = address!("efc2c1444ebcc4db75e7613d20c6a62ff67a167c");
+
+/// The entry points user operations can be submitted to.
+pub const SUPPORTED_ENTRY_POINTS: [Address; 2] = [ENTRY_POINT_V06, ENTRY_POINT_V07];
+
+/// The gas every bundle transaction pays, shared by the operations of the bundle.
+const BUNDLE_TRANSACTION_GAS: u64 = 21_000;
+
+/// The gas the entry point spends on every operation of a bundle.
+const PER_USER_OPERATION_GAS: u64 = 18_300;
+
+/// The gas the entry point spends on every word of an operation.
+const PER_USER_OPERATION_WORD_GAS: u64 = 4;
+
+/// The number of operations bundles are assumed to have when estimating the pre-verification gas.
+const ESTIMATED_BUNDLE_SIZE: u64 = 1;
+
+/// The entry point v0.6 interface.
+mod v06 {
+    alloy_sol_types::sol! {
+        struct UserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            uint256 callGasLimit;
+            uint256 verificationGasLimit;
+            uint256 preVerificationGas;
+            uint256 maxFeePerGas;
+            uint256 maxPriorityFeePerGas;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        function validateUserOp(
+            UserOperation calldata userOp,
+            bytes32 userOpHash,
+            uint256 missingAccountFunds
+        ) external returns (uint256 validationData);
+
+        function validatePaymasterUserOp(
+            UserOperation calldata userOp,
+            bytes32 userOpHash,
+            uint256 maxCost
+        ) external returns (bytes memory context, uint256 validationData);
+    }
+}
+
+/// The entry point v0.7 interface.
+mod v07 {
+    alloy_sol_types::sol! {
+        struct PackedUserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            bytes32 accountGasLimits;
+            uint256 preVerificationGas;
+            bytes32 gasFees;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        function validateUserOp(
+            PackedUserOperation calldata userOp,
+            bytes32 userOpHash,
+            uint256 missingAccountFunds
+        ) external returns (uint256 validationData);
+
+        function validatePaymasterUserOp(
+            PackedUserOperation calldata userOp,
+            bytes32 userOpHash,
+            uint256 maxCost
+        ) external returns (bytes memory context, uint256 validationData);
+    }
+}
+
+/// The functions shared by both entry point versions.
+mod shared {
+    alloy_sol_types::sol! {
+        function balanceOf(address account) external view returns (uint256);
+
+        function createSender(bytes calldata initCode) external returns (address sender);
+    }
+}
+
+/// A user operation as submitted to `eth_sendUserOperation` and `eth_estimateUserOperationGas`.
+///
+/// The entry point version is determined by the fields of the operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UserOperationRequest {
+    /// An operation for the v0.6 entry point, which has the `initCode` and `paymasterAndData`
+    /// fields.
+    EntryPointV06(UserOperation),
+    /// An operation for the v0.7 entry point.
+    EntryPointV07(PackedUserOperation),
+}
+
+impl From<UserOperationRequest> for SendUserOperation {
+    fn from(request: UserOperationRequest) -> Self {
+        match request {
+            UserOperationRequest::EntryPointV06(op) => Self::EntryPointV06(op),
+            UserOperationRequest::EntryPointV07(op) => Self::EntryPointV07(op),
+        }
+    }
+}
+
+/// The validation data returned by accounts and paymasters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationData {
+    /// The signature aggregator, [`ValidationData::SIGNATURE_FAILED`] if the signature is
+    /// invalid.
+    pub aggregator: Address,
+    /// The timestamp until which the operation is valid, `0` if it doesn't expire.
+    pub valid_until: u64,
+    /// The timestamp from which on the operation is valid.
+    pub valid_after: u64,
+}
+
+impl ValidationData {
+    /// The aggregator that marks an invalid signature.
+    pub const SIGNATURE_FAILED: Address = address!("0000000000000000000000000000000000000001");
+
+    /// Returns `true` if the signature is invalid.
+    pub fn signature_failed(&self) -> bool {
+        self.aggregator == Self::SIGNATURE_FAILED
+    }
+
+    /// Returns `true` if the operation is valid at the given timestamp.
+    pub const fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.valid_after <= timestamp && (self.valid_until == 0 || timestamp <= self.valid_until)
+    }
+}
+
+impl From<U256> for ValidationData {
+    fn from(value: U256) -> Self {
+        // validAfter (6 bytes) | validUntil (6 bytes) | aggregator (20 bytes)
+        let bytes = value.to_be_bytes::<32>();
+        let timestamp = |bytes: &[u8]| {
+            bytes.iter().fold(0u64, |timestamp, byte| (timestamp << 8) | *byte as u64)
+        };
+        Self {
+            aggregator: Address::from_slice(&bytes[12..]),
+            valid_until: timestamp(&bytes[6..12]),
+            valid_after: timestamp(&bytes[..6]),
+        }
+    }
+}
+
+/// Extension trait for the user operations of the supported entry points.
+pub trait UserOperationExt {
+    /// Returns the entry point the operation is encoded for.
+    fn entry_point(&self) -> Address;
+
+    /// Returns the contract the entry point deploys the account through.
+    fn sender_creator(&self) -> Address;
+
+    /// Returns the smart contract account.
+    fn sender(&self) -> Address;
+
+    /// Returns the data the account is called with in the execution phase.
+    fn call_data(&self) -> &Bytes;
+
+    /// Returns the factory address followed by the factory data, empty if the account exists.
+    fn init_code(&self) -> Bytes;
+
+    /// Returns the paymaster, if any.
+    fn paymaster(&self) -> Option<Address>;
+
+    /// Returns the maximum fee per gas.
+    fn max_fee_per_gas(&self) -> U256;
+
+    /// Returns the maximum priority fee per gas.
+    fn max_priority_fee_per_gas(&self) -> U256;
+
+    /// Returns the gas limits of the operation that must fit into 128 bits, with their names.
+    fn gas_limits(&self) -> Vec<(&'static str, U256)>;
+
+    /// Returns the pre-verification gas.
+    fn pre_verification_gas(&self) -> U256;
+
+    /// Returns the gas limit of the validation, including the deployment of the account and the
+    /// validation of the paymaster.
+    fn verification_gas_limit(&self) -> U256;
+
+    /// Returns the funds the entry point requires from the account or paymaster before executing
+    /// the operation.
+    fn required_prefund(&self) -> U256;
+
+    /// Returns the ABI encoding of the operation, as it's passed to the entry point.
+    fn abi_encode_operation(&self) -> Vec<u8>;
+
+    /// Returns the hash of the operation, as computed by the entry point.
+    fn hash(&self, chain_id: u64) -> B256;
+
+    /// Returns the input of the `validateUserOp` call of the account.
+    fn validate_user_op_input(&self, hash: B256, missing_account_funds: U256) -> Bytes;
+
+    /// Returns the input of the `validatePaymasterUserOp` call of the paymaster.
+    fn validate_paymaster_user_op_input(&self, hash: B256, max_cost: U256) -> Bytes;
+
+    /// Returns the gas the bundler spends on the operation outside of its execution.
+    ///
+    /// This is the calldata cost of the operation and its share of the bundle overhead.
+    fn estimate_pre_verification_gas(&self) -> u64 {
+        let encoded = self.abi_encode_operation();
+        let calldata_gas = encoded.iter().map(|byte| if *byte == 0 { 4 } else { 16 }).sum::<u64>();
+        let words = encoded.len().div_ceil(32) as u64;
+        calldata_gas +
+            BUNDLE_TRANSACTION_GAS / ESTIMATED_BUNDLE_SIZE +
+            PER_USER_OPERATION_GAS +
+            PER_USER_OPERATION_WORD_GAS * words
+    }
+}
+
+impl UserOperationExt for SendUserOperation {
+    fn entry_point(&self) -> Address {
+        match self {
+            Self::EntryPointV06(_) => ENTRY_POINT_V06,
+            Self::EntryPointV07(_) => ENTRY_POINT_V07,
+        }
+    }
+
+    fn sender_creator(&self) -> Address {
+        match self {
+            Self::EntryPointV06(_) => SENDER_CREATOR_V06,
+            Self::EntryPointV07(_) => SENDER_CREATOR_V07,
+        }
+    }
+
+    fn sender(&self) -> Address {
+        match self {
+            Self::EntryPointV06(op) => op.sender,
+            Self::EntryPointV07(op) => op.sender,
+        }
+    }
+
+    fn call_data(&self) -> &Bytes {
+        match self {
+            Self::EntryPointV06(op) => &op.call_data,
+            Self::EntryPointV07(op) => &op.call_data,
+        }
+    }
+
+    fn init_code(&self) -> Bytes {
+        match self {
+            Self::EntryPointV06(op) => op.init_code.clone(),
+            Self::EntryPointV07(op) => op
+                .factory
+                .map(|factory| {
+                    let data = op.factory_data.as_ref().map(|data| &data[..]).unwrap_or_default();
+                    [factory.as_slice(), data].concat().into()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn paymaster(&self) -> Option<Address> {
+        match self {
+            Self::EntryPointV06(op) => (op.paymaster_and_data.len() >= 20)
+                .then(|| Address::from_slice(&op.paymaster_and_data[..20])),
+            Self::EntryPointV07(op) => op.paymaster,
+        }
+    }
+
+    fn max_fee_per_gas(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.max_fee_per_gas,
+            Self::EntryPointV07(op) => op.max_fee_per_gas,
+        }
+    }
+
+    fn max_priority_fee_per_gas(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.max_priority_fee_per_gas,
+            Self::EntryPointV07(op) => op.max_priority_fee_per_gas,
+        }
+    }
+
+    fn gas_limits(&self) -> Vec<(&'static str, U256)> {
+        match self {
+            Self::EntryPointV06(op) => vec![
+                ("callGasLimit", op.call_gas_limit),
+                ("verificationGasLimit", op.verification_gas_limit),
+                ("preVerificationGas", op.pre_verification_gas),
+                ("maxFeePerGas", op.max_fee_per_gas),
+                ("maxPriorityFeePerGas", op.max_priority_fee_per_gas),
+            ],
+            Self::EntryPointV07(op) => vec![
+                ("callGasLimit", op.call_gas_limit),
+                ("verificationGasLimit", op.verification_gas_limit),
+                ("preVerificationGas", op.pre_verification_gas),
+                ("maxFeePerGas", op.max_fee_per_gas),
+                ("maxPriorityFeePerGas", op.max_priority_fee_per_gas),
+                (
+                    "paymasterVerificationGasLimit",
+                    op.paymaster_verification_gas_limit.unwrap_or_default(),
+                ),
+                ("paymasterPostOpGasLimit", op.paymaster_post_op_gas_limit.unwrap_or_default()),
+            ],
+        }
+    }
+
+    fn pre_verification_gas(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.pre_verification_gas,
+            Self::EntryPointV07(op) => op.pre_verification_gas,
+        }
+    }
+
+    fn verification_gas_limit(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.verification_gas_limit,
+            Self::EntryPointV07(op) => op
+                .verification_gas_limit
+                .saturating_add(op.paymaster_verification_gas_limit.unwrap_or_default()),
+        }
+    }
+
+    fn required_prefund(&self) -> U256 {
+        let required_gas = match self {
+            Self::EntryPointV06(op) => {
+                // the verification gas limit also covers the paymaster validation and post-op
+                let multiplier = if self.paymaster().is_some() { 3 } else { 1 };
+                op.call_gas_limit
+                    .saturating_add(
+                        op.verification_gas_limit.saturating_mul(U256::from(multiplier)),
+                    )
+                    .saturating_add(op.pre_verification_gas)
+            }
+            Self::EntryPointV07(op) => op
+                .verification_gas_limit
+                .saturating_add(op.call_gas_limit)
+                .saturating_add(op.paymaster_verification_gas_limit.unwrap_or_default())
+                .saturating_add(op.paymaster_post_op_gas_limit.unwrap_or_default())
+                .saturating_add(op.pre_verification_gas),
+        };
+        required_gas.saturating_mul(self.max_fee_per_gas())
+    }
+
+    fn abi_encode_operation(&self) -> Vec<u8> {
+        match self {
+            Self::EntryPointV06(op) => to_v06(op).abi_encode(),
+            Self::EntryPointV07(op) => to_v07(op).abi_encode(),
+        }
+    }
+
+    fn hash(&self, chain_id: u64) -> B256 {
+        let packed = match self {
+            Self::EntryPointV06(op) => (
+                op.sender,
+                op.nonce,
+                keccak256(&op.init_code),
+                keccak256(&op.call_data),
+                op.call_gas_limit,
+                op.verification_gas_limit,
+                op.pre_verification_gas,
+                op.max_fee_per_gas,
+                op.max_priority_fee_per_gas,
+                keccak256(&op.paymaster_and_data),
+            )
+                .abi_encode(),
+            Self::EntryPointV07(op) => {
+                let packed = to_v07(op);
+                (
+                    packed.sender,
+                    packed.nonce,
+                    keccak256(&packed.initCode),
+                    keccak256(&packed.callData),
+                    packed.accountGasLimits,
+                    packed.preVerificationGas,
+                    packed.gasFees,
+                    keccak256(&packed.paymasterAndData),
+                )
+                    .abi_encode()
+            }
+        };
+        keccak256((keccak256(packed), self.entry_point(), U256::from(chain_id)).abi_encode())
+    }
+
+    fn validate_user_op_input(&self, hash: B256, missing_account_funds: U256) -> Bytes {
+        match self {
+            Self::EntryPointV06(op) => v06::validateUserOpCall {
+                userOp: to_v06(op),
+                userOpHash: hash,
+                missingAccountFunds: missing_account_funds,
+            }
+            .abi_encode(),
+            Self::EntryPointV07(op) => v07::validateUserOpCall {
+                userOp: to_v07(op),
+                userOpHash: hash,
+                missingAccountFunds: missing_account_funds,
+            }
+            .abi_encode(),
+        }
+        .into()
+    }
+
+    fn validate_paymaster_user_op_input(&self, hash: B256, max_cost: U256) -> Bytes {
+        match self {
+            Self::EntryPointV06(op) => v06::validatePaymasterUserOpCall {
+                userOp: to_v06(op),
+                userOpHash: hash,
+                maxCost: max_cost,
+            }
+            .abi_encode(),
+            Self::EntryPointV07(op) => v07::validatePaymasterUserOpCall {
+                userOp: to_v07(op),
+                userOpHash: hash,
+                maxCost: max_cost,
+            }
+            .abi_encode(),
+        }
+        .into()
+    }
+}
+
+/// Converts the operation into the struct of the v0.6 entry point.
+fn to_v06(op: &UserOperation) -> v06::UserOperation {
+    v06::UserOperation {
+        sender: op.sender,
+        nonce: op.nonce,
+        initCode: op.init_code.clone(),
+        callData: op.call_data.clone(),
+        callGasLimit: op.call_gas_limit,
+        verificationGasLimit: op.verification_gas_limit,
+        preVerificationGas: op.pre_verification_gas,
+        maxFeePerGas: op.max_fee_per_gas,
+        maxPriorityFeePerGas: op.max_priority_fee_per_gas,
+        paymasterAndData: op.paymaster_and_data.clone(),
+        signature: op.signature.clone(),
+    }
+}
+
+/// Converts the operation into the packed struct of the v0.7 entry point.
+fn to_v07(op: &PackedUserOperation) -> v07::PackedUserOperation {
+    let init_code = SendUserOperation::EntryPointV07(op.clone()).init_code();
+    let paymaster_and_data = op
+        .paymaster
+        .map(|paymaster| {
+            [
+                paymaster.as_slice(),
+                &low_u128_bytes(op.paymaster_verification_gas_limit.unwrap_or_default()),
+                &low_u128_bytes(op.paymaster_post_op_gas_limit.unwrap_or_default()),
+                op.paymaster_data.as_ref().map(|data| &data[..]).unwrap_or_default(),
+            ]
+            .concat()
+            .into()
+        })
+        .unwrap_or_default();
+    v07::PackedUserOperation {
+        sender: op.sender,
+        nonce: op.nonce,
+        initCode: init_code,
+        callData: op.call_data.clone(),
+        accountGasLimits: pack_u128s(op.verification_gas_limit, op.call_gas_limit),
+        preVerificationGas: op.pre_verification_gas,
+        gasFees: pack_u128s(op.max_priority_fee_per_gas, op.max_fee_per_gas),
+        paymasterAndData: paymaster_and_data,
+        signature: op.signature.clone(),
+    }
+}
+
+/// Returns the big endian bytes of the lower 128 bits of the value.
+fn low_u128_bytes(value: U256) -> [u8; 16] {
+    let bytes = value.to_be_bytes::<32>();
+    bytes[16..].try_into().expect("16 bytes")
+}
+
+/// Packs two values into the upper and lower 128 bits of a word.
+fn pack_u128s(high: U256, low: U256) -> B256 {
+    let mut packed = [0u8; 32];
+    packed[..16].copy_from_slice(&low_u128_bytes(high));
+    packed[16..].copy_from_slice(&low_u128_bytes(low));
+    B256::from(packed)
+}
+
+/// Decodes the output of a `validateUserOp` call.
+pub fn decode_validate_user_op_output(output: &[u8]) -> Option<ValidationData> {
+    let validation_data = v06::validateUserOpCall::abi_decode_returns(output, true).ok()?;
+    Some(validation_data.validationData.into())
+}
+
+/// Decodes the output of a `validatePaymasterUserOp` call.
+pub fn decode_validate_paymaster_user_op_output(output: &[u8]) -> Option<ValidationData> {
+    let output = v06::validatePaymasterUserOpCall::abi_decode_returns(output, true).ok()?;
+    Some(output.validationData.into())
+}
+
+/// Returns the input of the `balanceOf` call of the entry point, which returns the deposit of an
+/// account.
+pub fn balance_of_input(account: Address) -> Bytes {
+    shared::balanceOfCall { account }.abi_encode().into()
+}
+
+/// Decodes the output of a `balanceOf` call.
+pub fn decode_balance_of_output(output: &[u8]) -> Option<U256> {
+    Some(shared::balanceOfCall::abi_decode_returns(output, true).ok()?._0)
+}
+
+/// Returns the input of the `createSender` call of the sender creator, which deploys the account
+/// with the factory of the init code.
+pub fn create_sender_input(init_code: Bytes) -> Bytes {
+    shared::createSenderCall { initCode: init_code }.abi_encode().into()
+}
+
+/// Errors returned by the ERC-4337 user operation methods.
+///
+/// The error codes are defined by the bundler RPC specification, see
+/// <https://github.com/eth-infinitism/bundler-spec>.
+#[derive(Debug, thiserror::Error)]
+pub enum UserOperationError {
+    /// The operation was submitted to an unsupported entry point or doesn't match its version.
+    #[error("unsupported entry point {0}")]
+    UnsupportedEntryPoint(Address),
+    /// A field of the operation is invalid.
+    #[error("invalid user operation: {0}")]
+    InvalidFields(String),
+    /// The account deployment or account validation reverted.
+    #[error("account validation reverted: {0}")]
+    ValidationReverted(Bytes),
+    /// The paymaster validation reverted.
+    #[error("paymaster validation reverted: {0}")]
+    PaymasterValidationReverted(Bytes),
+    /// The validation violated the ERC-7562 validation rules.
+    #[error("validation rule violated: {0}")]
+    RuleViolation(String),
+    /// The operation is expired or not yet valid.
+    #[error("user operation is not valid at the current timestamp")]
+    OutOfTimeRange,
+    /// The operation requires a signature aggregator.
+    #[error("unsupported signature aggregator {0}")]
+    UnsupportedAggregator(Address),
+    /// The execution of the operation reverted during gas estimation.
+    #[error("user operation execution reverted: {0}")]
+    ExecutionReverted(Bytes),
+    /// The signature of the operation is invalid.
+    #[error("invalid user operation signature")]
+    InvalidSignature,
+    /// The operation was rejected by the pool.
+    #[error(transparent)]
+    Pool(#[from] UserOperationPoolError),
+}
+
+impl UserOperationError {
+    const fn error_code(&self) -> i32 {
+        match self {
+            Self::UnsupportedEntryPoint(_) |
+            Self::InvalidFields(_) |
+            Self::Pool(
+                UserOperationPoolError::AlreadyKnown |
+                UserOperationPoolError::ReplacementUnderpriced,
+            ) => -32602,
+            Self::ValidationReverted(_) => -32500,
+            Self::PaymasterValidationReverted(_) => -32501,
+            Self::RuleViolation(_) => -32502,
+            Self::OutOfTimeRange => -32503,
+            Self::Pool(
+                UserOperationPoolError::SenderLimitExceeded(_) | UserOperationPoolError::PoolFull,
+            ) => -32504,
+            Self::UnsupportedAggregator(_) => -32506,
+            Self::InvalidSignature => -32507,
+            Self::ExecutionReverted(_) => -32521,
+        }
+    }
+}
+
+impl ToRpcError for UserOperationError {
+    fn to_rpc_error(&self) -> ErrorObject<'static> {
+        rpc_err(self.error_code(), self.to_string(), None)
+    }
+}
+
+impl From<UserOperationError> for EthApiError {
+    fn from(err: UserOperationError) -> Self {
+        Self::other(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, bytes};
+
+    #[test]
+    fn decode_validation_data() {
+        let value = U256::from_be_slice(&[
+            0, 0, 0, 0, 0, 10, // valid after
+            0, 0, 0, 0, 0, 20, // valid until
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // aggregator
+        ]);
+        let data = ValidationData::from(value);
+        assert!(data.signature_failed());
+        assert_eq!((data.valid_after, data.valid_until), (10, 20));
+        assert!(data.is_valid_at(15));
+        assert!(!data.is_valid_at(21));
+        assert!(ValidationData::from(U256::ZERO).is_valid_at(u64::MAX));
+    }
+
+    #[test]
+    fn request_entry_point_version() {
+        let v06 = r#"{"sender":"0x0000000000000000000000000000000000000001","nonce":"0x0","initCode":"0x","callData":"0x","callGasLimit":"0x1","verificationGasLimit":"0x1","preVerificationGas":"0x1","maxFeePerGas":"0x1","maxPriorityFeePerGas":"0x1","paymasterAndData":"0x","signature":"0x"}"#;
+        let request = serde_json::from_str::<UserOperationRequest>(v06).unwrap();
+        assert!(matches!(request, UserOperationRequest::EntryPointV06(_)));
+
+        let v07 = r#"{"sender":"0x0000000000000000000000000000000000000001","nonce":"0x0","callData":"0x","callGasLimit":"0x1","verificationGasLimit":"0x1","preVerificationGas":"0x1","maxFeePerGas":"0x1","maxPriorityFeePerGas":"0x1","signature":"0x"}"#;
+        let request = serde_json::from_str::<UserOperationRequest>(v07).unwrap();
+        assert!(matches!(request, UserOperationRequest::EntryPointV07(_)));
+    }
+
+    #[test]
+    fn packed_user_operation_encoding() {
+        let op = SendUserOperation::EntryPointV07(PackedUserOperation {
+            sender: Address::with_last_byte(1),
+            nonce: U256::ZERO,
+            factory: Some(Address::with_last_byte(2)),
+            factory_data: Some(bytes!("aabb")),
+            call_data: Bytes::new(),
+            call_gas_limit: U256::from(2),
+            verification_gas_limit: U256::from(1),
+            pre_verification_gas: U256::from(3),
+            max_fee_per_gas: U256::from(5),
+            max_priority_fee_per_gas: U256::from(4),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::new(),
+        });
+        assert_eq!(op.init_code(), bytes!("0000000000000000000000000000000000000002aabb"));
+        let SendUserOperation::EntryPointV07(packed) = &op else { unreachable!() };
+        let packed = to_v07(packed);
+        assert_eq!(
+            packed.accountGasLimits,
+            b256!("0000000000000000000000000000000100000000000000000000000000000002")
+        );
+        assert_eq!(
+            packed.gasFees,
+            b256!("0000000000000000000000000000000400000000000000000000000000000005")
+        );
+        assert_eq!(op.required_prefund(), U256::from(30));
+    }
+}
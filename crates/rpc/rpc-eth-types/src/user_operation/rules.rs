@@ -0,0 +1,189 @@
+//! Enforcement of the ERC-7562 validation rules.
+//!
+//! See also <https://eips.ethereum.org/EIPS/eip-7562>
+
+use alloy_primitives::{keccak256, Address, U256};
+use revm::{
+    interpreter::{opcode, CallInputs, CallOutcome, Interpreter, OpCode},
+    Database, EvmContext, Inspector,
+};
+
+/// The number of slots following a slot derived from the sender that are associated with the
+/// sender, e.g. the members of a struct in a mapping.
+const ASSOCIATED_SLOTS: u64 = 128;
+
+/// The opcodes the validation must not use, since their results can change between the
+/// validation and the inclusion of the operation.
+const BANNED_OPCODES: [u8; 15] = [
+    opcode::GASPRICE,
+    opcode::GASLIMIT,
+    opcode::DIFFICULTY,
+    opcode::TIMESTAMP,
+    opcode::BASEFEE,
+    opcode::BLOCKHASH,
+    opcode::NUMBER,
+    opcode::SELFBALANCE,
+    opcode::BALANCE,
+    opcode::ORIGIN,
+    opcode::CREATE,
+    opcode::COINBASE,
+    opcode::SELFDESTRUCT,
+    opcode::BLOBHASH,
+    opcode::BLOBBASEFEE,
+];
+
+/// An [`Inspector`] that records the first violation of the validation rules of the unstaked
+/// entities of a user operation.
+///
+/// This enforces a subset of the ERC-7562 rules:
+///  - the banned opcodes are never used, `GAS` is only used right before a call
+///  - `CREATE2` is only used by the factory
+///  - storage is only accessed if it's associated with the sender
+///  - value is only sent to the entry point
+#[derive(Debug, Clone)]
+pub struct ValidationRulesInspector {
+    /// The entry point of the operation.
+    entry_point: Address,
+    /// The account of the operation.
+    sender: Address,
+    /// Whether the account is currently deployed by the factory.
+    deploying: bool,
+    /// Whether the previous opcode was `GAS`.
+    after_gas: bool,
+    /// The storage slots derived from the sender by hashing.
+    sender_slots: Vec<U256>,
+    /// The first violation.
+    violation: Option<String>,
+}
+
+impl ValidationRulesInspector {
+    /// Creates a new inspector for the validation of an operation of the given sender.
+    pub const fn new(entry_point: Address, sender: Address) -> Self {
+        Self {
+            entry_point,
+            sender,
+            deploying: false,
+            after_gas: false,
+            sender_slots: Vec::new(),
+            violation: None,
+        }
+    }
+
+    /// Sets whether the inspected calls deploy the account, which allows using `CREATE2`.
+    pub fn set_deploying(&mut self, deploying: bool) {
+        self.deploying = deploying;
+    }
+
+    /// Returns the first violation of the rules, if any.
+    pub fn violation(&self) -> Option<&str> {
+        self.violation.as_deref()
+    }
+
+    /// Returns the first violation of the rules and resets it.
+    pub fn take_violation(&mut self) -> Option<String> {
+        self.violation.take()
+    }
+
+    /// Records the violation, unless there already is one.
+    fn violate(&mut self, address: Address, violation: impl FnOnce() -> String) {
+        if self.violation.is_none() {
+            self.violation = Some(format!("{} at {address}", violation()));
+        }
+    }
+
+    /// Returns `true` if the slot of a contract other than the sender is associated with the
+    /// sender.
+    fn is_associated_slot(&self, slot: U256) -> bool {
+        slot == U256::from_be_slice(self.sender.as_slice()) ||
+            self.sender_slots
+                .iter()
+                .any(|base| slot >= *base && slot - *base <= U256::from(ASSOCIATED_SLOTS))
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ValidationRulesInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let op = interp.current_opcode();
+        let address = interp.contract.target_address;
+        let name = || OpCode::new(op).map(OpCode::as_str).unwrap_or("INVALID");
+
+        if std::mem::take(&mut self.after_gas) &&
+            !matches!(
+                op,
+                opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL
+            )
+        {
+            self.violate(address, || "GAS opcode not followed by a call".to_string());
+        }
+
+        match op {
+            opcode::GAS => self.after_gas = true,
+            opcode::CREATE2 if !self.deploying => {
+                self.violate(address, || "CREATE2 outside of the account deployment".to_string())
+            }
+            opcode::KECCAK256 => {
+                // remember the slots of mappings that are keyed by the sender
+                let (Ok(offset), Ok(len)) = (interp.stack().peek(0), interp.stack().peek(1)) else {
+                    return
+                };
+                let (Ok(offset), Ok(len)) = (usize::try_from(offset), usize::try_from(len)) else {
+                    return
+                };
+                if len < 32 || offset.saturating_add(len) > interp.shared_memory.len() {
+                    return
+                }
+                let input = interp.shared_memory.slice(offset, len);
+                if input[..12].iter().all(|byte| *byte == 0) && input[12..32] == self.sender[..] {
+                    self.sender_slots.push(keccak256(input).into());
+                }
+            }
+            opcode::SLOAD | opcode::SSTORE | opcode::TLOAD | opcode::TSTORE => {
+                if address == self.sender || address == self.entry_point {
+                    return
+                }
+                let Ok(slot) = interp.stack().peek(0) else { return };
+                if !self.is_associated_slot(slot) {
+                    self.violate(address, || {
+                        format!("{} of slot {slot} not associated with the sender", name())
+                    });
+                }
+            }
+            op if BANNED_OPCODES.contains(&op) => {
+                self.violate(address, || format!("banned opcode {}", name()))
+            }
+            _ => {}
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.transfers_value() && inputs.target_address != self.entry_point {
+            self.violate(inputs.target_address, || {
+                "value transfer to a contract other than the entry point".to_string()
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn associated_slots() {
+        let sender = Address::with_last_byte(1);
+        let mut inspector = ValidationRulesInspector::new(Address::with_last_byte(2), sender);
+        let base = U256::from(1_000);
+        inspector.sender_slots.push(base);
+
+        assert!(inspector.is_associated_slot(U256::from(1)));
+        assert!(inspector.is_associated_slot(base));
+        assert!(inspector.is_associated_slot(base + U256::from(ASSOCIATED_SLOTS)));
+        assert!(!inspector.is_associated_slot(base + U256::from(ASSOCIATED_SLOTS + 1)));
+        assert!(!inspector.is_associated_slot(base - U256::from(1)));
+    }
+}
@@ -0,0 +1,25 @@
+//! GraphQL server of the node, serving the block, transaction, log and account queries of the
+//! [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767) schema.
+//!
+//! The schema is read-only and resolves queries directly against the provider. The `pending`,
+//! `syncing` and `gasPrice` queries, the `call` and `estimateGas` fields of blocks and the
+//! `sendRawTransaction` mutation of EIP-1767 are not supported.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod scalars;
+
+mod schema;
+pub use schema::{
+    schema, Account, Block, BlockFilterCriteria, FilterCriteria, GraphQlProvider, GraphQlSchema,
+    Log, Query, Transaction, MAX_BLOCK_RANGE, MAX_QUERY_COMPLEXITY, MAX_QUERY_DEPTH,
+};
+
+mod server;
+pub use server::start_graphql_server;
@@ -0,0 +1,121 @@
+//! The scalar types of the EIP-1767 schema.
+//!
+//! All scalars are serialized as `0x`-prefixed hex strings. Numbers are also accepted as decimal
+//! strings or GraphQL integers.
+
+use alloy_primitives::{hex, U256};
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// A 64 bit unsigned integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Long(pub u64);
+
+#[Scalar(name = "Long")]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => {
+                number.as_u64().map(Self).ok_or_else(|| InputValueError::expected_type(value))
+            }
+            Value::String(s) => {
+                let parsed = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                parsed.map(Self).map_err(|_| InputValueError::expected_type(value))
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// An arbitrarily large integer, up to 256 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigInt(pub U256);
+
+#[Scalar(name = "BigInt")]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number
+                .as_u64()
+                .map(|n| Self(U256::from(n)))
+                .ok_or_else(|| InputValueError::expected_type(value)),
+            Value::String(s) => {
+                s.parse().map(Self).map_err(|_| InputValueError::expected_type(value))
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+macro_rules! hex_scalar {
+    ($(#[$attr:meta])* $name:ident($inner:ty)) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub $inner);
+
+        #[Scalar]
+        impl ScalarType for $name {
+            fn parse(value: Value) -> InputValueResult<Self> {
+                match &value {
+                    Value::String(s) => {
+                        s.parse().map(Self).map_err(|_| InputValueError::expected_type(value))
+                    }
+                    _ => Err(InputValueError::expected_type(value)),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(hex::encode_prefixed(&self.0))
+            }
+        }
+    };
+}
+
+hex_scalar!(
+    /// A 32 byte value, e.g. a hash.
+    Bytes32(alloy_primitives::B256)
+);
+
+hex_scalar!(
+    /// A 20 byte account address.
+    Address(alloy_primitives::Address)
+);
+
+hex_scalar!(
+    /// An arbitrary length binary string.
+    Bytes(alloy_primitives::Bytes)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scalars() {
+        assert_eq!(Long::parse(Value::from(10)).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::from("10")).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::from("0x10")).unwrap(), Long(16));
+        assert!(Long::parse(Value::from("0xzz")).is_err());
+        assert_eq!(Long(16).to_value(), Value::from("0x10"));
+
+        assert_eq!(BigInt::parse(Value::from("0x100")).unwrap(), BigInt(U256::from(256)));
+        assert_eq!(BigInt::parse(Value::from("256")).unwrap(), BigInt(U256::from(256)));
+        assert_eq!(BigInt(U256::from(256)).to_value(), Value::from("0x100"));
+
+        let address = alloy_primitives::Address::with_last_byte(1);
+        let value = Address(address).to_value();
+        assert_eq!(value, Value::from("0x0000000000000000000000000000000000000001"));
+        assert_eq!(Address::parse(value).unwrap(), Address(address));
+        assert!(Bytes32::parse(Value::from("0x01")).is_err());
+    }
+}
@@ -0,0 +1,802 @@
+//! The read-only queries of the EIP-1767 schema, resolved against the provider.
+
+use crate::scalars::{Address, BigInt, Bytes, Bytes32, Long};
+use alloy_consensus::{transaction::to_eip155_value, Transaction as _};
+use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
+use alloy_primitives::{BloomInput, TxKind, U256};
+use async_graphql::{EmptyMutation, EmptySubscription, InputObject, Object, Result, Schema};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_primitives::{Receipt, SealedBlockWithSenders, SealedHeader, TransactionSigned, TxType};
+use reth_storage_api::{
+    BlockReaderIdExt, StateProviderBox, StateProviderFactory, TransactionVariant,
+};
+use std::{ops::RangeInclusive, sync::Arc};
+use tokio::sync::OnceCell;
+
+/// The maximum number of blocks a single `blocks` or `logs` query may cover.
+pub const MAX_BLOCK_RANGE: u64 = 1_000;
+
+/// The maximum depth of a query.
+pub const MAX_QUERY_DEPTH: usize = 16;
+
+/// The maximum complexity of a query.
+///
+/// Every field adds one to the complexity, the fields of the blocks and logs of a block range
+/// count once per block of the range.
+pub const MAX_QUERY_COMPLEXITY: usize = 100_000;
+
+/// The read-only EIP-1767 schema, mutations and subscriptions are not supported.
+pub type GraphQlSchema<P> = Schema<Query<P>, EmptyMutation, EmptySubscription>;
+
+/// Builds the schema that resolves queries against the provider.
+pub fn schema<P: GraphQlProvider>(provider: P) -> GraphQlSchema<P> {
+    Schema::build(Query { provider }, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Runs a provider read on a blocking task, so it doesn't block the executor.
+async fn blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await?
+}
+
+/// Returns the complexity of a field that resolves `child_complexity` for every block of a range.
+///
+/// Ranges without an end are assumed to cover [`MAX_BLOCK_RANGE`] blocks, since the latest block
+/// isn't known when the complexity is computed.
+fn block_range_complexity(from: Option<Long>, to: Option<Long>, child_complexity: usize) -> usize {
+    let blocks = match (from, to) {
+        (Some(from), Some(to)) => (to.0.saturating_sub(from.0) + 1).min(MAX_BLOCK_RANGE),
+        (Some(_), None) => MAX_BLOCK_RANGE,
+        (None, _) => 1,
+    };
+    child_complexity.saturating_mul(blocks as usize)
+}
+
+/// The provider the schema resolves queries against.
+pub trait GraphQlProvider:
+    BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + Clone + 'static
+{
+}
+
+impl<T> GraphQlProvider for T where
+    T: BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + Clone + 'static
+{
+}
+
+/// The root of all queries.
+#[derive(Debug)]
+pub struct Query<P> {
+    provider: P,
+}
+
+#[Object(name = "Query")]
+impl<P: GraphQlProvider> Query<P> {
+    /// Fetches a block by number or hash, or the latest block if neither is given.
+    async fn block(&self, number: Option<Long>, hash: Option<Bytes32>) -> Result<Option<Block<P>>> {
+        let provider = self.provider.clone();
+        blocking(move || {
+            let id = match (number, hash) {
+                (Some(_), Some(_)) => {
+                    return Err("only one of number or hash must be specified".into())
+                }
+                (Some(number), None) => number.0.into(),
+                (None, Some(hash)) => hash.0.into(),
+                (None, None) => provider.best_block_number()?.into(),
+            };
+            Block::load(&provider, id)
+        })
+        .await
+    }
+
+    /// Fetches the blocks in the inclusive range, up to the latest block if `to` is not given.
+    #[graphql(complexity = "block_range_complexity(Some(from), to, child_complexity)")]
+    async fn blocks(&self, from: Long, to: Option<Long>) -> Result<Vec<Block<P>>> {
+        let provider = self.provider.clone();
+        blocking(move || {
+            let range = block_range(&provider, Some(from), to)?;
+            Ok(provider
+                .sealed_block_with_senders_range(range)?
+                .into_iter()
+                .map(|block| Block::new(provider.clone(), block))
+                .collect())
+        })
+        .await
+    }
+
+    /// Fetches a transaction by hash.
+    async fn transaction(&self, hash: Bytes32) -> Result<Option<Transaction<P>>> {
+        let provider = self.provider.clone();
+        blocking(move || {
+            let Some((_, meta)) = provider.transaction_by_hash_with_meta(hash.0)? else {
+                return Ok(None)
+            };
+            let block = Block::load(&provider, meta.block_hash.into())?;
+            Ok(block.map(|block| Transaction { block, index: meta.index as usize }))
+        })
+        .await
+    }
+
+    /// Returns the logs matching the filter, from the latest block if the filter has no range.
+    #[graphql(
+        complexity = "block_range_complexity(filter.from_block, filter.to_block, child_complexity)"
+    )]
+    async fn logs(&self, filter: FilterCriteria) -> Result<Vec<Log<P>>> {
+        let criteria = BlockFilterCriteria { addresses: filter.addresses, topics: filter.topics };
+        let provider = self.provider.clone();
+        let bloom_criteria = criteria.clone();
+        let blocks = blocking(move || {
+            let range = block_range(&provider, filter.from_block, filter.to_block)?;
+            let mut blocks = Vec::new();
+            for header in provider.sealed_headers_range(range)? {
+                if !bloom_criteria.matches_bloom(&header) {
+                    continue
+                }
+                blocks.extend(Block::load(&provider, header.hash().into())?);
+            }
+            Ok(blocks)
+        })
+        .await?;
+
+        let mut logs = Vec::new();
+        for block in blocks {
+            logs.extend(block.filter_logs(&criteria).await?);
+        }
+        Ok(logs)
+    }
+
+    /// The chain ID used for transaction signing.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self) -> BigInt {
+        BigInt(U256::from(self.provider.chain_spec().chain_id()))
+    }
+}
+
+/// Returns the block range of a query, checked against [`MAX_BLOCK_RANGE`].
+fn block_range<P: GraphQlProvider>(
+    provider: &P,
+    from: Option<Long>,
+    to: Option<Long>,
+) -> Result<RangeInclusive<u64>> {
+    let latest = provider.best_block_number()?;
+    let to = to.map_or(latest, |to| to.0.min(latest));
+    let from = from.map_or(to, |from| from.0);
+    if from > to {
+        return Err("from block is after to block".into())
+    }
+    if to - from >= MAX_BLOCK_RANGE {
+        return Err(format!("block range exceeds the maximum of {MAX_BLOCK_RANGE} blocks").into())
+    }
+    Ok(from..=to)
+}
+
+/// A block with its transactions.
+#[derive(Debug)]
+pub struct Block<P> {
+    provider: P,
+    block: Arc<SealedBlockWithSenders>,
+    /// The receipts of the block, loaded by the first field that needs them.
+    receipts: Arc<OnceCell<Vec<Receipt>>>,
+}
+
+impl<P> Clone for Block<P>
+where
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            block: self.block.clone(),
+            receipts: self.receipts.clone(),
+        }
+    }
+}
+
+impl<P: GraphQlProvider> Block<P> {
+    fn new(provider: P, block: SealedBlockWithSenders) -> Self {
+        Self { provider, block: Arc::new(block), receipts: Default::default() }
+    }
+
+    fn load(provider: &P, id: BlockHashOrNumber) -> Result<Option<Self>> {
+        Ok(provider
+            .sealed_block_with_senders(id, TransactionVariant::WithHash)?
+            .map(|block| Self::new(provider.clone(), block)))
+    }
+
+    fn header(&self) -> &SealedHeader {
+        &self.block.header
+    }
+
+    /// Returns the receipts of the block, they are only loaded once.
+    async fn receipts(&self) -> Result<&[Receipt]> {
+        let receipts = self
+            .receipts
+            .get_or_try_init(|| {
+                let provider = self.provider.clone();
+                let hash = self.header().hash();
+                blocking(move || {
+                    provider
+                        .receipts_by_block(hash.into())?
+                        .ok_or_else(|| format!("receipts of block {hash} not found").into())
+                })
+            })
+            .await?;
+        Ok(receipts)
+    }
+
+    /// Returns the logs of the block that match the criteria.
+    async fn filter_logs(&self, criteria: &BlockFilterCriteria) -> Result<Vec<Log<P>>> {
+        let mut logs = Vec::new();
+        let mut log_index = 0;
+        for (index, receipt) in self.receipts().await?.iter().enumerate() {
+            for log in &receipt.logs {
+                if criteria.matches(log) {
+                    let transaction = Transaction { block: self.clone(), index };
+                    logs.push(Log { transaction, index: log_index, log: log.clone() });
+                }
+                log_index += 1;
+            }
+        }
+        Ok(logs)
+    }
+
+    fn account_at_block(&self, address: alloy_primitives::Address) -> Account<P> {
+        Account { provider: self.provider.clone(), address, block: Some(self.header().number) }
+    }
+}
+
+#[Object(name = "Block")]
+impl<P: GraphQlProvider> Block<P> {
+    /// The block number.
+    async fn number(&self) -> Long {
+        Long(self.header().number)
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.header().hash())
+    }
+
+    /// The parent block, or null for the genesis block.
+    async fn parent(&self) -> Result<Option<Self>> {
+        if self.header().number == 0 {
+            return Ok(None)
+        }
+        let provider = self.provider.clone();
+        let parent_hash = self.header().parent_hash;
+        blocking(move || Self::load(&provider, parent_hash.into())).await
+    }
+
+    /// The block nonce, an 8 byte sequence determined by the miner.
+    async fn nonce(&self) -> Bytes {
+        Bytes(self.header().nonce.to_vec().into())
+    }
+
+    /// The root of the transaction trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.header().transactions_root)
+    }
+
+    /// The number of transactions in the block.
+    async fn transaction_count(&self) -> Long {
+        Long(self.block.body.transactions.len() as u64)
+    }
+
+    /// The root of the final state trie of the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.header().state_root)
+    }
+
+    /// The root of the receipts trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.header().receipts_root)
+    }
+
+    /// The account that mined the block, at the given block or this block.
+    async fn miner(&self, block: Option<Long>) -> Account<P> {
+        let mut account = self.account_at_block(self.header().beneficiary);
+        account.block = block.map(|block| block.0).or(account.block);
+        account
+    }
+
+    /// Arbitrary data included by the miner.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.header().extra_data.clone())
+    }
+
+    /// The maximum amount of gas the transactions of the block may use.
+    async fn gas_limit(&self) -> Long {
+        Long(self.header().gas_limit)
+    }
+
+    /// The amount of gas used by the transactions of the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.header().gas_used)
+    }
+
+    /// The base fee per gas of the block, if it is after London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.header().base_fee_per_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The unix timestamp of the block.
+    async fn timestamp(&self) -> Long {
+        Long(self.header().timestamp)
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(self.header().logs_bloom.to_vec().into())
+    }
+
+    /// The mix hash of the block, or the prev randao value after the merge.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.header().mix_hash)
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.header().difficulty)
+    }
+
+    /// The hash of the ommers of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.header().ommers_hash)
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self) -> Long {
+        Long(self.block.body.ommers.len() as u64)
+    }
+
+    /// The root of the withdrawals trie of the block, if it is after Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.header().withdrawals_root.map(Bytes32)
+    }
+
+    /// The RLP encoded header of the block.
+    async fn raw_header(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(self.header().header()).into())
+    }
+
+    /// The RLP encoded block.
+    async fn raw(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(self.block.block.clone().unseal()).into())
+    }
+
+    /// The transactions of the block.
+    async fn transactions(&self) -> Vec<Transaction<P>> {
+        (0..self.block.body.transactions.len())
+            .map(|index| Transaction { block: self.clone(), index })
+            .collect()
+    }
+
+    /// The transaction at the given index of the block.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction<P>> {
+        let index = index.0 as usize;
+        (index < self.block.body.transactions.len())
+            .then(|| Transaction { block: self.clone(), index })
+    }
+
+    /// The logs of the block that match the filter.
+    async fn logs(&self, filter: BlockFilterCriteria) -> Result<Vec<Log<P>>> {
+        self.filter_logs(&filter).await
+    }
+
+    /// An account, at the state after this block.
+    async fn account(&self, address: Address) -> Account<P> {
+        self.account_at_block(address.0)
+    }
+}
+
+/// A transaction of a block.
+#[derive(Debug)]
+pub struct Transaction<P> {
+    block: Block<P>,
+    index: usize,
+}
+
+impl<P: GraphQlProvider> Transaction<P> {
+    fn tx(&self) -> &TransactionSigned {
+        &self.block.block.body.transactions[self.index]
+    }
+
+    fn sender(&self) -> alloy_primitives::Address {
+        self.block.block.senders[self.index]
+    }
+
+    fn price_per_gas(&self) -> u128 {
+        self.tx().effective_gas_price(self.block.header().base_fee_per_gas)
+    }
+
+    /// Returns the receipt of the transaction and the gas used by the preceding transactions.
+    async fn receipt(&self) -> Result<(&Receipt, u64)> {
+        let receipts = self.block.receipts().await?;
+        let Some(receipt) = receipts.get(self.index) else {
+            return Err(format!("receipt of transaction {} not found", self.tx().hash()).into())
+        };
+        let preceding_gas =
+            self.index.checked_sub(1).map_or(0, |prev| receipts[prev].cumulative_gas_used);
+        Ok((receipt, preceding_gas))
+    }
+}
+
+#[Object(name = "Transaction")]
+impl<P: GraphQlProvider> Transaction<P> {
+    /// The transaction hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx().hash())
+    }
+
+    /// The nonce of the sender at the time of the transaction.
+    async fn nonce(&self) -> Long {
+        Long(self.tx().nonce())
+    }
+
+    /// The index of the transaction in its block.
+    async fn index(&self) -> Long {
+        Long(self.index as u64)
+    }
+
+    /// The sender of the transaction, at the given block or the block of the transaction.
+    async fn from(&self, block: Option<Long>) -> Account<P> {
+        let mut account = self.block.account_at_block(self.sender());
+        account.block = block.map(|block| block.0).or(account.block);
+        account
+    }
+
+    /// The recipient of the transaction, or null for contract creations.
+    async fn to(&self, block: Option<Long>) -> Option<Account<P>> {
+        let TxKind::Call(to) = self.tx().kind() else { return None };
+        let mut account = self.block.account_at_block(to);
+        account.block = block.map(|block| block.0).or(account.block);
+        Some(account)
+    }
+
+    /// The value sent by the transaction, in wei.
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx().value())
+    }
+
+    /// The price per gas paid by the sender, in wei.
+    async fn gas_price(&self) -> BigInt {
+        BigInt(U256::from(self.price_per_gas()))
+    }
+
+    /// The maximum fee per gas of dynamic fee transactions.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        let tx = self.tx();
+        tx.is_dynamic_fee().then(|| BigInt(U256::from(tx.max_fee_per_gas())))
+    }
+
+    /// The maximum priority fee per gas of dynamic fee transactions.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx().max_priority_fee_per_gas().map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The priority fee per gas paid to the miner.
+    async fn effective_tip(&self) -> Option<BigInt> {
+        let base_fee = self.block.header().base_fee_per_gas.unwrap_or_default();
+        self.tx().effective_tip_per_gas(base_fee).map(|tip| BigInt(U256::from(tip)))
+    }
+
+    /// The maximum amount of gas the transaction may use.
+    async fn gas(&self) -> Long {
+        Long(self.tx().gas_limit())
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx().input().clone())
+    }
+
+    /// The block the transaction was included in.
+    async fn block(&self) -> Block<P> {
+        self.block.clone()
+    }
+
+    /// The status of the transaction, 1 for success and 0 for failure.
+    async fn status(&self) -> Result<Long> {
+        Ok(Long(self.receipt().await?.0.success as u64))
+    }
+
+    /// The amount of gas used by the transaction.
+    async fn gas_used(&self) -> Result<Long> {
+        let (receipt, preceding_gas) = self.receipt().await?;
+        Ok(Long(receipt.cumulative_gas_used - preceding_gas))
+    }
+
+    /// The total amount of gas used by the transactions of the block up to this one.
+    async fn cumulative_gas_used(&self) -> Result<Long> {
+        Ok(Long(self.receipt().await?.0.cumulative_gas_used))
+    }
+
+    /// The price per gas paid by the sender, in wei.
+    async fn effective_gas_price(&self) -> BigInt {
+        BigInt(U256::from(self.price_per_gas()))
+    }
+
+    /// The contract created by the transaction, if it is a contract creation.
+    async fn created_contract(&self, block: Option<Long>) -> Option<Account<P>> {
+        if !self.tx().kind().is_create() {
+            return None
+        }
+        let address = self.sender().create(self.tx().nonce());
+        let mut account = self.block.account_at_block(address);
+        account.block = block.map(|block| block.0).or(account.block);
+        Some(account)
+    }
+
+    /// The logs emitted by the transaction.
+    async fn logs(&self) -> Result<Vec<Log<P>>> {
+        let receipts = self.block.receipts().await?;
+        let Some(receipt) = receipts.get(self.index) else { return Ok(Vec::new()) };
+        let first_log_index = receipts[..self.index].iter().map(|r| r.logs.len()).sum::<usize>();
+        Ok(receipt
+            .logs
+            .iter()
+            .enumerate()
+            .map(|(index, log)| {
+                let transaction = Self { block: self.block.clone(), index: self.index };
+                Log { transaction, index: first_log_index + index, log: log.clone() }
+            })
+            .collect())
+    }
+
+    /// The `r` value of the signature.
+    async fn r(&self) -> BigInt {
+        BigInt(self.tx().signature().r())
+    }
+
+    /// The `s` value of the signature.
+    async fn s(&self) -> BigInt {
+        BigInt(self.tx().signature().s())
+    }
+
+    /// The `v` value of the signature, including the chain ID for EIP-155 legacy transactions.
+    async fn v(&self) -> BigInt {
+        let tx = self.tx();
+        let y_parity = tx.signature().v();
+        let v = if tx.tx_type() == TxType::Legacy {
+            to_eip155_value(y_parity, tx.chain_id())
+        } else {
+            y_parity as u128
+        };
+        BigInt(U256::from(v))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn ty(&self) -> Long {
+        Long(self.tx().ty() as u64)
+    }
+
+    /// The EIP-2718 encoded transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.tx().encoded_2718().into())
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug)]
+pub struct Log<P> {
+    transaction: Transaction<P>,
+    index: usize,
+    log: alloy_primitives::Log,
+}
+
+#[Object(name = "Log")]
+impl<P: GraphQlProvider> Log<P> {
+    /// The index of the log in its block.
+    async fn index(&self) -> Long {
+        Long(self.index as u64)
+    }
+
+    /// The account that emitted the log, at the given block or the block of the log.
+    async fn account(&self, block: Option<Long>) -> Account<P> {
+        let mut account = self.transaction.block.account_at_block(self.log.address);
+        account.block = block.map(|block| block.0).or(account.block);
+        account
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.log.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.log.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self) -> Transaction<P> {
+        Transaction { block: self.transaction.block.clone(), index: self.transaction.index }
+    }
+}
+
+/// An account at a block, or at the latest block if none is set.
+#[derive(Debug)]
+pub struct Account<P> {
+    provider: P,
+    address: alloy_primitives::Address,
+    block: Option<u64>,
+}
+
+impl<P: GraphQlProvider> Account<P> {
+    /// Reads from the state at the block of the account on a blocking task.
+    async fn with_state<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(StateProviderBox, alloy_primitives::Address) -> Result<T> + Send + 'static,
+    {
+        let provider = self.provider.clone();
+        let (address, block) = (self.address, self.block);
+        blocking(move || {
+            let state = match block {
+                Some(block) => provider.history_by_block_number(block)?,
+                None => provider.latest()?,
+            };
+            f(state, address)
+        })
+        .await
+    }
+}
+
+#[Object(name = "Account")]
+impl<P: GraphQlProvider> Account<P> {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account, in wei.
+    async fn balance(&self) -> Result<BigInt> {
+        self.with_state(|state, address| {
+            Ok(BigInt(state.account_balance(address)?.unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The number of transactions sent by the account.
+    async fn transaction_count(&self) -> Result<Long> {
+        self.with_state(|state, address| {
+            Ok(Long(state.account_nonce(address)?.unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The code of the contract, empty if the account is not a contract.
+    async fn code(&self) -> Result<Bytes> {
+        self.with_state(|state, address| {
+            let code = state.account_code(address)?;
+            Ok(Bytes(code.map(|code| code.original_bytes()).unwrap_or_default()))
+        })
+        .await
+    }
+
+    /// The value of a storage slot of the account.
+    async fn storage(&self, slot: Bytes32) -> Result<Bytes32> {
+        self.with_state(move |state, address| {
+            let value = state.storage(address, slot.0)?.unwrap_or_default();
+            Ok(Bytes32(value.to_be_bytes().into()))
+        })
+        .await
+    }
+}
+
+/// The filter of the `logs` query.
+#[derive(Debug, Clone, InputObject)]
+pub struct FilterCriteria {
+    /// The first block to search, the latest block if not set.
+    pub from_block: Option<Long>,
+    /// The last block to search, the latest block if not set.
+    pub to_block: Option<Long>,
+    /// The addresses that may have emitted the logs, any address if empty.
+    pub addresses: Option<Vec<Address>>,
+    /// The topics the logs must have at each position, any topic if empty.
+    pub topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// The filter of the `logs` field of a block.
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct BlockFilterCriteria {
+    /// The addresses that may have emitted the logs, any address if empty.
+    pub addresses: Option<Vec<Address>>,
+    /// The topics the logs must have at each position, any topic if empty.
+    pub topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+impl BlockFilterCriteria {
+    /// Returns `true` if the log matches the criteria.
+    fn matches(&self, log: &alloy_primitives::Log) -> bool {
+        let address_matches = self.addresses.as_ref().is_none_or(|addresses| {
+            addresses.is_empty() || addresses.iter().any(|address| address.0 == log.address)
+        });
+        let topics_match = self.topics.as_ref().is_none_or(|topics| {
+            topics.iter().enumerate().all(|(idx, topics)| {
+                topics.is_empty() ||
+                    log.topics()
+                        .get(idx)
+                        .is_some_and(|topic| topics.iter().any(|expected| expected.0 == *topic))
+            })
+        });
+        address_matches && topics_match
+    }
+
+    /// Returns `true` if the bloom filter of the block may contain matching logs.
+    fn matches_bloom(&self, header: &SealedHeader) -> bool {
+        let bloom = &header.logs_bloom;
+        let address_matches = self.addresses.as_ref().is_none_or(|addresses| {
+            addresses.is_empty() ||
+                addresses
+                    .iter()
+                    .any(|address| bloom.contains_input(BloomInput::Raw(&address.0[..])))
+        });
+        let topics_match = self.topics.as_ref().is_none_or(|topics| {
+            topics.iter().all(|topics| {
+                topics.is_empty() ||
+                    topics
+                        .iter()
+                        .any(|topic| bloom.contains_input(BloomInput::Raw(&topic.0[..])))
+            })
+        });
+        address_matches && topics_match
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bloom, LogData, B256};
+    use reth_provider::test_utils::MockEthProvider;
+
+    fn log(address: u8, topics: Vec<B256>) -> alloy_primitives::Log {
+        alloy_primitives::Log {
+            address: alloy_primitives::Address::with_last_byte(address),
+            data: LogData::new_unchecked(topics, Default::default()),
+        }
+    }
+
+    #[test]
+    fn block_filter_criteria() {
+        let topic = B256::with_last_byte(1);
+        let criteria = BlockFilterCriteria {
+            addresses: Some(vec![Address(alloy_primitives::Address::with_last_byte(1))]),
+            topics: Some(vec![vec![], vec![Bytes32(topic)]]),
+        };
+        assert!(criteria.matches(&log(1, vec![B256::ZERO, topic])));
+        assert!(!criteria.matches(&log(2, vec![B256::ZERO, topic])));
+        assert!(!criteria.matches(&log(1, vec![topic])));
+        assert!(BlockFilterCriteria::default().matches(&log(2, vec![])));
+
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(&alloy_primitives::Address::with_last_byte(1)[..]));
+        bloom.accrue(BloomInput::Raw(&topic[..]));
+        let header = alloy_consensus::Header { logs_bloom: bloom, ..Default::default() };
+        assert!(criteria.matches_bloom(&SealedHeader::seal(header)));
+        assert!(!criteria.matches_bloom(&SealedHeader::seal(Default::default())));
+    }
+
+    #[tokio::test]
+    async fn query_complexity() {
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::ZERO, alloy_consensus::Header::default());
+        let schema = schema(provider);
+
+        let res = schema.execute("{ blocks(from: 0, to: 9) { number hash } }").await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        // the fields of a block range without an end count for the maximum number of blocks
+        let fields = (0..200).map(|i| format!("n{i}: number")).collect::<Vec<_>>().join(" ");
+        let res = schema.execute(format!("{{ blocks(from: 0) {{ {fields} }} }}")).await;
+        assert_eq!(res.errors[0].message, "Query is too complex.");
+        let res = schema.execute(format!("{{ blocks(from: 0, to: 9) {{ {fields} }} }}")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+    }
+}
@@ -0,0 +1,100 @@
+//! HTTP server of the GraphQL endpoint.
+
+use crate::schema::{schema, GraphQlProvider, GraphQlSchema};
+use eyre::WrapErr;
+use http::{header::CONTENT_TYPE, HeaderValue, Method, Request, Response, StatusCode};
+use http_body_util::BodyExt;
+use hyper::body::Incoming;
+use reth_tasks::TaskExecutor;
+use std::{convert::Infallible, net::SocketAddr};
+use tracing::{debug, error};
+
+/// Starts the GraphQL server on the given address.
+///
+/// The server accepts `POST` requests with a JSON encoded GraphQL request, as described in
+/// [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767), on any path. Only the read-only queries
+/// of the schema are supported. It is shut down with the
+/// task executor.
+pub async fn start_graphql_server<P: GraphQlProvider>(
+    listen_addr: SocketAddr,
+    provider: P,
+    task_executor: TaskExecutor,
+) -> eyre::Result<SocketAddr> {
+    let listener =
+        tokio::net::TcpListener::bind(listen_addr).await.wrap_err("Could not bind to address")?;
+    let local_addr = listener.local_addr()?;
+    let schema = schema(provider);
+
+    task_executor.spawn_with_graceful_shutdown_signal(|mut signal| async move {
+        loop {
+            let io = tokio::select! {
+                _ = &mut signal => break,
+                io = listener.accept() => {
+                    match io {
+                        Ok((stream, _remote_addr)) => stream,
+                        Err(err) => {
+                            error!(target: "rpc::graphql", %err, "failed to accept connection");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let schema = schema.clone();
+            let service = tower::service_fn(move |req: Request<Incoming>| {
+                let schema = schema.clone();
+                async move { Ok::<_, Infallible>(handle_request(&schema, req).await) }
+            });
+
+            let mut shutdown = signal.clone().ignore_guard();
+            tokio::task::spawn(async move {
+                if let Err(error) =
+                    jsonrpsee::server::serve_with_graceful_shutdown(io, service, &mut shutdown)
+                        .await
+                {
+                    debug!(target: "rpc::graphql", %error, "failed to serve request")
+                }
+            });
+        }
+    });
+
+    Ok(local_addr)
+}
+
+/// Executes a GraphQL request and returns the JSON encoded response.
+async fn handle_request<P: GraphQlProvider>(
+    schema: &GraphQlSchema<P>,
+    req: Request<Incoming>,
+) -> Response<String> {
+    if req.method() != Method::POST {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED, "only POST requests are supported")
+    }
+
+    let body = match req.into_body().collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(err) => return error_response(StatusCode::BAD_REQUEST, &err.to_string()),
+    };
+    let request: async_graphql::Request = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(err) => return error_response(StatusCode::BAD_REQUEST, &err.to_string()),
+    };
+
+    let response = schema.execute(request).await;
+    match serde_json::to_string(&response) {
+        Ok(body) => json_response(StatusCode::OK, body),
+        Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+/// Returns a GraphQL error response with the given status.
+fn error_response(status: StatusCode, message: &str) -> Response<String> {
+    let body = serde_json::json!({ "errors": [{ "message": message }] }).to_string();
+    json_response(status, body)
+}
+
+fn json_response(status: StatusCode, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
@@ -39,6 +39,7 @@ use tower::{Layer, Service};
 /// }
 /// ```
 #[allow(missing_debug_implementations)]
+#[derive(Clone)]
 pub struct AuthLayer<V> {
     validator: V,
 }
@@ -0,0 +1,85 @@
+//! Configuration of the per-client rate limits of the RPC server.
+
+use crate::method_limits::pattern_match_len;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, num::NonZeroU32};
+
+/// Token bucket rate limits of the clients of the HTTP and WS servers.
+///
+/// Every client has a bucket of credits that refills at a constant rate, and every call costs the
+/// credits configured for its method. Calls are rejected while the bucket of a client doesn't hold
+/// enough credits, and clients with too many consecutive rejected calls are banned temporarily.
+///
+/// Clients are identified by the IP they connect from, or by the IP in
+/// [`Self::client_ip_header`] if the server is behind a reverse proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcClientLimitsConfig {
+    /// The number of credits a client regains per second.
+    pub credits_per_second: NonZeroU32,
+    /// The maximum number of credits a client can hold, which limits the size of its bursts.
+    pub burst: NonZeroU32,
+    /// The credits a call costs, by method name or method prefix followed by `*`, e.g. `trace_*`.
+    ///
+    /// If several patterns match a method, the method name or else the longest prefix applies.
+    /// Methods without a matching pattern cost one credit.
+    pub method_costs: BTreeMap<String, u32>,
+    /// The number of consecutive rejected calls after which a client is banned. Clients are never
+    /// banned if zero.
+    pub ban_threshold: u32,
+    /// How long all calls of a banned client are rejected, in seconds.
+    pub ban_duration_secs: u64,
+    /// The header that carries the IP of the client, e.g. `x-forwarded-for`.
+    ///
+    /// This must only be set if the server is only reachable through a reverse proxy that sets
+    /// the header, since clients can set arbitrary values otherwise. The last address of the
+    /// header is used, which is the one appended by the proxy. Requests without the header are
+    /// identified by the IP they connect from.
+    pub client_ip_header: Option<String>,
+}
+
+impl RpcClientLimitsConfig {
+    /// Returns the credits a call of the method costs.
+    pub fn cost(&self, method: &str) -> u32 {
+        self.method_costs
+            .iter()
+            .filter_map(|(pattern, cost)| Some((pattern_match_len(pattern, method)?, *cost)))
+            .max_by_key(|(len, _)| *len)
+            .map_or(1, |(_, cost)| cost)
+    }
+}
+
+impl Default for RpcClientLimitsConfig {
+    fn default() -> Self {
+        Self {
+            credits_per_second: NonZeroU32::new(100).expect("is not zero"),
+            burst: NonZeroU32::new(500).expect("is not zero"),
+            method_costs: BTreeMap::from([
+                ("debug_*".to_string(), 20),
+                ("trace_*".to_string(), 20),
+                ("eth_call".to_string(), 5),
+                ("eth_estimateGas".to_string(), 5),
+                ("eth_getLogs".to_string(), 10),
+            ]),
+            ban_threshold: 100,
+            ban_duration_secs: 300,
+            client_ip_header: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_costs() {
+        let mut config = RpcClientLimitsConfig::default();
+        config.method_costs.insert("trace_block".to_string(), 50);
+        assert_eq!(config.cost("eth_chainId"), 1);
+        assert_eq!(config.cost("eth_call"), 5);
+        assert_eq!(config.cost("eth_callMany"), 1);
+        assert_eq!(config.cost("trace_filter"), 20);
+        assert_eq!(config.cost("trace_block"), 50);
+    }
+}
@@ -6,6 +6,9 @@ pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
 /// The default port for the ws server
 pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 
+/// The default port for the GraphQL server
+pub const DEFAULT_GRAPHQL_PORT: u16 = 8547;
+
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
@@ -116,6 +119,9 @@ pub mod cache {
     /// Default cache size for the env cache: 1000 envs.
     pub const DEFAULT_ENV_CACHE_MAX_LEN: u32 = 1000;
 
+    /// Default cache size for the block receipts cache: 64 blocks.
+    pub const DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN: u32 = 64;
+
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
 }
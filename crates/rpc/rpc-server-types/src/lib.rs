@@ -12,7 +12,16 @@
 pub mod constants;
 pub mod result;
 
+mod client_limits;
+pub use client_limits::RpcClientLimitsConfig;
+
+mod method_limits;
+pub use method_limits::{RpcMethodLimit, RpcMethodLimits, RpcMethodLimitsError};
+
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+mod tenancy;
+pub use tenancy::{RpcTenantsConfig, TenantConfig};
+
 pub use result::ToRpcResult;
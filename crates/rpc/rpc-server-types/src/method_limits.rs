@@ -0,0 +1,165 @@
+//! Per-method concurrency limits of the RPC server.
+
+use std::{fmt, num::NonZeroUsize, str::FromStr};
+
+/// Errors when parsing [`RpcMethodLimits`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RpcMethodLimitsError {
+    /// An entry is not of the form `<method>=<limit>`.
+    #[error("invalid method limit {0:?}, expected <method>=<limit>")]
+    InvalidEntry(String),
+    /// The limit of an entry is not a positive number.
+    #[error("invalid limit {limit:?} for {pattern}, expected a positive number")]
+    InvalidLimit {
+        /// The method pattern of the entry.
+        pattern: String,
+        /// The invalid limit.
+        limit: String,
+    },
+    /// The same method pattern is limited more than once.
+    #[error("{0} is limited more than once")]
+    Duplicate(String),
+}
+
+/// The concurrency limit of the methods matching a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcMethodLimit {
+    /// A method name, e.g. `eth_call`, or a method prefix followed by `*`, e.g. `trace_*`.
+    pub pattern: String,
+    /// The maximum number of calls that are executed concurrently.
+    pub max_concurrent: NonZeroUsize,
+}
+
+impl RpcMethodLimit {
+    /// Returns the length of the matched part of the method name, if the pattern matches it.
+    ///
+    /// A method name matches exactly, so it is always preferred over a prefix.
+    fn match_len(&self, method: &str) -> Option<usize> {
+        pattern_match_len(&self.pattern, method)
+    }
+}
+
+/// Returns the length of the part of the method name matched by a method name or prefix pattern,
+/// with `usize::MAX` for exact matches.
+pub(crate) fn pattern_match_len(pattern: &str, method: &str) -> Option<usize> {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix).then_some(prefix.len()),
+        None => (pattern == method).then_some(usize::MAX),
+    }
+}
+
+/// Concurrency limits of RPC methods, e.g. `trace_*=4,eth_call=64`.
+///
+/// Calls of a limited method beyond its limit are queued until a running call of a method matching
+/// the same pattern completes, so heavy methods can't starve latency-sensitive ones. If several
+/// patterns match a method, the method name or else the longest prefix applies.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcMethodLimits {
+    limits: Vec<RpcMethodLimit>,
+}
+
+impl RpcMethodLimits {
+    /// Returns all configured limits.
+    pub fn limits(&self) -> &[RpcMethodLimit] {
+        &self.limits
+    }
+
+    /// Returns `true` if no method is limited.
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty()
+    }
+
+    /// Returns the index of the limit that applies to the method, if any.
+    pub fn position(&self, method: &str) -> Option<usize> {
+        self.limits
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, limit)| Some((idx, limit.match_len(method)?)))
+            .max_by_key(|(_, len)| *len)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns the limit that applies to the method, if any.
+    pub fn get(&self, method: &str) -> Option<&RpcMethodLimit> {
+        self.position(method).map(|idx| &self.limits[idx])
+    }
+}
+
+impl FromStr for RpcMethodLimits {
+    type Err = RpcMethodLimitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut limits: Vec<RpcMethodLimit> = Vec::new();
+        for entry in s.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (pattern, limit) = entry
+                .split_once('=')
+                .map(|(pattern, limit)| (pattern.trim(), limit.trim()))
+                .filter(|(pattern, _)| !pattern.is_empty())
+                .ok_or_else(|| RpcMethodLimitsError::InvalidEntry(entry.to_string()))?;
+            let max_concurrent = limit.parse().map_err(|_| RpcMethodLimitsError::InvalidLimit {
+                pattern: pattern.to_string(),
+                limit: limit.to_string(),
+            })?;
+            if limits.iter().any(|limit| limit.pattern == pattern) {
+                return Err(RpcMethodLimitsError::Duplicate(pattern.to_string()))
+            }
+            limits.push(RpcMethodLimit { pattern: pattern.to_string(), max_concurrent });
+        }
+        Ok(Self { limits })
+    }
+}
+
+impl fmt::Display for RpcMethodLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, limit) in self.limits.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", limit.pattern, limit.max_concurrent)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_method_limits() {
+        let limits: RpcMethodLimits = "trace_*=4, eth_call=64,trace_block=1,*=100".parse().unwrap();
+        assert_eq!(limits.to_string(), "trace_*=4,eth_call=64,trace_block=1,*=100");
+
+        assert_eq!(limits.get("trace_filter").unwrap().max_concurrent.get(), 4);
+        assert_eq!(limits.get("trace_block").unwrap().max_concurrent.get(), 1);
+        assert_eq!(limits.get("eth_call").unwrap().max_concurrent.get(), 64);
+        assert_eq!(limits.get("eth_callMany").unwrap().max_concurrent.get(), 100);
+
+        let limits: RpcMethodLimits = "trace_*=4".parse().unwrap();
+        assert!(limits.get("eth_sendRawTransaction").is_none());
+        assert!("".parse::<RpcMethodLimits>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid_method_limits() {
+        assert_eq!(
+            "trace_*".parse::<RpcMethodLimits>(),
+            Err(RpcMethodLimitsError::InvalidEntry("trace_*".to_string()))
+        );
+        assert_eq!(
+            "=4".parse::<RpcMethodLimits>(),
+            Err(RpcMethodLimitsError::InvalidEntry("=4".to_string()))
+        );
+        assert_eq!(
+            "eth_call=0".parse::<RpcMethodLimits>(),
+            Err(RpcMethodLimitsError::InvalidLimit {
+                pattern: "eth_call".to_string(),
+                limit: "0".to_string()
+            })
+        );
+        assert_eq!(
+            "eth_call=1,eth_call=2".parse::<RpcMethodLimits>(),
+            Err(RpcMethodLimitsError::Duplicate("eth_call".to_string()))
+        );
+    }
+}
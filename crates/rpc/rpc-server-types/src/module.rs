@@ -269,6 +269,10 @@ pub enum RethRpcModule {
     Ots,
     /// `flashbots_` module
     Flashbots,
+    /// `mev_` module, also serves `eth_sendBundle` and private transactions
+    Mev,
+    /// `eth_` ERC-4337 user operation methods
+    Bundler,
 }
 
 // === impl RethRpcModule ===
@@ -318,6 +322,8 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "flashbots" => Self::Flashbots,
+            "mev" => Self::Mev,
+            "bundler" => Self::Bundler,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
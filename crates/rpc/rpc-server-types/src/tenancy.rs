@@ -0,0 +1,50 @@
+//! Configuration of the API key tenants of the RPC server.
+
+use crate::RethRpcModule;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+/// The configuration of all tenants.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcTenantsConfig {
+    /// The tenants.
+    #[serde(default, rename = "tenant", skip_serializing_if = "Vec::is_empty")]
+    pub tenants: Vec<TenantConfig>,
+}
+
+/// The configuration of a single tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfig {
+    /// The name of the tenant, used for logging and the usage counters.
+    pub name: String,
+    /// The API key of the tenant.
+    pub api_key: String,
+    /// The namespaces the tenant may call. All namespaces served by the endpoint are allowed if
+    /// unset.
+    #[serde(default)]
+    pub namespaces: Option<Vec<RethRpcModule>>,
+    /// The methods the tenant may call, either method names or prefixes followed by `*`, e.g.
+    /// `eth_get*`. All methods of the allowed namespaces are allowed if unset.
+    #[serde(default)]
+    pub methods: Option<Vec<String>>,
+    /// The maximum number of requests per second. Unlimited if unset.
+    #[serde(default)]
+    pub requests_per_second: Option<NonZeroU32>,
+    /// The maximum total number of requests. Unlimited if unset.
+    #[serde(default)]
+    pub max_requests: Option<u64>,
+}
+
+impl TenantConfig {
+    /// Returns `true` if the method is in the method allowlist of the tenant, or there is none.
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        self.methods.as_ref().is_none_or(|methods| {
+            methods.iter().any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => method.starts_with(prefix),
+                None => pattern == method,
+            })
+        })
+    }
+}
@@ -0,0 +1,280 @@
+//! Index of the blocks that contain transactions of an address, used to serve the Otterscan
+//! address search.
+
+use crate::block_index::{append_blocks, last_indexed_block, push_block, BlockIndexer};
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, BlockNumber};
+use reth_db::{models::ShardedKey, tables};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_provider::{
+    BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory, TransactionVariant,
+};
+use reth_stages_types::StageId;
+use std::{collections::BTreeMap, fmt, ops::RangeInclusive};
+
+/// The id under which the last block of the address index is stored.
+pub const ADDRESS_INDEX_ID: StageId = StageId::Other("AddressIndex");
+
+/// An index from addresses to the blocks that contain their transactions.
+///
+/// When configured, `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter` use the index
+/// to only fetch the blocks that can contain transactions of the searched address. The
+/// transactions of these blocks are still matched against the address, so the index may return
+/// blocks without transactions of the address, but it must never omit a block in which the address
+/// is the sender or recipient of a transaction or the contract created by it.
+///
+/// [`DatabaseAddressIndex`] implements the index on top of a table that is maintained by a
+/// [`BlockIndexTask`](crate::BlockIndexTask) running the [`AddressIndexer`].
+pub trait AddressIndex: fmt::Debug + Send + Sync + 'static {
+    /// Returns the number of the highest block covered by the index, or `None` if no block is
+    /// covered.
+    ///
+    /// Blocks above it are searched without the index.
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the numbers of up to `limit` blocks in the range that can contain a transaction of
+    /// the address, in ascending order, or in descending order if `reverse` is set.
+    ///
+    /// The range must be covered by the index.
+    fn blocks_with_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// An [`AddressIndex`] backed by the [`tables::TransactionAddressIndex`] table.
+#[derive(Clone)]
+pub struct DatabaseAddressIndex<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> DatabaseAddressIndex<Provider> {
+    /// Creates a new index that reads the address index table of the given database.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> AddressIndex for DatabaseAddressIndex<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader + 'static,
+{
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        last_indexed_block(&self.provider, ADDRESS_INDEX_ID)
+    }
+
+    fn blocks_with_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let provider = self.provider.database_provider_ro()?;
+        let mut cursor = provider.tx_ref().cursor_read::<tables::TransactionAddressIndex>()?;
+        let mut blocks = Vec::new();
+
+        // Shards are keyed by their highest block and the last shard of every address is keyed by
+        // `u64::MAX`, so the seek always lands on the shard that covers the block if the address
+        // has any transactions.
+        let seek_block = if reverse { *range.end() } else { *range.start() };
+        let mut entry = cursor.seek(ShardedKey::new(address, seek_block))?;
+        while let Some((key, list)) = entry.filter(|(key, _)| key.key == address) {
+            let shard = list.iter().filter(|block| range.contains(block)).collect::<Vec<_>>();
+            let remaining = limit - blocks.len();
+            if reverse {
+                blocks.extend(shard.into_iter().rev().take(remaining));
+            } else {
+                blocks.extend(shard.into_iter().take(remaining));
+            }
+
+            let done = if reverse {
+                list.min().is_none_or(|lowest| lowest <= *range.start())
+            } else {
+                key.highest_block_number >= *range.end()
+            };
+            if done || blocks.len() >= limit {
+                break
+            }
+
+            entry = if reverse { cursor.prev()? } else { cursor.next()? };
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<Provider> fmt::Debug for DatabaseAddressIndex<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseAddressIndex").finish_non_exhaustive()
+    }
+}
+
+/// A [`BlockIndexer`] that indexes the senders, recipients and created contracts of the
+/// transactions of a block, for the [`DatabaseAddressIndex`].
+#[derive(Clone)]
+pub struct AddressIndexer<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> AddressIndexer<Provider> {
+    /// Creates a new indexer that reads the blocks of the given provider.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> BlockIndexer for AddressIndexer<Provider>
+where
+    Provider: BlockReader + 'static,
+{
+    type Entries = BTreeMap<Address, Vec<BlockNumber>>;
+    type Error = ProviderError;
+
+    const ID: StageId = ADDRESS_INDEX_ID;
+    const BATCH_SIZE: u64 = 1_000;
+
+    async fn collect(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        let mut entries = BTreeMap::<_, Vec<_>>::new();
+        for number in range {
+            let Some(block) =
+                self.provider.block_with_senders(number.into(), TransactionVariant::NoHash)?
+            else {
+                continue
+            };
+            for (sender, tx) in block.transactions_with_sender() {
+                // the address of a created contract only depends on the sender and its nonce, so
+                // it's known without the receipt, like the `contractAddress` of the receipt
+                let to = tx.to().unwrap_or_else(|| sender.create(tx.nonce()));
+                push_block(entries.entry(*sender).or_default(), number);
+                push_block(entries.entry(to).or_default(), number);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write<TX: DbTxMut + DbTx>(
+        &self,
+        tx: &TX,
+        entries: BTreeMap<Address, Vec<BlockNumber>>,
+    ) -> ProviderResult<()> {
+        for (address, blocks) in entries {
+            append_blocks::<tables::TransactionAddressIndex, _>(tx, address, &blocks)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Provider> fmt::Debug for AddressIndexer<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddressIndexer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockIndexTask;
+    use reth_db::BlockNumberList;
+    use reth_primitives::StaticFileSegment;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileProviderFactory,
+        StaticFileWriter,
+    };
+    use reth_stages_types::StageCheckpoint;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn blocks_with_transactions_of_shards() {
+        let address = Address::with_last_byte(1);
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::TransactionAddressIndex>(
+            ShardedKey::new(address, 5),
+            BlockNumberList::new([1, 3, 5]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::TransactionAddressIndex>(
+            ShardedKey::last(address),
+            BlockNumberList::new([7, 9]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::TransactionAddressIndex>(
+            ShardedKey::last(Address::with_last_byte(2)),
+            BlockNumberList::new([2, 4]).unwrap(),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let index = DatabaseAddressIndex::new(factory);
+        let blocks = |range, reverse, limit| {
+            index.blocks_with_transactions(address, range, reverse, limit).unwrap()
+        };
+        assert_eq!(blocks(0..=10, false, 10), vec![1, 3, 5, 7, 9]);
+        assert_eq!(blocks(2..=8, false, 2), vec![3, 5]);
+        assert_eq!(blocks(6..=6, false, 10), Vec::<u64>::new());
+        assert_eq!(blocks(0..=10, true, 10), vec![9, 7, 5, 3, 1]);
+        assert_eq!(blocks(0..=6, true, 2), vec![5, 3]);
+        assert_eq!(blocks(6..=8, true, 10), vec![7]);
+        assert!(index
+            .blocks_with_transactions(Address::with_last_byte(3), 0..=10, true, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn index_transactions_of_canonical_blocks() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=9,
+            BlockRangeParams { tx_count: 0..3, ..Default::default() },
+        );
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let mut expected = BTreeMap::<_, Vec<_>>::new();
+        for block in blocks {
+            let block = block.try_seal_with_senders().unwrap();
+            for (sender, tx) in block.transactions_with_sender() {
+                push_block(expected.entry(*sender).or_default(), block.number);
+                if let Some(to) = tx.to() {
+                    push_block(expected.entry(to).or_default(), block.number);
+                }
+            }
+            provider.insert_historical_block(block).unwrap();
+        }
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(9)).unwrap();
+        provider.commit().unwrap();
+        assert!(!expected.is_empty());
+
+        let task = BlockIndexTask::new(factory.clone(), AddressIndexer::new(factory.clone()));
+        let index = DatabaseAddressIndex::new(factory);
+        assert_eq!(index.last_indexed_block().unwrap(), None);
+        task.sync().await.unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(9));
+
+        // every sender and recipient is found in exactly the blocks of its transactions
+        for (address, blocks) in &expected {
+            assert_eq!(
+                &index.blocks_with_transactions(*address, 0..=9, false, 10).unwrap(),
+                blocks
+            );
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use alloy_genesis::ChainConfig;
+use alloy_primitives::TxHash;
 use alloy_rpc_types_admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
     Ports, ProtocolInfo,
@@ -9,34 +10,39 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkCondition};
 use reth_network_api::{NetworkInfo, Peers};
-use reth_network_peers::{id2pk, AnyNode, NodeRecord};
-use reth_network_types::PeerKind;
+use reth_network_peers::{id2pk, AnyNode, NodeRecord, PeerId};
+use reth_network_types::{PeerDiagnostics, PeerKind, PeerReputationRecord};
 use reth_primitives::EthereumHardfork;
+use reth_profiling::{cpu_profiler, DEFAULT_PROFILING_FREQUENCY};
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_transaction_pool::{PoolRuntimeConfig, TransactionPool};
 
 /// `admin` API implementation.
 ///
 /// This type provides the functionality for handling `admin` related requests.
-pub struct AdminApi<N, ChainSpec> {
+pub struct AdminApi<N, ChainSpec, Pool> {
     /// An interface to interact with the network
     network: N,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// The transaction pool whose settings can be changed.
+    pool: Pool,
 }
 
-impl<N, ChainSpec> AdminApi<N, ChainSpec> {
+impl<N, ChainSpec, Pool> AdminApi<N, ChainSpec, Pool> {
     /// Creates a new instance of `AdminApi`.
-    pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+    pub const fn new(network: N, chain_spec: Arc<ChainSpec>, pool: Pool) -> Self {
+        Self { network, chain_spec, pool }
     }
 }
 
 #[async_trait]
-impl<N, ChainSpec> AdminApiServer for AdminApi<N, ChainSpec>
+impl<N, ChainSpec, Pool> AdminApiServer for AdminApi<N, ChainSpec, Pool>
 where
     N: NetworkInfo + Peers + 'static,
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `admin_addPeer`
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool> {
@@ -104,6 +110,19 @@ where
         Ok(infos)
     }
 
+    /// Handler for `admin_peerReputation`
+    async fn peer_reputation(
+        &self,
+        peer_id: Option<PeerId>,
+    ) -> RpcResult<Vec<PeerReputationRecord>> {
+        self.network.peer_reputations(peer_id).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_peerDiagnostics`
+    async fn peer_diagnostics(&self, peer_id: Option<PeerId>) -> RpcResult<Vec<PeerDiagnostics>> {
+        self.network.peer_diagnostics(peer_id).await.to_rpc_result()
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -179,6 +198,40 @@ where
         })
     }
 
+    /// Handler for `admin_startCpuProfile`
+    fn start_cpu_profile(&self, frequency: Option<u32>) -> RpcResult<bool> {
+        cpu_profiler()
+            .start(frequency.unwrap_or(DEFAULT_PROFILING_FREQUENCY))
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `admin_stopCpuProfile`
+    fn stop_cpu_profile(&self) -> RpcResult<String> {
+        let path = cpu_profiler()
+            .stop_and_save("admin")
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(path.display().to_string())
+    }
+
+    /// Handler for `admin_profileNextCall`
+    fn profile_next_call(&self, method: String, frequency: Option<u32>) -> RpcResult<bool> {
+        cpu_profiler()
+            .profile_next_call(method, frequency.unwrap_or(DEFAULT_PROFILING_FREQUENCY))
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `admin_txpoolConfig`
+    fn txpool_config(&self) -> RpcResult<PoolRuntimeConfig> {
+        Ok(self.pool.runtime_config())
+    }
+
+    /// Handler for `admin_setTxpoolConfig`
+    fn set_txpool_config(&self, config: PoolRuntimeConfig) -> RpcResult<Vec<TxHash>> {
+        Ok(self.pool.update_runtime_config(config).into_iter().collect())
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -188,7 +241,7 @@ where
     }
 }
 
-impl<N, ChainSpec> std::fmt::Debug for AdminApi<N, ChainSpec> {
+impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
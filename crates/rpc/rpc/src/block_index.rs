@@ -0,0 +1,356 @@
+//! Indices from keys, like addresses or log topics, to the blocks they appear in, that are kept in
+//! sync with the canonical chain by a [`BlockIndexTask`].
+
+use alloy_primitives::{BlockNumber, B256};
+use futures::StreamExt;
+use reth_db::{models::ShardedKey, tables, BlockNumberList};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_provider::{BlockNumReader, CanonStateSubscriptions, DBProvider, DatabaseProviderFactory};
+use reth_stages_types::{StageCheckpoint, StageId};
+use std::{fmt, future::Future, ops::RangeInclusive};
+use tracing::{debug, warn};
+
+/// Extracts the keys of an index from blocks and writes them to the tables of the index.
+pub trait BlockIndexer: Send + Sync + 'static {
+    /// The keys of a range of blocks, e.g. the blocks of every key.
+    type Entries: Send;
+
+    /// The error of [`Self::collect`].
+    type Error: From<ProviderError> + fmt::Display + Send;
+
+    /// The id under which the last indexed block is stored.
+    const ID: StageId;
+
+    /// The maximum number of blocks that are written in a single transaction.
+    const BATCH_SIZE: u64;
+
+    /// Collects the entries of the blocks in the range.
+    fn collect(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Future<Output = Result<Self::Entries, Self::Error>> + Send;
+
+    /// Writes the entries of blocks that directly follow the last indexed block.
+    ///
+    /// This is expected to use [`append_blocks`], which replaces the blocks of a key that are not
+    /// below the first written block, so blocks that were indexed before a reorg are overwritten.
+    fn write<TX: DbTxMut + DbTx>(&self, tx: &TX, entries: Self::Entries) -> ProviderResult<()>;
+}
+
+/// Keeps the index of a [`BlockIndexer`] in sync with the canonical chain.
+///
+/// The task indexes all blocks above the last indexed block, in batches of
+/// [`BlockIndexer::BATCH_SIZE`] blocks, and then the blocks of every canonical state notification.
+/// The number and hash of the last indexed block are stored in the stage checkpoint tables under
+/// [`BlockIndexer::ID`].
+///
+/// On reorgs the last indexed block is moved back to the block before the fork, so the new blocks
+/// are indexed again. If the last indexed block is no longer canonical, e.g. because a reorg was
+/// missed or the node did not persist the blocks before shutting down, the index is moved back to
+/// the last finalized block. Keys that only appeared in reorged blocks keep their stale entries,
+/// which are harmless since the indices may return blocks without matches.
+pub struct BlockIndexTask<Provider, Indexer> {
+    provider: Provider,
+    indexer: Indexer,
+}
+
+impl<Provider, Indexer> BlockIndexTask<Provider, Indexer> {
+    /// Creates a new task that indexes the canonical blocks of the provider.
+    pub const fn new(provider: Provider, indexer: Indexer) -> Self {
+        Self { provider, indexer }
+    }
+}
+
+impl<Provider, Indexer> BlockIndexTask<Provider, Indexer>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader,
+    Indexer: BlockIndexer,
+{
+    /// Indexes all canonical blocks above the last indexed block.
+    pub async fn sync(&self) -> Result<(), Indexer::Error> {
+        let mut next = self.first_unindexed_block()?;
+        let best = self.provider.best_block_number()?;
+        while next <= best {
+            let to = best.min(next + Indexer::BATCH_SIZE - 1);
+            // read the hash before the blocks, so a reorg in between is detected by the next sync
+            let hash = self
+                .provider
+                .block_hash(to)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(to.into()))?;
+            let entries = self.indexer.collect(next..=to).await?;
+
+            let provider = self.provider.database_provider_rw()?;
+            self.indexer.write(provider.tx_ref(), entries)?;
+            save_last_indexed_block(provider.tx_ref(), Indexer::ID, to, hash)?;
+            provider.commit()?;
+
+            debug!(target: "rpc::block_index", index = %Indexer::ID, from = next, to, "Indexed blocks");
+            next = to + 1;
+        }
+        Ok(())
+    }
+
+    /// Moves the last indexed block back to the given block, if it is above it.
+    pub fn unwind_to(&self, block: BlockNumber) -> ProviderResult<()> {
+        let provider = self.provider.database_provider_rw()?;
+        let Some((last_indexed, _)) = read_last_indexed_block(provider.tx_ref(), Indexer::ID)?
+        else {
+            return Ok(())
+        };
+        if last_indexed <= block {
+            return Ok(())
+        }
+
+        let hash = self
+            .provider
+            .block_hash(block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+        save_last_indexed_block(provider.tx_ref(), Indexer::ID, block, hash)?;
+        provider.commit()?;
+
+        debug!(target: "rpc::block_index", index = %Indexer::ID, from = last_indexed, to = block, "Unwound index");
+        Ok(())
+    }
+
+    /// Returns the first block that is not indexed, after moving the index back to the last
+    /// finalized block if the last indexed block is no longer canonical.
+    fn first_unindexed_block(&self) -> ProviderResult<BlockNumber> {
+        let provider = self.provider.database_provider_ro()?;
+        let Some((last_indexed, hash)) = read_last_indexed_block(provider.tx_ref(), Indexer::ID)?
+        else {
+            return Ok(0)
+        };
+        if self.provider.block_hash(last_indexed)? == hash {
+            return Ok(last_indexed + 1)
+        }
+
+        let finalized = read_finalized_block(provider.tx_ref())?.unwrap_or_default();
+        drop(provider);
+        warn!(target: "rpc::block_index", index = %Indexer::ID, last_indexed, finalized, "Last indexed block is not canonical");
+        let block = finalized.min(last_indexed);
+        self.unwind_to(block)?;
+        Ok(block + 1)
+    }
+}
+
+impl<Provider, Indexer> BlockIndexTask<Provider, Indexer>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader + CanonStateSubscriptions,
+    Indexer: BlockIndexer,
+{
+    /// Runs the task until the canonical state notifications end.
+    ///
+    /// Errors are logged and the index is updated again on the next notification.
+    pub async fn run(self) {
+        let mut notifications = self.provider.canonical_state_stream();
+        loop {
+            if let Err(err) = self.sync().await {
+                warn!(target: "rpc::block_index", index = %Indexer::ID, %err, "Failed to update index");
+            }
+
+            let Some(notification) = notifications.next().await else { return };
+            if let Some(reverted) = notification.reverted() {
+                let fork_block = reverted.first().number.saturating_sub(1);
+                if let Err(err) = self.unwind_to(fork_block) {
+                    warn!(target: "rpc::block_index", index = %Indexer::ID, %err, "Failed to unwind index");
+                }
+            }
+        }
+    }
+}
+
+impl<Provider, Indexer: fmt::Debug> fmt::Debug for BlockIndexTask<Provider, Indexer> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockIndexTask").field("indexer", &self.indexer).finish_non_exhaustive()
+    }
+}
+
+/// Returns the last block covered by the index with the given id, or `None` if no block was
+/// indexed yet.
+///
+/// If the last indexed block is no longer canonical, only the blocks up to the last finalized
+/// block are reported as covered, since blocks above it may be indexed from a reorged chain.
+pub(crate) fn last_indexed_block<Provider>(
+    provider: &Provider,
+    id: StageId,
+) -> ProviderResult<Option<BlockNumber>>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader,
+{
+    let db = provider.database_provider_ro()?;
+    let Some((last_indexed, hash)) = read_last_indexed_block(db.tx_ref(), id)? else {
+        return Ok(None)
+    };
+    if provider.block_hash(last_indexed)? == hash {
+        return Ok(Some(last_indexed))
+    }
+    Ok(read_finalized_block(db.tx_ref())?.map(|finalized| finalized.min(last_indexed)))
+}
+
+/// Returns the blocks of the key in the range, in ascending order, from the index table `T`.
+///
+/// The table is sharded like [`tables::AccountsHistory`], the last shard of every key is keyed by
+/// `u64::MAX`.
+pub(crate) fn key_blocks<T, K>(
+    tx: &impl DbTx,
+    key: K,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Clone + PartialEq,
+{
+    let mut cursor = tx.cursor_read::<T>()?;
+    let mut blocks = Vec::new();
+
+    // shards are keyed by their highest block, so the first shard that can contain the start of
+    // the range is the first one that is keyed by a block at or above it
+    let mut entry = cursor.seek(ShardedKey::new(key.clone(), *range.start()))?;
+    while let Some((sharded_key, list)) = entry.filter(|(sharded_key, _)| sharded_key.key == key) {
+        blocks.extend(
+            list.iter()
+                .skip_while(|block| block < range.start())
+                .take_while(|block| block <= range.end()),
+        );
+        if sharded_key.highest_block_number >= *range.end() {
+            break
+        }
+        entry = cursor.next()?;
+    }
+
+    Ok(blocks)
+}
+
+/// Appends the blocks, in ascending order, to the shards of the key in the index table `T`.
+///
+/// Blocks of the key that are not below the first of the given blocks are removed first, so
+/// indexing blocks again after a reorg replaces the blocks of the reorged chain.
+pub fn append_blocks<T, K>(tx: &impl DbTxMut, key: K, blocks: &[BlockNumber]) -> ProviderResult<()>
+where
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Clone + PartialEq,
+{
+    let Some(&first) = blocks.first() else { return Ok(()) };
+    let mut cursor = tx.cursor_write::<T>()?;
+
+    // take the shards that contain blocks at or above the first block, starting at the last one
+    let mut indices = Vec::new();
+    let mut entry = cursor.seek_exact(ShardedKey::last(key.clone()))?;
+    while let Some((sharded_key, list)) = entry.filter(|(sharded_key, _)| sharded_key.key == key) {
+        if sharded_key.highest_block_number < first {
+            break
+        }
+        cursor.delete_current()?;
+
+        if list.iter().next().is_some_and(|lowest| lowest >= first) {
+            entry = cursor.prev()?;
+            continue
+        }
+        indices = list.iter().take_while(|block| *block < first).collect();
+        break
+    }
+
+    indices.extend_from_slice(blocks);
+    let mut chunks = indices.chunks(NUM_OF_INDICES_IN_SHARD).peekable();
+    while let Some(list) = chunks.next() {
+        let highest_block_number = if chunks.peek().is_some() {
+            *list.last().expect("`chunks` does not return empty list")
+        } else {
+            u64::MAX
+        };
+        cursor.upsert(
+            ShardedKey::new(key.clone(), highest_block_number),
+            BlockNumberList::new_pre_sorted(list.iter().copied()),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Appends the block to the ascending blocks, unless it's already the last one.
+pub(crate) fn push_block(blocks: &mut Vec<BlockNumber>, number: BlockNumber) {
+    if blocks.last() != Some(&number) {
+        blocks.push(number);
+    }
+}
+
+/// Reads the number and hash of the last block indexed by the index with the given id.
+fn read_last_indexed_block(
+    tx: &impl DbTx,
+    id: StageId,
+) -> ProviderResult<Option<(BlockNumber, Option<B256>)>> {
+    let Some(checkpoint) = tx.get::<tables::StageCheckpoints>(id.to_string())? else {
+        return Ok(None)
+    };
+    let hash = tx
+        .get::<tables::StageCheckpointProgresses>(id.to_string())?
+        .and_then(|hash| B256::try_from(hash.as_slice()).ok());
+    Ok(Some((checkpoint.block_number, hash)))
+}
+
+/// Stores the number and hash of the last block indexed by the index with the given id.
+fn save_last_indexed_block(
+    tx: &impl DbTxMut,
+    id: StageId,
+    number: BlockNumber,
+    hash: B256,
+) -> ProviderResult<()> {
+    tx.put::<tables::StageCheckpoints>(id.to_string(), StageCheckpoint::new(number))?;
+    tx.put::<tables::StageCheckpointProgresses>(id.to_string(), hash.to_vec())?;
+    Ok(())
+}
+
+/// Reads the last finalized block that was persisted.
+fn read_finalized_block(tx: &impl DbTx) -> ProviderResult<Option<BlockNumber>> {
+    Ok(tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn append_and_read_key_blocks() {
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let factory = create_test_provider_factory();
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let blocks = (0..NUM_OF_INDICES_IN_SHARD as u64 + 10).map(|n| n * 2).collect::<Vec<_>>();
+        append_blocks::<tables::LogAddressIndex, _>(&tx, address, &blocks[..100]).unwrap();
+        append_blocks::<tables::LogAddressIndex, _>(&tx, address, &blocks[100..]).unwrap();
+        append_blocks::<tables::LogAddressIndex, _>(&tx, other, &[3]).unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        // the blocks are split into a full shard and the last shard
+        assert_eq!(tx.entries::<tables::LogAddressIndex>().unwrap(), 3);
+        let read = |range| key_blocks::<tables::LogAddressIndex, _>(&tx, address, &range).unwrap();
+        assert_eq!(read(0..=u64::MAX), blocks);
+        assert_eq!(read(5..=11), vec![6, 8, 10]);
+        assert_eq!(read(3_994..=4_002), vec![3_994, 3_996, 3_998, 4_000, 4_002]);
+        assert_eq!(read(1..=1), Vec::<u64>::new());
+        assert_eq!(key_blocks::<tables::LogAddressIndex, _>(&tx, other, &(0..=10)).unwrap(), [3]);
+        drop(tx);
+
+        // indexing blocks again replaces all blocks that are not below the first one
+        let tx = factory.provider_rw().unwrap().into_tx();
+        append_blocks::<tables::LogAddressIndex, _>(&tx, address, &[7, 9]).unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        assert_eq!(tx.entries::<tables::LogAddressIndex>().unwrap(), 2);
+        assert_eq!(
+            key_blocks::<tables::LogAddressIndex, _>(&tx, address, &(0..=u64::MAX)).unwrap(),
+            vec![0, 2, 4, 6, 7, 9]
+        );
+    }
+}
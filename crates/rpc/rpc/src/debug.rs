@@ -487,7 +487,19 @@ where
         let StateContext { transaction_index, block_number } = state_context.unwrap_or_default();
         let transaction_index = transaction_index.unwrap_or_default();
 
-        let target_block = block_number.unwrap_or_default();
+        let mut target_block = block_number.unwrap_or_default();
+
+        // resolve the block hash, so that all provider calls query data of the same block
+        if !target_block.is_pending() {
+            target_block = self
+                .inner
+                .provider
+                .block_hash_for_id(target_block)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(target_block))?
+                .into();
+        }
+
         let ((cfg, mut block_env, _), block) = futures::try_join!(
             self.eth_api().evm_env_at(target_block),
             self.eth_api().block_with_senders(target_block),
@@ -599,12 +611,26 @@ where
         &self,
         block_id: BlockNumberOrTag,
     ) -> Result<ExecutionWitness, Eth::Error> {
+        self.execution_witness(block_id.into()).await
+    }
+
+    /// The `debug_executionWitnessByBlockHash` method is the same as `debug_executionWitness`, but
+    /// identifies the block by its hash.
+    pub async fn debug_execution_witness_by_block_hash(
+        &self,
+        hash: B256,
+    ) -> Result<ExecutionWitness, Eth::Error> {
+        self.execution_witness(hash.into()).await
+    }
+
+    /// Re-executes the block on top of the state of its parent and records the execution witness.
+    async fn execution_witness(&self, block_id: BlockId) -> Result<ExecutionWitness, Eth::Error> {
         let this = self.clone();
         let block = this
             .eth_api()
-            .block_with_senders(block_id.into())
+            .block_with_senders(block_id)
             .await?
-            .ok_or(EthApiError::HeaderNotFound(block_id.into()))?;
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
 
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash.into(), move |state_provider| {
@@ -965,6 +991,15 @@ where
         Self::debug_execution_witness(self, block).await.map_err(Into::into)
     }
 
+    /// Handler for `debug_executionWitnessByBlockHash`
+    async fn debug_execution_witness_by_block_hash(
+        &self,
+        hash: B256,
+    ) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_execution_witness_by_block_hash(self, hash).await.map_err(Into::into)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -1,3 +1,4 @@
+use crate::eth::filter::EthFilterError;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, Bytes, B256, U256, U64};
 use alloy_rpc_types_eth::{
@@ -10,6 +11,7 @@ use reth_rpc_api::{EngineEthApiServer, EthApiServer, EthFilterApiServer};
 /// Re-export for convenience
 pub use reth_rpc_engine_api::EngineApi;
 use reth_rpc_eth_api::{FullEthApiTypes, RpcBlock, RpcReceipt, RpcTransaction};
+use reth_rpc_eth_types::logs_utils::LogsResponse;
 use tracing_futures::Instrument;
 
 macro_rules! engine_span {
@@ -108,8 +110,16 @@ where
     }
 
     /// Handler for `eth_getLogs`
+    ///
+    /// The engine namespace doesn't support cursors, so a query that exceeds the max results is
+    /// rejected.
     async fn logs(&self, filter: Filter) -> Result<Vec<Log>> {
-        self.eth_filter.logs(filter).instrument(engine_span!()).await
+        match self.eth_filter.logs(filter, None).instrument(engine_span!()).await? {
+            LogsResponse::All(logs) => Ok(logs),
+            LogsResponse::Partial { logs, cursor } => {
+                Err(EthFilterError::QueryExceedsMaxResults { max_logs: logs.len(), cursor }.into())
+            }
+        }
     }
 
     /// Handler for `eth_getProof`
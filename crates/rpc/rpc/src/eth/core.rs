@@ -5,7 +5,8 @@ use std::sync::Arc;
 
 use alloy_eips::BlockNumberOrTag;
 use alloy_network::Ethereum;
-use alloy_primitives::U256;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_eth::TransactionReceipt;
 use derive_more::Deref;
 use reth_provider::{BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider};
 use reth_rpc_eth_api::{
@@ -17,14 +18,20 @@ use reth_rpc_eth_types::{
     EthApiBuilderCtx, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
     PendingBlock,
 };
+use reth_rpc_server_types::constants::cache::DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN;
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner, TokioTaskExecutor,
 };
+use schnellru::{ByLength, LruMap};
 use tokio::sync::Mutex;
 
 use crate::eth::EthTxBuilder;
 
+/// The receipt responses of recently requested blocks, by block hash.
+pub type BlockReceiptsCache =
+    parking_lot::Mutex<LruMap<B256, Arc<Vec<TransactionReceipt>>, ByLength>>;
+
 /// `Eth` API implementation.
 ///
 /// This type provides the functionality for handling `eth_` related requests.
@@ -121,7 +128,8 @@ where
             ctx.evm_config.clone(),
             ctx.executor.clone(),
             ctx.config.proof_permits,
-        );
+        )
+        .with_max_block_receipts(ctx.config.cache.max_block_receipts);
 
         Self { inner: Arc::new(inner), tx_resp_builder: EthTxBuilder }
     }
@@ -245,6 +253,8 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     blocking_task_pool: BlockingTaskPool,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache,
+    /// Cache for the receipt responses of recently requested blocks
+    block_receipts_cache: BlockReceiptsCache,
     /// The type that defines how to configure the EVM
     evm_config: EvmConfig,
 
@@ -299,6 +309,9 @@ where
             pending_block: Default::default(),
             blocking_task_pool,
             fee_history_cache,
+            block_receipts_cache: parking_lot::Mutex::new(LruMap::new(ByLength::new(
+                DEFAULT_BLOCK_RECEIPTS_CACHE_MAX_LEN,
+            ))),
             evm_config,
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
         }
@@ -306,6 +319,12 @@ where
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig> {
+    /// Sets the max number of blocks whose `eth_getBlockReceipts` responses are cached.
+    pub fn with_max_block_receipts(mut self, max_blocks: u32) -> Self {
+        self.block_receipts_cache = parking_lot::Mutex::new(LruMap::new(ByLength::new(max_blocks)));
+        self
+    }
+
     /// Returns a handle to data on disk.
     #[inline]
     pub const fn provider(&self) -> &Provider {
@@ -372,6 +391,12 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         &self.fee_history_cache
     }
 
+    /// Returns a handle to the cache of the receipt responses of recently requested blocks.
+    #[inline]
+    pub const fn block_receipts_cache(&self) -> &BlockReceiptsCache {
+        &self.block_receipts_cache
+    }
+
     /// Returns a handle to the signers.
     #[inline]
     pub const fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>> {
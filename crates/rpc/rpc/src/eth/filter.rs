@@ -23,7 +23,7 @@ use reth_rpc_eth_api::{
     EthApiTypes, EthFilterApiServer, FullEthApiTypes, RpcTransaction, TransactionCompat,
 };
 use reth_rpc_eth_types::{
-    logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
+    logs_utils::{self, append_matching_block_logs, LogCursor, LogsResponse, ProviderOrBlock},
     EthApiError, EthFilterConfig, EthStateCache, EthSubscriptionIdProvider,
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
@@ -36,6 +36,8 @@ use tokio::{
 };
 use tracing::{error, trace};
 
+use super::LogIndex;
+
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
@@ -77,6 +79,50 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
         tx_resp_builder: Eth::TransactionCompat,
+    ) -> Self {
+        Self::new_with_log_index(
+            provider,
+            pool,
+            eth_cache,
+            config,
+            task_spawner,
+            tx_resp_builder,
+            None,
+        )
+    }
+
+    /// Creates a new, shareable instance that looks up the blocks with matching logs in the given
+    /// [`LogIndex`].
+    ///
+    /// See also [`Self::new`].
+    pub fn with_log_index(
+        provider: Provider,
+        pool: Pool,
+        eth_cache: EthStateCache,
+        config: EthFilterConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+        tx_resp_builder: Eth::TransactionCompat,
+        log_index: Arc<dyn LogIndex>,
+    ) -> Self {
+        Self::new_with_log_index(
+            provider,
+            pool,
+            eth_cache,
+            config,
+            task_spawner,
+            tx_resp_builder,
+            Some(log_index),
+        )
+    }
+
+    fn new_with_log_index(
+        provider: Provider,
+        pool: Pool,
+        eth_cache: EthStateCache,
+        config: EthFilterConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+        tx_resp_builder: Eth::TransactionCompat,
+        log_index: Option<Arc<dyn LogIndex>>,
     ) -> Self {
         let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
             config;
@@ -92,6 +138,7 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            log_index,
         };
 
         let eth_filter = Self { inner: Arc::new(inner), tx_resp_builder };
@@ -211,9 +258,15 @@ where
                 };
                 let logs = self
                     .inner
-                    .get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
+                    .get_logs_in_block_range(
+                        &filter,
+                        from_block_number,
+                        to_block_number,
+                        None,
+                        info,
+                    )
                     .await?;
-                Ok(FilterChanges::Logs(logs))
+                Ok(FilterChanges::Logs(self.inner.all_logs(logs)?))
             }
         }
     }
@@ -236,7 +289,8 @@ where
             }
         };
 
-        self.inner.logs_for_filter(filter).await
+        let logs = self.inner.logs_for_filter(filter, None).await?;
+        self.inner.all_logs(logs)
     }
 }
 
@@ -325,9 +379,9 @@ where
     /// Returns logs matching given filter object.
     ///
     /// Handler for `eth_getLogs`
-    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
-        trace!(target: "rpc::eth", "Serving eth_getLogs");
-        Ok(self.inner.logs_for_filter(filter).await?)
+    async fn logs(&self, filter: Filter, cursor: Option<LogCursor>) -> RpcResult<LogsResponse> {
+        trace!(target: "rpc::eth", ?cursor, "Serving eth_getLogs");
+        Ok(self.inner.logs_for_filter(filter, cursor).await?)
     }
 }
 
@@ -363,6 +417,8 @@ struct EthFilterInner<Provider, Pool, Tx> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Optional index used to look up the blocks that contain matching logs
+    log_index: Option<Arc<dyn LogIndex>>,
 }
 
 impl<Provider, Pool, Tx> EthFilterInner<Provider, Pool, Tx>
@@ -370,8 +426,12 @@ where
     Provider: BlockReader + BlockIdReader + 'static,
     Pool: TransactionPool + 'static,
 {
-    /// Returns logs matching given filter object.
-    async fn logs_for_filter(&self, filter: Filter) -> Result<Vec<Log>, EthFilterError> {
+    /// Returns logs matching given filter object, starting at the cursor, if any.
+    async fn logs_for_filter(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+    ) -> Result<LogsResponse, EthFilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
                 // for all matching logs in the block
@@ -405,8 +465,9 @@ where
                     false,
                     header.timestamp,
                 )?;
+                skip_logs_before_cursor(&mut all_logs, 0, cursor);
 
-                Ok(all_logs)
+                Ok(LogsResponse::All(all_logs))
             }
             FilterBlockOption::Range { from_block, to_block } => {
                 // compute the range
@@ -424,8 +485,14 @@ where
                     .flatten();
                 let (from_block_number, to_block_number) =
                     logs_utils::get_filter_block_range(from, to, start_block, info);
-                self.get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
-                    .await
+                self.get_logs_in_block_range(
+                    &filter,
+                    from_block_number,
+                    to_block_number,
+                    cursor,
+                    info,
+                )
+                .await
             }
         }
     }
@@ -446,31 +513,86 @@ where
         Ok(id)
     }
 
-    /// Returns all logs in the given _inclusive_ range that match the filter
+    /// Returns all logs in the given _inclusive_ range that match the filter, starting at the
+    /// cursor, if any.
+    ///
+    /// If the logs of a range of multiple blocks exceed the configured limit, only the logs up to
+    /// the limit are returned, with the cursor of the first log that is cut off.
     ///
     /// Returns an error if:
     ///  - underlying database error
-    ///  - amount of matches exceeds configured limit
+    ///  - the cursor is outside of the range
     async fn get_logs_in_block_range(
         &self,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
+        cursor: Option<LogCursor>,
         chain_info: ChainInfo,
-    ) -> Result<Vec<Log>, EthFilterError> {
-        trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
+    ) -> Result<LogsResponse, EthFilterError> {
+        trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?cursor, ?filter, "finding logs in range");
 
         if to_block < from_block {
             return Err(EthFilterError::InvalidBlockRangeParams)
         }
 
-        if to_block - from_block > self.max_blocks_per_filter {
-            return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
-        }
+        // a query is continued at the block of the cursor
+        let from_block = match cursor {
+            Some(cursor) if (from_block..=to_block).contains(&cursor.block_number) => {
+                cursor.block_number
+            }
+            Some(_) => return Err(EthFilterError::InvalidLogCursor),
+            None => from_block,
+        };
+        // all logs of a single block are returned
+        let is_multi_block_range = from_block != to_block;
 
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
+        // the blocks covered by the index are looked up in the index, the logs bloom of the
+        // remaining blocks is checked
+        let mut bloom_from_block = from_block;
+        if let Some((indexed_to_block, block_numbers)) =
+            self.indexed_blocks(filter, from_block, to_block)?
+        {
+            // the index already narrowed down the blocks, so the limit applies to their number
+            // rather than the size of the range
+            if block_numbers.len() as u64 > self.max_blocks_per_filter {
+                return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+            }
+            bloom_from_block = indexed_to_block + 1;
+
+            for number in block_numbers {
+                let header = self
+                    .provider
+                    .sealed_header(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                let num_hash = BlockNumHash::new(header.number, header.hash());
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    num_hash,
+                    header.timestamp,
+                    cursor,
+                    chain_info.best_number,
+                )
+                .await?;
+
+                if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                    return Ok(self.partial_logs(all_logs))
+                }
+            }
+
+            if bloom_from_block > to_block {
+                return Ok(LogsResponse::All(all_logs))
+            }
+        }
+
+        if to_block - bloom_from_block > self.max_blocks_per_filter {
+            return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -478,7 +600,7 @@ where
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(bloom_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
@@ -498,35 +620,105 @@ where
                     };
 
                     let num_hash = BlockNumHash::new(header.number, block_hash);
-                    if let Some((receipts, maybe_block)) =
-                        self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
-                    {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            maybe_block
-                                .map(|block| ProviderOrBlock::Block(block))
-                                .unwrap_or_else(|| ProviderOrBlock::Provider(&self.provider)),
-                            &filter_params,
-                            num_hash,
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        num_hash,
+                        header.timestamp,
+                        cursor,
+                        chain_info.best_number,
+                    )
+                    .await?;
+
+                    if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                        return Ok(self.partial_logs(all_logs))
                     }
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(LogsResponse::All(all_logs))
+    }
+
+    /// Appends the logs of the block that match the filter and are not before the cursor.
+    ///
+    /// Blocks whose receipts are not available are skipped.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        num_hash: BlockNumHash,
+        timestamp: u64,
+        cursor: Option<LogCursor>,
+        best_number: u64,
+    ) -> Result<(), EthFilterError> {
+        let Some((receipts, maybe_block)) =
+            self.receipts_and_maybe_block(&num_hash, best_number).await?
+        else {
+            return Ok(())
+        };
+
+        let block_start = all_logs.len();
+        append_matching_block_logs(
+            all_logs,
+            maybe_block
+                .map(|block| ProviderOrBlock::Block(block))
+                .unwrap_or_else(|| ProviderOrBlock::Provider(&self.provider)),
+            filter_params,
+            num_hash,
+            &receipts,
+            false,
+            timestamp,
+        )?;
+        skip_logs_before_cursor(all_logs, block_start, cursor);
+
+        Ok(())
+    }
+
+    /// Cuts the logs off at the response limit and returns them with the cursor of the first log
+    /// that was cut off.
+    fn partial_logs(&self, mut logs: Vec<Log>) -> LogsResponse {
+        let cursor = LogCursor::of_log(&logs[self.max_logs_per_response]);
+        logs.truncate(self.max_logs_per_response);
+        LogsResponse::Partial { logs, cursor }
+    }
+
+    /// Returns all logs of the response, or the error for logs that exceed the response limit, for
+    /// requests that can't be continued with a cursor.
+    fn all_logs(&self, response: LogsResponse) -> Result<Vec<Log>, EthFilterError> {
+        match response {
+            LogsResponse::All(logs) => Ok(logs),
+            LogsResponse::Partial { cursor, .. } => Err(EthFilterError::QueryExceedsMaxResults {
+                max_logs: self.max_logs_per_response,
+                cursor,
+            }),
+        }
+    }
+
+    /// Returns the last block of the given _inclusive_ range that is covered by the [`LogIndex`],
+    /// if one is configured, and the blocks up to it that can contain logs matching the filter.
+    ///
+    /// Filters without addresses and topics match all logs, so they are never served from the
+    /// index.
+    fn indexed_blocks(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<(u64, Vec<u64>)>, EthFilterError> {
+        let Some(log_index) = &self.log_index else { return Ok(None) };
+        if filter.address.is_empty() && filter.topics.iter().all(|topic| topic.is_empty()) {
+            return Ok(None)
+        }
+        let Some(indexed_to_block) = log_index
+            .last_indexed_block()?
+            .map(|last_indexed| last_indexed.min(to_block))
+            .filter(|indexed_to_block| *indexed_to_block >= from_block)
+        else {
+            return Ok(None)
+        };
+        let blocks = log_index.blocks_with_logs(filter, from_block..=indexed_to_block)?;
+        Ok(Some((indexed_to_block, blocks)))
     }
 
     /// Retrieves receipts and block from cache if near the tip (4 blocks), otherwise only receipts.
@@ -711,6 +903,19 @@ impl Iterator for BlockRangeInclusiveIter {
     }
 }
 
+/// Removes the logs from `start` on that belong to the block of the cursor and precede it, since
+/// they were part of the previous response.
+fn skip_logs_before_cursor(logs: &mut Vec<Log>, start: usize, cursor: Option<LogCursor>) {
+    let Some(cursor) = cursor else { return };
+    let skipped = logs[start..]
+        .iter()
+        .take_while(|log| {
+            log.block_number == Some(cursor.block_number) && LogCursor::of_log(log) < cursor
+        })
+        .count();
+    logs.drain(start..start + skipped);
+}
+
 /// Errors that can occur in the handler implementation
 #[derive(Debug, thiserror::Error)]
 pub enum EthFilterError {
@@ -724,8 +929,20 @@ pub enum EthFilterError {
     #[error("query exceeds max block range {0}")]
     QueryExceedsMaxBlocks(u64),
     /// Query result is too large.
-    #[error("query exceeds max results {0}")]
-    QueryExceedsMaxResults(usize),
+    #[error(
+        "query exceeds max results {max_logs}, continue from log {} of block {} with eth_getLogs",
+        cursor.log_index,
+        cursor.block_number
+    )]
+    QueryExceedsMaxResults {
+        /// Maximum number of logs that can be returned in a response
+        max_logs: usize,
+        /// The first log that exceeded the limit
+        cursor: LogCursor,
+    },
+    /// The cursor of a query is outside of its block range.
+    #[error("log cursor is outside of the block range")]
+    InvalidLogCursor,
     /// Error serving request in `eth_` namespace.
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
@@ -745,9 +962,14 @@ impl From<EthFilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             EthFilterError::EthAPIError(err) => err.into(),
+            EthFilterError::QueryExceedsMaxResults { cursor, .. } => Self::owned(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+                Some(cursor),
+            ),
             err @ (EthFilterError::InvalidBlockRangeParams |
             EthFilterError::QueryExceedsMaxBlocks(_) |
-            EthFilterError::QueryExceedsMaxResults(_)) => {
+            EthFilterError::InvalidLogCursor) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
         }
@@ -763,8 +985,28 @@ impl From<ProviderError> for EthFilterError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{eth::helpers::types::EthTxBuilder, EthApi};
+    use alloy_primitives::{Address, B256};
     use rand::Rng;
-    use reth_testing_utils::generators;
+    use reth_chainspec::ChainSpecProvider;
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{SealedBlock, SealedHeader, StaticFileSegment};
+    use reth_provider::{
+        providers::BlockchainProvider2,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        StageCheckpointWriter, StaticFileProviderFactory, StaticFileWriter,
+    };
+    use reth_rpc_eth_api::EthFilterApiServer;
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestEthApi =
+        EthApi<BlockchainProvider2<MockNodeTypesWithDB>, TestPool, NoopNetwork, EthEvmConfig>;
 
     #[test]
     fn test_block_range_iter() {
@@ -787,4 +1029,104 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[test]
+    fn test_exceeds_max_results_returns_cursor() {
+        let err = EthFilterError::QueryExceedsMaxResults {
+            max_logs: 10,
+            cursor: LogCursor { block_number: 10, log_index: 3 },
+        };
+        assert_eq!(
+            err.to_string(),
+            "query exceeds max results 10, continue from log 3 of block 10 with eth_getLogs"
+        );
+
+        let err = jsonrpsee::types::error::ErrorObject::from(err);
+        let cursor = serde_json::from_str::<LogCursor>(err.data().unwrap().get()).unwrap();
+        assert_eq!(cursor, LogCursor { block_number: 10, log_index: 3 });
+        assert_eq!(err.data().unwrap().get(), r#"{"blockNumber":10,"logIndex":3}"#);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_logs_with_cursor() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        // every block has a single transaction whose logs are from `a`, except for the first log
+        // of even blocks
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut expected = Vec::new();
+        let mut parent = B256::ZERO;
+        for number in 0..10 {
+            let logs = (0..number % 3 + 1 + (number + 1) % 2)
+                .map(|log_index| {
+                    let address = if log_index == 0 && number % 2 == 0 { b } else { a };
+                    if address == a {
+                        expected.push(LogCursor { block_number: number, log_index });
+                    }
+                    alloy_primitives::Log::new_unchecked(address, vec![], Default::default())
+                })
+                .collect();
+            let receipt = Receipt { logs, success: true, ..Default::default() };
+
+            let block = random_block(
+                &mut rng,
+                number,
+                BlockParams { parent: Some(parent), tx_count: Some(1), ..Default::default() },
+            );
+            let mut header = block.header.unseal();
+            header.logs_bloom = receipt.bloom_slow();
+            let block = SealedBlock { header: SealedHeader::seal(header), body: block.body };
+            parent = block.hash();
+
+            provider_rw.tx_ref().put::<tables::Receipts>(number, receipt).unwrap();
+            provider_rw.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        }
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(9)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = BlockchainProvider2::new(factory).unwrap();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_filter = EthFilter::<_, _, TestEthApi>::new(
+            provider,
+            testing_pool(),
+            cache,
+            EthFilterConfig::default().max_logs_per_response(4),
+            Box::new(TokioTaskExecutor::default()),
+            EthTxBuilder,
+        );
+
+        // page through the logs, until all remaining logs fit into a response
+        let filter = Filter::new().address(a).from_block(0).to_block(9);
+        let mut logs = Vec::new();
+        let mut cursor = None;
+        loop {
+            match eth_filter.logs(filter.clone(), cursor).await.unwrap() {
+                LogsResponse::All(page) => {
+                    assert!(page.len() <= 4);
+                    logs.extend(page);
+                    break
+                }
+                LogsResponse::Partial { logs: page, cursor: next } => {
+                    assert_eq!(page.len(), 4);
+                    assert!(page.iter().all(|log| LogCursor::of_log(log) < next));
+                    logs.extend(page);
+                    cursor = Some(next);
+                }
+            }
+        }
+        assert_eq!(logs.iter().map(LogCursor::of_log).collect::<Vec<_>>(), expected);
+
+        // a cursor must be part of the range of the filter
+        let cursor = LogCursor { block_number: 10, log_index: 0 };
+        assert!(eth_filter.logs(filter, Some(cursor)).await.is_err());
+    }
 }
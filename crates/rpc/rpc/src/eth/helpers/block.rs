@@ -2,12 +2,13 @@
 
 use alloy_rpc_types_eth::{BlockId, TransactionReceipt};
 use reth_primitives::TransactionMeta;
-use reth_provider::{BlockReaderIdExt, HeaderProvider};
+use reth_provider::{BlockIdReader, BlockReaderIdExt, HeaderProvider};
 use reth_rpc_eth_api::{
     helpers::{EthBlocks, LoadBlock, LoadPendingBlock, LoadReceipt, SpawnBlocking},
-    RpcReceipt,
+    FromEthApiError, RpcNodeCore, RpcReceipt,
 };
-use reth_rpc_eth_types::{EthApiError, EthReceiptBuilder};
+use reth_rpc_eth_types::{receipt::build_block_receipts, EthApiError, EthReceiptBuilder};
+use std::sync::Arc;
 
 use crate::EthApi;
 
@@ -26,6 +27,46 @@ where
     where
         Self: LoadReceipt,
     {
+        if block_id.is_pending() {
+            return self.pending_block_receipts(block_id).await
+        }
+
+        let Some(block_hash) =
+            self.provider().block_hash_for_id(block_id).map_err(Self::Error::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+
+        if let Some(receipts) = self.block_receipts_cache().lock().get(&block_hash) {
+            return Ok(Some(receipts.to_vec()))
+        }
+
+        let Some((block, receipts)) = self
+            .cache()
+            .get_block_and_receipts(block_hash)
+            .await
+            .map_err(Self::Error::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+
+        let receipts = Arc::new(build_block_receipts(&block, &receipts)?);
+        self.block_receipts_cache().lock().insert(block_hash, receipts.clone());
+        Ok(Some(receipts.to_vec()))
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Self: EthBlocks<Error = EthApiError> + LoadReceipt,
+{
+    /// Returns the receipt responses of the pending block.
+    ///
+    /// These are not cached, since the pending block changes without changing its hash.
+    async fn pending_block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, EthApiError> {
         if let Some((block, receipts)) = self.load_block_and_receipts(block_id).await? {
             let block_number = block.number;
             let base_fee = block.base_fee_per_gas;
@@ -53,7 +94,7 @@ where
                     EthReceiptBuilder::new(&tx, meta, receipt, &receipts)
                         .map(|builder| builder.build())
                 })
-                .collect::<Result<Vec<_>, Self::Error>>()
+                .collect::<Result<Vec<_>, _>>()
                 .map(Some)
         }
 
@@ -0,0 +1,268 @@
+//! Index of the blocks that contain logs of an address or topic, used to serve log filters.
+
+use crate::block_index::{append_blocks, key_blocks, last_indexed_block, push_block, BlockIndexer};
+use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_rpc_types_eth::Filter;
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_provider::{
+    BlockNumReader, DBProvider, DatabaseProviderFactory, ProviderError, ProviderResult,
+    ReceiptProvider,
+};
+use reth_stages_types::StageId;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::RangeInclusive,
+};
+
+/// The id under which the last block of the log index is stored.
+pub const LOG_INDEX_ID: StageId = StageId::Other("LogIndex");
+
+/// An index from log addresses and topics to the blocks that contain them.
+///
+/// When configured, `eth_getLogs` and log filters use the index to look up the blocks of the
+/// requested range that can contain matching logs, instead of checking the logs bloom of every
+/// header in the range. The receipts of these blocks are still matched against the filter, so the
+/// index may return blocks without matching logs, but it must never omit a block with matching
+/// logs.
+///
+/// [`DatabaseLogIndex`] implements the index on top of tables that are maintained by a
+/// [`BlockIndexTask`](crate::BlockIndexTask) running the [`LogIndexer`].
+pub trait LogIndex: fmt::Debug + Send + Sync + 'static {
+    /// Returns the number of the highest block covered by the index, or `None` if no block is
+    /// covered.
+    ///
+    /// Blocks above it are searched without the index.
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the numbers of all blocks in the range, in ascending order, that can contain a log
+    /// matching the addresses and topics of the filter.
+    ///
+    /// The range must be covered by the index.
+    fn blocks_with_logs(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// A [`LogIndex`] backed by the [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] tables.
+///
+/// Topics are indexed regardless of their position in the log, so a filter for a topic at one
+/// position also returns the blocks that contain the topic at another position.
+#[derive(Clone)]
+pub struct DatabaseLogIndex<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> DatabaseLogIndex<Provider> {
+    /// Creates a new index that reads the log index tables of the given database.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> LogIndex for DatabaseLogIndex<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader + 'static,
+{
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        last_indexed_block(&self.provider, LOG_INDEX_ID)
+    }
+
+    fn blocks_with_logs(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let provider = self.provider.database_provider_ro()?;
+        let tx = provider.tx_ref();
+
+        // a log matches if it has one of the addresses and one of the topics of every position, so
+        // the candidates are the intersection of the blocks of every non-empty set
+        let mut blocks: Option<BTreeSet<BlockNumber>> = None;
+        let mut intersect = |set: BTreeSet<BlockNumber>| {
+            blocks = Some(match blocks.take() {
+                Some(blocks) => blocks.intersection(&set).copied().collect(),
+                None => set,
+            });
+        };
+
+        if !filter.address.is_empty() {
+            let mut set = BTreeSet::new();
+            for address in filter.address.iter() {
+                set.extend(key_blocks::<tables::LogAddressIndex, _>(tx, *address, &range)?);
+            }
+            intersect(set);
+        }
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut set = BTreeSet::new();
+            for topic in topics.iter() {
+                set.extend(key_blocks::<tables::LogTopicIndex, _>(tx, *topic, &range)?);
+            }
+            intersect(set);
+        }
+
+        Ok(blocks.map(Vec::from_iter).unwrap_or_else(|| range.collect()))
+    }
+}
+
+impl<Provider> fmt::Debug for DatabaseLogIndex<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseLogIndex").finish_non_exhaustive()
+    }
+}
+
+/// The blocks of every log address and topic of a range of blocks.
+#[derive(Debug, Default)]
+pub struct LogIndexEntries {
+    addresses: BTreeMap<Address, Vec<BlockNumber>>,
+    topics: BTreeMap<B256, Vec<BlockNumber>>,
+}
+
+/// A [`BlockIndexer`] that indexes the addresses and topics of the logs in the receipts of a block,
+/// for the [`DatabaseLogIndex`].
+#[derive(Clone)]
+pub struct LogIndexer<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> LogIndexer<Provider> {
+    /// Creates a new indexer that reads the receipts of the given provider.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> BlockIndexer for LogIndexer<Provider>
+where
+    Provider: ReceiptProvider + 'static,
+{
+    type Entries = LogIndexEntries;
+    type Error = ProviderError;
+
+    const ID: StageId = LOG_INDEX_ID;
+    const BATCH_SIZE: u64 = 1_000;
+
+    async fn collect(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<LogIndexEntries> {
+        let mut entries = LogIndexEntries::default();
+        for number in range {
+            // blocks without receipts, e.g. because they are pruned, can't be served anyway
+            let Some(receipts) = self.provider.receipts_by_block(number.into())? else { continue };
+            for log in receipts.iter().flat_map(|receipt| &receipt.logs) {
+                push_block(entries.addresses.entry(log.address).or_default(), number);
+                for topic in log.topics() {
+                    push_block(entries.topics.entry(*topic).or_default(), number);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write<TX: DbTxMut + DbTx>(&self, tx: &TX, entries: LogIndexEntries) -> ProviderResult<()> {
+        for (address, blocks) in entries.addresses {
+            append_blocks::<tables::LogAddressIndex, _>(tx, address, &blocks)?;
+        }
+        for (topic, blocks) in entries.topics {
+            append_blocks::<tables::LogTopicIndex, _>(tx, topic, &blocks)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Provider> fmt::Debug for LogIndexer<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogIndexer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockIndexTask;
+    use alloy_primitives::{Bytes, Log};
+    use reth_db::tables::ChainStateKey;
+    use reth_primitives::{Receipt, StaticFileSegment};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileProviderFactory,
+        StaticFileWriter,
+    };
+    use reth_stages_types::StageCheckpoint;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[tokio::test]
+    async fn index_logs_of_canonical_blocks() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (t1, t2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let logs = [(2, a, vec![t1]), (5, b, vec![t1, t2]), (7, a, vec![t2])];
+
+        // insert blocks with a single transaction, so the transaction number is the block number
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=9,
+            BlockRangeParams { tx_count: 1..2, ..Default::default() },
+        );
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        for block in blocks {
+            let logs = logs
+                .iter()
+                .filter(|(number, ..)| *number == block.number)
+                .map(|(_, address, topics)| {
+                    Log::new_unchecked(*address, topics.clone(), Bytes::new())
+                })
+                .collect();
+            provider
+                .tx_ref()
+                .put::<tables::Receipts>(block.number, Receipt { logs, ..Default::default() })
+                .unwrap();
+            provider.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        }
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(9)).unwrap();
+        provider.commit().unwrap();
+
+        let task = BlockIndexTask::new(factory.clone(), LogIndexer::new(factory.clone()));
+        let index = DatabaseLogIndex::new(factory.clone());
+        assert_eq!(index.last_indexed_block().unwrap(), None);
+        task.sync().await.unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(9));
+
+        let blocks = |filter: Filter| index.blocks_with_logs(&filter, 0..=9).unwrap();
+        assert_eq!(blocks(Filter::new().address(a)), vec![2, 7]);
+        assert_eq!(blocks(Filter::new().address(vec![a, b])), vec![2, 5, 7]);
+        assert_eq!(blocks(Filter::new().event_signature(t1)), vec![2, 5]);
+        assert_eq!(blocks(Filter::new().address(a).topic1(t2)), vec![7]);
+        // topics are indexed regardless of their position
+        assert_eq!(blocks(Filter::new().address(b).event_signature(t2)), vec![5]);
+        assert!(index.blocks_with_logs(&Filter::new().address(a), 3..=6).unwrap().is_empty());
+
+        // blocks above the block the index is unwound to are indexed again
+        task.unwind_to(4).unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(4));
+        task.sync().await.unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(9));
+        assert_eq!(blocks(Filter::new().address(a)), vec![2, 7]);
+
+        // an index whose last block is no longer canonical only covers the finalized blocks, until
+        // it is indexed again from the finalized block
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::StageCheckpointProgresses>(LOG_INDEX_ID.to_string(), B256::ZERO.to_vec())
+            .unwrap();
+        provider.tx_ref().put::<tables::ChainState>(ChainStateKey::LastFinalizedBlock, 3).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(3));
+        task.sync().await.unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(9));
+        assert_eq!(blocks(Filter::new().address(a)), vec![2, 7]);
+    }
+}
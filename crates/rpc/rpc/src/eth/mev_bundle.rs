@@ -0,0 +1,228 @@
+//! Submission of bundles to the [`MevBundlePool`] of the local payload builder.
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCallBundle, EthSendBundle, SendBundleRequest, SendBundleResponse,
+    SimBundleOverrides, SimBundleResponse,
+};
+use jsonrpsee::core::RpcResult;
+use reth_primitives::TransactionSignedEcRecovered;
+use reth_rpc_api::{MevFullApiServer, MevSimApiServer};
+use reth_rpc_eth_api::{
+    helpers::{Call, EthTransactions, LoadPendingBlock},
+    EthSendBundleApiServer,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::alt::{MevBundle, MevBundlePool};
+use std::sync::Arc;
+
+use crate::eth::{sim_bundle::EthSimBundle, EthBundle};
+
+/// `eth_sendBundle` and `mev_sendBundle` implementation.
+///
+/// Bundles are simulated on top of the latest block and added to a [`MevBundlePool`], which the
+/// payload builder includes at the top of the blocks the bundles target.
+pub struct MevBundleApi<Eth> {
+    /// All nested fields bundled together.
+    inner: Arc<MevBundleApiInner<Eth>>,
+}
+
+impl<Eth: Clone> MevBundleApi<Eth> {
+    /// Create a new `MevBundleApi` instance that adds bundles to the given pool.
+    pub fn new(
+        eth_api: Eth,
+        bundle_pool: MevBundlePool,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> Self {
+        Self {
+            inner: Arc::new(MevBundleApiInner {
+                call_bundle: EthBundle::new(eth_api.clone(), blocking_task_guard.clone()),
+                sim_bundle: EthSimBundle::new(eth_api, blocking_task_guard),
+                bundle_pool,
+            }),
+        }
+    }
+}
+
+impl<Eth> MevBundleApi<Eth> {
+    /// Returns the pool the bundles are added to.
+    pub fn bundle_pool(&self) -> &MevBundlePool {
+        &self.inner.bundle_pool
+    }
+}
+
+impl<Eth> MevBundleApi<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    /// Simulates a bundle of `eth_sendBundle` on top of the latest block and adds it to the pool,
+    /// unless a transaction that is not allowed to revert reverts.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+
+        let transactions = txs
+            .iter()
+            .cloned()
+            .map(|tx| recover_raw_transaction(tx).map(|tx| tx.into_ecrecovered_transaction()))
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_no_blob_transactions(&transactions)?;
+
+        let response = self
+            .inner
+            .call_bundle
+            .call_bundle(EthCallBundle {
+                txs,
+                block_number,
+                state_block_number: BlockNumberOrTag::Latest,
+                timestamp: None,
+                gas_limit: None,
+                difficulty: None,
+                base_fee: None,
+            })
+            .await?;
+        if let Some(reverted) = response
+            .results
+            .iter()
+            .find(|res| res.revert.is_some() && !reverting_tx_hashes.contains(&res.tx_hash))
+        {
+            return Err(EthApiError::InvalidParams(
+                MevBundleError::TransactionReverted(reverted.tx_hash).to_string(),
+            )
+            .into())
+        }
+
+        let bundle_hash = self
+            .inner
+            .bundle_pool
+            .add_bundle(MevBundle {
+                transactions,
+                block_range: block_number..=block_number,
+                min_timestamp,
+                max_timestamp,
+                replacement_uuid,
+            })
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthSendBundleApiServer for MevBundleApi<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).await.map_err(Into::into)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for MevBundleApi<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        let transactions = self
+            .inner
+            .sim_bundle
+            .parse_and_flatten_bundle(&request)?
+            .into_iter()
+            .map(|item| item.tx.with_signer(item.signer))
+            .collect::<Vec<_>>();
+        ensure_no_blob_transactions(&transactions)?;
+
+        let response = MevSimApiServer::sim_bundle(
+            &self.inner.sim_bundle,
+            request.clone(),
+            SimBundleOverrides::default(),
+        )
+        .await?;
+        if !response.success {
+            return Err(EthApiError::InvalidParams(
+                MevBundleError::SimulationFailed(response.error.unwrap_or_default()).to_string(),
+            )
+            .into())
+        }
+
+        let block = request.inclusion.block_number();
+        let max_block = request.inclusion.max_block_number().unwrap_or(block);
+        let bundle_hash = self
+            .inner
+            .bundle_pool
+            .add_bundle(MevBundle {
+                transactions,
+                block_range: block..=max_block,
+                min_timestamp: None,
+                max_timestamp: None,
+                replacement_uuid: None,
+            })
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(&self.inner.sim_bundle, request, overrides).await
+    }
+}
+
+/// Returns an error if the bundle contains blob transactions, which the payload builder only
+/// includes from the transaction pool.
+fn ensure_no_blob_transactions(
+    transactions: &[TransactionSignedEcRecovered],
+) -> Result<(), EthApiError> {
+    if transactions.iter().any(|tx| tx.is_eip4844()) {
+        return Err(EthApiError::InvalidParams(MevBundleError::BlobTransaction.to_string()))
+    }
+    Ok(())
+}
+
+/// Container type for `MevBundleApi` internals
+struct MevBundleApiInner<Eth> {
+    /// Simulates `eth_sendBundle` bundles
+    call_bundle: EthBundle<Eth>,
+    /// Simulates `mev_sendBundle` bundles
+    sim_bundle: EthSimBundle<Eth>,
+    /// The pool the bundles are added to
+    bundle_pool: MevBundlePool,
+}
+
+impl<Eth> std::fmt::Debug for MevBundleApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MevBundleApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for MevBundleApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// [`MevBundleApi`] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum MevBundleError {
+    /// Thrown if the bundle contains a blob transaction.
+    #[error("bundle contains blob transactions")]
+    BlobTransaction,
+    /// Thrown if a transaction that is not allowed to revert reverted in the simulation.
+    #[error("transaction {0} reverted")]
+    TransactionReverted(B256),
+    /// Thrown if the simulation of the bundle failed.
+    #[error("bundle simulation failed: {0}")]
+    SimulationFailed(String),
+}
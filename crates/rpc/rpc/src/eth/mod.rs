@@ -4,14 +4,22 @@ pub mod bundle;
 pub mod core;
 pub mod filter;
 pub mod helpers;
+pub mod log_index;
+pub mod mev_bundle;
+pub mod private_tx;
 pub mod pubsub;
 pub mod sim_bundle;
+pub mod user_operation;
 
 /// Implementation of `eth` namespace API.
 pub use bundle::EthBundle;
 pub use core::EthApi;
 pub use filter::EthFilter;
+pub use log_index::{DatabaseLogIndex, LogIndex, LogIndexer};
+pub use mev_bundle::MevBundleApi;
+pub use private_tx::EthPrivateTransactions;
 pub use pubsub::EthPubSub;
+pub use user_operation::UserOperationApi;
 
 pub use helpers::{
     signer::DevSigner,
@@ -0,0 +1,79 @@
+//! Submission of private transactions that are only included by the local payload builder.
+
+use alloy_primitives::{BlockNumber, Bytes, B256};
+use alloy_rpc_types_mev::{CancelPrivateTransactionRequest, PrivateTransactionRequest};
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::EthPrivateTransactionApiServer;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, EthResult};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+
+/// `eth_sendPrivateTransaction` implementation.
+///
+/// Private transactions are added to the pool as
+/// [`TransactionOrigin::Private`](reth_transaction_pool::TransactionOrigin::Private), so they are
+/// never propagated to peers and only included by the local payload builder. They're removed from
+/// the pool after their max block number, see
+/// [`PoolConfig::private_tx_lifetime`](reth_transaction_pool::PoolConfig::private_tx_lifetime).
+#[derive(Debug, Clone)]
+pub struct EthPrivateTransactions<Pool> {
+    /// The pool the transactions are added to.
+    pool: Pool,
+}
+
+impl<Pool> EthPrivateTransactions<Pool> {
+    /// Creates a new instance that adds private transactions to the given pool.
+    pub const fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+impl<Pool> EthPrivateTransactions<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Adds the raw transaction to the pool, to be included up to the block with the given max
+    /// block number.
+    pub async fn send_private_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<BlockNumber>,
+    ) -> EthResult<B256> {
+        if let Some(max_block_number) = max_block_number {
+            let latest = self.pool.block_info().last_seen_block_number;
+            if max_block_number <= latest {
+                return Err(EthApiError::InvalidParams(format!(
+                    "max block number {max_block_number} is not after the latest block {latest}"
+                )))
+            }
+        }
+
+        let recovered = recover_raw_transaction(tx)?;
+        let transaction = Pool::Transaction::from_pooled(recovered.into());
+        Ok(self.pool.add_private_transaction(transaction, max_block_number).await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Pool> EthPrivateTransactionApiServer for EthPrivateTransactions<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        let PrivateTransactionRequest { tx, max_block_number, .. } = request;
+        Ok(Self::send_private_transaction(self, tx, max_block_number).await?)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        Ok(Self::send_private_transaction(self, bytes, None).await?)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(self.pool.remove_private_transaction(request.tx_hash).is_some())
+    }
+}
@@ -16,12 +16,15 @@ use jsonrpsee::{
 };
 use reth_network_api::NetworkInfo;
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
-use reth_rpc_eth_api::{pubsub::EthPubSubApiServer, TransactionCompat};
+use reth_rpc_eth_api::{
+    pubsub::{EthPubSubApiServer, PendingTransactionsFilter, SubscriptionParams},
+    TransactionCompat,
+};
 use reth_rpc_eth_types::logs_utils;
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_rpc_types_compat::transaction::from_recovered;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
+use reth_transaction_pool::{NewTransactionEvent, PoolTransaction, TransactionPool};
 use serde::Serialize;
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
@@ -93,7 +96,7 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
@@ -111,7 +114,7 @@ async fn handle_accepted<Provider, Pool, Events, Network, Eth>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
-    params: Option<Params>,
+    params: Option<SubscriptionParams>,
     tx_resp_builder: Eth,
 ) -> Result<(), ErrorObject<'static>>
 where
@@ -131,10 +134,13 @@ where
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
-                    return Err(invalid_params_rpc_err("Invalid params for logs"))
+                Some(SubscriptionParams::Standard(Params::Logs(filter))) => {
+                    FilteredParams::new(Some(*filter))
                 }
+                Some(
+                    SubscriptionParams::Standard(Params::Bool(_)) |
+                    SubscriptionParams::PendingTransactions(_),
+                ) => return Err(invalid_params_rpc_err("Invalid params for logs")),
                 _ => FilteredParams::default(),
             };
             let stream = pubsub
@@ -143,44 +149,51 @@ where
             pipe_from_stream(accepted_sink, stream).await
         }
         SubscriptionKind::NewPendingTransactions => {
-            if let Some(params) = params {
-                match params {
-                    Params::Bool(true) => {
-                        // full transaction objects requested
-                        let stream = pubsub.full_pending_transaction_stream().filter_map(|tx| {
-                            let tx_value = match from_recovered(
-                                tx.transaction.to_recovered_transaction(),
-                                &tx_resp_builder,
-                            ) {
-                                Ok(tx) => {
-                                    Some(EthSubscriptionResult::FullTransaction(Box::new(tx)))
-                                }
-                                Err(err) => {
-                                    error!(target = "rpc",
-                                        %err,
-                                        "Failed to fill transaction with block context"
-                                    );
-                                    None
-                                }
-                            };
-                            std::future::ready(tx_value)
-                        });
-                        return pipe_from_stream(accepted_sink, stream).await
-                    }
-                    Params::Bool(false) | Params::None => {
-                        // only hashes requested
-                    }
-                    Params::Logs(_) => {
-                        return Err(invalid_params_rpc_err(
-                            "Invalid params for newPendingTransactions",
-                        ))
-                    }
+            let filter = match params {
+                Some(SubscriptionParams::PendingTransactions(filter)) => filter,
+                Some(SubscriptionParams::Standard(Params::Bool(full_transactions))) => {
+                    PendingTransactionsFilter { full_transactions, ..Default::default() }
+                }
+                Some(SubscriptionParams::Standard(Params::Logs(_))) => {
+                    return Err(invalid_params_rpc_err("Invalid params for newPendingTransactions"))
                 }
+                Some(SubscriptionParams::Standard(Params::None)) | None => Default::default(),
+            };
+
+            if filter == PendingTransactionsFilter::default() {
+                // only hashes of all transactions requested
+                let stream = pubsub
+                    .pending_transaction_hashes_stream()
+                    .map(EthSubscriptionResult::<()>::TransactionHash);
+                return pipe_from_stream(accepted_sink, stream).await
             }
 
-            let stream = pubsub
-                .pending_transaction_hashes_stream()
-                .map(EthSubscriptionResult::<()>::TransactionHash);
+            let stream = pubsub.full_pending_transaction_stream().filter_map(move |tx| {
+                let tx = &tx.transaction;
+                let (to, tip) = (tx.transaction.to(), tx.transaction.priority_fee_or_price());
+                if !filter.matches(tx.sender(), to, tip) {
+                    return std::future::ready(None)
+                }
+                if !filter.full_transactions {
+                    return std::future::ready(Some(EthSubscriptionResult::TransactionHash(
+                        *tx.hash(),
+                    )))
+                }
+
+                // full transaction objects requested
+                let tx_value = match from_recovered(tx.to_recovered_transaction(), &tx_resp_builder)
+                {
+                    Ok(tx) => Some(EthSubscriptionResult::FullTransaction(Box::new(tx))),
+                    Err(err) => {
+                        error!(target = "rpc",
+                            %err,
+                            "Failed to fill transaction with block context"
+                        );
+                        None
+                    }
+                };
+                std::future::ready(tx_value)
+            });
             pipe_from_stream(accepted_sink, stream).await
         }
         SubscriptionKind::Syncing => {
@@ -238,7 +251,7 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<T, St>(
+pub(crate) async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
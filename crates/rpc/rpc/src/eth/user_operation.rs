@@ -0,0 +1,383 @@
+//! Validation of ERC-4337 user operations for the [`UserOperationPool`].
+
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
+use alloy_rpc_types_eth::{
+    erc4337::{SendUserOperation, UserOperationGasEstimation},
+    BlockId,
+};
+use jsonrpsee::core::RpcResult;
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_eth_api::{
+    helpers::{Call, LoadPendingBlock},
+    EthUserOperationApiServer, FromEthApiError,
+};
+use reth_rpc_eth_types::{
+    user_operation::{
+        balance_of_input, create_sender_input, decode_balance_of_output,
+        decode_validate_paymaster_user_op_output, decode_validate_user_op_output,
+        UserOperationError, UserOperationExt, UserOperationRequest, ValidationData,
+        ValidationRulesInspector, SUPPORTED_ENTRY_POINTS,
+    },
+    EthApiError,
+};
+use reth_transaction_pool::alt::{PooledUserOperation, UserOperationPool};
+use revm::{
+    db::{CacheDB, DatabaseCommit, DatabaseRef},
+    primitives::{EnvWithHandlerCfg, ExecutionResult, ResultAndState, TxEnv},
+};
+use std::sync::Arc;
+
+/// The gas of the simulated calls that is not spent by the called contract.
+const CALL_BASE_GAS: u64 = 21_000;
+
+/// `eth_sendUserOperation` and `eth_estimateUserOperationGas` implementation.
+///
+/// User operations are validated by calling the account and paymaster from the entry point on
+/// top of the latest block, while enforcing the ERC-7562 validation rules with the
+/// [`ValidationRulesInspector`]. Valid operations are added to a [`UserOperationPool`], which a
+/// bundler that shares the pool packs into `handleOps` transactions.
+pub struct UserOperationApi<Eth> {
+    /// All nested fields bundled together.
+    inner: Arc<UserOperationApiInner<Eth>>,
+}
+
+impl<Eth> UserOperationApi<Eth> {
+    /// Create a new `UserOperationApi` instance that adds user operations to the given pool.
+    pub fn new(eth_api: Eth, user_operation_pool: UserOperationPool) -> Self {
+        Self { inner: Arc::new(UserOperationApiInner { eth_api, user_operation_pool }) }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Returns the pool the user operations are added to.
+    pub fn user_operation_pool(&self) -> &UserOperationPool {
+        &self.inner.user_operation_pool
+    }
+}
+
+impl<Eth> UserOperationApi<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Validates the user operation and adds it to the pool.
+    pub async fn send_user_operation(
+        &self,
+        operation: SendUserOperation,
+        entry_point: Address,
+    ) -> Result<B256, Eth::Error> {
+        ensure_valid_fields(&operation, entry_point)?;
+        if operation.pre_verification_gas() < U256::from(operation.estimate_pre_verification_gas())
+        {
+            return Err(EthApiError::from(UserOperationError::InvalidFields(
+                "preVerificationGas too low".to_string(),
+            ))
+            .into())
+        }
+
+        let outcome = self.simulate(operation.clone(), false).await?;
+        if U256::from(outcome.verification_gas + outcome.paymaster_verification_gas) >
+            operation.verification_gas_limit()
+        {
+            return Err(EthApiError::from(UserOperationError::InvalidFields(
+                "verificationGasLimit too low".to_string(),
+            ))
+            .into())
+        }
+
+        self.user_operation_pool()
+            .add_user_operation(PooledUserOperation { hash: outcome.hash, entry_point, operation })
+            .map_err(|err| Eth::Error::from_eth_err(UserOperationError::from(err)))
+    }
+
+    /// Estimates the gas limits of the user operation, without validating its signature and
+    /// validity time range.
+    pub async fn estimate_user_operation_gas(
+        &self,
+        operation: SendUserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGasEstimation, Eth::Error> {
+        ensure_valid_fields(&operation, entry_point)?;
+        let pre_verification_gas = operation.estimate_pre_verification_gas();
+        let outcome = self.simulate(operation.clone(), true).await?;
+
+        let (verification_gas, paymaster_verification_gas) = match operation {
+            // the verification gas limit of v0.6 operations also covers the paymaster
+            SendUserOperation::EntryPointV06(_) => {
+                (outcome.verification_gas + outcome.paymaster_verification_gas, 0)
+            }
+            SendUserOperation::EntryPointV07(_) => {
+                (outcome.verification_gas, outcome.paymaster_verification_gas)
+            }
+        };
+        Ok(UserOperationGasEstimation {
+            pre_verification_gas: U256::from(pre_verification_gas),
+            verification_gas: U256::from(verification_gas),
+            paymaster_verification_gas: U256::from(paymaster_verification_gas),
+            call_gas_limit: U256::from(outcome.call_gas),
+        })
+    }
+
+    /// Simulates the validation of the operation on top of the latest block.
+    ///
+    /// If `estimate` is set, the signature and validity time range are not checked and the
+    /// execution of the operation is simulated as well.
+    async fn simulate(
+        &self,
+        operation: SendUserOperation,
+        estimate: bool,
+    ) -> Result<SimulationOutcome, Eth::Error> {
+        let (mut cfg, block_env, at) = self.eth_api().evm_env_at(BlockId::latest()).await?;
+        // the calls are made from the entry point, which is a contract and doesn't pay for gas
+        cfg.disable_base_fee = true;
+        cfg.disable_eip3607 = true;
+        let chain_id = cfg.chain_id;
+        let timestamp = block_env.timestamp.saturating_to::<u64>();
+
+        let eth_api = self.eth_api().clone();
+        self.eth_api()
+            .spawn_with_state_at_block(at, move |state| {
+                let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let entry_point = operation.entry_point();
+                let sender = operation.sender();
+                let hash = operation.hash(chain_id);
+                let mut inspector = ValidationRulesInspector::new(entry_point, sender);
+                let mut call = |to: Address, input: Bytes, inspector: Option<&mut _>| {
+                    call_from_entry_point(
+                        &eth_api,
+                        &mut db,
+                        &env,
+                        entry_point,
+                        to,
+                        input,
+                        inspector,
+                    )
+                };
+
+                let mut verification_gas = 0;
+                let init_code = operation.init_code();
+                if !init_code.is_empty() {
+                    inspector.set_deploying(true);
+                    let result = call(
+                        operation.sender_creator(),
+                        create_sender_input(init_code),
+                        Some(&mut inspector),
+                    )?;
+                    inspector.set_deploying(false);
+                    verification_gas +=
+                        ensure_success(result, UserOperationError::ValidationReverted)?;
+                }
+
+                let result = call(entry_point, balance_of_input(sender), None)?;
+                let deposit = result.output().and_then(|out| decode_balance_of_output(out));
+                let prefund = operation.required_prefund();
+                let missing_funds = prefund.saturating_sub(deposit.unwrap_or_default());
+
+                let result = call(
+                    sender,
+                    operation.validate_user_op_input(hash, missing_funds),
+                    Some(&mut inspector),
+                )?;
+                let output = result.output().cloned().unwrap_or_default();
+                verification_gas += ensure_success(result, UserOperationError::ValidationReverted)?;
+                let validation = decode_validate_user_op_output(&output)
+                    .ok_or(UserOperationError::ValidationReverted(output))
+                    .map_err(EthApiError::from)?;
+                check_validation_data(validation, timestamp, estimate)
+                    .map_err(EthApiError::from)?;
+
+                let mut paymaster_verification_gas = 0;
+                if let Some(paymaster) = operation.paymaster() {
+                    let result = call(
+                        paymaster,
+                        operation.validate_paymaster_user_op_input(hash, prefund),
+                        Some(&mut inspector),
+                    )?;
+                    let output = result.output().cloned().unwrap_or_default();
+                    paymaster_verification_gas +=
+                        ensure_success(result, UserOperationError::PaymasterValidationReverted)?;
+                    let validation = decode_validate_paymaster_user_op_output(&output)
+                        .ok_or(UserOperationError::PaymasterValidationReverted(output))
+                        .map_err(EthApiError::from)?;
+                    check_validation_data(validation, timestamp, estimate)
+                        .map_err(EthApiError::from)?;
+                }
+
+                if let Some(violation) = inspector.take_violation() {
+                    return Err(Eth::Error::from_eth_err(UserOperationError::RuleViolation(
+                        violation,
+                    )))
+                }
+
+                let mut call_gas = 0;
+                if estimate {
+                    let result = call(sender, operation.call_data().clone(), None)?;
+                    // the entry point forwards at most 63/64 of its remaining gas
+                    call_gas =
+                        ensure_success(result, UserOperationError::ExecutionReverted)? * 64 / 63;
+                }
+
+                Ok(SimulationOutcome {
+                    hash,
+                    verification_gas,
+                    paymaster_verification_gas,
+                    call_gas,
+                })
+            })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthUserOperationApiServer for UserOperationApi<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    async fn send_user_operation(
+        &self,
+        user_operation: UserOperationRequest,
+        entry_point: Address,
+    ) -> RpcResult<B256> {
+        Self::send_user_operation(self, user_operation.into(), entry_point)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: UserOperationRequest,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation> {
+        Self::estimate_user_operation_gas(self, user_operation.into(), entry_point)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn supported_entry_points(&self) -> RpcResult<Vec<Address>> {
+        Ok(SUPPORTED_ENTRY_POINTS.to_vec())
+    }
+}
+
+/// The outcome of a simulated validation.
+#[derive(Debug)]
+struct SimulationOutcome {
+    /// The hash of the operation.
+    hash: B256,
+    /// The gas used by the deployment and validation of the account.
+    verification_gas: u64,
+    /// The gas used by the validation of the paymaster.
+    paymaster_verification_gas: u64,
+    /// The gas used by the execution of the operation, only simulated for estimations.
+    call_gas: u64,
+}
+
+/// Executes a call from the entry point and commits its state changes.
+fn call_from_entry_point<Eth, DB>(
+    eth_api: &Eth,
+    db: &mut CacheDB<DB>,
+    env: &EnvWithHandlerCfg,
+    entry_point: Address,
+    to: Address,
+    input: Bytes,
+    inspector: Option<&mut ValidationRulesInspector>,
+) -> Result<ExecutionResult, Eth::Error>
+where
+    Eth: Call,
+    DB: DatabaseRef,
+    EthApiError: From<DB::Error>,
+{
+    let mut env = env.clone();
+    env.tx = TxEnv {
+        caller: entry_point,
+        gas_limit: env.block.gas_limit.saturating_to(),
+        gas_price: U256::ZERO,
+        transact_to: TxKind::Call(to),
+        data: input,
+        ..Default::default()
+    };
+    let (ResultAndState { result, state }, _) = match inspector {
+        Some(inspector) => eth_api.transact_with_inspector(&mut *db, env, inspector)?,
+        None => eth_api.transact(&mut *db, env)?,
+    };
+    db.commit(state);
+    Ok(result)
+}
+
+/// Returns the gas used by the called contract, or the error for the output if the call failed.
+fn ensure_success(
+    result: ExecutionResult,
+    error: impl FnOnce(Bytes) -> UserOperationError,
+) -> Result<u64, EthApiError> {
+    let gas_used = result.gas_used().saturating_sub(CALL_BASE_GAS);
+    match result {
+        ExecutionResult::Success { .. } => Ok(gas_used),
+        ExecutionResult::Revert { output, .. } => Err(error(output).into()),
+        ExecutionResult::Halt { .. } => Err(error(Bytes::new()).into()),
+    }
+}
+
+/// Checks the validation data returned by the account or paymaster.
+///
+/// The signature and validity time range are not checked if `estimate` is set.
+fn check_validation_data(
+    validation: ValidationData,
+    timestamp: u64,
+    estimate: bool,
+) -> Result<(), UserOperationError> {
+    if validation.signature_failed() {
+        if !estimate {
+            return Err(UserOperationError::InvalidSignature)
+        }
+    } else if !validation.aggregator.is_zero() {
+        return Err(UserOperationError::UnsupportedAggregator(validation.aggregator))
+    }
+    if !estimate && !validation.is_valid_at(timestamp) {
+        return Err(UserOperationError::OutOfTimeRange)
+    }
+    Ok(())
+}
+
+/// Checks the entry point and the fields of the operation that don't depend on the state.
+fn ensure_valid_fields(
+    operation: &SendUserOperation,
+    entry_point: Address,
+) -> Result<(), EthApiError> {
+    if !SUPPORTED_ENTRY_POINTS.contains(&entry_point) || operation.entry_point() != entry_point {
+        return Err(UserOperationError::UnsupportedEntryPoint(entry_point).into())
+    }
+    if let Some((name, _)) =
+        operation.gas_limits().into_iter().find(|(_, value)| *value > U256::from(u128::MAX))
+    {
+        return Err(UserOperationError::InvalidFields(format!("{name} exceeds 128 bits")).into())
+    }
+    if operation.max_priority_fee_per_gas() > operation.max_fee_per_gas() {
+        return Err(UserOperationError::InvalidFields(
+            "maxPriorityFeePerGas exceeds maxFeePerGas".to_string(),
+        )
+        .into())
+    }
+    Ok(())
+}
+
+/// Container type for `UserOperationApi` internals
+struct UserOperationApiInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The pool the user operations are added to
+    user_operation_pool: UserOperationPool,
+}
+
+impl<Eth> std::fmt::Debug for UserOperationApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserOperationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for UserOperationApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
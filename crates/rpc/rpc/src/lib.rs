@@ -32,7 +32,9 @@ use jsonwebtoken as _;
 use pin_project as _;
 use tower as _;
 
+mod address_index;
 mod admin;
+mod block_index;
 mod debug;
 mod engine;
 pub mod eth;
@@ -40,20 +42,31 @@ mod net;
 mod otterscan;
 mod reth;
 mod rpc;
+mod stateless;
 mod trace;
+mod trace_index;
+mod trace_scheduler;
 mod txpool;
 mod validation;
 mod web3;
 
+pub use address_index::{AddressIndex, AddressIndexer, DatabaseAddressIndex, ADDRESS_INDEX_ID};
 pub use admin::AdminApi;
+pub use block_index::{append_blocks, BlockIndexTask, BlockIndexer};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    EthApi, EthBundle, EthFilter, EthPrivateTransactions, EthPubSub, MevBundleApi, UserOperationApi,
+};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
+pub use stateless::{StatelessValidationApi, StatelessValidationError};
 pub use trace::TraceApi;
+pub use trace_index::{
+    trace_addresses, DatabaseTraceIndex, TraceIndex, TraceIndexError, TraceIndexer, TRACE_INDEX_ID,
+};
 pub use txpool::TxPoolApi;
 pub use validation::{ValidationApi, ValidationApiConfig};
 pub use web3::Web3Api;
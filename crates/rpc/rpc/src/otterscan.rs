@@ -1,7 +1,8 @@
+use crate::AddressIndex;
 use alloy_consensus::Transaction;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, Header, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -24,19 +25,38 @@ use revm_inspectors::{
     transfer::{TransferInspector, TransferKind},
 };
 use revm_primitives::{ExecutionResult, SignedAuthorization};
+use std::{fmt, ops::RangeInclusive, sync::Arc};
 
 const API_LEVEL: u64 = 8;
 
 /// Otterscan API.
-#[derive(Debug)]
 pub struct OtterscanApi<Eth> {
     eth: Eth,
+    /// Index used to search the transactions of an address
+    address_index: Option<Arc<dyn AddressIndex>>,
 }
 
 impl<Eth> OtterscanApi<Eth> {
     /// Creates a new instance of `Otterscan`.
+    ///
+    /// The address search is not available without an [`AddressIndex`].
     pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+        Self { eth, address_index: None }
+    }
+
+    /// Creates a new instance of `Otterscan` that searches the transactions of an address with the
+    /// given [`AddressIndex`].
+    pub fn with_address_index(eth: Eth, address_index: Arc<dyn AddressIndex>) -> Self {
+        Self { eth, address_index: Some(address_index) }
+    }
+}
+
+impl<Eth: fmt::Debug> fmt::Debug for OtterscanApi<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtterscanApi")
+            .field("eth", &self.eth)
+            .field("address_index", &self.address_index.is_some())
+            .finish()
     }
 }
 
@@ -60,6 +80,158 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+        > + FullEthApiTypes,
+{
+    /// Returns the transactions of the address in the blocks of the range, newest first, until at
+    /// least `page_size` transactions are found.
+    ///
+    /// The blocks are searched from the end of the range if `reverse` is set, and from its start
+    /// otherwise. Also returns whether the range may contain more transactions of the address.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        reverse: bool,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let index = self
+            .address_index
+            .as_deref()
+            .ok_or_else(|| internal_rpc_err("address search requires an address index"))?;
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let (mut start, mut end) = range.into_inner();
+        let mut has_more = start <= end;
+        'search: while has_more {
+            let blocks = self.candidate_blocks(index, address, start..=end, reverse, page_size)?;
+            if blocks.is_empty() {
+                has_more = false;
+                break
+            }
+
+            for block in blocks {
+                let (block_txs, block_receipts) = self.address_transactions(address, block).await?;
+                if reverse {
+                    txs.extend(block_txs.into_iter().rev());
+                    receipts.extend(block_receipts.into_iter().rev());
+                } else {
+                    txs.extend(block_txs);
+                    receipts.extend(block_receipts);
+                }
+
+                // shrink the range to the blocks that haven't been searched yet
+                if reverse {
+                    has_more = block > start;
+                    end = block.saturating_sub(1);
+                } else {
+                    has_more = block < end;
+                    start = block + 1;
+                }
+
+                if txs.len() >= page_size || !has_more {
+                    break 'search
+                }
+            }
+        }
+
+        if has_more {
+            has_more = !self.candidate_blocks(index, address, start..=end, reverse, 1)?.is_empty();
+        }
+
+        // results are always ordered from the newest to the oldest transaction
+        if !reverse {
+            txs.reverse();
+            receipts.reverse();
+        }
+
+        let (first_page, last_page) = if reverse { (false, !has_more) } else { (!has_more, false) };
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+
+    /// Returns up to `limit` blocks of the range that can contain transactions of the address,
+    /// ordered in the search direction.
+    ///
+    /// Blocks that are not covered by the index yet are always searched.
+    fn candidate_blocks(
+        &self,
+        index: &dyn AddressIndex,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> RpcResult<Vec<BlockNumber>> {
+        let (start, end) = range.into_inner();
+        let limit = limit.max(1);
+        let last_indexed = index.last_indexed_block().map_err(EthApiError::from)?;
+        let indexed = last_indexed
+            .filter(|last_indexed| start <= *last_indexed)
+            .map(|last_indexed| start..=end.min(last_indexed));
+        let unindexed = last_indexed.map_or(0, |last_indexed| last_indexed + 1).max(start)..=end;
+
+        let mut blocks = Vec::new();
+        if reverse {
+            blocks.extend(unindexed.rev().take(limit));
+            if let Some(indexed) = indexed.filter(|_| blocks.len() < limit) {
+                blocks.extend(
+                    index
+                        .blocks_with_transactions(address, indexed, true, limit - blocks.len())
+                        .map_err(EthApiError::from)?,
+                );
+            }
+        } else {
+            if let Some(indexed) = indexed {
+                blocks = index
+                    .blocks_with_transactions(address, indexed, false, limit)
+                    .map_err(EthApiError::from)?;
+            }
+            blocks.extend(unindexed.take(limit - blocks.len()));
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the transactions of the block that are sent by the address, sent to it or that
+    /// created it, with their receipts.
+    async fn address_transactions(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let block_id = block_number.into();
+        let block = self.eth.block_by_number(block_id, true);
+        let block_id = block_id.into();
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+        let timestamp = Some(block.header.timestamp);
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .filter(|(tx, receipt)| {
+                TransactionResponse::from(tx) == address ||
+                    Transaction::to(tx) == Some(address) ||
+                    receipt.contract_address() == Some(address)
+            })
+            .map(|(tx, receipt)| {
+                let receipt = ots_receipt(&receipt, Transaction::ty(&tx), timestamp);
+                (tx, receipt)
+            })
+            .unzip())
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>> for OtterscanApi<Eth>
 where
@@ -240,35 +412,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Transaction::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used() as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                    authorization_list: receipt
-                        .authorization_list()
-                        .map(<[SignedAuthorization]>::to_vec),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -280,21 +424,36 @@ where
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // `0` searches from the latest block
+        let end = match block_number {
+            0 => self.eth.block_number()?.saturating_to(),
+            block_number => block_number - 1,
+        };
+        let mut res = self.search_transactions(address, 0..=end, true, page_size).await?;
+        res.first_page = block_number == 0;
+        Ok(res)
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // `0` searches from the genesis block
+        let start = match block_number {
+            0 => 0,
+            block_number => block_number + 1,
+        };
+        let end = self.eth.block_number()?.saturating_to();
+        let mut res = self.search_transactions(address, start..=end, false, page_size).await?;
+        res.last_page = block_number == 0;
+        Ok(res)
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -375,3 +534,36 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into the trimmed receipt returned by Otterscan, without logs.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used() as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+        authorization_list: receipt.authorization_list().map(<[SignedAuthorization]>::to_vec),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
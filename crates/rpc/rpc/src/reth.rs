@@ -1,41 +1,70 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
 
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, TxHash, B256, U256};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink};
 use reth_errors::RethResult;
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
-use reth_rpc_api::RethApiServer;
+use reth_provider::{
+    BlockReaderIdExt, CanonStateNotification, CanonStateSubscriptions, Chain, ChangeSetReader,
+    StateProviderFactory,
+};
+use reth_rpc_api::{
+    AccountStateDiff, ChainStateDiff, NonceGap, PoolEvent, PromotionBlocker, RethApiServer,
+    SenderPoolStatus, SenderPoolTransaction,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{
+    AdmissionRecord, BlockInfo, FullTransactionEvent, LocalTransaction, PoolTransaction,
+    TransactionPool, ValidPoolTransaction,
+};
 use tokio::sync::oneshot;
 
+use crate::eth::pubsub::pipe_from_stream;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Pool, Events> {
+    inner: Arc<RethApiInner<Provider, Pool, Events>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Pool, Events> RethApi<Provider, Pool, Events> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// The transaction pool.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        chain_events: Events,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, pool, chain_events, task_spawner });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Pool, Events> RethApi<Provider, Pool, Events>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -83,12 +112,40 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the recorded transaction pool admission decisions for the transaction.
+    pub async fn tx_admission_history(&self, tx_hash: TxHash) -> EthResult<Vec<AdmissionRecord>> {
+        self.on_blocking_task(|this| async move { Ok(this.pool().admission_history(tx_hash)) })
+            .await
+    }
+
+    /// Returns the transactions of the sender in the pool and what keeps the queued ones from
+    /// being promoted to pending.
+    pub async fn sender_pool_status(&self, sender: Address) -> EthResult<SenderPoolStatus> {
+        self.on_blocking_task(|this| async move { this.try_sender_pool_status(sender) }).await
+    }
+
+    fn try_sender_pool_status(&self, sender: Address) -> EthResult<SenderPoolStatus> {
+        let state = self.provider().latest()?;
+        let state_nonce = state.account_nonce(sender)?.unwrap_or_default();
+        let balance = state.account_balance(sender)?.unwrap_or_default();
+        Ok(sender_pool_status(
+            sender,
+            state_nonce,
+            balance,
+            self.pool().block_info(),
+            self.pool().get_pending_transactions_by_sender(sender),
+            self.pool().get_queued_transactions_by_sender(sender),
+        ))
+    }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Pool, Events> RethApiServer for RethApi<Provider, Pool, Events>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -97,23 +154,313 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getTxAdmissionHistory`
+    async fn reth_get_tx_admission_history(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Vec<AdmissionRecord>> {
+        Ok(Self::tx_admission_history(self, tx_hash).await?)
+    }
+
+    /// Handler for `reth_getLocalTransactionStatus`
+    async fn reth_get_local_transaction_status(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<LocalTransaction>> {
+        Ok(self.pool().local_transaction(tx_hash))
+    }
+
+    /// Handler for `reth_getLocalTransactions`
+    async fn reth_get_local_transactions(&self) -> RpcResult<Vec<LocalTransaction>> {
+        Ok(self.pool().local_transactions())
+    }
+
+    /// Handler for `reth_getSenderPoolStatus`
+    async fn reth_get_sender_pool_status(&self, sender: Address) -> RpcResult<SenderPoolStatus> {
+        Ok(Self::sender_pool_status(self, sender).await?)
+    }
+
+    /// Handler for `reth_subscribeChainNotifications`
+    async fn reth_subscribe_chain_notifications(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let stream = self
+            .inner
+            .chain_events
+            .canonical_state_stream()
+            .flat_map(|notification| futures::stream::iter(chain_state_diffs(&notification)));
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+
+    /// Handler for `reth_subscribePoolEvents`
+    async fn reth_subscribe_pool_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let this = self.clone();
+        let stream = self
+            .pool()
+            .all_transactions_event_listener()
+            .filter_map(move |event| futures::future::ready(this.pool_event(event)));
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+}
+
+impl<Provider, Pool, Events> RethApi<Provider, Pool, Events>
+where
+    Provider: BlockReaderIdExt,
+{
+    /// Converts the event of the pool into a [`PoolEvent`], looking up the position of mined
+    /// transactions.
+    ///
+    /// Returns `None` for propagation events, which are not tied to a single transaction.
+    fn pool_event<T: PoolTransaction>(&self, event: FullTransactionEvent<T>) -> Option<PoolEvent> {
+        let event = match event {
+            FullTransactionEvent::Pending(tx_hash) => PoolEvent::Pending { tx_hash },
+            FullTransactionEvent::Queued(tx_hash) => PoolEvent::Queued { tx_hash },
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                PoolEvent::Replaced { tx_hash: *transaction.hash(), replaced_by }
+            }
+            FullTransactionEvent::Discarded { tx_hash, reason } => {
+                PoolEvent::Discarded { tx_hash, reason }
+            }
+            FullTransactionEvent::Invalid(tx_hash) => PoolEvent::Invalid { tx_hash },
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                let (block_number, transaction_index) = self
+                    .provider()
+                    .transaction_by_hash_with_meta(tx_hash)
+                    .ok()
+                    .flatten()
+                    .filter(|(_, meta)| meta.block_hash == block_hash)
+                    .map(|(_, meta)| (meta.block_number, meta.index))
+                    .unzip();
+                PoolEvent::Mined { tx_hash, block_hash, block_number, transaction_index }
+            }
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+        Some(event)
+    }
+}
+
+/// Reconstructs why the queued transactions of the sender are not pending.
+///
+/// Like the pool, this walks the transactions in nonce order starting at the state nonce: a
+/// transaction is blocked by a gap if any nonce before it is missing, and by the balance if the
+/// cumulative cost up to and including the transaction exceeds it.
+fn sender_pool_status<T: PoolTransaction>(
+    sender: Address,
+    state_nonce: u64,
+    balance: U256,
+    block_info: BlockInfo,
+    pending: Vec<Arc<ValidPoolTransaction<T>>>,
+    queued: Vec<Arc<ValidPoolTransaction<T>>>,
+) -> SenderPoolStatus {
+    let mut transactions = pending
+        .into_iter()
+        .map(|tx| (tx, true))
+        .chain(queued.into_iter().map(|tx| (tx, false)))
+        .collect::<Vec<_>>();
+    transactions.sort_by_key(|(tx, _)| tx.nonce());
+
+    let mut status = SenderPoolStatus {
+        sender,
+        state_nonce,
+        balance,
+        pending: Vec::new(),
+        queued: Vec::new(),
+        nonce_gaps: Vec::new(),
+    };
+    let mut next_nonce = state_nonce;
+    let mut cumulative_cost = U256::ZERO;
+    for (tx, is_pending) in transactions {
+        let nonce = tx.nonce();
+        if nonce > next_nonce {
+            status.nonce_gaps.push(NonceGap { from: next_nonce, to: nonce - 1 });
+        }
+        next_nonce = next_nonce.max(nonce + 1);
+        cumulative_cost = cumulative_cost.saturating_add(tx.cost());
+
+        let mut transaction =
+            SenderPoolTransaction { hash: *tx.hash(), nonce, blocked_by: Vec::new() };
+        if is_pending {
+            status.pending.push(transaction);
+            continue
+        }
+
+        if !status.nonce_gaps.is_empty() {
+            transaction.blocked_by.push(PromotionBlocker::NonceGap);
+        }
+        if cumulative_cost > balance {
+            transaction
+                .blocked_by
+                .push(PromotionBlocker::InsufficientBalance { required: cumulative_cost, balance });
+        }
+        let max_fee_per_gas = tx.max_fee_per_gas();
+        if max_fee_per_gas < block_info.pending_basefee as u128 {
+            transaction.blocked_by.push(PromotionBlocker::FeeCapBelowBaseFee {
+                max_fee_per_gas,
+                base_fee: block_info.pending_basefee,
+            });
+        }
+        if let (Some(max_fee_per_blob_gas), Some(blob_fee)) =
+            (tx.transaction.max_fee_per_blob_gas(), block_info.pending_blob_fee)
+        {
+            if max_fee_per_blob_gas < blob_fee {
+                transaction.blocked_by.push(PromotionBlocker::BlobFeeCapBelowBlobFee {
+                    max_fee_per_blob_gas,
+                    blob_fee,
+                });
+            }
+        }
+        status.queued.push(transaction);
+    }
+    status
+}
+
+/// Returns the state diffs of a canonical state notification, the reverted chain first.
+fn chain_state_diffs(notification: &CanonStateNotification) -> Vec<ChainStateDiff> {
+    let mut diffs = Vec::with_capacity(2);
+    if let Some(reverted) = notification.reverted() {
+        diffs.push(chain_state_diff(&reverted, true));
+    }
+    diffs.push(chain_state_diff(&notification.committed(), false));
+    diffs
+}
+
+/// Returns the state changes of the chain.
+///
+/// If the chain was reverted, this restores the state before the chain from the original values of
+/// its bundle state.
+fn chain_state_diff(chain: &Chain, reverted: bool) -> ChainStateDiff {
+    let mut diff = ChainStateDiff {
+        first_block: chain.first().num_hash(),
+        last_block: chain.tip().num_hash(),
+        reverted,
+        ..Default::default()
+    };
+
+    for (address, account) in chain.execution_outcome().bundle_accounts_iter() {
+        let (info, original_info) = if reverted {
+            (&account.original_info, &account.info)
+        } else {
+            (&account.info, &account.original_info)
+        };
+        if info != original_info {
+            diff.accounts.insert(
+                address,
+                info.as_ref().map(|info| AccountStateDiff {
+                    balance: info.balance,
+                    nonce: info.nonce,
+                    code_hash: info.code_hash,
+                }),
+            );
+        }
+
+        let storage = account
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed())
+            .map(|(key, slot)| {
+                let value =
+                    if reverted { slot.previous_or_original_value } else { slot.present_value };
+                (B256::new(key.to_be_bytes()), value)
+            })
+            .collect::<BTreeMap<_, _>>();
+        if !storage.is_empty() {
+            diff.storage.insert(address, storage);
+        }
+    }
+
+    if !reverted {
+        diff.code = chain
+            .execution_outcome()
+            .bundle
+            .contracts
+            .iter()
+            .map(|(code_hash, code)| (*code_hash, code.original_bytes()))
+            .collect();
+    }
+
+    diff
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Pool, Events> std::fmt::Debug for RethApi<Provider, Pool, Events> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Pool, Events> Clone for RethApi<Provider, Pool, Events> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Pool, Events> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The transaction pool.
+    pool: Pool,
+    /// A type that allows to create new event subscriptions.
+    chain_events: Events,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn sender_pool_status_blockers() {
+        let mut factory = MockTransactionFactory::default();
+        let first =
+            MockTransaction::eip1559().with_nonce(0).with_gas_limit(21_000).with_max_fee(10);
+        let second = first.next();
+        let fourth = second.skip(1).with_max_fee(1);
+        let balance = first.cost();
+        let block_info = BlockInfo { pending_basefee: 5, ..Default::default() };
+
+        let status = sender_pool_status(
+            first.sender(),
+            0,
+            balance,
+            block_info,
+            vec![factory.validated_arc(first.clone())],
+            vec![factory.validated_arc(fourth), factory.validated_arc(second.clone())],
+        );
+
+        assert_eq!(status.pending.len(), 1);
+        assert!(status.pending[0].blocked_by.is_empty());
+        assert_eq!(status.nonce_gaps, vec![NonceGap { from: 2, to: 2 }]);
+
+        let queued = &status.queued;
+        assert_eq!(queued.iter().map(|tx| tx.nonce).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(
+            queued[0].blocked_by,
+            vec![PromotionBlocker::InsufficientBalance {
+                required: first.cost() + second.cost(),
+                balance
+            }]
+        );
+        assert_eq!(queued[1].blocked_by.len(), 3);
+        assert_eq!(queued[1].blocked_by[0], PromotionBlocker::NonceGap);
+        assert_eq!(
+            queued[1].blocked_by[2],
+            PromotionBlocker::FeeCapBelowBaseFee { max_fee_per_gas: 1, base_fee: 5 }
+        );
+    }
+}
@@ -0,0 +1,568 @@
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, map::B256HashMap, Address, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV3,
+    PayloadError, PayloadStatus, PayloadStatusEnum,
+};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_consensus::{Consensus, PostExecutionInput};
+use reth_errors::{BlockExecutionError, BlockValidationError, ConsensusError, ProviderError};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_payload_validator::ExecutionPayloadValidator;
+use reth_primitives::{GotExpected, SealedHeader};
+use reth_provider::{BlockExecutionInput, BlockHashReader, HeaderProvider};
+use reth_rpc_api::StatelessValidationApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use reth_trie::{HashedPostState, HashedStorage, Nibbles, TrieAccount, TrieNode, EMPTY_ROOT_HASH};
+use reth_trie_sparse::{RevealedSparseTrie, SparseTrieError};
+use revm::db::{DatabaseRef, WrapDatabaseRef};
+use revm_primitives::{AccountInfo, Bytecode, EVMError};
+use std::sync::Arc;
+
+/// The type that implements the stateless validation rpc trait.
+///
+/// Payloads are executed against the state of an [`ExecutionWitness`] instead of the state of the
+/// database, and the state root is computed from the trie nodes of the witness. Only the headers
+/// of the parent and the ancestors of the payload are read from the database.
+#[derive(Debug, derive_more::Deref)]
+pub struct StatelessValidationApi<Provider: ChainSpecProvider, E> {
+    #[deref]
+    inner: Arc<StatelessValidationApiInner<Provider, E>>,
+}
+
+impl<Provider, E> StatelessValidationApi<Provider, E>
+where
+    Provider: ChainSpecProvider,
+{
+    /// Create a new instance of the [`StatelessValidationApi`]
+    pub fn new(provider: Provider, consensus: Arc<dyn Consensus>, executor_provider: E) -> Self {
+        let payload_validator = ExecutionPayloadValidator::new(provider.chain_spec());
+        let inner = Arc::new(StatelessValidationApiInner {
+            provider,
+            consensus,
+            payload_validator,
+            executor_provider,
+        });
+
+        Self { inner }
+    }
+}
+
+impl<Provider, E> StatelessValidationApi<Provider, E>
+where
+    Provider: ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + HeaderProvider
+        + BlockHashReader
+        + 'static,
+    E: BlockExecutorProvider,
+{
+    /// Validates the payload against the state of the given witness.
+    pub fn validate_payload_with_witness(
+        &self,
+        payload: ExecutionPayload,
+        sidecar: ExecutionPayloadSidecar,
+        witness: &ExecutionWitness,
+    ) -> Result<(), StatelessValidationError> {
+        let block = self
+            .payload_validator
+            .ensure_well_formed_payload(payload, sidecar)?
+            .try_seal_with_senders()
+            .map_err(|_| BlockValidationError::SenderRecoveryError)?;
+
+        let parent = self
+            .provider
+            .header(&block.parent_hash)?
+            .ok_or(StatelessValidationError::MissingParent(block.parent_hash))?;
+        let parent = SealedHeader::new(parent, block.parent_hash);
+
+        self.consensus.validate_header_with_total_difficulty(&block.header, U256::MAX)?;
+        self.consensus.validate_header(&block.header)?;
+        self.consensus.validate_header_against_parent(&block.header, &parent)?;
+        self.consensus.validate_block_pre_execution(&block)?;
+
+        let trie = WitnessTrie::new(&witness.state);
+        let db = WitnessDatabase {
+            trie,
+            codes: &witness.codes,
+            state_root: parent.state_root,
+            provider: &self.provider,
+        };
+        let block = block.unseal();
+        let output = self
+            .executor_provider
+            .executor(WrapDatabaseRef(db))
+            .execute(BlockExecutionInput::new(&block, U256::MAX))?;
+
+        self.consensus.validate_block_post_execution(
+            &block,
+            PostExecutionInput::new(&output.receipts, &output.requests),
+        )?;
+
+        let state_root = trie.state_root(
+            parent.state_root,
+            &HashedPostState::from_bundle_state(&output.state.state),
+        )?;
+        if state_root != block.state_root {
+            return Err(ConsensusError::BodyStateRootDiff(
+                GotExpected { got: state_root, expected: block.state_root }.into(),
+            )
+            .into())
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, E> StatelessValidationApiServer for StatelessValidationApi<Provider, E>
+where
+    Provider: ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + HeaderProvider
+        + BlockHashReader
+        + 'static,
+    E: BlockExecutorProvider,
+{
+    /// Handler for `reth_newPayloadWithWitnessV3`
+    async fn new_payload_with_witness_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        witness: ExecutionWitness,
+    ) -> RpcResult<PayloadStatus> {
+        let block_hash = payload.payload_inner.payload_inner.block_hash;
+        let sidecar = ExecutionPayloadSidecar::v3(CancunPayloadFields {
+            versioned_hashes,
+            parent_beacon_block_root,
+        });
+
+        match self.validate_payload_with_witness(ExecutionPayload::V3(payload), sidecar, &witness) {
+            Ok(()) => Ok(PayloadStatus::new(PayloadStatusEnum::Valid, Some(block_hash))),
+            Err(err) if err.is_invalid_block() => {
+                Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                    validation_error: err.to_string(),
+                }))
+            }
+            Err(err) => Err(invalid_params_rpc_err(err.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StatelessValidationApiInner<Provider: ChainSpecProvider, E> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Consensus implementation.
+    consensus: Arc<dyn Consensus>,
+    /// Execution payload validator.
+    payload_validator: ExecutionPayloadValidator<Provider::ChainSpec>,
+    /// Block executor factory.
+    executor_provider: E,
+}
+
+/// The trie nodes of an [`ExecutionWitness`].
+#[derive(Debug, Clone, Copy)]
+struct WitnessTrie<'a> {
+    /// Trie nodes by their hash.
+    nodes: &'a B256HashMap<Bytes>,
+}
+
+impl<'a> WitnessTrie<'a> {
+    const fn new(nodes: &'a B256HashMap<Bytes>) -> Self {
+        Self { nodes }
+    }
+
+    /// Returns the node with the given hash.
+    fn node(&self, hash: B256) -> Result<TrieNode, StatelessValidationError> {
+        let node = self.nodes.get(&hash).ok_or(StatelessValidationError::MissingTrieNode(hash))?;
+        Ok(TrieNode::decode(&mut &node[..])?)
+    }
+
+    /// Returns the child node that is either referenced by its hash or embedded.
+    fn child(&self, child: &[u8]) -> Result<TrieNode, StatelessValidationError> {
+        if child.len() == B256::len_bytes() + 1 {
+            self.node(B256::from_slice(&child[1..]))
+        } else {
+            Ok(TrieNode::decode(&mut &child[..])?)
+        }
+    }
+
+    /// Returns the value of the leaf with the given key in the trie with the given root.
+    fn get(&self, root: B256, key: B256) -> Result<Option<Vec<u8>>, StatelessValidationError> {
+        if root == EMPTY_ROOT_HASH {
+            return Ok(None)
+        }
+
+        let path = Nibbles::unpack(key);
+        let mut node = self.node(root)?;
+        let mut offset = 0;
+        loop {
+            match node {
+                TrieNode::EmptyRoot => return Ok(None),
+                TrieNode::Branch(branch) => {
+                    let Some(nibble) = path.get(offset) else { return Ok(None) };
+                    let branch = branch.as_ref();
+                    let child = branch
+                        .children()
+                        .find_map(|(index, child)| (index == *nibble).then_some(child))
+                        .flatten();
+                    let Some(child) = child else { return Ok(None) };
+                    node = self.child(child)?;
+                    offset += 1;
+                }
+                TrieNode::Extension(extension) => {
+                    if !path[offset..].starts_with(&extension.key) {
+                        return Ok(None)
+                    }
+                    offset += extension.key.len();
+                    node = self.child(&extension.child)?;
+                }
+                TrieNode::Leaf(leaf) => {
+                    return Ok((path[offset..] == leaf.key[..]).then_some(leaf.value))
+                }
+            }
+        }
+    }
+
+    /// Returns the account with the given hashed address in the state trie with the given root.
+    fn account(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+    ) -> Result<Option<TrieAccount>, StatelessValidationError> {
+        self.get(state_root, hashed_address)?
+            .map(|account| TrieAccount::decode(&mut &account[..]))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Returns a sparse trie with all nodes of the trie with the given root that are part of the
+    /// witness revealed.
+    fn reveal(&self, root: B256) -> Result<RevealedSparseTrie, StatelessValidationError> {
+        if root == EMPTY_ROOT_HASH {
+            return Ok(RevealedSparseTrie::default())
+        }
+
+        let root_node = self.node(root)?;
+        let mut trie = RevealedSparseTrie::from_root(root_node.clone())?;
+        let mut stack = vec![(Nibbles::default(), root_node)];
+        while let Some((path, node)) = stack.pop() {
+            // embedded nodes are revealed with their parent, so only the children that are
+            // referenced by their hash are looked up
+            let children: Vec<(Nibbles, B256)> = match &node {
+                TrieNode::Branch(branch) => branch
+                    .as_ref()
+                    .children()
+                    .filter_map(|(nibble, child)| {
+                        let hash = child?.as_hash()?;
+                        let mut child_path = path.clone();
+                        child_path.push(nibble);
+                        Some((child_path, hash))
+                    })
+                    .collect(),
+                TrieNode::Extension(extension) => {
+                    let mut child_path = path.clone();
+                    child_path.extend_from_slice(&extension.key);
+                    extension.child.as_hash().map(|hash| (child_path, hash)).into_iter().collect()
+                }
+                TrieNode::EmptyRoot | TrieNode::Leaf(_) => Vec::new(),
+            };
+
+            for (child_path, hash) in children {
+                // nodes that are not part of the witness stay blinded
+                let Some(encoded) = self.nodes.get(&hash) else { continue };
+                let child = TrieNode::decode(&mut &encoded[..])?;
+                trie.reveal_node(child_path.clone(), child.clone())?;
+                stack.push((child_path, child));
+            }
+        }
+
+        Ok(trie)
+    }
+
+    /// Computes the state root after applying the given state changes to the state with the given
+    /// root.
+    fn state_root(
+        &self,
+        state_root: B256,
+        state: &HashedPostState,
+    ) -> Result<B256, StatelessValidationError> {
+        let mut trie = self.reveal(state_root)?;
+
+        let hashed_addresses = state.accounts.keys().chain(
+            state
+                .storages
+                .keys()
+                .filter(|hashed_address| !state.accounts.contains_key(*hashed_address)),
+        );
+        for hashed_address in hashed_addresses {
+            let path = Nibbles::unpack(hashed_address);
+            let previous = self.account(state_root, *hashed_address)?;
+
+            let account = match state.accounts.get(hashed_address) {
+                Some(Some(account)) => TrieAccount::from((*account, EMPTY_ROOT_HASH)),
+                Some(None) => {
+                    if previous.is_some() {
+                        trie.remove_leaf(&path)?;
+                    }
+                    continue
+                }
+                None => match previous {
+                    Some(previous) => previous,
+                    None => continue,
+                },
+            };
+
+            let previous_storage_root = previous.map_or(EMPTY_ROOT_HASH, |a| a.storage_root);
+            let storage_root = match state.storages.get(hashed_address) {
+                Some(storage) => self.storage_root(previous_storage_root, storage)?,
+                None => previous_storage_root,
+            };
+
+            let account = TrieAccount { storage_root, ..account };
+            trie.update_leaf(path, alloy_rlp::encode(account))?;
+        }
+
+        Ok(trie.root())
+    }
+
+    /// Computes the storage root after applying the given storage changes to the storage with the
+    /// given root.
+    fn storage_root(
+        &self,
+        storage_root: B256,
+        storage: &HashedStorage,
+    ) -> Result<B256, StatelessValidationError> {
+        let storage_root = if storage.wiped { EMPTY_ROOT_HASH } else { storage_root };
+        let mut trie = self.reveal(storage_root)?;
+
+        for (hashed_slot, value) in &storage.storage {
+            let path = Nibbles::unpack(hashed_slot);
+            if value.is_zero() {
+                if self.get(storage_root, *hashed_slot)?.is_some() {
+                    trie.remove_leaf(&path)?;
+                }
+            } else {
+                trie.update_leaf(path, alloy_rlp::encode_fixed_size(value).to_vec())?;
+            }
+        }
+
+        Ok(trie.root())
+    }
+}
+
+/// A [`DatabaseRef`] that reads the state from an [`ExecutionWitness`] and block hashes from the
+/// provider.
+#[derive(Debug)]
+struct WitnessDatabase<'a, Provider> {
+    trie: WitnessTrie<'a>,
+    /// Bytecode by its hash.
+    codes: &'a B256HashMap<Bytes>,
+    /// The state root of the parent block.
+    state_root: B256,
+    provider: &'a Provider,
+}
+
+impl<Provider> WitnessDatabase<'_, Provider> {
+    fn account(&self, address: Address) -> Result<Option<TrieAccount>, ProviderError> {
+        Ok(self.trie.account(self.state_root, keccak256(address))?)
+    }
+}
+
+impl<Provider: BlockHashReader> DatabaseRef for WitnessDatabase<'_, Provider> {
+    type Error = ProviderError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Ok(self.account(address)?.map(|account| AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.code_hash,
+            code: None,
+        }))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default())
+        }
+        let code = self
+            .codes
+            .get(&code_hash)
+            .ok_or(StatelessValidationError::MissingBytecode(code_hash))?;
+        Ok(Bytecode::new_raw(code.clone()))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let Some(account) = self.account(address)? else { return Ok(U256::ZERO) };
+        let value = self
+            .trie
+            .get(account.storage_root, keccak256(B256::from(index)))?
+            .map(|value| U256::decode(&mut &value[..]))
+            .transpose()
+            .map_err(StatelessValidationError::from)?;
+        Ok(value.unwrap_or_default())
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        Ok(self.provider.block_hash(number)?.unwrap_or_default())
+    }
+}
+
+/// Errors thrown by the stateless validation API.
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessValidationError {
+    /// A trie node that is required for the validation is not part of the witness.
+    #[error("missing trie node {_0} in witness")]
+    MissingTrieNode(B256),
+    /// A bytecode that is required for the validation is not part of the witness.
+    #[error("missing bytecode {_0} in witness")]
+    MissingBytecode(B256),
+    /// The header of the parent block is unknown.
+    #[error("missing parent header {_0}")]
+    MissingParent(B256),
+    /// A trie node or leaf of the witness could not be decoded.
+    #[error("invalid witness: {_0}")]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The state root could not be computed with the trie nodes of the witness.
+    #[error("incomplete witness: {_0}")]
+    SparseTrie(#[from] SparseTrieError),
+    /// The payload is malformed.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// The block violates a consensus rule.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// Failed to read from the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The execution of the block failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+}
+
+impl StatelessValidationError {
+    /// Returns `true` if the payload is invalid, and `false` if the payload could not be validated
+    /// with the given witness.
+    pub fn is_invalid_block(&self) -> bool {
+        match self {
+            // the database errors of the execution are missing witness data
+            Self::Execution(BlockExecutionError::Validation(BlockValidationError::EVM {
+                error,
+                ..
+            })) => !matches!(**error, EVMError::Database(_)),
+            Self::Payload(_) |
+            Self::Consensus(_) |
+            Self::Execution(
+                BlockExecutionError::Validation(_) | BlockExecutionError::Consensus(_),
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<BlockValidationError> for StatelessValidationError {
+    fn from(err: BlockValidationError) -> Self {
+        Self::Execution(err.into())
+    }
+}
+
+impl From<StatelessValidationError> for ProviderError {
+    fn from(err: StatelessValidationError) -> Self {
+        Self::TrieWitnessError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Account;
+    use reth_trie::HashBuilder;
+    use reth_trie_common::{proof::ProofRetainer, root};
+
+    /// Returns the root and all nodes of the trie with the given leaves.
+    fn trie(leaves: &[(B256, Vec<u8>)]) -> (B256, B256HashMap<Bytes>) {
+        let mut leaves = leaves.to_vec();
+        leaves.sort_by_key(|(key, _)| *key);
+        let targets = leaves.iter().map(|(key, _)| Nibbles::unpack(key)).collect();
+        let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in &leaves {
+            builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = builder.root();
+        let nodes = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| (keccak256(&node), node))
+            .collect();
+        (root, nodes)
+    }
+
+    fn account(nonce: u64) -> Account {
+        Account { nonce, balance: U256::from(nonce), bytecode_hash: None }
+    }
+
+    fn leaf(account: Account, storage_root: B256) -> Vec<u8> {
+        alloy_rlp::encode(TrieAccount::from((account, storage_root)))
+    }
+
+    #[test]
+    fn witness_trie_get() {
+        let leaves: Vec<_> =
+            (0..20u8).map(|i| (keccak256([i]), leaf(account(i as u64), EMPTY_ROOT_HASH))).collect();
+        let (root, nodes) = trie(&leaves);
+        let trie = WitnessTrie::new(&nodes);
+
+        for (key, value) in &leaves {
+            assert_eq!(trie.get(root, *key).unwrap().as_ref(), Some(value));
+        }
+        assert_eq!(trie.get(root, keccak256([100])).unwrap(), None);
+        assert_eq!(trie.get(EMPTY_ROOT_HASH, keccak256([1])).unwrap(), None);
+        assert!(matches!(
+            WitnessTrie::new(&B256HashMap::default()).get(root, keccak256([1])),
+            Err(StatelessValidationError::MissingTrieNode(_))
+        ));
+    }
+
+    #[test]
+    fn witness_trie_state_root() {
+        let slot = keccak256(B256::with_last_byte(1));
+        let storage = vec![(slot, U256::from(1))];
+        let storage_leaves: Vec<_> = storage
+            .iter()
+            .map(|(slot, value)| (*slot, alloy_rlp::encode_fixed_size(value).to_vec()))
+            .collect();
+        let (storage_root, storage_nodes) = trie(&storage_leaves);
+        assert_eq!(storage_root, root::storage_root(storage));
+
+        let leaves: Vec<_> = (0..20u8)
+            .map(|i| {
+                let root = if i == 0 { storage_root } else { EMPTY_ROOT_HASH };
+                (keccak256([i]), leaf(account(i as u64), root))
+            })
+            .collect();
+        let (root, mut nodes) = trie(&leaves);
+        nodes.extend(storage_nodes);
+        let trie_nodes = WitnessTrie::new(&nodes);
+
+        // update an account, clear the storage slot, destroy an account and create an account
+        let mut state = HashedPostState::default();
+        state.accounts.insert(keccak256([0]), Some(account(100)));
+        state
+            .storages
+            .insert(keccak256([0]), HashedStorage::from_iter(false, [(slot, U256::ZERO)]));
+        state.accounts.insert(keccak256([1]), None);
+        state.accounts.insert(keccak256([50]), Some(account(50)));
+
+        let mut expected: Vec<_> = leaves[2..].to_vec();
+        expected.push((keccak256([0]), leaf(account(100), EMPTY_ROOT_HASH)));
+        expected.push((keccak256([50]), leaf(account(50), EMPTY_ROOT_HASH)));
+        let (expected_root, _) = trie(&expected);
+
+        assert_eq!(trie_nodes.state_root(root, &state).unwrap(), expected_root);
+    }
+}
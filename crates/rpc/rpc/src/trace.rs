@@ -19,11 +19,14 @@ use reth_consensus_common::calc::{
     base_block_reward, base_block_reward_pre_merge, block_reward, ommer_reward,
 };
 use reth_evm::ConfigureEvmEnv;
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_primitives::SealedBlockWithSenders;
+use reth_provider::{
+    BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory, TransactionVariant,
+};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_eth_api::{helpers::TraceExt, FromEthApiError};
-use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction};
+use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, TransactionSource};
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::{CacheDB, DatabaseCommit},
@@ -36,6 +39,14 @@ use revm_inspectors::{
 use std::sync::Arc;
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+use crate::{
+    trace_scheduler::{BlockTraceScheduler, BlockTraces, DEFAULT_MAX_CACHED_BLOCK_TRACES},
+    TraceIndex,
+};
+
+/// The maximum number of blocks `trace_filter` executes for a single request.
+const MAX_TRACE_FILTER_BLOCKS: u64 = 100;
+
 /// `trace` API implementation.
 ///
 /// This type provides the functionality for handling `trace` related requests.
@@ -53,7 +64,33 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
     /// Create a new instance of the [`TraceApi`]
     pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+        Self::new_with_trace_index(provider, eth_api, blocking_task_guard, None)
+    }
+
+    /// Create a new instance of the [`TraceApi`] that serves `trace_filter` requests with the
+    /// help of the given [`TraceIndex`].
+    pub fn with_trace_index(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_index: Arc<dyn TraceIndex>,
+    ) -> Self {
+        Self::new_with_trace_index(provider, eth_api, blocking_task_guard, Some(trace_index))
+    }
+
+    fn new_with_trace_index(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_index: Option<Arc<dyn TraceIndex>>,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            block_traces: BlockTraceScheduler::new(DEFAULT_MAX_CACHED_BLOCK_TRACES),
+            trace_index,
+        });
         Self { inner }
     }
 
@@ -244,13 +281,17 @@ where
     ///
     /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
     /// the filter.
+    ///
+    /// If a [`TraceIndex`] is configured and the filter restricts the addresses, only the blocks
+    /// that the index reports for these addresses, and the blocks above the last indexed block, are
+    /// executed, and the range is only limited by the number of such blocks.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
-        // We'll reuse the matcher across multiple blocks that are traced in parallel
-        let matcher = Arc::new(filter.matcher());
-        let TraceFilter { from_block, to_block, after, count, .. } = filter;
+        let matcher = filter.matcher();
+        let TraceFilter { from_block, to_block, from_address, to_address, after, count, .. } =
+            filter;
         let start = from_block.unwrap_or(0);
         let end = if let Some(to_block) = to_block {
             to_block
@@ -265,48 +306,93 @@ where
             .into())
         }
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > 100 {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            )
-            .into())
-        }
+        // the blocks up to the last indexed block that can contain matching traces, if the index
+        // covers the start of the range
+        let indexed = match &self.inner.trace_index {
+            Some(index) if !from_address.is_empty() || !to_address.is_empty() => {
+                match index.last_indexed_block().map_err(Eth::Error::from_eth_err)? {
+                    Some(last_indexed) if last_indexed >= start => {
+                        let indexed_end = last_indexed.min(end);
+                        let block_numbers = index
+                            .blocks_with_traces(&from_address, &to_address, start..=indexed_end)
+                            .map_err(Eth::Error::from_eth_err)?;
+                        Some((indexed_end, block_numbers))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
 
-        // fetch all blocks in that range
-        let blocks = self
-            .provider()
-            .sealed_block_with_senders_range(start..=end)
-            .map_err(Eth::Error::from_eth_err)?
-            .into_iter()
-            .map(Arc::new)
-            .collect::<Vec<_>>();
+        let blocks = if let Some((indexed_end, block_numbers)) = indexed {
+            // blocks above the last indexed block are executed regardless of the addresses
+            let unindexed = (indexed_end < end).then(|| indexed_end + 1..=end);
+            let unindexed_count =
+                unindexed.as_ref().map_or(0, |range| range.end() - range.start() + 1);
+
+            // ensure that we don't need to execute too many blocks
+            if (block_numbers.len() as u64).saturating_add(unindexed_count) >
+                MAX_TRACE_FILTER_BLOCKS
+            {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Too many matching blocks; currently limited to {MAX_TRACE_FILTER_BLOCKS} blocks"
+                ))
+                .into())
+            }
 
-        // trace all blocks
-        let mut block_traces = Vec::with_capacity(blocks.len());
-        for block in &blocks {
-            let matcher = matcher.clone();
-            let traces = self.eth_api().trace_block_until(
-                block.hash().into(),
-                Some(block.clone()),
-                None,
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, _, _, _| {
-                    let mut traces =
-                        inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
-                    traces.retain(|trace| matcher.matches(&trace.trace));
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
-        }
+            // fetch only the blocks that can contain matching traces
+            let mut blocks = Vec::with_capacity(block_numbers.len());
+            for number in block_numbers {
+                let block = self
+                    .provider()
+                    .sealed_block_with_senders(number.into(), TransactionVariant::WithHash)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                blocks.push(Arc::new(block));
+            }
+            if let Some(range) = unindexed {
+                blocks.extend(
+                    self.provider()
+                        .sealed_block_with_senders_range(range)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .into_iter()
+                        .map(Arc::new),
+                );
+            }
+            blocks
+        } else {
+            // ensure that the range is not too large, since we need to fetch all blocks in the
+            // range
+            let distance = end.saturating_sub(start);
+            if distance > MAX_TRACE_FILTER_BLOCKS {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Block range too large; currently limited to {MAX_TRACE_FILTER_BLOCKS} blocks"
+                ))
+                .into())
+            }
+
+            // fetch all blocks in that range
+            self.provider()
+                .sealed_block_with_senders_range(start..=end)
+                .map_err(Eth::Error::from_eth_err)?
+                .into_iter()
+                .map(Arc::new)
+                .collect::<Vec<_>>()
+        };
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
+        // trace all blocks
+        let block_traces = futures::future::try_join_all(
+            blocks
+                .iter()
+                .map(|block| self.localized_block_traces(block.hash().into(), block.clone())),
+        )
+        .await?;
         let mut all_traces = block_traces
-            .into_iter()
+            .iter()
             .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
+            .flat_map(|traces| traces.iter().flatten())
+            .filter(|trace| matcher.matches(&trace.trace))
+            .cloned()
             .collect::<Vec<_>>();
 
         // add reward traces for all blocks
@@ -351,10 +437,22 @@ where
     }
 
     /// Returns all traces for the given transaction hash
+    ///
+    /// The traces are taken from the traces of the entire block, so that requests for other
+    /// transactions of the same block don't execute it again.
     pub async fn trace_transaction(
         &self,
         hash: B256,
     ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        let Some((transaction, block)) = self.eth_api().transaction_and_block(hash).await? else {
+            return Ok(None)
+        };
+        if let TransactionSource::Block { index, .. } = transaction {
+            let traces = self.localized_block_traces(block.hash().into(), block).await?;
+            return Ok(traces.and_then(|traces| traces.get(index as usize).cloned()))
+        }
+
+        // pending transactions are traced on their own
         self.eth_api()
             .spawn_trace_transaction_in_block(
                 hash,
@@ -373,34 +471,51 @@ where
         &self,
         block_id: BlockId,
     ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
-        let traces = self.eth_api().trace_block_with(
-            block_id,
-            None,
-            TracingInspectorConfig::default_parity(),
-            |tx_info, inspector, _, _, _| {
-                let traces =
-                    inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
-                Ok(traces)
-            },
-        );
-
-        let block = self.eth_api().block_with_senders(block_id);
-        let (maybe_traces, maybe_block) = futures::try_join!(traces, block)?;
+        let Some(block) = self.eth_api().block_with_senders(block_id).await? else {
+            return Ok(None)
+        };
 
-        let mut maybe_traces =
-            maybe_traces.map(|traces| traces.into_iter().flatten().collect::<Vec<_>>());
+        let Some(block_traces) = self.localized_block_traces(block_id, block.clone()).await? else {
+            return Ok(None)
+        };
+        let mut traces = block_traces.iter().flatten().cloned().collect::<Vec<_>>();
 
-        if let (Some(block), Some(traces)) = (maybe_block, maybe_traces.as_mut()) {
-            if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
-                traces.extend(self.extract_reward_traces(
-                    &block.header,
-                    &block.body.ommers,
-                    base_block_reward,
-                ));
-            }
+        if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
+            traces.extend(self.extract_reward_traces(
+                &block.header,
+                &block.body.ommers,
+                base_block_reward,
+            ));
         }
 
-        Ok(maybe_traces)
+        Ok(Some(traces))
+    }
+
+    /// Returns the parity traces of all transactions in the given block, grouped by transaction.
+    ///
+    /// Concurrent and repeated requests for the same block share a single execution of the block,
+    /// see [`BlockTraceScheduler`].
+    async fn localized_block_traces(
+        &self,
+        block_id: BlockId,
+        block: Arc<SealedBlockWithSenders>,
+    ) -> Result<Option<BlockTraces>, Eth::Error> {
+        self.inner
+            .block_traces
+            .block_traces(block.hash(), || {
+                self.eth_api().trace_block_until(
+                    block_id,
+                    Some(block.clone()),
+                    None,
+                    TracingInspectorConfig::default_parity(),
+                    |tx_info, inspector, _, _, _| {
+                        Ok(inspector
+                            .into_parity_builder()
+                            .into_localized_transaction_traces(tx_info))
+                    },
+                )
+            })
+            .await
     }
 
     /// Replays all transactions in a block
@@ -709,6 +824,10 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Shares block executions between `trace_*` requests for the same block.
+    block_traces: BlockTraceScheduler,
+    /// Optional index used to narrow down the blocks `trace_filter` needs to execute.
+    trace_index: Option<Arc<dyn TraceIndex>>,
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
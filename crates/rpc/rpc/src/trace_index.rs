@@ -0,0 +1,401 @@
+//! Index of the blocks that contain traces of an address, used to serve `trace_filter`.
+
+use crate::{
+    block_index::{append_blocks, key_blocks, last_indexed_block, push_block, BlockIndexer},
+    TraceApi,
+};
+use alloy_primitives::{Address, BlockNumber};
+use alloy_rpc_types_trace::parity::{
+    Action, CallAction, CreateAction, CreateOutput, RewardAction, SelfdestructAction, TraceOutput,
+    TransactionTrace,
+};
+use reth_chainspec::EthereumHardforks;
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, DBProvider, DatabaseProviderFactory,
+    EvmEnvProvider, StateProviderFactory,
+};
+use reth_rpc_eth_api::helpers::TraceExt;
+use reth_stages_types::StageId;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::RangeInclusive,
+};
+
+/// The id under which the last block of the trace index is stored.
+pub const TRACE_INDEX_ID: StageId = StageId::Other("TraceIndex");
+
+/// An index from addresses to the blocks in which they appear in a trace.
+///
+/// When configured, `trace_filter` uses the index to only execute the blocks of the requested range
+/// that can contain matching traces, which makes queries over large block ranges feasible.
+///
+/// [`DatabaseTraceIndex`] implements the index on top of tables that are maintained by a
+/// [`BlockIndexTask`](crate::BlockIndexTask) running the [`TraceIndexer`], see
+/// [`trace_addresses`] for which addresses of a trace are indexed.
+pub trait TraceIndex: fmt::Debug + Send + Sync + 'static {
+    /// Returns the number of the highest block covered by the index, or `None` if no block is
+    /// covered.
+    ///
+    /// Blocks above it are searched without the index.
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the numbers of all blocks in the range, in ascending order, that contain a trace
+    /// _from_ one of `from_addresses` or _to_ one of `to_addresses`.
+    ///
+    /// The range must be covered by the index.
+    fn blocks_with_traces(
+        &self,
+        from_addresses: &[Address],
+        to_addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// A [`TraceIndex`] backed by the [`tables::TraceFromIndex`] and [`tables::TraceToIndex`] tables.
+#[derive(Clone)]
+pub struct DatabaseTraceIndex<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> DatabaseTraceIndex<Provider> {
+    /// Creates a new index that reads the trace index tables of the given database.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> TraceIndex for DatabaseTraceIndex<Provider>
+where
+    Provider: DatabaseProviderFactory + BlockNumReader + 'static,
+{
+    fn last_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        last_indexed_block(&self.provider, TRACE_INDEX_ID)
+    }
+
+    fn blocks_with_traces(
+        &self,
+        from_addresses: &[Address],
+        to_addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let provider = self.provider.database_provider_ro()?;
+        let tx = provider.tx_ref();
+
+        let mut blocks = BTreeSet::new();
+        for address in from_addresses {
+            blocks.extend(key_blocks::<tables::TraceFromIndex, _>(tx, *address, &range)?);
+        }
+        for address in to_addresses {
+            blocks.extend(key_blocks::<tables::TraceToIndex, _>(tx, *address, &range)?);
+        }
+
+        Ok(blocks.into_iter().collect())
+    }
+}
+
+impl<Provider> fmt::Debug for DatabaseTraceIndex<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseTraceIndex").finish_non_exhaustive()
+    }
+}
+
+/// The blocks of every trace address of a range of blocks.
+#[derive(Debug, Default)]
+pub struct TraceIndexEntries {
+    from: BTreeMap<Address, Vec<BlockNumber>>,
+    to: BTreeMap<Address, Vec<BlockNumber>>,
+}
+
+/// Error of the [`TraceIndexer`].
+#[derive(Debug, thiserror::Error)]
+pub enum TraceIndexError<E> {
+    /// Reading the index or a block failed.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Tracing a block failed.
+    #[error(transparent)]
+    Trace(E),
+}
+
+/// A [`BlockIndexer`] that indexes the addresses of the parity traces of a block, including its
+/// reward traces, for the [`DatabaseTraceIndex`].
+///
+/// Every block is traced, so the historical state of all indexed blocks must be available.
+#[derive(Clone)]
+pub struct TraceIndexer<Provider, Eth> {
+    trace_api: TraceApi<Provider, Eth>,
+}
+
+impl<Provider, Eth> TraceIndexer<Provider, Eth> {
+    /// Creates a new indexer that traces blocks with the given API.
+    ///
+    /// The API should not be shared with the RPC server, so indexed blocks don't evict the
+    /// cached block traces of requests.
+    pub const fn new(trace_api: TraceApi<Provider, Eth>) -> Self {
+        Self { trace_api }
+    }
+}
+
+impl<Provider, Eth> BlockIndexer for TraceIndexer<Provider, Eth>
+where
+    Provider: BlockReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + 'static,
+    Eth: TraceExt + 'static,
+{
+    type Entries = TraceIndexEntries;
+    type Error = TraceIndexError<Eth::Error>;
+
+    const ID: StageId = TRACE_INDEX_ID;
+    const BATCH_SIZE: u64 = 100;
+
+    async fn collect(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<TraceIndexEntries, Self::Error> {
+        let mut entries = TraceIndexEntries::default();
+        for number in range {
+            let Some(traces) =
+                self.trace_api.trace_block(number.into()).await.map_err(TraceIndexError::Trace)?
+            else {
+                continue
+            };
+            for trace in &traces {
+                let (from, to) = trace_addresses(&trace.trace);
+                if let Some(from) = from {
+                    push_block(entries.from.entry(from).or_default(), number);
+                }
+                if let Some(to) = to {
+                    push_block(entries.to.entry(to).or_default(), number);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write<TX: DbTxMut + DbTx>(&self, tx: &TX, entries: TraceIndexEntries) -> ProviderResult<()> {
+        for (address, blocks) in entries.from {
+            append_blocks::<tables::TraceFromIndex, _>(tx, address, &blocks)?;
+        }
+        for (address, blocks) in entries.to {
+            append_blocks::<tables::TraceToIndex, _>(tx, address, &blocks)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Provider, Eth> fmt::Debug for TraceIndexer<Provider, Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceIndexer").finish_non_exhaustive()
+    }
+}
+
+/// Returns the addresses a trace is indexed by, as `(from, to)`.
+///
+/// These are the addresses that `trace_filter` matches the `fromAddress` and `toAddress` filters
+/// against:
+/// - calls: the caller and the callee
+/// - creates: the creator and the created contract, if the creation succeeded
+/// - selfdestructs: the destructed contract and the refund address
+/// - rewards: the block author, which has no _from_ address
+pub const fn trace_addresses(trace: &TransactionTrace) -> (Option<Address>, Option<Address>) {
+    match trace.action {
+        Action::Call(CallAction { from, to, .. }) => (Some(from), Some(to)),
+        Action::Create(CreateAction { from, .. }) => {
+            let to = match trace.result {
+                Some(TraceOutput::Create(CreateOutput { address, .. })) => Some(address),
+                _ => None,
+            };
+            (Some(from), to)
+        }
+        Action::Selfdestruct(SelfdestructAction { address, refund_address, .. }) => {
+            (Some(address), Some(refund_address))
+        }
+        Action::Reward(RewardAction { author, .. }) => (None, Some(author)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockIndexTask, EthApi};
+    use alloy_consensus::{constants::EMPTY_OMMER_ROOT_HASH, Header};
+    use alloy_primitives::{Bytes, B256, U256};
+    use alloy_rpc_types_trace::{
+        filter::{TraceFilter, TraceFilterMode},
+        parity::{CallType, LocalizedTransactionTrace, RewardType},
+    };
+    use reth_chainspec::EthChainSpec;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{SealedBlock, SealedHeader, StaticFileSegment};
+    use reth_provider::{
+        providers::BlockchainProvider2, test_utils::create_test_provider_factory,
+        StageCheckpointWriter, StaticFileProviderFactory, StaticFileWriter,
+    };
+    use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_stages_types::StageCheckpoint;
+    use reth_tasks::pool::{BlockingTaskGuard, BlockingTaskPool};
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::sync::Arc;
+
+    /// A trace must only match a filter if one of its indexed addresses is part of the filter,
+    /// otherwise `trace_filter` would skip blocks that contain matching traces.
+    #[test]
+    fn indexed_addresses_cover_matching_traces() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let c = Address::with_last_byte(3);
+
+        let traces = [
+            TransactionTrace {
+                action: Action::Call(CallAction {
+                    from: a,
+                    to: b,
+                    call_type: CallType::Call,
+                    gas: 0,
+                    input: Bytes::new(),
+                    value: U256::ZERO,
+                }),
+                ..Default::default()
+            },
+            TransactionTrace {
+                action: Action::Selfdestruct(SelfdestructAction {
+                    address: b,
+                    refund_address: c,
+                    balance: U256::ZERO,
+                }),
+                ..Default::default()
+            },
+            TransactionTrace {
+                action: Action::Reward(RewardAction {
+                    author: c,
+                    reward_type: RewardType::Block,
+                    value: U256::ZERO,
+                }),
+                ..Default::default()
+            },
+        ];
+
+        for mode in [TraceFilterMode::Union, TraceFilterMode::Intersection] {
+            for (from_address, to_address) in
+                [(vec![a], vec![]), (vec![], vec![c]), (vec![a], vec![c]), (vec![b], vec![b])]
+            {
+                let filter = TraceFilter {
+                    from_address: from_address.clone(),
+                    to_address: to_address.clone(),
+                    mode,
+                    ..Default::default()
+                };
+                let matcher = filter.matcher();
+                for trace in &traces {
+                    if !matcher.matches(trace) {
+                        continue
+                    }
+                    let (from, to) = trace_addresses(trace);
+                    assert!(
+                        from.is_some_and(|from| from_address.contains(&from)) ||
+                            to.is_some_and(|to| to_address.contains(&to)),
+                        "{trace:?} matches {filter:?} but is not indexed by its addresses"
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn index_traces_and_filter_with_index() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        // insert empty pre-merge blocks, so their only traces are the block rewards of the author
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut parent_hash = B256::ZERO;
+        for number in 0..150 {
+            let header = Header {
+                number,
+                parent_hash,
+                beneficiary: if number == 3 || number == 120 { a } else { b },
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                ..Default::default()
+            };
+            let block =
+                SealedBlock { header: SealedHeader::seal(header), body: Default::default() };
+            parent_hash = block.hash();
+            provider_rw.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        }
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(149)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = BlockchainProvider2::new(factory).unwrap();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+            provider.chain_spec().max_gas_limit(),
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().unwrap(),
+            FeeHistoryCache::new(cache, Default::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+        let trace_api = |trace_index: Option<Arc<dyn TraceIndex>>| {
+            let guard = BlockingTaskGuard::new(1);
+            match trace_index {
+                Some(index) => {
+                    TraceApi::with_trace_index(provider.clone(), eth_api.clone(), guard, index)
+                }
+                None => TraceApi::new(provider.clone(), eth_api.clone(), guard),
+            }
+        };
+
+        let task = BlockIndexTask::new(provider.clone(), TraceIndexer::new(trace_api(None)));
+        let index = DatabaseTraceIndex::new(provider.clone());
+        task.sync().await.unwrap();
+        assert_eq!(index.last_indexed_block().unwrap(), Some(149));
+        assert_eq!(index.blocks_with_traces(&[], &[a], 0..=149).unwrap(), vec![3, 120]);
+        assert_eq!(index.blocks_with_traces(&[], &[a], 4..=149).unwrap(), vec![120]);
+        assert!(index.blocks_with_traces(&[a, b], &[], 0..=149).unwrap().is_empty());
+
+        // without the index the range is too large to be executed
+        let filter = TraceFilter {
+            from_block: Some(0),
+            to_block: Some(149),
+            to_address: vec![a],
+            ..Default::default()
+        };
+        assert!(trace_api(None).trace_filter(filter.clone()).await.is_err());
+
+        let traced_blocks = |traces: Vec<LocalizedTransactionTrace>| {
+            traces.into_iter().map(|trace| trace.block_number.unwrap()).collect::<Vec<_>>()
+        };
+        let indexed_api = trace_api(Some(Arc::new(index)));
+        let traces = indexed_api.trace_filter(filter.clone()).await.unwrap();
+        assert_eq!(traced_blocks(traces), vec![3, 120]);
+
+        // blocks above the last indexed block are executed without the index
+        task.unwind_to(100).unwrap();
+        let traces = indexed_api.trace_filter(filter).await.unwrap();
+        assert_eq!(traced_blocks(traces), vec![3, 120]);
+    }
+}
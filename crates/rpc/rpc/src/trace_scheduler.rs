@@ -0,0 +1,166 @@
+//! Shares block executions between concurrent tracing requests.
+
+use alloy_primitives::{map::HashMap, B256};
+use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use futures::{
+    channel::oneshot,
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+use std::{future::Future, sync::Arc};
+
+/// Parity traces of all transactions in a block, in block order.
+pub(crate) type BlockTraces = Arc<Vec<Vec<LocalizedTransactionTrace>>>;
+
+/// The default number of blocks for which the traces are kept after the execution completed.
+pub(crate) const DEFAULT_MAX_CACHED_BLOCK_TRACES: u32 = 32;
+
+/// A pending block execution that other requests for the same block can wait on.
+///
+/// Resolves to `None` if the execution failed or was cancelled.
+type PendingBlockTraces = Shared<BoxFuture<'static, Option<BlockTraces>>>;
+
+/// Schedules block executions for `trace_*` requests.
+///
+/// Indexers commonly request the traces of the same block several times, e.g. via `trace_block`
+/// followed by `trace_transaction` for each of its transactions. The scheduler executes the block
+/// once for all of them: requests for a block that is currently being traced wait for that
+/// execution, and the traces of recently executed blocks are cached by block hash.
+#[derive(Clone)]
+pub(crate) struct BlockTraceScheduler {
+    inner: Arc<Mutex<SchedulerState>>,
+}
+
+impl BlockTraceScheduler {
+    /// Creates a new scheduler that caches the traces of up to `max_cached_blocks` blocks.
+    pub(crate) fn new(max_cached_blocks: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SchedulerState {
+                pending: HashMap::default(),
+                cached: LruMap::new(ByLength::new(max_cached_blocks)),
+            })),
+        }
+    }
+
+    /// Returns the traces of the block with the given hash.
+    ///
+    /// The block is only traced with `trace_block` if its traces are neither cached nor already
+    /// being computed for another request. If the execution of another request fails, the block is
+    /// traced again so that the error is reported to every request.
+    pub(crate) async fn block_traces<F, Fut, E>(
+        &self,
+        block_hash: B256,
+        trace_block: F,
+    ) -> Result<Option<BlockTraces>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<Vec<Vec<LocalizedTransactionTrace>>>, E>>,
+    {
+        let pending = {
+            let mut state = self.inner.lock();
+            if let Some(traces) = state.cached.get(&block_hash) {
+                return Ok(Some(traces.clone()))
+            }
+            state.pending.get(&block_hash).cloned()
+        };
+
+        if let Some(pending) = pending {
+            if let Some(traces) = pending.await {
+                return Ok(Some(traces))
+            }
+            return Ok(trace_block().await?.map(Arc::new))
+        }
+
+        let (tx, rx) = oneshot::channel();
+        self.inner
+            .lock()
+            .pending
+            .insert(block_hash, rx.map(|res| res.ok().flatten()).boxed().shared());
+        let _guard = PendingGuard { scheduler: self, block_hash };
+
+        let res = trace_block().await.map(|traces| traces.map(Arc::new));
+        let traces = res.as_ref().ok().cloned().flatten();
+        if let Some(traces) = &traces {
+            self.inner.lock().cached.insert(block_hash, traces.clone());
+        }
+        let _ = tx.send(traces);
+
+        res
+    }
+}
+
+struct SchedulerState {
+    /// Blocks that are currently being traced.
+    pending: HashMap<B256, PendingBlockTraces>,
+    /// Traces of recently traced blocks.
+    cached: LruMap<B256, BlockTraces, ByLength>,
+}
+
+/// Removes the pending execution of a block once the request tracing it completes or is dropped.
+struct PendingGuard<'a> {
+    scheduler: &'a BlockTraceScheduler,
+    block_hash: B256,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.scheduler.inner.lock().pending.remove(&self.block_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn traces() -> Vec<Vec<LocalizedTransactionTrace>> {
+        vec![Vec::new(), Vec::new()]
+    }
+
+    #[tokio::test]
+    async fn executes_block_once() {
+        let scheduler = BlockTraceScheduler::new(DEFAULT_MAX_CACHED_BLOCK_TRACES);
+        let executions = AtomicUsize::new(0);
+        let trace_block = || async {
+            executions.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok::<_, ()>(Some(traces()))
+        };
+
+        let (first, second) = futures::join!(
+            scheduler.block_traces(B256::ZERO, trace_block),
+            scheduler.block_traces(B256::ZERO, trace_block)
+        );
+        assert_eq!(first.unwrap().unwrap().len(), 2);
+        assert_eq!(second.unwrap().unwrap().len(), 2);
+
+        let cached = scheduler.block_traces(B256::ZERO, trace_block).await;
+        assert_eq!(cached.unwrap().unwrap().len(), 2);
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+
+        scheduler.block_traces(B256::with_last_byte(1), trace_block).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_failed_execution() {
+        let scheduler = BlockTraceScheduler::new(DEFAULT_MAX_CACHED_BLOCK_TRACES);
+        let executions = AtomicUsize::new(0);
+        let trace_block = || async {
+            executions.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Err::<Option<Vec<Vec<LocalizedTransactionTrace>>>, _>("execution failed")
+        };
+
+        let (first, second) = futures::join!(
+            scheduler.block_traces(B256::ZERO, trace_block),
+            scheduler.block_traces(B256::ZERO, trace_block)
+        );
+        assert_eq!(first, Err("execution failed"));
+        assert_eq!(second, Err("execution failed"));
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+        assert!(scheduler.inner.lock().pending.is_empty());
+    }
+}
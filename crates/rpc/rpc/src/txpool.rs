@@ -1,7 +1,6 @@
 use core::fmt;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
-use alloy_consensus::Transaction;
 use alloy_primitives::Address;
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
@@ -11,7 +10,9 @@ use jsonrpsee::core::RpcResult;
 use reth_primitives::TransactionSignedEcRecovered;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types_compat::{transaction::from_recovered, TransactionCompat};
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    AllPoolTransactions, PoolTransaction, TransactionPool, ValidPoolTransaction,
+};
 use tracing::trace;
 
 /// `txpool` API implementation.
@@ -37,24 +38,6 @@ where
     Eth: TransactionCompat,
 {
     fn content(&self) -> Result<TxpoolContent<Eth::Transaction>, Eth::Error> {
-        #[inline]
-        fn insert<Tx, RpcTxB>(
-            tx: &Tx,
-            content: &mut BTreeMap<Address, BTreeMap<String, RpcTxB::Transaction>>,
-            resp_builder: &RpcTxB,
-        ) -> Result<(), RpcTxB::Error>
-        where
-            Tx: PoolTransaction<Consensus: Into<TransactionSignedEcRecovered>>,
-            RpcTxB: TransactionCompat,
-        {
-            content.entry(tx.sender()).or_default().insert(
-                tx.nonce().to_string(),
-                from_recovered(tx.clone().into_consensus().into(), resp_builder)?,
-            );
-
-            Ok(())
-        }
-
         let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
 
         let mut content = TxpoolContent { pending: BTreeMap::new(), queued: BTreeMap::new() };
@@ -67,6 +50,64 @@ where
 
         Ok(content)
     }
+
+    fn content_from(
+        &self,
+        from: Address,
+    ) -> Result<TxpoolContentFrom<Eth::Transaction>, Eth::Error> {
+        #[inline]
+        fn collect<Tx, RpcTxB>(
+            txs: Vec<Arc<ValidPoolTransaction<Tx>>>,
+            resp_builder: &RpcTxB,
+        ) -> Result<BTreeMap<String, RpcTxB::Transaction>, RpcTxB::Error>
+        where
+            Tx: PoolTransaction<Consensus: Into<TransactionSignedEcRecovered>>,
+            RpcTxB: TransactionCompat,
+        {
+            txs.into_iter()
+                .map(|tx| {
+                    let nonce = tx.nonce().to_string();
+                    Ok((
+                        nonce,
+                        from_recovered(
+                            tx.transaction.clone().into_consensus().into(),
+                            resp_builder,
+                        )?,
+                    ))
+                })
+                .collect()
+        }
+
+        Ok(TxpoolContentFrom {
+            pending: collect(
+                self.pool.get_pending_transactions_by_sender(from),
+                &self.tx_resp_builder,
+            )?,
+            queued: collect(
+                self.pool.get_queued_transactions_by_sender(from),
+                &self.tx_resp_builder,
+            )?,
+        })
+    }
+}
+
+/// Converts the transaction and inserts it into the content of its sender, keyed by nonce.
+#[inline]
+fn insert<Tx, RpcTxB>(
+    tx: &Tx,
+    content: &mut BTreeMap<Address, BTreeMap<String, RpcTxB::Transaction>>,
+    resp_builder: &RpcTxB,
+) -> Result<(), RpcTxB::Error>
+where
+    Tx: PoolTransaction<Consensus: Into<TransactionSignedEcRecovered>>,
+    RpcTxB: TransactionCompat,
+{
+    content.entry(tx.sender()).or_default().insert(
+        tx.nonce().to_string(),
+        from_recovered(tx.clone().into_consensus().into(), resp_builder)?,
+    );
+
+    Ok(())
 }
 
 #[async_trait]
@@ -82,8 +123,11 @@ where
     /// Handler for `txpool_status`
     async fn txpool_status(&self) -> RpcResult<TxpoolStatus> {
         trace!(target: "rpc::eth", "Serving txpool_status");
-        let all = self.pool.all_transactions();
-        Ok(TxpoolStatus { pending: all.pending.len() as u64, queued: all.queued.len() as u64 })
+        let size = self.pool.pool_size();
+        Ok(TxpoolStatus {
+            pending: size.pending as u64,
+            queued: (size.basefee + size.queued + size.blob) as u64,
+        })
     }
 
     /// Returns a summary of all the transactions currently pending for inclusion in the next
@@ -96,19 +140,17 @@ where
         trace!(target: "rpc::eth", "Serving txpool_inspect");
 
         #[inline]
-        fn insert<T: PoolTransaction<Consensus: Into<TransactionSignedEcRecovered>>>(
+        fn insert<T: PoolTransaction>(
             tx: &T,
             inspect: &mut BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
         ) {
-            let entry = inspect.entry(tx.sender()).or_default();
-            let tx: TransactionSignedEcRecovered = tx.clone().into_consensus().into();
-            entry.insert(
+            inspect.entry(tx.sender()).or_default().insert(
                 tx.nonce().to_string(),
                 TxpoolInspectSummary {
                     to: tx.to(),
                     value: tx.value(),
                     gas: tx.gas_limit() as u128,
-                    gas_price: tx.transaction.max_fee_per_gas(),
+                    gas_price: tx.max_fee_per_gas(),
                 },
             );
         }
@@ -137,7 +179,7 @@ where
         from: Address,
     ) -> RpcResult<TxpoolContentFrom<Eth::Transaction>> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().map_err(Into::into)?.remove_from(&from))
+        Ok(self.content_from(from).map_err(Into::into)?)
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -15,6 +15,8 @@ pub struct PipelineBuilder<Provider> {
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
     fail_on_unwind: bool,
+    /// The interval in blocks at which the state root is verified.
+    state_root_check_interval: Option<u64>,
 }
 
 impl<Provider> PipelineBuilder<Provider> {
@@ -69,6 +71,15 @@ impl<Provider> PipelineBuilder<Provider> {
         self
     }
 
+    /// Set the interval in blocks at which the state root is verified.
+    ///
+    /// The execution stage only progresses to the next multiple of the interval in each pass of
+    /// the pipeline, so the merkle stage verifies the state root at every multiple.
+    pub const fn with_state_root_check_interval(mut self, interval: Option<u64>) -> Self {
+        self.state_root_check_interval = interval;
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build<N>(
         self,
@@ -79,7 +90,14 @@ impl<Provider> PipelineBuilder<Provider> {
         N: ProviderNodeTypes,
         ProviderFactory<N>: DatabaseProviderFactory<ProviderRW = Provider>,
     {
-        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind } = self;
+        let Self {
+            stages,
+            max_block,
+            tip_tx,
+            metrics_tx,
+            fail_on_unwind,
+            state_root_check_interval,
+        } = self;
         Pipeline {
             provider_factory,
             stages,
@@ -90,6 +108,7 @@ impl<Provider> PipelineBuilder<Provider> {
             progress: Default::default(),
             metrics_tx,
             fail_on_unwind,
+            state_root_check_interval: state_root_check_interval.filter(|interval| *interval > 0),
         }
     }
 }
@@ -102,6 +121,7 @@ impl<Provider> Default for PipelineBuilder<Provider> {
             tip_tx: None,
             metrics_tx: None,
             fail_on_unwind: false,
+            state_root_check_interval: None,
         }
     }
 }
@@ -112,6 +132,7 @@ impl<Provider> std::fmt::Debug for PipelineBuilder<Provider> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("fail_on_unwind", &self.fail_on_unwind)
+            .field("state_root_check_interval", &self.state_root_check_interval)
             .finish()
     }
 }
@@ -81,6 +81,9 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Whether an unwind should fail the syncing process. Should only be set when downloading
     /// blocks from trusted sources and expecting them to be valid.
     fail_on_unwind: bool,
+    /// The interval in blocks at which the state root is verified, see
+    /// [`PipelineBuilder::with_state_root_check_interval`].
+    state_root_check_interval: Option<u64>,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -205,36 +208,50 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
         self.move_to_static_files()?;
 
-        let mut previous_stage = None;
-        for stage_index in 0..self.stages.len() {
-            let stage = &self.stages[stage_index];
-            let stage_id = stage.id();
-
-            trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
-
-            trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
-
-            match next {
-                ControlFlow::NoProgress { block_number } => {
-                    if let Some(block_number) = block_number {
-                        self.progress.update(block_number);
+        loop {
+            // whether the execution stage stopped short of its target at a state root check
+            // interval, in which case another pass is required
+            let mut execution_interrupted = false;
+            let mut previous_stage = None;
+            for stage_index in 0..self.stages.len() {
+                let stage = &self.stages[stage_index];
+                let stage_id = stage.id();
+
+                trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
+                let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+
+                trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+
+                match next {
+                    ControlFlow::NoProgress { block_number } => {
+                        if let Some(block_number) = block_number {
+                            self.progress.update(block_number);
+                        }
+                    }
+                    ControlFlow::Continue { block_number } => self.progress.update(block_number),
+                    ControlFlow::Unwind { target, bad_block } => {
+                        self.unwind(target, Some(bad_block.number))?;
+                        return Ok(ControlFlow::Unwind { target, bad_block })
                     }
                 }
-                ControlFlow::Continue { block_number } => self.progress.update(block_number),
-                ControlFlow::Unwind { target, bad_block } => {
-                    self.unwind(target, Some(bad_block.number))?;
-                    return Ok(ControlFlow::Unwind { target, bad_block })
-                }
-            }
 
-            previous_stage = Some(
-                self.provider_factory
+                let checkpoint = self
+                    .provider_factory
                     .provider()?
                     .get_stage_checkpoint(stage_id)?
                     .unwrap_or_default()
-                    .block_number,
-            );
+                    .block_number;
+                if stage_id == StageId::Execution && self.state_root_check_interval.is_some() {
+                    execution_interrupted =
+                        previous_stage.or(self.max_block).is_some_and(|target| checkpoint < target);
+                }
+                previous_stage = Some(checkpoint);
+            }
+
+            if !execution_interrupted {
+                break
+            }
+            debug!(target: "sync::pipeline", checkpoint = ?previous_stage, "Verified state root at check interval");
         }
 
         Ok(self.progress.next_ctrl())
@@ -386,7 +403,27 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let stage = &mut self.stages[stage_index];
         let stage_id = stage.id();
         let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
+        let mut target = self.max_block.or(previous_stage);
+
+        if let Some(interval) = self.state_root_check_interval {
+            // stages must not pass the previous stage, which may have stopped at a check
+            target = match (target, previous_stage) {
+                (Some(target), Some(previous_stage)) => Some(target.min(previous_stage)),
+                (target, previous_stage) => target.or(previous_stage),
+            };
+
+            // only execute up to the next state root check, the following stages will then verify
+            // the state root at that block
+            if stage_id == StageId::Execution {
+                let checkpoint = self
+                    .provider_factory
+                    .get_stage_checkpoint(stage_id)?
+                    .unwrap_or_default()
+                    .block_number;
+                let next_check = (checkpoint / interval + 1).saturating_mul(interval);
+                target = target.map(|target| target.min(next_check));
+            }
+        }
 
         loop {
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
@@ -588,6 +625,7 @@ impl<N: ProviderNodeTypes> std::fmt::Debug for Pipeline<N> {
             .field("max_block", &self.max_block)
             .field("event_sender", &self.event_sender)
             .field("fail_on_unwind", &self.fail_on_unwind)
+            .field("state_root_check_interval", &self.state_root_check_interval)
             .finish()
     }
 }
@@ -711,6 +749,61 @@ mod tests {
         assert_eq!(post_unwind_commit_counter_b.load(Ordering::Relaxed), 0);
     }
 
+    /// Runs a pipeline that verifies the state root every 10 blocks.
+    #[tokio::test]
+    async fn run_pipeline_with_state_root_check_interval() {
+        let provider_factory = create_test_provider_factory();
+
+        let stage_a = TestStage::new(StageId::Other("A"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(25), done: true }));
+        let execution = TestStage::new(StageId::Execution)
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(25), done: true }));
+        let stage_b = TestStage::new(StageId::Other("B"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(25), done: true }));
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(stage_a)
+            .add_stage(execution)
+            .add_stage(stage_b)
+            .with_max_block(25)
+            .with_state_root_check_interval(Some(10))
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+
+        // Run pipeline
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        // Check that execution stopped at every interval and the next stage followed it
+        let runs = events
+            .filter_map(|event| match event {
+                PipelineEvent::Run { stage_id, target, .. } => Some((stage_id, target)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            runs,
+            vec![
+                (StageId::Other("A"), Some(25)),
+                (StageId::Execution, Some(10)),
+                (StageId::Other("B"), Some(10)),
+                (StageId::Execution, Some(20)),
+                (StageId::Other("B"), Some(20)),
+                (StageId::Execution, Some(25)),
+                (StageId::Other("B"), Some(25)),
+            ]
+        );
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
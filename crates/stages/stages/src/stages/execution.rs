@@ -17,8 +17,8 @@ use reth_provider::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
     writer::UnifiedStorageWriter,
     BlockHashReader, BlockReader, DBProvider, HeaderProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, StateChangeWriter, StateWriter, StaticFileProviderFactory,
-    StatsReader, TransactionVariant,
+    OriginalValuesKnown, ProviderError, StageCheckpointReader, StateChangeWriter, StateWriter,
+    StaticFileProviderFactory, StatsReader, TransactionVariant,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
@@ -152,9 +152,12 @@ impl<E> ExecutionStage<E> {
     /// Given that `start_block` changes with each checkpoint, it's necessary to inspect
     /// [`tables::AccountsTrie`] to ensure that [`super::MerkleStage`] hasn't
     /// been previously executed.
+    ///
+    /// The Hashing stages never run from scratch on a snap synced state, so its changesets are
+    /// never pruned.
     fn adjust_prune_modes(
         &self,
-        provider: impl StatsReader,
+        provider: impl StatsReader + StageCheckpointReader,
         start_block: u64,
         max_block: u64,
     ) -> Result<PruneModes, StageError> {
@@ -163,7 +166,8 @@ impl<E> ExecutionStage<E> {
         // If we're not executing MerkleStage from scratch (by threshold or first-sync), then erase
         // changeset related pruning configurations
         if !(max_block - start_block > self.external_clean_threshold ||
-            provider.count_entries::<tables::AccountsTrie>()?.is_zero())
+            provider.count_entries::<tables::AccountsTrie>()?.is_zero()) ||
+            provider.snap_sync_pivot()?.is_some()
         {
             prune_modes.account_history = None;
             prune_modes.storage_history = None;
@@ -180,6 +184,7 @@ where
         + StaticFileProviderFactory
         + StatsReader
         + StateChangeWriter
+        + StageCheckpointReader
         + BlockHashReader,
     for<'a> UnifiedStorageWriter<'a, Provider, StaticFileProviderRWRefMut<'a, Provider::Primitives>>:
         StateWriter,
@@ -225,7 +230,9 @@ where
             None
         };
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
+        let db = StateProviderDatabase(
+            LatestStateProviderRef::new(provider).with_snap_sync_pivot(provider.snap_sync_pivot()?),
+        );
         let mut executor = self.executor_provider.batch_executor(db);
         executor.set_tip(max_block);
         executor.set_prune_modes(prune_modes);
@@ -361,9 +368,18 @@ where
 
         let time = Instant::now();
 
+        // The accounts and storage slots of a snap synced state that didn't change after the pivot
+        // are only in the hashed state, so it has to be updated along with the plain state to
+        // not go stale.
+        let hashed_state =
+            provider.snap_sync_pivot()?.is_some().then(|| state.hash_state_slow().into_sorted());
+
         // write output
         let mut writer = UnifiedStorageWriter::new(provider, static_file_producer);
         writer.write_to_storage(state, OriginalValuesKnown::Yes)?;
+        if let Some(hashed_state) = hashed_state {
+            provider.write_hashed_state(&hashed_state)?;
+        }
 
         let db_write_duration = time.elapsed();
         debug!(
@@ -404,6 +420,17 @@ where
     ) -> Result<UnwindOutput, StageError> {
         let (range, unwind_to, _) =
             input.unwind_block_range_with_threshold(self.thresholds.max_blocks.unwrap_or(u64::MAX));
+
+        // There are no changesets before the pivot of a snap synced state.
+        if let Some(pivot) = provider.snap_sync_pivot()? {
+            if unwind_to < pivot {
+                return Err(StageError::Fatal(
+                    format!("can't unwind the snap synced state below the pivot block {pivot}")
+                        .into(),
+                ))
+            }
+        }
+
         if range.is_empty() {
             return Ok(UnwindOutput {
                 checkpoint: input.checkpoint.with_block_number(input.unwind_to),
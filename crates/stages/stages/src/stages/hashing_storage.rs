@@ -10,7 +10,7 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_primitives::StorageEntry;
-use reth_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
+use reth_provider::{DBProvider, HashingWriter, StageCheckpointReader, StatsReader, StorageReader};
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
     StorageHashingCheckpoint, UnwindInput, UnwindOutput,
@@ -64,7 +64,11 @@ impl Default for StorageHashingStage {
 
 impl<Provider> Stage<Provider> for StorageHashingStage
 where
-    Provider: DBProvider<Tx: DbTxMut> + StorageReader + HashingWriter + StatsReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + StorageReader
+        + HashingWriter
+        + StatsReader
+        + StageCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -83,8 +87,12 @@ where
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
-        // genesis accounts are not in changeset, along with their storages.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        // genesis accounts are not in changeset, along with their storages. A snap synced state
+        // can't be hashed from scratch, as the plain state only holds the storage slots that
+        // changed after the pivot.
+        if (to_block - from_block > self.clean_threshold || from_block == 1) &&
+            provider.snap_sync_pivot()?.is_none()
+        {
             // clear table, load all accounts and hash it
             tx.clear::<tables::HashedStorages>()?;
 
@@ -14,7 +14,7 @@ use reth_stages_api::{
 };
 use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
 use reth_trie_db::DatabaseStateRoot;
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::*;
 
 // TODO: automate the process outlined below so the user can just send in a debugging package
@@ -276,7 +276,7 @@ where
         // Reset the checkpoint
         self.save_execution_checkpoint(provider, None)?;
 
-        validate_state_root(trie_root, SealedHeader::seal(target_block), to_block)?;
+        validate_state_root(trie_root, SealedHeader::seal(target_block), from_block..=to_block)?;
 
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(to_block)
@@ -321,7 +321,7 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
+            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range.clone())
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -329,7 +329,7 @@ where
                 .header_by_number(input.unwind_to)?
                 .ok_or_else(|| ProviderError::HeaderNotFound(input.unwind_to.into()))?;
 
-            validate_state_root(block_root, SealedHeader::seal(target), input.unwind_to)?;
+            validate_state_root(block_root, SealedHeader::seal(target), range)?;
 
             // Validation passed, apply unwind changes to the database.
             provider.write_trie_updates(&updates)?;
@@ -342,16 +342,27 @@ where
 }
 
 /// Check that the computed state root matches the root in the expected header.
+///
+/// The range holds the blocks whose changes were applied to compute the state root, on a mismatch
+/// one of them caused the divergence.
 #[inline]
 fn validate_state_root(
     got: B256,
     expected: SealedHeader,
-    target_block: BlockNumber,
+    range: RangeInclusive<BlockNumber>,
 ) -> Result<(), StageError> {
     if got == expected.state_root {
         Ok(())
     } else {
-        error!(target: "sync::stages::merkle", ?target_block, ?got, ?expected, "Failed to verify block state root! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
+        error!(
+            target: "sync::stages::merkle",
+            target_block = expected.number,
+            ?got,
+            ?expected,
+            "Failed to verify block state root, the divergence was introduced in blocks {}..={}! {INVALID_STATE_ROOT_ERROR_MESSAGE}",
+            range.start(),
+            range.end(),
+        );
         Err(StageError::Block {
             error: BlockErrorKind::Validation(ConsensusError::BodyStateRootDiff(
                 GotExpected { got, expected: expected.state_root }.into(),
@@ -135,6 +135,17 @@ pub struct IndexHistoryCheckpoint {
     pub progress: EntitiesCheckpoint,
 }
 
+/// Saves the progress of `SnapSync` stage.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct SnapSyncCheckpoint {
+    /// The block whose state was downloaded.
+    pub pivot: BlockNumber,
+    /// Progress measured in accounts.
+    pub progress: EntitiesCheckpoint,
+}
+
 /// Saves the progress of abstract stage iterating over or downloading entities.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Compact)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
@@ -247,7 +258,10 @@ impl StageCheckpoint {
             StageUnitCheckpoint::IndexHistory(IndexHistoryCheckpoint {
                 progress: entities,
                 ..
-            }) => Some(entities),
+            }) |
+            StageUnitCheckpoint::SnapSync(SnapSyncCheckpoint { progress: entities, .. }) => {
+                Some(entities)
+            }
         }
     }
 }
@@ -271,6 +285,8 @@ pub enum StageUnitCheckpoint {
     Headers(HeadersCheckpoint),
     /// Saves the progress of Index History stage.
     IndexHistory(IndexHistoryCheckpoint),
+    /// Saves the progress of `SnapSync` stage.
+    SnapSync(SnapSyncCheckpoint),
 }
 
 impl StageUnitCheckpoint {
@@ -379,6 +395,15 @@ stage_unit_checkpoints!(
         index_history_stage_checkpoint,
         /// Sets the stage checkpoint to index history.
         with_index_history_stage_checkpoint
+    ),
+    (
+        6,
+        SnapSync,
+        SnapSyncCheckpoint,
+        /// Returns the snap sync stage checkpoint, if any.
+        snap_sync_stage_checkpoint,
+        /// Sets the stage checkpoint to snap sync.
+        with_snap_sync_stage_checkpoint
     )
 );
 
@@ -0,0 +1,13 @@
+use reth_metrics::{metrics::Gauge, Metrics};
+
+/// Metrics of a static file segment that is being moved to static files.
+#[derive(Metrics)]
+#[metrics(scope = "static_file_producer.segments")]
+pub(crate) struct StaticFileSegmentMetrics {
+    /// Number of rows moved to static files by the current run
+    pub(crate) rows_done: Gauge,
+    /// Total number of rows to move to static files by the current run
+    pub(crate) rows_total: Gauge,
+    /// Estimated number of seconds until the current run finishes
+    pub(crate) eta_seconds: Gauge,
+}
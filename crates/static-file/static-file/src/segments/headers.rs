@@ -1,4 +1,4 @@
-use crate::segments::Segment;
+use crate::segments::{Segment, SegmentProgress};
 use alloy_primitives::BlockNumber;
 use reth_db::tables;
 use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
@@ -20,6 +20,7 @@ impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for Hea
         &self,
         provider: Provider,
         block_range: RangeInclusive<BlockNumber>,
+        progress: &mut SegmentProgress,
     ) -> ProviderResult<()> {
         let static_file_provider = provider.static_file_provider();
         let mut static_file_writer =
@@ -47,6 +48,7 @@ impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for Hea
             debug_assert_eq!(header_td_block, canonical_header_block);
 
             static_file_writer.append_header(&header, header_td.0, &canonical_header)?;
+            progress.advance(1);
         }
 
         Ok(())
@@ -9,23 +9,150 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+use crate::metrics::StaticFileSegmentMetrics;
 use alloy_primitives::BlockNumber;
-use reth_provider::StaticFileProviderFactory;
-use reth_static_file_types::StaticFileSegment;
-use reth_storage_errors::provider::ProviderResult;
-use std::ops::RangeInclusive;
+use reth_provider::{BlockReader, StaticFileProviderFactory};
+use reth_static_file_types::{StaticFileProducerEvent, StaticFileSegment};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_tokio_util::EventSender;
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+/// Minimum interval between two [`StaticFileProducerEvent::Progress`] events of a segment.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
 
 /// A segment represents moving some portion of the data to static files.
 pub trait Segment<Provider: StaticFileProviderFactory>: Send + Sync {
     /// Returns the [`StaticFileSegment`].
     fn segment(&self) -> StaticFileSegment;
 
+    /// Returns the number of rows that [`Segment::copy_to_static_files`] moves for the provided
+    /// block range.
+    ///
+    /// Defaults to one row per block.
+    fn rows_total(
+        &self,
+        _provider: &Provider,
+        block_range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<u64> {
+        Ok(block_range.end().saturating_sub(*block_range.start()) + 1)
+    }
+
     /// Move data to static files for the provided block range.
     /// [`StaticFileProvider`](reth_provider::providers::StaticFileProvider) will handle
     /// the management of and writing to files.
+    ///
+    /// The number of moved rows is reported to `progress`.
     fn copy_to_static_files(
         &self,
         provider: Provider,
         block_range: RangeInclusive<BlockNumber>,
+        progress: &mut SegmentProgress,
     ) -> ProviderResult<()>;
 }
+
+/// Progress of moving a segment to static files.
+///
+/// Periodically emits [`StaticFileProducerEvent::Progress`] events and updates the segment
+/// metrics.
+#[derive(Debug)]
+pub struct SegmentProgress {
+    segment: StaticFileSegment,
+    rows_done: u64,
+    rows_total: u64,
+    started_at: Instant,
+    last_reported_at: Instant,
+    event_sender: EventSender<StaticFileProducerEvent>,
+    metrics: StaticFileSegmentMetrics,
+}
+
+impl SegmentProgress {
+    /// Creates a new [`SegmentProgress`] for moving `rows_total` rows of the segment.
+    pub fn new(
+        segment: StaticFileSegment,
+        rows_total: u64,
+        event_sender: EventSender<StaticFileProducerEvent>,
+    ) -> Self {
+        let metrics =
+            StaticFileSegmentMetrics::new_with_labels(&[("segment", segment.to_string())]);
+        metrics.rows_done.set(0.0);
+        metrics.rows_total.set(rows_total as f64);
+
+        let now = Instant::now();
+        Self {
+            segment,
+            rows_done: 0,
+            rows_total,
+            started_at: now,
+            last_reported_at: now,
+            event_sender,
+            metrics,
+        }
+    }
+
+    /// Returns the number of rows moved so far.
+    pub const fn rows_done(&self) -> u64 {
+        self.rows_done
+    }
+
+    /// Records that `rows` more rows were moved, and reports the progress if the last report is
+    /// older than [`PROGRESS_INTERVAL`].
+    pub fn advance(&mut self, rows: u64) {
+        self.rows_done += rows;
+        if self.last_reported_at.elapsed() >= PROGRESS_INTERVAL {
+            self.report();
+        }
+    }
+
+    /// Reports the current progress.
+    pub fn report(&mut self) {
+        self.last_reported_at = Instant::now();
+
+        let eta = eta(self.started_at.elapsed(), self.rows_done, self.rows_total);
+        self.metrics.rows_done.set(self.rows_done as f64);
+        self.metrics.eta_seconds.set(eta.unwrap_or_default().as_secs_f64());
+        self.event_sender.notify(StaticFileProducerEvent::Progress {
+            segment: self.segment,
+            rows_done: self.rows_done,
+            rows_total: self.rows_total,
+            eta,
+        });
+    }
+}
+
+/// Returns the number of transactions in the provided block range.
+pub(crate) fn tx_rows_total<Provider: BlockReader>(
+    provider: &Provider,
+    block_range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<u64> {
+    let first = provider
+        .block_body_indices(*block_range.start())?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(*block_range.start()))?;
+    let last = provider
+        .block_body_indices(*block_range.end())?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(*block_range.end()))?;
+    Ok(last.next_tx_num().saturating_sub(first.first_tx_num()))
+}
+
+/// Estimates the remaining time from the rate of rows moved so far.
+fn eta(elapsed: Duration, rows_done: u64, rows_total: u64) -> Option<Duration> {
+    if rows_done == 0 {
+        return None
+    }
+    let remaining = rows_total.saturating_sub(rows_done);
+    Duration::try_from_secs_f64(elapsed.as_secs_f64() * remaining as f64 / rows_done as f64).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_progress_eta() {
+        assert_eq!(eta(Duration::from_secs(10), 0, 100), None);
+        assert_eq!(eta(Duration::from_secs(10), 25, 100), Some(Duration::from_secs(30)));
+        assert_eq!(eta(Duration::from_secs(10), 100, 100), Some(Duration::ZERO));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::segments::Segment;
+use crate::segments::{tx_rows_total, Segment, SegmentProgress};
 use alloy_primitives::BlockNumber;
 use reth_db::tables;
 use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
@@ -20,12 +20,21 @@ impl<Provider: StaticFileProviderFactory + DBProvider + BlockReader> Segment<Pro
         StaticFileSegment::Transactions
     }
 
+    fn rows_total(
+        &self,
+        provider: &Provider,
+        block_range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<u64> {
+        tx_rows_total(provider, block_range)
+    }
+
     /// Write transactions from database table [`tables::Transactions`] to static files with segment
     /// [`StaticFileSegment::Transactions`] for the provided block range.
     fn copy_to_static_files(
         &self,
         provider: Provider,
         block_range: RangeInclusive<BlockNumber>,
+        progress: &mut SegmentProgress,
     ) -> ProviderResult<()> {
         let static_file_provider = provider.static_file_provider();
         let mut static_file_writer = static_file_provider
@@ -48,6 +57,7 @@ impl<Provider: StaticFileProviderFactory + DBProvider + BlockReader> Segment<Pro
 
                 static_file_writer.append_transaction(tx_number, &transaction)?;
             }
+            progress.advance(block_body_indices.tx_count());
         }
 
         Ok(())
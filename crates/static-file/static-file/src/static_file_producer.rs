@@ -1,6 +1,10 @@
 //! Support for producing static files.
 
-use crate::{segments, segments::Segment, StaticFileProducerEvent};
+use crate::{
+    segments,
+    segments::{Segment, SegmentProgress},
+    StaticFileProducerEvent,
+};
 use alloy_primitives::BlockNumber;
 use parking_lot::Mutex;
 use rayon::prelude::*;
@@ -138,7 +142,10 @@ where
             // Create a new database transaction on every segment to prevent long-lived read-only
             // transactions
             let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
-            segment.copy_to_static_files(provider,  block_range.clone())?;
+            let rows_total = segment.rows_total(&provider, block_range)?;
+            let mut progress = SegmentProgress::new(segment.segment(), rows_total, self.event_sender.clone());
+            segment.copy_to_static_files(provider, block_range.clone(), &mut progress)?;
+            progress.report();
 
             let elapsed = start.elapsed(); // TODO(alexey): track in metrics
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
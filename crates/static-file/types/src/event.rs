@@ -1,4 +1,4 @@
-use crate::StaticFileTargets;
+use crate::{StaticFileSegment, StaticFileTargets};
 use std::time::Duration;
 
 /// An event emitted by the static file producer.
@@ -9,6 +9,17 @@ pub enum StaticFileProducerEvent {
         /// Targets that will be moved to static files
         targets: StaticFileTargets,
     },
+    /// Emitted periodically while a segment is being moved to static files.
+    Progress {
+        /// Segment that is being moved to static files
+        segment: StaticFileSegment,
+        /// Number of rows moved to static files so far
+        rows_done: u64,
+        /// Total number of rows to move to static files
+        rows_total: u64,
+        /// Estimated time until all rows are moved, if known
+        eta: Option<Duration>,
+    },
     /// Emitted when static file producer finished running.
     Finished {
         /// Targets that were moved to static files
@@ -22,6 +22,7 @@ use strum::{AsRefStr, EnumIter, EnumString};
     Display,
 )]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 /// Segment of the data that can be moved to static files.
 pub enum StaticFileSegment {
     #[strum(serialize = "headers")]
@@ -132,6 +133,7 @@ impl StaticFileSegment {
 
 /// A segment header that contains information common to all segments. Used for storage.
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 pub struct SegmentHeader {
     /// Defines the expected block range for a static file segment. This attribute is crucial for
     /// scenarios where the file contains no data, allowing for a representation beyond a
@@ -304,6 +306,7 @@ pub struct SegmentConfig {
 ///
 /// They can be modified on a hot loop, which makes the `std::ops::RangeInclusive` a poor fit.
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 pub struct SegmentRangeInclusive {
     start: u64,
     end: u64,
@@ -353,6 +356,19 @@ impl From<SegmentRangeInclusive> for RangeInclusive<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::proptest;
+    use proptest_arbitrary_interop::arb;
+
+    proptest! {
+        #[test]
+        fn segment_header_roundtrip(header in arb::<SegmentHeader>()) {
+            // Segment headers are persisted with `bincode` as part of the static file
+            // configuration.
+            let encoded = bincode::serialize(&header).unwrap();
+            let decoded: SegmentHeader = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(header, decoded);
+        }
+    }
 
     #[test]
     fn test_filename() {
@@ -2,18 +2,13 @@ use super::*;
 
 #[derive(Debug)]
 pub struct EnumHandler<'a> {
-    current_variant_index: u8,
     fields_iterator: std::iter::Peekable<std::slice::Iter<'a, FieldTypes>>,
     enum_lines: Vec<TokenStream2>,
 }
 
 impl<'a> EnumHandler<'a> {
     pub fn new(fields: &'a FieldList) -> Self {
-        EnumHandler {
-            current_variant_index: 0u8,
-            enum_lines: vec![],
-            fields_iterator: fields.iter().peekable(),
-        }
+        EnumHandler { enum_lines: vec![], fields_iterator: fields.iter().peekable() }
     }
 
     pub fn next_field(&mut self) -> Option<&'a FieldTypes> {
@@ -25,7 +20,7 @@ impl<'a> EnumHandler<'a> {
             match field {
                 //  The following method will advance the
                 // `fields_iterator` by itself and stop right before the next variant.
-                FieldTypes::EnumVariant(name) => self.to(name, ident),
+                FieldTypes::EnumVariant((name, index)) => self.to(name, *index, ident),
                 FieldTypes::EnumUnnamedField(_) | FieldTypes::StructField(_) => unreachable!(),
             }
         }
@@ -37,7 +32,7 @@ impl<'a> EnumHandler<'a> {
             match field {
                 //  The following method will advance the
                 // `fields_iterator` by itself and stop right before the next variant.
-                FieldTypes::EnumVariant(name) => self.from(name, ident),
+                FieldTypes::EnumVariant((name, index)) => self.from(name, *index, ident),
                 FieldTypes::EnumUnnamedField(_) | FieldTypes::StructField(_) => unreachable!(),
             }
         }
@@ -48,15 +43,15 @@ impl<'a> EnumHandler<'a> {
     ///
     /// `fields_iterator` might look something like \[`VariantUnit`, `VariantUnnamedField`, Field,
     /// `VariantUnit`...\].
-    pub fn from(&mut self, variant_name: &str, ident: &Ident) {
+    pub fn from(&mut self, variant_name: &str, current_variant_index: VariantIndex, ident: &Ident) {
         let variant_name = format_ident!("{variant_name}");
-        let current_variant_index = self.current_variant_index;
 
         if let Some(next_field) = self.fields_iterator.peek() {
             match next_field {
                 FieldTypes::EnumUnnamedField((next_ftype, use_alt_impl)) => {
                     // This variant is of the type `EnumVariant(UnnamedField)`
-                    let field_type = format_ident!("{next_ftype}");
+                    let field_type: syn::Type =
+                        syn::parse_str(next_ftype).expect("valid variant field type");
                     let from_compact_ident = if *use_alt_impl {
                         format_ident!("specialized_from_compact")
                     } else {
@@ -66,7 +61,7 @@ impl<'a> EnumHandler<'a> {
                     // Unnamed type
                     self.enum_lines.push(quote! {
                         #current_variant_index => {
-                            let (inner, new_buf) = #field_type::#from_compact_ident(buf, buf.len());
+                            let (inner, new_buf) = <#field_type>::#from_compact_ident(buf, buf.len());
                             buf = new_buf;
                             #ident::#variant_name(inner)
                         }
@@ -84,16 +79,14 @@ impl<'a> EnumHandler<'a> {
                 #current_variant_index => #ident::#variant_name,
             });
         }
-        self.current_variant_index += 1;
     }
 
     /// Generates `to_compact` code for an enum variant.
     ///
     /// `fields_iterator` might look something like [`VariantUnit`, `VariantUnnamedField`, Field,
     /// `VariantUnit`...].
-    pub fn to(&mut self, variant_name: &str, ident: &Ident) {
+    pub fn to(&mut self, variant_name: &str, current_variant_index: VariantIndex, ident: &Ident) {
         let variant_name = format_ident!("{variant_name}");
-        let current_variant_index = self.current_variant_index;
 
         if let Some(next_field) = self.fields_iterator.peek() {
             match next_field {
@@ -124,6 +117,5 @@ impl<'a> EnumHandler<'a> {
                 #ident::#variant_name => #current_variant_index,
             });
         }
-        self.current_variant_index += 1;
     }
 }
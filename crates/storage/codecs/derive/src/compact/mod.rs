@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use syn::{parse_macro_input, Data, DeriveInput, Generics};
 
 mod generator;
@@ -29,13 +29,17 @@ type FieldType = String;
 type UseAlternative = bool;
 // Helper Alias type
 type StructFieldDescriptor = (FieldName, FieldType, IsCompact, UseAlternative);
+/// Value stored in the `variant` bitfield to identify an enum variant.
+type VariantIndex = u8;
+// Helper Alias type
+type EnumVariantDescriptor = (FieldName, VariantIndex);
 // Helper Alias type
 type FieldList = Vec<FieldTypes>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum FieldTypes {
     StructField(StructFieldDescriptor),
-    EnumVariant(String),
+    EnumVariant(EnumVariantDescriptor),
     EnumUnnamedField((FieldType, UseAlternative)),
 }
 
@@ -80,8 +84,25 @@ pub fn get_fields(data: &Data) -> FieldList {
             syn::Fields::Unit => todo!(),
         },
         Data::Enum(data) => {
+            let mut used_indices = vec![];
+            let mut next_index = 0u16;
             for variant in &data.variants {
-                fields.push(FieldTypes::EnumVariant(variant.ident.to_string()));
+                let index = parse_variant_index(&variant.attrs).unwrap_or(next_index);
+                assert!(
+                    index <= VariantIndex::MAX as u16,
+                    "Compact only supports up to 256 enum variants: {} has index {index}.",
+                    variant.ident
+                );
+                let index = index as VariantIndex;
+                assert!(
+                    !used_indices.contains(&index),
+                    "Compact enum variant index {index} of {} is already in use.",
+                    variant.ident
+                );
+                used_indices.push(index);
+                next_index = index as u16 + 1;
+
+                fields.push(FieldTypes::EnumVariant((variant.ident.to_string(), index)));
 
                 match &variant.fields {
                     syn::Fields::Named(_) => {
@@ -105,6 +126,24 @@ pub fn get_fields(data: &Data) -> FieldList {
     fields
 }
 
+/// Parses the index of an enum variant from the `#[reth_codecs(variant = N)]` attribute.
+fn parse_variant_index(attrs: &[syn::Attribute]) -> Option<u16> {
+    let mut index = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("reth_codecs")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("variant") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                index = Some(lit.base10_parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        })
+        .unwrap();
+    }
+    index
+}
+
 fn load_field(field: &syn::Field, fields: &mut FieldList, is_enum: bool) {
     match field.ty {
         syn::Type::Reference(ref reference) => match &*reference.elem {
@@ -141,6 +180,8 @@ fn load_field_from_segments(
         }
 
         if is_enum {
+            // Keep the full type, so that variants can hold paths and generic types.
+            let ftype = field.ty.to_token_stream().to_string();
             fields.push(FieldTypes::EnumUnnamedField((ftype, use_alt_impl)));
         } else {
             let should_compact = is_flag_type(&ftype) ||
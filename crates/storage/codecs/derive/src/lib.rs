@@ -49,6 +49,37 @@ mod compact;
 ///   own encoding and do not rely on the bitflag struct.
 /// - `Bytes` fields and any types containing a `Bytes` field should be placed last to ensure
 ///   efficient decoding.
+///
+/// ## Enums:
+/// Enums, e.g. custom transaction or receipt envelopes, are encoded as a single byte bitflag
+/// holding the variant index, followed by the compacted inner value of the variant. Variants can
+/// either be units or hold a single unnamed field of any type implementing `Compact`, including
+/// fully qualified paths and generic types.
+///
+/// Variant indices are assigned in declaration order starting from `0`. Since the index is part of
+/// the stored data, reordering variants is a breaking change. To keep the layout stable, a variant
+/// index can be set explicitly with `#[reth_codecs(variant = N)]`; subsequent variants continue
+/// counting from `N + 1`. Indices have to be unique and fit into a `u8`.
+///
+/// ## Usage outside of reth:
+/// The generated code expects `bytes` and `modular-bitfield` to be dependencies of the crate using
+/// the derive, and `test-fuzz` as a dev-dependency, since a roundtrip test of the type's `Default`
+/// value is generated. If `reth_codecs` is not available under its own name, its path can be
+/// overridden with `#[reth_codecs(crate = "...")]`.
+///
+/// ```ignore
+/// use reth_codecs::Compact;
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Default, Compact)]
+/// enum CustomTransaction {
+///     #[default]
+///     Empty,
+///     Legacy(alloy_consensus::TxLegacy),
+///     Eip1559(alloy_consensus::TxEip1559),
+///     #[reth_codecs(variant = 0x7e)]
+///     Custom(CustomTx),
+/// }
+/// ```
 #[proc_macro_derive(Compact, attributes(maybe_zero, reth_codecs))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let is_zstd = false;
@@ -166,6 +166,10 @@ mod tests {
         extra_fields: None,
     };
 
+    crate::roundtrip_tests! {
+        alloy_header_roundtrip: AlloyHeader => [compact],
+    }
+
     #[test]
     fn test_ensure_backwards_compatibility() {
         assert_eq!(Header::bitflag_encoded_bytes(), 4);
@@ -128,6 +128,11 @@ mod tests {
     use super::*;
     use alloy_primitives::hex;
 
+    crate::roundtrip_tests! {
+        hash_builder_value_roundtrip: HashBuilderValue => [compact],
+        trie_mask_roundtrip: TrieMask => [compact],
+    }
+
     #[test]
     fn node_encoding() {
         let n = BranchNodeCompact::new(
@@ -749,4 +749,33 @@ mod tests {
 
         compact_test_enum_all_variants(var0, var1, var2);
     }
+
+    #[derive(
+        Debug, PartialEq, Clone, Default, Serialize, Deserialize, Compact, arbitrary::Arbitrary,
+    )]
+    #[add_arbitrary_tests(crate, compact)]
+    #[reth_codecs(crate = "crate")]
+    enum TestEnvelope {
+        #[default]
+        Empty,
+        Value(alloy_primitives::U256),
+        #[reth_codecs(variant = 0x7e)]
+        Custom(Option<u64>),
+        Other(u64),
+    }
+
+    #[test]
+    fn compact_test_enum_variant_index() {
+        for (envelope, index) in [
+            (TestEnvelope::Empty, 0),
+            (TestEnvelope::Value(U256::from(1)), 1),
+            (TestEnvelope::Custom(Some(1)), 0x7e),
+            (TestEnvelope::Other(2), 0x7f),
+        ] {
+            let mut buf = vec![];
+            let len = envelope.to_compact(&mut buf);
+            assert_eq!(buf[0], index);
+            assert_eq!(TestEnvelope::from_compact(&buf, len), (envelope, [].as_slice()));
+        }
+    }
 }
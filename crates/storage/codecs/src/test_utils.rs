@@ -62,6 +62,50 @@ macro_rules! validate_bitflag_backwards_compat {
     };
 }
 
+/// Generates proptest roundtrip tests for types implementing [`arbitrary::Arbitrary`].
+///
+/// This is meant for types that can't use [`crate::add_arbitrary_tests`], e.g. foreign types with
+/// a [`Compact`](crate::Compact) implementation in this crate. Each entry generates a test named
+/// after it, which asserts that values roundtrip through all listed codecs: `compact` and/or
+/// `rlp`.
+///
+/// The calling crate requires `proptest` and `proptest-arbitrary-interop` as dev-dependencies.
+///
+/// ```rust,ignore
+/// reth_codecs::roundtrip_tests! {
+///     header_roundtrip: alloy_consensus::Header => [compact],
+///     log_roundtrip: alloy_primitives::Log => [compact, rlp],
+/// }
+/// ```
+#[macro_export]
+macro_rules! roundtrip_tests {
+    (@compact $value:ident: $type:ty) => {{
+        let mut buf = vec![];
+        let len = $crate::Compact::to_compact(&$value, &mut buf);
+        let (decoded, _) = <$type as $crate::Compact>::from_compact(&buf, len);
+        assert_eq!($value, decoded, "roundtrip_tests::compact");
+    }};
+    (@rlp $value:ident: $type:ty) => {{
+        let mut buf = vec![];
+        alloy_rlp::Encodable::encode(&$value, &mut buf);
+        let mut b = buf.as_slice();
+        let decoded = <$type as alloy_rlp::Decodable>::decode(&mut b).unwrap();
+        assert_eq!($value, decoded, "roundtrip_tests::rlp");
+        // ensure buffer is fully consumed by decode
+        assert!(b.is_empty(), "buffer was not consumed entirely");
+    }};
+    ($($name:ident: $type:ty => [$($codec:ident),+ $(,)?]),+ $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                proptest::proptest!(|(value in proptest_arbitrary_interop::arb::<$type>())| {
+                    $($crate::roundtrip_tests!(@$codec value: $type);)+
+                });
+            }
+        )+
+    };
+}
+
 /// Whether there are zero or more unused bits on `Compact` bitflag struct.
 ///
 /// To be used with [`validate_bitflag_backwards_compat`].
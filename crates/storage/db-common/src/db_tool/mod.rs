@@ -131,6 +131,17 @@ impl<N: ProviderNodeTypes> DbTool<N> {
             .map_err(|e| eyre::eyre!(e))
     }
 
+    /// Grabs up to `len` entries of the table, starting at the first key that is greater than or
+    /// equal to the given key.
+    pub fn seek<T: Table>(&self, key: T::Key, len: usize) -> Result<Vec<TableRow<T>>> {
+        self.provider_factory
+            .db_ref()
+            .view(|tx| {
+                tx.cursor_read::<T>()?.walk(Some(key))?.take(len).collect::<Result<Vec<_>, _>>()
+            })?
+            .map_err(|e: DatabaseError| eyre::eyre!(e))
+    }
+
     /// Drops the database and the static files at the given path.
     pub fn drop(
         &self,
@@ -1,8 +1,9 @@
 //! Reth genesis initialization utility functions.
 
+use alloy_consensus::Header;
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{Address, B256, U256};
-use reth_chainspec::EthChainSpec;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition, Hardforks};
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
@@ -20,7 +21,10 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
 use reth_trie_db::DatabaseStateRoot;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io::BufRead};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::BufRead,
+};
 use tracing::{debug, error, info, trace};
 
 /// Default soft limit for number of bytes to read from state dump file, before inserting into
@@ -45,13 +49,21 @@ const SOFT_LIMIT_COUNT_FLUSHED_UPDATES: usize = 1_000_000;
 pub enum InitDatabaseError {
     /// An existing genesis block was found in the database, and its hash did not match the hash of
     /// the chainspec.
-    #[error("genesis hash in the database does not match the specified chainspec: chainspec is {chainspec_hash}, database is {database_hash}")]
+    #[error("genesis hash in the database does not match the specified chainspec: chainspec is {chainspec_hash}, database is {database_hash}. The datadir was created for a different chain, pass the `--chain` it was created with or use a different `--datadir`")]
     GenesisHashMismatch {
         /// Expected genesis hash.
         chainspec_hash: B256,
         /// Actual genesis hash.
         database_hash: B256,
     },
+    /// A block in the database is inconsistent with the hardfork schedule of the chainspec.
+    #[error("block {block} in the database is inconsistent with the specified chainspec: {reason}. The datadir was synced with a different hardfork schedule, pass the `--chain` it was created with or unwind the datadir with `reth stage unwind to-block {}`", .block.saturating_sub(1))]
+    HardforkMismatch {
+        /// The inconsistent block.
+        block: BlockNumber,
+        /// Why the block is inconsistent with the chainspec.
+        reason: String,
+    },
     /// Provider error.
     #[error(transparent)]
     Provider(#[from] ProviderError),
@@ -137,6 +149,126 @@ where
     Ok(hash)
 }
 
+/// Checks that the blocks in the database follow the hardfork schedule of the chainspec.
+///
+/// The genesis hash is checked by [`init_genesis`], this catches chainspecs that share the genesis
+/// of the database, but activate hardforks at different heights. Since every hardfork that
+/// changed the header format is detectable from the presence of the new header fields, only the
+/// headers around each activation and the tip are checked.
+pub fn validate_chain_spec<PF>(factory: &PF) -> Result<(), InitDatabaseError>
+where
+    PF: ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + HeaderProvider
+        + BlockNumReader,
+{
+    let chain = factory.chain_spec();
+    let tip = factory.last_block_number()?;
+    if tip == 0 {
+        return Ok(())
+    }
+
+    let mut blocks = BTreeSet::from([tip]);
+    // Pre-Bedrock headers of OP chains don't follow the London rules.
+    if let (ForkCondition::Block(london), false) =
+        (chain.fork(EthereumHardfork::London), chain.is_optimism())
+    {
+        blocks.extend([london.saturating_sub(1), london]);
+    }
+    for fork in [EthereumHardfork::Shanghai, EthereumHardfork::Cancun, EthereumHardfork::Prague] {
+        if let ForkCondition::Timestamp(timestamp) = chain.fork(fork) {
+            if let Some(block) = first_block_at_timestamp(factory, timestamp, tip)? {
+                blocks.extend([block.saturating_sub(1), block]);
+            }
+        }
+    }
+
+    for number in blocks.into_iter().filter(|number| (1..=tip).contains(number)) {
+        let header = header(factory, number)?;
+        if let Some(reason) = hardfork_mismatch(&*chain, &header) {
+            return Err(InitDatabaseError::HardforkMismatch { block: number, reason })
+        }
+    }
+
+    debug!(target: "reth::cli", tip, "Validated chainspec against the database");
+
+    Ok(())
+}
+
+/// Returns the reason why the header is inconsistent with the hardforks the chainspec activates at
+/// its height, if it is.
+fn hardfork_mismatch(
+    chain: &(impl EthChainSpec + EthereumHardforks),
+    header: &Header,
+) -> Option<String> {
+    let mut checks = vec![
+        (
+            EthereumHardfork::Shanghai,
+            chain.is_shanghai_active_at_timestamp(header.timestamp),
+            header.withdrawals_root.is_some(),
+        ),
+        (
+            EthereumHardfork::Cancun,
+            chain.is_cancun_active_at_timestamp(header.timestamp),
+            header.blob_gas_used.is_some(),
+        ),
+        (
+            EthereumHardfork::Prague,
+            chain.is_prague_active_at_timestamp(header.timestamp),
+            header.requests_hash.is_some(),
+        ),
+    ];
+    if !chain.is_optimism() {
+        checks.push((
+            EthereumHardfork::London,
+            chain.is_fork_active_at_block(EthereumHardfork::London, header.number),
+            header.base_fee_per_gas.is_some(),
+        ));
+    }
+
+    checks.into_iter().find(|(_, active, has_fields)| active != has_fields).map(
+        |(fork, active, _)| {
+            if active {
+                format!("the chainspec activates {fork}, but the block was built without it")
+            } else {
+                format!("the block was built with {fork}, but the chainspec doesn't activate it")
+            }
+        },
+    )
+}
+
+/// Returns the first block up to `tip` with a timestamp of at least `timestamp`.
+fn first_block_at_timestamp<PF>(
+    factory: &PF,
+    timestamp: u64,
+    tip: BlockNumber,
+) -> Result<Option<BlockNumber>, InitDatabaseError>
+where
+    PF: HeaderProvider,
+{
+    if header(factory, tip)?.timestamp < timestamp {
+        return Ok(None)
+    }
+
+    let (mut low, mut high) = (0, tip);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if header(factory, mid)?.timestamp < timestamp {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(Some(low))
+}
+
+/// Returns the header of block `number`, which must exist.
+fn header<PF>(factory: &PF, number: BlockNumber) -> Result<Header, InitDatabaseError>
+where
+    PF: HeaderProvider,
+{
+    Ok(factory.header_by_number(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?)
+}
+
 /// Inserts the genesis state into the database.
 pub fn insert_genesis_state<'a, 'b, Provider>(
     provider: &Provider,
@@ -597,7 +729,7 @@ mod tests {
         HOLESKY_GENESIS_HASH, MAINNET_GENESIS_HASH, SEPOLIA_GENESIS_HASH,
     };
     use alloy_genesis::Genesis;
-    use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET, SEPOLIA};
+    use reth_chainspec::{Chain, ChainSpec, ChainSpecBuilder, HOLESKY, MAINNET, SEPOLIA};
     use reth_db::DatabaseEnv;
     use reth_db_api::{
         cursor::DbCursorRO,
@@ -727,4 +859,50 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    fn validate_chain_spec_hardfork_mismatch() {
+        // mainnet genesis, but Shanghai activates at timestamp 10
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(10))
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+        validate_chain_spec(&factory).unwrap();
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut parent_hash = chain_spec.genesis_hash();
+        for (number, timestamp) in [(1, 5), (2, 10), (3, 15)] {
+            let header = Header {
+                parent_hash,
+                number,
+                timestamp,
+                withdrawals_root: chain_spec
+                    .is_shanghai_active_at_timestamp(timestamp)
+                    .then_some(B256::ZERO),
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            writer.append_header(&header, U256::ZERO, &parent_hash).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        validate_chain_spec(&factory).unwrap();
+
+        // same genesis, but Shanghai is not active at the tip
+        let mainnet_factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            factory.db_ref().clone(),
+            MAINNET.clone(),
+            static_file_provider,
+        );
+        assert_eq!(init_genesis(&mainnet_factory).unwrap(), MAINNET_GENESIS_HASH);
+        assert!(matches!(
+            validate_chain_spec(&mainnet_factory),
+            Err(InitDatabaseError::HardforkMismatch { block: 3, .. })
+        ));
+    }
 }
@@ -0,0 +1,111 @@
+//! Database stats snapshot model.
+
+use bytes::Buf;
+use reth_codecs::{add_arbitrary_tests, Compact};
+use serde::{Deserialize, Serialize};
+
+/// The size of the database and its tables at a point in time.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct DbStatsSnapshot {
+    /// The page size of the database in bytes.
+    pub page_size: u32,
+    /// The number of pages allocated in the database file.
+    pub allocated_pages: u64,
+    /// The number of allocated pages that are free for reuse.
+    pub freelist_pages: u64,
+    /// The stats of each table.
+    pub tables: Vec<TableStatsSnapshot>,
+}
+
+impl DbStatsSnapshot {
+    /// Returns the number of allocated pages that are in use.
+    pub const fn used_pages(&self) -> u64 {
+        self.allocated_pages.saturating_sub(self.freelist_pages)
+    }
+
+    /// Returns the size of the database file in bytes.
+    pub const fn allocated_size(&self) -> u64 {
+        self.allocated_pages * self.page_size as u64
+    }
+
+    /// Returns the size of the free pages in bytes.
+    pub const fn freelist_size(&self) -> u64 {
+        self.freelist_pages * self.page_size as u64
+    }
+
+    /// Returns the share of allocated pages that are in use, between 0 and 1.
+    pub fn utilization(&self) -> f64 {
+        if self.allocated_pages == 0 {
+            return 0.0
+        }
+        self.used_pages() as f64 / self.allocated_pages as f64
+    }
+}
+
+impl Compact for DbStatsSnapshot {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u32(self.page_size);
+        buf.put_u64(self.allocated_pages);
+        buf.put_u64(self.freelist_pages);
+        buf.put_u32(self.tables.len() as u32);
+        let mut len = 24;
+        for table in &self.tables {
+            len += table.to_compact(buf);
+        }
+        len
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let page_size = buf.get_u32();
+        let allocated_pages = buf.get_u64();
+        let freelist_pages = buf.get_u64();
+        let count = buf.get_u32() as usize;
+        let mut tables = Vec::with_capacity(count);
+        for _ in 0..count {
+            let table;
+            (table, buf) = TableStatsSnapshot::from_compact(buf, 0);
+            tables.push(table);
+        }
+        (Self { page_size, allocated_pages, freelist_pages, tables }, buf)
+    }
+}
+
+/// The size of a table at a point in time.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct TableStatsSnapshot {
+    /// The name of the table.
+    pub name: String,
+    /// The number of entries in the table.
+    pub entries: u64,
+    /// The number of branch, leaf and overflow pages of the table.
+    pub pages: u64,
+}
+
+impl Compact for TableStatsSnapshot {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u32(self.name.len() as u32);
+        buf.put_slice(self.name.as_bytes());
+        buf.put_u64(self.entries);
+        buf.put_u64(self.pages);
+        4 + self.name.len() + 16
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let name_len = buf.get_u32() as usize;
+        let name = String::from_utf8_lossy(&buf[..name_len]).into_owned();
+        buf.advance(name_len);
+        let entries = buf.get_u64();
+        let pages = buf.get_u64();
+        (Self { name, entries, pages }, buf)
+    }
+}
@@ -11,3 +11,7 @@ pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals};
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Database Stats
+pub mod db_stats;
+pub use db_stats::{DbStatsSnapshot, TableStatsSnapshot};
@@ -13,7 +13,7 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
-    models::ClientVersion,
+    models::{ClientVersion, DbStatsSnapshot, TableStatsSnapshot},
     transaction::{DbTx, DbTxMut},
 };
 use reth_libmdbx::{
@@ -26,7 +26,7 @@ use std::{
     ops::{Deref, Range},
     path::Path,
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tx::Tx;
 
@@ -42,6 +42,13 @@ pub const GIGABYTE: usize = MEGABYTE * 1024;
 /// 1 TB in bytes
 pub const TERABYTE: usize = GIGABYTE * 1024;
 
+/// The minimum time between two snapshots in the [`tables::DbStatsHistory`] table.
+pub const DB_STATS_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The maximum number of snapshots kept in the [`tables::DbStatsHistory`] table, older snapshots
+/// are removed.
+pub const DB_STATS_HISTORY_LIMIT: usize = 1_000;
+
 /// MDBX allows up to 32767 readers (`MDBX_READERS_LIMIT`), but we limit it to slightly below that
 const DEFAULT_MAX_READERS: u64 = 32_000;
 
@@ -477,6 +484,63 @@ impl DatabaseEnv {
 
         Ok(())
     }
+
+    /// Returns the current size of the database and its tables.
+    ///
+    /// Tables that don't exist in the database are skipped.
+    pub fn stats_snapshot(&self) -> Result<DbStatsSnapshot, DatabaseError> {
+        let tx = self.tx()?;
+        let mut tables = Vec::with_capacity(Tables::COUNT);
+        for table in Tables::ALL.iter().map(Tables::name) {
+            let Ok(table_db) = tx.inner.open_db(Some(table)) else { continue };
+            let stats = tx.inner.db_stat(&table_db).map_err(|e| DatabaseError::Stats(e.into()))?;
+            tables.push(TableStatsSnapshot {
+                name: table.to_string(),
+                entries: stats.entries() as u64,
+                pages: (stats.branch_pages() + stats.leaf_pages() + stats.overflow_pages()) as u64,
+            });
+        }
+
+        let page_size = self.stat().map_err(|e| DatabaseError::Stats(e.into()))?.page_size();
+        let info = self.info().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let freelist = self.freelist().map_err(|e| DatabaseError::Stats(e.into()))?;
+
+        Ok(DbStatsSnapshot {
+            page_size,
+            allocated_pages: info.last_pgno() as u64 + 1,
+            freelist_pages: freelist as u64,
+            tables,
+        })
+    }
+
+    /// Records a snapshot of the database size in the [`tables::DbStatsHistory`] table, unless
+    /// the last snapshot is more recent than the given interval.
+    ///
+    /// Only the latest [`DB_STATS_HISTORY_LIMIT`] snapshots are kept.
+    pub fn record_stats_snapshot(&self, interval: Duration) -> Result<(), DatabaseError> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let snapshot = self.stats_snapshot()?;
+
+        let tx = self.tx_mut()?;
+        let mut cursor = tx.cursor_write::<tables::DbStatsHistory>()?;
+        if cursor.last()?.is_some_and(|(timestamp, _)| timestamp + interval.as_secs() > now) {
+            return Ok(())
+        }
+        cursor.upsert(now, snapshot)?;
+
+        let count = tx.entries::<tables::DbStatsHistory>()?;
+        let mut walker = cursor.walk(None)?;
+        for _ in DB_STATS_HISTORY_LIMIT..count {
+            if walker.next().transpose()?.is_none() {
+                break
+            }
+            walker.delete_current()?;
+        }
+        drop(walker);
+        tx.commit()?;
+
+        Ok(())
+    }
 }
 
 impl Deref for DatabaseEnv {
@@ -543,6 +607,23 @@ mod tests {
         create_test_db(DatabaseEnvKind::RW);
     }
 
+    #[test]
+    fn db_record_stats_snapshot() {
+        let env = create_test_db(DatabaseEnvKind::RW);
+
+        env.record_stats_snapshot(DB_STATS_SNAPSHOT_INTERVAL).unwrap();
+        // too recent, so no new snapshot is recorded
+        env.record_stats_snapshot(DB_STATS_SNAPSHOT_INTERVAL).unwrap();
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.entries::<tables::DbStatsHistory>().unwrap(), 1);
+        let (_, snapshot) =
+            tx.cursor_read::<tables::DbStatsHistory>().unwrap().last().unwrap().unwrap();
+        assert_eq!(snapshot.tables.len(), Tables::COUNT);
+        assert!(snapshot.allocated_pages >= snapshot.used_pages());
+        assert!(snapshot.utilization() > 0.0);
+    }
+
     #[test]
     fn db_manual_put_get() {
         let env = create_test_db(DatabaseEnvKind::RW);
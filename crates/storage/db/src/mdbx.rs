@@ -2,6 +2,7 @@
 
 use crate::is_database_empty;
 use eyre::Context;
+use reth_tracing::tracing::warn;
 use std::path::Path;
 
 pub use crate::implementation::mdbx::*;
@@ -35,6 +36,7 @@ pub fn init_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result
     let db = create_db(path, args)?;
     db.create_tables()?;
     db.record_client_version(client_version)?;
+    record_stats_snapshot(&db);
     Ok(db)
 }
 
@@ -50,5 +52,14 @@ pub fn open_db(path: &Path, args: DatabaseArguments) -> eyre::Result<DatabaseEnv
     let db = DatabaseEnv::open(path, DatabaseEnvKind::RW, args.clone())
         .with_context(|| format!("Could not open database at path: {}", path.display()))?;
     db.record_client_version(args.client_version().clone())?;
+    record_stats_snapshot(&db);
     Ok(db)
 }
+
+/// Records a snapshot of the database size, failures are logged since the history is only used
+/// for diagnostics.
+fn record_stats_snapshot(db: &DatabaseEnv) {
+    if let Err(err) = db.record_stats_snapshot(DB_STATS_SNAPSHOT_INTERVAL) {
+        warn!(target: "storage::db::mdbx", %err, "Failed to record database stats snapshot");
+    }
+}
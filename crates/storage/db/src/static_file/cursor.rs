@@ -5,7 +5,7 @@ use reth_db_api::table::Decompress;
 use reth_nippy_jar::{DataReader, NippyJar, NippyJarCursor};
 use reth_primitives::static_file::SegmentHeader;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// Cursor of a static file segment.
 #[derive(Debug, Deref, DerefMut)]
@@ -69,6 +69,33 @@ impl<'a> StaticFileCursor<'a> {
         }
     }
 
+    /// Gets one column value of every row in the range of `BlockNumber`s or `TxNumber`s, in order.
+    ///
+    /// Unlike [`Self::get_one`] for every number, the cursor is positioned once and the rows are
+    /// read sequentially, so every value of the range is decompressed exactly once. Stops at the
+    /// end of the static file, so fewer values than requested may be returned.
+    pub fn get_one_range<M: ColumnSelectorOne>(
+        &mut self,
+        range: Range<u64>,
+    ) -> ProviderResult<Vec<M::FIRST>> {
+        let Some(offset) = self.jar().user_header().start() else { return Ok(Vec::new()) };
+        let start = range.start.max(offset);
+        let mut values = Vec::with_capacity(range.end.saturating_sub(start) as usize);
+
+        for number in start..range.end {
+            let row = if number == start {
+                self.row_by_number_with_cols((start - offset) as usize, M::MASK)
+            } else {
+                self.next_row_with_cols(M::MASK)
+            }
+            .map_err(|err| ProviderError::NippyJar(err.to_string()))?;
+            let Some(row) = row else { break };
+            values.push(M::FIRST::decompress(row[0])?);
+        }
+
+        Ok(values)
+    }
+
     /// Gets two column values from a row.
     pub fn get_two<M: ColumnSelectorTwo>(
         &mut self,
@@ -1,4 +1,5 @@
 use reth_db_api::table::Decompress;
+use reth_primitives::StaticFileSegment;
 
 /// Generic Mask helper struct for selecting specific column values to read and decompress.
 ///
@@ -21,17 +22,27 @@ use reth_db_api::table::Decompress;
 pub struct Mask<FIRST, SECOND = (), THIRD = ()>(std::marker::PhantomData<(FIRST, SECOND, THIRD)>);
 
 macro_rules! add_segments {
-    ($($segment:tt),+) => {
+    ($($segment:tt => $static_file_segment:ident),+) => {
         paste::paste! {
             $(
                 #[doc = concat!("Mask for ", stringify!($segment), " static file segment. See [`Mask`] for more.")]
                 #[derive(Debug)]
                 pub struct [<$segment Mask>]<FIRST, SECOND = (), THIRD = ()>(Mask<FIRST, SECOND, THIRD>);
+
+                impl<FIRST, SECOND, THIRD> SegmentMask for [<$segment Mask>]<FIRST, SECOND, THIRD> {
+                    const SEGMENT: StaticFileSegment = StaticFileSegment::$static_file_segment;
+                }
             )+
         }
     };
 }
-add_segments!(Header, Receipt, Transaction);
+add_segments!(Header => Headers, Receipt => Receipts, Transaction => Transactions);
+
+/// Trait for specifying the static file segment a mask selects columns from.
+pub trait SegmentMask {
+    /// Static file segment of the mask.
+    const SEGMENT: StaticFileSegment;
+}
 
 ///  Trait for specifying a mask to select one column value.
 pub trait ColumnSelectorOne {
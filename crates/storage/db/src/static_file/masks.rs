@@ -17,6 +17,7 @@ add_static_file_mask!(HeaderMask, <HeaderTerminalDifficulties as Table>::Value,
 
 // RECEIPT MASKS
 add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
+add_static_file_mask!(ReceiptMask, RawValue<<Receipts as Table>::Value>, 0b1);
 
 // TRANSACTION MASKS
 add_static_file_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
@@ -26,8 +26,8 @@ use reth_db_api::{
         accounts::BlockNumberAddress,
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
-        AccountBeforeTx, ClientVersion, CompactU256, ShardedKey, StoredBlockBodyIndices,
-        StoredBlockWithdrawals,
+        AccountBeforeTx, ClientVersion, CompactU256, DbStatsSnapshot, ShardedKey,
+        StoredBlockBodyIndices, StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table},
 };
@@ -501,6 +501,58 @@ tables! {
         type Value = ClientVersion;
     }
 
+    /// Stores periodic snapshots of the size of the database and its tables by unix timestamp in seconds.
+    table DbStatsHistory {
+        type Key = u64;
+        type Value = DbStatsSnapshot;
+    }
+
+    /// Stores pointers to the blocks that contain logs emitted by an address.
+    ///
+    /// Maintained by the RPC log index, if enabled. Shards are laid out like
+    /// [`AccountsHistory`], the last shard of every address is keyed by `u64::MAX`.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain logs with a topic, at any position.
+    ///
+    /// Maintained by the RPC log index, if enabled. Shards are laid out like
+    /// [`AccountsHistory`], the last shard of every topic is keyed by `u64::MAX`.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain a trace _from_ an address.
+    ///
+    /// Maintained by the RPC trace index, if enabled. Shards are laid out like
+    /// [`AccountsHistory`], the last shard of every address is keyed by `u64::MAX`.
+    table TraceFromIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain a trace _to_ an address.
+    ///
+    /// Maintained by the RPC trace index, if enabled. Shards are laid out like
+    /// [`AccountsHistory`], the last shard of every address is keyed by `u64::MAX`.
+    table TraceToIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks that contain a transaction sent by an address, sent to it or
+    /// creating it.
+    ///
+    /// Maintained by the RPC address index, if enabled. Shards are laid out like
+    /// [`AccountsHistory`], the last shard of every address is keyed by `u64::MAX`.
+    table TransactionAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores generic chain state info, like the last finalized block.
     table ChainState {
         type Key = ChainStateKey;
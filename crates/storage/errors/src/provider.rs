@@ -25,6 +25,9 @@ pub enum ProviderError {
     /// Nippy jar error.
     #[display("nippy jar error: {_0}")]
     NippyJar(String),
+    /// Remote static file error.
+    #[display("remote static file error: {_0}")]
+    RemoteStaticFile(String),
     /// Trie witness error.
     #[display("trie witness error: {_0}")]
     TrieWitnessError(String),
@@ -114,6 +117,10 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[display("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// State is not available for the given block number because the state was snap synced at a
+    /// later block, so its history was never downloaded.
+    #[display("state history at block #{_0} is not available")]
+    StateHistoryNotAvailable(BlockNumber),
     /// Provider does not support this particular request.
     #[display("this provider does not support this request")]
     UnsupportedProvider,
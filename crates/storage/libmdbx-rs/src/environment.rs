@@ -225,6 +225,34 @@ impl Environment {
 
         Ok(freelist)
     }
+
+    /// Copies the environment into a new file at `dest`, which must not exist yet.
+    ///
+    /// The copy is made within a read transaction, so it's a consistent snapshot of the
+    /// environment even if it's concurrently written to. If `compact` is set, free pages are
+    /// omitted and all pages are renumbered sequentially.
+    ///
+    /// Note: the read transaction is held for the whole duration of the copy.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = CString::new(path_to_bytes(dest)).map_err(|_| Error::Invalid)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
 }
 
 /// Container type for Environment internals.
@@ -690,20 +718,6 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
@@ -114,6 +114,30 @@ fn test_stat() {
     assert_eq!(stat.entries(), 64);
 }
 
+#[test]
+fn test_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let tx = env.begin_rw_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    tx.put(db.dbi(), b"key", b"value", WriteFlags::empty()).unwrap();
+    tx.commit().unwrap();
+
+    for compact in [false, true] {
+        let copy_dir = tempdir().unwrap();
+        let dest = copy_dir.path().join("mdbx.dat");
+        env.copy(&dest, compact).unwrap();
+        // the destination must not exist yet
+        env.copy(&dest, compact).unwrap_err();
+
+        let copy = Environment::builder().open(copy_dir.path()).unwrap();
+        let tx = copy.begin_ro_txn().unwrap();
+        let db = tx.open_db(None).unwrap();
+        assert_eq!(tx.get::<[u8; 5]>(db.dbi(), b"key").unwrap(), Some(*b"value"));
+    }
+}
+
 #[test]
 fn test_info() {
     let map_size = 1024 * 1024;
@@ -160,7 +160,9 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProvider::new(self.database_provider_ro()?)))
+        let provider = self.database_provider_ro()?;
+        let snap_sync_pivot = provider.snap_sync_pivot()?;
+        Ok(Box::new(LatestStateProvider::new(provider).with_snap_sync_pivot(snap_sync_pivot)))
     }
 
     /// Storage provider for state at that given block
@@ -151,7 +151,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     /// State provider for latest block
     pub fn latest<'a>(&'a self) -> ProviderResult<Box<dyn StateProvider + 'a>> {
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProviderRef::new(self)))
+        Ok(Box::new(
+            LatestStateProviderRef::new(self).with_snap_sync_pivot(self.snap_sync_pivot()?),
+        ))
     }
 
     /// Storage provider for state at that given block hash
@@ -161,10 +163,13 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     ) -> ProviderResult<Box<dyn StateProvider + 'a>> {
         let mut block_number =
             self.block_number(block_hash)?.ok_or(ProviderError::BlockHashNotFound(block_hash))?;
+        let snap_sync_pivot = self.snap_sync_pivot()?;
         if block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
-            return Ok(Box::new(LatestStateProviderRef::new(self)))
+            return Ok(Box::new(
+                LatestStateProviderRef::new(self).with_snap_sync_pivot(snap_sync_pivot),
+            ))
         }
 
         // +1 as the changeset that we want is the one that was applied after this block.
@@ -175,7 +180,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProviderRef::new(self, block_number);
+        let mut state_provider = HistoricalStateProviderRef::new(self, block_number)
+            .with_snap_sync_pivot(snap_sync_pivot);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -240,10 +246,13 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
         self,
         mut block_number: BlockNumber,
     ) -> ProviderResult<StateProviderBox> {
+        let snap_sync_pivot = self.snap_sync_pivot()?;
         if block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
-            return Ok(Box::new(LatestStateProvider::new(self)))
+            return Ok(Box::new(
+                LatestStateProvider::new(self).with_snap_sync_pivot(snap_sync_pivot),
+            ))
         }
 
         // +1 as the changeset that we want is the one that was applied after this block.
@@ -254,7 +263,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProvider::new(self, block_number);
+        let mut state_provider =
+            HistoricalStateProvider::new(self, block_number).with_snap_sync_pivot(snap_sync_pivot);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
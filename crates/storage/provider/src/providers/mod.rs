@@ -47,6 +47,8 @@ pub use static_file::{
     StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
     StaticFileProviderRWRefMut, StaticFileWriter,
 };
+#[cfg(feature = "remote-static-files")]
+pub use static_file::{RemoteStaticFiles, REMOTE_MANIFEST_FILE_NAME};
 
 mod state;
 pub use state::{
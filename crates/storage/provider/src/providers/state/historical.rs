@@ -1,6 +1,9 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    ProviderError, StateProvider, StateRootProvider,
+    providers::state::{
+        latest::{snap_synced_account, snap_synced_storage},
+        macros::delegate_provider_impls,
+    },
+    AccountReader, BlockHashReader, ProviderError, StateProvider, StateRootProvider,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
 use alloy_primitives::{
@@ -48,6 +51,8 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// The block whose state was downloaded by snap sync, if the state was snap synced.
+    snap_sync_pivot: Option<BlockNumber>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -61,7 +66,12 @@ pub enum HistoryInfo {
 impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, Provider> {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snap_sync_pivot: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -71,11 +81,12 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self { provider, block_number, lowest_available_blocks, snap_sync_pivot: None }
     }
 
     /// Lookup an account in the `AccountsHistory` table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
+        self.ensure_history_synced()?;
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
@@ -85,7 +96,9 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         self.history_info::<tables::AccountsHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            self.lowest_available_blocks
+                .account_history_block_number
+                .max(self.synced_history_start()),
         )
     }
 
@@ -95,6 +108,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
+        self.ensure_history_synced()?;
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
@@ -104,10 +118,31 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         self.history_info::<tables::StoragesHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            self.lowest_available_blocks
+                .storage_history_block_number
+                .max(self.synced_history_start()),
         )
     }
 
+    /// Returns the first block whose changesets exist, if the state was snap synced.
+    ///
+    /// The history starts after the snap sync pivot, state that didn't change since is only in
+    /// the hashed state.
+    fn synced_history_start(&self) -> Option<BlockNumber> {
+        self.snap_sync_pivot.map(|pivot| pivot + 1)
+    }
+
+    /// Returns an error if the state was snap synced after the block, its history was never
+    /// downloaded.
+    const fn ensure_history_synced(&self) -> ProviderResult<()> {
+        if let Some(pivot) = self.snap_sync_pivot {
+            if self.block_number <= pivot {
+                return Err(ProviderError::StateHistoryNotAvailable(self.block_number))
+            }
+        }
+        Ok(())
+    }
+
     /// Checks and returns `true` if distance to historical block exceeds the provided limit.
     fn check_distance_against_limit(&self, limit: u64) -> ProviderResult<bool> {
         let tip = self.provider.last_block_number()?;
@@ -117,6 +152,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
 
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        self.ensure_history_synced()?;
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -136,6 +172,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
 
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
+        self.ensure_history_synced()?;
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
@@ -231,6 +268,15 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         self.lowest_available_blocks.storage_history_block_number = Some(block_number);
         self
     }
+
+    /// Set the block whose state was downloaded by snap sync.
+    ///
+    /// The history before the pivot isn't available, and accounts and storage slots that didn't
+    /// change after it are read from the hashed state.
+    pub const fn with_snap_sync_pivot(mut self, snap_sync_pivot: Option<BlockNumber>) -> Self {
+        self.snap_sync_pivot = snap_sync_pivot;
+        self
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provider> {
@@ -257,7 +303,10 @@ impl<Provider: DBProvider + BlockNumReader> AccountReader
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx().get::<tables::PlainAccountState>(address)?)
+                match self.tx().get::<tables::PlainAccountState>(address)? {
+                    Some(account) => Ok(Some(account)),
+                    None => snap_synced_account(self.tx(), self.snap_sync_pivot, address),
+                }
             }
         }
     }
@@ -400,13 +449,23 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> StateProvider
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                match self
+                    .tx()
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                {
+                    Some(entry) => Ok(Some(entry.value)),
+                    None => Ok(snap_synced_storage(
+                        self.tx(),
+                        self.snap_sync_pivot,
+                        address,
+                        storage_key,
+                    )?
+                    .or(Some(StorageValue::ZERO))),
+                }
+            }
         }
     }
 
@@ -426,12 +485,19 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// The block whose state was downloaded by snap sync, if the state was snap synced.
+    snap_sync_pivot: Option<BlockNumber>,
 }
 
 impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snap_sync_pivot: None,
+        }
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -452,6 +518,13 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
         self
     }
 
+    /// Set the block whose state was downloaded by snap sync, see
+    /// [`HistoricalStateProviderRef::with_snap_sync_pivot`].
+    pub const fn with_snap_sync_pivot(mut self, snap_sync_pivot: Option<BlockNumber>) -> Self {
+        self.snap_sync_pivot = snap_sync_pivot;
+        self
+    }
+
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
@@ -460,6 +533,7 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_snap_sync_pivot(self.snap_sync_pivot)
     }
 }
 
@@ -501,7 +575,7 @@ mod tests {
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
@@ -776,4 +850,34 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         );
     }
+    #[test]
+    fn history_provider_snap_synced() {
+        let factory = create_test_provider_factory();
+        let db = factory.database_provider_rw().unwrap();
+        let account = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+        db.tx_ref().put::<tables::HashedAccounts>(keccak256(ADDRESS), account).unwrap();
+        db.tx_ref()
+            .put::<tables::HashedStorages>(
+                keccak256(ADDRESS),
+                StorageEntry { key: keccak256(STORAGE), value: U256::from(2) },
+            )
+            .unwrap();
+
+        // the state was snap synced at block 5, there is no history before it
+        let provider = HistoricalStateProviderRef::new(&db, 5).with_snap_sync_pivot(Some(5));
+        assert_eq!(
+            provider.basic_account(ADDRESS),
+            Err(ProviderError::StateHistoryNotAvailable(5))
+        );
+        assert_eq!(
+            provider.storage(ADDRESS, STORAGE),
+            Err(ProviderError::StateHistoryNotAvailable(5))
+        );
+
+        // state that didn't change after the pivot is read from the hashed state
+        let provider = HistoricalStateProviderRef::new(&db, 6).with_snap_sync_pivot(Some(5));
+        assert_eq!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::MaybeInPlainState));
+        assert_eq!(provider.basic_account(ADDRESS), Ok(Some(account)));
+        assert_eq!(provider.storage(ADDRESS, STORAGE), Ok(Some(U256::from(2))));
+    }
 }
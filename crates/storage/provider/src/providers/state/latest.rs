@@ -3,6 +3,7 @@ use crate::{
     StateProvider, StateRootProvider,
 };
 use alloy_primitives::{
+    keccak256,
     map::{HashMap, HashSet},
     Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
 };
@@ -26,30 +27,48 @@ use reth_trie_db::{
 ///
 /// Wraps a [`DBProvider`] to get access to database.
 #[derive(Debug)]
-pub struct LatestStateProviderRef<'b, Provider>(&'b Provider);
+pub struct LatestStateProviderRef<'b, Provider> {
+    /// Database provider
+    provider: &'b Provider,
+    /// The block whose state was downloaded by snap sync, if the state was snap synced.
+    snap_sync_pivot: Option<BlockNumber>,
+}
 
 impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     /// Create new state provider
     pub const fn new(provider: &'b Provider) -> Self {
-        Self(provider)
+        Self { provider, snap_sync_pivot: None }
+    }
+
+    /// Set the block whose state was downloaded by snap sync.
+    ///
+    /// Accounts and storage slots that are missing in the plain state are read from the hashed
+    /// state if this is set, see
+    /// [`StageCheckpointReader::snap_sync_pivot`](reth_storage_api::StageCheckpointReader::snap_sync_pivot).
+    pub const fn with_snap_sync_pivot(mut self, snap_sync_pivot: Option<BlockNumber>) -> Self {
+        self.snap_sync_pivot = snap_sync_pivot;
+        self
     }
 
     fn tx(&self) -> &Provider::Tx {
-        self.0.tx_ref()
+        self.provider.tx_ref()
     }
 }
 
 impl<Provider: DBProvider> AccountReader for LatestStateProviderRef<'_, Provider> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        self.tx().get::<tables::PlainAccountState>(address).map_err(Into::into)
+        match self.tx().get::<tables::PlainAccountState>(address)? {
+            Some(account) => Ok(Some(account)),
+            None => snap_synced_account(self.tx(), self.snap_sync_pivot, address),
+        }
     }
 }
 
 impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, Provider> {
     /// Get block hash by number.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
-        self.0.block_hash(number)
+        self.provider.block_hash(number)
     }
 
     fn canonical_hashes_range(
@@ -57,7 +76,7 @@ impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, P
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>> {
-        self.0.canonical_hashes_range(start, end)
+        self.provider.canonical_hashes_range(start, end)
     }
 }
 
@@ -153,7 +172,7 @@ impl<Provider: DBProvider + BlockHashReader> StateProvider
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        snap_synced_storage(self.tx(), self.snap_sync_pivot, account, storage_key)
     }
 
     /// Get account code by its hash
@@ -162,20 +181,65 @@ impl<Provider: DBProvider + BlockHashReader> StateProvider
     }
 }
 
+/// Reads an account that's missing in the plain state from the hashed state, if the state was snap
+/// synced. Accounts that didn't change after the snap sync pivot are only in the hashed state.
+pub(crate) fn snap_synced_account<TX: DbTx>(
+    tx: &TX,
+    snap_sync_pivot: Option<BlockNumber>,
+    address: Address,
+) -> ProviderResult<Option<Account>> {
+    if snap_sync_pivot.is_none() {
+        return Ok(None)
+    }
+    Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+}
+
+/// Reads a storage slot that's missing in the plain state from the hashed state, if the state was
+/// snap synced. Storage slots that didn't change after the snap sync pivot are only in the hashed
+/// state.
+pub(crate) fn snap_synced_storage<TX: DbTx>(
+    tx: &TX,
+    snap_sync_pivot: Option<BlockNumber>,
+    address: Address,
+    storage_key: StorageKey,
+) -> ProviderResult<Option<StorageValue>> {
+    if snap_sync_pivot.is_none() {
+        return Ok(None)
+    }
+    let hashed_slot = keccak256(storage_key);
+    Ok(tx
+        .cursor_dup_read::<tables::HashedStorages>()?
+        .seek_by_key_subkey(keccak256(address), hashed_slot)?
+        .filter(|entry| entry.key == hashed_slot)
+        .map(|entry| entry.value))
+}
+
 /// State provider for the latest state.
 #[derive(Debug)]
-pub struct LatestStateProvider<Provider>(Provider);
+pub struct LatestStateProvider<Provider> {
+    /// Database provider
+    provider: Provider,
+    /// The block whose state was downloaded by snap sync, if the state was snap synced.
+    snap_sync_pivot: Option<BlockNumber>,
+}
 
 impl<Provider: DBProvider> LatestStateProvider<Provider> {
     /// Create new state provider
     pub const fn new(db: Provider) -> Self {
-        Self(db)
+        Self { provider: db, snap_sync_pivot: None }
+    }
+
+    /// Set the block whose state was downloaded by snap sync, see
+    /// [`LatestStateProviderRef::with_snap_sync_pivot`].
+    pub const fn with_snap_sync_pivot(mut self, snap_sync_pivot: Option<BlockNumber>) -> Self {
+        self.snap_sync_pivot = snap_sync_pivot;
+        self
     }
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> LatestStateProviderRef<'_, Provider> {
-        LatestStateProviderRef::new(&self.0)
+        LatestStateProviderRef::new(&self.provider).with_snap_sync_pivot(self.snap_sync_pivot)
     }
 }
 
@@ -185,10 +249,54 @@ delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvid
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::U256;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::StorageEntry;
+    use reth_stages_types::{SnapSyncCheckpoint, StageCheckpoint, StageId};
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[allow(dead_code)]
     const fn assert_latest_state_provider<T: DBProvider + BlockHashReader>() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
+
+    #[test]
+    fn snap_synced_state() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let address = Address::with_last_byte(1);
+        let slot = B256::with_last_byte(2);
+        let account = Account { nonce: 1, ..Default::default() };
+        provider.tx_ref().put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::HashedStorages>(
+                keccak256(address),
+                StorageEntry { key: keccak256(slot), value: U256::from(3) },
+            )
+            .unwrap();
+
+        // the hashed state is only read if the state was snap synced
+        let state = LatestStateProviderRef::new(&*provider);
+        assert_eq!(state.basic_account(address).unwrap(), None);
+        assert_eq!(state.storage(address, slot).unwrap(), None);
+
+        let state = state.with_snap_sync_pivot(Some(1));
+        assert_eq!(state.basic_account(address).unwrap(), Some(account));
+        assert_eq!(state.storage(address, slot).unwrap(), Some(U256::from(3)));
+        assert_eq!(state.storage(address, B256::ZERO).unwrap(), None);
+
+        // the pivot is read when the state provider of the database provider is created
+        assert_eq!(provider.latest().unwrap().basic_account(address).unwrap(), None);
+        provider
+            .tx_ref()
+            .put::<tables::StageCheckpoints>(
+                StageId::SnapSync.to_string(),
+                StageCheckpoint::new(1)
+                    .with_snap_sync_stage_checkpoint(SnapSyncCheckpoint::default()),
+            )
+            .unwrap();
+        assert_eq!(provider.latest().unwrap().basic_account(address).unwrap(), Some(account));
+    }
 }
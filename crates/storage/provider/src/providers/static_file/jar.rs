@@ -325,15 +325,6 @@ impl<N: NodePrimitives> ReceiptProvider for StaticFileJarProvider<'_, N> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>> {
-        let range = to_range(range);
-        let mut cursor = self.cursor()?;
-        let mut receipts = Vec::with_capacity((range.end - range.start) as usize);
-
-        for num in range {
-            if let Some(tx) = cursor.get_one::<ReceiptMask<Receipt>>(num.into())? {
-                receipts.push(tx)
-            }
-        }
-        Ok(receipts)
+        self.cursor()?.get_one_range::<ReceiptMask<Receipt>>(to_range(range))
     }
 }
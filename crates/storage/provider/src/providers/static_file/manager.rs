@@ -1,3 +1,5 @@
+#[cfg(feature = "remote-static-files")]
+use super::RemoteStaticFiles;
 use super::{
     metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
@@ -12,14 +14,19 @@ use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber,
 };
-use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{
+    keccak256, Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
+};
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
 use reth_chainspec::{ChainInfo, ChainSpecProvider};
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask,
+        SegmentMask, StaticFileCursor, TransactionMask,
+    },
     tables,
 };
 use reth_db_api::{
@@ -228,6 +235,9 @@ pub struct StaticFileProviderInner<N> {
     access: StaticFileAccess,
     /// Number of blocks per file.
     blocks_per_file: u64,
+    /// Remote location to fetch static files from, if they're missing on disk.
+    #[cfg(feature = "remote-static-files")]
+    remote: Option<RemoteStaticFiles>,
     /// Write lock for when access is [`StaticFileAccess::RW`].
     _lock_file: Option<StorageLock>,
     /// Node primitives
@@ -252,6 +262,8 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             metrics: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
+            #[cfg(feature = "remote-static-files")]
+            remote: None,
             _lock_file,
             _pd: Default::default(),
         };
@@ -288,6 +300,25 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Fetches static files that are missing on disk from the given remote location.
+    ///
+    /// Remote static files are indexed right away, but their data is only downloaded once it's
+    /// accessed.
+    ///
+    /// Returns an error if the provider is already shared, e.g. with a file watcher.
+    #[cfg(feature = "remote-static-files")]
+    pub fn with_remote(self, remote: RemoteStaticFiles) -> ProviderResult<Self> {
+        let mut provider = Arc::try_unwrap(self.0).map_err(|_| {
+            ProviderError::RemoteStaticFile(
+                "remote static files must be configured before the provider is shared".to_string(),
+            )
+        })?;
+        provider.remote = Some(remote);
+        let provider = Self(Arc::new(provider));
+        provider.initialize_index()?;
+        Ok(provider)
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -450,6 +481,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
             let path = self.path.join(segment.filename(fixed_block_range));
+            self.fetch_remote_jar(&path)?;
             let jar = NippyJar::load(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
             self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
         };
@@ -512,6 +544,12 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
+        // Download before taking the index locks, so readers aren't blocked by the download.
+        if let Some(segment_max_block) = segment_max_block {
+            let fixed_range = self.find_fixed_range(segment_max_block);
+            self.fetch_remote_jar(&self.path.join(segment.filename(&fixed_range)))?;
+        }
+
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
@@ -521,10 +559,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
 
-                let jar = NippyJar::<SegmentHeader>::load(
-                    &self.path.join(segment.filename(&fixed_range)),
-                )
-                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                let path = self.path.join(segment.filename(&fixed_range));
+                let jar = NippyJar::<SegmentHeader>::load(&path)
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
                 // Updates the tx index by first removing all entries which have a higher
                 // block_start than our current static file.
@@ -581,17 +618,55 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Returns the segment, block range and transaction range of the remote jars whose data is
+    /// not on disk yet.
+    #[cfg_attr(not(feature = "remote-static-files"), allow(clippy::missing_const_for_fn))]
+    fn missing_remote_jars(
+        &self,
+    ) -> ProviderResult<
+        Vec<(StaticFileSegment, SegmentRangeInclusive, Option<SegmentRangeInclusive>)>,
+    > {
+        #[cfg(feature = "remote-static-files")]
+        if let Some(remote) = &self.remote {
+            return remote.missing_jars(&self.path)
+        }
+        Ok(Vec::new())
+    }
+
+    /// Downloads the files of the jar at `path` from the remote location, if they're missing on
+    /// disk.
+    #[cfg_attr(not(feature = "remote-static-files"), allow(clippy::missing_const_for_fn))]
+    fn fetch_remote_jar(&self, _path: &Path) -> ProviderResult<()> {
+        #[cfg(feature = "remote-static-files")]
+        if let Some(remote) = &self.remote {
+            remote.fetch_jar(_path)?;
+        }
+        Ok(())
+    }
+
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
+        // Download before taking the index locks, so readers aren't blocked by the download.
+        let missing_jars = self.missing_remote_jars()?;
+
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         max_block.clear();
         tx_index.clear();
 
-        for (segment, ranges) in
-            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
-        {
+        let mut static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        if !missing_jars.is_empty() {
+            for (segment, block_range, tx_range) in missing_jars {
+                static_files.entry(segment).or_default().push((block_range, tx_range));
+            }
+            for ranges in static_files.values_mut() {
+                ranges.sort_by_key(|(block_range, _)| block_range.end());
+            }
+        }
+
+        for (segment, ranges) in static_files {
             // Update last block for each segment
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
@@ -1073,6 +1148,78 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         }))
     }
 
+    /// Returns an iterator over the column selected by the mask `M`, within a block or
+    /// transaction range of the mask's segment.
+    ///
+    /// Only the selected column of each row is read and decompressed. Selecting a
+    /// [`RawValue`](reth_db::RawValue) column defers decoding until the value is accessed.
+    pub fn column_iter<M>(
+        &self,
+        range: Range<u64>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<M::FIRST>> + '_>
+    where
+        M: SegmentMask + ColumnSelectorOne,
+        M::FIRST: Debug,
+    {
+        self.fetch_range_iter(M::SEGMENT, range, |cursor, number| {
+            cursor.get_one::<M>(number.into())
+        })
+    }
+
+    /// Returns an iterator over the two columns selected by the mask `M`, within a block or
+    /// transaction range of the mask's segment.
+    ///
+    /// See [`Self::column_iter`].
+    pub fn columns_iter<M>(
+        &self,
+        range: Range<u64>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(M::FIRST, M::SECOND)>> + '_>
+    where
+        M: SegmentMask + ColumnSelectorTwo,
+        M::FIRST: Debug,
+        M::SECOND: Debug,
+    {
+        self.fetch_range_iter(M::SEGMENT, range, |cursor, number| {
+            cursor.get_two::<M>(number.into())
+        })
+    }
+
+    /// Returns an iterator over the hashes of the block range, without decoding the headers.
+    pub fn block_hashes_iter(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<BlockHash>> + '_> {
+        self.column_iter::<HeaderMask<BlockHash>>(range)
+    }
+
+    /// Returns an iterator over the transaction numbers and hashes of the transaction range.
+    ///
+    /// Hashes are not stored in static files, so every transaction is decoded to compute its hash.
+    /// Senders are not recovered.
+    pub fn transaction_hashes_iter(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(TxNumber, TxHash)>> + '_> {
+        self.fetch_range_iter(StaticFileSegment::Transactions, range, |cursor, number| {
+            Ok(cursor
+                .get_one::<TransactionMask<TransactionSignedNoHash>>(number.into())?
+                .map(|transaction| (number, transaction.hash())))
+        })
+    }
+
+    /// Returns an iterator over the transaction numbers and receipt logs of the transaction
+    /// range.
+    pub fn receipt_logs_iter(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(TxNumber, Vec<Log>)>> + '_> {
+        self.fetch_range_iter(StaticFileSegment::Receipts, range, |cursor, number| {
+            Ok(cursor
+                .get_one::<ReceiptMask<Receipt>>(number.into())?
+                .map(|receipt| (number, receipt.logs)))
+        })
+    }
+
     /// Returns directory where `static_files` are located.
     pub fn directory(&self) -> &Path {
         &self.path
@@ -1365,12 +1512,30 @@ impl<N: NodePrimitives> ReceiptProvider for StaticFileProvider<N> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>> {
-        self.fetch_range_with_predicate(
-            StaticFileSegment::Receipts,
-            to_range(range),
-            |cursor, number| cursor.get_one::<ReceiptMask<Receipt>>(number.into()),
-            |_| true,
-        )
+        let range = to_range(range);
+        let mut receipts = Vec::with_capacity((range.end - range.start).min(100) as usize);
+
+        // the receipts of every static file in the range are read in a single pass, so the
+        // receipts of a block are decompressed once instead of seeking to every receipt
+        let mut start = range.start;
+        while start < range.end {
+            let provider = self.get_segment_provider_from_transaction(
+                StaticFileSegment::Receipts,
+                start,
+                None,
+            )?;
+            let end = provider.user_header().tx_end().map_or(start, |tx_end| tx_end + 1);
+            let read = provider
+                .cursor()?
+                .get_one_range::<ReceiptMask<Receipt>>(start..end.min(range.end))?;
+            if read.is_empty() {
+                return Err(ProviderError::MissingStaticFileTx(StaticFileSegment::Receipts, start))
+            }
+            start += read.len() as u64;
+            receipts.extend(read);
+        }
+
+        Ok(receipts)
     }
 }
 
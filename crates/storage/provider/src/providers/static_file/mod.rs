@@ -9,6 +9,11 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+#[cfg(feature = "remote-static-files")]
+mod remote;
+#[cfg(feature = "remote-static-files")]
+pub use remote::{RemoteStaticFiles, REMOTE_MANIFEST_FILE_NAME};
+
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -62,10 +67,11 @@ mod tests {
     use alloy_primitives::{BlockHash, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::{
-        test_utils::create_test_static_files_dir, CanonicalHeaders, HeaderNumbers,
-        HeaderTerminalDifficulties, Headers,
+        static_file::{HeaderMask, ReceiptMask},
+        test_utils::create_test_static_files_dir,
+        CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers, RawValue,
     };
-    use reth_db_api::transaction::DbTxMut;
+    use reth_db_api::{models::CompactU256, transaction::DbTxMut};
     use reth_primitives::{
         static_file::{find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE},
         Receipt, TransactionSignedNoHash,
@@ -549,4 +555,172 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_column_iterators() {
+        let blocks_per_file = 10;
+
+        // Headers spanning three static files
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw = StaticFileProvider::<()>::read_write(&static_dir)
+            .unwrap()
+            .with_custom_blocks_per_file(blocks_per_file);
+        let headers = random_header_range(&mut generators::rng(), 0..25, B256::random());
+        let mut writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut td = U256::ZERO;
+        let mut expected_tds = Vec::new();
+        for header in &headers {
+            td += header.difficulty;
+            expected_tds.push((td.into(), header.hash()));
+            writer.append_header(header.header(), td, &header.hash()).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let hashes = sf_rw.block_hashes_iter(0..25).unwrap().collect::<ProviderResult<Vec<_>>>();
+        assert_eq!(hashes.unwrap(), headers.iter().map(|header| header.hash()).collect::<Vec<_>>());
+        let tds = sf_rw
+            .columns_iter::<HeaderMask<CompactU256, BlockHash>>(0..25)
+            .unwrap()
+            .collect::<ProviderResult<Vec<_>>>();
+        assert_eq!(tds.unwrap(), expected_tds);
+
+        // Transactions and receipts spanning three static files, the second one being empty
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            let (static_dir, _) = create_test_static_files_dir();
+            let sf_rw = StaticFileProvider::read_write(&static_dir)
+                .unwrap()
+                .with_custom_blocks_per_file(blocks_per_file);
+            setup_tx_based_scenario(&sf_rw, segment, blocks_per_file);
+            let tx_range = 0..sf_rw.get_highest_static_file_tx(segment).unwrap() + 1;
+
+            if segment.is_receipts() {
+                let logs = sf_rw.receipt_logs_iter(tx_range.clone()).unwrap();
+                assert_eq!(
+                    logs.collect::<ProviderResult<Vec<_>>>().unwrap(),
+                    tx_range.clone().map(|id| (id, vec![])).collect::<Vec<_>>()
+                );
+
+                // Uses cumulative_gas_used as id
+                let ids = sf_rw
+                    .column_iter::<ReceiptMask<RawValue<Receipt>>>(tx_range.clone())
+                    .unwrap()
+                    .map(|receipt| Ok(receipt?.value()?.cumulative_gas_used))
+                    .collect::<ProviderResult<Vec<_>>>();
+                assert_eq!(ids.unwrap(), tx_range.collect::<Vec<_>>());
+            } else {
+                let hashes = sf_rw.transaction_hashes_iter(tx_range.clone()).unwrap();
+                let expected = tx_range
+                    .map(|id| (id, sf_rw.transaction_by_id(id).unwrap().unwrap().hash()))
+                    .collect::<Vec<_>>();
+                assert_eq!(hashes.collect::<ProviderResult<Vec<_>>>().unwrap(), expected);
+            }
+        }
+    }
+
+    /// Serves the files of `dir` over HTTP until the test process exits.
+    #[cfg(feature = "remote-static-files")]
+    fn serve_dir(dir: std::path::PathBuf) -> String {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut request_line).unwrap();
+                // Skip the remaining request headers
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+
+                let file_name = request_line.split(' ').nth(1).unwrap().trim_start_matches('/');
+                match fs::read(dir.join(file_name)) {
+                    Ok(body) => {
+                        write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len())
+                            .unwrap();
+                        stream.write_all(&body).unwrap();
+                    }
+                    Err(_) => stream
+                        .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                        .unwrap(),
+                }
+            }
+        });
+        url
+    }
+
+    #[test]
+    #[cfg(feature = "remote-static-files")]
+    fn test_remote_static_files() {
+        use sha2::{Digest, Sha256};
+
+        let blocks_per_file = 10;
+        let tip = 24;
+
+        // [ Remote static files with manifest ]
+        let (_remote_tmp, remote_dir) = create_test_static_files_dir();
+        let headers = random_header_range(&mut generators::rng(), 0..tip + 1, B256::random());
+        {
+            let sf_rw = StaticFileProvider::<()>::read_write(&remote_dir)
+                .unwrap()
+                .with_custom_blocks_per_file(blocks_per_file);
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in &headers {
+                writer.append_header(header.header(), U256::ZERO, &header.hash()).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        let mut manifest = String::new();
+        for entry in fs::read_dir(&remote_dir).unwrap() {
+            let file_name = entry.unwrap().file_name().into_string().unwrap();
+            if file_name.starts_with("static_file_") {
+                let checksum = B256::from_slice(&Sha256::digest(
+                    fs::read(remote_dir.join(&file_name)).unwrap(),
+                ));
+                manifest.push_str(&format!("{:x}  {file_name}\n", checksum));
+            }
+        }
+        fs::write(remote_dir.join(REMOTE_MANIFEST_FILE_NAME), manifest).unwrap();
+        let url = serve_dir(remote_dir.clone());
+
+        // [ Local provider without any static files ]
+        let (_local_tmp, local_dir) = create_test_static_files_dir();
+        let remote = RemoteStaticFiles::new(&url).unwrap();
+        let sf_ro = StaticFileProvider::<()>::read_only(&local_dir, false)
+            .unwrap()
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_remote(remote)
+            .unwrap();
+        assert_eq!(sf_ro.get_highest_static_file_block(StaticFileSegment::Headers), Some(tip));
+
+        // Only the accessed jar is downloaded
+        let header = &headers[5];
+        assert_eq!(sf_ro.header_by_number(5).unwrap().as_ref(), Some(header.header()));
+        let filename =
+            |block| StaticFileSegment::Headers.filename(&find_fixed_range(block, blocks_per_file));
+        assert!(local_dir.join(filename(5)).exists());
+        assert!(!local_dir.join(filename(15)).exists());
+
+        // Corrupted remote files are rejected
+        let corrupted = remote_dir.join(filename(15));
+        let mut data = fs::read(&corrupted).unwrap();
+        data[0] ^= 1;
+        fs::write(&corrupted, data).unwrap();
+        assert!(matches!(sf_ro.header_by_number(15), Err(ProviderError::RemoteStaticFile(_))));
+        assert!(!local_dir.join(filename(15)).exists());
+
+        assert_eq!(sf_ro.header_by_number(tip).unwrap().as_ref(), Some(headers[24].header()));
+
+        // The remote can't be configured once the provider is shared
+        let shared = StaticFileProvider::<()>::read_only(&local_dir, false).unwrap();
+        let _reader = shared.clone();
+        assert!(matches!(
+            shared.with_remote(RemoteStaticFiles::new(&url).unwrap()),
+            Err(ProviderError::RemoteStaticFile(_))
+        ));
+    }
 }
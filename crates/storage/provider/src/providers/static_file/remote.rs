@@ -0,0 +1,236 @@
+use alloy_primitives::B256;
+use parking_lot::Mutex;
+use reth_nippy_jar::{NippyJar, CONFIG_FILE_EXTENSION};
+use reth_primitives::{
+    static_file::{SegmentHeader, SegmentRangeInclusive},
+    StaticFileSegment,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use tracing::{debug, info};
+
+/// Name of the checksum manifest expected at the root of a remote static files location.
+///
+/// The manifest uses the `sha256sum` output format, i.e. it can be created with
+/// `sha256sum static_file_* > SHA256SUMS`.
+pub const REMOTE_MANIFEST_FILE_NAME: &str = "SHA256SUMS";
+
+/// Remote location that static files missing on disk are fetched from.
+///
+/// The location is an HTTP(S) base URL, or an `s3://bucket/prefix` URL of a publicly readable S3
+/// bucket, that serves the static files under their regular file names next to a
+/// [`REMOTE_MANIFEST_FILE_NAME`] manifest. Every downloaded file is validated against its
+/// manifest checksum before it is moved into the static files directory.
+#[derive(Debug, Clone)]
+pub struct RemoteStaticFiles {
+    /// Base URL without trailing slash.
+    base_url: String,
+    /// Expected SHA-256 checksums by file name.
+    checksums: Arc<HashMap<String, B256>>,
+    /// Download locks by file name, so concurrent readers don't fetch the same file twice, while
+    /// different files are fetched in parallel.
+    downloads: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
+}
+
+impl RemoteStaticFiles {
+    /// Fetches the manifest of the remote location at `url`.
+    pub fn new(url: &str) -> ProviderResult<Self> {
+        let base_url = match url.strip_prefix("s3://") {
+            Some(path) => {
+                let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+                format!("https://{bucket}.s3.amazonaws.com/{prefix}")
+            }
+            None => url.to_string(),
+        }
+        .trim_end_matches('/')
+        .to_string();
+
+        let manifest_url = format!("{base_url}/{REMOTE_MANIFEST_FILE_NAME}");
+        let manifest = download(&manifest_url, |reader| {
+            let mut manifest = String::new();
+            reader.read_to_string(&mut manifest)?;
+            Ok(manifest)
+        })?;
+        let checksums = parse_manifest(&manifest)?;
+
+        info!(target: "provider::static_file", %base_url, files = checksums.len(), "Loaded remote static files manifest");
+
+        Ok(Self { base_url, checksums: Arc::new(checksums), downloads: Default::default() })
+    }
+
+    /// Returns the base URL of the remote location.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the segment, block range and transaction range of all remote jars whose data is
+    /// not available in `dir`.
+    ///
+    /// Configuration files of those jars are downloaded into `dir`, data files are only fetched
+    /// once the jar is accessed, see [`Self::fetch_jar`].
+    pub(crate) fn missing_jars(
+        &self,
+        dir: &Path,
+    ) -> ProviderResult<
+        Vec<(StaticFileSegment, SegmentRangeInclusive, Option<SegmentRangeInclusive>)>,
+    > {
+        let mut jars = Vec::new();
+        for file_name in self.checksums.keys() {
+            let Some(jar_name) = file_name.strip_suffix(&format!(".{CONFIG_FILE_EXTENSION}"))
+            else {
+                continue
+            };
+            let Some((segment, _)) = StaticFileSegment::parse_filename(jar_name) else { continue };
+
+            let jar_path = dir.join(jar_name);
+            if jar_path.exists() {
+                continue
+            }
+
+            self.fetch(dir, file_name)?;
+            let jar = NippyJar::<SegmentHeader>::load(&jar_path)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            if let Some(block_range) = jar.user_header().block_range().copied() {
+                jars.push((segment, block_range, jar.user_header().tx_range().copied()));
+            }
+        }
+        Ok(jars)
+    }
+
+    /// Downloads all files of the jar at `jar_path` that are available remotely, but missing on
+    /// disk.
+    pub(crate) fn fetch_jar(&self, jar_path: &Path) -> ProviderResult<()> {
+        let (Some(dir), Some(jar_name)) =
+            (jar_path.parent(), jar_path.file_name().and_then(|name| name.to_str()))
+        else {
+            return Ok(())
+        };
+
+        for extension in ["", ".off", ".idx", ".conf"] {
+            let file_name = format!("{jar_name}{extension}");
+            if self.checksums.contains_key(&file_name) {
+                self.fetch(dir, &file_name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Downloads `file_name` into `dir` and validates its checksum, unless the file already
+    /// exists.
+    fn fetch(&self, dir: &Path, file_name: &str) -> ProviderResult<()> {
+        let path = dir.join(file_name);
+        if path.exists() {
+            return Ok(())
+        }
+
+        let file_lock = self.downloads.lock().entry(file_name.to_string()).or_default().clone();
+        let _guard = file_lock.lock();
+        // another reader may have fetched the file while we were waiting
+        if path.exists() {
+            return Ok(())
+        }
+        let expected = self.checksums.get(file_name).ok_or_else(|| {
+            ProviderError::RemoteStaticFile(format!("{file_name} is not in the remote manifest"))
+        })?;
+
+        let url = format!("{}/{file_name}", self.base_url);
+        debug!(target: "provider::static_file", %url, "Downloading static file");
+
+        let tmp_path = dir.join(format!(".{file_name}.download"));
+        let checksum = download(&url, |reader| {
+            let mut file = File::create(&tmp_path)?;
+            let mut hasher = Sha256::new();
+            let mut buf = vec![0; 64 * 1024];
+            loop {
+                let read = reader.read(&mut buf)?;
+                if read == 0 {
+                    break
+                }
+                hasher.update(&buf[..read]);
+                file.write_all(&buf[..read])?;
+            }
+            file.sync_all()?;
+            Ok(B256::from_slice(&hasher.finalize()))
+        })?;
+
+        if checksum != *expected {
+            let _ = reth_fs_util::remove_file(&tmp_path);
+            return Err(ProviderError::RemoteStaticFile(format!(
+                "checksum mismatch for {file_name}: expected {expected}, got {checksum}"
+            )))
+        }
+        reth_fs_util::rename(&tmp_path, &path)
+            .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+
+        info!(target: "provider::static_file", file = file_name, "Downloaded remote static file");
+        Ok(())
+    }
+}
+
+/// Downloads `url` and hands the response body to `f`.
+///
+/// The request runs on a dedicated thread, since static files are accessed synchronously and the
+/// caller may be running inside an async runtime.
+fn download<T: Send>(
+    url: &str,
+    f: impl FnOnce(&mut dyn Read) -> std::io::Result<T> + Send,
+) -> ProviderResult<T> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let mut response = reqwest::blocking::get(url)
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|e| ProviderError::RemoteStaticFile(format!("{url}: {e}")))?;
+                f(&mut response).map_err(|e| ProviderError::RemoteStaticFile(format!("{url}: {e}")))
+            })
+            .join()
+            .map_err(|_| ProviderError::RemoteStaticFile(format!("{url}: download panicked")))?
+    })
+}
+
+/// Parses a `sha256sum` formatted manifest.
+fn parse_manifest(manifest: &str) -> ProviderResult<HashMap<String, B256>> {
+    manifest
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (checksum, file_name) = line.split_once(char::is_whitespace).ok_or_else(|| {
+                ProviderError::RemoteStaticFile(format!("invalid manifest line: {line}"))
+            })?;
+            let checksum = checksum.parse::<B256>().map_err(|_| {
+                ProviderError::RemoteStaticFile(format!("invalid checksum: {line}"))
+            })?;
+            // `sha256sum` marks files read in binary mode with a leading `*`
+            let file_name = file_name.trim().trim_start_matches('*');
+            let file_name =
+                Path::new(file_name).file_name().and_then(|name| name.to_str()).ok_or_else(
+                    || ProviderError::RemoteStaticFile(format!("invalid file name: {line}")),
+                )?;
+            Ok((file_name.to_string(), checksum))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sha256sum_manifest() {
+        let manifest = "\
+4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b  static_file_headers_0_499999
+0000000000000000000000000000000000000000000000000000000000000001 *static_files/static_file_headers_0_499999.conf
+";
+        let checksums = parse_manifest(manifest).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["static_file_headers_0_499999.conf"], B256::with_last_byte(1));
+        assert!(parse_manifest("not-a-checksum file").is_err());
+    }
+}
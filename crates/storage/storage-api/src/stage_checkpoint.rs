@@ -14,6 +14,18 @@ pub trait StageCheckpointReader: Send + Sync {
     /// Reads all stage checkpoints and returns a list with the name of the stage and the checkpoint
     /// data.
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>>;
+
+    /// Returns the block whose state was downloaded by snap sync, or `None` if the state wasn't
+    /// snap synced.
+    ///
+    /// The plain state of a snap synced database only holds the accounts and storage slots that
+    /// changed after this block, the others are only in the hashed state.
+    fn snap_sync_pivot(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(StageId::SnapSync)?
+            .and_then(|checkpoint| checkpoint.snap_sync_stage_checkpoint())
+            .map(|checkpoint| checkpoint.pivot))
+    }
 }
 
 /// The trait for updating stage checkpoint related data.
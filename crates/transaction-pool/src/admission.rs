@@ -0,0 +1,299 @@
+//! A bounded on-disk log of the admission decisions of the pool.
+//!
+//! Every decision is appended as a single line to the log file. Once the file exceeds half of the
+//! configured maximum size it is rotated, which keeps the total size of the log bounded while
+//! retaining the most recent decisions.
+
+use crate::TransactionOrigin;
+use alloy_primitives::TxHash;
+use parking_lot::Mutex;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// The default maximum size of the admission log in megabytes, including the rotated file.
+pub const ADMISSION_LOG_MAX_SIZE_MB_DEFAULT: u64 = 64;
+
+/// Configuration of the [`AdmissionLog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdmissionLogConfig {
+    /// The path of the log file.
+    pub path: PathBuf,
+    /// The maximum size of the log in bytes, including the rotated file.
+    pub max_size: u64,
+}
+
+impl AdmissionLogConfig {
+    /// Creates a new config for a log at the given path with the default maximum size.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), max_size: ADMISSION_LOG_MAX_SIZE_MB_DEFAULT * 1024 * 1024 }
+    }
+
+    /// Sets the maximum size of the log in bytes.
+    pub const fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+/// The outcome of an admission decision.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum AdmissionOutcome {
+    /// The transaction was added to the pool, the reason holds the sub-pool.
+    Accepted,
+    /// The transaction was rejected, the reason holds the error.
+    Rejected,
+    /// The transaction was replaced by another transaction with the same nonce.
+    Replaced,
+    /// The transaction was discarded to enforce the size limits of the pool.
+    Discarded,
+}
+
+impl AdmissionOutcome {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+            Self::Replaced => "replaced",
+            Self::Discarded => "discarded",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "accepted" => Some(Self::Accepted),
+            "rejected" => Some(Self::Rejected),
+            "replaced" => Some(Self::Replaced),
+            "discarded" => Some(Self::Discarded),
+            _ => None,
+        }
+    }
+}
+
+/// A single admission decision of the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AdmissionRecord {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// When the decision was made, in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// Where the transaction originates from.
+    pub origin: TransactionOrigin,
+    /// The outcome of the decision.
+    pub outcome: AdmissionOutcome,
+    /// Why the pool decided this way, if known.
+    pub reason: Option<String>,
+}
+
+impl AdmissionRecord {
+    /// Creates a new record with the current time.
+    pub fn new(
+        tx_hash: TxHash,
+        origin: TransactionOrigin,
+        outcome: AdmissionOutcome,
+        reason: Option<String>,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        Self { tx_hash, timestamp, origin, outcome, reason }
+    }
+
+    /// Encodes the record as a tab separated line.
+    fn to_line(&self) -> String {
+        let origin = match self.origin {
+            TransactionOrigin::Local => "local",
+            TransactionOrigin::External => "external",
+            TransactionOrigin::Private => "private",
+        };
+        // the reason is free text, so make sure it can't break the line format
+        let reason = self.reason.as_deref().unwrap_or_default().replace(['\t', '\n', '\r'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            self.timestamp,
+            self.tx_hash,
+            origin,
+            self.outcome.as_str(),
+            reason
+        )
+    }
+
+    /// Decodes a line written by [`Self::to_line`].
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let tx_hash = fields.next()?.parse().ok()?;
+        let origin = match fields.next()? {
+            "local" => TransactionOrigin::Local,
+            "external" => TransactionOrigin::External,
+            "private" => TransactionOrigin::Private,
+            _ => return None,
+        };
+        let outcome = AdmissionOutcome::from_str(fields.next()?)?;
+        let reason = fields.next().filter(|reason| !reason.is_empty()).map(String::from);
+        Some(Self { tx_hash, timestamp, origin, outcome, reason })
+    }
+}
+
+/// Appends admission decisions to a size bounded log file.
+///
+/// When the log file would grow beyond half of the maximum size, it's moved to `<path>.1`,
+/// replacing the previously rotated file, and a new log file is started.
+#[derive(Debug)]
+pub struct AdmissionLog {
+    path: PathBuf,
+    rotated_path: PathBuf,
+    max_size: u64,
+    writer: Mutex<LogWriter>,
+}
+
+#[derive(Debug)]
+struct LogWriter {
+    file: BufWriter<File>,
+    size: u64,
+}
+
+impl AdmissionLog {
+    /// Opens the log, creating the file and its parent directories if they don't exist.
+    pub fn open(config: AdmissionLogConfig) -> io::Result<Self> {
+        let AdmissionLogConfig { path, max_size } = config;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+
+        let mut rotated_path = path.clone().into_os_string();
+        rotated_path.push(".1");
+
+        Ok(Self {
+            rotated_path: rotated_path.into(),
+            path,
+            max_size,
+            writer: Mutex::new(LogWriter { file: BufWriter::new(file), size }),
+        })
+    }
+
+    /// Appends the records to the log.
+    ///
+    /// Write errors are logged and otherwise ignored, the log must never affect the pool.
+    pub fn record(&self, records: impl IntoIterator<Item = AdmissionRecord>) {
+        let mut writer = self.writer.lock();
+        for record in records {
+            if let Err(err) = self.append(&mut writer, &record) {
+                warn!(target: "txpool", %err, path = ?self.path, "Failed to write to admission log");
+                return
+            }
+        }
+    }
+
+    fn append(&self, writer: &mut LogWriter, record: &AdmissionRecord) -> io::Result<()> {
+        let line = record.to_line();
+        if writer.size > 0 && writer.size + line.len() as u64 > self.max_size / 2 {
+            writer.file.flush()?;
+            fs::rename(&self.path, &self.rotated_path)?;
+            *writer = LogWriter { file: BufWriter::new(open_append(&self.path)?), size: 0 };
+        }
+
+        writer.file.write_all(line.as_bytes())?;
+        writer.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Returns all recorded decisions for the given transaction, oldest first.
+    ///
+    /// This scans the entire log, so it should not be called on a hot path.
+    pub fn history(&self, tx_hash: &TxHash) -> io::Result<Vec<AdmissionRecord>> {
+        self.writer.lock().file.flush()?;
+
+        let needle = tx_hash.to_string();
+        let mut records = Vec::new();
+        for path in [&self.rotated_path, &self.path] {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if !line.contains(&needle) {
+                    continue
+                }
+                // the hash may also appear in the reason of another transaction's record
+                records.extend(
+                    AdmissionRecord::from_line(&line).filter(|record| record.tx_hash == *tx_hash),
+                );
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl Drop for AdmissionLog {
+    fn drop(&mut self) {
+        let _ = self.writer.get_mut().file.flush();
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admission_log_history_and_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("admission.log");
+        let log = AdmissionLog::open(AdmissionLogConfig::new(&path).with_max_size(2_000)).unwrap();
+
+        let tx = TxHash::with_last_byte(1);
+        let other = TxHash::with_last_byte(2);
+        let accepted = AdmissionRecord::new(
+            tx,
+            TransactionOrigin::External,
+            AdmissionOutcome::Accepted,
+            Some("pending".to_string()),
+        );
+        log.record([accepted]);
+        for _ in 0..20 {
+            log.record([AdmissionRecord::new(
+                other,
+                TransactionOrigin::Local,
+                AdmissionOutcome::Rejected,
+                Some("nonce too low:\ttx nonce 1".to_string()),
+            )]);
+        }
+        let replaced =
+            AdmissionRecord::new(tx, TransactionOrigin::External, AdmissionOutcome::Replaced, None);
+        log.record([replaced.clone()]);
+
+        // the first record was rotated out
+        assert_eq!(log.history(&tx).unwrap(), vec![replaced]);
+        let history = log.history(&other).unwrap();
+        assert!(!history.is_empty());
+        assert_eq!(history[0].reason.as_deref(), Some("nonce too low: tx nonce 1"));
+        assert!(
+            fs::metadata(&path).unwrap().len() +
+                fs::metadata(dir.path().join("admission.log.1")).unwrap().len() <=
+                2_000
+        );
+        assert!(log.history(&TxHash::ZERO).unwrap().is_empty());
+
+        // reopening keeps the history
+        drop(log);
+        let log = AdmissionLog::open(AdmissionLogConfig::new(&path)).unwrap();
+        assert_eq!(log.history(&tx).unwrap().len(), 1);
+    }
+}
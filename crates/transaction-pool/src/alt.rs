@@ -0,0 +1,724 @@
+//! Alternative mempools that live alongside the main transaction pool.
+//!
+//! An alternative mempool manages entries the main pool doesn't know about, for example ERC-4337
+//! user operations, and contributes transactions built from them to payloads, for example the
+//! `handleOps` bundle transactions of a bundler. The bundler can either run inside the node and
+//! implement [`AltMempool`] directly, or run externally and submit its bundles through an RPC
+//! extension into a [`BundleAltMempool`].
+//!
+//! [`MevBundlePool`] holds searcher bundles, e.g. submitted via `eth_sendBundle`, and includes the
+//! transactions of each bundle in order at the top of the blocks the bundle targets.
+//!
+//! [`UserOperationPool`] holds validated ERC-4337 user operations, e.g. submitted via
+//! `eth_sendUserOperation`, for a bundler that runs inside the node.
+
+use crate::BestTransactionsAttributes;
+use alloy_consensus::Transaction;
+use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, B256};
+use futures_util::{Stream, StreamExt};
+use parking_lot::RwLock;
+use reth_chain_state::CanonStateNotification;
+use reth_payload_util::{PayloadTransactions, PayloadTransactionsFixed};
+use reth_primitives::TransactionSignedEcRecovered;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque},
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod user_operation;
+pub use user_operation::{
+    maintain_user_operation_pool, PooledUserOperation, UserOperationPool, UserOperationPoolError,
+    DEFAULT_MAX_USER_OPERATIONS, MAX_USER_OPERATIONS_PER_SENDER, USER_OPERATION_PRICE_BUMP,
+};
+
+/// A mempool alongside the main transaction pool that contributes transactions to payloads.
+pub trait AltMempool: Clone + Send + Sync + Unpin + 'static {
+    /// Returns the transactions that should be included in a payload with the given attributes,
+    /// in the order they should be included.
+    ///
+    /// Payload builders include them ahead of the transactions of the main pool: an alternative
+    /// mempool may contribute sequences that must be included contiguously and in order, such as
+    /// bundles, which interleaving them with pool transactions would break. Mempools therefore
+    /// rank their transactions themselves, e.g. by effective tip like [`BundleAltMempool`]. Blob
+    /// transactions are skipped, since their sidecars are only available for transactions of the
+    /// main pool.
+    fn best_transactions(&self, attributes: BestTransactionsAttributes)
+        -> impl PayloadTransactions;
+
+    /// Notifies the mempool about transactions that were included in canonical blocks.
+    fn on_mined_transactions(&self, _hashes: &HashSet<TxHash>) {}
+
+    /// Notifies the mempool about the number of the new canonical tip.
+    fn on_canonical_tip(&self, _number: BlockNumber) {}
+}
+
+impl AltMempool for () {
+    fn best_transactions(
+        &self,
+        _attributes: BestTransactionsAttributes,
+    ) -> impl PayloadTransactions {
+        PayloadTransactionsFixed::<TransactionSignedEcRecovered>::new(Vec::new())
+    }
+}
+
+/// The default maximum number of transactions held by a [`BundleAltMempool`].
+pub const DEFAULT_MAX_ALT_MEMPOOL_TRANSACTIONS: usize = 4096;
+
+/// An [`AltMempool`] that holds submitted transactions until they are mined or removed.
+///
+/// Transactions are included by their effective tip, the transactions of a sender in nonce order.
+///
+/// Once the mempool is full, a new transaction evicts the transaction with the lowest priority fee
+/// if it pays a higher one.
+#[derive(Debug, Clone)]
+pub struct BundleAltMempool {
+    inner: Arc<RwLock<BundleAltMempoolInner>>,
+    max_transactions: usize,
+}
+
+#[derive(Debug, Default)]
+struct BundleAltMempoolInner {
+    /// The transactions by their submission id.
+    transactions: BTreeMap<u64, TransactionSignedEcRecovered>,
+    /// The submission ids of the transactions by their hash.
+    by_hash: HashMap<TxHash, u64>,
+    /// The submission id of the next transaction.
+    next_id: u64,
+}
+
+impl BundleAltMempoolInner {
+    fn remove(&mut self, hash: &TxHash) -> bool {
+        self.by_hash.remove(hash).and_then(|id| self.transactions.remove(&id)).is_some()
+    }
+}
+
+impl Default for BundleAltMempool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ALT_MEMPOOL_TRANSACTIONS)
+    }
+}
+
+impl BundleAltMempool {
+    /// Creates a new mempool that holds up to `max_transactions` transactions.
+    pub fn new(max_transactions: usize) -> Self {
+        Self { inner: Default::default(), max_transactions }
+    }
+
+    /// Adds a transaction that is included in payloads until it's mined or removed.
+    ///
+    /// Returns `false` if the transaction is already known, or if the mempool is full and the
+    /// transaction doesn't pay a higher priority fee than any transaction in it.
+    pub fn add_transaction(&self, transaction: TransactionSignedEcRecovered) -> bool {
+        let mut inner = self.inner.write();
+        if inner.by_hash.contains_key(&transaction.hash()) {
+            return false
+        }
+        if inner.transactions.len() >= self.max_transactions {
+            // the base fee of the blocks the transactions are included in isn't known yet, of the
+            // transactions with the lowest priority fee the last submitted one is evicted
+            let priority_fee =
+                |tx: &TransactionSignedEcRecovered| tx.effective_tip_per_gas(0).unwrap_or_default();
+            let evicted = inner
+                .transactions
+                .values()
+                .rev()
+                .min_by_key(|tx| priority_fee(tx))
+                .filter(|tx| priority_fee(tx) < priority_fee(&transaction))
+                .map(|tx| tx.hash());
+            match evicted {
+                Some(hash) => inner.remove(&hash),
+                None => return false,
+            };
+        }
+        let id = inner.next_id;
+        inner.by_hash.insert(transaction.hash(), id);
+        inner.transactions.insert(id, transaction);
+        inner.next_id += 1;
+        true
+    }
+
+    /// Removes the transaction with the given hash, returns `true` if it was known.
+    pub fn remove_transaction(&self, hash: &TxHash) -> bool {
+        self.inner.write().remove(hash)
+    }
+
+    /// Returns all transactions, in submission order.
+    pub fn transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        self.inner.read().transactions.values().cloned().collect()
+    }
+
+    /// Returns the number of transactions.
+    pub fn len(&self) -> usize {
+        self.inner.read().transactions.len()
+    }
+
+    /// Returns `true` if there are no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().transactions.is_empty()
+    }
+}
+
+impl AltMempool for BundleAltMempool {
+    fn best_transactions(
+        &self,
+        attributes: BestTransactionsAttributes,
+    ) -> impl PayloadTransactions {
+        let base_fee = attributes.basefee;
+
+        // the transactions of each sender in nonce order, ties in submission order
+        let mut by_sender = HashMap::<Address, Vec<_>>::new();
+        for (id, tx) in &self.inner.read().transactions {
+            if tx.max_fee_per_gas() >= base_fee as u128 {
+                by_sender.entry(tx.signer()).or_default().push((*id, tx.clone()));
+            }
+        }
+        let mut queues = by_sender
+            .into_values()
+            .map(|mut txs| {
+                txs.sort_by_key(|(id, tx)| (tx.nonce(), *id));
+                VecDeque::from(txs)
+            })
+            .collect::<Vec<_>>();
+
+        // repeatedly picks the next transaction of the sender whose next transaction pays the
+        // highest effective tip, ties in submission order
+        let head = |queue: &VecDeque<(u64, TransactionSignedEcRecovered)>, idx| {
+            queue.front().map(|(id, tx)| {
+                (tx.effective_tip_per_gas(base_fee).unwrap_or_default(), Reverse(*id), idx)
+            })
+        };
+        let mut heads = queues
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, queue)| head(queue, idx))
+            .collect::<BinaryHeap<_>>();
+        let mut transactions = Vec::new();
+        while let Some((_, _, idx)) = heads.pop() {
+            let (_, tx) = queues[idx].pop_front().expect("queue has a head");
+            transactions.push(tx);
+            heads.extend(head(&queues[idx], idx));
+        }
+        PayloadTransactionsFixed::new(transactions)
+    }
+
+    fn on_mined_transactions(&self, hashes: &HashSet<TxHash>) {
+        let mut inner = self.inner.write();
+        for hash in hashes {
+            inner.remove(hash);
+        }
+    }
+}
+
+/// The default maximum number of bundles held by a [`MevBundlePool`].
+pub const DEFAULT_MAX_MEV_BUNDLES: usize = 1024;
+
+/// The maximum number of blocks past the next block that a bundle added to a [`MevBundlePool`]
+/// may target.
+pub const MAX_MEV_BUNDLE_BLOCKS_AHEAD: u64 = 128;
+
+/// A bundle of transactions that is included in order at the top of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MevBundle {
+    /// The transactions of the bundle, in inclusion order.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// The blocks the bundle may be included in.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// The minimum timestamp, in seconds, at which the bundle may be included.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp, in seconds, at which the bundle may be included.
+    pub max_timestamp: Option<u64>,
+    /// An identifier that replaces an earlier bundle with the same identifier.
+    pub replacement_uuid: Option<String>,
+}
+
+impl MevBundle {
+    /// Returns the hash of the bundle, the keccak256 hash of the concatenated transaction hashes.
+    pub fn hash(&self) -> B256 {
+        let hashes = self.transactions.iter().flat_map(|tx| tx.hash().0).collect::<Vec<_>>();
+        keccak256(hashes)
+    }
+
+    /// Returns the priority fee per gas the bundle pays at the given base fee, averaged over its
+    /// transactions weighted by their gas limits.
+    ///
+    /// This estimates the value of the bundle without simulating it, so payments to the fee
+    /// recipient made by the transactions themselves are not accounted for.
+    pub fn effective_tip_per_gas(&self, base_fee: u64) -> u128 {
+        let (tips, gas) = self.transactions.iter().fold((0u128, 0u128), |(tips, gas), tx| {
+            let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            let gas_limit = tx.gas_limit() as u128;
+            (tips.saturating_add(tip.saturating_mul(gas_limit)), gas + gas_limit)
+        });
+        tips.checked_div(gas).unwrap_or_default()
+    }
+
+    /// Returns `true` if the bundle may be included at the given timestamp.
+    fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.is_none_or(|min| min <= timestamp) &&
+            self.max_timestamp.is_none_or(|max| max >= timestamp)
+    }
+}
+
+/// Errors returned when adding a bundle to a [`MevBundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MevBundlePoolError {
+    /// The bundle has no transactions.
+    #[error("bundle has no transactions")]
+    EmptyBundle,
+    /// All blocks the bundle targets are already mined.
+    #[error("bundle targets already mined blocks")]
+    Outdated,
+    /// The bundle targets blocks too far past the next block.
+    #[error("bundle targets blocks more than {MAX_MEV_BUNDLE_BLOCKS_AHEAD} blocks ahead")]
+    TooFarAhead,
+    /// The bundle is already known.
+    #[error("bundle already known")]
+    AlreadyKnown,
+    /// The pool is full of bundles that rank higher.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// An [`AltMempool`] of searcher bundles.
+///
+/// Each bundle contributes its transactions in order to payloads for the blocks it targets, ahead
+/// of the transactions of the main pool. If a transaction of a bundle can't be included, the
+/// remaining transactions of the bundle are skipped. The transactions of the bundle that were
+/// already included remain in the payload, so callers should simulate bundles before adding them.
+///
+/// The most valuable bundles, by their [effective tip](MevBundle::effective_tip_per_gas), are
+/// included first. A bundle that includes a transaction with the same sender and nonce as a more
+/// valuable bundle conflicts with it and is left out.
+///
+/// Since the timestamp of a payload is not known to the pool, the timestamp bounds of bundles are
+/// checked against the current time.
+///
+/// Bundles may target blocks up to [`MAX_MEV_BUNDLE_BLOCKS_AHEAD`] blocks past the next block.
+/// Once the pool is full, a new bundle evicts the bundle whose first block is the furthest away,
+/// the one with the lowest priority fee of those, if the new bundle ranks higher.
+#[derive(Debug, Clone)]
+pub struct MevBundlePool {
+    inner: Arc<RwLock<MevBundlePoolInner>>,
+    max_bundles: usize,
+}
+
+#[derive(Debug, Default)]
+struct MevBundlePoolInner {
+    /// The bundles, in submission order.
+    bundles: Vec<(B256, MevBundle)>,
+    /// The number of the next block to build.
+    next_block: BlockNumber,
+}
+
+impl Default for MevBundlePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_MEV_BUNDLES)
+    }
+}
+
+impl MevBundlePool {
+    /// Creates a new pool that holds up to `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self { inner: Default::default(), max_bundles }
+    }
+
+    /// Adds a bundle and returns its hash.
+    ///
+    /// A bundle with a replacement identifier replaces the bundle with the same identifier.
+    pub fn add_bundle(&self, bundle: MevBundle) -> Result<B256, MevBundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(MevBundlePoolError::EmptyBundle)
+        }
+
+        let hash = bundle.hash();
+        let mut inner = self.inner.write();
+        if *bundle.block_range.end() < inner.next_block {
+            return Err(MevBundlePoolError::Outdated)
+        }
+        if *bundle.block_range.end() > inner.next_block.saturating_add(MAX_MEV_BUNDLE_BLOCKS_AHEAD)
+        {
+            return Err(MevBundlePoolError::TooFarAhead)
+        }
+        if inner.bundles.iter().any(|(known, _)| *known == hash) {
+            return Err(MevBundlePoolError::AlreadyKnown)
+        }
+        if let Some(uuid) = &bundle.replacement_uuid {
+            inner.bundles.retain(|(_, known)| known.replacement_uuid.as_ref() != Some(uuid));
+        }
+        if inner.bundles.len() >= self.max_bundles {
+            // the base fee of the targeted blocks isn't known yet, so bundles are valued by their
+            // priority fee
+            let next_block = inner.next_block;
+            let rank = |bundle: &MevBundle| {
+                (
+                    Reverse((*bundle.block_range.start()).max(next_block)),
+                    bundle.effective_tip_per_gas(0),
+                )
+            };
+            let evicted = inner
+                .bundles
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, known))| rank(known))
+                .filter(|(_, (_, known))| rank(known) < rank(&bundle))
+                .map(|(idx, _)| idx);
+            match evicted {
+                Some(idx) => inner.bundles.remove(idx),
+                None => return Err(MevBundlePoolError::PoolFull),
+            };
+        }
+        inner.bundles.push((hash, bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given hash, returns `true` if it was known.
+    pub fn remove_bundle(&self, hash: &B256) -> bool {
+        let mut inner = self.inner.write();
+        let len = inner.bundles.len();
+        inner.bundles.retain(|(known, _)| known != hash);
+        inner.bundles.len() != len
+    }
+
+    /// Returns all bundles, in submission order.
+    pub fn bundles(&self) -> Vec<MevBundle> {
+        self.inner.read().bundles.iter().map(|(_, bundle)| bundle.clone()).collect()
+    }
+
+    /// Returns the number of bundles.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if there are no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+
+    /// Returns the transactions of the bundles for the next block that pay the base fee and are
+    /// valid at the given timestamp, the most valuable bundles first.
+    fn best_bundles(
+        &self,
+        attributes: BestTransactionsAttributes,
+        timestamp: u64,
+    ) -> MevBundleTransactions {
+        let inner = self.inner.read();
+        let mut bundles = inner
+            .bundles
+            .iter()
+            .map(|(_, bundle)| bundle)
+            .filter(|bundle| {
+                bundle.block_range.contains(&inner.next_block) &&
+                    bundle.is_valid_at(timestamp) &&
+                    bundle
+                        .transactions
+                        .iter()
+                        .all(|tx| tx.max_fee_per_gas() >= attributes.basefee as u128)
+            })
+            .collect::<Vec<_>>();
+
+        // bundles with the same value remain in submission order
+        bundles
+            .sort_by_cached_key(|bundle| Reverse(bundle.effective_tip_per_gas(attributes.basefee)));
+
+        // only one of the bundles that include a transaction of the same sender and nonce can be
+        // included, which is the more valuable one
+        let mut nonces = HashSet::new();
+        let bundles = bundles
+            .into_iter()
+            .filter(|bundle| {
+                let conflicts = bundle
+                    .transactions
+                    .iter()
+                    .any(|tx| nonces.contains(&(tx.signer(), tx.nonce())));
+                if !conflicts {
+                    nonces.extend(bundle.transactions.iter().map(|tx| (tx.signer(), tx.nonce())));
+                }
+                !conflicts
+            })
+            .map(|bundle| bundle.transactions.clone());
+        MevBundleTransactions::new(bundles)
+    }
+}
+
+impl AltMempool for MevBundlePool {
+    fn best_transactions(
+        &self,
+        attributes: BestTransactionsAttributes,
+    ) -> impl PayloadTransactions {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.best_bundles(attributes, now)
+    }
+
+    fn on_mined_transactions(&self, hashes: &HashSet<TxHash>) {
+        self.inner.write().bundles.retain(|(_, bundle)| {
+            !bundle.transactions.iter().any(|tx| hashes.contains(&tx.hash()))
+        });
+    }
+
+    fn on_canonical_tip(&self, number: BlockNumber) {
+        let mut inner = self.inner.write();
+        inner.next_block = number + 1;
+        inner.bundles.retain(|(_, bundle)| *bundle.block_range.end() > number);
+    }
+}
+
+/// The transactions of the bundles of a [`MevBundlePool`] for a payload.
+///
+/// Marking a transaction as invalid skips the remaining transactions of its bundle.
+#[derive(Debug)]
+pub struct MevBundleTransactions {
+    /// The transactions with the index of their bundle.
+    transactions: VecDeque<(usize, TransactionSignedEcRecovered)>,
+    /// The bundle of the last returned transaction.
+    current_bundle: Option<usize>,
+}
+
+impl MevBundleTransactions {
+    fn new(bundles: impl IntoIterator<Item = Vec<TransactionSignedEcRecovered>>) -> Self {
+        let transactions = bundles
+            .into_iter()
+            .enumerate()
+            .flat_map(|(idx, transactions)| transactions.into_iter().map(move |tx| (idx, tx)))
+            .collect();
+        Self { transactions, current_bundle: None }
+    }
+}
+
+impl PayloadTransactions for MevBundleTransactions {
+    fn next(&mut self, _ctx: ()) -> Option<TransactionSignedEcRecovered> {
+        let (bundle, tx) = self.transactions.pop_front()?;
+        self.current_bundle = Some(bundle);
+        Some(tx)
+    }
+
+    fn mark_invalid(&mut self, _sender: Address, _nonce: u64) {
+        if let Some(bundle) = self.current_bundle.take() {
+            while self.transactions.front().is_some_and(|(idx, _)| *idx == bundle) {
+                self.transactions.pop_front();
+            }
+        }
+    }
+}
+
+/// Notifies the [`AltMempool`] about the transactions and the tip of every new canonical chain.
+pub async fn maintain_alt_mempool<A, St>(alt_mempool: A, mut events: St)
+where
+    A: AltMempool,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        let mined = event
+            .committed()
+            .blocks_iter()
+            .flat_map(|block| block.body.transactions().map(|tx| tx.hash()))
+            .collect::<HashSet<_>>();
+        alt_mempool.on_mined_transactions(&mined);
+        alt_mempool.on_canonical_tip(event.tip().number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Transaction as _;
+    use alloy_primitives::{Address, PrimitiveSignature as Signature};
+    use reth_primitives::{Transaction, TransactionSigned};
+
+    fn transaction(nonce: u64, max_fee_per_gas: u128) -> TransactionSignedEcRecovered {
+        transaction_from(1, nonce, max_fee_per_gas)
+    }
+
+    fn transaction_from(
+        sender: u8,
+        nonce: u64,
+        max_fee_per_gas: u128,
+    ) -> TransactionSignedEcRecovered {
+        let tx = Transaction::Eip1559(alloy_consensus::TxEip1559 {
+            nonce,
+            max_fee_per_gas,
+            max_priority_fee_per_gas: max_fee_per_gas,
+            gas_limit: 100_000,
+            ..Default::default()
+        });
+        TransactionSigned::from_transaction_and_signature(tx, Signature::test_signature())
+            .with_signer(Address::with_last_byte(sender))
+    }
+
+    #[test]
+    fn bundle_alt_mempool() {
+        let alt_mempool = BundleAltMempool::default();
+        let cheap = transaction(0, 5);
+        let expensive = transaction(1, 20);
+        assert!(alt_mempool.add_transaction(cheap.clone()));
+        assert!(alt_mempool.add_transaction(expensive.clone()));
+        assert!(!alt_mempool.add_transaction(expensive.clone()));
+        assert_eq!(alt_mempool.len(), 2);
+
+        // only transactions that pay the base fee are included
+        let mut best = alt_mempool.best_transactions(BestTransactionsAttributes::base_fee(10));
+        assert_eq!(best.next(()).map(|tx| tx.hash()), Some(expensive.hash()));
+        assert!(best.next(()).is_none());
+
+        alt_mempool.on_mined_transactions(&HashSet::from([expensive.hash()]));
+        assert_eq!(alt_mempool.transactions(), vec![cheap.clone()]);
+
+        assert!(alt_mempool.remove_transaction(&cheap.hash()));
+        assert!(!alt_mempool.remove_transaction(&cheap.hash()));
+        assert!(alt_mempool.is_empty());
+    }
+
+    #[test]
+    fn bundle_alt_mempool_orders_by_tip() {
+        let alt_mempool = BundleAltMempool::default();
+        let low_first = transaction_from(1, 0, 12);
+        let high_second = transaction_from(1, 1, 40);
+        let mid = transaction_from(2, 0, 20);
+        let other_mid = transaction_from(3, 5, 20);
+        for tx in [&high_second, &low_first, &mid, &other_mid] {
+            assert!(alt_mempool.add_transaction(tx.clone()));
+        }
+
+        // the second transaction of sender 1 waits for its first, equal tips in submission order
+        let mut best = alt_mempool.best_transactions(BestTransactionsAttributes::base_fee(10));
+        let order = std::iter::from_fn(|| best.next(())).map(|tx| tx.hash()).collect::<Vec<_>>();
+        assert_eq!(order, vec![mid.hash(), other_mid.hash(), low_first.hash(), high_second.hash()]);
+    }
+
+    #[test]
+    fn bundle_alt_mempool_evicts_lowest_tip() {
+        let alt_mempool = BundleAltMempool::new(2);
+        let cheap = transaction_from(1, 0, 5);
+        let other_cheap = transaction_from(2, 1, 5);
+        assert!(alt_mempool.add_transaction(cheap.clone()));
+        assert!(alt_mempool.add_transaction(other_cheap));
+
+        // a transaction that doesn't pay more is rejected, otherwise the last submitted of the
+        // cheapest transactions is evicted
+        assert!(!alt_mempool.add_transaction(transaction_from(3, 2, 5)));
+        let expensive = transaction_from(3, 2, 20);
+        assert!(alt_mempool.add_transaction(expensive.clone()));
+        assert_eq!(alt_mempool.transactions(), vec![cheap, expensive]);
+    }
+
+    #[test]
+    fn mev_bundle_pool() {
+        let pool = MevBundlePool::new(2);
+        pool.on_canonical_tip(9);
+        let bundle = |nonces: &[u64], block_range| MevBundle {
+            transactions: nonces.iter().map(|nonce| transaction(*nonce, 20)).collect(),
+            block_range,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        };
+
+        assert_eq!(pool.add_bundle(bundle(&[], 10..=10)), Err(MevBundlePoolError::EmptyBundle));
+        assert_eq!(pool.add_bundle(bundle(&[0], 5..=9)), Err(MevBundlePoolError::Outdated));
+        assert_eq!(
+            pool.add_bundle(bundle(&[0], 10..=11 + MAX_MEV_BUNDLE_BLOCKS_AHEAD)),
+            Err(MevBundlePoolError::TooFarAhead)
+        );
+        let first = pool.add_bundle(bundle(&[0, 1], 10..=10)).unwrap();
+        assert_eq!(
+            pool.add_bundle(bundle(&[0, 1], 10..=11)),
+            Err(MevBundlePoolError::AlreadyKnown)
+        );
+        pool.add_bundle(bundle(&[2, 3], 10..=11)).unwrap();
+        assert_eq!(pool.add_bundle(bundle(&[4], 10..=11)), Err(MevBundlePoolError::PoolFull));
+
+        // the remaining transactions of a bundle are skipped if one can't be included
+        let mut best = pool.best_bundles(BestTransactionsAttributes::base_fee(10), 0);
+        let first_tx = best.next(()).unwrap();
+        assert_eq!(first_tx.nonce(), 0);
+        best.mark_invalid(first_tx.signer(), first_tx.nonce());
+        assert_eq!(best.next(()).map(|tx| tx.nonce()), Some(2));
+        assert_eq!(best.next(()).map(|tx| tx.nonce()), Some(3));
+        assert!(best.next(()).is_none());
+
+        // bundles are removed once the blocks they target are mined
+        pool.on_canonical_tip(10);
+        assert_eq!(pool.len(), 1);
+        assert!(!pool.remove_bundle(&first));
+
+        // a bundle replaces the bundle with the same replacement identifier
+        let mut replaceable = bundle(&[5], 11..=11);
+        replaceable.replacement_uuid = Some("uuid".to_string());
+        pool.add_bundle(replaceable.clone()).unwrap();
+        replaceable.transactions = vec![transaction(6, 20)];
+        let replacement = pool.add_bundle(replaceable).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(pool.remove_bundle(&replacement));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn mev_bundle_pool_evicts_furthest_and_least_valuable() {
+        let pool = MevBundlePool::new(2);
+        pool.on_canonical_tip(9);
+        let bundle = |nonce, max_fee_per_gas, block| MevBundle {
+            transactions: vec![transaction(nonce, max_fee_per_gas)],
+            block_range: block..=block,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        };
+
+        let next = pool.add_bundle(bundle(0, 20, 10)).unwrap();
+        let far = pool.add_bundle(bundle(1, 50, 12)).unwrap();
+
+        // a bundle for the next block evicts the bundle for a later block
+        let cheap_next = pool.add_bundle(bundle(2, 5, 10)).unwrap();
+        assert!(!pool.remove_bundle(&far));
+
+        // of the bundles for the same block, the one with the lowest priority fee is evicted
+        assert_eq!(pool.add_bundle(bundle(3, 5, 10)), Err(MevBundlePoolError::PoolFull));
+        assert_eq!(pool.add_bundle(bundle(3, 50, 11)), Err(MevBundlePoolError::PoolFull));
+        pool.add_bundle(bundle(3, 10, 10)).unwrap();
+        assert!(!pool.remove_bundle(&cheap_next));
+        assert!(pool.remove_bundle(&next));
+    }
+
+    #[test]
+    fn mev_bundle_pool_orders_by_value() {
+        let pool = MevBundlePool::default();
+        let bundle = |txs: &[(u64, u128)]| MevBundle {
+            transactions: txs
+                .iter()
+                .map(|(nonce, tip)| {
+                    let tx = Transaction::Eip1559(alloy_consensus::TxEip1559 {
+                        nonce: *nonce,
+                        max_fee_per_gas: 100,
+                        max_priority_fee_per_gas: *tip,
+                        gas_limit: 100_000,
+                        ..Default::default()
+                    });
+                    TransactionSigned::from_transaction_and_signature(
+                        tx,
+                        Signature::test_signature(),
+                    )
+                    .with_signer(Address::with_last_byte(1))
+                })
+                .collect(),
+            block_range: 0..=10,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        };
+
+        let cheap = bundle(&[(0, 1)]);
+        let valuable = bundle(&[(1, 2), (2, 8)]);
+        // conflicts with the more valuable bundle
+        let conflicting = bundle(&[(2, 3)]);
+        assert_eq!(valuable.effective_tip_per_gas(10), 5);
+        pool.add_bundle(cheap).unwrap();
+        pool.add_bundle(conflicting).unwrap();
+        pool.add_bundle(valuable).unwrap();
+
+        let mut best = pool.best_bundles(BestTransactionsAttributes::base_fee(10), 0);
+        let nonces = std::iter::from_fn(|| best.next(()).map(|tx| tx.nonce())).collect::<Vec<_>>();
+        assert_eq!(nonces, vec![1, 2, 0]);
+    }
+}
@@ -0,0 +1,369 @@
+//! A pool of ERC-4337 user operations.
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_rpc_types_eth::erc4337::SendUserOperation;
+use futures_util::{Stream, StreamExt};
+use parking_lot::RwLock;
+use reth_chain_state::CanonStateNotification;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+/// The default maximum number of user operations held by a [`UserOperationPool`].
+pub const DEFAULT_MAX_USER_OPERATIONS: usize = 4096;
+
+/// The maximum number of user operations of a single sender held by a [`UserOperationPool`].
+pub const MAX_USER_OPERATIONS_PER_SENDER: usize = 4;
+
+/// The minimum price bump, in percent, to replace a user operation with the same sender and nonce.
+pub const USER_OPERATION_PRICE_BUMP: u64 = 10;
+
+/// The signature of the event the entry point emits for every executed user operation.
+const USER_OPERATION_EVENT: &str =
+    "UserOperationEvent(bytes32,address,address,uint256,bool,uint256,uint256)";
+
+/// A validated user operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PooledUserOperation {
+    /// The hash of the operation, as computed by the entry point.
+    pub hash: B256,
+    /// The entry point the operation was submitted to.
+    pub entry_point: Address,
+    /// The operation.
+    pub operation: SendUserOperation,
+}
+
+impl PooledUserOperation {
+    /// Returns the smart contract account of the operation.
+    pub const fn sender(&self) -> Address {
+        match &self.operation {
+            SendUserOperation::EntryPointV06(op) => op.sender,
+            SendUserOperation::EntryPointV07(op) => op.sender,
+        }
+    }
+
+    /// Returns the nonce of the operation.
+    pub const fn nonce(&self) -> U256 {
+        match &self.operation {
+            SendUserOperation::EntryPointV06(op) => op.nonce,
+            SendUserOperation::EntryPointV07(op) => op.nonce,
+        }
+    }
+
+    /// Returns the maximum fee per gas of the operation.
+    pub const fn max_fee_per_gas(&self) -> U256 {
+        match &self.operation {
+            SendUserOperation::EntryPointV06(op) => op.max_fee_per_gas,
+            SendUserOperation::EntryPointV07(op) => op.max_fee_per_gas,
+        }
+    }
+
+    /// Returns the maximum priority fee per gas of the operation.
+    pub const fn max_priority_fee_per_gas(&self) -> U256 {
+        match &self.operation {
+            SendUserOperation::EntryPointV06(op) => op.max_priority_fee_per_gas,
+            SendUserOperation::EntryPointV07(op) => op.max_priority_fee_per_gas,
+        }
+    }
+
+    /// Returns the priority fee per gas the operation pays at the given base fee, or `None` if it
+    /// doesn't pay the base fee.
+    pub fn effective_tip_per_gas(&self, base_fee: u64) -> Option<U256> {
+        let tip = self.max_fee_per_gas().checked_sub(U256::from(base_fee))?;
+        Some(tip.min(self.max_priority_fee_per_gas()))
+    }
+
+    /// Returns `true` if both fees of the operation exceed the fees of the given operation by at
+    /// least [`USER_OPERATION_PRICE_BUMP`] percent.
+    fn is_replacement_for(&self, other: &Self) -> bool {
+        let bumped =
+            |fee: U256| fee * U256::from(100 + USER_OPERATION_PRICE_BUMP) / U256::from(100);
+        self.max_fee_per_gas() >= bumped(other.max_fee_per_gas()) &&
+            self.max_priority_fee_per_gas() >= bumped(other.max_priority_fee_per_gas())
+    }
+}
+
+/// Errors returned when adding a user operation to a [`UserOperationPool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UserOperationPoolError {
+    /// The operation is already known.
+    #[error("user operation already known")]
+    AlreadyKnown,
+    /// An operation with the same sender and nonce pays more than the operation.
+    #[error("replacement user operation underpriced")]
+    ReplacementUnderpriced,
+    /// The sender already has [`MAX_USER_OPERATIONS_PER_SENDER`] operations in the pool.
+    #[error("too many user operations of sender {0}")]
+    SenderLimitExceeded(Address),
+    /// The pool is full.
+    #[error("user operation pool is full")]
+    PoolFull,
+}
+
+/// A pool of validated ERC-4337 user operations, the mempool of a bundler.
+///
+/// The pool doesn't validate the operations, this is up to the caller, e.g. the `eth_` user
+/// operation RPC methods. A bundler takes the operations to include from
+/// [`UserOperationPool::best_user_operations`] and [`maintain_user_operation_pool`] removes the
+/// operations once the entry point executed them.
+#[derive(Debug, Clone)]
+pub struct UserOperationPool {
+    inner: Arc<RwLock<UserOperationPoolInner>>,
+    max_operations: usize,
+}
+
+#[derive(Debug, Default)]
+struct UserOperationPoolInner {
+    /// The operations by entry point, sender and nonce.
+    operations: BTreeMap<(Address, Address, U256), PooledUserOperation>,
+    /// The keys of the operations by hash.
+    by_hash: HashMap<B256, (Address, Address, U256)>,
+}
+
+impl UserOperationPoolInner {
+    fn remove(&mut self, hash: &B256) -> Option<PooledUserOperation> {
+        let key = self.by_hash.remove(hash)?;
+        self.operations.remove(&key)
+    }
+}
+
+impl Default for UserOperationPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_USER_OPERATIONS)
+    }
+}
+
+impl UserOperationPool {
+    /// Creates a new pool that holds up to `max_operations` user operations.
+    pub fn new(max_operations: usize) -> Self {
+        Self { inner: Default::default(), max_operations }
+    }
+
+    /// Adds a validated user operation and returns its hash.
+    ///
+    /// The operation replaces an operation of the same sender and nonce if it pays at least
+    /// [`USER_OPERATION_PRICE_BUMP`] percent more.
+    pub fn add_user_operation(
+        &self,
+        operation: PooledUserOperation,
+    ) -> Result<B256, UserOperationPoolError> {
+        let hash = operation.hash;
+        let key = (operation.entry_point, operation.sender(), operation.nonce());
+        let mut inner = self.inner.write();
+        if inner.by_hash.contains_key(&hash) {
+            return Err(UserOperationPoolError::AlreadyKnown)
+        }
+
+        if let Some(replaced) = inner.operations.get(&key) {
+            if !operation.is_replacement_for(replaced) {
+                return Err(UserOperationPoolError::ReplacementUnderpriced)
+            }
+            let replaced = replaced.hash;
+            inner.by_hash.remove(&replaced);
+        } else {
+            let (entry_point, sender, _) = key;
+            let sender_operations = inner
+                .operations
+                .range((entry_point, sender, U256::ZERO)..=(entry_point, sender, U256::MAX))
+                .count();
+            if sender_operations >= MAX_USER_OPERATIONS_PER_SENDER {
+                return Err(UserOperationPoolError::SenderLimitExceeded(sender))
+            }
+            if inner.operations.len() >= self.max_operations {
+                return Err(UserOperationPoolError::PoolFull)
+            }
+        }
+
+        inner.by_hash.insert(hash, key);
+        inner.operations.insert(key, operation);
+        Ok(hash)
+    }
+
+    /// Removes the user operation with the given hash, returns `true` if it was known.
+    pub fn remove_user_operation(&self, hash: &B256) -> bool {
+        self.inner.write().remove(hash).is_some()
+    }
+
+    /// Returns the user operation with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<PooledUserOperation> {
+        let inner = self.inner.read();
+        inner.by_hash.get(hash).and_then(|key| inner.operations.get(key)).cloned()
+    }
+
+    /// Returns all user operations of the entry point, ordered by sender and nonce.
+    pub fn user_operations(&self, entry_point: Address) -> Vec<PooledUserOperation> {
+        self.inner
+            .read()
+            .operations
+            .range(
+                (entry_point, Address::ZERO, U256::ZERO)..=
+                    (entry_point, Address::repeat_byte(0xff), U256::MAX),
+            )
+            .map(|(_, operation)| operation.clone())
+            .collect()
+    }
+
+    /// Returns the user operations of the entry point that pay the given base fee, in the order a
+    /// bundler should include them.
+    ///
+    /// Senders are ordered by the priority fee of their operation with the lowest nonce, the
+    /// operations of a sender are ordered by nonce. The operations of a sender that follow an
+    /// operation that doesn't pay the base fee are skipped.
+    pub fn best_user_operations(
+        &self,
+        entry_point: Address,
+        base_fee: u64,
+    ) -> Vec<PooledUserOperation> {
+        let operations = self.user_operations(entry_point);
+        let mut by_sender = operations
+            .chunk_by(|a, b| a.sender() == b.sender())
+            .filter_map(|operations| {
+                // an operation can only be included after the operations with lower nonces
+                let operations = operations
+                    .iter()
+                    .take_while(|operation| operation.effective_tip_per_gas(base_fee).is_some())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let tip = operations.first()?.effective_tip_per_gas(base_fee)?;
+                Some((tip, operations))
+            })
+            .collect::<Vec<_>>();
+        by_sender.sort_by(|(a, _), (b, _)| b.cmp(a));
+        by_sender.into_iter().flat_map(|(_, operations)| operations).collect()
+    }
+
+    /// Returns the number of user operations.
+    pub fn len(&self) -> usize {
+        self.inner.read().operations.len()
+    }
+
+    /// Returns `true` if there are no user operations.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().operations.is_empty()
+    }
+
+    /// Removes the user operations that were executed by their entry point.
+    pub fn on_executed_user_operations(&self, executed: &HashSet<(Address, B256)>) {
+        let mut inner = self.inner.write();
+        for (entry_point, hash) in executed {
+            if inner.by_hash.get(hash).is_some_and(|(known, _, _)| known == entry_point) {
+                inner.remove(hash);
+            }
+        }
+    }
+}
+
+/// Removes the user operations of every new canonical chain from the [`UserOperationPool`], based
+/// on the `UserOperationEvent`s their entry points emitted.
+pub async fn maintain_user_operation_pool<St>(pool: UserOperationPool, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    let event_signature = keccak256(USER_OPERATION_EVENT);
+    while let Some(event) = events.next().await {
+        let chain = event.committed();
+        let executed = chain
+            .blocks_iter()
+            .filter_map(|block| chain.execution_outcome().logs(block.number))
+            .flatten()
+            .filter_map(|log| match log.topics() {
+                [signature, hash, ..] if *signature == event_signature => {
+                    Some((log.address, *hash))
+                }
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        if !executed.is_empty() {
+            pool.on_executed_user_operations(&executed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+
+    fn operation(sender: u8, nonce: u64, max_priority_fee_per_gas: u64) -> PooledUserOperation {
+        let operation = PackedUserOperation {
+            sender: Address::with_last_byte(sender),
+            nonce: U256::from(nonce),
+            factory: None,
+            factory_data: None,
+            call_data: Default::default(),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(100_000),
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: U256::from(100),
+            max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Default::default(),
+        };
+        PooledUserOperation {
+            hash: keccak256([sender, nonce as u8, max_priority_fee_per_gas as u8]),
+            entry_point: Address::with_last_byte(0xee),
+            operation: SendUserOperation::EntryPointV07(operation),
+        }
+    }
+
+    #[test]
+    fn user_operation_pool() {
+        let pool = UserOperationPool::new(6);
+        let entry_point = Address::with_last_byte(0xee);
+
+        let first = pool.add_user_operation(operation(1, 0, 1)).unwrap();
+        assert_eq!(
+            pool.add_user_operation(operation(1, 0, 1)),
+            Err(UserOperationPoolError::AlreadyKnown)
+        );
+        assert_eq!(
+            pool.add_user_operation(operation(1, 0, 2)),
+            Err(UserOperationPoolError::ReplacementUnderpriced)
+        );
+        for nonce in 1..MAX_USER_OPERATIONS_PER_SENDER as u64 {
+            pool.add_user_operation(operation(1, nonce, 1)).unwrap();
+        }
+        assert_eq!(
+            pool.add_user_operation(operation(1, 10, 1)),
+            Err(UserOperationPoolError::SenderLimitExceeded(Address::with_last_byte(1)))
+        );
+
+        // a replacement with a bumped fee is always accepted
+        let mut replacement = operation(1, 0, 10);
+        if let SendUserOperation::EntryPointV07(op) = &mut replacement.operation {
+            op.max_fee_per_gas = U256::from(110);
+        }
+        let replacement = pool.add_user_operation(replacement).unwrap();
+        assert!(pool.get(&first).is_none());
+
+        pool.add_user_operation(operation(2, 0, 5)).unwrap();
+        pool.add_user_operation(operation(3, 0, 20)).unwrap();
+        assert_eq!(
+            pool.add_user_operation(operation(4, 0, 1)),
+            Err(UserOperationPoolError::PoolFull)
+        );
+
+        // senders are ordered by the tip of their first operation, operations of a sender by nonce
+        let best = pool
+            .best_user_operations(entry_point, 90)
+            .iter()
+            .map(|op| (op.sender().0[19], op.nonce().to::<u64>()))
+            .collect::<Vec<_>>();
+        assert_eq!(best, [(1, 0), (1, 1), (1, 2), (1, 3), (3, 0), (2, 0)]);
+
+        // operations that don't pay the base fee are skipped, including the following operations
+        // of the same sender
+        assert_eq!(pool.best_user_operations(entry_point, 105).len(), 1);
+
+        // executed operations are removed, but only if they were executed by their entry point
+        pool.on_executed_user_operations(&HashSet::from([(Address::ZERO, replacement)]));
+        assert!(pool.get(&replacement).is_some());
+        pool.on_executed_user_operations(&HashSet::from([(entry_point, replacement)]));
+        assert!(pool.get(&replacement).is_none());
+        assert_eq!(pool.len(), 5);
+    }
+}
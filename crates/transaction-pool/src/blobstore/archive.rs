@@ -0,0 +1,326 @@
+//! An on-disk archive of the blob sidecars of finalized blocks.
+//!
+//! The pool only keeps blob sidecars until the transactions are finalized, but the consensus layer
+//! and RPC consumers may request them for the whole data availability window. The archive keeps the
+//! sidecars of finalized blocks for a configurable number of blocks and prunes older blocks.
+//!
+//! Every block is stored in its own directory, named after the block number, which contains one
+//! file per transaction with the RLP encoded sidecar, named after the transaction hash, and an
+//! index file with the versioned hashes of all transactions of the block.
+
+use crate::blobstore::BlobStoreError;
+use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
+use alloy_primitives::{BlockNumber, TxHash, B256};
+use parking_lot::RwLock;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::debug;
+
+/// The default number of blocks the blob sidecars are retained for.
+///
+/// This covers the `MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS` (4096 epochs of 32 slots, ~18 days) of
+/// the consensus layer.
+pub const DEFAULT_BLOB_RETENTION_BLOCKS: u64 = 4096 * 32;
+
+/// The name of the index file of a block.
+const INDEX_FILE: &str = "index";
+
+/// Configuration of the [`BlobArchive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobArchiveConfig {
+    /// The directory of the archive.
+    pub path: PathBuf,
+    /// The number of blocks below the finalized block the sidecars are retained for.
+    pub retention_blocks: u64,
+}
+
+impl BlobArchiveConfig {
+    /// Creates a new config for an archive in the given directory with the default retention.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), retention_blocks: DEFAULT_BLOB_RETENTION_BLOCKS }
+    }
+
+    /// Sets the number of blocks the sidecars are retained for.
+    pub const fn with_retention_blocks(mut self, retention_blocks: u64) -> Self {
+        self.retention_blocks = retention_blocks;
+        self
+    }
+}
+
+/// Keeps the blob sidecars of finalized blocks on disk for the configured retention window.
+#[derive(Clone, Debug)]
+pub struct BlobArchive {
+    inner: Arc<BlobArchiveInner>,
+}
+
+impl BlobArchive {
+    /// Opens the archive, creating its directory if it doesn't exist yet.
+    ///
+    /// This reads the index files of all archived blocks.
+    pub fn open(config: BlobArchiveConfig) -> Result<Self, BlobStoreError> {
+        fs::create_dir_all(&config.path).map_err(|err| BlobStoreError::Other(Box::new(err)))?;
+
+        let mut index = ArchiveIndex::default();
+        for entry in
+            fs::read_dir(&config.path).map_err(|err| BlobStoreError::Other(Box::new(err)))?
+        {
+            let entry = entry.map_err(|err| BlobStoreError::Other(Box::new(err)))?;
+            let Some(block) = entry.file_name().to_str().and_then(|name| name.parse().ok()) else {
+                continue
+            };
+            match fs::read(entry.path().join(INDEX_FILE)) {
+                Ok(data) => index.insert(block, decode_index(&data)?),
+                // the block was not completely written, it's removed on the next prune
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    index.blocks.insert(block, Vec::new());
+                }
+                Err(err) => return Err(BlobStoreError::Other(Box::new(err))),
+            }
+        }
+        debug!(
+            target: "txpool::blob",
+            blocks = index.blocks.len(),
+            path = ?config.path,
+            "Opened blob archive"
+        );
+
+        Ok(Self { inner: Arc::new(BlobArchiveInner { config, index: RwLock::new(index) }) })
+    }
+
+    /// Returns the configuration of the archive.
+    pub fn config(&self) -> &BlobArchiveConfig {
+        &self.inner.config
+    }
+
+    /// Archives the sidecars of the blob transactions of the given block.
+    ///
+    /// This replaces any sidecars that were previously archived for the block.
+    pub fn insert_block(
+        &self,
+        block: BlockNumber,
+        sidecars: Vec<(TxHash, Arc<BlobTransactionSidecar>)>,
+    ) -> Result<(), BlobStoreError> {
+        self.remove_block(block)?;
+
+        let dir = self.block_dir(block);
+        fs::create_dir_all(&dir).map_err(|err| BlobStoreError::Other(Box::new(err)))?;
+        let mut entries = Vec::with_capacity(sidecars.len());
+        for (tx, sidecar) in sidecars {
+            let mut buf = Vec::with_capacity(sidecar.rlp_encoded_fields_length());
+            sidecar.rlp_encode_fields(&mut buf);
+            fs::write(dir.join(format!("{tx:x}")), buf)
+                .map_err(|err| BlobStoreError::Other(Box::new(err)))?;
+            entries.push((tx, sidecar.versioned_hashes().collect()));
+        }
+        // the index is written last, so it only exists if all sidecars were written
+        fs::write(dir.join(INDEX_FILE), encode_index(&entries))
+            .map_err(|err| BlobStoreError::Other(Box::new(err)))?;
+
+        self.inner.index.write().insert(block, entries);
+        Ok(())
+    }
+
+    /// Removes all blocks that are outside the retention window below the given finalized block.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn prune(&self, finalized_block: BlockNumber) -> Result<usize, BlobStoreError> {
+        let Some(lowest) = finalized_block.checked_sub(self.inner.config.retention_blocks) else {
+            return Ok(0)
+        };
+        let expired = self
+            .inner
+            .index
+            .read()
+            .blocks
+            .range(..lowest)
+            .map(|(block, _)| *block)
+            .collect::<Vec<_>>();
+        for block in &expired {
+            self.remove_block(*block)?;
+        }
+        if !expired.is_empty() {
+            debug!(
+                target: "txpool::blob",
+                blocks = expired.len(),
+                %finalized_block,
+                "Pruned blob archive"
+            );
+        }
+        Ok(expired.len())
+    }
+
+    /// Returns the archived sidecar of the given transaction.
+    pub fn get(&self, tx: TxHash) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+        let Some(block) = self.inner.index.read().txs.get(&tx).copied() else { return Ok(None) };
+        let data = match fs::read(self.block_dir(block).join(format!("{tx:x}"))) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(BlobStoreError::Other(Box::new(err))),
+        };
+        Ok(Some(Arc::new(BlobTransactionSidecar::rlp_decode_fields(&mut data.as_slice())?)))
+    }
+
+    /// Fills the blobs of the given versioned hashes that are missing in the result with the
+    /// archived blobs.
+    pub fn fill_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+        result: &mut [Option<BlobAndProofV1>],
+    ) -> Result<(), BlobStoreError> {
+        for (versioned_hash, blob) in versioned_hashes.iter().zip(result.iter_mut()) {
+            if blob.is_some() {
+                continue
+            }
+            let Some((tx, index)) =
+                self.inner.index.read().versioned_hashes.get(versioned_hash).copied()
+            else {
+                continue
+            };
+            if let Some(sidecar) = self.get(tx)? {
+                *blob = Some(BlobAndProofV1 {
+                    blob: Box::new(sidecar.blobs[index]),
+                    proof: sidecar.proofs[index],
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of archived blocks.
+    pub fn blocks_len(&self) -> usize {
+        self.inner.index.read().blocks.len()
+    }
+
+    /// Removes the given block from the archive.
+    fn remove_block(&self, block: BlockNumber) -> Result<(), BlobStoreError> {
+        self.inner.index.write().remove(block);
+        match fs::remove_dir_all(self.block_dir(block)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(BlobStoreError::Other(Box::new(err))),
+        }
+    }
+
+    fn block_dir(&self, block: BlockNumber) -> PathBuf {
+        self.inner.config.path.join(block.to_string())
+    }
+}
+
+#[derive(Debug)]
+struct BlobArchiveInner {
+    config: BlobArchiveConfig,
+    index: RwLock<ArchiveIndex>,
+}
+
+/// The in-memory index of the archived sidecars.
+#[derive(Debug, Default)]
+struct ArchiveIndex {
+    /// The archived transactions of every block.
+    blocks: BTreeMap<BlockNumber, Vec<TxHash>>,
+    /// The block of every archived transaction.
+    txs: HashMap<TxHash, BlockNumber>,
+    /// The transaction and position in its sidecar of every archived blob.
+    versioned_hashes: HashMap<B256, (TxHash, usize)>,
+}
+
+impl ArchiveIndex {
+    fn insert(&mut self, block: BlockNumber, entries: Vec<(TxHash, Vec<B256>)>) {
+        let mut txs = Vec::with_capacity(entries.len());
+        for (tx, versioned_hashes) in entries {
+            for (index, versioned_hash) in versioned_hashes.into_iter().enumerate() {
+                self.versioned_hashes.insert(versioned_hash, (tx, index));
+            }
+            self.txs.insert(tx, block);
+            txs.push(tx);
+        }
+        self.blocks.insert(block, txs);
+    }
+
+    fn remove(&mut self, block: BlockNumber) {
+        let Some(txs) = self.blocks.remove(&block) else { return };
+        for tx in txs {
+            self.txs.remove(&tx);
+        }
+        self.versioned_hashes.retain(|_, (tx, _)| self.txs.contains_key(tx));
+    }
+}
+
+/// Encodes the index of a block as the transaction hash, the number of blobs and the versioned
+/// hashes of every transaction.
+fn encode_index(entries: &[(TxHash, Vec<B256>)]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (tx, versioned_hashes) in entries {
+        buf.extend_from_slice(tx.as_slice());
+        buf.push(versioned_hashes.len() as u8);
+        for versioned_hash in versioned_hashes {
+            buf.extend_from_slice(versioned_hash.as_slice());
+        }
+    }
+    buf
+}
+
+/// Decodes an index encoded by [`encode_index`].
+fn decode_index(mut data: &[u8]) -> Result<Vec<(TxHash, Vec<B256>)>, BlobStoreError> {
+    let mut entries = Vec::new();
+    while !data.is_empty() {
+        let (tx, rest) = data.split_at_checked(32).ok_or(alloy_rlp::Error::InputTooShort)?;
+        let (len, mut rest) = rest.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        let mut versioned_hashes = Vec::with_capacity(*len as usize);
+        for _ in 0..*len {
+            let (versioned_hash, next) =
+                rest.split_at_checked(32).ok_or(alloy_rlp::Error::InputTooShort)?;
+            versioned_hashes.push(B256::from_slice(versioned_hash));
+            rest = next;
+        }
+        entries.push((TxHash::from_slice(tx), versioned_hashes));
+        data = rest;
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::{Blob, Bytes48};
+
+    fn sidecar(byte: u8) -> BlobTransactionSidecar {
+        BlobTransactionSidecar::new(
+            vec![Blob::repeat_byte(byte)],
+            vec![Bytes48::repeat_byte(byte)],
+            vec![Bytes48::repeat_byte(byte)],
+        )
+    }
+
+    #[test]
+    fn archive_insert_get_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BlobArchiveConfig::new(dir.path()).with_retention_blocks(10);
+        let archive = BlobArchive::open(config.clone()).unwrap();
+
+        let (tx1, tx2) = (TxHash::random(), TxHash::random());
+        let (sidecar1, sidecar2) = (sidecar(1), sidecar(2));
+        archive.insert_block(1, vec![(tx1, Arc::new(sidecar1.clone()))]).unwrap();
+        archive.insert_block(5, vec![(tx2, Arc::new(sidecar2.clone()))]).unwrap();
+        assert_eq!(archive.get(tx1).unwrap().as_deref(), Some(&sidecar1));
+
+        let versioned_hash = sidecar2.versioned_hashes().next().unwrap();
+        let mut result = vec![None, None];
+        archive.fill_by_versioned_hashes(&[B256::random(), versioned_hash], &mut result).unwrap();
+        assert!(result[0].is_none());
+        assert_eq!(result[1].as_ref().unwrap().proof, sidecar2.proofs[0]);
+
+        // the index is restored when the archive is reopened
+        let archive = BlobArchive::open(config).unwrap();
+        assert_eq!(archive.blocks_len(), 2);
+        assert_eq!(archive.get(tx2).unwrap().as_deref(), Some(&sidecar2));
+
+        assert_eq!(archive.prune(12).unwrap(), 1);
+        assert!(archive.get(tx1).unwrap().is_none());
+        assert!(archive.get(tx2).unwrap().is_some());
+        assert!(!dir.path().join("1").exists());
+    }
+}
@@ -2,6 +2,7 @@
 
 use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
 use alloy_primitives::B256;
+pub use archive::{BlobArchive, BlobArchiveConfig, DEFAULT_BLOB_RETENTION_BLOCKS};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
@@ -14,6 +15,7 @@ use std::{
 };
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
+mod archive;
 pub mod disk;
 mod mem;
 mod noop;
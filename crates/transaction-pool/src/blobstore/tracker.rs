@@ -53,14 +53,8 @@ impl BlobStoreCanonTracker {
     ///
     /// This returns all blob transactions that were included in blocks that are now finalized.
     pub fn on_finalized_block(&mut self, finalized_block: BlockNumber) -> BlobStoreUpdates {
-        let mut finalized = Vec::new();
-        while let Some(entry) = self.blob_txs_in_blocks.first_entry() {
-            if *entry.key() <= finalized_block {
-                finalized.extend(entry.remove_entry().1);
-            } else {
-                break
-            }
-        }
+        let finalized =
+            self.take_finalized_blocks(finalized_block).into_values().flatten().collect::<Vec<_>>();
 
         if finalized.is_empty() {
             BlobStoreUpdates::None
@@ -68,6 +62,17 @@ impl BlobStoreCanonTracker {
             BlobStoreUpdates::Finalized(finalized)
         }
     }
+
+    /// Removes all blocks that are now finalized from the tracker.
+    ///
+    /// This returns the blob transactions of every finalized block.
+    pub fn take_finalized_blocks(
+        &mut self,
+        finalized_block: BlockNumber,
+    ) -> BTreeMap<BlockNumber, Vec<B256>> {
+        let unfinalized = self.blob_txs_in_blocks.split_off(&finalized_block.saturating_add(1));
+        std::mem::replace(&mut self.blob_txs_in_blocks, unfinalized)
+    }
 }
 
 /// Updates that should be applied to the blob store.
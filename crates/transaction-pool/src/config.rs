@@ -1,4 +1,6 @@
 use crate::{
+    admission::AdmissionLogConfig,
+    blobstore::BlobArchiveConfig,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
 };
@@ -19,6 +21,9 @@ pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 /// The default additional validation tasks size.
 pub const DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS: usize = 1;
 
+/// The default max number of transactions that are validated together by a validation task.
+pub const DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE: usize = 64;
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -30,6 +35,10 @@ pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
+/// The default number of blocks a private transaction is kept in the pool for, if it doesn't
+/// specify a max block number.
+pub const DEFAULT_PRIVATE_TX_LIFETIME: u64 = 25;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -58,6 +67,13 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
+    /// Where to record the admission decisions of the pool, if enabled.
+    pub admission_log: Option<AdmissionLogConfig>,
+    /// Where to archive the blob sidecars of finalized blocks, if enabled.
+    pub blob_archive: Option<BlobArchiveConfig>,
+    /// The number of blocks a [`TransactionOrigin::Private`] transaction is kept in the pool for,
+    /// unless it specifies a max block number.
+    pub private_tx_lifetime: u64,
 }
 
 impl PoolConfig {
@@ -69,6 +85,54 @@ impl PoolConfig {
             self.basefee_limit.is_exceeded(pool_size.basefee, pool_size.basefee_size) ||
             self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size)
     }
+
+    /// Returns the settings that can be changed while the pool is running.
+    pub const fn runtime_config(&self) -> PoolRuntimeConfig {
+        PoolRuntimeConfig {
+            price_bump: Some(self.price_bumps.default_price_bump),
+            blob_price_bump: Some(self.price_bumps.replace_blob_tx_price_bump),
+            max_account_slots: Some(self.max_account_slots),
+            pending_limit: Some(self.pending_limit),
+            basefee_limit: Some(self.basefee_limit),
+            queued_limit: Some(self.queued_limit),
+            blob_limit: Some(self.blob_limit),
+        }
+    }
+
+    /// Applies all settings that are set in the given [`PoolRuntimeConfig`].
+    pub fn apply_runtime_config(&mut self, config: &PoolRuntimeConfig) {
+        let PoolRuntimeConfig {
+            price_bump,
+            blob_price_bump,
+            max_account_slots,
+            pending_limit,
+            basefee_limit,
+            queued_limit,
+            blob_limit,
+        } = *config;
+
+        if let Some(price_bump) = price_bump {
+            self.price_bumps.default_price_bump = price_bump;
+        }
+        if let Some(blob_price_bump) = blob_price_bump {
+            self.price_bumps.replace_blob_tx_price_bump = blob_price_bump;
+        }
+        if let Some(max_account_slots) = max_account_slots {
+            self.max_account_slots = max_account_slots;
+        }
+        if let Some(pending_limit) = pending_limit {
+            self.pending_limit = pending_limit;
+        }
+        if let Some(basefee_limit) = basefee_limit {
+            self.basefee_limit = basefee_limit;
+        }
+        if let Some(queued_limit) = queued_limit {
+            self.queued_limit = queued_limit;
+        }
+        if let Some(blob_limit) = blob_limit {
+            self.blob_limit = blob_limit;
+        }
+    }
 }
 
 impl Default for PoolConfig {
@@ -86,12 +150,41 @@ impl Default for PoolConfig {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            admission_log: None,
+            blob_archive: None,
+            private_tx_lifetime: DEFAULT_PRIVATE_TX_LIFETIME,
         }
     }
 }
 
+/// The settings of the [`PoolConfig`] that can be changed while the pool is running, see
+/// [`TransactionPool::update_runtime_config`](crate::TransactionPool::update_runtime_config).
+///
+/// Settings that are not set are left unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct PoolRuntimeConfig {
+    /// Price bump (in %) required to replace a non-blob transaction.
+    pub price_bump: Option<u128>,
+    /// Price bump (in %) required to replace a blob transaction.
+    pub blob_price_bump: Option<u128>,
+    /// Max number of executable transaction slots guaranteed per account.
+    pub max_account_slots: Option<usize>,
+    /// Size limits of the pending sub-pool.
+    pub pending_limit: Option<SubPoolLimit>,
+    /// Size limits of the basefee sub-pool.
+    pub basefee_limit: Option<SubPoolLimit>,
+    /// Size limits of the queued sub-pool.
+    pub queued_limit: Option<SubPoolLimit>,
+    /// Size limits of the blob sub-pool.
+    pub blob_limit: Option<SubPoolLimit>,
+}
+
 /// Size limits for a sub-pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct SubPoolLimit {
     /// Maximum amount of transaction in the pool.
     pub max_txs: usize,
@@ -262,6 +355,28 @@ mod tests {
         assert!(config.is_exceeded(pool_size));
     }
 
+    #[test]
+    fn test_apply_runtime_config() {
+        let mut config = PoolConfig::default();
+        config.apply_runtime_config(&PoolRuntimeConfig {
+            blob_price_bump: Some(50),
+            max_account_slots: Some(4),
+            pending_limit: Some(SubPoolLimit::new(10, 1024)),
+            ..Default::default()
+        });
+
+        assert_eq!(config.price_bumps.default_price_bump, DEFAULT_PRICE_BUMP);
+        assert_eq!(config.price_bumps.price_bump(EIP4844_TX_TYPE_ID), 50);
+        assert_eq!(config.max_account_slots, 4);
+        assert_eq!(config.pending_limit, SubPoolLimit::new(10, 1024));
+        assert_eq!(config.queued_limit, SubPoolLimit::default());
+
+        // applying the current settings is a noop
+        let mut updated = config.clone();
+        updated.apply_runtime_config(&config.runtime_config());
+        assert_eq!(updated.runtime_config(), config.runtime_config());
+    }
+
     #[test]
     fn test_default_config() {
         let config = LocalTransactionConfig::default();
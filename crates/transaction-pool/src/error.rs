@@ -180,6 +180,11 @@ pub enum Eip7702PoolTransactionError {
     /// Thrown if the transaction has no items in its authorization list
     #[error("no items in authorization list for EIP7702 transaction")]
     MissingEip7702AuthorizationList,
+    /// Thrown if an item of the authorization list is signed for a different chain.
+    ///
+    /// Such an authorization can never be applied on this chain.
+    #[error("authorization list item has invalid chain id {0}")]
+    InvalidAuthorizationChainId(u64),
 }
 
 /// Represents errors that can happen when validating transactions for the pool
@@ -313,6 +318,11 @@ impl InvalidPoolTransactionError {
             }
             Self::Eip7702(eip7702_err) => match eip7702_err {
                 Eip7702PoolTransactionError::MissingEip7702AuthorizationList => false,
+                Eip7702PoolTransactionError::InvalidAuthorizationChainId(_) => {
+                    // the transaction is valid, but includes authorizations that can't be applied
+                    // on this chain
+                    false
+                }
             },
         }
     }
@@ -152,29 +152,38 @@
 
 use crate::{identifier::TransactionId, pool::PoolInner};
 use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 use reth_primitives::PooledTransactionsElement;
 use reth_storage_api::StateProviderFactory;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
+    admission::{
+        AdmissionLogConfig, AdmissionOutcome, AdmissionRecord, ADMISSION_LOG_MAX_SIZE_MB_DEFAULT,
+    },
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        LocalTransactionConfig, PoolConfig, PoolRuntimeConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_PRICE_BUMP, DEFAULT_PRIVATE_TX_LIFETIME,
+        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
+        MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, LocalTransaction, LocalTransactionStatus, TransactionEvent,
+        TransactionEvents,
     },
     traits::*,
     validate::{
@@ -183,6 +192,8 @@ pub use crate::{
     },
 };
 
+pub mod admission;
+pub mod alt;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
@@ -239,14 +250,19 @@ where
 
     /// Returns future that validates all transactions in the given iterator.
     ///
+    /// The transactions are validated as a batch, see
+    /// [`TransactionValidator::validate_transactions`].
+    ///
     /// This returns the validated transactions in the iterator's order.
     async fn validate_all(
         &self,
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = V::Transaction>,
     ) -> Vec<(TxHash, TransactionValidationOutcome<V::Transaction>)> {
-        futures_util::future::join_all(transactions.into_iter().map(|tx| self.validate(origin, tx)))
-            .await
+        let transactions: Vec<_> = transactions.into_iter().map(|tx| (origin, tx)).collect();
+        let hashes: Vec<_> = transactions.iter().map(|(_, tx)| *tx.hash()).collect();
+        let outcomes = self.pool.validator().validate_transactions(transactions).await;
+        hashes.into_iter().zip(outcomes).collect()
     }
 
     /// Validates the given transaction
@@ -370,6 +386,25 @@ where
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = self.add_transaction(TransactionOrigin::Private, transaction).await?;
+        if let Some(max_block_number) = max_block_number {
+            self.pool.set_private_max_block_number(hash, max_block_number);
+        }
+        Ok(hash)
+    }
+
+    fn remove_private_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.remove_private_transaction(tx_hash)
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
@@ -537,7 +572,7 @@ where
         sender: Address,
         nonce: u64,
     ) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>> {
-        let transaction_id = TransactionId::new(self.pool.get_sender_id(sender), nonce);
+        let transaction_id = TransactionId::new(self.pool.sender_id(&sender)?, nonce);
 
         self.inner().get_pool_data().all().get(&transaction_id).map(|tx| tx.transaction.clone())
     }
@@ -561,32 +596,52 @@ where
         self.pool.unique_senders()
     }
 
+    fn admission_history(&self, tx_hash: TxHash) -> Vec<AdmissionRecord> {
+        self.pool.admission_history(&tx_hash)
+    }
+
+    fn local_transaction(&self, tx_hash: TxHash) -> Option<LocalTransaction> {
+        self.pool.local_transaction(&tx_hash)
+    }
+
+    fn local_transactions(&self) -> Vec<LocalTransaction> {
+        self.pool.local_transactions()
+    }
+
+    fn runtime_config(&self) -> PoolRuntimeConfig {
+        self.pool.runtime_config()
+    }
+
+    fn update_runtime_config(&self, config: PoolRuntimeConfig) -> HashSet<TxHash> {
+        self.pool.update_runtime_config(&config)
+    }
+
     fn get_blob(
         &self,
         tx_hash: TxHash,
     ) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
-        self.pool.blob_store().get(tx_hash)
+        self.pool.get_blob(tx_hash)
     }
 
     fn get_all_blobs(
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<(TxHash, Arc<BlobTransactionSidecar>)>, BlobStoreError> {
-        self.pool.blob_store().get_all(tx_hashes)
+        self.pool.get_all_blobs(tx_hashes)
     }
 
     fn get_all_blobs_exact(
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<Arc<BlobTransactionSidecar>>, BlobStoreError> {
-        self.pool.blob_store().get_exact(tx_hashes)
+        self.pool.get_all_blobs_exact(tx_hashes)
     }
 
     fn get_blobs_for_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
-        self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
+        self.pool.get_blobs_for_versioned_hashes(versioned_hashes)
     }
 }
 
@@ -619,6 +674,14 @@ where
         self.pool.delete_blobs(txs)
     }
 
+    fn archive_blobs(
+        &self,
+        finalized_block: BlockNumber,
+        blocks: BTreeMap<BlockNumber, Vec<TxHash>>,
+    ) {
+        self.pool.archive_blobs(finalized_block, blocks)
+    }
+
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
@@ -1,7 +1,7 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::BlobStoreCanonTracker,
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, TransactionPool, TransactionPoolExt},
@@ -190,15 +190,16 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
-            if let BlobStoreUpdates::Finalized(blobs) =
-                blob_store_tracker.on_finalized_block(finalized)
-            {
+            let finalized_blocks = blob_store_tracker.take_finalized_blocks(finalized);
+            let blobs = finalized_blocks.values().flatten().copied().collect::<Vec<_>>();
+            if !blobs.is_empty() {
                 metrics.inc_deleted_tracked_blobs(blobs.len());
-                // remove all finalized blobs from the blob store
-                pool.delete_blobs(blobs);
+                // move all finalized blobs to the blob archive and remove them from the blob store
                 // and also do periodic cleanup
                 let pool = pool.clone();
                 task_spawner.spawn_blocking(Box::pin(async move {
+                    pool.archive_blobs(finalized, finalized_blocks);
+                    pool.delete_blobs(blobs);
                     debug!(target: "txpool", finalized_block = %finalized, "cleaning up blob store");
                     pool.cleanup_blobs();
                 }));
@@ -1,7 +1,7 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -67,6 +67,16 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_entries: Gauge,
 }
 
+/// Transaction pool KZG proof verification metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct BlobVerificationMetrics {
+    /// How long it took to verify the KZG proofs of a blob transaction
+    pub(crate) blob_verification_duration_seconds: Histogram,
+    /// Number of blob transactions with invalid KZG proofs
+    pub(crate) invalid_blob_proofs: Counter,
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -4,23 +4,26 @@
 //! to be generic over it.
 
 use crate::{
+    admission::AdmissionRecord,
     blobstore::BlobStoreError,
     error::PoolError,
+    pool::LocalTransaction,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
         TransactionListenerKind,
     },
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
-    EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize, PoolTransaction,
-    PooledTransactionsElement, PropagatedTransactions, TransactionEvents, TransactionOrigin,
-    TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    EthPooledTransaction, NewTransactionEvent, PoolConfig, PoolResult, PoolRuntimeConfig, PoolSize,
+    PoolTransaction, PooledTransactionsElement, PropagatedTransactions, TransactionEvents,
+    TransactionOrigin, TransactionPool, TransactionValidationOutcome, TransactionValidator,
+    ValidPoolTransaction,
 };
 use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT,
     eip4844::{BlobAndProofV1, BlobTransactionSidecar},
 };
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use reth_eth_wire_types::HandleMempoolData;
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
@@ -82,6 +85,22 @@ impl TransactionPool for NoopTransactionPool {
             .collect()
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        _max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
+    fn remove_private_transaction(
+        &self,
+        _tx_hash: TxHash,
+    ) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        None
+    }
+
     fn transaction_event_listener(&self, _tx_hash: TxHash) -> Option<TransactionEvents> {
         None
     }
@@ -277,6 +296,26 @@ impl TransactionPool for NoopTransactionPool {
         Default::default()
     }
 
+    fn admission_history(&self, _tx_hash: TxHash) -> Vec<AdmissionRecord> {
+        Vec::new()
+    }
+
+    fn local_transaction(&self, _tx_hash: TxHash) -> Option<LocalTransaction> {
+        None
+    }
+
+    fn local_transactions(&self) -> Vec<LocalTransaction> {
+        Vec::new()
+    }
+
+    fn runtime_config(&self) -> PoolRuntimeConfig {
+        PoolConfig::default().runtime_config()
+    }
+
+    fn update_runtime_config(&self, _config: PoolRuntimeConfig) -> HashSet<TxHash> {
+        HashSet::new()
+    }
+
     fn get_blob(
         &self,
         _tx_hash: TxHash,
@@ -134,6 +134,11 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         self.by_id.len()
     }
 
+    /// Returns an iterator over all transactions in the pool
+    pub(crate) fn all(&self) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.by_id.values().map(|tx| tx.transaction.clone())
+    }
+
     /// Returns whether the pool is empty
     #[cfg(test)]
     #[allow(dead_code)]
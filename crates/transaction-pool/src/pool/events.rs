@@ -28,8 +28,13 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
-    /// Transaction was dropped due to configured limits.
-    Discarded(TxHash),
+    /// Transaction was dropped from the pool.
+    Discarded {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -47,7 +52,9 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
-            Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Discarded { tx_hash, reason } => {
+                Self::Discarded { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
@@ -76,6 +83,23 @@ pub enum TransactionEvent {
     Propagated(Arc<Vec<PropagateKind>>),
 }
 
+/// The reason a transaction was dropped from the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DiscardReason {
+    /// The pool exceeded its configured size limits and the transaction was one of the worst.
+    PoolLimit,
+    /// The nonce of the transaction was used by another transaction that was mined.
+    NonceTooLow,
+    /// The transaction failed validation and was never added to the pool.
+    Rejected,
+    /// The transaction was removed explicitly, e.g. via RPC.
+    Removed,
+    /// The private transaction was not included up to its max block number.
+    Expired,
+}
+
 impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
@@ -1,11 +1,14 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::{
+        events::{DiscardReason, FullTransactionEvent, TransactionEvent},
+        local::{LocalTransaction, LocalTransactions},
+    },
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{Address, TxHash, B256};
 use futures_util::Stream;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -78,6 +81,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// Status of the transactions that were submitted to this node.
+    local_transactions: LocalTransactions,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -85,6 +90,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            local_transactions: LocalTransactions::default(),
         }
     }
 }
@@ -97,6 +103,8 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         event: TransactionEvent,
         pool_event: FullTransactionEvent<T>,
     ) {
+        self.local_transactions.on_event(hash, &event);
+
         // Broadcast to all listeners for the transaction hash.
         if let Entry::Occupied(mut sink) = self.broadcasters_by_hash.entry(*hash) {
             sink.get_mut().broadcast(event.clone());
@@ -125,6 +133,21 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         TransactionEvents { hash: tx_hash, events: rx }
     }
 
+    /// Starts tracking the status of a transaction that was submitted to this node.
+    pub(crate) fn track_local(&mut self, tx_hash: TxHash, sender: Address, nonce: u64) {
+        self.local_transactions.track(tx_hash, sender, nonce);
+    }
+
+    /// Returns the tracked local transaction.
+    pub(crate) fn local_transaction(&self, tx_hash: &TxHash) -> Option<LocalTransaction> {
+        self.local_transactions.get(tx_hash)
+    }
+
+    /// Returns all tracked local transactions.
+    pub(crate) fn local_transactions(&self) -> Vec<LocalTransaction> {
+        self.local_transactions.all()
+    }
+
     /// Create a new subscription for all transactions.
     pub(crate) fn subscribe_all(&mut self) -> AllTransactionsEvents<T> {
         let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
@@ -168,8 +191,12 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded { tx_hash: *tx, reason },
+        );
     }
 
     /// Notify listeners that the transaction was mined
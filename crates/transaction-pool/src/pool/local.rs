@@ -0,0 +1,147 @@
+//! Tracking of the transactions that were submitted to this node.
+
+use crate::pool::events::TransactionEvent;
+use alloy_primitives::{Address, TxHash, B256};
+use schnellru::{ByLength, LruMap};
+
+/// The maximum number of tracked local transactions, including the ones that already left the
+/// pool.
+pub const MAX_TRACKED_LOCAL_TRANSACTIONS: u32 = 4096;
+
+/// The status of a local transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "status", rename_all = "camelCase"))]
+pub enum LocalTransactionStatus {
+    /// The transaction is in the pending sub-pool.
+    Pending,
+    /// The transaction is parked in one of the queued sub-pools.
+    Queued,
+    /// The transaction was included in the block with the given hash.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Mined {
+        /// The hash of the block.
+        block_hash: B256,
+    },
+    /// The transaction was replaced by another transaction with the same nonce.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Replaced {
+        /// The hash of the replacement transaction.
+        replaced_by: TxHash,
+    },
+    /// The transaction was dropped from the pool.
+    Discarded,
+    /// The transaction became invalid.
+    Invalid,
+}
+
+/// A transaction that was submitted to this node and its current status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct LocalTransaction {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The current status of the transaction.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub status: LocalTransactionStatus,
+    /// How often the transaction was propagated to peers.
+    pub propagations: u64,
+}
+
+impl LocalTransaction {
+    /// Returns `true` if the transaction is still in the pool.
+    pub const fn is_in_pool(&self) -> bool {
+        matches!(self.status, LocalTransactionStatus::Pending | LocalTransactionStatus::Queued)
+    }
+}
+
+/// Keeps track of the status of the most recent local transactions.
+#[derive(Debug)]
+pub(crate) struct LocalTransactions {
+    txs: LruMap<TxHash, LocalTransaction, ByLength>,
+}
+
+impl Default for LocalTransactions {
+    fn default() -> Self {
+        Self { txs: LruMap::new(ByLength::new(MAX_TRACKED_LOCAL_TRANSACTIONS)) }
+    }
+}
+
+impl LocalTransactions {
+    /// Starts tracking the given transaction, the status is set by the following event.
+    pub(crate) fn track(&mut self, tx_hash: TxHash, sender: Address, nonce: u64) {
+        self.txs.insert(
+            tx_hash,
+            LocalTransaction {
+                tx_hash,
+                sender,
+                nonce,
+                status: LocalTransactionStatus::Queued,
+                propagations: 0,
+            },
+        );
+    }
+
+    /// Updates the status of the transaction if it's tracked.
+    pub(crate) fn on_event(&mut self, tx_hash: &TxHash, event: &TransactionEvent) {
+        let Some(tx) = self.txs.peek_mut(tx_hash) else { return };
+        tx.status = match event {
+            TransactionEvent::Pending => LocalTransactionStatus::Pending,
+            TransactionEvent::Queued => LocalTransactionStatus::Queued,
+            TransactionEvent::Mined(block_hash) => {
+                LocalTransactionStatus::Mined { block_hash: *block_hash }
+            }
+            TransactionEvent::Replaced(replaced_by) => {
+                LocalTransactionStatus::Replaced { replaced_by: *replaced_by }
+            }
+            TransactionEvent::Discarded => LocalTransactionStatus::Discarded,
+            TransactionEvent::Invalid => LocalTransactionStatus::Invalid,
+            TransactionEvent::Propagated(_) => {
+                tx.propagations += 1;
+                return
+            }
+        };
+    }
+
+    /// Returns the tracked transaction.
+    pub(crate) fn get(&self, tx_hash: &TxHash) -> Option<LocalTransaction> {
+        self.txs.peek(tx_hash).cloned()
+    }
+
+    /// Returns all tracked transactions, most recently submitted first.
+    pub(crate) fn all(&self) -> Vec<LocalTransaction> {
+        self.txs.iter().map(|(_, tx)| tx.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn local_transaction_status() {
+        let mut local = LocalTransactions::default();
+        let (tx, replacement) = (TxHash::random(), TxHash::random());
+        local.track(tx, Address::random(), 0);
+        local.on_event(&tx, &TransactionEvent::Pending);
+        local.on_event(&tx, &TransactionEvent::Propagated(Arc::new(Vec::new())));
+        assert_eq!(local.get(&tx).unwrap().status, LocalTransactionStatus::Pending);
+        assert_eq!(local.get(&tx).unwrap().propagations, 1);
+
+        local.on_event(&tx, &TransactionEvent::Replaced(replacement));
+        let tracked = local.get(&tx).unwrap();
+        assert_eq!(tracked.status, LocalTransactionStatus::Replaced { replaced_by: replacement });
+        assert!(!tracked.is_in_pool());
+
+        // events of untracked transactions are ignored
+        local.on_event(&replacement, &TransactionEvent::Pending);
+        assert!(local.get(&replacement).is_none());
+        assert_eq!(local.all().len(), 1);
+    }
+}
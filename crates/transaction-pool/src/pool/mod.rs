@@ -66,6 +66,7 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    admission::{AdmissionLog, AdmissionOutcome, AdmissionRecord},
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
@@ -78,20 +79,20 @@ use crate::{
         PoolTransaction, PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
-    CanonicalStateUpdate, PoolConfig, TransactionOrdering, TransactionValidator,
+    CanonicalStateUpdate, PoolConfig, PoolRuntimeConfig, TransactionOrdering, TransactionValidator,
 };
-use alloy_primitives::{Address, TxHash, B256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
-use alloy_eips::eip4844::BlobTransactionSidecar;
+use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
 use reth_primitives::{
     BlobTransaction, PooledTransactionsElement, TransactionSigned, TransactionSignedEcRecovered,
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     sync::Arc,
     time::Instant,
@@ -100,9 +101,9 @@ use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobArchive, BlobStore, BlobStoreError},
     metrics::BlobStoreMetrics,
-    pool::txpool::UpdateOutcome,
+    pool::{private::PrivateTransactions, txpool::UpdateOutcome},
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
     validate::ValidTransaction,
 };
@@ -110,16 +111,19 @@ pub use best::{
     BestPayloadTransactions, BestTransactionFilter, BestTransactionsWithPrioritizedSenders,
 };
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
+pub use local::{LocalTransaction, LocalTransactionStatus, MAX_TRACKED_LOCAL_TRANSACTIONS};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 
 mod best;
 mod blob;
 mod listener;
+mod local;
 mod parked;
 pub(crate) mod pending;
+mod private;
 pub(crate) mod size;
 pub(crate) mod state;
 pub mod txpool;
@@ -157,6 +161,12 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Records the admission decisions of the pool, if enabled.
+    admission_log: Option<AdmissionLog>,
+    /// Retains the blob sidecars of finalized blocks, if enabled.
+    blob_archive: Option<BlobArchive>,
+    /// The max block numbers of the private transactions.
+    private_transactions: RwLock<PrivateTransactions>,
 }
 
 // === impl PoolInner ===
@@ -169,6 +179,22 @@ where
 {
     /// Create a new transaction pool instance.
     pub(crate) fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        let admission_log = config.admission_log.clone().and_then(|config| {
+            let path = config.path.clone();
+            AdmissionLog::open(config)
+                .inspect_err(|err| {
+                    warn!(target: "txpool", %err, ?path, "Failed to open admission log, disabling it")
+                })
+                .ok()
+        });
+        let blob_archive = config.blob_archive.clone().and_then(|config| {
+            let path = config.path.clone();
+            BlobArchive::open(config)
+                .inspect_err(|err| {
+                    warn!(target: "txpool", %err, ?path, "Failed to open blob archive, disabling it")
+                })
+                .ok()
+        });
         Self {
             identifiers: Default::default(),
             validator,
@@ -180,10 +206,14 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            admission_log,
+            blob_archive,
+            private_transactions: Default::default(),
         }
     }
 
     /// Returns the configured blob store.
+    #[cfg(any(test, feature = "test-utils"))]
     pub(crate) const fn blob_store(&self) -> &S {
         &self.blob_store
     }
@@ -207,6 +237,11 @@ where
         self.identifiers.write().sender_id_or_create(addr)
     }
 
+    /// Returns the internal [`SenderId`] for this address if it's known, without assigning one
+    pub(crate) fn sender_id(&self, addr: &Address) -> Option<SenderId> {
+        self.identifiers.read().sender_id(addr)
+    }
+
     /// Returns all senders in the pool
     pub(crate) fn unique_senders(&self) -> HashSet<Address> {
         self.get_pool_data().unique_senders()
@@ -229,10 +264,31 @@ where
     }
 
     /// Get the config the pool was configured with.
+    ///
+    /// This does not include updates of the [`PoolRuntimeConfig`].
     pub const fn config(&self) -> &PoolConfig {
         &self.config
     }
 
+    /// Returns the settings the pool currently uses that can be changed while it is running.
+    pub fn runtime_config(&self) -> PoolRuntimeConfig {
+        self.get_pool_data().runtime_config()
+    }
+
+    /// Applies the given settings to the pool and returns the hashes of all transactions that
+    /// were discarded because of lowered size limits.
+    pub fn update_runtime_config(&self, config: &PoolRuntimeConfig) -> HashSet<TxHash> {
+        self.pool.write().update_runtime_config(config);
+        debug!(target: "txpool", ?config, "updated pool config");
+
+        let discarded = self.discard_worst();
+        if !discarded.is_empty() {
+            let mut listener = self.event_listener.write();
+            discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolLimit));
+        }
+        discarded
+    }
+
     /// Get the validator reference.
     pub const fn validator(&self) -> &V {
         &self.validator
@@ -331,7 +387,8 @@ where
         let transactions = self.get_all(tx_hashes);
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
-        for transaction in transactions {
+        // transactions that must not be propagated aren't served to peers either
+        for transaction in transactions.into_iter().filter(|tx| tx.propagate) {
             let encoded_len = transaction.encoded_length();
             let recovered: TransactionSignedEcRecovered =
                 transaction.transaction.clone().into_consensus().into();
@@ -400,6 +457,13 @@ where
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
+        // private transactions that can't be included in the next block are expired
+        let expired = {
+            let mut private = self.private_transactions.write();
+            private.remove(&mined_transactions);
+            private.remove_expired(new_tip.number)
+        };
+
         // update the pool
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
@@ -413,6 +477,16 @@ where
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+
+        if !expired.is_empty() {
+            debug!(target: "txpool", ?expired, "removing expired private transactions");
+            let removed = self.pool.write().remove_transactions(expired);
+            {
+                let mut listener = self.event_listener.write();
+                removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Expired));
+            }
+            self.delete_discarded_blobs(removed.iter());
+        }
     }
 
     /// Performs account updates on the pool.
@@ -425,7 +499,7 @@ where
         let mut listener = self.event_listener.write();
 
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -466,14 +540,40 @@ where
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
-                    propagate,
+                    // private transactions are never propagated, regardless of the validator
+                    propagate: propagate && !origin.is_private(),
                     timestamp: Instant::now(),
                     origin,
                 };
+                let (sender, nonce) = (tx.sender(), tx.nonce());
 
-                let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
+                let added = match self.pool.write().add_transaction(tx, balance, state_nonce) {
+                    Ok(added) => added,
+                    Err(err) => {
+                        self.record_rejected(origin, &err);
+                        return Err(err)
+                    }
+                };
                 let hash = *added.hash();
 
+                if let Some(log) = &self.admission_log {
+                    let accepted = AdmissionRecord::new(
+                        hash,
+                        origin,
+                        AdmissionOutcome::Accepted,
+                        Some(format!("{:?}", added.subpool())),
+                    );
+                    let replaced = added.replaced().map(|replaced| {
+                        AdmissionRecord::new(
+                            *replaced.hash(),
+                            replaced.origin,
+                            AdmissionOutcome::Replaced,
+                            Some(format!("replaced by {hash}")),
+                        )
+                    });
+                    log.record(std::iter::once(accepted).chain(replaced));
+                }
+
                 // transaction was successfully inserted into the pool
                 if let Some(sidecar) = maybe_sidecar {
                     // notify blob sidecar listeners
@@ -493,6 +593,20 @@ where
                     self.on_new_pending_transaction(pending);
                 }
 
+                // Track the status of transactions that were submitted to this node
+                if origin.is_local() {
+                    self.event_listener.write().track_local(hash, sender, nonce);
+                }
+
+                // Private transactions expire after the configured number of blocks
+                if origin.is_private() {
+                    let max_block_number = self
+                        .block_info()
+                        .last_seen_block_number
+                        .saturating_add(self.config.private_tx_lifetime);
+                    self.private_transactions.write().track(hash, max_block_number);
+                }
+
                 // Notify tx event listeners
                 self.notify_event_listeners(&added);
 
@@ -507,15 +621,77 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
-                Err(PoolError::new(*tx.hash(), err))
+                listener.discarded(tx.hash(), DiscardReason::Rejected);
+                let err = PoolError::new(*tx.hash(), err);
+                self.record_rejected(origin, &err);
+                Err(err)
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
-                Err(PoolError::other(tx_hash, err))
+                listener.discarded(&tx_hash, DiscardReason::Rejected);
+                let err = PoolError::other(tx_hash, err);
+                self.record_rejected(origin, &err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Records that the transaction was rejected, if the admission log is enabled.
+    fn record_rejected(&self, origin: TransactionOrigin, err: &PoolError) {
+        if let Some(log) = &self.admission_log {
+            log.record([AdmissionRecord::new(
+                err.hash,
+                origin,
+                AdmissionOutcome::Rejected,
+                Some(err.kind.to_string()),
+            )]);
+        }
+    }
+
+    /// Returns the recorded admission decisions for the transaction, oldest first.
+    pub(crate) fn admission_history(&self, tx_hash: &TxHash) -> Vec<AdmissionRecord> {
+        let Some(log) = &self.admission_log else { return Vec::new() };
+        log.history(tx_hash).unwrap_or_else(|err| {
+            warn!(target: "txpool", %err, "Failed to read admission log");
+            Vec::new()
+        })
+    }
+
+    /// Returns the status of the transaction if it was submitted to this node.
+    pub(crate) fn local_transaction(&self, tx_hash: &TxHash) -> Option<LocalTransaction> {
+        self.event_listener.read().local_transaction(tx_hash)
+    }
+
+    /// Returns the status of the most recent transactions that were submitted to this node.
+    pub(crate) fn local_transactions(&self) -> Vec<LocalTransaction> {
+        self.event_listener.read().local_transactions()
+    }
+
+    /// Lowers the highest block number the private transaction can be included in.
+    ///
+    /// The max block number can't exceed the configured lifetime of private transactions.
+    pub(crate) fn set_private_max_block_number(&self, tx_hash: TxHash, max_block_number: u64) {
+        let mut private = self.private_transactions.write();
+        if let Some(current) = private.max_block_number(&tx_hash) {
+            private.track(tx_hash, current.min(max_block_number));
+        }
+    }
+
+    /// Removes the transaction if it was submitted as private.
+    pub(crate) fn remove_private_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
+        {
+            let mut private = self.private_transactions.write();
+            if !private.contains(&tx_hash) {
+                return None
             }
+            private.remove([&tx_hash]);
         }
+        let removed = self.remove_transactions(vec![tx_hash]).pop()?;
+        self.delete_discarded_blobs(std::iter::once(&removed));
+        Some(removed)
     }
 
     pub(crate) fn add_transaction_and_subscribe(
@@ -551,7 +727,7 @@ where
 
         {
             let mut listener = self.event_listener.write();
-            discarded.iter().for_each(|tx| listener.discarded(tx));
+            discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolLimit));
         }
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
@@ -647,7 +823,7 @@ where
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -660,7 +836,9 @@ where
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
@@ -717,7 +895,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -735,7 +913,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -744,12 +922,12 @@ where
         &self,
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
+        let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -779,7 +957,7 @@ where
         &self,
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
+        let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
         self.get_pool_data().get_transactions_by_sender(sender_id)
     }
 
@@ -788,8 +966,8 @@ where
         &self,
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
-        self.get_pool_data().pending_txs_by_sender(sender_id)
+        let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
+        self.get_pool_data().queued_txs_by_sender(sender_id)
     }
 
     /// Returns all pending transactions filtered by predicate
@@ -805,8 +983,8 @@ where
         &self,
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
-        self.get_pool_data().queued_txs_by_sender(sender_id)
+        let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
+        self.get_pool_data().pending_txs_by_sender(sender_id)
     }
 
     /// Returns the highest transaction of the address
@@ -814,7 +992,7 @@ where
         &self,
         sender: Address,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
+        let sender_id = self.sender_id(&sender)?;
         self.get_pool_data().get_highest_transaction_by_sender(sender_id)
     }
 
@@ -824,7 +1002,7 @@ where
         sender: Address,
         on_chain_nonce: u64,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let sender_id = self.get_sender_id(sender);
+        let sender_id = self.sender_id(&sender)?;
         self.get_pool_data().get_highest_consecutive_transaction_by_sender(
             sender_id.into_transaction_id(on_chain_nonce),
         )
@@ -894,6 +1072,17 @@ where
         // delete any blobs associated with discarded blob transactions
         self.delete_discarded_blobs(discarded.iter());
 
+        if let Some(log) = &self.admission_log {
+            log.record(discarded.iter().map(|tx| {
+                AdmissionRecord::new(
+                    *tx.hash(),
+                    tx.origin,
+                    AdmissionOutcome::Discarded,
+                    Some("pool size limits exceeded".to_string()),
+                )
+            }));
+        }
+
         // then collect into tx hashes
         discarded.into_iter().map(|tx| *tx.hash()).collect()
     }
@@ -918,6 +1107,85 @@ where
         let _ = self.blob_store.delete_all(txs);
     }
 
+    /// Moves the blobs of the finalized blocks into the blob archive, if enabled, and prunes the
+    /// blocks that are outside the retention window.
+    pub(crate) fn archive_blobs(
+        &self,
+        finalized_block: BlockNumber,
+        blocks: BTreeMap<BlockNumber, Vec<TxHash>>,
+    ) {
+        let Some(archive) = &self.blob_archive else { return };
+        for (block, txs) in blocks {
+            let res = self
+                .blob_store
+                .get_all(txs)
+                .and_then(|sidecars| archive.insert_block(block, sidecars));
+            if let Err(err) = res {
+                warn!(target: "txpool", %err, %block, "Failed to archive blobs");
+            }
+        }
+        if let Err(err) = archive.prune(finalized_block) {
+            warn!(target: "txpool", %err, %finalized_block, "Failed to prune blob archive");
+        }
+    }
+
+    /// Returns the sidecar of the given transaction from the blob store, or the blob archive if
+    /// it's no longer in the store.
+    pub(crate) fn get_blob(
+        &self,
+        tx: TxHash,
+    ) -> Result<Option<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+        match (self.blob_store.get(tx)?, &self.blob_archive) {
+            (None, Some(archive)) => archive.get(tx),
+            (sidecar, _) => Ok(sidecar),
+        }
+    }
+
+    /// Returns the sidecars of the given transactions that are in the blob store or the blob
+    /// archive.
+    pub(crate) fn get_all_blobs(
+        &self,
+        txs: Vec<TxHash>,
+    ) -> Result<Vec<(TxHash, Arc<BlobTransactionSidecar>)>, BlobStoreError> {
+        let Some(archive) = &self.blob_archive else { return self.blob_store.get_all(txs) };
+        let mut sidecars = self.blob_store.get_all(txs.clone())?;
+        if sidecars.len() < txs.len() {
+            let found = sidecars.iter().map(|(tx, _)| *tx).collect::<HashSet<_>>();
+            for tx in txs.into_iter().filter(|tx| !found.contains(tx)) {
+                if let Some(sidecar) = archive.get(tx)? {
+                    sidecars.push((tx, sidecar));
+                }
+            }
+        }
+        Ok(sidecars)
+    }
+
+    /// Returns the sidecars of the given transactions in the requested order, from the blob store
+    /// or the blob archive.
+    pub(crate) fn get_all_blobs_exact(
+        &self,
+        txs: Vec<TxHash>,
+    ) -> Result<Vec<Arc<BlobTransactionSidecar>>, BlobStoreError> {
+        if self.blob_archive.is_none() {
+            return self.blob_store.get_exact(txs)
+        }
+        txs.into_iter()
+            .map(|tx| self.get_blob(tx)?.ok_or(BlobStoreError::MissingSidecar(tx)))
+            .collect()
+    }
+
+    /// Returns the blobs for the given versioned hashes from the blob store or the blob archive.
+    pub(crate) fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        let mut blobs = self.blob_store.get_by_versioned_hashes(versioned_hashes)?;
+        if let Some(archive) = &self.blob_archive {
+            archive.fill_by_versioned_hashes(versioned_hashes, &mut blobs)?;
+        }
+        Ok(blobs)
+    }
+
     /// Cleans up the blob store
     pub(crate) fn cleanup_blobs(&self) {
         let stat = self.blob_store.cleanup();
@@ -1184,7 +1452,6 @@ impl<T: PoolTransaction> AddedTransaction<T> {
     }
 
     /// Returns the subpool this transaction was added to
-    #[cfg(test)]
     pub(crate) const fn subpool(&self) -> SubPool {
         match self {
             Self::Pending(_) => SubPool::Pending,
@@ -1249,10 +1516,12 @@ mod tests {
         blobstore::{BlobStore, InMemoryBlobStore},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, CanonicalStateUpdate, PoolConfig, PoolTransaction, PoolUpdateKind, SubPoolLimit,
+        TransactionOrigin, TransactionValidationOutcome, U256,
     };
+    use alloy_consensus::Header;
     use alloy_eips::eip4844::BlobTransactionSidecar;
-    use reth_primitives::kzg::Blob;
+    use reth_primitives::{kzg::Blob, BlockBody, SealedBlock, SealedHeader};
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -1334,4 +1603,69 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn test_expire_private_transactions() {
+        let test_pool = &TestPoolBuilder::default()
+            .with_config(PoolConfig { private_tx_lifetime: 10, ..Default::default() })
+            .pool;
+
+        let add = |origin, tx: MockTransaction| {
+            let outcome = TransactionValidationOutcome::Valid {
+                balance: U256::from(1_000),
+                state_nonce: 0,
+                transaction: ValidTransaction::Valid(tx),
+                propagate: true,
+            };
+            test_pool.add_transactions(origin, [outcome]).pop().unwrap().unwrap()
+        };
+        let on_new_block = |number| {
+            let block = SealedBlock::new(
+                SealedHeader::seal(Header { number, ..Default::default() }),
+                BlockBody::default(),
+            );
+            test_pool.on_canonical_state_change(CanonicalStateUpdate {
+                new_tip: &block,
+                pending_block_base_fee: 0,
+                pending_block_blob_fee: None,
+                changed_accounts: Vec::new(),
+                mined_transactions: Vec::new(),
+                update_kind: PoolUpdateKind::Commit,
+            });
+        };
+
+        let private = add(TransactionOrigin::Private, MockTransaction::eip1559());
+        let short_lived = add(TransactionOrigin::Private, MockTransaction::eip1559());
+        let external = add(TransactionOrigin::External, MockTransaction::eip1559());
+        test_pool.set_private_max_block_number(short_lived, 5);
+
+        // private transactions are never propagated
+        assert!(!test_pool.get(&private).unwrap().propagate);
+        assert!(test_pool.get(&external).unwrap().propagate);
+
+        on_new_block(5);
+        assert!(test_pool.get(&short_lived).is_none());
+        assert!(test_pool.get(&private).is_some());
+
+        on_new_block(10);
+        assert!(test_pool.get(&private).is_none());
+        assert!(test_pool.get(&external).is_some());
+
+        // only private transactions can be removed as private
+        assert!(test_pool.remove_private_transaction(external).is_none());
+        assert!(test_pool.get(&external).is_some());
+    }
+
+    #[test]
+    fn test_lookup_unknown_sender() {
+        let test_pool = &TestPoolBuilder::default().pool;
+        let sender = MockTransaction::eip1559().sender();
+
+        // looking up the transactions of a sender doesn't assign it an identifier
+        assert!(test_pool.get_pending_transactions_by_sender(sender).is_empty());
+        assert!(test_pool.get_queued_transactions_by_sender(sender).is_empty());
+        assert!(test_pool.get_transactions_by_sender(sender).is_empty());
+        assert!(test_pool.get_highest_transaction_by_sender(sender).is_none());
+        assert!(test_pool.sender_id(&sender).is_none());
+    }
 }
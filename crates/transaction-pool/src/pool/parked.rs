@@ -183,6 +183,9 @@ impl<T: ParkedOrd> ParkedPool<T> {
     /// Then, for each sender, all transactions for that sender are removed, until the pool limits
     /// have been met.
     ///
+    /// Senders with local transactions are skipped, unless the pool still exceeds the limits after
+    /// all other senders were removed.
+    ///
     /// Any removed transactions are returned.
     pub fn truncate_pool(
         &mut self,
@@ -195,20 +198,28 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         let mut removed = Vec::new();
 
-        while limit.is_exceeded(self.len(), self.size()) && !self.last_sender_submission.is_empty()
-        {
-            // NOTE: This will not panic due to `!last_sender_transaction.is_empty()`
-            let sender_id = self.last_sender_submission.last().expect("not empty").sender_id;
-            let list = self.get_txs_by_sender(sender_id);
-
-            // Drop transactions from this sender until the pool is under limits
-            for txid in list.into_iter().rev() {
-                if let Some(tx) = self.remove_transaction(&txid) {
-                    removed.push(tx);
+        for remove_locals in [false, true] {
+            let senders = self
+                .last_sender_submission
+                .iter()
+                .rev()
+                .map(|submission| submission.sender_id)
+                .collect::<Vec<_>>();
+            for sender_id in senders {
+                let list = self.get_txs_by_sender(sender_id);
+                if !remove_locals && list.iter().any(|id| self.by_id[id].transaction.is_local()) {
+                    continue
                 }
 
-                if !self.exceeds(&limit) {
-                    break
+                // Drop transactions from this sender until the pool is under limits
+                for txid in list.into_iter().rev() {
+                    if let Some(tx) = self.remove_transaction(&txid) {
+                        removed.push(tx);
+                    }
+
+                    if !self.exceeds(&limit) {
+                        return removed
+                    }
                 }
             }
         }
@@ -519,7 +530,10 @@ impl<T: PoolTransaction> Ord for QueuedOrd<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{MockTransaction, MockTransactionFactory, MockTransactionSet};
+    use crate::{
+        test_utils::{MockTransaction, MockTransactionFactory, MockTransactionSet},
+        TransactionOrigin,
+    };
     use alloy_primitives::address;
     use reth_primitives::TxType;
     use std::collections::HashSet;
@@ -654,6 +668,34 @@ mod tests {
         assert_eq!(parked, expected_parked);
     }
 
+    #[test]
+    fn truncate_parked_keeps_local_txs() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+
+        // A is the oldest sender and would be evicted first, but submitted its txs locally
+        let a = MockTransactionSet::dependent(a_sender, 0, 2, TxType::Eip1559).into_vec();
+        let b = MockTransactionSet::dependent(b_sender, 0, 2, TxType::Eip1559).into_vec();
+        for tx in a {
+            pool.add_transaction(Arc::new(f.validated_with_origin(TransactionOrigin::Local, tx)));
+        }
+        for tx in b {
+            pool.add_transaction(f.validated_arc(tx));
+        }
+
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 2, max_size: usize::MAX });
+        assert!(removed.iter().all(|tx| tx.sender() == b_sender));
+        assert!(pool.all().all(|tx| tx.sender() == a_sender));
+
+        // locals are only evicted if there is nothing else left
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 1, max_size: usize::MAX });
+        assert_eq!(removed.len(), 1);
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn test_truncate_parked_with_large_tx() {
         let mut f = MockTransactionFactory::default();
@@ -0,0 +1,74 @@
+//! Expiry of the transactions that were submitted as private.
+
+use alloy_primitives::{BlockNumber, TxHash};
+use std::collections::HashMap;
+
+/// Keeps track of the highest block number the private transactions of the pool can be included
+/// in.
+///
+/// Private transactions are never propagated, so they can only be included by the local payload
+/// builder and are removed from the pool once their max block number is reached.
+#[derive(Debug, Default)]
+pub(crate) struct PrivateTransactions {
+    max_block_numbers: HashMap<TxHash, BlockNumber>,
+}
+
+impl PrivateTransactions {
+    /// Sets the highest block number the transaction can be included in.
+    pub(crate) fn track(&mut self, tx_hash: TxHash, max_block_number: BlockNumber) {
+        self.max_block_numbers.insert(tx_hash, max_block_number);
+    }
+
+    /// Returns `true` if the transaction was submitted as private.
+    pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
+        self.max_block_numbers.contains_key(tx_hash)
+    }
+
+    /// Returns the highest block number the transaction can be included in.
+    pub(crate) fn max_block_number(&self, tx_hash: &TxHash) -> Option<BlockNumber> {
+        self.max_block_numbers.get(tx_hash).copied()
+    }
+
+    /// Stops tracking the transactions, e.g. because they were mined.
+    pub(crate) fn remove<'a>(&mut self, tx_hashes: impl IntoIterator<Item = &'a TxHash>) {
+        for tx_hash in tx_hashes {
+            self.max_block_numbers.remove(tx_hash);
+        }
+    }
+
+    /// Removes and returns all transactions that can't be included after the given block.
+    pub(crate) fn remove_expired(&mut self, block_number: BlockNumber) -> Vec<TxHash> {
+        let mut expired = Vec::new();
+        self.max_block_numbers.retain(|tx_hash, max_block_number| {
+            if *max_block_number > block_number {
+                return true
+            }
+            expired.push(*tx_hash);
+            false
+        });
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_transaction_expiry() {
+        let mut private = PrivateTransactions::default();
+        let (first, second, mined) = (TxHash::random(), TxHash::random(), TxHash::random());
+        private.track(first, 10);
+        private.track(second, 12);
+        private.track(mined, 10);
+
+        private.remove([&mined]);
+        assert!(!private.contains(&mined));
+
+        assert!(private.remove_expired(9).is_empty());
+        assert_eq!(private.remove_expired(10), vec![first]);
+        assert_eq!(private.max_block_number(&second), Some(12));
+        assert_eq!(private.remove_expired(13), vec![second]);
+        assert!(!private.contains(&second));
+    }
+}
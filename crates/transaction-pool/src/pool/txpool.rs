@@ -15,8 +15,8 @@ use crate::{
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
     traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    PoolConfig, PoolResult, PoolRuntimeConfig, PoolTransaction, PoolUpdateKind, PriceBumpConfig,
+    TransactionOrdering, ValidPoolTransaction, U256,
 };
 use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
@@ -177,6 +177,21 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Returns the settings of the pool that can be changed while it is running.
+    pub const fn runtime_config(&self) -> PoolRuntimeConfig {
+        self.config.runtime_config()
+    }
+
+    /// Applies the given settings to the pool.
+    ///
+    /// Replacements and new transactions are checked against the new settings right away, lowered
+    /// size limits are enforced by the next [`Self::discard_worst`].
+    pub fn update_runtime_config(&mut self, config: &PoolRuntimeConfig) {
+        self.config.apply_runtime_config(config);
+        self.all_transactions.max_account_slots = self.config.max_account_slots;
+        self.all_transactions.price_bumps = self.config.price_bumps;
+    }
+
     /// Updates the tracked blob fee
     fn update_blob_fee(&mut self, mut pending_blob_fee: u128, base_fee_update: Ordering) {
         std::mem::swap(&mut self.all_transactions.pending_fees.blob_fee, &mut pending_blob_fee);
@@ -380,19 +395,24 @@ impl<T: TransactionOrdering> TxPool<T> {
         &self,
         sender: SenderId,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.pending_transactions_iter().filter(|tx| tx.sender_id() == sender).collect()
+        self.all_transactions
+            .txs_iter(sender)
+            .filter(|(_, tx)| tx.subpool.is_pending())
+            .map(|(_, tx)| Arc::clone(&tx.transaction))
+            .collect()
     }
 
     /// Returns all transactions from parked pools
     pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.basefee_pool.all().chain(self.queued_pool.all()).collect()
+        self.queued_transactions_iter().collect()
     }
 
-    /// Returns an iterator over all transactions from parked pools
+    /// Returns an iterator over all transactions from parked pools, including blob transactions
+    /// that are not pending
     pub(crate) fn queued_transactions_iter(
         &self,
     ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
-        self.basefee_pool.all().chain(self.queued_pool.all())
+        self.basefee_pool.all().chain(self.queued_pool.all()).chain(self.blob_pool.all())
     }
 
     /// Returns queued and pending transactions for the specified sender
@@ -408,7 +428,11 @@ impl<T: TransactionOrdering> TxPool<T> {
         &self,
         sender: SenderId,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.queued_transactions_iter().filter(|tx| tx.sender_id() == sender).collect()
+        self.all_transactions
+            .txs_iter(sender)
+            .filter(|(_, tx)| !tx.subpool.is_pending())
+            .map(|(_, tx)| Arc::clone(&tx.transaction))
+            .collect()
     }
 
     /// Returns `true` if the transaction with the given hash is already included in this pool.
@@ -1935,7 +1959,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        SubPoolLimit, REPLACE_BLOB_PRICE_BUMP,
     };
     use alloy_primitives::address;
     use reth_primitives::TxType;
@@ -2944,6 +2968,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_runtime_config() {
+        let on_chain_balance = U256::from(1_000);
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let mut tx = MockTransaction::eip1559().inc_limit();
+        tx.set_priority_fee(100);
+        tx.set_max_fee(100);
+        pool.add_transaction(f.validated(tx.clone()), on_chain_balance, 0).unwrap();
+
+        // a price bump of 5% is not enough for the default price bump
+        let mut replacement = tx.clone().rng_hash();
+        replacement.set_priority_fee(105);
+        replacement.set_max_fee(105);
+        assert!(pool.add_transaction(f.validated(replacement), on_chain_balance, 0).is_err());
+
+        pool.update_runtime_config(&PoolRuntimeConfig {
+            price_bump: Some(5),
+            queued_limit: Some(SubPoolLimit::new(0, usize::MAX)),
+            ..Default::default()
+        });
+        assert_eq!(pool.runtime_config().price_bump, Some(5));
+        assert_eq!(pool.runtime_config().blob_price_bump, Some(REPLACE_BLOB_PRICE_BUMP));
+
+        // replacements are checked against the new price bump
+        let mut replacement = tx.clone().rng_hash();
+        replacement.set_priority_fee(106);
+        replacement.set_max_fee(106);
+        pool.add_transaction(f.validated(replacement), on_chain_balance, 0).unwrap();
+
+        // the lowered limit is enforced by discarding the worst transactions
+        pool.add_transaction(f.validated(tx.skip(1)), on_chain_balance, 0).unwrap();
+        assert_eq!(pool.size().queued, 1);
+        assert_eq!(pool.discard_worst().len(), 1);
+        assert_eq!(pool.size().queued, 0);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
@@ -3321,4 +3384,31 @@ mod tests {
 
         assert_eq!(pool.pending_pool.independent().len(), 1);
     }
+
+    #[test]
+    fn pending_and_queued_txs_by_sender() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx_0 = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let tx_2 = tx_0.skip(1);
+        let other = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+
+        let v0 = f.validated(tx_0);
+        let v2 = f.validated(tx_2);
+        let other = f.validated(other);
+        pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(other, on_chain_balance, on_chain_nonce).unwrap();
+
+        let pending = pool.pending_txs_by_sender(v0.sender_id());
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].hash(), v0.hash());
+
+        let queued = pool.queued_txs_by_sender(v0.sender_id());
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].hash(), v2.hash());
+    }
 }
@@ -606,6 +606,10 @@ impl PoolTransaction for MockTransaction {
         }
     }
 
+    fn value(&self) -> U256 {
+        *self.get_value()
+    }
+
     fn gas_limit(&self) -> u64 {
         *self.get_gas_limit()
     }
@@ -751,6 +755,10 @@ impl EthPoolTransaction for MockTransaction {
     fn authorization_count(&self) -> usize {
         0
     }
+
+    fn authorization_list(&self) -> Option<&[alloy_eips::eip7702::SignedAuthorization]> {
+        None
+    }
 }
 
 impl TryFrom<TransactionSignedEcRecovered> for MockTransaction {
@@ -1,9 +1,10 @@
 use crate::{
+    admission::AdmissionRecord,
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolResult},
-    pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    pool::{state::SubPool, BestTransactionFilter, LocalTransaction, TransactionEvents},
     validate::ValidPoolTransaction,
-    AllTransactionsEvents,
+    AllTransactionsEvents, PoolRuntimeConfig,
 };
 use alloy_consensus::{
     constants::{EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID},
@@ -13,8 +14,9 @@ use alloy_eips::{
     eip2718::Encodable2718,
     eip2930::AccessList,
     eip4844::{BlobAndProofV1, BlobTransactionSidecar, BlobTransactionValidationError},
+    eip7702::SignedAuthorization,
 };
-use alloy_primitives::{Address, TxHash, TxKind, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxKind, B256, U256};
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -25,7 +27,7 @@ use reth_primitives::{
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     future::Future,
     pin::Pin,
@@ -113,6 +115,30 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
 
+    /// Adds an _unvalidated_ transaction into the pool as [`TransactionOrigin::Private`].
+    ///
+    /// The transaction is never propagated, so it's only included by the local payload builder.
+    /// It's removed from the pool after the block with the given max block number, but at the
+    /// latest after [`PoolConfig::private_tx_lifetime`](crate::PoolConfig::private_tx_lifetime)
+    /// blocks.
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
+    /// Removes the transaction if it was added as [`TransactionOrigin::Private`].
+    ///
+    /// Returns `None` if the transaction is not a private transaction of the pool.
+    ///
+    /// Consumer: RPC
+    fn remove_private_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -225,6 +251,8 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// If the transaction is a blob transaction, the sidecar will be included.
     ///
+    /// Transactions that are not allowed to be propagated, e.g. private transactions, are skipped.
+    ///
     /// Consumer: P2P
     fn get_pooled_transaction_elements(
         &self,
@@ -438,6 +466,34 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
+    /// Returns the recorded admission decisions for the given transaction hash, oldest first.
+    ///
+    /// This is empty if the admission log is disabled, see
+    /// [`PoolConfig::admission_log`](crate::PoolConfig::admission_log).
+    ///
+    /// Note: this reads the log from disk and should not be called on a hot path.
+    fn admission_history(&self, tx_hash: TxHash) -> Vec<AdmissionRecord>;
+
+    /// Returns the status of the transaction if it was submitted to this node, see
+    /// [`TransactionOrigin::Local`].
+    ///
+    /// The status is also returned for tracked transactions that already left the pool, e.g.
+    /// because they were mined or replaced.
+    fn local_transaction(&self, tx_hash: TxHash) -> Option<LocalTransaction>;
+
+    /// Returns the status of the most recent transactions that were submitted to this node, at
+    /// most [`MAX_TRACKED_LOCAL_TRANSACTIONS`](crate::pool::MAX_TRACKED_LOCAL_TRANSACTIONS).
+    fn local_transactions(&self) -> Vec<LocalTransaction>;
+
+    /// Returns the replacement and size settings the pool currently uses.
+    fn runtime_config(&self) -> PoolRuntimeConfig;
+
+    /// Applies the given replacement and size settings while the pool is running.
+    ///
+    /// If the new size limits are exceeded, the worst transactions are discarded and their hashes
+    /// returned.
+    fn update_runtime_config(&self, config: PoolRuntimeConfig) -> HashSet<TxHash>;
+
     /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
     /// store.
     fn get_blob(
@@ -503,6 +559,18 @@ pub trait TransactionPoolExt: TransactionPool {
     /// Deletes multiple blob sidecars from the blob store
     fn delete_blobs(&self, txs: Vec<B256>);
 
+    /// Moves the blob sidecars of the given finalized blocks into the blob archive, if the pool
+    /// keeps one, and prunes the archived blocks that are outside the retention window below the
+    /// finalized block.
+    ///
+    /// This must be called before the sidecars are deleted from the blob store.
+    fn archive_blobs(
+        &self,
+        _finalized_block: BlockNumber,
+        _blocks: BTreeMap<BlockNumber, Vec<B256>>,
+    ) {
+    }
+
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
 }
@@ -635,6 +703,8 @@ pub struct NewBlobSidecar {
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     #[default]
@@ -958,6 +1028,9 @@ pub trait PoolTransaction: fmt::Debug + Send + Sync + Clone {
     /// max_blob_fee_per_gas * blob_gas_used`.
     fn cost(&self) -> U256;
 
+    /// Returns the value transferred by this transaction.
+    fn value(&self) -> U256;
+
     /// Amount of gas that should be used in executing this transaction. This is paid up-front.
     fn gas_limit(&self) -> u64;
 
@@ -1077,6 +1150,9 @@ pub trait EthPoolTransaction:
 
     /// Returns the number of authorizations this transaction has.
     fn authorization_count(&self) -> usize;
+
+    /// Returns the authorization list of the transaction, if it is an EIP-7702 transaction.
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]>;
 }
 
 /// The default [`PoolTransaction`] for the [Pool](crate::Pool) for Ethereum.
@@ -1227,6 +1303,11 @@ impl PoolTransaction for EthPooledTransaction {
         self.cost
     }
 
+    /// Returns the value transferred by this transaction.
+    fn value(&self) -> U256 {
+        self.transaction.value()
+    }
+
     /// Amount of gas that should be used in executing this transaction. This is paid up-front.
     fn gas_limit(&self) -> u64 {
         self.transaction.gas_limit()
@@ -1349,6 +1430,13 @@ impl EthPoolTransaction for EthPooledTransaction {
             _ => 0,
         }
     }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        match &self.transaction.transaction {
+            Transaction::Eip7702(tx) => Some(&tx.authorization_list),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<TransactionSignedEcRecovered> for EthPooledTransaction {
@@ -6,28 +6,36 @@ use crate::{
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
+    metrics::BlobVerificationMetrics,
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
-    EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
+    validate::{KzgSettingsHandle, ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
+    DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
 };
 use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
     LEGACY_TX_TYPE_ID,
 };
-use alloy_eips::eip4844::MAX_BLOBS_PER_BLOCK;
+use alloy_eips::eip4844::{
+    BlobTransactionSidecar, BlobTransactionValidationError, MAX_BLOBS_PER_BLOCK,
+};
 use reth_chainspec::{ChainSpec, EthereumHardforks};
-use reth_primitives::{InvalidTransactionError, SealedBlock};
+use reth_primitives::{Account, InvalidTransactionError, SealedBlock};
 use reth_primitives_traits::GotExpected;
-use reth_storage_api::{AccountReader, StateProviderFactory};
+use reth_storage_api::{
+    errors::provider::ProviderError, AccountReader, StateProvider, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
 use revm::{
     interpreter::gas::validate_initial_tx_gas,
     primitives::{EnvKzgSettings, SpecId},
 };
 use std::{
+    collections::{BTreeSet, HashMap},
     marker::PhantomData,
     sync::{atomic::AtomicBool, Arc},
+    time::Instant,
 };
 use tokio::sync::Mutex;
 
@@ -75,7 +83,7 @@ where
         &self,
         transactions: Vec<(TransactionOrigin, Tx)>,
     ) -> Vec<TransactionValidationOutcome<Tx>> {
-        transactions.into_iter().map(|(origin, tx)| self.validate_one(origin, tx)).collect()
+        self.inner.validate_all(transactions)
     }
 }
 
@@ -130,7 +138,11 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
     /// Stores the setup and parameters needed for validating KZG proofs.
-    kzg_settings: EnvKzgSettings,
+    kzg_settings: KzgSettingsHandle,
+    /// Whether the KZG proofs are verified after the transaction was added to the pool.
+    lazy_blob_verification: bool,
+    /// Metrics for the verification of KZG proofs.
+    blob_verification_metrics: BlobVerificationMetrics,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
@@ -148,6 +160,27 @@ impl<Client, Tx> EthTransactionValidatorInner<Client, Tx> {
     }
 }
 
+impl<Client, Tx: EthPoolTransaction> EthTransactionValidatorInner<Client, Tx> {
+    /// Verifies the KZG proofs of the blob sidecar, unless blobs are verified lazily.
+    fn validate_blob(
+        &self,
+        transaction: &Tx,
+        sidecar: &BlobTransactionSidecar,
+    ) -> Result<(), BlobTransactionValidationError> {
+        if self.lazy_blob_verification {
+            return Ok(())
+        }
+
+        let start = Instant::now();
+        let result = transaction.validate_blob(sidecar, self.kzg_settings.get().get());
+        self.blob_verification_metrics.blob_verification_duration_seconds.record(start.elapsed());
+        if result.is_err() {
+            self.blob_verification_metrics.invalid_blob_proofs.increment(1);
+        }
+        result
+    }
+}
+
 impl<Client, Tx> EthTransactionValidatorInner<Client, Tx>
 where
     Client: StateProviderFactory,
@@ -157,8 +190,102 @@ where
     fn validate_one(
         &self,
         origin: TransactionOrigin,
-        mut transaction: Tx,
+        transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
+        let transaction = match self.validate_stateless(origin, transaction) {
+            Ok(transaction) => transaction,
+            Err(outcome) => return outcome,
+        };
+
+        let state = match self.client.latest() {
+            Ok(state) => state,
+            Err(err) => {
+                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+            }
+        };
+        let account = match state.basic_account(transaction.sender()) {
+            Ok(account) => account.unwrap_or_default(),
+            Err(err) => {
+                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+            }
+        };
+
+        self.validate_stateful(origin, transaction, account, &state)
+    }
+
+    /// Validates all given transactions.
+    ///
+    /// The accounts of the senders are fetched from a single state provider up front, instead of
+    /// looking them up for every transaction.
+    fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut valid = Vec::new();
+        for (origin, transaction) in transactions {
+            match self.validate_stateless(origin, transaction) {
+                Ok(transaction) => {
+                    outcomes.push(None);
+                    valid.push((origin, transaction));
+                }
+                Err(outcome) => outcomes.push(Some(outcome)),
+            }
+        }
+
+        let mut validated = self.validate_all_stateful(valid).into_iter();
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| validated.next().expect("one outcome per transaction"))
+            })
+            .collect()
+    }
+
+    /// Validates the transactions against the state, after prefetching the accounts of all
+    /// senders.
+    fn validate_all_stateful(
+        &self,
+        transactions: Vec<(TransactionOrigin, Tx)>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+
+        let state = match self.client.latest() {
+            Ok(state) => state,
+            Err(err) => return error_outcomes(transactions, err),
+        };
+
+        // look up every sender once, in order
+        let senders: BTreeSet<_> = transactions.iter().map(|(_, tx)| tx.sender()).collect();
+        let mut accounts = HashMap::with_capacity(senders.len());
+        for sender in senders {
+            match state.basic_account(sender) {
+                Ok(account) => {
+                    accounts.insert(sender, account.unwrap_or_default());
+                }
+                Err(err) => return error_outcomes(transactions, err),
+            }
+        }
+
+        transactions
+            .into_iter()
+            .map(|(origin, transaction)| {
+                let account = accounts[&transaction.sender()];
+                self.validate_stateful(origin, transaction, account, &state)
+            })
+            .collect()
+    }
+
+    /// Performs all checks that don't require the state.
+    ///
+    /// Returns the transaction if it passed the checks.
+    fn validate_stateless(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> Result<Tx, TransactionValidationOutcome<Tx>> {
         // Checks for tx_type
         match transaction.tx_type() {
             LEGACY_TX_TYPE_ID => {
@@ -167,85 +294,85 @@ where
             EIP2930_TX_TYPE_ID => {
                 // Accept only legacy transactions until EIP-2718/2930 activates
                 if !self.eip2718 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip2930Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP1559_TX_TYPE_ID => {
                 // Reject dynamic fee transactions until EIP-1559 activates.
                 if !self.eip1559 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip1559Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP4844_TX_TYPE_ID => {
                 // Reject blob transactions.
                 if !self.eip4844 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip4844Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP7702_TX_TYPE_ID => {
                 // Reject EIP-7702 transactions.
                 if !self.eip7702 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip7702Disabled.into(),
-                    )
+                    ))
                 }
             }
 
             _ => {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
         };
 
         // Reject transactions over defined size to prevent DOS attacks
         let transaction_size = transaction.size();
         if transaction_size > self.max_tx_input_bytes {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::OversizedData(
                     transaction_size,
                     self.max_tx_input_bytes,
                 ),
-            )
+            ))
         }
 
         // Check whether the init code size has been exceeded.
         if self.fork_tracker.is_shanghai_activated() {
             if let Err(err) = transaction.ensure_max_init_code_size(MAX_INIT_CODE_BYTE_SIZE) {
-                return TransactionValidationOutcome::Invalid(transaction, err)
+                return Err(TransactionValidationOutcome::Invalid(transaction, err))
             }
         }
 
         // Checks for gas limit
         let transaction_gas_limit = transaction.gas_limit();
         if transaction_gas_limit > self.block_gas_limit {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::ExceedsGasLimit(
                     transaction_gas_limit,
                     self.block_gas_limit,
                 ),
-            )
+            ))
         }
 
         // Ensure max_priority_fee_per_gas (if EIP1559) is less than max_fee_per_gas if any.
         if transaction.max_priority_fee_per_gas() > Some(transaction.max_fee_per_gas()) {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::TipAboveFeeCap.into(),
-            )
+            ))
         }
 
         // Drop non-local transactions with a fee lower than the configured fee for acceptance into
@@ -254,67 +381,81 @@ where
             transaction.is_eip1559() &&
             transaction.max_priority_fee_per_gas() < self.minimum_priority_fee
         {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::Underpriced,
-            )
+            ))
         }
 
         // Checks for chainid
         if let Some(chain_id) = transaction.chain_id() {
             if chain_id != self.chain_id() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::ChainIdMismatch.into(),
-                )
+                ))
             }
         }
 
         if transaction.is_eip7702() {
-            // Cancun fork is required for 7702 txs
+            // Prague fork is required for 7702 txs
             if !self.fork_tracker.is_prague_activated() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
 
             if transaction.authorization_count() == 0 {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     Eip7702PoolTransactionError::MissingEip7702AuthorizationList.into(),
-                )
+                ))
+            }
+
+            // Authorizations must either be valid on any chain or signed for this chain.
+            if let Some(chain_id) = transaction
+                .authorization_list()
+                .into_iter()
+                .flatten()
+                .map(|authorization| authorization.chain_id)
+                .find(|chain_id| *chain_id != 0 && *chain_id != self.chain_id())
+            {
+                return Err(TransactionValidationOutcome::Invalid(
+                    transaction,
+                    Eip7702PoolTransactionError::InvalidAuthorizationChainId(chain_id).into(),
+                ))
             }
         }
 
         if let Err(err) = ensure_intrinsic_gas(&transaction, &self.fork_tracker) {
-            return TransactionValidationOutcome::Invalid(transaction, err)
+            return Err(TransactionValidationOutcome::Invalid(transaction, err))
         }
 
         // light blob tx pre-checks
         if transaction.is_eip4844() {
             // Cancun fork is required for blob txs
             if !self.fork_tracker.is_cancun_activated() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
 
             let blob_count = transaction.blob_count();
             if blob_count == 0 {
                 // no blobs
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::NoEip4844Blobs,
                     ),
-                )
+                ))
             }
 
             if blob_count > MAX_BLOBS_PER_BLOCK {
                 // too many blobs
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
@@ -322,21 +463,21 @@ where
                             permitted: MAX_BLOBS_PER_BLOCK,
                         },
                     ),
-                )
+                ))
             }
         }
 
-        let account = match self
-            .client
-            .latest()
-            .and_then(|state| state.basic_account(transaction.sender()))
-        {
-            Ok(account) => account.unwrap_or_default(),
-            Err(err) => {
-                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
-            }
-        };
+        Ok(transaction)
+    }
 
+    /// Performs the checks against the state, given the account of the sender.
+    fn validate_stateful(
+        &self,
+        origin: TransactionOrigin,
+        mut transaction: Tx,
+        account: Account,
+        state: &dyn StateProvider,
+    ) -> TransactionValidationOutcome<Tx> {
         // Unless Prague is active, the signer account shouldn't have bytecode.
         //
         // If Prague is active, only EIP-7702 bytecode is allowed for the sender.
@@ -345,11 +486,7 @@ where
         // transactions.
         if account.has_bytecode() {
             let is_eip7702 = if self.fork_tracker.is_prague_activated() {
-                match self
-                    .client
-                    .latest()
-                    .and_then(|state| state.bytecode_by_hash(account.get_bytecode_hash()))
-                {
+                match state.bytecode_by_hash(account.get_bytecode_hash()) {
                     Ok(bytecode) => bytecode.unwrap_or_default().is_eip7702(),
                     Err(err) => {
                         return TransactionValidationOutcome::Error(
@@ -424,8 +561,8 @@ where
                     }
                 }
                 EthBlobTransactionSidecar::Present(blob) => {
-                    // validate the blob
-                    if let Err(err) = transaction.validate_blob(&blob, self.kzg_settings.get()) {
+                    // validate the blob, unless it's verified once the transaction is pending
+                    if let Err(err) = self.validate_blob(&transaction, &blob) {
                         return TransactionValidationOutcome::Invalid(
                             transaction,
                             InvalidPoolTransactionError::Eip4844(
@@ -497,9 +634,13 @@ pub struct EthTransactionValidatorBuilder {
     ///
     /// Default is 1
     additional_tasks: usize,
+    /// Max number of transactions that are validated together by one task.
+    validation_batch_size: usize,
 
     /// Stores the setup and parameters needed for validating KZG proofs.
-    kzg_settings: EnvKzgSettings,
+    kzg_settings: KzgSettingsHandle,
+    /// Whether the KZG proofs are verified after the transaction was added to the pool.
+    lazy_blob_verification: bool,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
@@ -521,7 +662,9 @@ impl EthTransactionValidatorBuilder {
             chain_spec,
             minimum_priority_fee: None,
             additional_tasks: 1,
-            kzg_settings: EnvKzgSettings::Default,
+            validation_batch_size: DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
+            kzg_settings: Default::default(),
+            lazy_blob_verification: false,
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
 
@@ -619,10 +762,30 @@ impl EthTransactionValidatorBuilder {
 
     /// Sets the [`EnvKzgSettings`] to use for validating KZG proofs.
     pub fn kzg_settings(mut self, kzg_settings: EnvKzgSettings) -> Self {
+        self.kzg_settings = KzgSettingsHandle::new(kzg_settings);
+        self
+    }
+
+    /// Sets the handle to the [`EnvKzgSettings`] to use for validating KZG proofs.
+    ///
+    /// The settings can be replaced at runtime through the handle, e.g. to load the trusted setup
+    /// of a devnet.
+    pub fn kzg_settings_handle(mut self, kzg_settings: KzgSettingsHandle) -> Self {
         self.kzg_settings = kzg_settings;
         self
     }
 
+    /// Sets whether the KZG proofs of blob transactions are verified lazily.
+    ///
+    /// If enabled, the proofs are not verified when the transaction is added to the pool, but once
+    /// it's promoted to pending by the
+    /// [`verify_pending_blob_transactions`](crate::validate::verify_pending_blob_transactions)
+    /// future, which must be spawned separately.
+    pub const fn with_lazy_blob_verification(mut self, lazy_blob_verification: bool) -> Self {
+        self.lazy_blob_verification = lazy_blob_verification;
+        self
+    }
+
     /// Sets a minimum priority fee that's enforced for acceptance into the pool.
     pub const fn with_minimum_priority_fee(mut self, minimum_priority_fee: u128) -> Self {
         self.minimum_priority_fee = Some(minimum_priority_fee);
@@ -635,6 +798,15 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the max number of transactions of a batch that are validated together by one task.
+    ///
+    /// The accounts of the transactions in a batch are prefetched together, larger batches are
+    /// split up and validated concurrently by the validation tasks.
+    pub const fn with_validation_batch_size(mut self, validation_batch_size: usize) -> Self {
+        self.validation_batch_size = validation_batch_size;
+        self
+    }
+
     /// Configures validation rules based on the head block's timestamp.
     ///
     /// For example, whether the Shanghai and Cancun hardfork is activated at launch.
@@ -680,6 +852,7 @@ impl EthTransactionValidatorBuilder {
             block_gas_limit,
             minimum_priority_fee,
             kzg_settings,
+            lazy_blob_verification,
             local_transactions_config,
             max_tx_input_bytes,
             ..
@@ -703,6 +876,8 @@ impl EthTransactionValidatorBuilder {
             minimum_priority_fee,
             blob_store: Box::new(blob_store),
             kzg_settings,
+            lazy_blob_verification,
+            blob_verification_metrics: Default::default(),
             local_transactions_config,
             max_tx_input_bytes,
             _marker: Default::default(),
@@ -728,6 +903,7 @@ impl EthTransactionValidatorBuilder {
         S: BlobStore,
     {
         let additional_tasks = self.additional_tasks;
+        let validation_batch_size = self.validation_batch_size;
         let validator = self.build(client, blob_store);
 
         let (tx, task) = ValidationTask::new();
@@ -751,10 +927,25 @@ impl EthTransactionValidatorBuilder {
 
         let to_validation_task = Arc::new(Mutex::new(tx));
 
-        TransactionValidationTaskExecutor { validator, to_validation_task }
+        TransactionValidationTaskExecutor {
+            validator,
+            to_validation_task,
+            batch_size: validation_batch_size.max(1),
+        }
     }
 }
 
+/// Returns the outcome of a provider error for each of the transactions.
+fn error_outcomes<Tx: PoolTransaction>(
+    transactions: Vec<(TransactionOrigin, Tx)>,
+    err: ProviderError,
+) -> Vec<TransactionValidationOutcome<Tx>> {
+    transactions
+        .into_iter()
+        .map(|(_, tx)| TransactionValidationOutcome::Error(*tx.hash(), Box::new(err.clone())))
+        .collect()
+}
+
 /// Keeps track of whether certain forks are activated
 #[derive(Debug)]
 pub struct ForkTracker {
@@ -820,10 +1011,11 @@ mod tests {
         blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_consensus::TxEip7702;
+    use alloy_eips::{eip2718::Decodable2718, eip7702::Authorization};
+    use alloy_primitives::{hex, Address, PrimitiveSignature, U256};
     use reth_chainspec::MAINNET;
-    use reth_primitives::PooledTransactionsElement;
+    use reth_primitives::{PooledTransactionsElement, Transaction, TransactionSigned};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 
     fn get_transaction() -> EthPooledTransaction {
@@ -871,6 +1063,41 @@ mod tests {
         assert!(tx.is_some());
     }
 
+    #[test]
+    fn validate_batch() {
+        let transaction = get_transaction();
+        let provider = MockEthProvider::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), InMemoryBlobStore::default());
+
+        // the sender has no funds
+        let batch = vec![
+            (TransactionOrigin::External, transaction.clone()),
+            (TransactionOrigin::Local, transaction.clone()),
+        ];
+        let outcomes = validator.validate_all(batch.clone());
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|outcome| outcome.is_invalid()));
+
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+        let outcomes = validator.validate_all(batch);
+        assert!(outcomes.iter().all(|outcome| outcome.is_valid()));
+        assert!(matches!(outcomes[0], TransactionValidationOutcome::Valid { propagate: true, .. }));
+
+        // transactions that fail the stateless checks keep their position in the batch
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .no_eip1559()
+            .build(provider, InMemoryBlobStore::default());
+        let outcomes = validator.validate_all(vec![
+            (TransactionOrigin::External, transaction.clone()),
+            (TransactionOrigin::External, transaction),
+        ]);
+        assert!(outcomes.iter().all(|outcome| outcome.is_invalid()));
+    }
+
     // <https://github.com/paradigmxyz/reth/issues/8550>
     #[tokio::test]
     async fn invalid_on_gas_limit_too_high() {
@@ -905,4 +1132,38 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn invalid_on_authorization_for_other_chain() {
+        let authorization = Authorization { chain_id: 5, address: Address::random(), nonce: 0 }
+            .into_signed(PrimitiveSignature::test_signature());
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip7702(TxEip7702 {
+                chain_id: MAINNET.chain().id(),
+                gas_limit: 100_000,
+                max_fee_per_gas: 1_000_000_000,
+                authorization_list: vec![authorization],
+                ..Default::default()
+            }),
+            PrimitiveSignature::test_signature(),
+        );
+        let transaction =
+            EthPooledTransaction::try_from(transaction.with_signer(Address::random())).unwrap();
+
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .set_prague(true)
+            .build(MockEthProvider::default(), InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Eip7702(
+                    Eip7702PoolTransactionError::InvalidAuthorizationChainId(5)
+                )
+            )
+        ));
+    }
 }
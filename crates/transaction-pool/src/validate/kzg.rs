@@ -0,0 +1,146 @@
+//! KZG proof verification of blob transactions.
+
+use crate::{
+    metrics::BlobVerificationMetrics, traits::TransactionListenerKind, EthPoolTransaction,
+    PoolTransaction, TransactionPool,
+};
+use alloy_primitives::TxHash;
+use futures_util::{future, FutureExt, StreamExt};
+use parking_lot::RwLock;
+use reth_primitives::kzg::{self, KzgSettings};
+use reth_tasks::pool::BlockingTaskPool;
+use revm::primitives::EnvKzgSettings;
+use schnellru::{ByLength, LruMap};
+use std::{path::Path, sync::Arc, time::Instant};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+/// Max number of blob transactions that are verified concurrently by the
+/// [`verify_pending_blob_transactions`] future.
+const MAX_CONCURRENT_BLOB_VERIFICATIONS: usize = 64;
+
+/// Max number of blob transactions that are remembered after their verification was started, so
+/// that they're not verified again if they're promoted to pending more than once.
+const MAX_VERIFIED_BLOB_TRANSACTIONS: u32 = 1024;
+
+/// A shared handle to the [`EnvKzgSettings`] that are used for validating KZG proofs.
+///
+/// The settings can be replaced at runtime, e.g. to load the trusted setup of a devnet. All
+/// clones of the handle observe the replaced settings.
+#[derive(Debug, Clone, Default)]
+pub struct KzgSettingsHandle {
+    settings: Arc<RwLock<EnvKzgSettings>>,
+}
+
+impl KzgSettingsHandle {
+    /// Creates a new handle with the given settings.
+    pub fn new(settings: EnvKzgSettings) -> Self {
+        Self { settings: Arc::new(RwLock::new(settings)) }
+    }
+
+    /// Returns the current settings.
+    pub fn get(&self) -> EnvKzgSettings {
+        self.settings.read().clone()
+    }
+
+    /// Replaces the settings.
+    pub fn set(&self, settings: EnvKzgSettings) {
+        *self.settings.write() = settings;
+    }
+
+    /// Loads the trusted setup from the given file and replaces the settings with it.
+    ///
+    /// The current settings are kept if the file can't be loaded.
+    pub fn load_trusted_setup_file(&self, path: &Path) -> Result<(), kzg::Error> {
+        let settings = KzgSettings::load_trusted_setup_file(path)?;
+        self.set(EnvKzgSettings::Custom(Arc::new(settings)));
+        Ok(())
+    }
+}
+
+impl From<EnvKzgSettings> for KzgSettingsHandle {
+    fn from(settings: EnvKzgSettings) -> Self {
+        Self::new(settings)
+    }
+}
+
+/// Verifies the KZG proofs of blob transactions once they're promoted to pending and removes the
+/// transactions with invalid proofs from the pool.
+///
+/// This is meant to be spawned if the validator verifies blob transactions lazily, see
+/// [`EthTransactionValidatorBuilder::with_lazy_blob_verification`](crate::validate::EthTransactionValidatorBuilder::with_lazy_blob_verification).
+/// The proofs are verified on the given worker pool.
+///
+/// Note: a pending transaction is only removed once its proofs are verified, so a payload that is
+/// built in between can include a transaction with invalid proofs.
+pub async fn verify_pending_blob_transactions<P>(
+    pool: P,
+    kzg_settings: KzgSettingsHandle,
+    workers: BlockingTaskPool,
+) where
+    P: TransactionPool + 'static,
+{
+    let metrics = BlobVerificationMetrics::default();
+    let mut verified = LruMap::<TxHash, ()>::new(ByLength::new(MAX_VERIFIED_BLOB_TRANSACTIONS));
+
+    let pending = pool.pending_transactions_listener_for(TransactionListenerKind::All);
+    ReceiverStream::new(pending)
+        .filter_map(|tx_hash| {
+            let mut blob_transaction = None;
+            if verified.peek(&tx_hash).is_none() {
+                if let Some(tx) = pool.get(&tx_hash).filter(|tx| tx.transaction.is_eip4844()) {
+                    match pool.get_blob(tx_hash) {
+                        Ok(Some(sidecar)) => {
+                            // transactions with invalid proofs are removed, so they're only
+                            // verified once either way
+                            verified.insert(tx_hash, ());
+                            blob_transaction = Some((tx, sidecar))
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            debug!(target: "txpool", %err, %tx_hash, "failed to get blob sidecar")
+                        }
+                    }
+                }
+            }
+            future::ready(blob_transaction)
+        })
+        .map(|(tx, sidecar)| {
+            let settings = kzg_settings.get();
+            let tx_hash = *tx.hash();
+            let start = Instant::now();
+            workers
+                .spawn(move || tx.transaction.validate_blob(&sidecar, settings.get()))
+                .map(move |result| (tx_hash, start.elapsed(), result))
+        })
+        .buffer_unordered(MAX_CONCURRENT_BLOB_VERIFICATIONS)
+        .for_each(|(tx_hash, elapsed, result)| {
+            metrics.blob_verification_duration_seconds.record(elapsed);
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    debug!(target: "txpool", %err, %tx_hash, "removing blob transaction with invalid proofs");
+                    metrics.invalid_blob_proofs.increment(1);
+                    pool.remove_transactions_and_descendants(vec![tx_hash]);
+                }
+                Err(_) => {
+                    debug!(target: "txpool", %tx_hash, "blob verification task panicked");
+                }
+            }
+            future::ready(())
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_kzg_settings_on_load_error() {
+        let handle = KzgSettingsHandle::default();
+        let other = handle.clone();
+        assert!(handle.load_trusted_setup_file(Path::new("missing_trusted_setup.txt")).is_err());
+        assert_eq!(other.get(), EnvKzgSettings::Default);
+    }
+}
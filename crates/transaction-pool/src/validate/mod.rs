@@ -14,11 +14,21 @@ use std::{fmt, future::Future, time::Instant};
 
 mod constants;
 mod eth;
+mod kzg;
+mod policy;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// KZG proof verification of blob transactions.
+pub use kzg::{verify_pending_blob_transactions, KzgSettingsHandle};
+
+/// Custom acceptance rules checked before validation.
+pub use policy::{
+    AdmissionPolicyError, AdmissionPolicyValidator, AllowedSenders, PoolAdmissionPolicy,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
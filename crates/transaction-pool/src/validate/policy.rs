@@ -0,0 +1,286 @@
+//! Custom acceptance rules for the transactions of the pool.
+
+use crate::{
+    error::{InvalidPoolTransactionError, PoolTransactionError},
+    traits::{PoolTransaction, TransactionOrigin},
+    validate::{TransactionValidationOutcome, TransactionValidator},
+};
+use alloy_primitives::Address;
+use reth_primitives::SealedBlock;
+use std::{collections::HashSet, fmt, sync::Arc};
+
+/// A rule that decides whether a transaction may enter the pool.
+///
+/// Policies are checked by the [`AdmissionPolicyValidator`] before the transaction is passed to
+/// the validator it wraps, so they only have access to the transaction itself and not to the
+/// state.
+///
+/// This is implemented for closures with the signature of [`PoolAdmissionPolicy::check`].
+pub trait PoolAdmissionPolicy<T: PoolTransaction>: Send + Sync {
+    /// Returns an error if the transaction must not enter the pool.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError>;
+}
+
+impl<T, F> PoolAdmissionPolicy<T> for F
+where
+    T: PoolTransaction,
+    F: Fn(TransactionOrigin, &T) -> Result<(), InvalidPoolTransactionError> + Send + Sync,
+{
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self(origin, transaction)
+    }
+}
+
+/// A [`PoolAdmissionPolicy`] that only admits transactions of the given senders, e.g. on private
+/// networks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowedSenders {
+    senders: HashSet<Address>,
+}
+
+impl AllowedSenders {
+    /// Creates a new policy that admits the transactions of the given senders.
+    pub fn new(senders: impl IntoIterator<Item = Address>) -> Self {
+        Self { senders: senders.into_iter().collect() }
+    }
+
+    /// Returns `true` if the transactions of the sender are admitted.
+    pub fn contains(&self, sender: &Address) -> bool {
+        self.senders.contains(sender)
+    }
+}
+
+impl<T: PoolTransaction> PoolAdmissionPolicy<T> for AllowedSenders {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if self.contains(&transaction.sender()) {
+            Ok(())
+        } else {
+            Err(AdmissionPolicyError::new("sender is not allowed").into())
+        }
+    }
+}
+
+/// The error of a transaction that was rejected by a [`PoolAdmissionPolicy`].
+///
+/// Rejected transactions are not considered bad, since the rules are specific to the node.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("transaction rejected by admission policy: {reason}")]
+pub struct AdmissionPolicyError {
+    reason: String,
+}
+
+impl AdmissionPolicyError {
+    /// Creates a new error with the reason of the rejection.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self { reason: reason.into() }
+    }
+
+    /// Returns the reason of the rejection.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl PoolTransactionError for AdmissionPolicyError {
+    fn is_bad_transaction(&self) -> bool {
+        false
+    }
+}
+
+impl From<AdmissionPolicyError> for InvalidPoolTransactionError {
+    fn from(err: AdmissionPolicyError) -> Self {
+        Self::Other(Box::new(err))
+    }
+}
+
+/// A [`TransactionValidator`] that checks a [`PoolAdmissionPolicy`] before validating the
+/// transaction with the wrapped validator.
+///
+/// A validator with spawned validation tasks can be wrapped with
+/// [`TransactionValidationTaskExecutor::map`](crate::TransactionValidationTaskExecutor::map):
+///
+/// ```
+/// use alloy_primitives::Address;
+/// use reth_chainspec::MAINNET;
+/// use reth_storage_api::StateProviderFactory;
+/// use reth_tasks::TokioTaskExecutor;
+/// use reth_transaction_pool::{
+///     blobstore::InMemoryBlobStore,
+///     validate::{AdmissionPolicyValidator, AllowedSenders},
+///     EthPooledTransaction, TransactionValidationTaskExecutor,
+/// };
+///
+/// fn validator<Client: StateProviderFactory>(client: Client, senders: Vec<Address>) {
+///     let _validator = TransactionValidationTaskExecutor::eth_builder(MAINNET.clone())
+///         .build_with_tasks::<_, EthPooledTransaction, _, _>(
+///             client,
+///             TokioTaskExecutor::default(),
+///             InMemoryBlobStore::default(),
+///         )
+///         .map(|validator| {
+///             AdmissionPolicyValidator::new(validator, AllowedSenders::new(senders.clone()))
+///         });
+/// }
+/// ```
+pub struct AdmissionPolicyValidator<V, P> {
+    /// The validator of the admitted transactions.
+    validator: V,
+    /// The rules transactions have to satisfy.
+    policy: Arc<P>,
+}
+
+impl<V, P> AdmissionPolicyValidator<V, P> {
+    /// Creates a new validator that checks the policy before validating transactions with the
+    /// given validator.
+    pub fn new(validator: V, policy: P) -> Self {
+        Self { validator, policy: Arc::new(policy) }
+    }
+
+    /// Returns the wrapped validator.
+    pub const fn validator(&self) -> &V {
+        &self.validator
+    }
+
+    /// Returns the policy.
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+}
+
+impl<V, P> AdmissionPolicyValidator<V, P>
+where
+    V: TransactionValidator,
+    P: PoolAdmissionPolicy<V::Transaction>,
+{
+    /// Checks the policy and returns the transaction if it's admitted.
+    fn admit(
+        &self,
+        origin: TransactionOrigin,
+        transaction: V::Transaction,
+    ) -> Result<V::Transaction, TransactionValidationOutcome<V::Transaction>> {
+        match self.policy.check(origin, &transaction) {
+            Ok(()) => Ok(transaction),
+            Err(err) => Err(TransactionValidationOutcome::Invalid(transaction, err)),
+        }
+    }
+}
+
+impl<V, P> TransactionValidator for AdmissionPolicyValidator<V, P>
+where
+    V: TransactionValidator,
+    P: PoolAdmissionPolicy<V::Transaction>,
+{
+    type Transaction = V::Transaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        match self.admit(origin, transaction) {
+            Ok(transaction) => self.validator.validate_transaction(origin, transaction).await,
+            Err(outcome) => outcome,
+        }
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        // only the admitted transactions are validated, the outcomes are merged back in order
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut admitted = Vec::new();
+        for (origin, transaction) in transactions {
+            match self.admit(origin, transaction) {
+                Ok(transaction) => {
+                    outcomes.push(None);
+                    admitted.push((origin, transaction));
+                }
+                Err(outcome) => outcomes.push(Some(outcome)),
+            }
+        }
+
+        let mut validated = self.validator.validate_transactions(admitted).await.into_iter();
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| validated.next().expect("one outcome per transaction"))
+            })
+            .collect()
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.validator.on_new_head_block(new_tip_block)
+    }
+}
+
+impl<V: Clone, P> Clone for AdmissionPolicyValidator<V, P> {
+    fn clone(&self) -> Self {
+        Self { validator: self.validator.clone(), policy: Arc::clone(&self.policy) }
+    }
+}
+
+impl<V: fmt::Debug, P> fmt::Debug for AdmissionPolicyValidator<V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionPolicyValidator")
+            .field("validator", &self.validator)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noop::MockTransactionValidator, test_utils::MockTransaction};
+
+    #[tokio::test]
+    async fn admission_policy() {
+        let allowed = Address::random();
+        let validator = AdmissionPolicyValidator::new(
+            MockTransactionValidator::<MockTransaction>::default(),
+            AllowedSenders::new([allowed]),
+        );
+
+        let transactions = vec![
+            (TransactionOrigin::External, MockTransaction::eip1559().with_sender(allowed)),
+            (TransactionOrigin::External, MockTransaction::eip1559()),
+            (TransactionOrigin::Local, MockTransaction::eip1559().with_sender(allowed)),
+        ];
+        let outcomes = validator.validate_transactions(transactions).await;
+        assert!(outcomes[0].is_valid());
+        assert!(matches!(
+            &outcomes[1],
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Other(err))
+                if !err.is_bad_transaction()
+        ));
+        assert!(outcomes[2].is_valid());
+
+        // closures can be used as policies
+        let validator = AdmissionPolicyValidator::new(
+            MockTransactionValidator::<MockTransaction>::default(),
+            |origin: TransactionOrigin, _: &MockTransaction| {
+                if origin.is_local() {
+                    Ok(())
+                } else {
+                    Err(InvalidPoolTransactionError::Underpriced)
+                }
+            },
+        );
+        let outcome = validator
+            .validate_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await;
+        assert!(!outcome.is_valid());
+    }
+}
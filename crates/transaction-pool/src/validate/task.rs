@@ -4,8 +4,9 @@ use crate::{
     blobstore::BlobStore,
     validate::{EthTransactionValidatorBuilder, TransactionValidatorError},
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
-    TransactionValidator,
+    TransactionValidator, DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
 };
+use alloy_primitives::TxHash;
 use futures_util::{lock::Mutex, StreamExt};
 use reth_chainspec::ChainSpec;
 use reth_primitives::SealedBlock;
@@ -86,6 +87,10 @@ pub struct TransactionValidationTaskExecutor<V> {
     pub validator: V,
     /// The sender half to validation tasks that perform the actual validation.
     pub to_validation_task: Arc<sync::Mutex<ValidationJobSender>>,
+    /// The max number of transactions of a batch that are validated together by one task.
+    ///
+    /// Larger batches are split up and validated concurrently by the validation tasks.
+    pub batch_size: usize,
 }
 
 // === impl TransactionValidationTaskExecutor ===
@@ -106,8 +111,15 @@ impl<V> TransactionValidationTaskExecutor<V> {
         TransactionValidationTaskExecutor {
             validator: f(self.validator),
             to_validation_task: self.to_validation_task,
+            batch_size: self.batch_size,
         }
     }
+
+    /// Sets the max number of transactions that are validated together by one task.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
 }
 
 impl<Client, Tx> TransactionValidationTaskExecutor<EthTransactionValidator<Client, Tx>> {
@@ -159,7 +171,11 @@ impl<V> TransactionValidationTaskExecutor<V> {
     /// validation tasks.
     pub fn new(validator: V) -> Self {
         let (tx, _) = ValidationTask::new();
-        Self { validator, to_validation_task: Arc::new(sync::Mutex::new(tx)) }
+        Self {
+            validator,
+            to_validation_task: Arc::new(sync::Mutex::new(tx)),
+            batch_size: DEFAULT_TXPOOL_VALIDATION_BATCH_SIZE,
+        }
     }
 }
 
@@ -205,7 +221,57 @@ where
         }
     }
 
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        // send a job for every chunk of the batch, so they're validated concurrently
+        let mut jobs = Vec::new();
+        let mut transactions = transactions.into_iter().peekable();
+        while transactions.peek().is_some() {
+            let chunk: Vec<_> = transactions.by_ref().take(self.batch_size).collect();
+            let hashes: Vec<TxHash> = chunk.iter().map(|(_, tx)| *tx.hash()).collect();
+            let (tx, rx) = oneshot::channel();
+            let res = {
+                let to_validation_task = self.to_validation_task.lock().await;
+                let validator = self.validator.clone();
+                to_validation_task
+                    .send(Box::pin(async move {
+                        let res = validator.validate_transactions(chunk).await;
+                        let _ = tx.send(res);
+                    }))
+                    .await
+            };
+            jobs.push((hashes, res.ok().map(|_| rx)));
+        }
+
+        let mut outcomes = Vec::new();
+        for (hashes, rx) in jobs {
+            let res = match rx {
+                Some(rx) => rx.await.ok(),
+                None => None,
+            };
+            match res {
+                Some(res) => outcomes.extend(res),
+                None => outcomes.extend(unreachable_outcomes(hashes)),
+            }
+        }
+        outcomes
+    }
+
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.validator.on_new_head_block(new_tip_block)
     }
 }
+
+/// Returns the outcomes of transactions that couldn't be sent to the validation service.
+fn unreachable_outcomes<T: PoolTransaction>(
+    hashes: Vec<TxHash>,
+) -> impl Iterator<Item = TransactionValidationOutcome<T>> {
+    hashes.into_iter().map(|hash| {
+        TransactionValidationOutcome::Error(
+            hash,
+            Box::new(TransactionValidatorError::ValidationServiceUnreachable),
+        )
+    })
+}
@@ -0,0 +1,41 @@
+use reth_transaction_pool::{
+    test_utils::{MockTransactionFactory, TestPoolBuilder},
+    AdmissionLogConfig, AdmissionOutcome, PoolConfig, PoolTransaction, TransactionOrigin,
+    TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_admission_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = PoolConfig {
+        admission_log: Some(AdmissionLogConfig::new(dir.path().join("admission.log"))),
+        ..Default::default()
+    };
+    let txpool = TestPoolBuilder::default().with_config(config);
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+    let hash = *transaction.hash();
+
+    txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+    assert!(txpool
+        .add_transaction(TransactionOrigin::External, transaction.clone())
+        .await
+        .is_err());
+
+    let replacement = transaction.inc_price_by(transaction.get_gas_price()).rng_hash();
+    let replacement_hash = *replacement.hash();
+    txpool.add_transaction(TransactionOrigin::Local, replacement).await.unwrap();
+
+    let history = txpool.admission_history(hash);
+    let outcomes = history.iter().map(|record| record.outcome).collect::<Vec<_>>();
+    assert_eq!(
+        outcomes,
+        vec![AdmissionOutcome::Accepted, AdmissionOutcome::Rejected, AdmissionOutcome::Replaced]
+    );
+    assert_eq!(history[0].reason.as_deref(), Some("Pending"));
+    assert_eq!(history[2].reason, Some(format!("replaced by {replacement_hash}")));
+
+    let history = txpool.admission_history(replacement_hash);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].origin, TransactionOrigin::Local);
+}
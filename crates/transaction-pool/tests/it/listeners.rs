@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    DiscardReason, FullTransactionEvent, TransactionEvent, TransactionListenerKind,
+    TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -41,6 +41,25 @@ async fn txpool_listener_all() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_discard_reason() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.hash();
+
+    let mut all_tx_events = txpool.all_transactions_event_listener();
+    txpool.add_transaction(TransactionOrigin::External, transaction.transaction).await.unwrap();
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Pending(_)));
+
+    txpool.remove_transactions(vec![hash]);
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Discarded { tx_hash, reason: DiscardReason::Removed })
+            if tx_hash == hash
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_propagate_only() {
     let txpool =
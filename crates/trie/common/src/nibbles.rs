@@ -19,7 +19,7 @@ pub use nybbles::Nibbles;
     Deserialize,
     derive_more::Index,
 )]
-#[cfg_attr(feature = "test-utils", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "arbitrary", reth_codecs::add_arbitrary_tests(compact))]
 pub struct StoredNibbles(pub Nibbles);
 
 impl From<Nibbles> for StoredNibbles {
@@ -75,7 +75,7 @@ impl Compact for StoredNibbles {
 
 /// The representation of nibbles of the merkle trie stored in the database.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord, Hash, Deref)]
-#[cfg_attr(feature = "test-utils", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "arbitrary", reth_codecs::add_arbitrary_tests(compact))]
 pub struct StoredNibblesSubKey(pub Nibbles);
 
 impl From<Nibbles> for StoredNibblesSubKey {
@@ -120,3 +120,30 @@ impl Compact for StoredNibblesSubKey {
         (Self(Nibbles::from_nibbles_unchecked(&buf[..len])), &buf[65..])
     }
 }
+
+/// Generates arbitrary [`Nibbles`] of the given length, where each nibble is in `0x0..=0xf`.
+#[cfg(feature = "arbitrary")]
+fn arbitrary_nibbles(
+    u: &mut arbitrary::Unstructured<'_>,
+    len: usize,
+) -> arbitrary::Result<Nibbles> {
+    let nibbles = (0..len).map(|_| u.int_in_range(0x0..=0xf)).collect::<Result<Vec<u8>, _>>()?;
+    Ok(Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StoredNibbles {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.arbitrary_len::<u8>()?;
+        arbitrary_nibbles(u, len).map(Self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StoredNibblesSubKey {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The subkey is stored right-padded to 64 nibbles.
+        let len = u.int_in_range(0..=64)?;
+        arbitrary_nibbles(u, len).map(Self)
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Account storage trie node.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", reth_codecs::add_arbitrary_tests(compact))]
 pub struct StorageTrieEntry {
     /// The nibbles of the intermediate node
     pub nibbles: StoredNibblesSubKey,
@@ -31,3 +32,10 @@ impl Compact for StorageTrieEntry {
         (this, buf)
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StorageTrieEntry {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self { nibbles: u.arbitrary()?, node: crate::subnode::arbitrary_branch_node(u)? })
+    }
+}
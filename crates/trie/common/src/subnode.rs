@@ -4,6 +4,7 @@ use reth_codecs::Compact;
 
 /// Walker sub node for storing intermediate state root calculation state in the database.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", reth_codecs::add_arbitrary_tests(compact))]
 pub struct StoredSubNode {
     /// The key of the current node.
     pub key: Vec<u8>,
@@ -64,6 +65,39 @@ impl Compact for StoredSubNode {
     }
 }
 
+/// Generates an arbitrary [`BranchNodeCompact`], upholding the invariants between its masks and
+/// hashes that are asserted by [`BranchNodeCompact::new`].
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_branch_node(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<BranchNodeCompact> {
+    use crate::TrieMask;
+
+    let state_mask: u16 = u.arbitrary()?;
+    let tree_mask = u.arbitrary::<u16>()? & state_mask;
+    let hash_mask = u.arbitrary::<u16>()? & state_mask;
+    let hashes = (0..hash_mask.count_ones()).map(|_| u.arbitrary()).collect::<Result<_, _>>()?;
+
+    Ok(BranchNodeCompact::new(
+        TrieMask::new(state_mask),
+        TrieMask::new(tree_mask),
+        TrieMask::new(hash_mask),
+        hashes,
+        u.arbitrary()?,
+    ))
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StoredSubNode {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            key: u.arbitrary()?,
+            nibble: u.arbitrary()?,
+            node: if u.arbitrary()? { Some(arbitrary_branch_node(u)?) } else { None },
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
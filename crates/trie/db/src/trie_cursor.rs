@@ -142,7 +142,7 @@ where
         storage_updates.sort_unstable_by(|a, b| a.0.cmp(b.0));
 
         let mut num_entries = 0;
-        for (nibbles, maybe_updated) in storage_updates.into_iter().filter(|(n, _)| !n.is_empty()) {
+        for (nibbles, maybe_updated) in storage_updates {
             num_entries += 1;
             let nibbles = StoredNibblesSubKey(nibbles.clone());
             // Delete the old entry if it exists.
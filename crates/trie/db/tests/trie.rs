@@ -19,7 +19,7 @@ use reth_trie::{
     test_utils::{state_root, state_root_prehashed, storage_root, storage_root_prehashed},
     BranchNodeCompact, StateRoot, StorageRoot, TrieMask,
 };
-use reth_trie_common::triehash::KeccakHasher;
+use reth_trie_common::{triehash::KeccakHasher, StoredNibblesSubKey};
 use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
 use std::{
     collections::{BTreeMap, HashMap},
@@ -115,6 +115,59 @@ fn branch_node_child_changes() {
     );
 }
 
+#[test]
+fn storage_trie_root_node() {
+    let factory = create_test_provider_factory();
+    let tx = factory.provider_rw().unwrap();
+    let hashed_address = B256::with_last_byte(1);
+    let root_node = || {
+        tx.tx_ref()
+            .cursor_dup_read::<tables::StoragesTrie>()
+            .unwrap()
+            .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(Nibbles::default()))
+            .unwrap()
+            .filter(|entry| entry.nibbles.0.is_empty())
+    };
+
+    let storage = (0..=255u8)
+        .map(|i| (keccak256(B256::with_last_byte(i)), U256::from(1)))
+        .collect::<BTreeMap<_, _>>();
+    for (key, value) in &storage {
+        tx.tx_ref()
+            .put::<tables::HashedStorages>(
+                hashed_address,
+                StorageEntry { key: *key, value: *value },
+            )
+            .unwrap();
+    }
+    let (root, _, updates) =
+        StorageRoot::from_tx_hashed(tx.tx_ref(), hashed_address).root_with_updates().unwrap();
+    assert_eq!(root, storage_root_prehashed(storage.clone()));
+    tx.write_individual_storage_trie_updates(hashed_address, &updates).unwrap();
+    assert_eq!(root_node().unwrap().node.root_hash, Some(root));
+
+    // the stored root is kept if the storage is unchanged
+    let (_, _, updates) =
+        StorageRoot::from_tx_hashed(tx.tx_ref(), hashed_address).root_with_updates().unwrap();
+    assert!(updates.is_empty());
+
+    // the stored root is removed once the root of the trie isn't stored anymore
+    let mut changes = PrefixSetMut::default();
+    let mut cursor = tx.tx_ref().cursor_dup_write::<tables::HashedStorages>().unwrap();
+    for key in storage.keys().skip(1) {
+        cursor.seek_by_key_subkey(hashed_address, *key).unwrap();
+        cursor.delete_current().unwrap();
+        changes.insert(Nibbles::unpack(key));
+    }
+    let (root, _, updates) = StorageRoot::from_tx_hashed(tx.tx_ref(), hashed_address)
+        .with_prefix_set(changes.freeze())
+        .root_with_updates()
+        .unwrap();
+    assert_eq!(root, storage_root_prehashed(storage.into_iter().take(1)));
+    tx.write_individual_storage_trie_updates(hashed_address, &updates).unwrap();
+    assert_eq!(root_node(), None);
+}
+
 #[test]
 fn arbitrary_storage_root() {
     proptest!(ProptestConfig::with_cases(10), |(item in arb::<(Address, std::collections::BTreeMap<B256, U256>)>())| {
@@ -109,6 +109,9 @@ impl TrieUpdates {
 }
 
 /// Trie updates for storage trie of a single account.
+///
+/// Unlike the account trie, the root node of a storage trie is kept, so the storage root of an
+/// account can be read from the database without walking its trie.
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorageTrieUpdates {
@@ -126,7 +129,7 @@ pub struct StorageTrieUpdates {
 impl StorageTrieUpdates {
     /// Creates a new storage trie updates that are not marked as deleted.
     pub fn new(updates: impl IntoIterator<Item = (Nibbles, BranchNodeCompact)>) -> Self {
-        Self { storage_nodes: exclude_empty_from_pair(updates).collect(), ..Default::default() }
+        Self { storage_nodes: updates.into_iter().collect(), ..Default::default() }
     }
 }
 
@@ -173,8 +176,8 @@ impl StorageTrieUpdates {
     /// Extends storage trie updates.
     pub fn extend(&mut self, other: Self) {
         self.extend_common(&other);
-        self.storage_nodes.extend(exclude_empty_from_pair(other.storage_nodes));
-        self.removed_nodes.extend(exclude_empty(other.removed_nodes));
+        self.storage_nodes.extend(other.storage_nodes);
+        self.removed_nodes.extend(other.removed_nodes);
     }
 
     /// Extends storage trie updates.
@@ -182,10 +185,8 @@ impl StorageTrieUpdates {
     /// Slightly less efficient than [`Self::extend`], but preferred to `extend(other.clone())`.
     pub fn extend_ref(&mut self, other: &Self) {
         self.extend_common(other);
-        self.storage_nodes.extend(exclude_empty_from_pair(
-            other.storage_nodes.iter().map(|(k, v)| (k.clone(), v.clone())),
-        ));
-        self.removed_nodes.extend(exclude_empty(other.removed_nodes.iter().cloned()));
+        self.storage_nodes.extend(other.storage_nodes.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.removed_nodes.extend(other.removed_nodes.iter().cloned());
     }
 
     fn extend_common(&mut self, other: &Self) {
@@ -201,11 +202,11 @@ impl StorageTrieUpdates {
     pub fn finalize<C>(&mut self, walker: TrieWalker<C>, hash_builder: HashBuilder) {
         // Retrieve deleted keys from trie walker.
         let (_, removed_keys) = walker.split();
-        self.removed_nodes.extend(exclude_empty(removed_keys));
+        self.removed_nodes.extend(removed_keys);
 
         // Retrieve updated nodes from hash builder.
         let (_, updated_nodes) = hash_builder.split();
-        self.storage_nodes.extend(exclude_empty_from_pair(updated_nodes));
+        self.storage_nodes.extend(updated_nodes);
     }
 
     /// Convert storage trie updates into [`StorageTrieUpdatesSorted`].
@@ -51,7 +51,15 @@ impl<C> TrieWalker<C> {
     /// Sets the flag whether the trie updates should be stored.
     pub fn with_deletions_retained(mut self, retained: bool) -> Self {
         if retained {
-            self.removed_keys = Some(HashSet::default());
+            let mut removed_keys = HashSet::default();
+            // A stored root node that can't be skipped is computed again, same as in
+            // `consume_node`. It's only replaced if the new root is stored as well.
+            if let [root] = self.stack.as_slice() {
+                if root.node.is_some() && !self.can_skip_current_node {
+                    removed_keys.insert(root.key.clone());
+                }
+            }
+            self.removed_keys = Some(removed_keys);
         }
         self
     }
@@ -6,7 +6,8 @@
 //! cargo run -p example-custom-rlpx-subprotocol -- node
 //! ```
 //!
-//! This launch a regular reth node with a custom rlpx subprotocol.
+//! This launch a regular reth node with a custom rlpx subprotocol that is registered via the node
+//! builder, and a second network instance with the same subprotocol that connects to it.
 
 mod subprotocol;
 
@@ -15,7 +16,7 @@ use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use reth::builder::NodeHandle;
 use reth_network::{
     config::SecretKey, protocol::IntoRlpxSubProtocol, EthNetworkPrimitives, NetworkConfig,
-    NetworkManager, NetworkProtocols,
+    NetworkManager,
 };
 use reth_network_api::{test_utils::PeersHandleProvider, NetworkInfo};
 use reth_node_ethereum::EthereumNode;
@@ -31,16 +32,17 @@ use tracing::info;
 
 fn main() -> eyre::Result<()> {
     reth::cli::Cli::parse_args().run(|builder, _args| async move {
-        // launch the node
-        let NodeHandle { node, node_exit_future } =
-            builder.node(EthereumNode::default()).launch().await?;
-        let peer_id = node.network.peer_id();
-        let peer_addr = node.network.local_addr();
-
-        // add the custom network subprotocol to the launched node
+        // launch the node with the custom network subprotocol, which is offered to all peers of
+        // the node
         let (tx, mut from_peer0) = mpsc::unbounded_channel();
         let custom_rlpx_handler = CustomRlpxProtoHandler { state: ProtocolState { events: tx } };
-        node.network.add_rlpx_sub_protocol(custom_rlpx_handler.into_rlpx_sub_protocol());
+        let NodeHandle { node, node_exit_future } = builder
+            .node(EthereumNode::default())
+            .add_rlpx_sub_protocol(custom_rlpx_handler)
+            .launch()
+            .await?;
+        let peer_id = node.network.peer_id();
+        let peer_addr = node.network.local_addr();
 
         // creates a separate network instance and adds the custom network subprotocol
         let secret_key = SecretKey::new(&mut rand::thread_rng());
@@ -12,7 +12,7 @@ pub(crate) struct ProtocolState {
 }
 
 /// The protocol handler takes care of incoming and outgoing connections.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct CustomRlpxProtoHandler {
     pub state: ProtocolState,
 }